@@ -77,7 +77,7 @@ async function setAuthorities(chainSpec, chainConfig, opts) {
     let substrateId = (new types.GenericAccountId(registry, substrate_id));
 
     return {
-      substrate_id: [...substrateId.toU8a()],
+      substrate_id: substrateId.toHex(),
       eth_address
     };
   });
@@ -0,0 +1,421 @@
+use codec::{Decode, Encode};
+use our_std::{convert::TryInto, iter, vec::Vec, RuntimeDebug};
+
+/// A single 32-byte ABI word, big-endian.
+pub type Word = [u8; 32];
+
+/// The subset of Solidity types we need to (de)serialize.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum ParamType {
+    Uint,
+    Address,
+    Bytes,
+    String,
+    Array(Box<ParamType>),
+    Tuple(Vec<ParamType>),
+}
+
+/// A Solidity value, as encoded or decoded by this module.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum Token {
+    Uint(Word),
+    Address([u8; 20]),
+    Bytes(Vec<u8>),
+    String(String),
+    Array(Vec<Token>),
+    Tuple(Vec<Token>),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum AbiError {
+    InvalidAddress,
+    InvalidLength,
+    InvalidOffset,
+    InvalidUtf8,
+    UnexpectedEnd,
+}
+
+impl ParamType {
+    /// Whether the type is encoded out of line, behind an offset.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            ParamType::Uint | ParamType::Address => false,
+            ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+            ParamType::Tuple(types) => types.iter().any(ParamType::is_dynamic),
+        }
+    }
+
+    /// The number of bytes the type occupies in the head of an encoding.
+    fn head_len(&self) -> usize {
+        match self {
+            ParamType::Tuple(types) if !self.is_dynamic() => {
+                types.iter().map(ParamType::head_len).sum()
+            }
+            _ => 32,
+        }
+    }
+}
+
+impl Token {
+    /// Build a uint256 token from any unsigned integer up to 128 bits.
+    pub fn uint<T: Into<u128>>(value: T) -> Token {
+        let mut word = [0u8; 32];
+        word[16..].copy_from_slice(&value.into().to_be_bytes());
+        Token::Uint(word)
+    }
+
+    /// Whether the token is encoded out of line, behind an offset.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            Token::Uint(_) | Token::Address(_) => false,
+            Token::Bytes(_) | Token::String(_) | Token::Array(_) => true,
+            Token::Tuple(tokens) => tokens.iter().any(Token::is_dynamic),
+        }
+    }
+
+    pub fn into_uint(self) -> Option<Word> {
+        match self {
+            Token::Uint(word) => Some(word),
+            _ => None,
+        }
+    }
+
+    pub fn into_address(self) -> Option<[u8; 20]> {
+        match self {
+            Token::Address(address) => Some(address),
+            _ => None,
+        }
+    }
+
+    pub fn into_bytes(self) -> Option<Vec<u8>> {
+        match self {
+            Token::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub fn into_string(self) -> Option<String> {
+        match self {
+            Token::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn into_array(self) -> Option<Vec<Token>> {
+        match self {
+            Token::Array(tokens) => Some(tokens),
+            _ => None,
+        }
+    }
+}
+
+/// Convert a uint256 word to a u64, if it fits.
+pub fn word_to_u64(word: &Word) -> Option<u64> {
+    if word[..24].iter().any(|b| *b != 0) {
+        return None;
+    }
+    Some(u64::from_be_bytes(word[24..].try_into().ok()?))
+}
+
+/// Convert a uint256 word to a u128, if it fits.
+pub fn word_to_u128(word: &Word) -> Option<u128> {
+    if word[..16].iter().any(|b| *b != 0) {
+        return None;
+    }
+    Some(u128::from_be_bytes(word[16..].try_into().ok()?))
+}
+
+/// Convert an int256 word to an i64, if it fits.
+pub fn word_to_i64(word: &Word) -> Option<i64> {
+    let value = i64::from_be_bytes(word[24..].try_into().ok()?);
+    let sign = if value < 0 { 0xff } else { 0 };
+    if word[..24].iter().any(|b| *b != sign) {
+        return None;
+    }
+    Some(value)
+}
+
+fn usize_to_word(value: usize) -> Word {
+    Token::uint(value as u128).into_uint().unwrap_or_default()
+}
+
+fn pad_right(bytes: &[u8], out: &mut Vec<u8>) {
+    out.extend_from_slice(&usize_to_word(bytes.len()));
+    out.extend_from_slice(bytes);
+    let remainder = bytes.len() % 32;
+    if remainder != 0 {
+        out.extend(iter::repeat(0u8).take(32 - remainder));
+    }
+}
+
+fn encode_token(token: &Token, out: &mut Vec<u8>) {
+    match token {
+        Token::Uint(word) => out.extend_from_slice(word),
+        Token::Address(address) => {
+            out.extend_from_slice(&[0u8; 12]);
+            out.extend_from_slice(address);
+        }
+        Token::Bytes(bytes) => pad_right(bytes, out),
+        Token::String(string) => pad_right(string.as_bytes(), out),
+        Token::Array(tokens) => {
+            out.extend_from_slice(&usize_to_word(tokens.len()));
+            out.extend(encode(tokens));
+        }
+        Token::Tuple(tokens) => out.extend(encode(tokens)),
+    }
+}
+
+fn token_head_len(token: &Token) -> usize {
+    match token {
+        Token::Tuple(tokens) if !token.is_dynamic() => tokens.iter().map(token_head_len).sum(),
+        _ => 32,
+    }
+}
+
+/// Encode a sequence of tokens as the arguments of a Solidity function, i.e. as a tuple.
+pub fn encode(tokens: &[Token]) -> Vec<u8> {
+    let heads_len: usize = tokens.iter().map(token_head_len).sum();
+    let mut heads = Vec::with_capacity(heads_len);
+    let mut tails = Vec::new();
+    for token in tokens {
+        if token.is_dynamic() {
+            heads.extend_from_slice(&usize_to_word(heads_len + tails.len()));
+            encode_token(token, &mut tails);
+        } else {
+            encode_token(token, &mut heads);
+        }
+    }
+    heads.extend(tails);
+    heads
+}
+
+fn read_word(data: &[u8], at: usize) -> Result<Word, AbiError> {
+    let end = at.checked_add(32).ok_or(AbiError::UnexpectedEnd)?;
+    data.get(at..end)
+        .ok_or(AbiError::UnexpectedEnd)?
+        .try_into()
+        .map_err(|_| AbiError::UnexpectedEnd)
+}
+
+fn read_usize(data: &[u8], at: usize, error: AbiError) -> Result<usize, AbiError> {
+    word_to_u64(&read_word(data, at)?)
+        .and_then(|value| value.try_into().ok())
+        .ok_or(error)
+}
+
+fn read_bytes(data: &[u8]) -> Result<Vec<u8>, AbiError> {
+    let len = read_usize(data, 0, AbiError::InvalidLength)?;
+    let end = len.checked_add(32).ok_or(AbiError::InvalidLength)?;
+    Ok(data.get(32..end).ok_or(AbiError::UnexpectedEnd)?.to_vec())
+}
+
+fn decode_token(param: &ParamType, data: &[u8]) -> Result<Token, AbiError> {
+    match param {
+        ParamType::Uint => Ok(Token::Uint(read_word(data, 0)?)),
+        ParamType::Address => {
+            let word = read_word(data, 0)?;
+            if word[..12].iter().any(|b| *b != 0) {
+                return Err(AbiError::InvalidAddress);
+            }
+            Ok(Token::Address(
+                word[12..]
+                    .try_into()
+                    .map_err(|_| AbiError::InvalidAddress)?,
+            ))
+        }
+        ParamType::Bytes => Ok(Token::Bytes(read_bytes(data)?)),
+        ParamType::String => Ok(Token::String(
+            String::from_utf8(read_bytes(data)?).map_err(|_| AbiError::InvalidUtf8)?,
+        )),
+        ParamType::Array(inner) => {
+            let len = read_usize(data, 0, AbiError::InvalidLength)?;
+            // Check the length against the data before allocating, as the input is untrusted
+            let elements = &data[32..];
+            if len > elements.len() / inner.head_len().max(1) {
+                return Err(AbiError::InvalidLength);
+            }
+            Ok(Token::Array(decode_params(
+                iter::repeat(inner.as_ref()).take(len),
+                elements,
+            )?))
+        }
+        ParamType::Tuple(types) => Ok(Token::Tuple(decode_params(types.iter(), data)?)),
+    }
+}
+
+fn decode_params<'a>(
+    params: impl Iterator<Item = &'a ParamType>,
+    data: &[u8],
+) -> Result<Vec<Token>, AbiError> {
+    let mut tokens = Vec::new();
+    let mut at = 0;
+    for param in params {
+        if param.is_dynamic() {
+            let offset = read_usize(data, at, AbiError::InvalidOffset)?;
+            let tail = data.get(offset..).ok_or(AbiError::InvalidOffset)?;
+            tokens.push(decode_token(param, tail)?);
+        } else {
+            let head = data.get(at..).ok_or(AbiError::UnexpectedEnd)?;
+            tokens.push(decode_token(param, head)?);
+        }
+        at += param.head_len();
+    }
+    Ok(tokens)
+}
+
+/// Decode the arguments of a Solidity function, i.e. a tuple of the given types.
+/// All offsets and lengths are bounds checked, as the input is generally untrusted.
+pub fn decode(params: &[ParamType], data: &[u8]) -> Result<Vec<Token>, AbiError> {
+    decode_params(params.iter(), data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ethabi_token(token: &Token) -> ethabi::Token {
+        match token {
+            Token::Uint(word) => ethabi::Token::Uint(word.into()),
+            Token::Address(address) => ethabi::Token::Address(address.into()),
+            Token::Bytes(bytes) => ethabi::Token::Bytes(bytes.clone()),
+            Token::String(string) => ethabi::Token::String(string.clone()),
+            Token::Array(tokens) => ethabi::Token::Array(tokens.iter().map(ethabi_token).collect()),
+            Token::Tuple(_) => panic!("Tuples are not supported by this version of ethabi"),
+        }
+    }
+
+    #[test]
+    fn test_encode_static() {
+        let tokens = vec![Token::uint(1u8), Token::Address([0x11; 20])];
+        assert_eq!(
+            encode(&tokens),
+            hex::decode(
+                "0000000000000000000000000000000000000000000000000000000000000001\
+                 0000000000000000000000001111111111111111111111111111111111111111"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_encode_matches_ethabi() {
+        let tokens = vec![
+            Token::String(String::from("prices")),
+            Token::uint(1234u64),
+            Token::Array(vec![
+                Token::Bytes(vec![1, 2, 3]),
+                Token::Bytes(vec![0xff; 33]),
+            ]),
+            Token::Address([0x22; 20]),
+            Token::uint(u128::MAX),
+            Token::String(String::new()),
+            Token::Array(vec![]),
+        ];
+        let expected = ethabi::encode(&tokens.iter().map(ethabi_token).collect::<Vec<_>>());
+        assert_eq!(encode(&tokens), expected);
+    }
+
+    #[test]
+    fn test_encode_tuples() {
+        // A static tuple is encoded in place, a dynamic tuple behind an offset
+        let inner = vec![Token::Address([0x22; 20]), Token::uint(u128::MAX)];
+        assert_eq!(
+            encode(&[Token::uint(1u8), Token::Tuple(inner.clone())]),
+            encode(&[Token::uint(1u8), inner[0].clone(), inner[1].clone()])
+        );
+
+        let inner = vec![Token::uint(7u8), Token::String(String::from("gateway"))];
+        let mut expected = encode(&[Token::uint(1u8), Token::uint(64u8)]);
+        expected.extend(encode(&inner));
+        assert_eq!(
+            encode(&[Token::uint(1u8), Token::Tuple(inner.clone())]),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_decode_roundtrip() {
+        let params = vec![
+            ParamType::String,
+            ParamType::Uint,
+            ParamType::Array(Box::new(ParamType::Bytes)),
+            ParamType::Tuple(vec![ParamType::Address, ParamType::Uint]),
+            ParamType::Tuple(vec![ParamType::Uint, ParamType::String]),
+        ];
+        let tokens = vec![
+            Token::String(String::from("ETH")),
+            Token::uint(u64::MAX),
+            Token::Array(vec![Token::Bytes(vec![]), Token::Bytes(vec![9; 40])]),
+            Token::Tuple(vec![Token::Address([0x33; 20]), Token::uint(1u8)]),
+            Token::Tuple(vec![
+                Token::uint(2u8),
+                Token::String(String::from("gateway")),
+            ]),
+        ];
+        assert_eq!(decode(&params, &encode(&tokens)), Ok(tokens));
+    }
+
+    #[test]
+    fn test_decode_rejects_bad_input() {
+        let data = encode(&[Token::String(String::from("prices"))]);
+        assert_eq!(
+            decode(&[ParamType::String], &data[..63]),
+            Err(AbiError::UnexpectedEnd)
+        );
+
+        let mut bad_offset = data.clone();
+        bad_offset[31] = 0xff;
+        assert_eq!(
+            decode(&[ParamType::String], &bad_offset),
+            Err(AbiError::InvalidOffset)
+        );
+
+        let mut bad_length = data.clone();
+        bad_length[32] = 0xff;
+        assert_eq!(
+            decode(&[ParamType::String], &bad_length),
+            Err(AbiError::InvalidLength)
+        );
+
+        let huge_array = encode(&[Token::uint(32u8), Token::uint(u64::MAX)]);
+        assert_eq!(
+            decode(&[ParamType::Array(Box::new(ParamType::Uint))], &huge_array),
+            Err(AbiError::InvalidLength)
+        );
+
+        let bad_address = encode(&[Token::Uint([0xff; 32])]);
+        assert_eq!(
+            decode(&[ParamType::Address], &bad_address),
+            Err(AbiError::InvalidAddress)
+        );
+
+        let bad_utf8 = encode(&[Token::Bytes(vec![0xff, 0xfe])]);
+        assert_eq!(
+            decode(&[ParamType::String], &bad_utf8),
+            Err(AbiError::InvalidUtf8)
+        );
+    }
+
+    #[test]
+    fn test_word_conversions() {
+        assert_eq!(word_to_u64(&Token::uint(5u8).into_uint().unwrap()), Some(5));
+        assert_eq!(
+            word_to_u64(&Token::uint(u64::MAX as u128 + 1).into_uint().unwrap()),
+            None
+        );
+        assert_eq!(
+            word_to_u128(&Token::uint(u128::MAX).into_uint().unwrap()),
+            Some(u128::MAX)
+        );
+        assert_eq!(word_to_u128(&[0xff; 32]), None);
+        assert_eq!(word_to_i64(&[0xff; 32]), Some(-1));
+        assert_eq!(word_to_i64(&Token::uint(7u8).into_uint().unwrap()), Some(7));
+        assert_eq!(
+            word_to_i64(&Token::uint(i64::MAX as u128 + 1).into_uint().unwrap()),
+            None
+        );
+        let mut word = [0xff; 32];
+        word[24] = 0x7f;
+        assert_eq!(word_to_i64(&word), None);
+    }
+}
@@ -0,0 +1,191 @@
+use codec::{Decode, Encode};
+use our_std::RuntimeDebug;
+use sp_runtime::offchain::storage::StorageValueRef;
+
+/// How long to stop sending requests to an endpoint after it fails, doubled for each consecutive failure.
+const ENDPOINT_BACKOFF: u64 = 5_000;
+
+/// The longest an endpoint is avoided after failing.
+const ENDPOINT_MAX_BACKOFF: u64 = 300_000;
+
+/// The length of the window over which requests to an endpoint are rate limited.
+const ENDPOINT_RATE_WINDOW: u64 = 1_000;
+
+/// The max number of requests to send to an endpoint within a window.
+const ENDPOINT_RATE_LIMIT: u32 = 50;
+
+/// Type for tracking the health and load of an RPC endpoint across worker runs.
+#[derive(Clone, Default, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct EndpointStatus {
+    pub failures: u32,
+    pub retry_at: u64,
+    pub window_start: u64,
+    pub window_requests: u32,
+}
+
+impl EndpointStatus {
+    pub fn is_healthy(&self, now: u64) -> bool {
+        self.retry_at <= now
+    }
+
+    pub fn is_rate_limited(&self, now: u64) -> bool {
+        now < self.window_end() && self.window_requests >= ENDPOINT_RATE_LIMIT
+    }
+
+    pub fn window_end(&self) -> u64 {
+        self.window_start.saturating_add(ENDPOINT_RATE_WINDOW)
+    }
+
+    pub fn record_request(&mut self, now: u64) {
+        if now >= self.window_end() {
+            self.window_start = now;
+            self.window_requests = 0;
+        }
+        self.window_requests = self.window_requests.saturating_add(1);
+    }
+
+    pub fn record_success(&mut self) {
+        self.failures = 0;
+        self.retry_at = 0;
+    }
+
+    pub fn record_failure(&mut self, now: u64) {
+        let backoff = (ENDPOINT_BACKOFF << self.failures.min(16)).min(ENDPOINT_MAX_BACKOFF);
+        self.failures = self.failures.saturating_add(1);
+        self.retry_at = now.saturating_add(backoff);
+    }
+}
+
+/// Split a configured server into its endpoints, which are given as a comma-separated list.
+pub fn parse_endpoints(server: &str) -> Vec<&str> {
+    server
+        .split(',')
+        .map(str::trim)
+        .filter(|endpoint| !endpoint.is_empty())
+        .collect()
+}
+
+fn status_key(endpoint: &str) -> Vec<u8> {
+    format!("ethereum_client::endpoint::{}", endpoint).into_bytes()
+}
+
+/// Get the last known status of an endpoint.
+pub fn get_status(endpoint: &str) -> EndpointStatus {
+    let key = status_key(endpoint);
+    match StorageValueRef::persistent(&key).get::<EndpointStatus>() {
+        Some(Some(status)) => status,
+        _ => EndpointStatus::default(),
+    }
+}
+
+/// Remember the status of an endpoint for subsequent requests.
+pub fn set_status(endpoint: &str, status: &EndpointStatus) {
+    let key = status_key(endpoint);
+    StorageValueRef::persistent(&key).set(status);
+}
+
+/// Order the endpoints of a server by preference for the next request.
+/// Healthy endpoints come first, in the configured order, with those at their rate limit last among them.
+/// Unhealthy endpoints follow, those which recover soonest first, so failures never stall requests entirely.
+pub fn select_endpoints<'a>(server: &'a str, now: u64) -> Vec<(&'a str, EndpointStatus)> {
+    let mut endpoints: Vec<(&str, EndpointStatus)> = parse_endpoints(server)
+        .into_iter()
+        .map(|endpoint| (endpoint, get_status(endpoint)))
+        .collect();
+    endpoints.sort_by_key(|(_, status)| {
+        if status.is_healthy(now) {
+            (0, status.is_rate_limited(now), 0)
+        } else {
+            (1, false, status.retry_at)
+        }
+    });
+    endpoints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sp_core::offchain::{testing, OffchainDbExt};
+
+    #[test]
+    fn test_parse_endpoints() {
+        assert_eq!(parse_endpoints("https://a"), vec!["https://a"]);
+        assert_eq!(
+            parse_endpoints("https://a, https://b,,"),
+            vec!["https://a", "https://b"]
+        );
+        assert_eq!(parse_endpoints(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_endpoint_status() {
+        let mut status = EndpointStatus::default();
+        assert!(status.is_healthy(0));
+
+        status.record_failure(1_000);
+        assert!(!status.is_healthy(5_999));
+        assert!(status.is_healthy(6_000));
+
+        status.record_failure(6_000);
+        assert_eq!(status.retry_at, 16_000);
+
+        for _ in 0..10 {
+            status.record_failure(16_000);
+        }
+        assert_eq!(status.retry_at, 16_000 + ENDPOINT_MAX_BACKOFF);
+
+        status.record_success();
+        assert!(status.is_healthy(16_000));
+
+        for _ in 0..ENDPOINT_RATE_LIMIT {
+            assert!(!status.is_rate_limited(20_000));
+            status.record_request(20_000);
+        }
+        assert!(status.is_rate_limited(20_999));
+        assert!(!status.is_rate_limited(21_000));
+        status.record_request(21_000);
+        assert_eq!(status.window_requests, 1);
+    }
+
+    #[test]
+    fn test_select_endpoints() {
+        let (offchain, _state) = testing::TestOffchainExt::new();
+        let mut t = sp_io::TestExternalities::default();
+        t.register_extension(OffchainDbExt::new(offchain));
+        t.execute_with(|| {
+            let server = "https://a,https://b,https://c,https://d";
+            let order = |now| -> Vec<&str> {
+                select_endpoints(server, now)
+                    .into_iter()
+                    .map(|(endpoint, _)| endpoint)
+                    .collect()
+            };
+            assert_eq!(
+                order(0),
+                vec!["https://a", "https://b", "https://c", "https://d"]
+            );
+
+            let mut failed = EndpointStatus::default();
+            failed.record_failure(0);
+            set_status("https://a", &failed);
+            failed.record_failure(0);
+            set_status("https://b", &failed);
+
+            let mut limited = EndpointStatus::default();
+            for _ in 0..ENDPOINT_RATE_LIMIT {
+                limited.record_request(0);
+            }
+            set_status("https://c", &limited);
+
+            assert_eq!(
+                order(0),
+                vec!["https://d", "https://c", "https://a", "https://b"]
+            );
+            assert_eq!(
+                order(5_000),
+                vec!["https://a", "https://c", "https://d", "https://b"]
+            );
+        });
+    }
+}
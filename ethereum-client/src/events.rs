@@ -1,3 +1,4 @@
+use crate::abi::{self, ParamType, Token};
 use crate::hex::{decode_hex, decode_topic};
 use codec::{Decode, Encode};
 use our_std::convert::TryInto;
@@ -255,30 +256,26 @@ fn parse_exec_trx_request_log(log: ethabi::Log) -> Result<EthereumEvent, EventEr
     }
 }
 
-fn parse_execute_proposal_log(log: ethabi::Log) -> Result<EthereumEvent, EventError> {
-    match &log.params[..] {
-        [ethabi::LogParam {
-            value: ethabi::token::Token::String(title),
-            ..
-        }, ethabi::LogParam {
-            value: ethabi::token::Token::Array(extrinsics_tokens),
-            ..
-        }] => {
-            let extrinsics = extrinsics_tokens
-                .clone()
-                .into_iter()
-                .map(|extrinsic| match extrinsic {
-                    ethabi::token::Token::Bytes(extrinsic) => Ok(extrinsic),
-                    _ => Err(EventError::InvalidLogParams),
-                })
-                .collect::<Result<Vec<Vec<u8>>, _>>()?;
-            Ok(EthereumEvent::ExecuteProposal {
-                title: title.into(),
-                extrinsics,
-            })
-        }
-        _ => Err(EventError::InvalidLogParams),
-    }
+fn parse_execute_proposal_log(data: &[u8]) -> Result<EthereumEvent, EventError> {
+    let types = [
+        ParamType::String,
+        ParamType::Array(Box::new(ParamType::Bytes)),
+    ];
+    let mut tokens = abi::decode(&types, data)
+        .map_err(|_| EventError::ErrorParsingLog)?
+        .into_iter();
+    let title = tokens
+        .next()
+        .and_then(Token::into_string)
+        .ok_or(EventError::InvalidLogParams)?;
+    let extrinsics = tokens
+        .next()
+        .and_then(Token::into_array)
+        .ok_or(EventError::InvalidLogParams)?
+        .into_iter()
+        .map(|extrinsic| extrinsic.into_bytes().ok_or(EventError::InvalidLogParams))
+        .collect::<Result<Vec<Vec<u8>>, _>>()?;
+    Ok(EthereumEvent::ExecuteProposal { title, extrinsics })
 }
 
 fn parse_notice_invoked_log(log: ethabi::Log) -> Result<EthereumEvent, EventError> {
@@ -345,13 +342,11 @@ pub fn decode_event(topics: Vec<String>, data: String) -> Result<EthereumEvent,
         }
 
         t if *t == *EXECUTE_PROPOSAL_EVENT_TOPIC => {
-            let log: ethabi::Log = EXECUTE_PROPOSAL_EVENT
-                .parse_log(ethabi::RawLog {
-                    topics: topic_hashes,
-                    data: decode_hex(&data).ok_or(EventError::InvalidHex)?,
-                })
-                .map_err(|_| EventError::ErrorParsingLog)?;
-            parse_execute_proposal_log(log)
+            // No params are indexed, so only the event signature is in the topics
+            if topic_hashes.len() != 1 {
+                return Err(EventError::ErrorParsingLog);
+            }
+            parse_execute_proposal_log(&decode_hex(&data).ok_or(EventError::InvalidHex)?)
         }
 
         t if *t == *NOTICE_INVOKED_EVENT_TOPIC => {
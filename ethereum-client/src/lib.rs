@@ -3,14 +3,21 @@ extern crate lazy_static;
 
 use codec::{Decode, Encode};
 use hex_buffer_serde::{ConstHex, ConstHexForm};
-use sp_runtime::offchain::{http, Duration};
+use sp_runtime::offchain::{http, Duration, Timestamp};
 use sp_runtime_interface::pass_by::PassByCodec;
 
-use our_std::{debug, error, info, trace, warn, Deserialize, RuntimeDebug, Serialize};
+use our_std::{
+    convert::TryFrom, debug, error, info, trace, warn, Deserialize, RuntimeDebug, Serialize,
+};
 use types_derive::{type_alias, Types};
 
+pub mod abi;
+pub mod endpoints;
 pub mod events;
 pub mod hex;
+pub mod proof;
+pub mod rlp;
+pub mod trie;
 
 pub use crate::events::EthereumEvent;
 pub use crate::hex::{parse_u64, parse_word};
@@ -50,6 +57,7 @@ pub enum EthereumClientError {
     InvalidUTF8,
     JsonParseError,
     NoResult,
+    InvalidProof,
 }
 
 #[derive(Deserialize, Serialize, RuntimeDebug, PartialEq)]
@@ -58,7 +66,7 @@ pub struct ResponseError {
     pub code: Option<i64>,
 }
 
-#[derive(Clone, Deserialize, RuntimeDebug, PartialEq)]
+#[derive(Clone, Default, Deserialize, RuntimeDebug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct LogObject {
     /// true when the log was removed, due to a chain reorganization. false if it's a valid log.
@@ -89,7 +97,7 @@ pub struct GetLogsResponse {
 }
 
 #[allow(non_snake_case)]
-#[derive(Clone, Deserialize, Serialize, RuntimeDebug, PartialEq)]
+#[derive(Clone, Default, Deserialize, Serialize, RuntimeDebug, PartialEq)]
 pub struct BlockObject {
     pub difficulty: Option<String>,
     pub extraData: Option<String>,
@@ -102,7 +110,7 @@ pub struct BlockObject {
     pub nonce: Option<String>,
     pub number: Option<String>,
     pub parentHash: Option<String>,
-    pub receiptRoot: Option<String>,
+    pub receiptsRoot: Option<String>,
     pub sha3Uncles: Option<String>,
     pub size: Option<String>,
     pub stateRoot: Option<String>,
@@ -111,6 +119,12 @@ pub struct BlockObject {
     pub transactions: Option<Vec<String>>,
     pub transactionsRoot: Option<String>,
     pub uncles: Option<Vec<String>>,
+    pub baseFeePerGas: Option<String>,
+    pub withdrawalsRoot: Option<String>,
+    pub blobGasUsed: Option<String>,
+    pub excessBlobGas: Option<String>,
+    pub parentBeaconBlockRoot: Option<String>,
+    pub requestsHash: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, RuntimeDebug, PartialEq)]
@@ -120,6 +134,25 @@ pub struct BlockResponse {
     pub error: Option<ResponseError>,
 }
 
+#[allow(non_snake_case)]
+#[derive(Clone, Default, Deserialize, RuntimeDebug, PartialEq)]
+pub struct ReceiptObject {
+    pub r#type: Option<String>,
+    pub status: Option<String>,
+    pub root: Option<String>,
+    pub cumulativeGasUsed: Option<String>,
+    pub logsBloom: Option<String>,
+    pub logs: Option<Vec<LogObject>>,
+    pub transactionIndex: Option<String>,
+}
+
+#[derive(Deserialize, RuntimeDebug, PartialEq)]
+pub struct BlockReceiptsResponse {
+    pub id: Option<u64>,
+    pub result: Option<Vec<ReceiptObject>>,
+    pub error: Option<ResponseError>,
+}
+
 #[derive(Deserialize, RuntimeDebug, PartialEq)]
 pub struct BlockNumberResponse {
     pub id: Option<u64>,
@@ -127,6 +160,13 @@ pub struct BlockNumberResponse {
     pub error: Option<ResponseError>,
 }
 
+#[derive(Deserialize, RuntimeDebug, PartialEq)]
+pub struct CallResponse {
+    pub id: Option<u64>,
+    pub result: Option<String>,
+    pub error: Option<ResponseError>,
+}
+
 fn parse_error(data: &str) -> EthereumClientError {
     error!("Error Parsing: {}", data);
     EthereumClientError::JsonParseError
@@ -142,6 +182,13 @@ fn deserialize_get_block_response(response: &str) -> Result<BlockResponse, Ether
     Ok(result.map_err(|_| parse_error(response))?)
 }
 
+fn deserialize_block_receipts_response(
+    response: &str,
+) -> Result<BlockReceiptsResponse, EthereumClientError> {
+    let result: serde_json::error::Result<BlockReceiptsResponse> = serde_json::from_str(response);
+    Ok(result.map_err(|_| parse_error(response))?)
+}
+
 fn deserialize_block_number_response(
     response: &str,
 ) -> Result<BlockNumberResponse, EthereumClientError> {
@@ -149,6 +196,11 @@ fn deserialize_block_number_response(
     Ok(result.map_err(|_| parse_error(response))?)
 }
 
+fn deserialize_call_response(response: &str) -> Result<CallResponse, EthereumClientError> {
+    let result: serde_json::error::Result<CallResponse> = serde_json::from_str(response);
+    Ok(result.map_err(|_| parse_error(response))?)
+}
+
 pub fn encode_block_hash_hex(block_hash: EthereumHash) -> String {
     format!("0x{}", ::hex::encode(&block_hash))
 }
@@ -157,12 +209,26 @@ pub fn encode_block_number_hex(block_number: EthereumBlockNumber) -> String {
     format!("{:#X}", block_number)
 }
 
+impl EthereumClientError {
+    /// Whether the error indicates a problem with the endpoint, rather than with the request.
+    pub fn is_endpoint_failure(&self) -> bool {
+        match self {
+            EthereumClientError::HttpIoError
+            | EthereumClientError::HttpTimeout
+            | EthereumClientError::HttpErrorCode(_)
+            | EthereumClientError::InvalidUTF8 => true,
+            _ => false,
+        }
+    }
+}
+
+/// Send an RPC request to a server, which may be a comma-separated list of endpoints.
+/// Endpoints are tried in order of preference, failing over to the next whenever one is unavailable.
 pub fn send_rpc(
     server: &str,
     method: serde_json::Value,
     params: Vec<serde_json::Value>,
 ) -> Result<String, EthereumClientError> {
-    let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(ETH_FETCH_DEADLINE));
     let data = serde_json::json!({
         "jsonrpc": "2.0",
         "method": method,
@@ -172,7 +238,38 @@ pub fn send_rpc(
     .to_string();
     trace!("RPC: {}", &data);
 
-    let request = http::Request::post(server, vec![data]);
+    let mut last_error = EthereumClientError::HttpIoError;
+    for (endpoint, mut status) in endpoints::select_endpoints(server, now_millis()) {
+        if status.is_rate_limited(now_millis()) {
+            sp_io::offchain::sleep_until(Timestamp::from_unix_millis(status.window_end()));
+        }
+        status.record_request(now_millis());
+        let result = send_rpc_to(endpoint, &data);
+        match result {
+            Err(err) if err.is_endpoint_failure() => {
+                warn!("RPC endpoint {} failed: {:?}", endpoint, err);
+                status.record_failure(now_millis());
+                endpoints::set_status(endpoint, &status);
+                last_error = err;
+            }
+            _ => {
+                status.record_success();
+                endpoints::set_status(endpoint, &status);
+                return result;
+            }
+        }
+    }
+    Err(last_error)
+}
+
+fn now_millis() -> u64 {
+    sp_io::offchain::timestamp().unix_millis()
+}
+
+/// Send an RPC request to a single endpoint.
+fn send_rpc_to(endpoint: &str, data: &str) -> Result<String, EthereumClientError> {
+    let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(ETH_FETCH_DEADLINE));
+    let request = http::Request::post(endpoint, vec![data]);
 
     let pending = request
         .deadline(deadline)
@@ -218,7 +315,59 @@ pub fn get_block(
     let event_objects = get_logs_response
         .result
         .ok_or_else(|| parse_error(&get_logs_response_str[..]))?;
+    let events = decode_events(&block_id, event_objects)?;
+    to_block(block_obj, events)
+}
+
+/// Get a block whose events are proven by the receipts of the block, rather than trusting the logs returned by the server.
+/// The header must hash to the block hash, and the receipts must form the receipts root of the header.
+/// Since the hash of each block is attested by a quorum of validators, a server cannot fabricate events for it.
+pub fn get_proven_block(
+    server: &str,
+    eth_starport_address: &[u8; 20],
+    block_id: EthereumBlockId,
+) -> Result<EthereumBlock, EthereumClientError> {
+    let block_obj = get_block_object(server, block_id.clone())?;
+    let hash = parse_word(block_obj.hash.clone()).ok_or_else(|| parse_error("bad hash"))?;
+    let header = proof::encode_header(&block_obj).ok_or_else(|| parse_error("bad header"))?;
+    if sp_io::hashing::keccak_256(&header) != hash {
+        warn!("Header does not match hash for Eth block {:?}", block_id);
+        return Err(EthereumClientError::InvalidProof);
+    }
+
+    let mut receipts = get_block_receipts(server, hash)?;
+    receipts.sort_by_key(|receipt| parse_u64(receipt.transactionIndex.clone()));
+    let encoded_receipts = receipts
+        .iter()
+        .map(proof::encode_receipt)
+        .collect::<Option<Vec<Vec<u8>>>>()
+        .ok_or_else(|| parse_error("bad receipt"))?;
+    let receipts_root = parse_word(block_obj.receiptsRoot.clone())
+        .ok_or_else(|| parse_error("bad receipts root"))?;
+    if trie::ordered_trie_root(encoded_receipts) != receipts_root {
+        warn!(
+            "Receipts do not match receipts root for Eth block {:?}",
+            block_id
+        );
+        return Err(EthereumClientError::InvalidProof);
+    }
 
+    let starport = format!("0x{}", ::hex::encode(&eth_starport_address[..]));
+    let event_objects = receipts
+        .into_iter()
+        .flat_map(|receipt| receipt.logs.unwrap_or_default())
+        .filter(|log| {
+            log.address.as_ref().map(|address| address.to_lowercase()) == Some(starport.clone())
+        })
+        .collect();
+    let events = decode_events(&block_id, event_objects)?;
+    to_block(block_obj, events)
+}
+
+fn decode_events(
+    block_id: &EthereumBlockId,
+    event_objects: Vec<LogObject>,
+) -> Result<Vec<EthereumEvent>, EthereumClientError> {
     if event_objects.len() > 0 {
         info!(
             "Found {} events for Eth block {:?}",
@@ -231,12 +380,8 @@ pub fn get_block(
 
     let mut events = Vec::with_capacity(event_objects.len());
     for ev_obj in event_objects {
-        let topics = ev_obj
-            .topics
-            .ok_or_else(|| parse_error(&get_logs_response_str[..]))?;
-        let data = ev_obj
-            .data
-            .ok_or_else(|| parse_error(&get_logs_response_str[..]))?;
+        let topics = ev_obj.topics.ok_or_else(|| parse_error("missing topics"))?;
+        let data = ev_obj.data.ok_or_else(|| parse_error("missing data"))?;
         match events::decode_event(topics, data) {
             Ok(event) => events.push(event),
             Err(events::EventError::UnknownEventTopic(topic)) => {
@@ -248,7 +393,13 @@ pub fn get_block(
             }
         }
     }
+    Ok(events)
+}
 
+fn to_block(
+    block_obj: BlockObject,
+    events: Vec<EthereumEvent>,
+) -> Result<EthereumBlock, EthereumClientError> {
     // note these error messages are imperfect as they don't show the broken data
     //  but also should never happen and not worth fixing for now
     Ok(EthereumBlock {
@@ -260,6 +411,19 @@ pub fn get_block(
     })
 }
 
+/// Get the receipts of all the transactions in a block.
+pub fn get_block_receipts(
+    server: &str,
+    block_hash: EthereumHash,
+) -> Result<Vec<ReceiptObject>, EthereumClientError> {
+    let params = vec![encode_block_hash_hex(block_hash).into()];
+    let response_str: String = send_rpc(server, "eth_getBlockReceipts".into(), params)?;
+    let response = deserialize_block_receipts_response(&response_str)?;
+    response
+        .result
+        .ok_or_else(|| parse_error(&response_str[..]))
+}
+
 pub fn get_block_object(
     server: &str,
     block_id: EthereumBlockId,
@@ -287,6 +451,71 @@ pub fn get_latest_block_number(server: &str) -> Result<u64, EthereumClientError>
         .ok_or(EthereumClientError::JsonParseError)
 }
 
+/// Get the current gas price (in wei) suggested by the node.
+pub fn get_gas_price(server: &str) -> Result<u64, EthereumClientError> {
+    let response_str: String = send_rpc(server, "eth_gasPrice".into(), vec![])?;
+    let response = deserialize_call_response(&response_str)?;
+    debug!("eth_gasPrice response: {:?}", response.result.clone());
+    parse_u64(Some(response.result.ok_or(EthereumClientError::NoResult)?))
+        .ok_or(EthereumClientError::JsonParseError)
+}
+
+/// The selector of `observe(uint32[])` on a Uniswap V3 pool.
+const UNISWAP_V3_OBSERVE_SELECTOR: [u8; 4] = [0x88, 0x3b, 0xdb, 0xfd];
+
+/// Get the time-weighted average tick of a Uniswap V3 pool over the last `period` seconds.
+/// Rounds towards negative infinity, like the Uniswap oracle library.
+pub fn get_twap_tick(
+    server: &str,
+    pool: &[u8; 20],
+    period: u32,
+) -> Result<i32, EthereumClientError> {
+    let mut data = UNISWAP_V3_OBSERVE_SELECTOR.to_vec();
+    data.extend(abi::encode(&[abi::Token::Array(vec![
+        abi::Token::uint(period),
+        abi::Token::uint(0u32),
+    ])]));
+    let params = vec![
+        serde_json::json!({
+            "to": format!("0x{}", ::hex::encode(&pool[..])),
+            "data": format!("0x{}", ::hex::encode(&data)),
+        }),
+        "latest".into(),
+    ];
+    let response_str: String = send_rpc(server, "eth_call".into(), params)?;
+    let response = deserialize_call_response(&response_str)?;
+    let result = response.result.ok_or(EthereumClientError::NoResult)?;
+    let bytes = hex::decode_hex(&result).ok_or(EthereumClientError::DecodeError)?;
+
+    let uints = abi::ParamType::Array(Box::new(abi::ParamType::Uint));
+    let tick_cumulatives = abi::decode(&[uints.clone(), uints], &bytes)
+        .ok()
+        .and_then(|tokens| tokens.into_iter().next())
+        .and_then(abi::Token::into_array)
+        .ok_or(EthereumClientError::DecodeError)?
+        .into_iter()
+        .map(|token| token.into_uint().as_ref().and_then(abi::word_to_i64))
+        .collect::<Option<Vec<i64>>>()
+        .ok_or(EthereumClientError::DecodeError)?;
+
+    match tick_cumulatives[..] {
+        [then, now] => {
+            let delta = now
+                .checked_sub(then)
+                .ok_or(EthereumClientError::DecodeError)?;
+            let period = period as i64;
+            let mut tick = delta
+                .checked_div(period)
+                .ok_or(EthereumClientError::DecodeError)?;
+            if delta < 0 && delta % period != 0 {
+                tick -= 1;
+            }
+            i32::try_from(tick).map_err(|_| EthereumClientError::DecodeError)
+        }
+        _ => Err(EthereumClientError::DecodeError),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
@@ -394,6 +623,157 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_get_proven_block() {
+        const GENESIS: &str = r#"{"difficulty":"0x400000000","extraData":"0x11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa","gasLimit":"0x1388","gasUsed":"0x0","hash":"0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","miner":"0x0000000000000000000000000000000000000000","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","nonce":"0x0000000000000042","number":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","receiptsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","sha3Uncles":"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347","size":"0x21c","stateRoot":"0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544","timestamp":"0x0","totalDifficulty":"0x400000000","transactions":[],"transactionsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","uncles":[]}"#;
+        const GET_BLOCK: &[u8] =
+            br#"{"jsonrpc":"2.0","method":"eth_getBlockByNumber","params":["0x0",false],"id":1}"#;
+        let get_block = |block: &str| testing::PendingRequest {
+            method: "POST".into(),
+            uri: "https://mainnet-eth.compound.finance".into(),
+            headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+            body: GET_BLOCK.to_vec(),
+            response: Some(
+                format!(r#"{{"jsonrpc":"2.0","id":1,"result":{}}}"#, block).into_bytes(),
+            ),
+            sent: true,
+            ..Default::default()
+        };
+        let get_receipts = testing::PendingRequest {
+            method: "POST".into(),
+            uri: "https://mainnet-eth.compound.finance".into(),
+            headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+            body: br#"{"jsonrpc":"2.0","method":"eth_getBlockReceipts","params":["0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"],"id":1}"#.to_vec(),
+            response: Some(br#"{"jsonrpc":"2.0","id":1,"result":[]}"#.to_vec()),
+            sent: true,
+            ..Default::default()
+        };
+
+        let (offchain, state) = testing::TestOffchainExt::new();
+        let mut t = sp_io::TestExternalities::default();
+        t.register_extension(OffchainDbExt::new(offchain.clone()));
+        t.register_extension(OffchainWorkerExt::new(offchain));
+        {
+            let mut s = state.write();
+            s.expect_request(get_block(GENESIS));
+            s.expect_request(get_receipts);
+            s.expect_request(get_block(&GENESIS.replace("0x1388", "0x1389")));
+        }
+        t.execute_with(|| {
+            let block = get_proven_block(
+                "https://mainnet-eth.compound.finance",
+                &[0; 20],
+                EthereumBlockId::Number(0),
+            )
+            .unwrap();
+            assert_eq!(block.number, 0);
+            assert_eq!(block.parent_hash, [0; 32]);
+            assert_eq!(block.events, vec![]);
+
+            // a header which does not match the block hash is rejected
+            assert_eq!(
+                get_proven_block(
+                    "https://mainnet-eth.compound.finance",
+                    &[0; 20],
+                    EthereumBlockId::Number(0),
+                ),
+                Err(EthereumClientError::InvalidProof)
+            );
+        });
+    }
+
+    #[test]
+    fn test_send_rpc_failover() {
+        let (offchain, state) = testing::TestOffchainExt::new();
+        let mut t = sp_io::TestExternalities::default();
+        t.register_extension(OffchainDbExt::new(offchain.clone()));
+        t.register_extension(OffchainWorkerExt::new(offchain));
+        {
+            let mut s = state.write();
+            s.expect_request(testing::PendingRequest {
+                method: "POST".into(),
+                uri: "https://backup-eth.compound.finance".into(),
+                headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+                body: br#"{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":1}"#
+                    .to_vec(),
+                response: Some(br#"{"jsonrpc":"2.0","id":1,"result":"0x123"}"#.to_vec()),
+                sent: true,
+                ..Default::default()
+            });
+        }
+        t.execute_with(|| {
+            let mut failed = endpoints::EndpointStatus::default();
+            failed.record_failure(0);
+            endpoints::set_status("https://mainnet-eth.compound.finance", &failed);
+
+            let result = get_latest_block_number(
+                "https://mainnet-eth.compound.finance,https://backup-eth.compound.finance",
+            );
+            assert_eq!(result, Ok(291));
+            assert_eq!(
+                endpoints::get_status("https://backup-eth.compound.finance").window_requests,
+                1
+            );
+        });
+    }
+
+    #[test]
+    fn test_get_gas_price() {
+        let (offchain, state) = testing::TestOffchainExt::new();
+        let mut t = sp_io::TestExternalities::default();
+        t.register_extension(OffchainDbExt::new(offchain.clone()));
+        t.register_extension(OffchainWorkerExt::new(offchain));
+        {
+            let mut s = state.write();
+            s.expect_request(testing::PendingRequest {
+                method: "POST".into(),
+                uri: "https://mainnet-eth.compound.finance".into(),
+                headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+                body: br#"{"jsonrpc":"2.0","method":"eth_gasPrice","params":[],"id":1}"#.to_vec(),
+                response: Some(br#"{"jsonrpc":"2.0","id":1,"result":"0x4a817c800"}"#.to_vec()),
+                sent: true,
+                ..Default::default()
+            });
+        }
+        t.execute_with(|| {
+            let result = get_gas_price("https://mainnet-eth.compound.finance");
+            assert_eq!(result, Ok(20_000_000_000));
+        });
+    }
+
+    #[test]
+    fn test_get_twap_tick() {
+        let (offchain, state) = testing::TestOffchainExt::new();
+        let mut t = sp_io::TestExternalities::default();
+        t.register_extension(OffchainDbExt::new(offchain.clone()));
+        t.register_extension(OffchainWorkerExt::new(offchain));
+        {
+            let mut s = state.write();
+            s.expect_request(testing::PendingRequest {
+                method: "POST".into(),
+                uri: "https://mainnet-eth.compound.finance".into(),
+                headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+                body: br#"{"jsonrpc":"2.0","method":"eth_call","params":[{"to":"0x8ad599c3a0ff1de082011efddc58f1908eb6e6d8","data":"0x883bdbfd0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000007080000000000000000000000000000000000000000000000000000000000000000"},"latest"],"id":1}"#
+                    .to_vec(),
+                response: Some(br#"{"jsonrpc":"2.0","id":1,"result":"0x000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000005f5e100fffffffffffffffffffffffffffffffffffffffffffffffffffffffff05ed26b000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"}"#.to_vec()),
+                sent: true,
+                ..Default::default()
+            });
+        }
+        t.execute_with(|| {
+            // tick cumulatives of 100000000 and -262221205 over 1800s average to -201234.0028
+            let result = get_twap_tick(
+                "https://mainnet-eth.compound.finance",
+                &[
+                    0x8a, 0xd5, 0x99, 0xc3, 0xa0, 0xff, 0x1d, 0xe0, 0x82, 0x01, 0x1e, 0xfd, 0xdc,
+                    0x58, 0xf1, 0x90, 0x8e, 0xb6, 0xe6, 0xd8,
+                ],
+                1800,
+            );
+            assert_eq!(result, Ok(-201235));
+        });
+    }
+
     #[test]
     fn test_get_block_object() {
         let (offchain, state) = testing::TestOffchainExt::new();
@@ -0,0 +1,201 @@
+use crate::{hex::decode_hex, rlp, BlockObject, LogObject, ReceiptObject};
+
+/// Decode a hex quantity, which unlike other data may have an odd number of digits.
+fn decode_quantity(val: &str) -> Option<Vec<u8>> {
+    let digits = val.strip_prefix("0x")?;
+    if digits.len() % 2 == 1 {
+        ::hex::decode(format!("0{}", digits)).ok()
+    } else {
+        ::hex::decode(digits).ok()
+    }
+}
+
+fn bytes_field(field: &Option<String>) -> Option<Vec<u8>> {
+    Some(rlp::encode_bytes(&decode_hex(field.as_ref()?)?))
+}
+
+fn uint_field(field: &Option<String>) -> Option<Vec<u8>> {
+    Some(rlp::encode_uint(&decode_quantity(field.as_ref()?)?))
+}
+
+/// Encode a block header the way it is hashed, so it can be checked against the block hash.
+pub fn encode_header(block: &BlockObject) -> Option<Vec<u8>> {
+    let mut fields = vec![
+        bytes_field(&block.parentHash)?,
+        bytes_field(&block.sha3Uncles)?,
+        bytes_field(&block.miner)?,
+        bytes_field(&block.stateRoot)?,
+        bytes_field(&block.transactionsRoot)?,
+        bytes_field(&block.receiptsRoot)?,
+        bytes_field(&block.logsBloom)?,
+        uint_field(&block.difficulty)?,
+        uint_field(&block.number)?,
+        uint_field(&block.gasLimit)?,
+        uint_field(&block.gasUsed)?,
+        uint_field(&block.timestamp)?,
+        bytes_field(&block.extraData)?,
+        bytes_field(&block.mixHash)?,
+        bytes_field(&block.nonce)?,
+    ];
+
+    // fields added by later forks are only present in blocks since then, always in this order
+    let fork_fields = [
+        uint_field(&block.baseFeePerGas),
+        bytes_field(&block.withdrawalsRoot),
+        uint_field(&block.blobGasUsed),
+        uint_field(&block.excessBlobGas),
+        bytes_field(&block.parentBeaconBlockRoot),
+        bytes_field(&block.requestsHash),
+    ];
+    fields.extend(
+        fork_fields
+            .iter()
+            .take_while(|f| f.is_some())
+            .flatten()
+            .cloned(),
+    );
+
+    Some(rlp::encode_list(&fields))
+}
+
+fn encode_log(log: &LogObject) -> Option<Vec<u8>> {
+    let topics = log
+        .topics
+        .as_ref()?
+        .iter()
+        .map(|topic| Some(rlp::encode_bytes(&decode_hex(topic)?)))
+        .collect::<Option<Vec<Vec<u8>>>>()?;
+    Some(rlp::encode_list(&[
+        bytes_field(&log.address)?,
+        rlp::encode_list(&topics),
+        bytes_field(&log.data)?,
+    ]))
+}
+
+/// Encode a transaction receipt the way it is stored in the receipts trie.
+pub fn encode_receipt(receipt: &ReceiptObject) -> Option<Vec<u8>> {
+    let outcome = match receipt.status {
+        Some(_) => uint_field(&receipt.status)?,
+        None => bytes_field(&receipt.root)?,
+    };
+    let logs = receipt
+        .logs
+        .as_ref()?
+        .iter()
+        .map(encode_log)
+        .collect::<Option<Vec<Vec<u8>>>>()?;
+    let payload = rlp::encode_list(&[
+        outcome,
+        uint_field(&receipt.cumulativeGasUsed)?,
+        bytes_field(&receipt.logsBloom)?,
+        rlp::encode_list(&logs),
+    ]);
+
+    // typed receipts are prefixed by their transaction type
+    let tx_type = match &receipt.r#type {
+        Some(tx_type) => decode_quantity(tx_type)?,
+        None => vec![],
+    };
+    match &tx_type[..] {
+        [] | [0] => Some(payload),
+        _ => Some([tx_type, payload].concat()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trie::ordered_trie_root;
+    use sp_io::hashing::keccak_256;
+
+    fn hex_string(bytes: &[u8]) -> Option<String> {
+        Some(format!("0x{}", ::hex::encode(bytes)))
+    }
+
+    #[test]
+    fn test_decode_quantity() {
+        assert_eq!(decode_quantity("0x0"), Some(vec![0]));
+        assert_eq!(decode_quantity("0x400"), Some(vec![4, 0]));
+        assert_eq!(decode_quantity("0x1388"), Some(vec![0x13, 0x88]));
+        assert_eq!(decode_quantity("1388"), None);
+    }
+
+    #[test]
+    fn test_encode_header() {
+        let empty_root = hex_string(
+            &::hex::decode("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+                .unwrap(),
+        );
+        let genesis = BlockObject {
+            parentHash: hex_string(&[0; 32]),
+            sha3Uncles: Some(
+                "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347".into(),
+            ),
+            miner: hex_string(&[0; 20]),
+            stateRoot: Some(
+                "0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544".into(),
+            ),
+            transactionsRoot: empty_root.clone(),
+            receiptsRoot: empty_root,
+            logsBloom: hex_string(&[0; 256]),
+            difficulty: Some("0x400000000".into()),
+            number: Some("0x0".into()),
+            gasLimit: Some("0x1388".into()),
+            gasUsed: Some("0x0".into()),
+            timestamp: Some("0x0".into()),
+            extraData: Some(
+                "0x11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa".into(),
+            ),
+            mixHash: hex_string(&[0; 32]),
+            nonce: Some("0x0000000000000042".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ::hex::encode(keccak_256(&encode_header(&genesis).unwrap())),
+            "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+        );
+        assert_eq!(
+            encode_header(&BlockObject {
+                parentHash: None,
+                ..genesis
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_encode_receipts() {
+        let legacy = ReceiptObject {
+            r#type: Some("0x0".into()),
+            status: Some("0x1".into()),
+            cumulativeGasUsed: Some("0x5208".into()),
+            logsBloom: hex_string(&[0; 256]),
+            logs: Some(vec![LogObject {
+                address: Some("0x3a275655586a049fe860be867d10cdae2ffc0f33".into()),
+                topics: Some(vec![
+                    "0xc459acef3ffe957663bb49d644b20d0c790bcb41573893752a72ba6f023b9386".into(),
+                ]),
+                data: hex_string(&[[0; 31].to_vec(), vec![0x40]].concat()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        let dynamic_fee = ReceiptObject {
+            r#type: Some("0x2".into()),
+            status: Some("0x0".into()),
+            cumulativeGasUsed: Some("0xa410".into()),
+            logsBloom: hex_string(&[0; 256]),
+            logs: Some(vec![]),
+            ..Default::default()
+        };
+        let receipts = vec![
+            encode_receipt(&legacy).unwrap(),
+            encode_receipt(&dynamic_fee).unwrap(),
+        ];
+        assert_eq!(receipts[1][0], 2);
+        assert_eq!(
+            ::hex::encode(ordered_trie_root(receipts)),
+            "794d6ada274050aa842a84f9066f5ea611eb7856d94e4d2535a1950ed29fe0e1"
+        );
+    }
+}
@@ -0,0 +1,87 @@
+/// Encode the length prefix of an RLP string or list.
+fn encode_length(len: usize, offset: u8) -> Vec<u8> {
+    if len < 56 {
+        vec![offset + len as u8]
+    } else {
+        let len_bytes = strip_leading_zeros(&(len as u64).to_be_bytes()).to_vec();
+        let mut out = vec![offset + 55 + len_bytes.len() as u8];
+        out.extend(len_bytes);
+        out
+    }
+}
+
+fn strip_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let skip = bytes.iter().take_while(|b| **b == 0).count();
+    &bytes[skip..]
+}
+
+/// Encode a byte string.
+pub fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        bytes.to_vec()
+    } else {
+        let mut out = encode_length(bytes.len(), 0x80);
+        out.extend(bytes);
+        out
+    }
+}
+
+/// Encode a list of already encoded items.
+pub fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut out = encode_length(payload.len(), 0xc0);
+    out.extend(payload);
+    out
+}
+
+/// Encode an unsigned integer, given as big-endian bytes.
+pub fn encode_uint(bytes: &[u8]) -> Vec<u8> {
+    encode_bytes(strip_leading_zeros(bytes))
+}
+
+/// Encode an unsigned integer.
+pub fn encode_u64(value: u64) -> Vec<u8> {
+    encode_uint(&value.to_be_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_bytes() {
+        assert_eq!(encode_bytes(b""), vec![0x80]);
+        assert_eq!(encode_bytes(&[0x0f]), vec![0x0f]);
+        assert_eq!(encode_bytes(&[0x80]), vec![0x81, 0x80]);
+        assert_eq!(encode_bytes(b"dog"), vec![0x83, b'd', b'o', b'g']);
+
+        let lorem = b"Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+        let mut expected = vec![0xb8, 0x38];
+        expected.extend(&lorem[..]);
+        assert_eq!(encode_bytes(lorem), expected);
+    }
+
+    #[test]
+    fn test_encode_list() {
+        assert_eq!(encode_list(&[]), vec![0xc0]);
+        assert_eq!(
+            encode_list(&[encode_bytes(b"cat"), encode_bytes(b"dog")]),
+            vec![0xc8, 0x83, b'c', b'a', b't', 0x83, b'd', b'o', b'g']
+        );
+        assert_eq!(
+            encode_list(&[encode_list(&[]), encode_list(&[encode_list(&[])])]),
+            vec![0xc3, 0xc0, 0xc1, 0xc0]
+        );
+    }
+
+    #[test]
+    fn test_encode_uint() {
+        assert_eq!(encode_u64(0), vec![0x80]);
+        assert_eq!(encode_u64(15), vec![0x0f]);
+        assert_eq!(encode_u64(1024), vec![0x82, 0x04, 0x00]);
+        assert_eq!(
+            encode_uint(&[0x00, 0x00, 0x04, 0x00]),
+            vec![0x82, 0x04, 0x00]
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use crate::rlp;
+use sp_io::hashing::keccak_256;
+use sp_std::collections::btree_map::BTreeMap;
+
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|b| vec![b >> 4, b & 0x0f]).collect()
+}
+
+/// Compact a path of nibbles, flagging whether it ends at a leaf.
+fn hex_prefix(nibbles: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = (if leaf { 2 } else { 0 }) + (nibbles.len() % 2) as u8;
+    let (mut out, rest) = if nibbles.len() % 2 == 1 {
+        (vec![(flag << 4) | nibbles[0]], &nibbles[1..])
+    } else {
+        (vec![flag << 4], nibbles)
+    };
+    out.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    out
+}
+
+/// Nodes shorter than a hash are embedded in their parent, others are referenced by hash.
+fn node_ref(node: Vec<u8>) -> Vec<u8> {
+    if node.len() < 32 {
+        node
+    } else {
+        rlp::encode_bytes(&keccak_256(&node))
+    }
+}
+
+/// Encode the node holding the given sorted items, whose paths match up to `depth`.
+fn encode_node(items: &[(Vec<u8>, &[u8])], depth: usize) -> Vec<u8> {
+    if let [(path, value)] = items {
+        return rlp::encode_list(&[
+            rlp::encode_bytes(&hex_prefix(&path[depth..], true)),
+            rlp::encode_bytes(value),
+        ]);
+    }
+
+    let first = &items[0].0;
+    let shared = (depth..first.len())
+        .take_while(|&i| items.iter().all(|(path, _)| path.get(i) == Some(&first[i])))
+        .count();
+    if shared > 0 {
+        return rlp::encode_list(&[
+            rlp::encode_bytes(&hex_prefix(&first[depth..depth + shared], false)),
+            node_ref(encode_node(items, depth + shared)),
+        ]);
+    }
+
+    let mut slots: Vec<Vec<u8>> = (0..16u8)
+        .map(|nibble| {
+            let children: Vec<(Vec<u8>, &[u8])> = items
+                .iter()
+                .filter(|(path, _)| path.get(depth) == Some(&nibble))
+                .cloned()
+                .collect();
+            if children.is_empty() {
+                rlp::encode_bytes(&[])
+            } else {
+                node_ref(encode_node(&children, depth + 1))
+            }
+        })
+        .collect();
+    slots.push(match items.iter().find(|(path, _)| path.len() == depth) {
+        Some((_, value)) => rlp::encode_bytes(value),
+        None => rlp::encode_bytes(&[]),
+    });
+    rlp::encode_list(&slots)
+}
+
+/// Compute the root hash of the Merkle-Patricia trie holding the given key-value pairs.
+pub fn trie_root(items: Vec<(Vec<u8>, Vec<u8>)>) -> [u8; 32] {
+    let sorted: BTreeMap<Vec<u8>, Vec<u8>> = items
+        .into_iter()
+        .map(|(key, value)| (to_nibbles(&key), value))
+        .collect();
+    if sorted.is_empty() {
+        return keccak_256(&rlp::encode_bytes(&[]));
+    }
+    let items: Vec<(Vec<u8>, &[u8])> = sorted
+        .iter()
+        .map(|(path, value)| (path.clone(), &value[..]))
+        .collect();
+    keccak_256(&encode_node(&items, 0))
+}
+
+/// Compute the root hash of a trie keyed by the index of each value, like the receipts of a block.
+pub fn ordered_trie_root(values: Vec<Vec<u8>>) -> [u8; 32] {
+    trie_root(
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| (rlp::encode_u64(index as u64), value))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root_of(items: &[(&str, &str)]) -> String {
+        ::hex::encode(trie_root(
+            items
+                .iter()
+                .map(|(k, v)| (k.as_bytes().to_vec(), v.as_bytes().to_vec()))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn test_trie_root() {
+        assert_eq!(
+            root_of(&[]),
+            "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+        );
+        assert_eq!(
+            root_of(&[("foo", "bar"), ("food", "bass")]),
+            "17beaa1648bafa633cda809c90c04af50fc8aed3cb40d16efbddee6fdf63c4c3"
+        );
+        assert_eq!(
+            root_of(&[
+                ("doe", "reindeer"),
+                ("dog", "puppy"),
+                ("dogglesworth", "cat")
+            ]),
+            "8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3"
+        );
+        assert_eq!(
+            root_of(&[
+                ("do", "verb"),
+                ("horse", "stallion"),
+                ("doge", "coin"),
+                ("dog", "puppy")
+            ]),
+            "5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84"
+        );
+    }
+
+    #[test]
+    fn test_ordered_trie_root() {
+        let values = |n: usize| -> Vec<Vec<u8>> {
+            (0..n)
+                .map(|i| format!("value{}", i).repeat(1 + i % 3).into_bytes())
+                .collect()
+        };
+        assert_eq!(
+            ::hex::encode(ordered_trie_root(values(1))),
+            "bbc7c0c01d29f2094816f4b3defa582b17a37e365c918b032482c200961e1ee4"
+        );
+        assert_eq!(
+            ::hex::encode(ordered_trie_root(values(3))),
+            "77e7a18458c33f09286120cac9245aa1bc3d8bcf028597af4e1c023311c1d097"
+        );
+        assert_eq!(
+            ::hex::encode(ordered_trie_root(values(200))),
+            "ec1ce42ac817e275f414ea35b105994f434232d6bef14ce5c8c2779b33bde438"
+        );
+    }
+}
@@ -29,7 +29,7 @@ contract Starport {
     event LockCash(address indexed sender, string chain, bytes32 indexed recipient, uint amount, uint128 principal);
     event ExecTrxRequest(address indexed account, string trxRequest);
     event Unlock(address indexed account, uint amount, address asset);
-    event UnlockCash(address indexed account, uint amount, uint128 principal);
+    event UnlockCash(address indexed account, uint amount, uint128 principal, uint128 index);
     event ChangeAuthorities(address[] newAuthorities);
     event SetFutureYield(uint128 nextCashYield, uint128 nextCashYieldIndex, uint nextCashYieldStart);
     event ExecuteProposal(string title, bytes[] extrinsics);
@@ -300,17 +300,34 @@ contract Starport {
         }
     }
 
+    /**
+     * @notice Unlock several assets from the Starport at once
+     * @dev This must be called from `invoke` via passing in a signed notice from Gateway.
+     * @param assets The Assets to unlock
+     * @param amounts The amounts of each asset to unlock in its native token units
+     * @param accounts The accounts to transfer each asset to
+     */
+    function unlockBatch(address[] calldata assets, uint[] calldata amounts, address payable[] calldata accounts) external {
+        require(msg.sender == address(this), "Call must originate locally");
+        require(assets.length == amounts.length && assets.length == accounts.length, "Batch length mismatch");
+
+        for (uint i = 0; i < assets.length; i++) {
+            Starport(this).unlock(assets[i], amounts[i], accounts[i]);
+        }
+    }
+
     /**
      * @notice Unlock CASH from the Starport by minting
      * @dev This must be called from `invoke` via passing in a signed notice from Gateway.
      * @param account The account to transfer the asset to
      * @param principal The principal of CASH to unlock
+     * @param index The CASH index on Gateway at the time of extraction
      */
-    function unlockCash(address account, uint128 principal) external {
+    function unlockCash(address account, uint128 principal, uint128 index) external {
         require(msg.sender == address(this), "Call must originate locally");
 
         uint256 amount = cash.mint(account, principal);
-        emit UnlockCash(account, amount, principal);
+        emit UnlockCash(account, amount, principal, index);
     }
 
     /**
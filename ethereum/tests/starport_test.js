@@ -1266,18 +1266,33 @@ describe('Starport', () => {
       await expect(call(starport, 'unlock_', [ETH_ADDRESS, unlockAmount, account2])).rejects.toRevert('revert');
     });
 
+    it('should unlock batch', async () => {
+      await tokenA.methods.transfer(starport._address, 1500).send({ from: root });
+
+      const tx = await send(starport, 'unlockBatch_', [[tokenA._address, tokenA._address], [1000, 200], [account2, root]]);
+
+      expect(tx.events.Unlock.length).toEqual(2);
+      expect(Number(await tokenA.methods.balanceOf(starport._address).call())).toEqual(300);
+      expect(Number(await tokenA.methods.balanceOf(account2).call())).toEqual(1000);
+    });
+
+    it('should not unlock batch with mismatched lengths', async () => {
+      await expect(call(starport, 'unlockBatch_', [[tokenA._address], [1000, 200], [account2]])).rejects.toRevert('revert Batch length mismatch');
+    });
+
     it('should unlock cash', async () => {
       let mintPrincipal = await cash.methods.amountToPrincipal(e6(1)).call();
 
       expect(Number(await cash.methods.balanceOf(starport._address).call())).toEqual(0);
       expect(Number(await cash.methods.balanceOf(account2).call())).toEqual(0);
 
-      const tx = await send(starport, 'unlockCash_', [account2, mintPrincipal]);
+      const tx = await send(starport, 'unlockCash_', [account2, mintPrincipal, e18(1)]);
 
       expect(tx.events.UnlockCash.returnValues).toMatchObject({
         account: account2,
         amount: '1000000',
-        principal: '1000000'
+        principal: '1000000',
+        index: e18(1).toString()
       });
 
       expect(Number(await cash.methods.balanceOf(starport._address).call())).toEqual(0);
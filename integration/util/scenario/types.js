@@ -66,10 +66,11 @@ function encodeNotice(notice) {
         inputs: [
           { name: '', type: 'address' },
           { name: '', type: 'uint128' },
+          { name: '', type: 'uint128' },
         ],
         outputs: [],
       },
-      [ethNotice.account, ethNotice.principal]
+      [ethNotice.account, ethNotice.principal, ethNotice.index]
     );
   } else if (notice.futureYieldNotice && notice.futureYieldNotice.eth) {
     let ethNotice = notice.futureYieldNotice.eth;
@@ -8,14 +8,20 @@ use jsonrpc_derive::rpc;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::str::FromStr;
 
+use codec::Decode;
 use pallet_cash::{
-    chains::{ChainAccount, ChainAsset},
+    chains::{ChainAccount, ChainAccountSignature, ChainAsset, ChainHash, ChainId},
     core::BTreeMap,
+    factor::Factor,
     portfolio::Portfolio,
     rates::APR,
     reason::Reason,
-    types::{AssetAmount, AssetBalance, AssetInfo, InterestRateModel, Symbol, ValidatorKeys},
+    types::{
+        AccountActivity, AccountHistoryEntry, AssetAmount, AssetBalance, AssetInfo,
+        CashOrChainAsset, InterestRateModel, ProtocolState, Symbol, ValidatorKeys,
+    },
 };
 
 use pallet_cash_runtime_api::CashApi as CashRuntimeApi;
@@ -65,6 +71,12 @@ pub enum ApiInterestRateModel {
     Fixed {
         rate: String,
     },
+    Dynamic {
+        target_utilization: String,
+        min_rate: String,
+        max_rate: String,
+        max_change: String,
+    },
 }
 
 #[derive(Deserialize, Serialize, Types)]
@@ -74,7 +86,9 @@ pub struct ApiAssetInfo {
     liquidity_factor: String,
     rate_model: ApiInterestRateModel,
     miner_shares: String,
+    reserve_factor: String,
     supply_cap: String,
+    borrow_cap: String,
     symbol: Symbol,
     ticker: String,
 }
@@ -89,6 +103,12 @@ pub struct ApiCashData {
     total_cash: String,
 }
 
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiHypotheticalLiquidity {
+    liquidity: String,
+    health_factor: Option<String>,
+}
+
 #[derive(Deserialize, Serialize, Types)]
 pub struct ApiPortfolio {
     cash: String,
@@ -102,6 +122,120 @@ pub struct ApiValidators {
     miner_payouts: Vec<(ChainAccount, String)>,
 }
 
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiChainBlockStatus {
+    chain_id: ChainId,
+    last_block: String,
+    pending_blocks: u32,
+    queued_events: u32,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiNoticeStatus {
+    chain_id: ChainId,
+    era_id: u32,
+    era_index: u32,
+    encoded_notice: String,
+    signatures: u32,
+    quorum: u32,
+    executed: bool,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiExecutionFee {
+    fee: String,
+    timestamp: u64,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiRateHistory {
+    timestamp: u64,
+    borrow_rate: ApiAPR,
+    supply_rate: ApiAPR,
+    cash_index: String,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiStarportLiquidity {
+    holdings: String,
+    shortfall: String,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiValidatorMetadata {
+    substrate_id: String,
+    eth_address: String,
+    name: Option<String>,
+    website: Option<String>,
+    signing_addresses: Vec<ChainAccount>,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiNoticeSigner {
+    substrate_id: String,
+    eth_address: String,
+    name: Option<String>,
+    signed: bool,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiBalanceChange {
+    account: ChainAccount,
+    asset: String,
+    change: String,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiAccountHistoryEntry {
+    block_number: u64,
+    activity: String,
+    asset: String,
+    collateral_asset: Option<String>,
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    amount: String,
+    cash_index: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiMarketState {
+    asset: ChainAsset,
+    total_supply: String,
+    total_borrow: String,
+    reserves: String,
+    supply_cap: String,
+    borrow_cap: String,
+    supply_rate: ApiAPR,
+    borrow_rate: ApiAPR,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiProtocolValidator {
+    substrate_id: String,
+    eth_address: String,
+    signing_addresses: Vec<ChainAccount>,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiProtocolState {
+    markets: Vec<ApiMarketState>,
+    cash_index: String,
+    cash_yield: ApiAPR,
+    total_cash_principal: String,
+    validators: Vec<ApiProtocolValidator>,
+    pending_notices: Vec<(ChainId, u32)>,
+    last_processed_blocks: Vec<(ChainId, String)>,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiPendingNotice {
+    notice_hash: String,
+    era_id: u32,
+    era_index: u32,
+    encoded_notice: String,
+    signatures: Vec<String>,
+}
+
 /// Converts a runtime trap into an RPC error.
 fn runtime_err(err: impl std::fmt::Debug) -> RpcError {
     RpcError {
@@ -120,6 +254,81 @@ fn chain_err(reason: Reason) -> RpcError {
     }
 }
 
+/// Converts an entry in the history of an account into its readable form.
+/// For liquidations, the sender is the liquidator and the recipient the borrower.
+fn readable_history_entry(entry: AccountHistoryEntry) -> ApiAccountHistoryEntry {
+    let cash = String::from(CashOrChainAsset::Cash);
+    let asset = |asset| String::from(CashOrChainAsset::ChainAsset(asset));
+    let (activity, asset, collateral_asset, sender, recipient, amount, cash_index) = match entry
+        .activity
+    {
+        AccountActivity::Locked(a, s, r, amount) => ("Locked", asset(a), None, s, r, amount, None),
+        AccountActivity::LockedCash(s, r, principal, index) => {
+            ("LockedCash", cash, None, s, r, principal.0, Some(index))
+        }
+        AccountActivity::ReorgRevertLocked(a, s, r, amount) => {
+            ("ReorgRevertLocked", asset(a), None, s, r, amount, None)
+        }
+        AccountActivity::ReorgRevertLockedCash(s, r, principal, index) => (
+            "ReorgRevertLockedCash",
+            cash,
+            None,
+            s,
+            r,
+            principal.0,
+            Some(index),
+        ),
+        AccountActivity::Extract(a, s, r, amount) => {
+            ("Extract", asset(a), None, s, r, amount, None)
+        }
+        AccountActivity::ExtractCash(s, r, principal, index) => {
+            ("ExtractCash", cash, None, s, r, principal.0, Some(index))
+        }
+        AccountActivity::Transfer(a, s, r, amount) => {
+            ("Transfer", asset(a), None, s, r, amount, None)
+        }
+        AccountActivity::TransferCash(s, r, principal, index) => {
+            ("TransferCash", cash, None, s, r, principal.0, Some(index))
+        }
+        AccountActivity::Liquidate(a, c, s, r, amount) => {
+            ("Liquidate", asset(a), Some(asset(c)), s, r, amount, None)
+        }
+        AccountActivity::LiquidateCash(c, s, r, principal, index) => (
+            "LiquidateCash",
+            cash,
+            Some(asset(c)),
+            s,
+            r,
+            principal.0,
+            Some(index),
+        ),
+        AccountActivity::LiquidateCashCollateral(a, s, r, amount) => (
+            "LiquidateCashCollateral",
+            asset(a),
+            Some(cash),
+            s,
+            r,
+            amount,
+            None,
+        ),
+    };
+    ApiAccountHistoryEntry {
+        block_number: entry.block_number,
+        activity: activity.into(),
+        asset,
+        collateral_asset,
+        sender,
+        recipient,
+        amount: format!("{}", amount),
+        cash_index: cash_index.map(|index| format!("{}", index.0)),
+    }
+}
+
+/// Converts bytes published on chain into a string, replacing any invalid UTF-8.
+fn readable_bytes(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
 #[rpc]
 pub trait GatewayRpcApi<BlockHash> {
     #[rpc(name = "gateway_assetdata")]
@@ -140,9 +349,27 @@ pub trait GatewayRpcApi<BlockHash> {
         at: Option<BlockHash>,
     ) -> RpcResult<ApiCashData>;
 
+    #[rpc(name = "gateway_balance")]
+    fn gateway_balance(
+        &self,
+        account: ChainAccount,
+        asset: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<String>;
+
     #[rpc(name = "gateway_liquidity")]
     fn gateway_liquidity(&self, account: ChainAccount, at: Option<BlockHash>) -> RpcResult<String>;
 
+    #[rpc(name = "gateway_hypotheticalLiquidity")]
+    fn gateway_hypothetical_liquidity(
+        &self,
+        account: ChainAccount,
+        modify_asset: ChainAsset,
+        redeem_amount: String,
+        borrow_amount: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<ApiHypotheticalLiquidity>;
+
     #[rpc(name = "gateway_price")]
     fn gateway_price(&self, ticker: String, at: Option<BlockHash>) -> RpcResult<String>;
 
@@ -170,6 +397,73 @@ pub trait GatewayRpcApi<BlockHash> {
 
     #[rpc(name = "gateway_validators")]
     fn validators(&self, at: Option<BlockHash>) -> RpcResult<ApiValidators>;
+
+    #[rpc(name = "gateway_last_processed_blocks")]
+    fn last_processed_blocks(&self, at: Option<BlockHash>) -> RpcResult<Vec<ApiChainBlockStatus>>;
+
+    #[rpc(name = "gateway_noticeStatus")]
+    fn notice_status(
+        &self,
+        notice_hash: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<ApiNoticeStatus>;
+
+    #[rpc(name = "gateway_executionFee")]
+    fn execution_fee(&self, chain_id: ChainId, at: Option<BlockHash>)
+        -> RpcResult<ApiExecutionFee>;
+
+    #[rpc(name = "gateway_pendingNotices")]
+    fn pending_notices(
+        &self,
+        chain_id: ChainId,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ApiPendingNotice>>;
+
+    #[rpc(name = "gateway_rateHistory")]
+    fn rate_history(
+        &self,
+        asset: ChainAsset,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ApiRateHistory>>;
+
+    #[rpc(name = "gateway_starportLiquidity")]
+    fn starport_liquidity(
+        &self,
+        asset: ChainAsset,
+        amount: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<ApiStarportLiquidity>;
+
+    #[rpc(name = "gateway_validatorMetadata")]
+    fn validator_metadata(&self, at: Option<BlockHash>) -> RpcResult<Vec<ApiValidatorMetadata>>;
+
+    #[rpc(name = "gateway_noticeSigners")]
+    fn notice_signers(
+        &self,
+        notice_hash: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ApiNoticeSigner>>;
+
+    #[rpc(name = "gateway_accountHistory")]
+    fn account_history(
+        &self,
+        account: ChainAccount,
+        from_block: u64,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ApiAccountHistoryEntry>>;
+
+    #[rpc(name = "gateway_dryRunTrxRequest")]
+    fn dry_run_trx_request(
+        &self,
+        request: String,
+        signature: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ApiBalanceChange>>;
+
+    #[rpc(name = "gateway_protocolState")]
+    fn protocol_state(&self, at: Option<BlockHash>) -> RpcResult<ApiProtocolState>;
 }
 
 pub struct GatewayRpcHandler<C, B> {
@@ -273,6 +567,22 @@ where
         })
     }
 
+    fn gateway_balance(
+        &self,
+        account: ChainAccount,
+        asset: String,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<String> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let asset = CashOrChainAsset::from_str(&asset).map_err(chain_err)?;
+        let balance: AssetBalance = api
+            .get_balance(&at, account, asset)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(format!("{}", balance))
+    }
+
     fn gateway_liquidity(
         &self,
         account: ChainAccount,
@@ -287,6 +597,30 @@ where
         Ok(format!("{}", result))
     }
 
+    fn gateway_hypothetical_liquidity(
+        &self,
+        account: ChainAccount,
+        modify_asset: ChainAsset,
+        redeem_amount: String,
+        borrow_amount: String,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<ApiHypotheticalLiquidity> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let redeem_amount = AssetAmount::from_str(&redeem_amount)
+            .map_err(|_| RpcError::invalid_params("invalid redeem amount"))?;
+        let borrow_amount = AssetAmount::from_str(&borrow_amount)
+            .map_err(|_| RpcError::invalid_params("invalid borrow amount"))?;
+        let (liquidity, health_factor): (AssetBalance, Option<Factor>) = api
+            .get_hypothetical_liquidity(&at, account, modify_asset, redeem_amount, borrow_amount)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(ApiHypotheticalLiquidity {
+            liquidity: format!("{}", liquidity),
+            health_factor: health_factor.map(|f| format!("{}", f.0)),
+        })
+    }
+
     fn gateway_price(&self, ticker: String, at: Option<<B as BlockT>::Hash>) -> RpcResult<String> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
@@ -335,6 +669,17 @@ where
                 InterestRateModel::Fixed { rate } => ApiInterestRateModel::Fixed {
                     rate: String::from(rate),
                 },
+                InterestRateModel::Dynamic {
+                    target_utilization,
+                    min_rate,
+                    max_rate,
+                    max_change,
+                } => ApiInterestRateModel::Dynamic {
+                    target_utilization: format!("{}", target_utilization.0),
+                    min_rate: String::from(min_rate),
+                    max_rate: String::from(max_rate),
+                    max_change: String::from(max_change),
+                },
             }
         }
 
@@ -346,7 +691,9 @@ where
                 liquidity_factor: format!("{}", asset_info.liquidity_factor.0),
                 rate_model: api_rate_model(asset_info.rate_model),
                 miner_shares: format!("{}", asset_info.miner_shares.0),
+                reserve_factor: format!("{}", asset_info.reserve_factor.0),
                 supply_cap: format!("{}", asset_info.supply_cap),
+                borrow_cap: format!("{}", asset_info.borrow_cap),
                 symbol: asset_info.symbol,
                 ticker: String::from(asset_info.ticker),
             })
@@ -445,4 +792,281 @@ where
             miner_payouts: miner_payouts,
         })
     }
+
+    fn last_processed_blocks(
+        &self,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiChainBlockStatus>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let statuses = api
+            .get_last_processed_blocks(&at)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(statuses
+            .into_iter()
+            .map(
+                |(chain_id, last_block, pending_blocks, queued_events)| ApiChainBlockStatus {
+                    chain_id,
+                    last_block: format!("{}", last_block),
+                    pending_blocks,
+                    queued_events,
+                },
+            )
+            .collect())
+    }
+
+    fn notice_status(
+        &self,
+        notice_hash: String,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<ApiNoticeStatus> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let notice_hash = ChainHash::from_str(&notice_hash).map_err(chain_err)?;
+        let (chain_id, notice_id, encoded_notice, signatures, quorum, executed) = api
+            .get_notice_status(&at, notice_hash)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(ApiNoticeStatus {
+            chain_id,
+            era_id: notice_id.era_id(),
+            era_index: notice_id.era_index(),
+            encoded_notice: format!("0x{}", hex::encode(encoded_notice)),
+            signatures,
+            quorum,
+            executed,
+        })
+    }
+
+    fn execution_fee(
+        &self,
+        chain_id: ChainId,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<ApiExecutionFee> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let (fee, timestamp) = api
+            .get_execution_fee(&at, chain_id)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(ApiExecutionFee {
+            fee: format!("{}", fee),
+            timestamp,
+        })
+    }
+
+    fn pending_notices(
+        &self,
+        chain_id: ChainId,
+        limit: u32,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiPendingNotice>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let notices = api
+            .get_pending_notices(&at, chain_id, limit)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(notices
+            .into_iter()
+            .map(
+                |(notice_id, notice_hash, encoded_notice, signatures)| ApiPendingNotice {
+                    notice_hash: format!("{}", notice_hash),
+                    era_id: notice_id.era_id(),
+                    era_index: notice_id.era_index(),
+                    encoded_notice: format!("0x{}", hex::encode(encoded_notice)),
+                    signatures: signatures
+                        .iter()
+                        .map(|signature| format!("0x{}", hex::encode(signature)))
+                        .collect(),
+                },
+            )
+            .collect())
+    }
+
+    fn rate_history(
+        &self,
+        asset: ChainAsset,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiRateHistory>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let history = api
+            .get_rate_history(&at, asset)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(history
+            .into_iter()
+            .map(
+                |(timestamp, borrow_rate, supply_rate, cash_index)| ApiRateHistory {
+                    timestamp,
+                    borrow_rate: borrow_rate.0 as ApiAPR,
+                    supply_rate: supply_rate.0 as ApiAPR,
+                    cash_index: format!("{}", cash_index.0),
+                },
+            )
+            .collect())
+    }
+
+    fn starport_liquidity(
+        &self,
+        asset: ChainAsset,
+        amount: String,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<ApiStarportLiquidity> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let amount = AssetAmount::from_str(&amount)
+            .map_err(|_| RpcError::invalid_params("invalid amount"))?;
+        let (holdings, shortfall) = api
+            .get_starport_liquidity(&at, asset, amount)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(ApiStarportLiquidity {
+            holdings: format!("{}", holdings),
+            shortfall: format!("{}", shortfall),
+        })
+    }
+
+    fn validator_metadata(
+        &self,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiValidatorMetadata>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let validators = api
+            .get_validator_metadata(&at)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(validators
+            .into_iter()
+            .map(|(validator, metadata)| ApiValidatorMetadata {
+                substrate_id: format!("{}", validator.substrate_id),
+                eth_address: hex::encode(validator.eth_address),
+                name: metadata.as_ref().map(|m| readable_bytes(&m.name)),
+                website: metadata.as_ref().map(|m| readable_bytes(&m.website)),
+                signing_addresses: metadata.map(|m| m.signing_addresses).unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    fn notice_signers(
+        &self,
+        notice_hash: String,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiNoticeSigner>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let notice_hash = ChainHash::from_str(&notice_hash).map_err(chain_err)?;
+        let signers = api
+            .get_notice_signers(&at, notice_hash)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(signers
+            .into_iter()
+            .map(|(validator, metadata, signed)| ApiNoticeSigner {
+                substrate_id: format!("{}", validator.substrate_id),
+                eth_address: hex::encode(validator.eth_address),
+                name: metadata.map(|m| readable_bytes(&m.name)),
+                signed,
+            })
+            .collect())
+    }
+
+    fn account_history(
+        &self,
+        account: ChainAccount,
+        from_block: u64,
+        limit: u32,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiAccountHistoryEntry>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let entries = api
+            .get_account_history(&at, account, from_block, limit)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(entries.into_iter().map(readable_history_entry).collect())
+    }
+
+    fn dry_run_trx_request(
+        &self,
+        request: String,
+        signature: String,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiBalanceChange>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let signature_bytes = hex::decode(signature.trim_start_matches("0x"))
+            .map_err(|_| RpcError::invalid_params("invalid signature"))?;
+        let signature = ChainAccountSignature::decode(&mut &signature_bytes[..])
+            .map_err(|_| RpcError::invalid_params("invalid signature"))?;
+        let changes = api
+            .dry_run_trx_request(&at, request.into_bytes(), signature)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(changes
+            .into_iter()
+            .map(|(account, asset, change)| ApiBalanceChange {
+                account,
+                asset: String::from(asset),
+                change: format!("{}", change),
+            })
+            .collect())
+    }
+
+    fn protocol_state(&self, at: Option<<B as BlockT>::Hash>) -> RpcResult<ApiProtocolState> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let state: ProtocolState = api
+            .get_protocol_state(&at)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(ApiProtocolState {
+            markets: state
+                .markets
+                .into_iter()
+                .map(|market| ApiMarketState {
+                    asset: market.asset,
+                    total_supply: format!("{}", market.total_supply),
+                    total_borrow: format!("{}", market.total_borrow),
+                    reserves: format!("{}", market.reserves.0),
+                    supply_cap: format!("{}", market.supply_cap),
+                    borrow_cap: format!("{}", market.borrow_cap),
+                    supply_rate: market.supply_rate.0 as ApiAPR,
+                    borrow_rate: market.borrow_rate.0 as ApiAPR,
+                })
+                .collect(),
+            cash_index: format!("{}", state.cash_index.0),
+            cash_yield: state.cash_yield.0 as ApiAPR,
+            total_cash_principal: format!("{}", state.total_cash_principal.0),
+            validators: state
+                .validators
+                .into_iter()
+                .map(|(validator, signing_addresses)| ApiProtocolValidator {
+                    substrate_id: format!("{}", validator.substrate_id),
+                    eth_address: hex::encode(validator.eth_address),
+                    signing_addresses,
+                })
+                .collect(),
+            pending_notices: state.pending_notices,
+            last_processed_blocks: state
+                .last_processed_blocks
+                .into_iter()
+                .map(|(chain_id, block_number)| (chain_id, format!("{}", block_number)))
+                .collect(),
+        })
+    }
 }
@@ -13,6 +13,8 @@ pub struct GatewayCmd {
     /// OPF_URL
     ///
     /// example ./gateway .... --env ETH_RPC_URL=http://... ETH_KEY_ID=.. MINER=Eth:0x01234567890123456789 OPF_URL=http://....
+    ///
+    /// ETH_RPC_URL and MATIC_RPC_URL may list several endpoints separated by commas, to fail over between.
     pub env: Vec<String>,
 }
 
@@ -26,6 +26,7 @@ native_executor_instance!(
         runtime_interfaces::validator_config_interface::HostFunctions,
         runtime_interfaces::keyring_interface::HostFunctions,
         runtime_interfaces::price_feed_interface::HostFunctions,
+        runtime_interfaces::metrics_interface::HostFunctions,
     ),
 );
 
@@ -177,6 +178,10 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
     let enable_grandpa = !config.disable_grandpa;
     let prometheus_registry = config.prometheus_registry().cloned();
 
+    if let Some(registry) = prometheus_registry.as_ref() {
+        runtime_interfaces::register_offchain_metrics(registry)?;
+    }
+
     let rpc_extensions_builder = {
         let client = client.clone();
         let pool = transaction_pool.clone();
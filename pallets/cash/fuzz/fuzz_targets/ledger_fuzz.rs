@@ -0,0 +1,54 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use pallet_cash::chains::{ChainAccount, ChainAsset};
+use pallet_cash::pure::Ledger;
+use pallet_cash::symbol::Units;
+use pallet_cash::types::{AssetInfo, CashPrincipal, CashPrincipalAmount};
+
+const ASSET: ChainAsset = ChainAsset::Eth([0xee; 20]);
+const ETH: Units = Units::from_ticker_str("ETH", 18);
+
+fn account(byte: u8) -> ChainAccount {
+    ChainAccount::Eth([byte % 4; 20])
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut ledger = Ledger::default().support_asset(AssetInfo::minimal(ASSET, ETH));
+    for op in data.chunks_exact(4) {
+        let (a, b, amount) = (account(op[1]), account(op[2]), op[3] as u128);
+        let result = match op[0] % 6 {
+            0 => ledger.clone().lock_asset(a, ASSET, amount),
+            1 => ledger.clone().extract_asset(a, ASSET, amount),
+            2 => ledger.clone().transfer_asset(a, b, ASSET, amount),
+            3 => ledger.clone().lock_cash(a, CashPrincipalAmount(amount)),
+            4 => ledger.clone().extract_cash(a, CashPrincipalAmount(amount)),
+            _ => ledger
+                .clone()
+                .transfer_cash(a, b, CashPrincipalAmount(amount)),
+        };
+        if let Ok(next) = result {
+            ledger = next;
+        }
+
+        // the markets and CASH balances must always add up
+        let (info, market) = ledger.get_market(ASSET).unwrap();
+        let (mut supply, mut borrow) = (0, 0);
+        let mut owed = 0;
+        for byte in 0..4 {
+            let balance = ledger.get_position(info, account(byte)).balance.value;
+            if balance > 0 {
+                supply += balance as u128;
+            } else {
+                borrow += (-balance) as u128;
+            }
+            let CashPrincipal(principal) = ledger.get_cash_principal(account(byte));
+            if principal < 0 {
+                owed += (-principal) as u128;
+            }
+        }
+        assert_eq!(market.total_supply.value, supply);
+        assert_eq!(market.total_borrow.value, borrow);
+        assert!(market.total_borrow <= market.total_supply);
+        assert_eq!(ledger.total_cash_principal, CashPrincipalAmount(owed));
+    }
+});
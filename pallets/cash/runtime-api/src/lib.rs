@@ -1,11 +1,16 @@
 use pallet_cash::{
-    chains::{ChainAccount, ChainAsset},
+    chains::{
+        ChainAccount, ChainAccountSignature, ChainAsset, ChainBlockNumber, ChainHash, ChainId,
+    },
     core::BTreeMap,
+    factor::Factor,
     portfolio::Portfolio,
     rates::APR,
     reason::Reason,
     types::{
-        AssetAmount, AssetBalance, AssetInfo, Balance, CashIndex, CashPrincipal, ValidatorKeys,
+        AccountHistoryEntry, AssetAmount, AssetBalance, AssetInfo, Balance, CashIndex,
+        CashOrChainAsset, CashPrincipal, EncodedNotice, NoticeId, ProtocolState, Timestamp,
+        ValidatorKeys, ValidatorMetadata,
     },
 };
 use pallet_oracle::{ticker::Ticker, types::AssetPrice};
@@ -13,11 +18,13 @@ use pallet_oracle::{ticker::Ticker, types::AssetPrice};
 sp_api::decl_runtime_apis! {
     pub trait CashApi {
         fn get_account_balance(account: ChainAccount, asset: ChainAsset) -> Result<AssetBalance, Reason>;
+        fn get_balance(account: ChainAccount, asset: CashOrChainAsset) -> Result<AssetBalance, Reason>;
         fn get_asset(asset: ChainAsset) -> Result<AssetInfo, Reason>;
         fn get_cash_yield() -> Result<APR, Reason>;
         fn get_cash_data() -> Result<(CashIndex, CashPrincipal, Balance), Reason>;
         fn get_full_cash_balance(account: ChainAccount) -> Result<AssetBalance, Reason>;
         fn get_liquidity(account: ChainAccount) -> Result<AssetBalance, Reason>;
+        fn get_hypothetical_liquidity(account: ChainAccount, modify_asset: ChainAsset, redeem_amount: AssetAmount, borrow_amount: AssetAmount) -> Result<(AssetBalance, Option<Factor>), Reason>;
         fn get_market_totals(asset: ChainAsset) -> Result<(AssetAmount, AssetAmount), Reason>;
         fn get_price(ticker: String) -> Result<AssetPrice, Reason>;
         fn get_price_with_ticker(ticker: Ticker) -> Result<AssetPrice, Reason>;
@@ -36,5 +43,16 @@ sp_api::decl_runtime_apis! {
         fn get_accounts_liquidity() -> Result<Vec<(ChainAccount, String)>, Reason>;
         fn get_portfolio(account: ChainAccount) -> Result<Portfolio, Reason>;
         fn get_validator_info() -> Result<(Vec<ValidatorKeys>, Vec<(ChainAccount, String)>), Reason>;
+        fn get_last_processed_blocks() -> Result<Vec<(ChainId, ChainBlockNumber, u32, u32)>, Reason>;
+        fn get_notice_status(notice_hash: ChainHash) -> Result<(ChainId, NoticeId, EncodedNotice, u32, u32, bool), Reason>;
+        fn get_pending_notices(chain_id: ChainId, limit: u32) -> Result<Vec<(NoticeId, ChainHash, EncodedNotice, Vec<Vec<u8>>)>, Reason>;
+        fn get_execution_fee(chain_id: ChainId) -> Result<(AssetAmount, Timestamp), Reason>;
+        fn get_rate_history(asset: ChainAsset) -> Result<Vec<(Timestamp, APR, APR, CashIndex)>, Reason>;
+        fn get_starport_liquidity(asset: ChainAsset, amount: AssetAmount) -> Result<(AssetAmount, AssetAmount), Reason>;
+        fn get_validator_metadata() -> Result<Vec<(ValidatorKeys, Option<ValidatorMetadata>)>, Reason>;
+        fn get_notice_signers(notice_hash: ChainHash) -> Result<Vec<(ValidatorKeys, Option<ValidatorMetadata>, bool)>, Reason>;
+        fn get_account_history(account: ChainAccount, from_block: u64, limit: u32) -> Result<Vec<AccountHistoryEntry>, Reason>;
+        fn dry_run_trx_request(request: Vec<u8>, signature: ChainAccountSignature) -> Result<Vec<(ChainAccount, CashOrChainAsset, AssetBalance)>, Reason>;
+        fn get_protocol_state() -> Result<ProtocolState, Reason>;
     }
 }
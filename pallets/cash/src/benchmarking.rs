@@ -67,6 +67,7 @@ fn init_asset_balance(asset: ChainAsset, account: ChainAccount, balance: AssetBa
             asset,
             (TotalSupplyAssets::get(asset) as i128 + balance) as u128,
         );
+        internal::holdings::add_holdings(asset, balance as AssetAmount);
     } else {
         TotalBorrowAssets::insert(
             asset,
@@ -134,7 +135,7 @@ benchmarks! {
     <<T as frame_system::Config>::Origin as OriginTrait>::AccountId: From<SubstrateId>
 }
 
-    // test gas up to 10 tokens
+    // test gas up to 10 tokens, asset interest is accrued lazily so this should not depend on them
     on_initialize {
         let z in 0 .. 10;
         let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
@@ -159,6 +160,8 @@ benchmarks! {
     }
 
     publish_signature {
+        // the other validators have all signed already
+        let z in 1 .. T::MaxValidators::get();
         let chain_id = ChainId::Eth;
         let notice_id = NoticeId(5, 6);
         let notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
@@ -173,8 +176,15 @@ benchmarks! {
             ChainSignature::Eth(a) => a,
             _ => panic!("absurd"),
         };
+        let mut signature_pairs = vec![];
+        for i in 1..z {
+            let substrate_id = AccountId32::new([i as u8; 32]);
+            let eth_address = [i as u8; 20];
+            Validators::insert(substrate_id.clone(), ValidatorKeys { substrate_id, eth_address });
+            signature_pairs.push((eth_address, [i as u8; 65]));
+        }
         let notice_state = NoticeState::Pending {
-            signature_pairs: ChainSignatureList::Eth(vec![]),
+            signature_pairs: ChainSignatureList::Eth(signature_pairs.clone().try_into().unwrap()),
         };
         NoticeStates::insert(chain_id, notice_id, notice_state);
         Notices::insert(chain_id, notice_id, notice);
@@ -188,8 +198,9 @@ benchmarks! {
             },
         );
 
+        signature_pairs.push((eth_address, eth_signature));
         let expected_notice_state = NoticeState::Pending {
-            signature_pairs: ChainSignatureList::Eth(vec![(eth_address, eth_signature)]),
+            signature_pairs: ChainSignatureList::Eth(signature_pairs.try_into().unwrap()),
         };
 
     }: {
@@ -208,6 +219,10 @@ benchmarks! {
         assert_eq!(Cash::<T>::set_yield_next(RawOrigin::Root.into(), APR(100).into(), 86400500), Ok(()));
     }
 
+    set_peg_tolerance {}: {
+        assert_ok!(Cash::<T>::set_peg_tolerance(RawOrigin::Root.into(), 100, 60 * 60 * 1000));
+    }
+
     receive_chain_blocks {
         let substrate_id = AccountId32::new([12u8; 32]);
         let eth_address = <Ethereum as Chain>::signer_address().unwrap();
@@ -235,6 +250,54 @@ benchmarks! {
         assert_ok!(Cash::<T>::receive_chain_blocks(RawOrigin::None.into(), blocks, signature));
     }
 
+    report_equivocation {
+        let substrate_id = AccountId32::new([12u8; 32]);
+        let eth_address = <Ethereum as Chain>::signer_address().unwrap();
+        Validators::insert(
+            substrate_id.clone(),
+            ValidatorKeys {
+                substrate_id: substrate_id.clone(),
+                eth_address,
+            },
+        );
+        AttestedPayloads::insert((ChainId::Eth, 1), vec![(substrate_id.clone(), ChainHash::Eth([1; 32]))]);
+        let blocks = ChainBlocks::Eth(vec![
+            ethereum_client::EthereumBlock {
+                hash: [2; 32],
+                parent_hash: [0; 32],
+                number: 1,
+                events: vec![],
+            }
+        ]);
+        let signature = ChainSignature::Eth(<Ethereum as Chain>::sign_message(&blocks.encode()).unwrap());
+    }: {
+        assert_ok!(Cash::<T>::report_equivocation(RawOrigin::None.into(), blocks, signature));
+    } verify {
+        assert!(ChilledValidators::contains_key(&substrate_id));
+    }
+
+    set_validator_metadata {
+        let substrate_id = AccountId32::new([12u8; 32]);
+        Validators::insert(
+            substrate_id.clone(),
+            ValidatorKeys {
+                substrate_id: substrate_id.clone(),
+                eth_address: <Ethereum as Chain>::signer_address().unwrap(),
+            },
+        );
+        let metadata = ValidatorMetadata {
+            timestamp: 1,
+            name: vec![b'a'; params::MAX_VALIDATOR_NAME_LEN],
+            website: vec![b'a'; params::MAX_VALIDATOR_WEBSITE_LEN],
+            signing_addresses: vec![ChainAccount::Eth([1; 20]), ChainAccount::Matic([1; 20])],
+        };
+        let signature = ChainSignature::Eth(<Ethereum as Chain>::sign_message(&metadata.encode()).unwrap());
+    }: {
+        assert_ok!(Cash::<T>::set_validator_metadata(RawOrigin::None.into(), metadata, signature));
+    } verify {
+        assert!(ValidatorMetadatas::contains_key(&substrate_id));
+    }
+
     receive_chain_reorg_pending {
         let z in 1 .. 10;
         // add 2 vals
@@ -280,6 +343,32 @@ benchmarks! {
         assert_ok!(Cash::<T>::support_asset(RawOrigin::Root.into(), info));
     }
 
+    set_asset_group {
+        let a in 1 .. (params::MAX_ASSET_GROUP_SIZE as u32);
+        let assets: Vec<ChainAsset> = (0..a).map(|i| ChainAsset::Eth([i as u8 + 1; 20])).collect();
+        for asset in &assets {
+            let info = AssetInfo::minimal(*asset, FromStr::from_str("USDC/6").unwrap());
+            assert_ok!(Cash::<T>::support_asset(RawOrigin::Root.into(), info));
+        }
+    }: {
+        assert_ok!(Cash::<T>::set_asset_group(RawOrigin::Root.into(), Symbol::new("USDC"), assets));
+    }
+
+    set_price_key {
+        let asset = ChainAsset::Eth([1u8; 20]);
+        let info = AssetInfo::minimal(asset, FromStr::from_str("USDC/6").unwrap());
+        assert_ok!(Cash::<T>::support_asset(RawOrigin::Root.into(), info));
+    }: {
+        assert_ok!(Cash::<T>::set_price_key(RawOrigin::Root.into(), info.ticker, Some(CashOrChainAsset::ChainAsset(asset))));
+    }
+
+    start_exodus {
+        <pallet_timestamp::Now<T>>::put(1u64);
+        endow_tkn::<T>(BOB_ADDRESS_BYTES, 1_000_000, TKN_ADDR_BYTES);
+    }: {
+        assert_ok!(Cash::<T>::start_exodus(RawOrigin::Root.into()));
+    }
+
     set_rate_model{
         let info = AssetInfo::minimal(
             ChainAsset::Eth([1u8; 20]),
@@ -300,6 +389,40 @@ benchmarks! {
         assert_ok!(Cash::<T>::set_liquidity_factor(RawOrigin::Root.into(), ChainAsset::Eth([1u8; 20]), Factor(1u128)));
     }
 
+    set_reserve_factor{
+        let info = AssetInfo::minimal(
+            ChainAsset::Eth([1u8; 20]),
+            FromStr::from_str("USDC/6").unwrap(),
+        );
+        assert_ok!(Cash::<T>::support_asset(RawOrigin::Root.into(), info));
+    }: {
+        assert_ok!(Cash::<T>::set_reserve_factor(RawOrigin::Root.into(), ChainAsset::Eth([1u8; 20]), Factor::from_nominal("0.1")));
+    }
+
+    sweep_reserves{
+        let info = AssetInfo::minimal(
+            ChainAsset::Eth([1u8; 20]),
+            FromStr::from_str("USDC/6").unwrap(),
+        );
+        assert_ok!(Cash::<T>::support_asset(RawOrigin::Root.into(), info));
+        ProtocolReserves::insert(ChainAsset::Eth([1u8; 20]), CashPrincipalAmount(1000000u128));
+        let recipient = ChainAccount::Eth(BOB_ADDRESS_BYTES);
+    }: {
+        assert_ok!(Cash::<T>::sweep_reserves(RawOrigin::Root.into(), ChainAsset::Eth([1u8; 20]), recipient));
+    }
+
+    write_off_bad_debt {
+        endow_tkn::<T>(BOB_ADDRESS_BYTES, 1_000_000, TKN_ADDR_BYTES);
+        let account = ChainAccount::Eth(BOB_ADDRESS_BYTES);
+        CashPrincipals::insert(account, CashPrincipal::from_nominal("-2"));
+        TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("2"));
+        BadDebts::insert(account, 1_000_000);
+        TotalBadDebt::put(1_000_000);
+        ProtocolReserves::insert(ChainAsset::Eth(TKN_ADDR_BYTES), CashPrincipalAmount::from_nominal("10"));
+    }: {
+        assert_ok!(Cash::<T>::write_off_bad_debt(RawOrigin::Root.into(), account, ChainAsset::Eth(TKN_ADDR_BYTES)));
+    }
+
     set_supply_cap{
         let info = AssetInfo::minimal(
             ChainAsset::Eth([1u8; 20]),
@@ -310,6 +433,258 @@ benchmarks! {
         assert_ok!(Cash::<T>::set_supply_cap(RawOrigin::Root.into(), ChainAsset::Eth([1u8; 20]), 1u128));
     }
 
+    set_borrow_cap{
+        let info = AssetInfo::minimal(
+            ChainAsset::Eth([1u8; 20]),
+            FromStr::from_str("USDC/6").unwrap(),
+        );
+        assert_ok!(Cash::<T>::support_asset(RawOrigin::Root.into(), info));
+    }: {
+        assert_ok!(Cash::<T>::set_borrow_cap(RawOrigin::Root.into(), ChainAsset::Eth([1u8; 20]), 1u128));
+    }
+
+    set_liquidation_incentive {}: {
+        assert_ok!(Cash::<T>::set_liquidation_incentive(RawOrigin::Root.into(), 1000));
+    }
+
+    set_close_factor {}: {
+        assert_ok!(Cash::<T>::set_close_factor(RawOrigin::Root.into(), Factor::from_nominal("0.5")));
+    }
+
+    set_seize_paused{
+        let info = AssetInfo::minimal(
+            ChainAsset::Eth([1u8; 20]),
+            FromStr::from_str("USDC/6").unwrap(),
+        );
+        assert_ok!(Cash::<T>::support_asset(RawOrigin::Root.into(), info));
+    }: {
+        assert_ok!(Cash::<T>::set_seize_paused(RawOrigin::Root.into(), ChainAsset::Eth([1u8; 20]), true));
+    }
+
+    set_liquidation_auction {
+        let config = LiquidationAuctionConfig {
+            min_value: 1_000_000_000000,
+            start_incentive: 0,
+            max_incentive: 1000,
+            ramp_blocks: 100,
+        };
+    }: {
+        assert_ok!(Cash::<T>::set_liquidation_auction(RawOrigin::Root.into(), Some(config)));
+    }
+
+    set_fee_rate {}: {
+        assert_ok!(Cash::<T>::set_fee_rate(RawOrigin::Root.into(), Factor::from_nominal("0.001")));
+    }
+
+    set_max_missed_signatures {}: {
+        assert_ok!(Cash::<T>::set_max_missed_signatures(RawOrigin::Root.into(), Factor::from_nominal("0.25")));
+    }
+
+    set_quorum_threshold {}: {
+        assert_ok!(Cash::<T>::set_quorum_threshold(RawOrigin::Root.into(), Permill::from_percent(75)));
+    }
+
+    set_governance_delay {}: {
+        assert_ok!(Cash::<T>::set_governance_delay(RawOrigin::Root.into(), 2 * 24 * 60 * 60 * 1000));
+    }
+
+    cancel_governance {
+        let extrinsics = vec![vec![0u8]];
+        let event = ChainBlockEvent::Eth(1, ethereum_client::EthereumEvent::ExecuteProposal {
+            title: String::from("Proposal"),
+            extrinsics: extrinsics.clone(),
+        });
+        QueuedGovernance::insert(0, (1u64, event, extrinsics));
+    }: {
+        assert_ok!(Cash::<T>::cancel_governance(RawOrigin::Root.into(), 0));
+    }
+
+    set_extraction_limit{
+        let info = AssetInfo::minimal(
+            ChainAsset::Eth([1u8; 20]),
+            FromStr::from_str("USDC/6").unwrap(),
+        );
+        assert_ok!(Cash::<T>::support_asset(RawOrigin::Root.into(), info));
+    }: {
+        assert_ok!(Cash::<T>::set_extraction_limit(RawOrigin::Root.into(), ChainAsset::Eth([1u8; 20]), Some(1u128)));
+    }
+
+    set_extraction_batch_budget {}: {
+        assert_ok!(Cash::<T>::set_extraction_batch_budget(RawOrigin::Root.into(), ChainId::Eth, Some(2048)));
+    }
+
+    discard_failed_event {
+        let event = ChainBlockEvent::Eth(1, ethereum_client::EthereumEvent::Lock {
+            asset: [1; 20],
+            sender: [3; 20],
+            chain: String::from("ETH"),
+            recipient: [4; 32],
+            amount: 100,
+        });
+        internal::events::record_failed_event::<T>(&event, Reason::AssetNotSupported);
+    }: {
+        assert_ok!(Cash::<T>::discard_failed_event(RawOrigin::Root.into(), event));
+    }
+
+    set_event_proof_mode {}: {
+        assert_ok!(Cash::<T>::set_event_proof_mode(RawOrigin::Root.into(), ChainId::Eth, true));
+    }
+
+    set_chain_status {}: {
+        assert_ok!(Cash::<T>::set_chain_status(RawOrigin::Root.into(), ChainId::Matic, ChainStatus::Halted));
+    }
+
+    set_event_expiration {}: {
+        assert_ok!(Cash::<T>::set_event_expiration(RawOrigin::Root.into(), 1200));
+    }
+
+    set_notice_retention {}: {
+        assert_ok!(Cash::<T>::set_notice_retention(RawOrigin::Root.into(), 8));
+    }
+
+    set_initialize_weight_budget {}: {
+        assert_ok!(Cash::<T>::set_initialize_weight_budget(RawOrigin::Root.into(), 1_000_000_000));
+    }
+
+    release_queued_governance {
+        let z in 1 .. params::GOVERNANCE_RELEASE_BATCH_SIZE;
+        let call: <T as Config>::Call = Call::<T>::set_governance_delay(0).into();
+        for id in 0..z {
+            let extrinsics = vec![call.encode()];
+            let event = ChainBlockEvent::Eth(id.into(), ethereum_client::EthereumEvent::ExecuteProposal {
+                title: String::from("Proposal"),
+                extrinsics: extrinsics.clone(),
+            });
+            QueuedGovernance::insert(id as GovernanceId, (1u64, event, extrinsics));
+        }
+    }: {
+        assert_ok!(internal::work::do_work::<T>(Work::ReleaseGovernance, 1));
+    } verify {
+        assert!(!QueuedGovernance::contains_key((z - 1) as GovernanceId));
+    }
+
+    release_queued_recoveries {
+        let z in 1 .. params::RECOVERY_RELEASE_BATCH_SIZE;
+        let amount: i128 = MIN_TX_VALUE.try_into().unwrap();
+        for i in 0..z {
+            let holder = [i as u8 + 1; 20];
+            let account = ChainAccount::Eth(holder);
+            endow_tkn::<T>(holder, amount, TKN_ADDR_BYTES);
+            QueuedRecoveries::insert(account, (1u64, ChainAccount::Eth([i as u8 + 128; 20])));
+            RecoveryQueue::insert(i as u64, (account, 1u64));
+        }
+        RecoveryQueueRange::put((0, z as u64));
+    }: {
+        assert_ok!(internal::work::do_work::<T>(Work::ReleaseRecoveries, 1));
+    } verify {
+        assert_eq!(RecoveryQueueRange::get(), (z as u64, z as u64));
+    }
+
+    release_queued_extractions {
+        // one of the batch goes to reading the queue
+        let z in 1 .. (params::EXTRACTION_RELEASE_BATCH_SIZE - 1);
+        let asset = ChainAsset::Eth([1u8; 20]);
+        let info = AssetInfo::minimal(asset, FromStr::from_str("USDC/6").unwrap());
+        assert_ok!(Cash::<T>::support_asset(RawOrigin::Root.into(), info));
+        ExtractionLimits::insert(asset, u128::MAX);
+        QueuedExtractions::insert(asset, vec![(ChainAccount::Eth([2u8; 20]), 1u128); z as usize]);
+    }: {
+        assert_ok!(internal::work::do_work::<T>(Work::ReleaseExtractions, 1));
+    } verify {
+        assert!(!QueuedExtractions::contains_key(asset));
+    }
+
+    dispatch_extraction_batches {
+        let z in 1 .. params::MAX_BATCHED_EXTRACTIONS;
+        let chain_id = ChainId::Eth;
+        // with no budget every extraction goes in a notice by itself
+        ExtractionBatchBudgets::insert(chain_id, 0);
+        PendingBatchExtractions::insert(
+            chain_id,
+            vec![(ChainAsset::Eth(TKN_ADDR_BYTES), ChainAccount::Eth(BOB_ADDRESS_BYTES), 1u128); z as usize],
+        );
+    }: {
+        assert_ok!(internal::work::do_work::<T>(Work::DispatchExtractionBatches, 1));
+    } verify {
+        assert!(!PendingBatchExtractions::contains_key(chain_id));
+    }
+
+    expire_pending_blocks {}: {
+        assert_ok!(internal::work::do_work::<T>(Work::ExpirePendingBlocks, 1));
+    }
+
+    retry_failed_events {
+        let z in 1 .. params::FAILED_EVENT_RETRY_BATCH_SIZE;
+        endow_tkn::<T>(BOB_ADDRESS_BYTES, 0, TKN_ADDR_BYTES);
+        // the list is full, but only the first z events are due, and now lock successfully
+        frame_system::Pallet::<T>::set_block_number(0u32.into());
+        for block in 0..params::MAX_FAILED_EVENTS {
+            if block == z {
+                frame_system::Pallet::<T>::set_block_number((params::FAILED_EVENT_RETRY_BLOCKS as u32).into());
+            }
+            let event = ChainBlockEvent::Eth(block.into(), ethereum_client::EthereumEvent::Lock {
+                asset: TKN_ADDR_BYTES,
+                sender: [3; 20],
+                chain: String::from("ETH"),
+                recipient: [4; 32],
+                amount: 100,
+            });
+            internal::events::record_failed_event::<T>(&event, Reason::AssetNotSupported);
+        }
+    }: {
+        assert_ok!(internal::work::do_work::<T>(Work::RetryFailedEvents, 1));
+    } verify {
+        assert_eq!(FailedChainBlockEvents::decode_len(), Some((params::MAX_FAILED_EVENTS - z) as usize));
+    }
+
+    process_exodus {}: {
+        assert_ok!(internal::work::do_work::<T>(Work::ProcessExodus, 1));
+    }
+
+    track_cash_peg {}: {
+        assert_ok!(internal::work::do_work::<T>(Work::TrackCashPeg, 1));
+    }
+
+    record_rate_history {
+        let z in 1 .. 10;
+        for i in 0..z {
+            endow_tkn::<T>(BOB_ADDRESS_BYTES, 1, [i as u8 + 1; 20]);
+        }
+    }: {
+        assert_ok!(internal::work::do_work::<T>(Work::RecordRateHistory, 1));
+    } verify {
+        assert_eq!(RateHistoryCursor::get(), (1, 1));
+    }
+
+    check_solvency {
+        let z in 1 .. 10;
+        for i in 0..z {
+            endow_tkn::<T>(BOB_ADDRESS_BYTES, 1, [i as u8 + 1; 20]);
+        }
+    }: {
+        assert_ok!(internal::work::do_work::<T>(Work::CheckSolvency, 1));
+    } verify {
+        assert!(LastSolvencySnapshot::get().is_some());
+    }
+
+    prune_notices {
+        let z in 1 .. params::MAX_PRUNE_NOTICES;
+        let chain_id = ChainId::Eth;
+        for index in 0..z {
+            let notice_id = NoticeId(1, index);
+            let notice_hash = ChainHash::Eth([index as u8; 32]);
+            NoticeStates::insert(chain_id, notice_id, NoticeState::Executed);
+            NoticeHashes::insert(notice_hash, notice_id);
+            ExecutedNotices::insert(chain_id, index as u64, (notice_id, notice_hash));
+        }
+        ExecutedNoticeRange::insert(chain_id, (0, z as u64));
+        LatestNotice::insert(chain_id, (NoticeId(100, 0), ChainHash::Eth([0; 32])));
+    }: {
+        assert_ok!(internal::work::do_work::<T>(Work::PruneNotices, 1));
+    } verify {
+        assert_eq!(ExecutedNoticeRange::get(chain_id), (z as u64, z as u64));
+    }
+
     allow_next_code_with_hash {
         let new_code = vec![3u8; 100_000];
         let hash = <Ethereum as Chain>::hash_bytes(&new_code);
@@ -326,37 +701,42 @@ benchmarks! {
         assert_eq!(Cash::<T>::set_next_code_via_hash(RawOrigin::None.into(), new_code), Ok(()));
     }
 
-    // todo: parameterize over # vals?
     change_validators {
-        let substrate_id: SubstrateId = [2; 32].into();
-        let eth_address = [1; 20];
-        let val_keys = ValidatorKeys {
-            substrate_id: substrate_id.clone(),
-            eth_address: eth_address.clone(),
-        };
-        let val_keyses = vec![val_keys];
-        let val_account = ChainAccount::Gate(substrate_id.clone().into());
-
-        // Min balance needed for account existence, to set session keys
-        let min_amount = params::MIN_PRINCIPAL_GATE.amount_withdrawable().unwrap();
-        ChainCashPrincipals::insert(ChainId::Gate, min_amount);
-        assert_ok!(internal::lock::lock_cash_principal_internal::<T>(
-            val_account,
-            val_account,
-            min_amount
-        ));
-
-        // Set session key
-        assert_eq!(
-            pallet_session::Module::<T>::set_keys(
-                T::Origin::signed(substrate_id.into()),
-                <T>::Keys::default(),
-                vec![]
-            ),
-            Ok(())
-        );
+        let z in 1 .. T::MaxValidators::get();
+        let mut val_keyses = vec![];
+        for i in 0..z {
+            let substrate_id: SubstrateId = [i as u8 + 1; 32].into();
+            let val_keys = ValidatorKeys {
+                substrate_id: substrate_id.clone(),
+                eth_address: [i as u8 + 1; 20],
+            };
+            let val_account = ChainAccount::Gate(substrate_id.clone().into());
+
+            // Min balance needed for account existence, to set session keys
+            let min_amount = params::MIN_PRINCIPAL_GATE.amount_withdrawable().unwrap();
+            ChainCashPrincipals::insert(ChainId::Gate, min_amount);
+            assert_ok!(internal::lock::lock_cash_principal_internal::<T>(
+                val_account,
+                val_account,
+                min_amount
+            ));
+
+            // Set session key
+            assert_eq!(
+                pallet_session::Module::<T>::set_keys(
+                    T::Origin::signed(substrate_id.into()),
+                    <T>::Keys::default(),
+                    vec![]
+                ),
+                Ok(())
+            );
+            val_keyses.push(val_keys);
+        }
     }: {
         assert_eq!(Cash::<T>::change_validators(RawOrigin::Root.into(), val_keyses), Ok(()));
+    } verify {
+        let last: SubstrateId = [z as u8; 32].into();
+        assert!(NextValidators::contains_key(&last));
     }
 
     exec_trx_request_extract {
@@ -425,6 +805,148 @@ benchmarks! {
     }: {
         assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
     }
+
+    exec_trx_request_start_auction {
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let nonce: Nonce = 0u32.into();
+        let transfer_amt: i128 = MIN_TX_VALUE.try_into().unwrap();
+
+        // bob supply tkn, borrow more eth than it covers
+        endow_tkn::<T>(BOB_ADDRESS_BYTES, transfer_amt * 5, TKN_ADDR_BYTES);
+        endow_tkn::<T>(BOB_ADDRESS_BYTES, -transfer_amt * 5, ETH_BYTES);
+        assert_ok!(Cash::<T>::set_liquidation_auction(RawOrigin::Root.into(), Some(LiquidationAuctionConfig {
+            min_value: 0,
+            start_incentive: 0,
+            max_incentive: 1000,
+            ramp_blocks: 10,
+        })));
+
+        let raw_req: String = format!("(StartAuction Eth:{})", BOB_ADDRESS);
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+        let full_request: Vec<u8> = format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    } verify {
+        assert!(LiquidationAuctions::contains_key(ChainAccount::Eth(BOB_ADDRESS_BYTES)));
+    }
+
+    exec_trx_request_transfer_portfolio {
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let holder = ChainAccount::Eth(signer_vec);
+        let nonce: Nonce = 0u32.into();
+        let transfer_amt: i128 = MIN_TX_VALUE.try_into().unwrap();
+
+        // alice supplies tkn and borrows eth, bob accepts the debt against his own collateral
+        endow_tkn::<T>(signer_vec, transfer_amt * 5, TKN_ADDR_BYTES);
+        endow_tkn::<T>(signer_vec, -transfer_amt, ETH_BYTES);
+        endow_tkn::<T>(BOB_ADDRESS_BYTES, transfer_amt * 5, [2; 20]);
+        PortfolioConsents::insert(ChainAccount::Eth(BOB_ADDRESS_BYTES), holder);
+
+        let raw_req: String = format!("(TransferPortfolio Eth:{})", BOB_ADDRESS);
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+        let full_request: Vec<u8> = format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    }
+
+    exec_trx_request_accept_portfolio {
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let nonce: Nonce = 0u32.into();
+
+        let raw_req: String = format!("(AcceptPortfolio Eth:{})", BOB_ADDRESS);
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+        let full_request: Vec<u8> = format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    }
+
+    exec_trx_request_set_guardians {
+        let g in 1 .. (params::MAX_GUARDIANS as u32);
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let nonce: Nonce = 0u32.into();
+
+        let guardians: Vec<String> = (0..g).map(|i| format!("Eth:0x{:040x}", i + 1)).collect();
+        let raw_req: String = format!("(SetGuardians {} {})", g, guardians.join(" "));
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+        let full_request: Vec<u8> = format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    }
+
+    exec_trx_request_recover_account {
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let nonce: Nonce = 0u32.into();
+
+        // alice is the last guardian bob needs to recover, which queues the recovery
+        <pallet_timestamp::Now<T>>::put(1u64);
+        Guardians::insert(ChainAccount::Eth(BOB_ADDRESS_BYTES), (1, vec![ChainAccount::Eth(signer_vec)]));
+
+        let raw_req: String = format!("(RecoverAccount Eth:{} Eth:{})", BOB_ADDRESS, TKN_ADDR);
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+        let full_request: Vec<u8> = format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    }
+
+    exec_trx_request_cancel_recovery {
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let nonce: Nonce = 0u32.into();
+
+        QueuedRecoveries::insert(ChainAccount::Eth(signer_vec), (1u64, ChainAccount::Eth(BOB_ADDRESS_BYTES)));
+
+        let raw_req: String = String::from("(CancelRecovery)");
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+        let full_request: Vec<u8> = format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    }
+
+    exec_trx_request_convert {
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let nonce: Nonce = 0u32.into();
+        let transfer_amt: i128 = MIN_TX_VALUE.try_into().unwrap();
+        endow_tkn::<T>(signer_vec, transfer_amt * 5, TKN_ADDR_BYTES);
+
+        let eth_tkn = SupportedAssets::get(ChainAsset::Eth(TKN_ADDR_BYTES)).unwrap();
+        let matic_tkn = AssetInfo { asset: ChainAsset::Matic(TKN_ADDR_BYTES), ..eth_tkn };
+        assert_ok!(Cash::<T>::support_asset(RawOrigin::Root.into(), matic_tkn));
+        assert_ok!(Cash::<T>::set_asset_group(RawOrigin::Root.into(), eth_tkn.symbol, vec![eth_tkn.asset, matic_tkn.asset]));
+        StarportHoldings::insert(matic_tkn.asset, transfer_amt as AssetAmount);
+
+        let raw_req: String = format!("(Convert {} Eth:{} Matic:{})", transfer_amt, TKN_ADDR, TKN_ADDR);
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+        let full_request: Vec<u8> = format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    }
 }
 
 impl_benchmark_test_suite!(Cash, crate::tests::new_test_ext(), crate::tests::Test,);
@@ -443,19 +965,64 @@ mod tests {
             initialize_storage();
             assert_ok!(test_benchmark_on_initialize::<Test>());
             assert_ok!(test_benchmark_receive_chain_blocks::<Test>());
+            assert_ok!(test_benchmark_report_equivocation::<Test>());
+            assert_ok!(test_benchmark_set_validator_metadata::<Test>());
             assert_ok!(test_benchmark_receive_chain_reorg_pending::<Test>());
             assert_ok!(test_benchmark_publish_signature::<Test>());
             assert_ok!(test_benchmark_set_yield_next::<Test>());
+            assert_ok!(test_benchmark_set_peg_tolerance::<Test>());
             assert_ok!(test_benchmark_support_asset::<Test>());
+            assert_ok!(test_benchmark_set_asset_group::<Test>());
+            assert_ok!(test_benchmark_set_price_key::<Test>());
+            assert_ok!(test_benchmark_start_exodus::<Test>());
             assert_ok!(test_benchmark_set_rate_model::<Test>());
             assert_ok!(test_benchmark_set_liquidity_factor::<Test>());
+            assert_ok!(test_benchmark_set_reserve_factor::<Test>());
+            assert_ok!(test_benchmark_sweep_reserves::<Test>());
+            assert_ok!(test_benchmark_write_off_bad_debt::<Test>());
             assert_ok!(test_benchmark_set_supply_cap::<Test>());
+            assert_ok!(test_benchmark_set_borrow_cap::<Test>());
+            assert_ok!(test_benchmark_set_liquidation_incentive::<Test>());
+            assert_ok!(test_benchmark_set_close_factor::<Test>());
+            assert_ok!(test_benchmark_set_seize_paused::<Test>());
+            assert_ok!(test_benchmark_set_liquidation_auction::<Test>());
+            assert_ok!(test_benchmark_set_fee_rate::<Test>());
+            assert_ok!(test_benchmark_set_max_missed_signatures::<Test>());
+            assert_ok!(test_benchmark_set_quorum_threshold::<Test>());
+            assert_ok!(test_benchmark_set_governance_delay::<Test>());
+            assert_ok!(test_benchmark_cancel_governance::<Test>());
+            assert_ok!(test_benchmark_set_extraction_limit::<Test>());
+            assert_ok!(test_benchmark_set_extraction_batch_budget::<Test>());
+            assert_ok!(test_benchmark_discard_failed_event::<Test>());
+            assert_ok!(test_benchmark_set_event_proof_mode::<Test>());
+            assert_ok!(test_benchmark_set_chain_status::<Test>());
+            assert_ok!(test_benchmark_set_event_expiration::<Test>());
+            assert_ok!(test_benchmark_set_notice_retention::<Test>());
+            assert_ok!(test_benchmark_set_initialize_weight_budget::<Test>());
+            assert_ok!(test_benchmark_release_queued_governance::<Test>());
+            assert_ok!(test_benchmark_release_queued_recoveries::<Test>());
+            assert_ok!(test_benchmark_release_queued_extractions::<Test>());
+            assert_ok!(test_benchmark_dispatch_extraction_batches::<Test>());
+            assert_ok!(test_benchmark_expire_pending_blocks::<Test>());
+            assert_ok!(test_benchmark_retry_failed_events::<Test>());
+            assert_ok!(test_benchmark_process_exodus::<Test>());
+            assert_ok!(test_benchmark_track_cash_peg::<Test>());
+            assert_ok!(test_benchmark_record_rate_history::<Test>());
+            assert_ok!(test_benchmark_check_solvency::<Test>());
+            assert_ok!(test_benchmark_prune_notices::<Test>());
             assert_ok!(test_benchmark_allow_next_code_with_hash::<Test>());
             assert_ok!(test_benchmark_set_next_code_via_hash::<Test>());
             assert_ok!(test_benchmark_change_validators::<Test>());
             assert_ok!(test_benchmark_exec_trx_request_extract::<Test>());
             assert_ok!(test_benchmark_exec_trx_request_transfer::<Test>());
             assert_ok!(test_benchmark_exec_trx_request_liquidate::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_transfer_portfolio::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_accept_portfolio::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_set_guardians::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_recover_account::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_cancel_recovery::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_convert::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_start_auction::<Test>());
         });
     }
 }
@@ -0,0 +1,247 @@
+use codec::{Decode, Encode, Error, Input, Output};
+use frame_support::traits::Get;
+use our_std::{
+    collections::btree_set::BTreeSet, convert::TryFrom, fmt, marker::PhantomData, ops::Deref,
+    vec::Vec,
+};
+
+/// A vector with a maximum length, which is also enforced when decoding.
+/// Mirrors `BoundedVec` from newer versions of frame-support, which should replace it on upgrade.
+pub struct BoundedVec<T, S>(Vec<T>, PhantomData<S>);
+
+impl<T, S: Get<u32>> BoundedVec<T, S> {
+    /// Return the maximum length of the vector.
+    pub fn bound() -> usize {
+        S::get() as usize
+    }
+
+    /// Push an element, failing if the vector is already full.
+    pub fn try_push(&mut self, element: T) -> Result<(), ()> {
+        if self.0.len() < Self::bound() {
+            self.0.push(element);
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Retain only the elements satisfying the predicate, which can never exceed the bound.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.0.retain(f)
+    }
+
+    /// Consume the bounded vector, returning the inner vector.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, S: Get<u32>> TryFrom<Vec<T>> for BoundedVec<T, S> {
+    type Error = ();
+
+    fn try_from(elements: Vec<T>) -> Result<Self, Self::Error> {
+        if elements.len() <= Self::bound() {
+            Ok(BoundedVec(elements, PhantomData))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl<T, S> Default for BoundedVec<T, S> {
+    fn default() -> Self {
+        BoundedVec(Vec::new(), PhantomData)
+    }
+}
+
+impl<T: Clone, S> Clone for BoundedVec<T, S> {
+    fn clone(&self) -> Self {
+        BoundedVec(self.0.clone(), PhantomData)
+    }
+}
+
+impl<T: PartialEq, S> PartialEq for BoundedVec<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq, S> Eq for BoundedVec<T, S> {}
+
+impl<T: fmt::Debug, S> fmt::Debug for BoundedVec<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BoundedVec").field(&self.0).finish()
+    }
+}
+
+impl<T, S> Deref for BoundedVec<T, S> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Encode, S> Encode for BoundedVec<T, S> {
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<W: Output>(&self, dest: &mut W) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl<T: Decode, S: Get<u32>> Decode for BoundedVec<T, S> {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let elements = Vec::<T>::decode(input)?;
+        BoundedVec::try_from(elements).map_err(|_| "BoundedVec exceeds its limit".into())
+    }
+}
+
+/// A set with a maximum number of elements, which is also enforced when decoding.
+/// Mirrors `BoundedBTreeSet` from newer versions of frame-support, which should replace it on upgrade.
+pub struct BoundedBTreeSet<T, S>(BTreeSet<T>, PhantomData<S>);
+
+impl<T: Ord, S: Get<u32>> BoundedBTreeSet<T, S> {
+    /// Return the maximum number of elements in the set.
+    pub fn bound() -> usize {
+        S::get() as usize
+    }
+
+    /// Create an empty set.
+    pub fn new() -> Self {
+        BoundedBTreeSet(BTreeSet::new(), PhantomData)
+    }
+
+    /// Insert an element, failing if the set is already full and does not contain it.
+    /// Returns whether the element was newly inserted.
+    pub fn try_insert(&mut self, element: T) -> Result<bool, ()> {
+        if self.0.len() < Self::bound() || self.0.contains(&element) {
+            Ok(self.0.insert(element))
+        } else {
+            Err(())
+        }
+    }
+
+    /// Remove an element, returning whether it was present.
+    pub fn remove(&mut self, element: &T) -> bool {
+        self.0.remove(element)
+    }
+
+    /// Consume the bounded set, returning the inner set.
+    pub fn into_inner(self) -> BTreeSet<T> {
+        self.0
+    }
+}
+
+impl<T: Ord, S: Get<u32>> TryFrom<BTreeSet<T>> for BoundedBTreeSet<T, S> {
+    type Error = ();
+
+    fn try_from(elements: BTreeSet<T>) -> Result<Self, Self::Error> {
+        if elements.len() <= Self::bound() {
+            Ok(BoundedBTreeSet(elements, PhantomData))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl<T: Ord, S> Default for BoundedBTreeSet<T, S> {
+    fn default() -> Self {
+        BoundedBTreeSet(BTreeSet::new(), PhantomData)
+    }
+}
+
+impl<T: Clone, S> Clone for BoundedBTreeSet<T, S> {
+    fn clone(&self) -> Self {
+        BoundedBTreeSet(self.0.clone(), PhantomData)
+    }
+}
+
+impl<T: PartialEq, S> PartialEq for BoundedBTreeSet<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq, S> Eq for BoundedBTreeSet<T, S> {}
+
+impl<T: fmt::Debug, S> fmt::Debug for BoundedBTreeSet<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BoundedBTreeSet").field(&self.0).finish()
+    }
+}
+
+impl<T, S> Deref for BoundedBTreeSet<T, S> {
+    type Target = BTreeSet<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Encode, S> Encode for BoundedBTreeSet<T, S> {
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<W: Output>(&self, dest: &mut W) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl<T: Decode + Ord, S: Get<u32>> Decode for BoundedBTreeSet<T, S> {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let elements = BTreeSet::<T>::decode(input)?;
+        BoundedBTreeSet::try_from(elements).map_err(|_| "BoundedBTreeSet exceeds its limit".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_support::parameter_types;
+
+    parameter_types! {
+        pub const Three: u32 = 3;
+    }
+
+    #[test]
+    fn test_bounded_vec_try_push() {
+        let mut v = BoundedVec::<u8, Three>::try_from(vec![1, 2]).unwrap();
+        assert_eq!(v.try_push(3), Ok(()));
+        assert_eq!(v.try_push(4), Err(()));
+        assert_eq!(*v, vec![1, 2, 3]);
+        assert_eq!(BoundedVec::<u8, Three>::try_from(vec![1, 2, 3, 4]), Err(()));
+    }
+
+    #[test]
+    fn test_bounded_vec_codec() {
+        let v = BoundedVec::<u8, Three>::try_from(vec![1, 2, 3]).unwrap();
+        assert_eq!(v.encode(), vec![1u8, 2, 3].encode());
+        assert_eq!(
+            BoundedVec::<u8, Three>::decode(&mut &v.encode()[..]).ok(),
+            Some(v)
+        );
+        assert!(BoundedVec::<u8, Three>::decode(&mut &vec![1u8, 2, 3, 4].encode()[..]).is_err());
+    }
+
+    #[test]
+    fn test_bounded_btree_set_try_insert() {
+        let mut s = BoundedBTreeSet::<u8, Three>::new();
+        assert_eq!(s.try_insert(1), Ok(true));
+        assert_eq!(s.try_insert(2), Ok(true));
+        assert_eq!(s.try_insert(3), Ok(true));
+        assert_eq!(s.try_insert(3), Ok(false));
+        assert_eq!(s.try_insert(4), Err(()));
+        assert!(s.remove(&1));
+        assert_eq!(s.try_insert(4), Ok(true));
+    }
+
+    #[test]
+    fn test_bounded_btree_set_codec() {
+        let set: BTreeSet<u8> = vec![1, 2, 3, 4].into_iter().collect();
+        assert!(BoundedBTreeSet::<u8, Three>::decode(&mut &set.encode()[..]).is_err());
+    }
+}
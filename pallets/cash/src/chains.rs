@@ -1,39 +1,99 @@
 use codec::{Decode, Encode};
 use ethereum_client::{EthereumBlock, EthereumEvent, EthereumHash};
 use gateway_crypto::public_key_bytes_to_eth_address;
+use our_std::mem::size_of;
 use our_std::vec::Vec;
 use our_std::{
     collections::btree_set::BTreeSet, str::FromStr, vec, Debuggable, Deserialize, RuntimeDebug,
     Serialize,
 };
+use sp_core::crypto::AccountId32;
+use sp_runtime::{traits::Verify, MultiSignature, Permill};
 use types_derive::{type_alias, Types};
 
 use crate::{
     rates::APR,
-    reason::Reason,
-    types::{AssetAmount, CashIndex, SignersSet, Timestamp, ValidatorIdentity, ValidatorKeys},
+    reason::{MathError, Reason},
+    types::{
+        AssetAmount, BoundedVec, CashIndex, MaxValidators, SignersSet, Timestamp,
+        ValidatorIdentity, ValidatorKeys,
+    },
 };
 
 /// Used to reserve enum variant fields for future use.
 #[type_alias]
 type Reserved = ();
 
+/// Types with a bounded encoding, so they may be used as storage keys and bounded storage values.
+/// Mirrors `MaxEncodedLen` from newer versions of codec, which should replace it on upgrade.
+pub trait MaxEncodedLen: Encode {
+    fn max_encoded_len() -> usize;
+}
+
+/// Return the maximum encoded length of an enum with the given variant field sizes.
+fn max_encoded_variant_len(field_lens: &[usize]) -> usize {
+    1 + field_lens.iter().copied().max().unwrap_or(0)
+}
+
 /// Type for representing the selection of an underlying chain.
 #[derive(Serialize, Deserialize)] // used in config
-#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Encode, Decode, RuntimeDebug, Types)]
+#[derive(
+    Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Encode, Decode, Hash, RuntimeDebug, Types,
+)]
 pub enum ChainId {
+    #[codec(index = 0)]
     Gate,
+    #[codec(index = 1)]
     Eth,
+    #[codec(index = 2)]
     Dot,
+    #[codec(index = 3)]
     Matic,
+    #[cfg(feature = "runtime-dev")]
+    #[codec(index = 4)]
+    Mock,
+}
+
+impl MaxEncodedLen for ChainId {
+    fn max_encoded_len() -> usize {
+        max_encoded_variant_len(&[])
+    }
 }
 
 impl ChainId {
+    /// The tag which leads the encoding of this chain, and of every account and asset on it.
+    /// Tags are fixed once assigned, new chains must only ever be given new tags.
+    pub fn tag(self) -> u8 {
+        match self {
+            ChainId::Gate => 0,
+            ChainId::Eth => 1,
+            ChainId::Dot => 2,
+            ChainId::Matic => 3,
+            #[cfg(feature = "runtime-dev")]
+            ChainId::Mock => 4,
+        }
+    }
+
+    /// Return the chain for a tag, if it is one this runtime knows.
+    pub fn from_tag(tag: u8) -> Option<ChainId> {
+        match tag {
+            0 => Some(ChainId::Gate),
+            1 => Some(ChainId::Eth),
+            2 => Some(ChainId::Dot),
+            3 => Some(ChainId::Matic),
+            #[cfg(feature = "runtime-dev")]
+            4 => Some(ChainId::Mock),
+            _ => None,
+        }
+    }
+
     pub fn to_account(self, addr: &str) -> Result<ChainAccount, Reason> {
         match self {
             ChainId::Gate => Ok(ChainAccount::Gate(Gateway::str_to_address(addr)?)),
             ChainId::Eth => Ok(ChainAccount::Eth(Ethereum::str_to_address(addr)?)),
             ChainId::Matic => Ok(ChainAccount::Matic(Polygon::str_to_address(addr)?)),
+            #[cfg(feature = "runtime-dev")]
+            ChainId::Mock => Ok(ChainAccount::Mock(Mock::str_to_address(addr)?)),
             ChainId::Dot => Ok(ChainAccount::Dot(Polkadot::str_to_address(addr)?)),
         }
     }
@@ -43,6 +103,8 @@ impl ChainId {
             ChainId::Gate => Err(Reason::Unreachable),
             ChainId::Eth => Ok(ChainAsset::Eth(Ethereum::str_to_address(addr)?)),
             ChainId::Matic => Ok(ChainAsset::Matic(Polygon::str_to_address(addr)?)),
+            #[cfg(feature = "runtime-dev")]
+            ChainId::Mock => Ok(ChainAsset::Mock(Mock::str_to_address(addr)?)),
             ChainId::Dot => Err(Reason::NotImplemented),
         }
     }
@@ -52,6 +114,8 @@ impl ChainId {
             ChainId::Gate => Ok(ChainHash::Gate(Gateway::str_to_hash(hash)?)),
             ChainId::Eth => Ok(ChainHash::Eth(Ethereum::str_to_hash(hash)?)),
             ChainId::Matic => Ok(ChainHash::Matic(Polygon::str_to_hash(hash)?)),
+            #[cfg(feature = "runtime-dev")]
+            ChainId::Mock => Ok(ChainHash::Mock(Mock::str_to_hash(hash)?)),
             ChainId::Dot => Ok(ChainHash::Dot(Polkadot::str_to_hash(hash)?)),
         }
     }
@@ -61,6 +125,8 @@ impl ChainId {
             ChainId::Gate => Ok(ChainAccount::Gate(<Gateway as Chain>::signer_address()?)),
             ChainId::Eth => Ok(ChainAccount::Eth(<Ethereum as Chain>::signer_address()?)),
             ChainId::Matic => Ok(ChainAccount::Matic(<Polygon as Chain>::signer_address()?)),
+            #[cfg(feature = "runtime-dev")]
+            ChainId::Mock => Ok(ChainAccount::Mock(<Mock as Chain>::signer_address()?)),
             ChainId::Dot => Ok(ChainAccount::Dot(<Polkadot as Chain>::signer_address()?)),
         }
     }
@@ -70,10 +136,23 @@ impl ChainId {
             ChainId::Gate => ChainHash::Gate(<Gateway as Chain>::hash_bytes(data)),
             ChainId::Eth => ChainHash::Eth(<Ethereum as Chain>::hash_bytes(data)),
             ChainId::Matic => ChainHash::Matic(<Polygon as Chain>::hash_bytes(data)),
+            #[cfg(feature = "runtime-dev")]
+            ChainId::Mock => ChainHash::Mock(<Mock as Chain>::hash_bytes(data)),
             ChainId::Dot => ChainHash::Dot(<Polkadot as Chain>::hash_bytes(data)),
         }
     }
 
+    pub fn hash_notice(self, payload: &[u8]) -> ChainHash {
+        match self {
+            ChainId::Gate => ChainHash::Gate(<Gateway as Chain>::hash_notice(payload)),
+            ChainId::Eth => ChainHash::Eth(<Ethereum as Chain>::hash_notice(payload)),
+            ChainId::Matic => ChainHash::Matic(<Polygon as Chain>::hash_notice(payload)),
+            #[cfg(feature = "runtime-dev")]
+            ChainId::Mock => ChainHash::Mock(<Mock as Chain>::hash_notice(payload)),
+            ChainId::Dot => ChainHash::Dot(<Polkadot as Chain>::hash_notice(payload)),
+        }
+    }
+
     pub fn sign(self, message: &[u8]) -> Result<ChainSignature, Reason> {
         match self {
             ChainId::Gate => Ok(ChainSignature::Gate(<Gateway as Chain>::sign_message(
@@ -88,6 +167,21 @@ impl ChainId {
             ChainId::Dot => Ok(ChainSignature::Dot(<Polkadot as Chain>::sign_message(
                 message,
             )?)),
+            #[cfg(feature = "runtime-dev")]
+            ChainId::Mock => Ok(ChainSignature::Mock(<Mock as Chain>::sign_message(
+                message,
+            )?)),
+        }
+    }
+
+    pub fn normalize_timestamp(self, timestamp: u64) -> Result<Timestamp, Reason> {
+        match self {
+            ChainId::Gate => <Gateway as Chain>::normalize_timestamp(timestamp),
+            ChainId::Eth => <Ethereum as Chain>::normalize_timestamp(timestamp),
+            ChainId::Matic => <Polygon as Chain>::normalize_timestamp(timestamp),
+            ChainId::Dot => <Polkadot as Chain>::normalize_timestamp(timestamp),
+            #[cfg(feature = "runtime-dev")]
+            ChainId::Mock => <Mock as Chain>::normalize_timestamp(timestamp),
         }
     }
 
@@ -97,6 +191,8 @@ impl ChainId {
             ChainId::Eth => ChainHash::Eth(<Ethereum as Chain>::zero_hash()),
             ChainId::Matic => ChainHash::Matic(<Polygon as Chain>::zero_hash()),
             ChainId::Dot => ChainHash::Dot(<Polkadot as Chain>::zero_hash()),
+            #[cfg(feature = "runtime-dev")]
+            ChainId::Mock => ChainHash::Mock(<Mock as Chain>::zero_hash()),
         }
     }
 }
@@ -105,12 +201,34 @@ impl ChainId {
 pub type ChainStarport = ChainAccount;
 
 /// Type for an account tied to a chain.
-#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Encode, Decode, RuntimeDebug, Types)]
+#[derive(
+    Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Encode, Decode, Hash, RuntimeDebug, Types,
+)]
 pub enum ChainAccount {
+    #[codec(index = 0)]
     Gate(<Gateway as Chain>::Address),
+    #[codec(index = 1)]
     Eth(<Ethereum as Chain>::Address),
+    #[codec(index = 2)]
     Dot(<Polkadot as Chain>::Address),
+    #[codec(index = 3)]
     Matic(<Polygon as Chain>::Address),
+    #[cfg(feature = "runtime-dev")]
+    #[codec(index = 4)]
+    Mock(<Mock as Chain>::Address),
+}
+
+impl MaxEncodedLen for ChainAccount {
+    fn max_encoded_len() -> usize {
+        max_encoded_variant_len(&[
+            size_of::<<Gateway as Chain>::Address>(),
+            size_of::<<Ethereum as Chain>::Address>(),
+            size_of::<<Polkadot as Chain>::Address>(),
+            size_of::<<Polygon as Chain>::Address>(),
+            #[cfg(feature = "runtime-dev")]
+            size_of::<<Mock as Chain>::Address>(),
+        ])
+    }
 }
 
 impl ChainAccount {
@@ -119,9 +237,23 @@ impl ChainAccount {
             ChainAccount::Gate(_) => ChainId::Gate,
             ChainAccount::Eth(_) => ChainId::Eth,
             ChainAccount::Matic(_) => ChainId::Matic,
+            #[cfg(feature = "runtime-dev")]
+            ChainAccount::Mock(_) => ChainId::Mock,
             ChainAccount::Dot(_) => ChainId::Dot,
         }
     }
+
+    /// Check the account is a sensible destination for an extraction on its chain.
+    pub fn validate_recipient(&self) -> Result<(), Reason> {
+        match self {
+            ChainAccount::Gate(address) => Gateway::validate_recipient(address),
+            ChainAccount::Eth(address) => Ethereum::validate_recipient(address),
+            ChainAccount::Matic(address) => Polygon::validate_recipient(address),
+            #[cfg(feature = "runtime-dev")]
+            ChainAccount::Mock(address) => Mock::validate_recipient(address),
+            ChainAccount::Dot(address) => Polkadot::validate_recipient(address),
+        }
+    }
 }
 
 // Implement deserialization for ChainAccounts so we can use them in GenesisConfig / ChainSpec JSON.
@@ -146,18 +278,42 @@ impl From<ChainAccount> for String {
             ChainAccount::Gate(address) => format!("GATE:0x{}", hex::encode(address)),
             ChainAccount::Eth(address) => format!("ETH:0x{}", hex::encode(address)),
             ChainAccount::Matic(address) => format!("MATIC:0x{}", hex::encode(address)),
-            ChainAccount::Dot(_) => String::from("DOT"), // XXX
+            #[cfg(feature = "runtime-dev")]
+            ChainAccount::Mock(address) => format!("MOCK:0x{}", hex::encode(address)),
+            ChainAccount::Dot(address) => format!("DOT:{}", Polkadot::address_string(&address)),
         }
     }
 }
 
 /// Type for an asset tied to a chain.
-#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Encode, Decode, RuntimeDebug, Types)]
+#[derive(
+    Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Encode, Decode, Hash, RuntimeDebug, Types,
+)]
 pub enum ChainAsset {
+    #[codec(index = 0)]
     Gate(Reserved),
+    #[codec(index = 1)]
     Eth(<Ethereum as Chain>::Address),
+    #[codec(index = 2)]
     Dot(Reserved),
+    #[codec(index = 3)]
     Matic(<Polygon as Chain>::Address),
+    #[cfg(feature = "runtime-dev")]
+    #[codec(index = 4)]
+    Mock(<Mock as Chain>::Address),
+}
+
+impl MaxEncodedLen for ChainAsset {
+    fn max_encoded_len() -> usize {
+        max_encoded_variant_len(&[
+            size_of::<Reserved>(),
+            size_of::<<Ethereum as Chain>::Address>(),
+            size_of::<Reserved>(),
+            size_of::<<Polygon as Chain>::Address>(),
+            #[cfg(feature = "runtime-dev")]
+            size_of::<<Mock as Chain>::Address>(),
+        ])
+    }
 }
 
 // For serialize (which we don't really use, but are required to implement)
@@ -167,6 +323,8 @@ impl ChainAsset {
             ChainAsset::Gate(_) => ChainId::Gate,
             ChainAsset::Eth(_) => ChainId::Eth,
             ChainAsset::Matic(_) => ChainId::Matic,
+            #[cfg(feature = "runtime-dev")]
+            ChainAsset::Mock(_) => ChainId::Mock,
             ChainAsset::Dot(_) => ChainId::Dot,
         }
     }
@@ -193,11 +351,98 @@ impl From<ChainAsset> for String {
             ChainAsset::Gate(_) => String::from("GATE"), // XXX
             ChainAsset::Eth(address) => format!("ETH:0x{}", hex::encode(address)),
             ChainAsset::Matic(address) => format!("MATIC:0x{}", hex::encode(address)),
+            #[cfg(feature = "runtime-dev")]
+            ChainAsset::Mock(address) => format!("MOCK:0x{}", hex::encode(address)),
             ChainAsset::Dot(_) => String::from("DOT"), // XXX
         }
     }
 }
 
+/// Types whose encoding is led by the tag of the chain they are tied to.
+pub trait ChainTagged: Decode {}
+
+impl ChainTagged for ChainId {}
+impl ChainTagged for ChainAccount {}
+impl ChainTagged for ChainAsset {}
+
+/// Type for decoding a value tied to a chain, which may have been encoded by a runtime knowing of more chains.
+/// Values tied to a chain this runtime does not know are kept as their tag and the rest of their encoding,
+/// instead of failing to decode, and encode back to exactly the same bytes.
+/// As the size of an unknown value cannot be known, it must be the last thing in its input.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum MaybeKnownChain<T: ChainTagged> {
+    Known(T),
+    Unknown(u8, Vec<u8>),
+}
+
+impl<T: ChainTagged> MaybeKnownChain<T> {
+    /// Return the value, if it is tied to a chain this runtime knows.
+    pub fn known(self) -> Option<T> {
+        match self {
+            MaybeKnownChain::Known(value) => Some(value),
+            MaybeKnownChain::Unknown(_, _) => None,
+        }
+    }
+}
+
+impl<T: ChainTagged + Encode> Encode for MaybeKnownChain<T> {
+    fn encode_to<W: codec::Output + ?Sized>(&self, dest: &mut W) {
+        match self {
+            MaybeKnownChain::Known(value) => value.encode_to(dest),
+            MaybeKnownChain::Unknown(tag, bytes) => {
+                dest.push_byte(*tag);
+                dest.write(bytes);
+            }
+        }
+    }
+}
+
+impl<T: ChainTagged> Decode for MaybeKnownChain<T> {
+    fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let tag = input.read_byte()?;
+        if ChainId::from_tag(tag).is_some() {
+            let mut input = TaggedInput {
+                tag: Some(tag),
+                input,
+            };
+            return Ok(MaybeKnownChain::Known(T::decode(&mut input)?));
+        }
+
+        let len = input
+            .remaining_len()?
+            .ok_or("Cannot decode value tied to unknown chain from unbounded input")?;
+        let mut bytes = vec![0u8; len];
+        input.read(&mut bytes)?;
+        Ok(MaybeKnownChain::Unknown(tag, bytes))
+    }
+}
+
+/// Input which replays a tag already read, ahead of the rest of the input it was read from.
+struct TaggedInput<'a, I: codec::Input> {
+    tag: Option<u8>,
+    input: &'a mut I,
+}
+
+impl<'a, I: codec::Input> codec::Input for TaggedInput<'a, I> {
+    fn remaining_len(&mut self) -> Result<Option<usize>, codec::Error> {
+        Ok(self
+            .input
+            .remaining_len()?
+            .map(|len| len + self.tag.iter().count()))
+    }
+
+    fn read(&mut self, into: &mut [u8]) -> Result<(), codec::Error> {
+        match (self.tag, into.split_first_mut()) {
+            (Some(tag), Some((first, rest))) => {
+                self.tag = None;
+                *first = tag;
+                self.input.read(rest)
+            }
+            _ => self.input.read(into),
+        }
+    }
+}
+
 /// Type for a signature and account tied to a chain.
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum ChainAccountSignature {
@@ -205,6 +450,8 @@ pub enum ChainAccountSignature {
     Eth(<Ethereum as Chain>::Address, <Ethereum as Chain>::Signature),
     Dot(<Polkadot as Chain>::Address, <Polkadot as Chain>::Signature),
     Matic(<Polygon as Chain>::Address, <Polygon as Chain>::Signature),
+    #[cfg(feature = "runtime-dev")]
+    Mock(<Mock as Chain>::Address, <Mock as Chain>::Signature),
 }
 
 impl ChainAccountSignature {
@@ -213,33 +460,53 @@ impl ChainAccountSignature {
             ChainAccountSignature::Gate(_, sig) => ChainSignature::Gate(sig),
             ChainAccountSignature::Eth(_, sig) => ChainSignature::Eth(sig),
             ChainAccountSignature::Matic(_, sig) => ChainSignature::Matic(sig),
+            #[cfg(feature = "runtime-dev")]
+            ChainAccountSignature::Mock(_, sig) => ChainSignature::Mock(sig),
             ChainAccountSignature::Dot(_, sig) => ChainSignature::Dot(sig),
         }
     }
 
-    fn check_chain_signature<T: Chain>(
+    /// Return the account the signature claims to be from, without verifying it.
+    pub fn claimed_account(&self) -> ChainAccount {
+        match *self {
+            ChainAccountSignature::Gate(account, _) => Gateway::chain_account(account),
+            ChainAccountSignature::Eth(account, _) => Ethereum::chain_account(account),
+            ChainAccountSignature::Matic(account, _) => Polygon::chain_account(account),
+            #[cfg(feature = "runtime-dev")]
+            ChainAccountSignature::Mock(account, _) => Mock::chain_account(account),
+            ChainAccountSignature::Dot(account, _) => Polkadot::chain_account(account),
+        }
+    }
+
+    fn verify_chain_signature<T: Chain>(
         message: &[u8],
         account: T::Address,
         signature: T::Signature,
     ) -> Result<ChainAccount, Reason> {
-        let recovered = T::recover_user_address(message, signature)?;
-        if account == recovered {
-            Ok(T::chain_account(recovered))
-        } else {
-            Err(Reason::SignatureAccountMismatch)
-        }
+        Ok(T::chain_account(T::verify_user_address(
+            message, signature, account,
+        )?))
     }
 
-    pub fn recover_account(self, message: &[u8]) -> Result<ChainAccount, Reason> {
+    /// Verify the signature is from the claimed account, recovering the signer where possible.
+    pub fn verify_account(self, message: &[u8]) -> Result<ChainAccount, Reason> {
         match self {
-            ChainAccountSignature::Gate(_, _) => Err(Reason::Unreachable),
+            ChainAccountSignature::Gate(account, sig) => {
+                Self::verify_chain_signature::<Gateway>(message, account, sig)
+            }
             ChainAccountSignature::Eth(eth_account, eth_sig) => {
-                Self::check_chain_signature::<Ethereum>(message, eth_account, eth_sig)
+                Self::verify_chain_signature::<Ethereum>(message, eth_account, eth_sig)
             }
             ChainAccountSignature::Matic(account, sig) => {
-                Self::check_chain_signature::<Polygon>(message, account, sig)
+                Self::verify_chain_signature::<Polygon>(message, account, sig)
+            }
+            ChainAccountSignature::Dot(account, sig) => {
+                Self::verify_chain_signature::<Polkadot>(message, account, sig)
+            }
+            #[cfg(feature = "runtime-dev")]
+            ChainAccountSignature::Mock(account, sig) => {
+                Self::verify_chain_signature::<Mock>(message, account, sig)
             }
-            ChainAccountSignature::Dot(_, _) => Err(Reason::Unreachable),
         }
     }
 }
@@ -249,26 +516,52 @@ impl ChainAccountSignature {
 pub type ChainBlockNumber = u64;
 
 /// Type for a hash tied to a chain.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, Types)]
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, Hash, RuntimeDebug, Types,
+)]
 pub enum ChainHash {
     Gate(<Gateway as Chain>::Hash),
     Eth(<Ethereum as Chain>::Hash),
     Dot(<Polkadot as Chain>::Hash),
     Matic(<Polygon as Chain>::Hash),
+    #[cfg(feature = "runtime-dev")]
+    Mock(<Mock as Chain>::Hash),
 }
 
-// Display so we can format local storage keys.
-impl our_std::fmt::Display for ChainHash {
-    fn fmt(&self, f: &mut our_std::fmt::Formatter<'_>) -> our_std::fmt::Result {
+impl MaxEncodedLen for ChainHash {
+    fn max_encoded_len() -> usize {
+        max_encoded_variant_len(&[
+            size_of::<<Gateway as Chain>::Hash>(),
+            size_of::<<Ethereum as Chain>::Hash>(),
+            size_of::<<Polkadot as Chain>::Hash>(),
+            size_of::<<Polygon as Chain>::Hash>(),
+            #[cfg(feature = "runtime-dev")]
+            size_of::<<Mock as Chain>::Hash>(),
+        ])
+    }
+}
+
+impl ChainHash {
+    pub fn chain_id(&self) -> ChainId {
         match self {
-            ChainHash::Gate(gate_hash) => write!(f, "GATE#{:X?}", gate_hash),
-            ChainHash::Eth(eth_hash) => write!(f, "ETH#{:X?}", eth_hash),
-            ChainHash::Matic(hash) => write!(f, "MATIC#{:X?}", hash),
-            ChainHash::Dot(dot_hash) => write!(f, "DOT#{:X?}", dot_hash),
+            ChainHash::Gate(_) => ChainId::Gate,
+            ChainHash::Eth(_) => ChainId::Eth,
+            ChainHash::Matic(_) => ChainId::Matic,
+            #[cfg(feature = "runtime-dev")]
+            ChainHash::Mock(_) => ChainId::Mock,
+            ChainHash::Dot(_) => ChainId::Dot,
         }
     }
 }
 
+// Display so we can format local storage keys, and refer to hashes in RPCs and proposals.
+//  i.e. Eth(0x...) > "ETH:0x..."
+impl our_std::fmt::Display for ChainHash {
+    fn fmt(&self, f: &mut our_std::fmt::Formatter<'_>) -> our_std::fmt::Result {
+        write!(f, "{}", String::from(*self))
+    }
+}
+
 // Implement deserialization for ChainHashes so we can use them in GenesisConfig / ChainSpec JSON.
 //  i.e. "eth:0x..." <> Eth(0x...)
 impl FromStr for ChainHash {
@@ -279,7 +572,7 @@ impl FromStr for ChainHash {
             let chain_id = ChainId::from_str(chain_id_str)?;
             Ok(chain_id.to_hash(hash_str)?)
         } else {
-            Err(Reason::BadHash)
+            Err(Reason::BadHash(None))
         }
     }
 }
@@ -287,10 +580,12 @@ impl FromStr for ChainHash {
 impl From<ChainHash> for String {
     fn from(hash: ChainHash) -> String {
         match hash {
-            ChainHash::Gate(_) => format!("GATE"), // XXX
-            ChainHash::Eth(eth_hash) => <Ethereum as Chain>::hash_string(&eth_hash),
-            ChainHash::Matic(hash) => <Polygon as Chain>::hash_string(&hash),
-            ChainHash::Dot(_) => format!("DOT"), // XXX
+            ChainHash::Gate(hash) => format!("GATE:{}", <Gateway as Chain>::hash_string(&hash)),
+            ChainHash::Eth(hash) => format!("ETH:{}", <Ethereum as Chain>::hash_string(&hash)),
+            ChainHash::Matic(hash) => format!("MATIC:{}", <Polygon as Chain>::hash_string(&hash)),
+            ChainHash::Dot(hash) => format!("DOT:{}", <Polkadot as Chain>::hash_string(&hash)),
+            #[cfg(feature = "runtime-dev")]
+            ChainHash::Mock(hash) => format!("MOCK:{}", <Mock as Chain>::hash_string(&hash)),
         }
     }
 }
@@ -302,6 +597,8 @@ pub enum ChainSignature {
     Eth(<Ethereum as Chain>::Signature),
     Dot(<Polkadot as Chain>::Signature),
     Matic(<Polygon as Chain>::Signature),
+    #[cfg(feature = "runtime-dev")]
+    Mock(<Mock as Chain>::Signature),
 }
 
 impl ChainSignature {
@@ -310,6 +607,8 @@ impl ChainSignature {
             ChainSignature::Gate(_) => ChainId::Gate,
             ChainSignature::Eth(_) => ChainId::Eth,
             ChainSignature::Matic(_) => ChainId::Matic,
+            #[cfg(feature = "runtime-dev")]
+            ChainSignature::Mock(_) => ChainId::Mock,
             ChainSignature::Dot(_) => ChainId::Dot,
         }
     }
@@ -324,6 +623,10 @@ impl ChainSignature {
                 <Polygon as Chain>::recover_address(message, *sig)?,
             )),
             ChainSignature::Dot(_) => Err(Reason::Unreachable),
+            #[cfg(feature = "runtime-dev")]
+            ChainSignature::Mock(sig) => Ok(ChainAccount::Mock(<Mock as Chain>::recover_address(
+                message, *sig,
+            )?)),
         }
     }
 }
@@ -332,9 +635,11 @@ impl ChainSignature {
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum ChainSignatureList {
     Gate(Reserved),
-    Eth(Vec<(<Ethereum as Chain>::Address, <Ethereum as Chain>::Signature)>),
-    Dot(Vec<(<Polkadot as Chain>::Address, <Polkadot as Chain>::Signature)>),
-    Matic(Vec<(<Polygon as Chain>::Address, <Polygon as Chain>::Signature)>),
+    Eth(BoundedVec<(<Ethereum as Chain>::Address, <Ethereum as Chain>::Signature), MaxValidators>),
+    Dot(BoundedVec<(<Polkadot as Chain>::Address, <Polkadot as Chain>::Signature), MaxValidators>),
+    Matic(BoundedVec<(<Polygon as Chain>::Address, <Polygon as Chain>::Signature), MaxValidators>),
+    #[cfg(feature = "runtime-dev")]
+    Mock(BoundedVec<(<Mock as Chain>::Address, <Mock as Chain>::Signature), MaxValidators>),
 }
 
 impl ChainSignatureList {
@@ -346,6 +651,10 @@ impl ChainSignatureList {
             (ChainSignatureList::Matic(eth_signature_pairs), ChainAccount::Matic(eth_account)) => {
                 eth_signature_pairs.iter().any(|(s, _)| *s == eth_account)
             }
+            #[cfg(feature = "runtime-dev")]
+            (ChainSignatureList::Mock(signature_pairs), ChainAccount::Mock(account)) => {
+                signature_pairs.iter().any(|(s, _)| *s == account)
+            }
             _ => false,
         }
     }
@@ -354,6 +663,8 @@ impl ChainSignatureList {
         match chain_id {
             ChainId::Eth => self.has_signer(ChainAccount::Eth(validator.eth_address)),
             ChainId::Matic => self.has_signer(ChainAccount::Matic(validator.eth_address)),
+            #[cfg(feature = "runtime-dev")]
+            ChainId::Mock => self.has_signer(ChainAccount::Mock(validator.eth_address)),
             _ => false,
         }
     }
@@ -364,15 +675,47 @@ impl ChainSignatureList {
         validator: &ValidatorKeys,
     ) -> Result<(), Reason> {
         match (self, signature) {
-            (ChainSignatureList::Eth(eth_sig_list), ChainSignature::Eth(eth_sig)) => {
-                Ok(eth_sig_list.push((validator.eth_address, eth_sig.clone())))
-            }
+            (ChainSignatureList::Eth(eth_sig_list), ChainSignature::Eth(eth_sig)) => eth_sig_list
+                .try_push((validator.eth_address, eth_sig.clone()))
+                .map_err(|_| Reason::TooManySignatures),
             (ChainSignatureList::Matic(eth_sig_list), ChainSignature::Matic(eth_sig)) => {
-                Ok(eth_sig_list.push((validator.eth_address, eth_sig.clone())))
+                eth_sig_list
+                    .try_push((validator.eth_address, eth_sig.clone()))
+                    .map_err(|_| Reason::TooManySignatures)
             }
+            #[cfg(feature = "runtime-dev")]
+            (ChainSignatureList::Mock(sig_list), ChainSignature::Mock(sig)) => sig_list
+                .try_push((validator.eth_address, sig.clone()))
+                .map_err(|_| Reason::TooManySignatures),
             _ => Err(Reason::SignatureMismatch),
         }
     }
+
+    /// Drop the signatures of any signer which is not one of the validators.
+    /// Returns whether any signatures were dropped.
+    pub fn retain_validators(&mut self, validators: &[ValidatorKeys]) -> bool {
+        let is_validator = |signer: &[u8; 20]| validators.iter().any(|v| v.eth_address == *signer);
+        match self {
+            ChainSignatureList::Eth(pairs) | ChainSignatureList::Matic(pairs) => {
+                let len = pairs.len();
+                pairs.retain(|(signer, _)| is_validator(signer));
+                pairs.len() != len
+            }
+            #[cfg(feature = "runtime-dev")]
+            ChainSignatureList::Mock(pairs) => {
+                let len = pairs.len();
+                pairs.retain(|(signer, _)| is_validator(signer));
+                pairs.len() != len
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether any signature is from a signer which is not one of the validators.
+    /// After a change of authority, the starport no longer accepts signatures from the old validators.
+    pub fn has_stale_signers(&self, validators: &[ValidatorKeys]) -> bool {
+        self.clone().retain_validators(validators)
+    }
 }
 
 // Implement deserialization for ChainIds so we can use them in GenesisConfig / ChainSpec JSON.
@@ -385,17 +728,38 @@ impl FromStr for ChainId {
             "DOT" => Ok(ChainId::Dot),
             "GATE" => Ok(ChainId::Gate),
             "MATIC" => Ok(ChainId::Matic),
+            #[cfg(feature = "runtime-dev")]
+            "MOCK" => Ok(ChainId::Mock),
             _ => Err(Reason::BadChainId),
         }
     }
 }
 
+/// Type for the status of an underlying chain, as set by governance.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub enum ChainStatus {
+    /// Events are applied and notices are sent to the chain.
+    Active,
+    /// Events are applied, but nothing is extracted to the chain and no notices are sent to it.
+    IngestOnly,
+    /// Nothing is applied from, extracted to or sent to the chain, e.g. while its starport is compromised.
+    Halted,
+}
+
+impl Default for ChainStatus {
+    fn default() -> Self {
+        ChainStatus::Active
+    }
+}
+
 /// Type for describing a block coming from an underlying chain.
 #[derive(Serialize, Deserialize)] // used in config
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum ChainBlock {
     Eth(<Ethereum as Chain>::Block),
     Matic(<Polygon as Chain>::Block),
+    #[cfg(feature = "runtime-dev")]
+    Mock(<Mock as Chain>::Block),
 }
 
 impl ChainBlock {
@@ -403,6 +767,8 @@ impl ChainBlock {
         match self {
             ChainBlock::Eth(_) => ChainId::Eth,
             ChainBlock::Matic(_) => ChainId::Matic,
+            #[cfg(feature = "runtime-dev")]
+            ChainBlock::Mock(_) => ChainId::Mock,
         }
     }
 
@@ -410,6 +776,8 @@ impl ChainBlock {
         match self {
             ChainBlock::Eth(block) => ChainHash::Eth(block.hash),
             ChainBlock::Matic(block) => ChainHash::Matic(block.hash),
+            #[cfg(feature = "runtime-dev")]
+            ChainBlock::Mock(block) => ChainHash::Mock(block.hash),
         }
     }
 
@@ -417,6 +785,8 @@ impl ChainBlock {
         match self {
             ChainBlock::Eth(block) => ChainHash::Eth(block.parent_hash),
             ChainBlock::Matic(block) => ChainHash::Matic(block.parent_hash),
+            #[cfg(feature = "runtime-dev")]
+            ChainBlock::Mock(block) => ChainHash::Mock(block.parent_hash),
         }
     }
 
@@ -424,6 +794,8 @@ impl ChainBlock {
         match self {
             ChainBlock::Eth(block) => block.number,
             ChainBlock::Matic(block) => block.number,
+            #[cfg(feature = "runtime-dev")]
+            ChainBlock::Mock(block) => block.number,
         }
     }
 
@@ -445,6 +817,8 @@ impl ChainBlock {
         match self {
             ChainBlock::Eth(block) => Self::map_events_eth_like(block, ChainBlockEvent::Eth),
             ChainBlock::Matic(block) => Self::map_events_eth_like(block, ChainBlockEvent::Matic),
+            #[cfg(feature = "runtime-dev")]
+            ChainBlock::Mock(block) => Self::map_events_eth_like(block, ChainBlockEvent::Mock),
         }
     }
 
@@ -458,6 +832,12 @@ impl ChainBlock {
             }
             (ChainBlock::Matic(_), ChainBlocks::Eth(_)) => Err(Reason::InvalidChainBlock),
             (ChainBlock::Eth(_), ChainBlocks::Matic(_)) => Err(Reason::InvalidChainBlock),
+            #[cfg(feature = "runtime-dev")]
+            (ChainBlock::Mock(block), ChainBlocks::Mock(blocks)) => {
+                Ok(ChainBlocks::Mock([vec![block], blocks].concat()))
+            }
+            #[cfg(feature = "runtime-dev")]
+            _ => Err(Reason::InvalidChainBlock),
         }
     }
 }
@@ -467,6 +847,8 @@ impl ChainBlock {
 pub enum ChainBlocks {
     Eth(Vec<<Ethereum as Chain>::Block>),
     Matic(Vec<<Polygon as Chain>::Block>),
+    #[cfg(feature = "runtime-dev")]
+    Mock(Vec<<Mock as Chain>::Block>),
 }
 
 impl ChainBlocks {
@@ -474,6 +856,8 @@ impl ChainBlocks {
         match self {
             ChainBlocks::Eth(_) => ChainId::Eth,
             ChainBlocks::Matic(_) => ChainId::Matic,
+            #[cfg(feature = "runtime-dev")]
+            ChainBlocks::Mock(_) => ChainId::Mock,
         }
     }
 
@@ -481,6 +865,8 @@ impl ChainBlocks {
         match self {
             ChainBlocks::Eth(blocks) => blocks.len(),
             ChainBlocks::Matic(blocks) => blocks.len(),
+            #[cfg(feature = "runtime-dev")]
+            ChainBlocks::Mock(blocks) => blocks.len(),
         }
     }
 
@@ -498,6 +884,8 @@ impl ChainBlocks {
         match self {
             ChainBlocks::Eth(blocks) => Self::blocks_eth_like(blocks, ChainBlock::Eth),
             ChainBlocks::Matic(blocks) => Self::blocks_eth_like(blocks, ChainBlock::Matic),
+            #[cfg(feature = "runtime-dev")]
+            ChainBlocks::Mock(blocks) => Self::blocks_eth_like(blocks, ChainBlock::Mock),
         }
     }
 
@@ -509,6 +897,8 @@ impl ChainBlocks {
         match self {
             ChainBlocks::Eth(blocks) => Self::blocks_numbers_eth_like(blocks),
             ChainBlocks::Matic(blocks) => Self::blocks_numbers_eth_like(blocks),
+            #[cfg(feature = "runtime-dev")]
+            ChainBlocks::Mock(blocks) => Self::blocks_numbers_eth_like(blocks),
         }
     }
 
@@ -550,6 +940,43 @@ impl ChainBlocks {
                 blocks,
                 ChainHash::Matic,
             )),
+            #[cfg(feature = "runtime-dev")]
+            ChainBlocks::Mock(blocks) => ChainBlocks::Mock(Self::filter_already_signed_eth_like(
+                signer,
+                pending_blocks,
+                blocks,
+                ChainHash::Mock,
+            )),
+        }
+    }
+
+    fn limit_events_eth_like(blocks: Vec<EthereumBlock>, max_events: u64) -> Vec<EthereumBlock> {
+        let mut num_events: u64 = 0;
+        blocks
+            .into_iter()
+            .enumerate()
+            .take_while(|(i, block)| {
+                num_events = num_events.saturating_add(block.events.len() as u64);
+                *i == 0 || num_events <= max_events
+            })
+            .map(|(_, block)| block)
+            .collect()
+    }
+
+    /// Keep only as many whole blocks from the front as fit within the given number of events.
+    /// The first block is always kept, so that a single large block cannot stall progress.
+    pub fn limit_events(self, max_events: u64) -> Self {
+        match self {
+            ChainBlocks::Eth(blocks) => {
+                ChainBlocks::Eth(Self::limit_events_eth_like(blocks, max_events))
+            }
+            ChainBlocks::Matic(blocks) => {
+                ChainBlocks::Matic(Self::limit_events_eth_like(blocks, max_events))
+            }
+            #[cfg(feature = "runtime-dev")]
+            ChainBlocks::Mock(blocks) => {
+                ChainBlocks::Mock(Self::limit_events_eth_like(blocks, max_events))
+            }
         }
     }
 }
@@ -559,6 +986,8 @@ impl From<ChainBlock> for ChainBlocks {
         match block {
             ChainBlock::Eth(block) => ChainBlocks::Eth(vec![block]),
             ChainBlock::Matic(block) => ChainBlocks::Matic(vec![block]),
+            #[cfg(feature = "runtime-dev")]
+            ChainBlock::Mock(block) => ChainBlocks::Mock(vec![block]),
         }
     }
 }
@@ -629,11 +1058,19 @@ impl ChainReorg {
     }
 }
 
-/// Calculate whether the signers have a super majority of the given validator set.
-pub fn has_super_majority<T: Ord>(signers: &BTreeSet<T>, validator_set: &BTreeSet<T>) -> bool {
-    // using ⌈j/m⌉ = ⌊(j+m-1)/m⌋
+/// Calculate whether the signers meet the quorum threshold of the given validator set.
+pub fn has_super_majority<T: Ord>(
+    signers: &BTreeSet<T>,
+    validator_set: &BTreeSet<T>,
+    threshold: Permill,
+) -> bool {
     let valid_signers: Vec<_> = validator_set.intersection(&signers).collect();
-    valid_signers.len() >= (2 * validator_set.len() + 3 - 1) / 3
+    valid_signers.len() >= super_majority(validator_set.len(), threshold)
+}
+
+/// Return the number of signers needed to meet the quorum threshold of the given number of validators.
+pub fn super_majority(num_validators: usize, threshold: Permill) -> usize {
+    threshold.mul_ceil(num_validators as u64) as usize
 }
 
 /// Type for tallying signatures for an underlying chain block.
@@ -645,30 +1082,38 @@ pub struct ChainBlockTally {
 }
 
 impl ChainBlockTally {
-    pub fn new(block: ChainBlock, validator: &ValidatorKeys) -> ChainBlockTally {
-        ChainBlockTally {
+    pub fn new(block: ChainBlock, validator: &ValidatorKeys) -> Result<ChainBlockTally, Reason> {
+        let mut tally = ChainBlockTally {
             block,
-            support: [validator.substrate_id.clone()].iter().cloned().collect(),
+            support: SignersSet::new(),
             dissent: SignersSet::new(),
-        }
+        };
+        tally.add_support(validator)?;
+        Ok(tally)
     }
 
-    pub fn add_support(&mut self, validator: &ValidatorKeys) {
-        self.support.insert(validator.substrate_id.clone());
+    pub fn add_support(&mut self, validator: &ValidatorKeys) -> Result<(), Reason> {
+        self.support
+            .try_insert(validator.substrate_id.clone())
+            .map_err(|_| Reason::TooManySignatures)?;
         self.dissent.remove(&validator.substrate_id);
+        Ok(())
     }
 
-    pub fn add_dissent(&mut self, validator: &ValidatorKeys) {
-        self.dissent.insert(validator.substrate_id.clone());
+    pub fn add_dissent(&mut self, validator: &ValidatorKeys) -> Result<(), Reason> {
+        self.dissent
+            .try_insert(validator.substrate_id.clone())
+            .map_err(|_| Reason::TooManySignatures)?;
         self.support.remove(&validator.substrate_id);
+        Ok(())
     }
 
-    pub fn has_enough_support(&self, validator_set: &SignersSet) -> bool {
-        has_super_majority(&self.support, validator_set)
+    pub fn has_enough_support(&self, validator_set: &SignersSet, threshold: Permill) -> bool {
+        has_super_majority(&self.support, validator_set, threshold)
     }
 
-    pub fn has_enough_dissent(&self, validator_set: &SignersSet) -> bool {
-        has_super_majority(&self.dissent, validator_set)
+    pub fn has_enough_dissent(&self, validator_set: &SignersSet, threshold: Permill) -> bool {
+        has_super_majority(&self.dissent, validator_set, threshold)
     }
 
     pub fn has_supporter(&self, validator_id: &ValidatorIdentity) -> bool {
@@ -685,33 +1130,45 @@ pub struct ChainReorgTally {
 }
 
 impl ChainReorgTally {
-    pub fn new(chain_id: ChainId, reorg: ChainReorg, validator: &ValidatorKeys) -> ChainReorgTally {
+    pub fn new(
+        chain_id: ChainId,
+        reorg: ChainReorg,
+        validator: &ValidatorKeys,
+    ) -> Result<ChainReorgTally, Reason> {
         match chain_id {
-            ChainId::Eth | ChainId::Matic => ChainReorgTally {
-                reorg,
-                support: [validator.substrate_id.clone()].iter().cloned().collect(),
-            },
+            ChainId::Eth | ChainId::Matic => {
+                let mut tally = ChainReorgTally {
+                    reorg,
+                    support: SignersSet::new(),
+                };
+                tally.add_support(validator)?;
+                Ok(tally)
+            }
 
             _ => panic!("xxx not implemented"),
         }
     }
 
-    pub fn add_support(&mut self, validator: &ValidatorKeys) {
-        self.support.insert(validator.substrate_id.clone());
+    pub fn add_support(&mut self, validator: &ValidatorKeys) -> Result<(), Reason> {
+        self.support
+            .try_insert(validator.substrate_id.clone())
+            .map_err(|_| Reason::TooManySignatures)?;
+        Ok(())
     }
 
     pub fn would_have_enough_support(
         &self,
         validator_set: &SignersSet,
         validator: &ValidatorKeys,
+        threshold: Permill,
     ) -> bool {
-        let mut hypothetical_signers = self.support.clone();
+        let mut hypothetical_signers = self.support.clone().into_inner();
         hypothetical_signers.insert(validator.substrate_id.clone());
-        has_super_majority(&hypothetical_signers, validator_set)
+        has_super_majority(&hypothetical_signers, validator_set, threshold)
     }
 
-    pub fn has_enough_support(&self, validator_set: &SignersSet) -> bool {
-        has_super_majority(&self.support, validator_set)
+    pub fn has_enough_support(&self, validator_set: &SignersSet, threshold: Permill) -> bool {
+        has_super_majority(&self.support, validator_set, threshold)
     }
 
     pub fn has_signer(&self, validator_id: &ValidatorIdentity) -> bool {
@@ -725,6 +1182,8 @@ pub enum ChainBlockEvent {
     Reserved,
     Eth(ChainBlockNumber, <Ethereum as Chain>::Event),
     Matic(ChainBlockNumber, <Polygon as Chain>::Event),
+    #[cfg(feature = "runtime-dev")]
+    Mock(ChainBlockNumber, <Mock as Chain>::Event),
 }
 
 impl ChainBlockEvent {
@@ -733,6 +1192,8 @@ impl ChainBlockEvent {
             ChainBlockEvent::Reserved => panic!("reserved"),
             ChainBlockEvent::Eth(..) => ChainId::Eth,
             ChainBlockEvent::Matic(..) => ChainId::Matic,
+            #[cfg(feature = "runtime-dev")]
+            ChainBlockEvent::Mock(..) => ChainId::Mock,
         }
     }
 
@@ -741,6 +1202,8 @@ impl ChainBlockEvent {
             ChainBlockEvent::Reserved => panic!("reserved"),
             ChainBlockEvent::Eth(block_num, _) => *block_num,
             ChainBlockEvent::Matic(block_num, _) => *block_num,
+            #[cfg(feature = "runtime-dev")]
+            ChainBlockEvent::Mock(block_num, _) => *block_num,
         }
     }
 
@@ -749,12 +1212,23 @@ impl ChainBlockEvent {
     }
 }
 
+/// Type for recording an event which failed to apply, so that it may be retried later.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct FailedChainBlockEvent {
+    pub event: ChainBlockEvent,
+    pub reason: Reason,
+    pub retries: u32,
+    pub next_retry_block: u64,
+}
+
 /// Type for describing a set of events coming from an underlying chain.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum ChainBlockEvents {
     Reserved,
     Eth(Vec<(ChainBlockNumber, <Ethereum as Chain>::Event)>),
     Matic(Vec<(ChainBlockNumber, <Polygon as Chain>::Event)>),
+    #[cfg(feature = "runtime-dev")]
+    Mock(Vec<(ChainBlockNumber, <Mock as Chain>::Event)>),
 }
 
 impl ChainBlockEvents {
@@ -765,6 +1239,8 @@ impl ChainBlockEvents {
             ChainId::Eth => Ok(ChainBlockEvents::Eth(vec![])),
             ChainId::Matic => Ok(ChainBlockEvents::Matic(vec![])),
             ChainId::Dot => Err(Reason::NotImplemented),
+            #[cfg(feature = "runtime-dev")]
+            ChainId::Mock => Ok(ChainBlockEvents::Mock(vec![])),
         }
     }
 
@@ -774,6 +1250,8 @@ impl ChainBlockEvents {
             ChainBlockEvents::Reserved => panic!("reserved"),
             ChainBlockEvents::Eth(eth_block_events) => eth_block_events.len(),
             ChainBlockEvents::Matic(block_events) => block_events.len(),
+            #[cfg(feature = "runtime-dev")]
+            ChainBlockEvents::Mock(block_events) => block_events.len(),
         }
     }
 
@@ -802,6 +1280,13 @@ impl ChainBlockEvents {
                 }
                 _ => panic!("block type mismatch"),
             },
+            #[cfg(feature = "runtime-dev")]
+            ChainBlockEvents::Mock(block_events) => match block {
+                ChainBlock::Mock(mock_block) => {
+                    Self::push_eth_events(block_events, mock_block);
+                }
+                _ => panic!("block type mismatch"),
+            },
         }
     }
 
@@ -815,11 +1300,15 @@ impl ChainBlockEvents {
             ChainBlockEvents::Reserved => panic!("reserved"),
             ChainBlockEvents::Eth(_) => ChainBlockEvent::Eth,
             ChainBlockEvents::Matic(_) => ChainBlockEvent::Matic,
+            #[cfg(feature = "runtime-dev")]
+            ChainBlockEvents::Mock(_) => ChainBlockEvent::Mock,
         };
         let eth_block_events = match self {
             ChainBlockEvents::Reserved => panic!("reserved"), // for exhaustive match - really just making the compiler happy here
             ChainBlockEvents::Eth(e) => e,
             ChainBlockEvents::Matic(e) => e,
+            #[cfg(feature = "runtime-dev")]
+            ChainBlockEvents::Mock(e) => e,
         };
 
         eth_block_events.retain(|(b, e)| f(&chain_block_event_fn(*b, e.clone())));
@@ -842,6 +1331,13 @@ impl ChainBlockEvents {
                 }
                 _ => panic!("unreachable"),
             },
+            #[cfg(feature = "runtime-dev")]
+            ChainBlockEvents::Mock(eth_block_events) => match event {
+                ChainBlockEvent::Mock(block_num, eth_block) => {
+                    (eth_block_events, block_num, eth_block)
+                }
+                _ => panic!("unreachable"),
+            },
         };
 
         eth_block_events
@@ -855,12 +1351,84 @@ impl ChainBlockEvents {
             ChainBlockEvents::Reserved => panic!("reserved"),
             ChainBlockEvents::Eth(eth_block_events) => eth_block_events,
             ChainBlockEvents::Matic(eth_block_events) => eth_block_events,
+            #[cfg(feature = "runtime-dev")]
+            ChainBlockEvents::Mock(eth_block_events) => eth_block_events,
         };
 
         eth_block_events.remove(pos);
     }
 }
 
+/// The context prepended to an SS58 payload, when computing its checksum.
+const SS58_CHECKSUM_CONTEXT: &[u8] = b"SS58PRE";
+
+/// Type for the network an SS58 address is encoded for, identified by its address prefix.
+#[derive(
+    Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Encode, Decode, Hash, RuntimeDebug, Types,
+)]
+pub enum Ss58Network {
+    Polkadot,
+    Kusama,
+    Substrate,
+}
+
+impl Ss58Network {
+    pub const fn prefix(self) -> u8 {
+        match self {
+            Ss58Network::Polkadot => 0,
+            Ss58Network::Kusama => 2,
+            Ss58Network::Substrate => 42,
+        }
+    }
+
+    pub fn from_prefix(prefix: u8) -> Option<Self> {
+        match prefix {
+            0 => Some(Ss58Network::Polkadot),
+            2 => Some(Ss58Network::Kusama),
+            42 => Some(Ss58Network::Substrate),
+            _ => None,
+        }
+    }
+}
+
+/// Type for a substrate account key, along with the network its address was encoded for.
+/// The network is recorded so that other relay chains may be told apart without re-parsing addresses.
+#[derive(
+    Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Encode, Decode, Hash, RuntimeDebug, Types,
+)]
+pub struct Ss58Address {
+    pub network: Ss58Network,
+    pub key: [u8; 32],
+}
+
+impl From<Ss58Address> for Vec<u8> {
+    fn from(address: Ss58Address) -> Vec<u8> {
+        address.key.to_vec()
+    }
+}
+
+/// Return the SS58 checksum of a prefixed key.
+fn ss58_checksum(payload: &[u8]) -> [u8; 2] {
+    let hash = sp_io::hashing::blake2_512(&[SS58_CHECKSUM_CONTEXT, payload].concat());
+    [hash[0], hash[1]]
+}
+
+/// Verify a SCALE-encoded ed25519 or sr25519 `MultiSignature` against a substrate address.
+/// These schemes do not allow recovering the signer, so the claimed address must be checked.
+fn verify_substrate_address(
+    data: &[u8],
+    signature: [u8; 65],
+    address: [u8; 32],
+) -> Result<[u8; 32], Reason> {
+    let multi_signature =
+        MultiSignature::decode(&mut &signature[..]).map_err(|_| Reason::SignatureMismatch)?;
+    if multi_signature.verify(data, &AccountId32::new(address)) {
+        Ok(address)
+    } else {
+        Err(Reason::SignatureAccountMismatch)
+    }
+}
+
 pub trait Chain {
     const ID: ChainId;
 
@@ -868,6 +1436,7 @@ pub trait Chain {
     type Amount: Debuggable + Clone + Eq + Into<AssetAmount>;
     type CashIndex: Debuggable + Clone + Eq + Into<CashIndex>;
     type Rate: Debuggable + Clone + Eq + Into<APR>;
+    /// A time as reported by the chain, in its own units, see `normalize_timestamp`.
     type Timestamp: Debuggable + Clone + Eq + Into<Timestamp>;
     type Hash: Debuggable + Clone + Eq;
     type PublicKey: Debuggable + Clone + Eq;
@@ -877,10 +1446,18 @@ pub trait Chain {
 
     fn zero_hash() -> Self::Hash;
     fn hash_bytes(data: &[u8]) -> Self::Hash;
+    /// Hash an encoded notice exactly as the chain does when verifying it.
+    /// The hash identifies the notice, both in storage and as the parent of the next notice.
+    fn hash_notice(payload: &[u8]) -> Self::Hash;
     fn recover_user_address(
         data: &[u8],
         signature: Self::Signature,
     ) -> Result<Self::Address, Reason>;
+    fn verify_user_address(
+        data: &[u8],
+        signature: Self::Signature,
+        address: Self::Address,
+    ) -> Result<Self::Address, Reason>;
     fn recover_address(data: &[u8], signature: Self::Signature) -> Result<Self::Address, Reason>;
     fn sign_message(message: &[u8]) -> Result<Self::Signature, Reason>;
     fn signer_address() -> Result<Self::Address, Reason>;
@@ -888,8 +1465,14 @@ pub trait Chain {
     fn address_string(address: &Self::Address) -> String;
     fn str_to_hash(hash: &str) -> Result<Self::Hash, Reason>;
     fn hash_string(hash: &Self::Hash) -> String;
+    fn normalize_timestamp(timestamp: Self::Timestamp) -> Result<Timestamp, Reason>;
     fn chain_account(address: Self::Address) -> ChainAccount;
     fn chain_block(block: Self::Block) -> ChainBlock;
+
+    /// Reject a destination which could never release what is extracted to it.
+    fn validate_recipient(_address: &Self::Address) -> Result<(), Reason> {
+        Ok(())
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
@@ -904,6 +1487,13 @@ pub struct Polygon {}
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
 pub struct Polkadot {}
 
+/// A developer chain which needs no RPC, for exercising quorum, notices and events locally.
+/// Blocks are synthesized deterministically, and a signature is just the signer address followed
+///  by a hash committing to the message, so it is trivially verifiable (and trivially forgeable).
+#[cfg(feature = "runtime-dev")]
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct Mock {}
+
 impl Chain for Gateway {
     const ID: ChainId = ChainId::Gate;
 
@@ -950,6 +1540,10 @@ impl Chain for Gateway {
         hash
     }
 
+    fn hash_notice(payload: &[u8]) -> Self::Hash {
+        Self::hash_bytes(payload)
+    }
+
     fn recover_user_address(
         _data: &[u8],
         _signature: Self::Signature,
@@ -957,6 +1551,14 @@ impl Chain for Gateway {
         panic!("XXX not implemented");
     }
 
+    fn verify_user_address(
+        data: &[u8],
+        signature: Self::Signature,
+        address: Self::Address,
+    ) -> Result<Self::Address, Reason> {
+        verify_substrate_address(data, signature, address)
+    }
+
     fn recover_address(_data: &[u8], _signature: Self::Signature) -> Result<Self::Address, Reason> {
         panic!("XXX not implemented");
     }
@@ -972,20 +1574,28 @@ impl Chain for Gateway {
     fn str_to_address(_addr: &str) -> Result<Self::Address, Reason> {
         match gateway_crypto::gateway_str_to_address(_addr) {
             Some(s) => Ok(s),
-            None => Err(Reason::BadAddress),
+            None => Err(Reason::BadAddress(Self::ID)),
         }
     }
 
-    fn address_string(_address: &Self::Address) -> String {
-        panic!("XXX not implemented");
+    fn address_string(address: &Self::Address) -> String {
+        format!("0x{}", hex::encode(address))
     }
 
-    fn str_to_hash(_hash: &str) -> Result<Self::Hash, Reason> {
-        panic!("XXX not implemented");
+    fn str_to_hash(hash: &str) -> Result<Self::Hash, Reason> {
+        match gateway_crypto::eth_str_to_hash(hash) {
+            Some(s) => Ok(s),
+            None => Err(Reason::BadHash(Some(Self::ID))),
+        }
     }
 
-    fn hash_string(_hash: &Self::Hash) -> String {
-        panic!("XXX not implemented");
+    fn hash_string(hash: &Self::Hash) -> String {
+        format!("0x{}", hex::encode(hash))
+    }
+
+    // Note: Gateway timestamps are already in milliseconds
+    fn normalize_timestamp(timestamp: Self::Timestamp) -> Result<Timestamp, Reason> {
+        Ok(timestamp)
     }
 
     fn chain_account(address: Self::Address) -> ChainAccount {
@@ -1043,6 +1653,12 @@ impl Chain for Ethereum {
         hash
     }
 
+    /// The Starport hashes the ABI encoded notice with keccak, and recovers signers from that digest.
+    /// Unlike the messages users sign, there is no EIP-191 preamble, as validators sign the raw notice.
+    fn hash_notice(payload: &[u8]) -> Self::Hash {
+        Self::hash_bytes(payload)
+    }
+
     fn recover_user_address(
         data: &[u8],
         signature: Self::Signature,
@@ -1054,6 +1670,19 @@ impl Chain for Ethereum {
         )?)
     }
 
+    fn verify_user_address(
+        data: &[u8],
+        signature: Self::Signature,
+        address: Self::Address,
+    ) -> Result<Self::Address, Reason> {
+        let recovered = Self::recover_user_address(data, signature)?;
+        if recovered == address {
+            Ok(recovered)
+        } else {
+            Err(Reason::SignatureAccountMismatch)
+        }
+    }
+
     fn recover_address(data: &[u8], signature: Self::Signature) -> Result<Self::Address, Reason> {
         Ok(runtime_interfaces::keyring_interface::eth_recover(
             data.into(),
@@ -1081,7 +1710,7 @@ impl Chain for Ethereum {
     fn str_to_address(addr: &str) -> Result<Self::Address, Reason> {
         match gateway_crypto::eth_str_to_address(addr) {
             Some(s) => Ok(s),
-            None => Err(Reason::BadAddress),
+            None => Err(Reason::BadAddress(Self::ID)),
         }
     }
 
@@ -1092,7 +1721,7 @@ impl Chain for Ethereum {
     fn str_to_hash(hash: &str) -> Result<Self::Hash, Reason> {
         match gateway_crypto::eth_str_to_hash(hash) {
             Some(s) => Ok(s),
-            None => Err(Reason::BadHash),
+            None => Err(Reason::BadHash(Some(Self::ID))),
         }
     }
 
@@ -1100,6 +1729,11 @@ impl Chain for Ethereum {
         gateway_crypto::eth_hash_string(hash)
     }
 
+    // Note: Ethereum timestamps are in seconds
+    fn normalize_timestamp(timestamp: Self::Timestamp) -> Result<Timestamp, Reason> {
+        Ok(timestamp.checked_mul(1000).ok_or(MathError::Overflow)?)
+    }
+
     fn chain_account(address: Self::Address) -> ChainAccount {
         ChainAccount::Eth(address)
     }
@@ -1107,6 +1741,15 @@ impl Chain for Ethereum {
     fn chain_block(block: Self::Block) -> ChainBlock {
         ChainBlock::Eth(block)
     }
+
+    // Note: tokens sent to the zero address are burned
+    fn validate_recipient(address: &Self::Address) -> Result<(), Reason> {
+        if *address == [0u8; 20] {
+            Err(Reason::InvalidRecipient)
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl Chain for Polygon {
@@ -1150,6 +1793,10 @@ impl Chain for Polygon {
         Ethereum::hash_bytes(data)
     }
 
+    fn hash_notice(payload: &[u8]) -> Self::Hash {
+        Ethereum::hash_notice(payload)
+    }
+
     fn recover_user_address(
         data: &[u8],
         signature: Self::Signature,
@@ -1157,6 +1804,14 @@ impl Chain for Polygon {
         Ethereum::recover_user_address(data, signature)
     }
 
+    fn verify_user_address(
+        data: &[u8],
+        signature: Self::Signature,
+        address: Self::Address,
+    ) -> Result<Self::Address, Reason> {
+        Ethereum::verify_user_address(data, signature, address)
+    }
+
     fn recover_address(data: &[u8], signature: Self::Signature) -> Result<Self::Address, Reason> {
         Ethereum::recover_address(data, signature)
     }
@@ -1170,7 +1825,7 @@ impl Chain for Polygon {
     }
 
     fn str_to_address(addr: &str) -> Result<Self::Address, Reason> {
-        Ethereum::str_to_address(addr)
+        Ethereum::str_to_address(addr).map_err(|_| Reason::BadAddress(Self::ID))
     }
 
     fn address_string(address: &Self::Address) -> String {
@@ -1178,13 +1833,17 @@ impl Chain for Polygon {
     }
 
     fn str_to_hash(hash: &str) -> Result<Self::Hash, Reason> {
-        Ethereum::str_to_hash(hash)
+        Ethereum::str_to_hash(hash).map_err(|_| Reason::BadHash(Some(Self::ID)))
     }
 
     fn hash_string(hash: &Self::Hash) -> String {
         Ethereum::hash_string(hash)
     }
 
+    fn normalize_timestamp(timestamp: Self::Timestamp) -> Result<Timestamp, Reason> {
+        Ethereum::normalize_timestamp(timestamp)
+    }
+
     fn chain_account(address: Self::Address) -> ChainAccount {
         ChainAccount::Matic(address)
     }
@@ -1192,13 +1851,17 @@ impl Chain for Polygon {
     fn chain_block(block: Self::Block) -> ChainBlock {
         ChainBlock::Matic(block)
     }
+
+    fn validate_recipient(address: &Self::Address) -> Result<(), Reason> {
+        Ethereum::validate_recipient(address)
+    }
 }
 
 impl Chain for Polkadot {
     const ID: ChainId = ChainId::Dot;
 
     #[type_alias("Polkadot__Chain__")]
-    type Address = [u8; 32];
+    type Address = Ss58Address;
 
     #[type_alias("Polkadot__Chain__")]
     type Amount = u128;
@@ -1235,6 +1898,10 @@ impl Chain for Polkadot {
         panic!("XXX not implemented");
     }
 
+    fn hash_notice(_payload: &[u8]) -> Self::Hash {
+        panic!("XXX not implemented");
+    }
+
     fn recover_user_address(
         _data: &[u8],
         _signature: Self::Signature,
@@ -1242,6 +1909,15 @@ impl Chain for Polkadot {
         panic!("XXX not implemented");
     }
 
+    fn verify_user_address(
+        data: &[u8],
+        signature: Self::Signature,
+        address: Self::Address,
+    ) -> Result<Self::Address, Reason> {
+        verify_substrate_address(data, signature, address.key)?;
+        Ok(address)
+    }
+
     fn recover_address(_data: &[u8], _signature: Self::Signature) -> Result<Self::Address, Reason> {
         panic!("XXX not implemented");
     }
@@ -1254,20 +1930,42 @@ impl Chain for Polkadot {
         panic!("XXX not implemented");
     }
 
-    fn str_to_address(_addr: &str) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+    // Note: only single byte prefixes are supported, which covers all the networks we know
+    fn str_to_address(addr: &str) -> Result<Self::Address, Reason> {
+        let bytes = bs58::decode(addr)
+            .into_vec()
+            .map_err(|_| Reason::BadAddress(Self::ID))?;
+        if bytes.len() != 35 || bytes[33..] != ss58_checksum(&bytes[..33])[..] {
+            return Err(Reason::BadAddress(Self::ID));
+        }
+        let network = Ss58Network::from_prefix(bytes[0]).ok_or(Reason::BadAddress(Self::ID))?;
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&bytes[1..33]);
+        Ok(Ss58Address { network, key })
     }
 
-    fn address_string(_address: &Self::Address) -> String {
-        panic!("XXX not implemented");
+    fn address_string(address: &Self::Address) -> String {
+        let mut payload = vec![address.network.prefix()];
+        payload.extend_from_slice(&address.key);
+        let checksum = ss58_checksum(&payload);
+        payload.extend_from_slice(&checksum);
+        bs58::encode(payload).into_string()
     }
 
-    fn str_to_hash(_hash: &str) -> Result<Self::Hash, Reason> {
-        panic!("XXX not implemented");
+    fn str_to_hash(hash: &str) -> Result<Self::Hash, Reason> {
+        match gateway_crypto::eth_str_to_hash(hash) {
+            Some(s) => Ok(s),
+            None => Err(Reason::BadHash(Some(Self::ID))),
+        }
     }
 
-    fn hash_string(_hash: &Self::Hash) -> String {
-        panic!("XXX not implemented");
+    fn hash_string(hash: &Self::Hash) -> String {
+        format!("0x{}", hex::encode(hash))
+    }
+
+    // Note: Polkadot timestamps are in milliseconds
+    fn normalize_timestamp(timestamp: Self::Timestamp) -> Result<Timestamp, Reason> {
+        Ok(timestamp)
     }
 
     fn chain_account(address: Self::Address) -> ChainAccount {
@@ -1279,11 +1977,154 @@ impl Chain for Polkadot {
     }
 }
 
+#[cfg(feature = "runtime-dev")]
+impl Mock {
+    /// Return the hash committing to the message for the given signer.
+    fn signature_hash(address: &[u8; 20], message: &[u8]) -> [u8; 32] {
+        Self::hash_bytes(&[&address[..], message].concat())
+    }
+
+    /// Return the hash of the synthetic block at the given number.
+    pub fn block_hash(number: ChainBlockNumber) -> [u8; 32] {
+        Self::hash_bytes(&number.encode())
+    }
+
+    /// Return the synthetic block at the given number, which never contains any events.
+    pub fn block(number: ChainBlockNumber) -> EthereumBlock {
+        EthereumBlock {
+            hash: Self::block_hash(number),
+            parent_hash: Self::block_hash(number.saturating_sub(1)),
+            number,
+            events: vec![],
+        }
+    }
+}
+
+#[cfg(feature = "runtime-dev")]
+impl Chain for Mock {
+    const ID: ChainId = ChainId::Mock;
+
+    #[type_alias("Mock__Chain__")]
+    type Address = [u8; 20];
+
+    #[type_alias("Mock__Chain__")]
+    type Amount = u128;
+
+    #[type_alias("Mock__Chain__")]
+    type CashIndex = u128;
+
+    #[type_alias("Mock__Chain__")]
+    type Rate = u128;
+
+    #[type_alias("Mock__Chain__")]
+    type Timestamp = u64;
+
+    #[type_alias("Mock__Chain__")]
+    type Hash = [u8; 32];
+
+    #[type_alias("Mock__Chain__")]
+    type PublicKey = [u8; 64];
+
+    #[type_alias("Mock__Chain__")]
+    type Signature = [u8; 52];
+
+    #[type_alias("Mock__Chain__")]
+    type Event = EthereumEvent;
+
+    #[type_alias("Mock__Chain__")]
+    type Block = EthereumBlock;
+
+    fn zero_hash() -> Self::Hash {
+        [0u8; 32]
+    }
+
+    fn hash_bytes(data: &[u8]) -> Self::Hash {
+        Ethereum::hash_bytes(data)
+    }
+
+    fn hash_notice(payload: &[u8]) -> Self::Hash {
+        Ethereum::hash_notice(payload)
+    }
+
+    fn recover_user_address(
+        data: &[u8],
+        signature: Self::Signature,
+    ) -> Result<Self::Address, Reason> {
+        Self::recover_address(data, signature)
+    }
+
+    fn verify_user_address(
+        data: &[u8],
+        signature: Self::Signature,
+        address: Self::Address,
+    ) -> Result<Self::Address, Reason> {
+        let recovered = Self::recover_user_address(data, signature)?;
+        if recovered == address {
+            Ok(recovered)
+        } else {
+            Err(Reason::SignatureAccountMismatch)
+        }
+    }
+
+    fn recover_address(data: &[u8], signature: Self::Signature) -> Result<Self::Address, Reason> {
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&signature[..20]);
+        if signature[20..] == Self::signature_hash(&address, data)[..] {
+            Ok(address)
+        } else {
+            Err(Reason::SignatureMismatch)
+        }
+    }
+
+    fn sign_message(message: &[u8]) -> Result<Self::Signature, Reason> {
+        let address = Self::signer_address()?;
+        let mut signature = [0u8; 52];
+        signature[..20].copy_from_slice(&address);
+        signature[20..].copy_from_slice(&Self::signature_hash(&address, message));
+        Ok(signature)
+    }
+
+    // Note: validators are identified by their Ethereum key, which is held in memory on dev nodes
+    fn signer_address() -> Result<Self::Address, Reason> {
+        Ethereum::signer_address()
+    }
+
+    fn str_to_address(addr: &str) -> Result<Self::Address, Reason> {
+        Ethereum::str_to_address(addr).map_err(|_| Reason::BadAddress(Self::ID))
+    }
+
+    fn address_string(address: &Self::Address) -> String {
+        Ethereum::address_string(address)
+    }
+
+    fn str_to_hash(hash: &str) -> Result<Self::Hash, Reason> {
+        Ethereum::str_to_hash(hash).map_err(|_| Reason::BadHash(Some(Self::ID)))
+    }
+
+    fn hash_string(hash: &Self::Hash) -> String {
+        Ethereum::hash_string(hash)
+    }
+
+    fn normalize_timestamp(timestamp: Self::Timestamp) -> Result<Timestamp, Reason> {
+        Ethereum::normalize_timestamp(timestamp)
+    }
+
+    fn chain_account(address: Self::Address) -> ChainAccount {
+        ChainAccount::Mock(address)
+    }
+
+    fn chain_block(block: Self::Block) -> ChainBlock {
+        ChainBlock::Mock(block)
+    }
+}
+
 pub fn get_chain_account(chain: String, recipient: [u8; 32]) -> Result<ChainAccount, Reason> {
     let chain_account_fn = match &chain.to_ascii_uppercase()[..] {
         "ETH" => ChainAccount::Eth,
         "MATIC" => ChainAccount::Matic,
         "MAT" => ChainAccount::Matic,
+        #[cfg(feature = "runtime-dev")]
+        "MOCK" => ChainAccount::Mock,
         _ => return Err(Reason::InvalidChain),
     };
 
@@ -1297,6 +2138,7 @@ pub fn get_chain_account(chain: String, recipient: [u8; 32]) -> Result<ChainAcco
 mod tests {
     use super::*;
     use ethereum_client::{EthereumBlock, EthereumEvent};
+    use our_std::convert::TryInto;
 
     #[test]
     fn test_chain_events_push() {
@@ -1335,6 +2177,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_super_majority() {
+        let two_thirds = crate::params::DEFAULT_QUORUM_THRESHOLD;
+        for n in 0..=300 {
+            assert_eq!(super_majority(n, two_thirds), (2 * n + 2) / 3);
+        }
+        assert_eq!(super_majority(4, Permill::from_percent(51)), 3);
+        assert_eq!(super_majority(4, Permill::one()), 4);
+
+        let validators: BTreeSet<u8> = vec![1, 2, 3].into_iter().collect();
+        let signers: BTreeSet<u8> = vec![1, 2, 4].into_iter().collect();
+        assert!(has_super_majority(&signers, &validators, two_thirds));
+        assert!(!has_super_majority(
+            &signers,
+            &validators,
+            Permill::from_percent(75)
+        ));
+    }
+
     #[test]
     fn test_chain_blocks_filter_already_suppported() {
         let signer = sp_core::crypto::AccountId32::new([7u8; 32]);
@@ -1361,7 +2222,12 @@ mod tests {
                 number: 0,
                 events: vec![],
             }),
-            support: [signer.clone()].iter().cloned().collect(),
+            support: [signer.clone()]
+                .iter()
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .try_into()
+                .unwrap(),
             dissent: SignersSet::new(),
         }];
 
@@ -1403,7 +2269,12 @@ mod tests {
                 events: vec![],
             }),
             support: SignersSet::new(),
-            dissent: [signer.clone()].iter().cloned().collect(),
+            dissent: [signer.clone()]
+                .iter()
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .try_into()
+                .unwrap(),
         }];
 
         assert_eq!(
@@ -1414,6 +2285,88 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_chain_blocks_limit_events() {
+        let lock = EthereumEvent::Lock {
+            asset: [4u8; 20],
+            sender: [5u8; 20],
+            chain: String::from("ETH"),
+            recipient: [6u8; 32],
+            amount: 100,
+        };
+        let block = |number: u8, num_events: usize| EthereumBlock {
+            hash: [number; 32],
+            parent_hash: [number - 1; 32],
+            number: number as u64,
+            events: vec![lock.clone(); num_events],
+        };
+        let blocks = ChainBlocks::Eth(vec![block(1, 3), block(2, 0), block(3, 2), block(4, 1)]);
+
+        assert_eq!(blocks.clone().limit_events(6), blocks);
+        assert_eq!(
+            blocks.clone().limit_events(5),
+            ChainBlocks::Eth(vec![block(1, 3), block(2, 0), block(3, 2)])
+        );
+        assert_eq!(
+            blocks.clone().limit_events(4),
+            ChainBlocks::Eth(vec![block(1, 3), block(2, 0)])
+        );
+        assert_eq!(
+            blocks.clone().limit_events(1),
+            ChainBlocks::Eth(vec![block(1, 3)])
+        );
+        assert_eq!(
+            ChainBlocks::Eth(vec![]).limit_events(1),
+            ChainBlocks::Eth(vec![])
+        );
+    }
+
+    #[test]
+    fn test_chain_signature_list_bounded() {
+        let signature = ChainSignature::Eth([1u8; 65]);
+        let validator = |i: u32| ValidatorKeys {
+            substrate_id: AccountId32::new([0u8; 32]),
+            eth_address: [i as u8; 20],
+        };
+        let mut signature_pairs = ChainSignatureList::Eth(BoundedVec::default());
+        for i in 0..MaxValidators::get() {
+            assert_eq!(
+                signature_pairs.add_validator_signature(&signature, &validator(i)),
+                Ok(())
+            );
+        }
+        assert_eq!(
+            signature_pairs.add_validator_signature(&signature, &validator(MaxValidators::get())),
+            Err(Reason::TooManySignatures)
+        );
+        assert_eq!(
+            ChainSignatureList::decode(&mut &signature_pairs.encode()[..]).ok(),
+            Some(signature_pairs)
+        );
+    }
+
+    #[test]
+    fn test_chain_signature_list_retain_validators() {
+        let validator = ValidatorKeys {
+            substrate_id: AccountId32::new([1u8; 32]),
+            eth_address: [1u8; 20],
+        };
+        let mut signature_pairs = ChainSignatureList::Eth(
+            vec![([1u8; 20], [1u8; 65]), ([2u8; 20], [2u8; 65])]
+                .try_into()
+                .unwrap(),
+        );
+
+        assert!(signature_pairs.has_stale_signers(&[validator.clone()]));
+        assert!(signature_pairs.retain_validators(&[validator.clone()]));
+        assert_eq!(
+            signature_pairs,
+            ChainSignatureList::Eth(vec![([1u8; 20], [1u8; 65])].try_into().unwrap())
+        );
+        assert!(!signature_pairs.has_stale_signers(&[validator.clone()]));
+        assert!(!signature_pairs.retain_validators(&[validator]));
+    }
+
     #[test]
     fn test_chain_reorg_is_already_signed() {
         let signer = sp_core::crypto::AccountId32::new([7u8; 32]);
@@ -1432,10 +2385,275 @@ mod tests {
                 forward_blocks: vec![],
                 reverse_blocks: vec![],
             },
-            support: [signer.clone()].iter().cloned().collect(),
+            support: [signer.clone()]
+                .iter()
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .try_into()
+                .unwrap(),
         }];
 
         assert_eq!(reorg.is_already_signed(&signer, vec![]), false);
         assert_eq!(reorg.is_already_signed(&signer, pending_reorgs), true);
     }
+
+    #[test]
+    fn test_chain_tags() {
+        for chain_id in &[ChainId::Gate, ChainId::Eth, ChainId::Dot, ChainId::Matic] {
+            assert_eq!(ChainId::from_tag(chain_id.tag()), Some(*chain_id));
+            assert_eq!(chain_id.encode(), vec![chain_id.tag()]);
+        }
+        assert_eq!(ChainId::from_tag(200), None);
+
+        let accounts = [
+            ChainAccount::Gate([1; 32]),
+            ChainAccount::Eth([1; 20]),
+            ChainAccount::Matic([1; 20]),
+        ];
+        for account in &accounts {
+            assert_eq!(account.encode()[0], account.chain_id().tag());
+        }
+
+        let assets = [
+            ChainAsset::Gate(()),
+            ChainAsset::Eth([1; 20]),
+            ChainAsset::Dot(()),
+            ChainAsset::Matic([1; 20]),
+        ];
+        for asset in &assets {
+            assert_eq!(asset.encode()[0], asset.chain_id().tag());
+        }
+    }
+
+    #[test]
+    fn test_maybe_known_chain() {
+        let account = ChainAccount::Matic([2; 20]);
+        let encoded = account.encode();
+        let decoded = MaybeKnownChain::<ChainAccount>::decode(&mut &encoded[..]).unwrap();
+        assert_eq!(decoded, MaybeKnownChain::Known(account));
+        assert_eq!(decoded.encode(), encoded);
+        assert_eq!(decoded.known(), Some(account));
+
+        let encoded = vec![200, 3, 3, 3];
+        let decoded = MaybeKnownChain::<ChainAccount>::decode(&mut &encoded[..]).unwrap();
+        assert_eq!(decoded, MaybeKnownChain::Unknown(200, vec![3, 3, 3]));
+        assert_eq!(decoded.encode(), encoded);
+        assert_eq!(decoded.known(), None);
+
+        // still fails if a known chain is encoded wrong
+        assert!(MaybeKnownChain::<ChainAsset>::decode(&mut &[1u8, 3, 3][..]).is_err());
+        assert!(ChainAccount::decode(&mut &[200u8, 3, 3, 3][..]).is_err());
+    }
+
+    #[test]
+    fn test_validate_recipient() {
+        assert_eq!(
+            ChainAccount::Eth([0u8; 20]).validate_recipient(),
+            Err(Reason::InvalidRecipient)
+        );
+        assert_eq!(
+            ChainAccount::Matic([0u8; 20]).validate_recipient(),
+            Err(Reason::InvalidRecipient)
+        );
+        assert_eq!(ChainAccount::Eth([1u8; 20]).validate_recipient(), Ok(()));
+        assert_eq!(ChainAccount::Gate([0u8; 32]).validate_recipient(), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_account_ed25519() {
+        use sp_core::{ed25519, Pair};
+
+        let pair = ed25519::Pair::from_seed(&[1u8; 32]);
+        let other = ed25519::Pair::from_seed(&[2u8; 32]);
+        let message = b"0:(Transfer 1 Cash Eth:0x0101010101010101010101010101010101010101)";
+        let signature: [u8; 65] = MultiSignature::from(pair.sign(message))
+            .encode()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(
+            ChainAccountSignature::Gate(pair.public().0, signature).verify_account(message),
+            Ok(ChainAccount::Gate(pair.public().0))
+        );
+        let dot_address = Ss58Address {
+            network: Ss58Network::Polkadot,
+            key: pair.public().0,
+        };
+        assert_eq!(
+            ChainAccountSignature::Dot(dot_address, signature).verify_account(message),
+            Ok(ChainAccount::Dot(dot_address))
+        );
+        assert_eq!(
+            ChainAccountSignature::Gate(other.public().0, signature).verify_account(message),
+            Err(Reason::SignatureAccountMismatch)
+        );
+        assert_eq!(
+            ChainAccountSignature::Gate(pair.public().0, [9u8; 65]).verify_account(message),
+            Err(Reason::SignatureMismatch)
+        );
+    }
+
+    #[test]
+    fn test_chain_hash_from_str_and_display() {
+        let hex_hash = "0x0101010101010101010101010101010101010101010101010101010101010101";
+        let eth_hash = ChainHash::from_str(&format!("eth:{}", hex_hash));
+        assert_eq!(eth_hash, Ok(ChainHash::Eth([1u8; 32])));
+        assert_eq!(
+            format!("{}", eth_hash.unwrap()),
+            format!("ETH:{}", hex_hash)
+        );
+
+        for hash in &[
+            ChainHash::Gate([2u8; 32]),
+            ChainHash::Eth([3u8; 32]),
+            ChainHash::Dot([4u8; 32]),
+            ChainHash::Matic([5u8; 32]),
+        ] {
+            assert_eq!(ChainHash::from_str(&format!("{}", hash)), Ok(*hash));
+        }
+
+        assert_eq!(
+            ChainHash::from_str("eth:0x0101"),
+            Err(Reason::BadHash(Some(ChainId::Eth)))
+        );
+        assert_eq!(ChainHash::from_str(hex_hash), Err(Reason::BadHash(None)));
+    }
+
+    #[test]
+    fn test_ss58_address_from_str_and_display() {
+        let key =
+            hex_literal::hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+        for (network, address) in &[
+            (
+                Ss58Network::Polkadot,
+                "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5",
+            ),
+            (
+                Ss58Network::Kusama,
+                "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F",
+            ),
+            (
+                Ss58Network::Substrate,
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+            ),
+        ] {
+            let dot_address = Ss58Address {
+                network: *network,
+                key,
+            };
+            assert_eq!(Polkadot::str_to_address(address), Ok(dot_address));
+            assert_eq!(Polkadot::address_string(&dot_address), *address);
+            assert_eq!(
+                ChainAccount::from_str(&format!("dot:{}", address)),
+                Ok(ChainAccount::Dot(dot_address))
+            );
+            assert_eq!(
+                String::from(ChainAccount::Dot(dot_address)),
+                format!("DOT:{}", address)
+            );
+        }
+
+        assert_eq!(
+            Polkadot::str_to_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ"),
+            Err(Reason::BadAddress(ChainId::Dot))
+        );
+        assert_eq!(
+            Polkadot::str_to_address("0x0101"),
+            Err(Reason::BadAddress(ChainId::Dot))
+        );
+    }
+
+    #[test]
+    fn test_max_encoded_len() {
+        assert_eq!(ChainId::max_encoded_len(), ChainId::Matic.encode().len());
+        assert_eq!(
+            ChainAccount::max_encoded_len(),
+            ChainAccount::Dot(Ss58Address {
+                network: Ss58Network::Substrate,
+                key: [1u8; 32]
+            })
+            .encode()
+            .len()
+        );
+        assert!(ChainAccount::Gate([1u8; 32]).encode().len() <= ChainAccount::max_encoded_len());
+        assert_eq!(
+            ChainAsset::max_encoded_len(),
+            ChainAsset::Eth([1u8; 20]).encode().len()
+        );
+        assert_eq!(
+            ChainHash::max_encoded_len(),
+            ChainHash::Eth([1u8; 32]).encode().len()
+        );
+        assert!(ChainAccount::Eth([1u8; 20]).encode().len() <= ChainAccount::max_encoded_len());
+        assert!(ChainAsset::Gate(()).encode().len() <= ChainAsset::max_encoded_len());
+        #[cfg(feature = "runtime-dev")]
+        {
+            assert!(
+                ChainAccount::Mock([1u8; 20]).encode().len() <= ChainAccount::max_encoded_len()
+            );
+            assert!(ChainAsset::Mock([1u8; 20]).encode().len() <= ChainAsset::max_encoded_len());
+            assert!(
+                ChainHash::Mock(<Mock as Chain>::zero_hash()).encode().len()
+                    <= ChainHash::max_encoded_len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_chain_keys_hash_consistently() {
+        use our_std::collections::btree_set::BTreeSet;
+        use std::collections::HashSet;
+
+        let accounts = vec![
+            ChainAccount::Eth([1u8; 20]),
+            ChainAccount::Matic([1u8; 20]),
+            ChainAccount::Eth([1u8; 20]),
+        ];
+        let hashed: HashSet<ChainAccount> = accounts.iter().cloned().collect();
+        let ordered: BTreeSet<ChainAccount> = accounts.iter().cloned().collect();
+        assert_eq!(hashed.len(), 2);
+        assert_eq!(ordered.len(), 2);
+    }
+
+    #[cfg(feature = "runtime-dev")]
+    #[test]
+    fn test_mock_signatures() {
+        let address = [7u8; 20];
+        let mut signature = [0u8; 52];
+        signature[..20].copy_from_slice(&address);
+        signature[20..].copy_from_slice(&Mock::signature_hash(&address, b"hello"));
+
+        assert_eq!(
+            ChainSignature::Mock(signature).recover(b"hello"),
+            Ok(ChainAccount::Mock(address))
+        );
+        assert_eq!(
+            ChainSignature::Mock(signature).recover(b"goodbye"),
+            Err(Reason::SignatureMismatch)
+        );
+        assert_eq!(
+            ChainAccountSignature::Mock(address, signature).verify_account(b"hello"),
+            Ok(ChainAccount::Mock(address))
+        );
+        assert_eq!(
+            ChainAccountSignature::Mock([8u8; 20], signature).verify_account(b"hello"),
+            Err(Reason::SignatureAccountMismatch)
+        );
+    }
+
+    #[cfg(feature = "runtime-dev")]
+    #[test]
+    fn test_mock_blocks() {
+        let parent = ChainBlock::Mock(Mock::block(1));
+        let block = ChainBlock::Mock(Mock::block(2));
+        assert_eq!(block, ChainBlock::Mock(Mock::block(2)));
+        assert_eq!(block.number(), 2);
+        assert_eq!(block.parent_hash(), parent.hash());
+        assert_eq!(block.events(), vec![]);
+        assert_eq!(ChainId::from_str("mock"), Ok(ChainId::Mock));
+        assert_eq!(
+            parent.concat(ChainBlocks::from(block)),
+            Ok(ChainBlocks::Mock(vec![Mock::block(1), Mock::block(2)]))
+        );
+    }
 }
@@ -12,22 +12,30 @@ pub use our_std::{
 use crate::{
     chains::{
         self, Chain, ChainAccount, ChainAsset, ChainBlock, ChainBlockEvent, ChainBlockEvents,
-        ChainHash, ChainId, ChainSignature, Ethereum, Polygon,
+        ChainBlockNumber, ChainHash, ChainId, ChainSignature, ChainSignatureList, Ethereum,
+        Polygon,
     },
-    internal, log, pipeline,
+    factor::Factor,
+    internal, log,
+    notices::{EncodeNotice, NoticeState},
+    params::MAX_CHAIN_TIMESTAMP_DRIFT,
+    pipeline,
     portfolio::Portfolio,
     rates::APR,
-    reason::Reason,
+    reason::{MathError, Reason},
     types::{
-        AssetAmount, AssetBalance, Balance, CashPrincipalAmount, GovernanceResult, NoticeId,
-        SignersSet, Timestamp, ValidatorKeys,
+        AssetAmount, AssetBalance, Balance, CashOrChainAsset, CashPrincipalAmount, ChainEventId,
+        EncodedNotice, GovernanceResult, MarketState, NoticeId, ProtocolState, SignersSet,
+        Timestamp, ValidatorKeys,
     },
-    AssetBalances, AssetsWithNonZeroBalance, CashIndex, CashPrincipals, CashYield, Config, Event,
-    FirstBlock, GlobalCashIndex, IngressionQueue, LastProcessedBlock, Pallet, Starports,
-    SupportedAssets, TotalBorrowAssets, TotalCashPrincipal, TotalSupplyAssets, Validators,
+    AssetBalances, AssetsWithNonZeroBalance, CashIndex, CashPrincipals, CashYield,
+    ChilledValidators, Config, Event, FirstBlock, GlobalCashIndex, IngressionQueue,
+    LastProcessedBlock, NoticeHashes, NoticeStates, Notices, Pallet, PendingChainBlocks,
+    ProtocolReserves, QuorumThreshold, Starports, SupportedAssets, TotalBorrowAssets,
+    TotalCashPrincipal, TotalSupplyAssets, Validators,
 };
 
-use codec::Decode;
+use codec::{Decode, Encode};
 use frame_support::{
     storage::{
         IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
@@ -46,6 +54,25 @@ pub fn get_recent_timestamp<T: Config>() -> Result<Timestamp, Reason> {
         .map_err(|_| Reason::TimestampMissing);
 }
 
+/// Normalize a timestamp reported by an underlying chain, and check it is near the Gateway clock.
+/// Accrual always uses the Gateway clock, chain-reported times are only ever sanity checked against it.
+pub fn check_chain_timestamp<T: Config>(
+    chain_id: ChainId,
+    timestamp: u64,
+) -> Result<Timestamp, Reason> {
+    let normalized = chain_id.normalize_timestamp(timestamp)?;
+    let now = get_recent_timestamp::<T>()?;
+    let drift = if normalized > now {
+        normalized - now
+    } else {
+        now - normalized
+    };
+    if drift > MAX_CHAIN_TIMESTAMP_DRIFT {
+        return Err(Reason::TimestampOutOfBounds);
+    }
+    Ok(normalized)
+}
+
 /// Return the event ingression queue for the underlying chain.
 pub fn get_event_queue<T: Config>(chain_id: ChainId) -> Result<ChainBlockEvents, Reason> {
     Ok(IngressionQueue::get(chain_id).unwrap_or(ChainBlockEvents::empty(chain_id)?))
@@ -61,6 +88,111 @@ pub fn get_last_block<T: Config>(chain_id: ChainId) -> Result<ChainBlock, Reason
     LastProcessedBlock::get(chain_id).ok_or(Reason::MissingBlock)
 }
 
+/// Return the ingestion progress for each tracked chain.
+/// [chain_id, last processed block number, pending block count, queued event count]
+pub fn get_last_processed_blocks<T: Config>(
+) -> Result<Vec<(ChainId, ChainBlockNumber, u32, u32)>, Reason> {
+    let mut statuses = Vec::new();
+    for (chain_id, block) in LastProcessedBlock::iter() {
+        let pending_blocks: u32 = PendingChainBlocks::get(chain_id)
+            .len()
+            .try_into()
+            .unwrap_or(u32::MAX);
+        let queued_events: u32 = get_event_queue::<T>(chain_id)?
+            .len()
+            .try_into()
+            .unwrap_or(u32::MAX);
+        statuses.push((chain_id, block.number(), pending_blocks, queued_events));
+    }
+    Ok(statuses)
+}
+
+/// Return the status of the notice with the given hash.
+/// The body of a notice is dropped once it executes, so an executed notice has an empty encoding.
+/// [chain_id, notice_id, encoded notice, signature count, quorum, executed]
+pub fn get_notice_status<T: Config>(
+    notice_hash: ChainHash,
+) -> Result<(ChainId, NoticeId, EncodedNotice, u32, u32, bool), Reason> {
+    let chain_id = notice_hash.chain_id();
+    let notice_id = NoticeHashes::get(notice_hash).ok_or(Reason::UnknownNotice)?;
+    let state = NoticeStates::get(chain_id, notice_id);
+    let encoded_notice = match (&state, Notices::get(chain_id, notice_id)) {
+        (_, Some(notice)) => notice.encode_notice(),
+        (NoticeState::Executed, None) => vec![],
+        (_, None) => return Err(Reason::NoticeMissing(chain_id, notice_id)),
+    };
+    let validators: Vec<ValidatorKeys> = Validators::iter().map(|(_, v)| v).collect();
+    let quorum: u32 = chains::super_majority(validators.len(), QuorumThreshold::get())
+        .try_into()
+        .unwrap_or(u32::MAX);
+    let (signatures, executed) = match state {
+        NoticeState::Pending { signature_pairs } => {
+            let signatures = validators
+                .iter()
+                .filter(|v| signature_pairs.has_validator_signature(chain_id, v))
+                .count();
+            (signatures.try_into().unwrap_or(u32::MAX), false)
+        }
+        NoticeState::Executed => (quorum, true),
+        NoticeState::Missing => (0, false),
+    };
+    Ok((
+        chain_id,
+        notice_id,
+        encoded_notice,
+        signatures,
+        quorum,
+        executed,
+    ))
+}
+
+/// Return up to limit notices for the chain which are fully signed but not yet executed, in order.
+/// Signatures are those of current validators, as each starport expects them (e.g. 65 byte Eth signatures).
+/// [notice_id, notice hash, encoded notice, signatures]
+pub fn get_pending_notices<T: Config>(
+    chain_id: ChainId,
+    limit: u32,
+) -> Result<Vec<(NoticeId, ChainHash, EncodedNotice, Vec<Vec<u8>>)>, Reason> {
+    let validators: Vec<ValidatorKeys> = Validators::iter().map(|(_, v)| v).collect();
+    let quorum = chains::super_majority(validators.len(), QuorumThreshold::get());
+    let mut ready: Vec<(NoticeId, ChainSignatureList)> = NoticeStates::iter_prefix(chain_id)
+        .filter_map(|(notice_id, state)| match state {
+            NoticeState::Pending { signature_pairs } => Some((notice_id, signature_pairs)),
+            _ => None,
+        })
+        .filter(|(_, signature_pairs)| {
+            validators
+                .iter()
+                .filter(|v| signature_pairs.has_validator_signature(chain_id, v))
+                .count()
+                >= quorum
+        })
+        .collect();
+    ready.sort_by_key(|(notice_id, _)| *notice_id);
+
+    let mut pending = Vec::new();
+    for (notice_id, signature_pairs) in ready.into_iter().take(limit as usize) {
+        let notice =
+            Notices::get(chain_id, notice_id).ok_or(Reason::NoticeMissing(chain_id, notice_id))?;
+        let signatures = match signature_pairs {
+            ChainSignatureList::Eth(pairs) | ChainSignatureList::Matic(pairs) => pairs
+                .iter()
+                .filter(|(signer, _)| validators.iter().any(|v| v.eth_address == *signer))
+                .map(|(_, signature)| signature.to_vec())
+                .collect(),
+            #[cfg(feature = "runtime-dev")]
+            ChainSignatureList::Mock(pairs) => pairs
+                .iter()
+                .filter(|(signer, _)| validators.iter().any(|v| v.eth_address == *signer))
+                .map(|(_, signature)| signature.to_vec())
+                .collect(),
+            _ => vec![],
+        };
+        pending.push((notice_id, notice.hash(), notice.encode_notice(), signatures));
+    }
+    Ok(pending)
+}
+
 /// Return the current total borrow and total supply balances for the asset.
 pub fn get_market_totals<T: Config>(
     asset: ChainAsset,
@@ -80,6 +212,20 @@ pub fn get_account_balance<T: Config>(
     Ok(AssetBalances::get(asset, account))
 }
 
+/// Return the balance of CASH or of a chain asset for the given account.
+pub fn get_balance<T: Config>(
+    account: ChainAccount,
+    asset: CashOrChainAsset,
+) -> Result<Balance, Reason> {
+    match asset {
+        CashOrChainAsset::Cash => get_cash_balance_with_asset_interest::<T>(account),
+        CashOrChainAsset::ChainAsset(chain_asset) => {
+            let info = internal::assets::get_asset::<T>(chain_asset)?;
+            Ok(info.as_balance(AssetBalances::get(chain_asset, account)))
+        }
+    }
+}
+
 /// Return the current cash yield.
 pub fn get_cash_yield<T: Config>() -> Result<APR, Reason> {
     Ok(CashYield::get())
@@ -90,6 +236,54 @@ pub fn get_cash_data<T: Config>() -> Result<(CashIndex, CashPrincipalAmount), Re
     Ok((GlobalCashIndex::get(), TotalCashPrincipal::get()))
 }
 
+/// Return a summary of the state of the whole protocol, for dashboards and monitoring.
+pub fn get_protocol_state<T: Config>() -> Result<ProtocolState, Reason> {
+    let mut markets = Vec::new();
+    for (asset, info) in SupportedAssets::iter() {
+        let (borrow_rate, supply_rate) = internal::assets::get_rates::<T>(asset)?;
+        markets.push(MarketState {
+            asset,
+            total_supply: TotalSupplyAssets::get(asset),
+            total_borrow: TotalBorrowAssets::get(asset),
+            reserves: ProtocolReserves::get(asset),
+            supply_cap: info.supply_cap,
+            borrow_cap: info.borrow_cap,
+            supply_rate,
+            borrow_rate,
+        });
+    }
+
+    let validators = internal::validator_metadata::get_validator_metadata::<T>()
+        .into_iter()
+        .map(|(validator, metadata)| {
+            let signing_addresses = metadata.map_or(vec![], |m| m.signing_addresses);
+            (validator, signing_addresses)
+        })
+        .collect();
+
+    let mut pending_notices = Vec::new();
+    let mut last_processed_blocks = Vec::new();
+    for (chain_id, block) in LastProcessedBlock::iter() {
+        let pending: u32 = NoticeStates::iter_prefix(chain_id)
+            .filter(|(_, state)| matches!(state, NoticeState::Pending { .. }))
+            .count()
+            .try_into()
+            .unwrap_or(u32::MAX);
+        pending_notices.push((chain_id, pending));
+        last_processed_blocks.push((chain_id, block.number()));
+    }
+
+    Ok(ProtocolState {
+        markets,
+        cash_index: GlobalCashIndex::get(),
+        cash_yield: CashYield::get(),
+        total_cash_principal: TotalCashPrincipal::get(),
+        validators,
+        pending_notices,
+        last_processed_blocks,
+    })
+}
+
 /// Return all ChainAccounts with any holdings
 pub fn get_accounts<T: Config>() -> Result<Vec<ChainAccount>, Reason> {
     let chain_asset_holders: BTreeSet<ChainAccount> = AssetsWithNonZeroBalance::iter()
@@ -167,10 +361,37 @@ pub fn get_liquidity<T: Config>(account: ChainAccount) -> Result<Balance, Reason
     Ok(pipeline::load_portfolio::<T>(account)?.get_liquidity::<T>()?)
 }
 
+/// Calculates the liquidity and health factor an account would have after redeeming and borrowing an asset.
+pub fn get_hypothetical_liquidity<T: Config>(
+    account: ChainAccount,
+    modify_asset: ChainAsset,
+    redeem_amount: AssetAmount,
+    borrow_amount: AssetAmount,
+) -> Result<(Balance, Option<Factor>), Reason> {
+    let asset_info = internal::assets::get_asset::<T>(modify_asset)?;
+    let amount = redeem_amount
+        .checked_add(borrow_amount)
+        .ok_or(MathError::Overflow)?;
+    let portfolio = pipeline::CashPipeline::new()
+        .extract_asset::<T>(account, modify_asset, asset_info.as_quantity(amount))?
+        .state
+        .build_portfolio::<T>(account)?;
+    Ok((
+        portfolio.get_liquidity::<T>()?,
+        portfolio.get_health_factor::<T>()?,
+    ))
+}
+
 /// Return the set of validator identities to compare with others.
+/// Chilled validators can no longer attest, so they are left out of every quorum.
 pub fn get_validator_set<T: Config>() -> Result<SignersSet, Reason> {
     // Note: inefficient, probably manage reading validators from storage better
-    Ok(Validators::iter().map(|(_, v)| v.substrate_id).collect())
+    Validators::iter()
+        .map(|(_, v)| v.substrate_id)
+        .filter(|id| !ChilledValidators::contains_key(id))
+        .collect::<BTreeSet<_>>()
+        .try_into()
+        .map_err(|_| Reason::TooManyValidators)
 }
 
 /// Return the validator associated with the given signer account.
@@ -228,6 +449,15 @@ pub fn recover_validator<T: Config>(
                 }
             }
         }
+        #[cfg(feature = "runtime-dev")]
+        ChainSignature::Mock(mock_sig) => {
+            let eth_address = <chains::Mock as Chain>::recover_address(data, mock_sig)?;
+            for (_, validator) in Validators::iter() {
+                if validator.eth_address == eth_address {
+                    return Ok(validator);
+                }
+            }
+        }
 
         _ => {
             // this is a placeholder for future variants, which should be kept minimal
@@ -249,9 +479,19 @@ pub fn validator_sign<T: Config>(data: &[u8]) -> Result<ChainSignature, Reason>
 
 // Protocol interface //
 
+/// Return the identifier of an event received from a chain.
+pub fn get_chain_event_id(event: &ChainBlockEvent) -> ChainEventId {
+    let chain_id = event.chain_id();
+    (
+        (chain_id, event.block_number()),
+        chain_id.hash_bytes(&event.encode()[..]),
+    )
+}
+
 /// Apply the event to the current state, effectively taking the action.
 pub fn apply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Result<(), Reason> {
     log!("apply_chain_event_internal(event): {:?}", event);
+    internal::chain_status::require_ingesting(event.chain_id())?;
 
     match event {
         ChainBlockEvent::Reserved => panic!("reserved"),
@@ -262,12 +502,13 @@ pub fn apply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Result<
                 chain,
                 recipient,
                 amount,
-            } => internal::lock::lock_internal::<T>(
-                internal::assets::get_asset::<T>(ChainAsset::Eth(*asset))?,
+            } => internal::lock::lock_or_refund_internal::<T>(
+                ChainAsset::Eth(*asset),
                 ChainAccount::Eth(*sender),
                 chains::get_chain_account(chain.to_string(), *recipient)?,
-                internal::assets::get_quantity::<T>(ChainAsset::Eth(*asset), *amount)?,
-            ),
+                *amount,
+            )
+            .map(|outcome| internal::lock::record_lock_outcome(event, outcome)),
 
             ethereum_client::EthereumEvent::LockCash {
                 sender,
@@ -279,12 +520,15 @@ pub fn apply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Result<
                 ChainAccount::Eth(*sender),
                 chains::get_chain_account(chain.to_string(), *recipient)?,
                 CashPrincipalAmount(*principal),
-            ),
+            )
+            .map(|outcome| internal::lock::record_lock_outcome(event, outcome)),
 
             ethereum_client::EthereumEvent::ExecuteProposal {
                 title: _title,
                 extrinsics,
-            } => dispatch_extrinsics_internal::<T>(extrinsics.to_vec()),
+            } => {
+                internal::governance::dispatch_or_queue_governance::<T>(event, extrinsics.to_vec())
+            }
 
             ethereum_client::EthereumEvent::ExecTrxRequest {
                 account,
@@ -314,12 +558,13 @@ pub fn apply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Result<
                 chain,
                 recipient,
                 amount,
-            } => internal::lock::lock_internal::<T>(
-                internal::assets::get_asset::<T>(ChainAsset::Matic(*asset))?,
+            } => internal::lock::lock_or_refund_internal::<T>(
+                ChainAsset::Matic(*asset),
                 ChainAccount::Matic(*sender),
                 chains::get_chain_account(chain.to_string(), *recipient)?,
-                internal::assets::get_quantity::<T>(ChainAsset::Matic(*asset), *amount)?,
-            ),
+                *amount,
+            )
+            .map(|outcome| internal::lock::record_lock_outcome(event, outcome)),
 
             ethereum_client::EthereumEvent::LockCash {
                 sender,
@@ -331,7 +576,8 @@ pub fn apply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Result<
                 ChainAccount::Matic(*sender),
                 chains::get_chain_account(chain.to_string(), *recipient)?,
                 CashPrincipalAmount(*principal),
-            ),
+            )
+            .map(|outcome| internal::lock::record_lock_outcome(event, outcome)),
 
             ethereum_client::EthereumEvent::ExecuteProposal { .. } => {
                 Err(EventError::ActionNotSupported)?
@@ -358,6 +604,60 @@ pub fn apply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Result<
                 result.to_vec(),
             ),
         },
+        #[cfg(feature = "runtime-dev")]
+        ChainBlockEvent::Mock(_block_num, mock_event) => match mock_event {
+            ethereum_client::EthereumEvent::Lock {
+                asset,
+                sender,
+                chain,
+                recipient,
+                amount,
+            } => internal::lock::lock_or_refund_internal::<T>(
+                ChainAsset::Mock(*asset),
+                ChainAccount::Mock(*sender),
+                chains::get_chain_account(chain.to_string(), *recipient)?,
+                *amount,
+            )
+            .map(|outcome| internal::lock::record_lock_outcome(event, outcome)),
+
+            ethereum_client::EthereumEvent::LockCash {
+                sender,
+                chain,
+                recipient,
+                principal,
+                ..
+            } => internal::lock::lock_cash_principal_internal::<T>(
+                ChainAccount::Mock(*sender),
+                chains::get_chain_account(chain.to_string(), *recipient)?,
+                CashPrincipalAmount(*principal),
+            )
+            .map(|outcome| internal::lock::record_lock_outcome(event, outcome)),
+
+            ethereum_client::EthereumEvent::ExecuteProposal { .. } => {
+                Err(EventError::ActionNotSupported)?
+            }
+
+            ethereum_client::EthereumEvent::ExecTrxRequest {
+                account,
+                trx_request,
+            } => internal::exec_trx_request::exec_trx_request::<T>(
+                &trx_request[..],
+                ChainAccount::Mock(*account),
+                None,
+            ),
+
+            ethereum_client::EthereumEvent::NoticeInvoked {
+                era_id,
+                era_index,
+                notice_hash,
+                result,
+            } => internal::notices::handle_notice_invoked::<T>(
+                ChainId::Mock,
+                NoticeId(*era_id, *era_index),
+                ChainHash::Mock(*notice_hash),
+                result.to_vec(),
+            ),
+        },
     }
 }
 
@@ -374,11 +674,12 @@ pub fn unapply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Resul
                 chain,
                 recipient,
                 amount,
-            } => internal::lock::undo_lock_internal::<T>(
-                internal::assets::get_asset::<T>(ChainAsset::Eth(*asset))?,
+            } => internal::lock::undo_lock_or_refund_internal::<T>(
+                event,
+                ChainAsset::Eth(*asset),
                 ChainAccount::Eth(*sender),
                 chains::get_chain_account(chain.to_string(), *recipient)?,
-                internal::assets::get_quantity::<T>(ChainAsset::Eth(*asset), *amount)?,
+                *amount,
             ),
 
             ethereum_client::EthereumEvent::LockCash {
@@ -388,6 +689,7 @@ pub fn unapply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Resul
                 principal,
                 ..
             } => internal::lock::undo_lock_cash_principal_internal::<T>(
+                event,
                 ChainAccount::Eth(*sender),
                 chains::get_chain_account(chain.to_string(), *recipient)?,
                 CashPrincipalAmount(*principal),
@@ -402,11 +704,12 @@ pub fn unapply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Resul
                 chain,
                 recipient,
                 amount,
-            } => internal::lock::undo_lock_internal::<T>(
-                internal::assets::get_asset::<T>(ChainAsset::Matic(*asset))?,
+            } => internal::lock::undo_lock_or_refund_internal::<T>(
+                event,
+                ChainAsset::Matic(*asset),
                 ChainAccount::Matic(*sender),
                 chains::get_chain_account(chain.to_string(), *recipient)?,
-                internal::assets::get_quantity::<T>(ChainAsset::Matic(*asset), *amount)?,
+                *amount,
             ),
 
             ethereum_client::EthereumEvent::LockCash {
@@ -416,6 +719,7 @@ pub fn unapply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Resul
                 principal,
                 ..
             } => internal::lock::undo_lock_cash_principal_internal::<T>(
+                event,
                 ChainAccount::Matic(*sender),
                 chains::get_chain_account(chain.to_string(), *recipient)?,
                 CashPrincipalAmount(*principal),
@@ -423,6 +727,69 @@ pub fn unapply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Resul
 
             _ => Ok(()),
         },
+        #[cfg(feature = "runtime-dev")]
+        ChainBlockEvent::Mock(_block_num, mock_event) => match mock_event {
+            ethereum_client::EthereumEvent::Lock {
+                asset,
+                sender,
+                chain,
+                recipient,
+                amount,
+            } => internal::lock::undo_lock_or_refund_internal::<T>(
+                event,
+                ChainAsset::Mock(*asset),
+                ChainAccount::Mock(*sender),
+                chains::get_chain_account(chain.to_string(), *recipient)?,
+                *amount,
+            ),
+
+            ethereum_client::EthereumEvent::LockCash {
+                sender,
+                chain,
+                recipient,
+                principal,
+                ..
+            } => internal::lock::undo_lock_cash_principal_internal::<T>(
+                event,
+                ChainAccount::Mock(*sender),
+                chains::get_chain_account(chain.to_string(), *recipient)?,
+                CashPrincipalAmount(*principal),
+            ),
+
+            _ => Ok(()),
+        },
+    }
+}
+
+/// Dispatch a SCALE-encoded extrinsic as Root, returning the result for governance.
+pub fn dispatch_extrinsic_internal<T: Config>(payload: &[u8]) -> GovernanceResult {
+    log!(
+        "dispatch_extrinsic_internal:: dispatching extrinsic {}",
+        hex::encode(payload)
+    );
+    let call_res: Result<<T as Config>::Call, _> = Decode::decode(&mut &payload[..]);
+    match call_res {
+        Ok(call) => {
+            log!("dispatch_extrinsic_internal:: dispatching {:?}", call);
+            let res = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
+
+            let gov_res = match res {
+                Ok(_) => GovernanceResult::DispatchSuccess,
+                Err(error_with_post_info) => {
+                    GovernanceResult::DispatchFailure(error_with_post_info.error)
+                }
+            };
+
+            log!("dispatch_extrinsic_internal:: res {:?}", res);
+            gov_res
+        }
+        _ => {
+            log!(
+                "dispatch_extrinsic_internal:: failed to decode extrinsic {}",
+                hex::encode(payload)
+            );
+            GovernanceResult::FailedToDecodeCall
+        }
     }
 }
 
@@ -432,34 +799,8 @@ pub fn dispatch_extrinsics_internal<T: Config>(extrinsics: Vec<Vec<u8>>) -> Resu
     let results: Vec<(Vec<u8>, GovernanceResult)> = extrinsics
         .into_iter()
         .map(|payload| {
-            log!(
-                "dispatch_extrinsics_internal:: dispatching extrinsic {}",
-                hex::encode(&payload)
-            );
-            let call_res: Result<<T as Config>::Call, _> = Decode::decode(&mut &payload[..]);
-            match call_res {
-                Ok(call) => {
-                    log!("dispatch_extrinsics_internal:: dispatching {:?}", call);
-                    let res = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
-
-                    let gov_res = match res {
-                        Ok(_) => GovernanceResult::DispatchSuccess,
-                        Err(error_with_post_info) => {
-                            GovernanceResult::DispatchFailure(error_with_post_info.error)
-                        }
-                    };
-
-                    log!("dispatch_extrinsics_internal:: res {:?}", res);
-                    (payload, gov_res)
-                }
-                _ => {
-                    log!(
-                        "dispatch_extrinsics_internal:: failed to decode extrinsic {}",
-                        hex::encode(&payload)
-                    );
-                    (payload, GovernanceResult::FailedToDecodeCall)
-                }
-            }
+            let gov_res = dispatch_extrinsic_internal::<T>(&payload);
+            (payload, gov_res)
         })
         .collect();
 
@@ -495,6 +836,72 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_get_balance() {
+        new_test_ext().execute_with(|| {
+            let account = ChainAccount::Eth([1; 20]);
+            let eth_asset = CashOrChainAsset::ChainAsset(Eth);
+            assert_eq!(
+                super::get_balance::<Test>(account, eth_asset),
+                Err(Reason::AssetNotSupported)
+            );
+
+            common::init_eth_asset().unwrap();
+            common::init_asset_balance(Eth, account, Balance::from_nominal("3", ETH).value);
+            common::init_cash(account, CashPrincipal::from_nominal("100"));
+
+            assert_eq!(
+                super::get_balance::<Test>(account, eth_asset),
+                Ok(Balance::from_nominal("3", ETH))
+            );
+            assert_eq!(
+                super::get_balance::<Test>(account, CashOrChainAsset::Cash),
+                Ok(Balance::from_nominal("100", CASH))
+            );
+        });
+    }
+
+    #[test]
+    fn test_get_hypothetical_liquidity() {
+        new_test_ext().execute_with(|| {
+            let account = ChainAccount::Eth([1; 20]);
+            assert_eq!(
+                super::get_hypothetical_liquidity::<Test>(account, Eth, 0, 0),
+                Err(Reason::AssetNotSupported)
+            );
+
+            common::init_eth_asset().unwrap();
+            common::init_wbtc_asset().unwrap();
+            common::init_asset_balance(Wbtc, account, Balance::from_nominal("1", WBTC).value); // 1 * 60000 * 0.6 = 36000
+            common::init_asset_balance(Eth, account, Balance::from_nominal("10", ETH).value); // 10 * 2000 * 0.8 = 16000
+
+            assert_eq!(
+                super::get_hypothetical_liquidity::<Test>(account, Eth, 0, 0),
+                Ok((Balance::from_nominal("52000", USD), None))
+            );
+
+            // Redeem the 10 ETH supplied and borrow 5 more: 36000 - 5 * 2000 / 0.8 = 23500
+            assert_eq!(
+                super::get_hypothetical_liquidity::<Test>(
+                    account,
+                    Eth,
+                    Quantity::from_nominal("10", ETH).value,
+                    Quantity::from_nominal("5", ETH).value
+                ),
+                Ok((
+                    Balance::from_nominal("23500", USD),
+                    Some(Factor::from_nominal("2.88"))
+                ))
+            );
+
+            // Nothing is committed
+            assert_eq!(
+                AssetBalances::get(Eth, account),
+                Balance::from_nominal("10", ETH).value
+            );
+        });
+    }
+
     #[test]
     fn test_compute_cash_principal_per() -> Result<(), Reason> {
         // round numbers (unrealistic but very easy to check)
@@ -559,6 +966,37 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_check_chain_timestamp() {
+        new_test_ext().execute_with(|| {
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1_600_000_000_000);
+            assert_eq!(
+                check_chain_timestamp::<Test>(ChainId::Eth, 1_600_000_000),
+                Ok(1_600_000_000_000)
+            );
+            assert_eq!(
+                check_chain_timestamp::<Test>(ChainId::Matic, 1_600_000_600),
+                Ok(1_600_000_600_000)
+            );
+            assert_eq!(
+                check_chain_timestamp::<Test>(ChainId::Gate, 1_600_000_000_000),
+                Ok(1_600_000_000_000)
+            );
+            assert_eq!(
+                check_chain_timestamp::<Test>(ChainId::Eth, u64::MAX),
+                Err(Reason::MathError(MathError::Overflow))
+            );
+            assert_eq!(
+                check_chain_timestamp::<Test>(ChainId::Eth, 1_600_001_000),
+                Err(Reason::TimestampOutOfBounds)
+            );
+            assert_eq!(
+                check_chain_timestamp::<Test>(ChainId::Eth, 1_599_999_000),
+                Err(Reason::TimestampOutOfBounds)
+            );
+        });
+    }
+
     #[test]
     fn test_get_current_validator() {
         new_test_ext().execute_with(|| {
@@ -570,4 +1008,232 @@ mod tests {
             assert_eq!(get_current_validator::<Test>().unwrap(), validator);
         })
     }
+
+    #[test]
+    fn test_get_last_processed_blocks() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(get_last_processed_blocks::<Test>(), Ok(vec![]));
+
+            LastProcessedBlock::insert(
+                ChainId::Eth,
+                ChainBlock::Eth(ethereum_client::EthereumBlock {
+                    hash: [2u8; 32],
+                    parent_hash: [1u8; 32],
+                    number: 10,
+                    events: vec![],
+                }),
+            );
+            IngressionQueue::insert(
+                ChainId::Eth,
+                ChainBlockEvents::Eth(vec![(
+                    9,
+                    ethereum_client::EthereumEvent::Lock {
+                        asset: [4u8; 20],
+                        sender: [5u8; 20],
+                        chain: String::from("ETH"),
+                        recipient: [6u8; 32],
+                        amount: 100,
+                    },
+                )]),
+            );
+
+            assert_eq!(
+                get_last_processed_blocks::<Test>(),
+                Ok(vec![(ChainId::Eth, 10, 0, 1)])
+            );
+        })
+    }
+
+    #[test]
+    fn test_get_notice_status() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let notice_id = NoticeId(80, 1);
+            let notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
+                id: notice_id,
+                parent: [3u8; 32],
+                asset: [1; 20],
+                account: [2; 20],
+                amount: 50,
+            });
+            let notice_hash = notice.hash();
+            assert_eq!(
+                get_notice_status::<Test>(notice_hash),
+                Err(Reason::UnknownNotice)
+            );
+
+            let signature_pairs =
+                ChainSignatureList::Eth(vec![(val_a().eth_address, [0u8; 65])].try_into().unwrap());
+            Notices::insert(ChainId::Eth, notice_id, &notice);
+            NoticeHashes::insert(notice_hash, notice_id);
+            NoticeStates::insert(
+                ChainId::Eth,
+                notice_id,
+                NoticeState::Pending { signature_pairs },
+            );
+            assert_eq!(
+                get_notice_status::<Test>(notice_hash),
+                Ok((ChainId::Eth, notice_id, notice.encode_notice(), 1, 2, false))
+            );
+
+            assert_ok!(internal::notices::handle_notice_invoked::<Test>(
+                ChainId::Eth,
+                notice_id,
+                notice_hash,
+                vec![]
+            ));
+            assert_eq!(
+                get_notice_status::<Test>(notice_hash),
+                Ok((ChainId::Eth, notice_id, vec![], 2, 2, true))
+            );
+        })
+    }
+
+    #[test]
+    fn test_get_pending_notices() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let signed_by = |signers: Vec<ValidatorKeys>| NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Eth(
+                    signers
+                        .iter()
+                        .map(|v| (v.eth_address, [7u8; 65]))
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .unwrap(),
+                ),
+            };
+            let notices: Vec<Notice> = (0..3)
+                .map(|i| {
+                    Notice::ExtractionNotice(ExtractionNotice::Eth {
+                        id: NoticeId(0, i),
+                        parent: [i as u8; 32],
+                        asset: [1; 20],
+                        account: [2; 20],
+                        amount: 50,
+                    })
+                })
+                .collect();
+            for (i, notice) in notices.iter().enumerate() {
+                Notices::insert(ChainId::Eth, NoticeId(0, i as u32), notice);
+            }
+            NoticeStates::insert(
+                ChainId::Eth,
+                NoticeId(0, 2),
+                signed_by(vec![val_a(), val_b()]),
+            );
+            NoticeStates::insert(ChainId::Eth, NoticeId(0, 1), signed_by(vec![val_a()]));
+            NoticeStates::insert(
+                ChainId::Eth,
+                NoticeId(0, 0),
+                signed_by(vec![val_b(), val_a()]),
+            );
+
+            let expected = |i: usize| {
+                (
+                    NoticeId(0, i as u32),
+                    notices[i].hash(),
+                    notices[i].encode_notice(),
+                    vec![[7u8; 65].to_vec(), [7u8; 65].to_vec()],
+                )
+            };
+            assert_eq!(
+                get_pending_notices::<Test>(ChainId::Eth, 10),
+                Ok(vec![expected(0), expected(2)])
+            );
+            assert_eq!(
+                get_pending_notices::<Test>(ChainId::Eth, 1),
+                Ok(vec![expected(0)])
+            );
+            assert_eq!(get_pending_notices::<Test>(ChainId::Matic, 10), Ok(vec![]));
+        })
+    }
+
+    #[test]
+    fn test_get_pending_notices_with_chilled() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let notice_id = NoticeId(0, 0);
+            let notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
+                id: notice_id,
+                parent: [0u8; 32],
+                asset: [1; 20],
+                account: [2; 20],
+                amount: 50,
+            });
+            let signature_pairs =
+                ChainSignatureList::Eth(vec![(val_a().eth_address, [7u8; 65])].try_into().unwrap());
+            Notices::insert(ChainId::Eth, notice_id, &notice);
+            NoticeHashes::insert(notice.hash(), notice_id);
+            NoticeStates::insert(
+                ChainId::Eth,
+                notice_id,
+                NoticeState::Pending { signature_pairs },
+            );
+            assert_eq!(get_pending_notices::<Test>(ChainId::Eth, 10), Ok(vec![]));
+
+            // starports verify against every validator until they are changed, chilled or not
+            ChilledValidators::insert(
+                &val_b().substrate_id,
+                Offense::Equivocation((ChainId::Eth, 1)),
+            );
+            assert_eq!(get_pending_notices::<Test>(ChainId::Eth, 10), Ok(vec![]));
+            assert_eq!(
+                get_notice_status::<Test>(notice.hash()),
+                Ok((ChainId::Eth, notice_id, notice.encode_notice(), 1, 2, false))
+            );
+        })
+    }
+
+    #[test]
+    fn test_get_protocol_state() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            TotalSupplyAssets::insert(Eth, 1000);
+            TotalBorrowAssets::insert(Eth, 100);
+            ProtocolReserves::insert(Eth, CashPrincipalAmount(5));
+            let signing_addresses = vec![ChainAccount::Matic([3u8; 20])];
+            ValidatorMetadatas::insert(
+                val_a().substrate_id,
+                ValidatorMetadata {
+                    timestamp: 1,
+                    name: vec![],
+                    website: vec![],
+                    signing_addresses: signing_addresses.clone(),
+                },
+            );
+            let pending = NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Eth(vec![].try_into().unwrap()),
+            };
+            NoticeStates::insert(ChainId::Eth, NoticeId(0, 0), NoticeState::Executed);
+            NoticeStates::insert(ChainId::Eth, NoticeId(0, 1), pending.clone());
+            NoticeStates::insert(ChainId::Eth, NoticeId(0, 2), pending);
+
+            let state = get_protocol_state::<Test>().unwrap();
+            let (borrow_rate, supply_rate) = internal::assets::get_rates::<Test>(Eth).unwrap();
+            let info = SupportedAssets::get(Eth).unwrap();
+            assert_eq!(state.markets.len(), 2);
+            assert!(state.markets.contains(&MarketState {
+                asset: Eth,
+                total_supply: 1000,
+                total_borrow: 100,
+                reserves: CashPrincipalAmount(5),
+                supply_cap: info.supply_cap,
+                borrow_cap: info.borrow_cap,
+                supply_rate,
+                borrow_rate,
+            }));
+            assert_eq!(state.cash_index, GlobalCashIndex::get());
+            assert_eq!(state.cash_yield, CashYield::get());
+            assert_eq!(state.total_cash_principal, TotalCashPrincipal::get());
+            assert_eq!(state.validators.len(), 2);
+            assert!(state.validators.contains(&(val_a(), signing_addresses)));
+            assert!(state.validators.contains(&(val_b(), vec![])));
+            assert_eq!(state.pending_notices, vec![(ChainId::Eth, 2)]);
+            assert_eq!(
+                state.last_processed_blocks,
+                vec![(ChainId::Eth, premined_block().number)]
+            );
+        })
+    }
 }
@@ -1,13 +1,18 @@
+#[cfg(feature = "runtime-dev")]
+use crate::chains::Mock;
 use crate::{
     chains::{
         Chain, ChainBlock, ChainBlockNumber, ChainBlocks, ChainHash, ChainId, ChainStarport,
         Ethereum,
     },
     debug,
+    metrics::time_rpc,
     reason::Reason,
+    EventProofMode,
 };
 use codec::{Decode, Encode};
 use ethereum_client::{EthereumBlock, EthereumBlockId, EthereumClientError};
+use frame_support::storage::StorageMap;
 use our_std::RuntimeDebug;
 use types_derive::Types;
 
@@ -52,6 +57,8 @@ pub fn fetch_chain_block(
         (ChainId::Matic, ChainStarport::Matic(starport_address)) => {
             Ok(fetch_matic_block(number, &starport_address).map(ChainBlock::Matic)?)
         }
+        #[cfg(feature = "runtime-dev")]
+        (ChainId::Mock, ChainStarport::Mock(_)) => Ok(ChainBlock::Mock(Mock::block(number))),
         (ChainId::Dot, _) => Err(Reason::Unreachable),
         _ => Err(Reason::Unreachable),
     }
@@ -72,11 +79,29 @@ pub fn fetch_chain_blocks(
         (ChainId::Matic, ChainStarport::Matic(starport_address)) => {
             Ok(fetch_matic_blocks(from, to, &starport_address)?)
         }
+        #[cfg(feature = "runtime-dev")]
+        (ChainId::Mock, ChainStarport::Mock(_)) => {
+            Ok(ChainBlocks::Mock((from..to).map(Mock::block).collect()))
+        }
         (ChainId::Dot, _) => Err(Reason::Unreachable),
         _ => Err(Reason::Unreachable),
     }
 }
 
+/// Fetch a block from an Ethereum-like chain, proving its events if required for the chain.
+fn get_eth_like_block(
+    chain_id: ChainId,
+    rpc_url: &str,
+    starport_address: &[u8; 20],
+    block_id: EthereumBlockId,
+) -> Result<EthereumBlock, EthereumClientError> {
+    if EventProofMode::get(chain_id) {
+        ethereum_client::get_proven_block(rpc_url, starport_address, block_id)
+    } else {
+        ethereum_client::get_block(rpc_url, starport_address, block_id)
+    }
+}
+
 /// Fetch a single block from the Etherum Starport by hash.
 fn fetch_eth_block_by_hash(
     hash: <Ethereum as Chain>::Hash,
@@ -85,11 +110,14 @@ fn fetch_eth_block_by_hash(
     debug!("Fetching Eth Block {:?}", hash);
     let eth_rpc_url = runtime_interfaces::validator_config_interface::get_eth_rpc_url()
         .ok_or(EventError::NoRpcUrl)?;
-    let eth_block = ethereum_client::get_block(
-        &eth_rpc_url,
-        eth_starport_address,
-        EthereumBlockId::Hash(hash),
-    )
+    let eth_block = time_rpc(ChainId::Eth, || {
+        get_eth_like_block(
+            ChainId::Eth,
+            &eth_rpc_url,
+            eth_starport_address,
+            EthereumBlockId::Hash(hash),
+        )
+    })
     .map_err(EventError::EthereumClientError)?;
     Ok(eth_block)
 }
@@ -102,15 +130,47 @@ fn fetch_eth_block(
     debug!("Fetching Eth Block {}", number);
     let eth_rpc_url = runtime_interfaces::validator_config_interface::get_eth_rpc_url()
         .ok_or(EventError::NoRpcUrl)?;
-    let eth_block = ethereum_client::get_block(
-        &eth_rpc_url,
-        eth_starport_address,
-        EthereumBlockId::Number(number),
-    )
+    let eth_block = time_rpc(ChainId::Eth, || {
+        get_eth_like_block(
+            ChainId::Eth,
+            &eth_rpc_url,
+            eth_starport_address,
+            EthereumBlockId::Number(number),
+        )
+    })
     .map_err(EventError::EthereumClientError)?;
     Ok(eth_block)
 }
 
+/// Fetch the time-weighted average tick of a Uniswap V3 pool on Ethereum.
+pub fn fetch_anchor_tick(pool: &[u8; 20], period: u32) -> Result<i32, EventError> {
+    debug!("Fetching anchor tick for pool {:?}", pool);
+    let eth_rpc_url = runtime_interfaces::validator_config_interface::get_eth_rpc_url()
+        .ok_or(EventError::NoRpcUrl)?;
+    let tick = ethereum_client::get_twap_tick(&eth_rpc_url, pool, period)
+        .map_err(EventError::EthereumClientError)?;
+    Ok(tick)
+}
+
+/// Fetch the current gas price (in wei) of an underlying chain.
+pub fn fetch_gas_price(chain_id: ChainId) -> Result<u64, Reason> {
+    match chain_id {
+        ChainId::Eth => {
+            let eth_rpc_url = runtime_interfaces::validator_config_interface::get_eth_rpc_url()
+                .ok_or(EventError::NoRpcUrl)?;
+            Ok(ethereum_client::get_gas_price(&eth_rpc_url)
+                .map_err(EventError::EthereumClientError)?)
+        }
+        ChainId::Matic => {
+            let matic_rpc_url = runtime_interfaces::validator_config_interface::get_matic_rpc_url()
+                .ok_or(EventError::NoRpcUrl)?;
+            Ok(ethereum_client::get_gas_price(&matic_rpc_url)
+                .map_err(EventError::PolygonClientError)?)
+        }
+        _ => Err(Reason::Unreachable),
+    }
+}
+
 /// Fetch a single block from the Etherum Starport.
 fn fetch_matic_block(
     number: ChainBlockNumber,
@@ -118,11 +178,14 @@ fn fetch_matic_block(
 ) -> Result<EthereumBlock, EventError> {
     let matic_rpc_url = runtime_interfaces::validator_config_interface::get_matic_rpc_url()
         .ok_or(EventError::NoRpcUrl)?;
-    let block = ethereum_client::get_block(
-        &matic_rpc_url,
-        matic_starport_address,
-        EthereumBlockId::Number(number),
-    )
+    let block = time_rpc(ChainId::Matic, || {
+        get_eth_like_block(
+            ChainId::Matic,
+            &matic_rpc_url,
+            matic_starport_address,
+            EthereumBlockId::Number(number),
+        )
+    })
     .map_err(EventError::PolygonClientError)?;
     Ok(block)
 }
@@ -0,0 +1,288 @@
+//! Canonical SCALE encodings of the types we keep in storage or emit as events.
+//!
+//! Enabled by the `codec_fixtures` feature, so that downstream tooling (indexers, the Starport
+//! test harness) can check that it encodes and decodes exactly as the chain does, and so that
+//! any change to these encodings shows up as a failing fixture rather than a broken chain.
+
+use crate::{
+    chains::{ChainAccount, ChainAsset, ChainHash, ChainId, ChainSignatureList},
+    notices::{
+        BatchExtractionNotice, CashExtractionNotice, ChangeAuthorityNotice, ExtractionNotice,
+        Notice, NoticeId, NoticeState,
+    },
+    types::{CashIndex, CashPrincipalAmount},
+    Event,
+};
+use codec::{Decode, Encode};
+use our_std::{convert::TryInto, fmt::Debug, vec::Vec};
+
+/// A value along with its canonical encoding, as a hex string.
+#[derive(Clone, Debug)]
+pub struct Fixture<T> {
+    pub name: &'static str,
+    pub value: T,
+    pub encoded: &'static str,
+}
+
+impl<T> Fixture<T> {
+    pub fn new(name: &'static str, value: T, encoded: &'static str) -> Self {
+        Fixture {
+            name,
+            value,
+            encoded,
+        }
+    }
+
+    /// The canonical encoding, as bytes.
+    pub fn bytes(&self) -> Vec<u8> {
+        hex::decode(self.encoded).expect("Fixture is not valid hex")
+    }
+}
+
+pub fn chain_accounts() -> Vec<Fixture<ChainAccount>> {
+    vec![
+        Fixture::new(
+            "ChainAccount::Gate",
+            ChainAccount::Gate([0x01; 32]),
+            "000101010101010101010101010101010101010101010101010101010101010101",
+        ),
+        Fixture::new(
+            "ChainAccount::Eth",
+            ChainAccount::Eth([0x02; 20]),
+            "010202020202020202020202020202020202020202",
+        ),
+        Fixture::new(
+            "ChainAccount::Matic",
+            ChainAccount::Matic([0x03; 20]),
+            "030303030303030303030303030303030303030303",
+        ),
+    ]
+}
+
+pub fn chain_assets() -> Vec<Fixture<ChainAsset>> {
+    vec![
+        Fixture::new(
+            "ChainAsset::Eth",
+            ChainAsset::Eth([0xee; 20]),
+            "01eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee",
+        ),
+        Fixture::new(
+            "ChainAsset::Matic",
+            ChainAsset::Matic([0xee; 20]),
+            "03eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee",
+        ),
+    ]
+}
+
+pub fn notices() -> Vec<Fixture<Notice>> {
+    vec![
+        Fixture::new(
+            "Notice::ExtractionNotice::Eth",
+            Notice::ExtractionNotice(ExtractionNotice::Eth {
+                id: NoticeId(1, 2),
+                parent: [0x11; 32],
+                asset: [0x22; 20],
+                account: [0x33; 20],
+                amount: 1000,
+            }),
+            "00000100000002000000111111111111111111111111111111111111111111111111111111111111111122222222222222222222222222222222222222223333333333333333333333333333333333333333e8030000000000000000000000000000",
+        ),
+        Fixture::new(
+            "Notice::CashExtractionNotice::Eth",
+            Notice::CashExtractionNotice(CashExtractionNotice::Eth {
+                id: NoticeId(1, 2),
+                parent: [0x11; 32],
+                account: [0x33; 20],
+                principal: 500,
+                index: 1_000000000000000000,
+            }),
+            "0100010000000200000011111111111111111111111111111111111111111111111111111111111111113333333333333333333333333333333333333333f4010000000000000000000000000000000064a7b3b6e00d0000000000000000",
+        ),
+        Fixture::new(
+            "Notice::ChangeAuthorityNotice::Matic",
+            Notice::ChangeAuthorityNotice(ChangeAuthorityNotice::Matic {
+                id: NoticeId(1, 2),
+                parent: [0x11; 32],
+                new_authorities: vec![[0x44; 20]],
+            }),
+            "040101000000020000001111111111111111111111111111111111111111111111111111111111111111044444444444444444444444444444444444444444",
+        ),
+        Fixture::new(
+            "Notice::BatchExtractionNotice::Eth",
+            Notice::BatchExtractionNotice(BatchExtractionNotice::Eth {
+                id: NoticeId(1, 2),
+                parent: [0x11; 32],
+                extractions: vec![([0x22; 20], [0x33; 20], 1000)],
+            }),
+            "0500010000000200000011111111111111111111111111111111111111111111111111111111111111110422222222222222222222222222222222222222223333333333333333333333333333333333333333e8030000000000000000000000000000",
+        ),
+    ]
+}
+
+pub fn notice_states() -> Vec<Fixture<NoticeState>> {
+    vec![
+        Fixture::new(
+            "NoticeState::Pending",
+            NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![([0x55; 20], [0x66; 65])].try_into().unwrap(),
+                ),
+            },
+            "01010455555555555555555555555555555555555555556666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666666",
+        ),
+        Fixture::new("NoticeState::Executed", NoticeState::Executed, "02"),
+    ]
+}
+
+pub fn events() -> Vec<Fixture<Event>> {
+    vec![
+        Fixture::new(
+            "Event::Locked",
+            Event::Locked(
+                ChainAsset::Eth([0xee; 20]),
+                ChainAccount::Eth([0x02; 20]),
+                ChainAccount::Gate([0x01; 32]),
+                1000,
+            ),
+            "0001eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee010202020202020202020202020202020202020202000101010101010101010101010101010101010101010101010101010101010101e8030000000000000000000000000000",
+        ),
+        Fixture::new(
+            "Event::TransferCash",
+            Event::TransferCash(
+                ChainAccount::Eth([0x02; 20]),
+                ChainAccount::Gate([0x01; 32]),
+                CashPrincipalAmount(100),
+                CashIndex(1_000000000000000000),
+            ),
+            "0701020202020202020202020202020202020202020200010101010101010101010101010101010101010101010101010101010101010164000000000000000000000000000000000064a7b3b6e00d0000000000000000",
+        ),
+        Fixture::new(
+            "Event::MinerPaid",
+            Event::MinerPaid(
+                ChainAccount::Eth([0x02; 20]),
+                CashPrincipalAmount(100),
+                CashIndex(1_000000000000000000),
+            ),
+            "0b0102020202020202020202020202020202020202020264000000000000000000000000000000000064a7b3b6e00d0000000000000000",
+        ),
+        Fixture::new(
+            "Event::NoticeExecuted",
+            Event::NoticeExecuted(
+                ChainId::Eth,
+                NoticeId(1, 2),
+                ChainHash::Eth([0xaa; 32]),
+                vec![0x01],
+            ),
+            "1b01010000000200000001aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa0401",
+        ),
+        Fixture::new(
+            "Event::SetExtractionLimit",
+            Event::SetExtractionLimit(ChainAsset::Eth([0xee; 20]), Some(1000)),
+            "1801eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee01e8030000000000000000000000000000",
+        ),
+    ]
+}
+
+/// Assert that a value encodes to exactly the given hex string.
+pub fn assert_encodes<T: Encode + Debug>(value: &T, encoded: &str) {
+    assert_eq!(
+        hex::encode(value.encode()),
+        encoded,
+        "Encoding of {:?} has changed",
+        value
+    );
+}
+
+/// Assert that the given hex string decodes to exactly the given value, consuming all input.
+pub fn assert_decodes<T: Decode + PartialEq + Debug>(encoded: &str, value: &T) {
+    let bytes = hex::decode(encoded).expect("Encoding is not valid hex");
+    let mut input = &bytes[..];
+    let decoded = T::decode(&mut input).expect("Encoding no longer decodes");
+    assert_eq!(&decoded, value, "Decoding of {} has changed", encoded);
+    assert!(
+        input.is_empty(),
+        "Decoding of {} left trailing bytes",
+        encoded
+    );
+}
+
+/// Assert that each fixture both encodes and decodes canonically.
+pub fn assert_fixtures<T: Encode + Decode + PartialEq + Debug>(fixtures: &[Fixture<T>]) {
+    for fixture in fixtures {
+        assert_encodes(&fixture.value, fixture.encoded);
+        assert_decodes(fixture.encoded, &fixture.value);
+    }
+}
+
+/// Assert every fixture in this module.
+pub fn assert_all_fixtures() {
+    assert_fixtures(&chain_accounts());
+    assert_fixtures(&chain_assets());
+    assert_fixtures(&notices());
+    assert_fixtures(&notice_states());
+    assert_fixtures(&events());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_fixtures() {
+        assert_all_fixtures();
+    }
+
+    #[test]
+    fn test_event_indices_are_stable() {
+        use crate::{
+            chains::ChainBlockEvent,
+            rates::APR,
+            reason::Reason,
+            types::{AssetInfo, GovernanceResult, Units, ValidatorKeys},
+        };
+
+        let asset = ChainAsset::Eth([0xee; 20]);
+        let account = ChainAccount::Eth([0x02; 20]);
+        let principal = CashPrincipalAmount(100);
+        let index = CashIndex(1_000000000000000000);
+        let notice = notices()[0].value.clone();
+
+        // New events are appended after Failure, so none of these indices may ever move
+        let events = vec![
+            Event::Locked(asset, account, account, 1),
+            Event::ReorgRevertLocked(asset, account, account, 1),
+            Event::LockedCash(account, account, principal, index),
+            Event::ReorgRevertLockedCash(account, account, principal, index),
+            Event::Extract(asset, account, account, 1),
+            Event::ExtractCash(account, account, principal, index),
+            Event::Transfer(asset, account, account, 1),
+            Event::TransferCash(account, account, principal, index),
+            Event::Liquidate(asset, asset, account, account, 1),
+            Event::LiquidateCash(asset, account, account, principal, index),
+            Event::LiquidateCashCollateral(asset, account, account, 1),
+            Event::MinerPaid(account, principal, index),
+            Event::AllowedNextCodeHash([0x01; 32]),
+            Event::AttemptedSetCodeByHash([0x01; 32], Ok(())),
+            Event::ProcessedChainBlockEvent(ChainBlockEvent::Reserved),
+            Event::FailedProcessingChainBlockEvent(ChainBlockEvent::Reserved, Reason::None),
+            Event::Notice(NoticeId(1, 2), notice, vec![]),
+            Event::ExecutedGovernance(vec![(vec![], GovernanceResult::DispatchSuccess)]),
+            Event::AssetModified(AssetInfo::minimal(asset, Units::from_ticker_str("ETH", 18))),
+            Event::ChangeValidators(vec![ValidatorKeys {
+                substrate_id: [0x01; 32].into(),
+                eth_address: [0x02; 20],
+            }]),
+            Event::SetYieldNext(APR(0), 0),
+            Event::Failure(Reason::None),
+        ];
+
+        for (index, event) in events.iter().enumerate() {
+            assert_eq!(
+                event.encode()[0] as usize,
+                index,
+                "Index of {:?} has changed",
+                event
+            );
+        }
+    }
+}
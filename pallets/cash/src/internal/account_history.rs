@@ -0,0 +1,170 @@
+use crate::{
+    chains::ChainAccount,
+    params::ACCOUNT_HISTORY_LENGTH,
+    types::{AccountActivity, AccountHistoryEntry},
+    AccountHistory, AccountHistoryCount, Config, Event, Module,
+};
+use frame_support::storage::{StorageDoubleMap, StorageMap};
+use our_std::vec::Vec;
+use sp_runtime::traits::UniqueSaturatedInto;
+
+/// Return the activity an event records, if it changes the balances of the accounts it involves.
+pub fn account_activity(event: &Event) -> Option<AccountActivity> {
+    match *event {
+        Event::Locked(asset, sender, recipient, amount) => {
+            Some(AccountActivity::Locked(asset, sender, recipient, amount))
+        }
+        Event::LockedCash(sender, recipient, principal, index) => Some(
+            AccountActivity::LockedCash(sender, recipient, principal, index),
+        ),
+        Event::ReorgRevertLocked(asset, sender, recipient, amount) => Some(
+            AccountActivity::ReorgRevertLocked(asset, sender, recipient, amount),
+        ),
+        Event::ReorgRevertLockedCash(sender, recipient, principal, index) => Some(
+            AccountActivity::ReorgRevertLockedCash(sender, recipient, principal, index),
+        ),
+        Event::Extract(asset, sender, recipient, amount) => {
+            Some(AccountActivity::Extract(asset, sender, recipient, amount))
+        }
+        Event::ExtractCash(sender, recipient, principal, index) => Some(
+            AccountActivity::ExtractCash(sender, recipient, principal, index),
+        ),
+        Event::Transfer(asset, sender, recipient, amount) => {
+            Some(AccountActivity::Transfer(asset, sender, recipient, amount))
+        }
+        Event::TransferCash(sender, recipient, principal, index) => Some(
+            AccountActivity::TransferCash(sender, recipient, principal, index),
+        ),
+        Event::Liquidate(asset, collateral_asset, liquidator, borrower, amount) => Some(
+            AccountActivity::Liquidate(asset, collateral_asset, liquidator, borrower, amount),
+        ),
+        Event::LiquidateCash(collateral_asset, liquidator, borrower, principal, index) => {
+            Some(AccountActivity::LiquidateCash(
+                collateral_asset,
+                liquidator,
+                borrower,
+                principal,
+                index,
+            ))
+        }
+        Event::LiquidateCashCollateral(asset, liquidator, borrower, amount) => Some(
+            AccountActivity::LiquidateCashCollateral(asset, liquidator, borrower, amount),
+        ),
+        _ => None,
+    }
+}
+
+/// Deposit an event, recording it in the history of each account it involves, if it changes their balances.
+pub fn deposit_account_event<T: Config>(event: Event) {
+    if let Some(activity) = account_activity(&event) {
+        record_account_activity::<T>(activity);
+    }
+    <Module<T>>::deposit_event(event);
+}
+
+/// Record the activity in the history of each account it involves, as of the current block.
+/// Histories are kept in a ring buffer per account, overwriting the oldest entry once it is full.
+pub fn record_account_activity<T: Config>(activity: AccountActivity) {
+    let entry = AccountHistoryEntry {
+        block_number: <frame_system::Pallet<T>>::block_number().unique_saturated_into(),
+        activity,
+    };
+    for account in entry.activity.accounts() {
+        let count = AccountHistoryCount::get(account);
+        AccountHistory::insert(account, history_slot(count), &entry);
+        AccountHistoryCount::insert(account, count.saturating_add(1));
+    }
+}
+
+/// Return the slot in the ring buffer of an account for the entry with the given sequence number.
+fn history_slot(seq: u64) -> u32 {
+    (seq % ACCOUNT_HISTORY_LENGTH as u64) as u32
+}
+
+/// Return up to a limit of the entries kept in the history of an account since a block, oldest first.
+pub fn get_account_history<T: Config>(
+    account: ChainAccount,
+    from_block: u64,
+    limit: u32,
+) -> Vec<AccountHistoryEntry> {
+    let count = AccountHistoryCount::get(account);
+    let first = count.saturating_sub(ACCOUNT_HISTORY_LENGTH as u64);
+    (first..count)
+        .filter_map(|seq| AccountHistory::get(account, history_slot(seq)))
+        .filter(|entry| entry.block_number >= from_block)
+        .take(limit as usize)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_account_history() {
+        new_test_ext().execute_with(|| {
+            let alice = ChainAccount::Eth([1; 20]);
+            let bob = ChainAccount::Eth([2; 20]);
+            let transfer = |amount| Event::Transfer(Eth, alice, bob, amount);
+
+            System::set_block_number(1);
+            deposit_account_event::<Test>(transfer(1));
+            deposit_account_event::<Test>(Event::SetFeeRate(Factor::from_nominal("1")));
+            System::set_block_number(2);
+            deposit_account_event::<Test>(Event::Locked(Eth, bob, bob, 5));
+
+            let entry = |block_number, activity| AccountHistoryEntry {
+                block_number,
+                activity,
+            };
+            assert_eq!(
+                get_account_history::<Test>(alice, 0, 10),
+                vec![entry(1, AccountActivity::Transfer(Eth, alice, bob, 1))]
+            );
+            assert_eq!(
+                get_account_history::<Test>(bob, 0, 10),
+                vec![
+                    entry(1, AccountActivity::Transfer(Eth, alice, bob, 1)),
+                    entry(2, AccountActivity::Locked(Eth, bob, bob, 5)),
+                ]
+            );
+            assert_eq!(
+                get_account_history::<Test>(bob, 2, 10),
+                vec![entry(2, AccountActivity::Locked(Eth, bob, bob, 5))]
+            );
+            assert_eq!(
+                get_account_history::<Test>(bob, 0, 1),
+                vec![entry(1, AccountActivity::Transfer(Eth, alice, bob, 1))]
+            );
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(Event::Locked(Eth, bob, bob, 5)))
+            );
+        });
+    }
+
+    #[test]
+    fn test_account_history_is_bounded() {
+        new_test_ext().execute_with(|| {
+            let alice = ChainAccount::Eth([1; 20]);
+            let bob = ChainAccount::Eth([2; 20]);
+            let length = ACCOUNT_HISTORY_LENGTH as u128;
+
+            for amount in 0..length + 5 {
+                record_account_activity::<Test>(AccountActivity::Transfer(Eth, alice, bob, amount));
+            }
+
+            let history = get_account_history::<Test>(alice, 0, u32::MAX);
+            assert_eq!(history.len(), ACCOUNT_HISTORY_LENGTH as usize);
+            assert_eq!(
+                history.first().map(|e| e.activity.clone()),
+                Some(AccountActivity::Transfer(Eth, alice, bob, 5))
+            );
+            assert_eq!(
+                history.last().map(|e| e.activity.clone()),
+                Some(AccountActivity::Transfer(Eth, alice, bob, length + 4))
+            );
+        });
+    }
+}
@@ -0,0 +1,334 @@
+use crate::{
+    chains::ChainAsset,
+    factor::Factor,
+    internal,
+    rates::APR,
+    reason::Reason,
+    types::{AssetIndex, CashPrincipalAmount, Quantity, Timestamp, CASH},
+    BorrowIndices, BorrowRates, Config, GlobalCashIndex, LastAccrualTimestamps, LastBlockTimestamp,
+    LastMinerSharePrincipal, ProtocolReserves, SupplyIndices, SupportedAssets, TotalBorrowAssets,
+    TotalCashPrincipal, TotalSupplyAssets,
+};
+use frame_support::storage::{StorageMap, StorageValue};
+use our_std::RuntimeDebug;
+
+/// The interest accrued by a single asset since it was last touched.
+#[derive(Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct AssetAccrual {
+    pub supply_index: AssetIndex,
+    pub borrow_index: AssetIndex,
+    pub cash_principal_borrow_increase: CashPrincipalAmount,
+    pub miner_share_principal: CashPrincipalAmount,
+    pub protocol_reserves: CashPrincipalAmount,
+    pub borrow_rate: APR,
+    pub timestamp: Timestamp,
+}
+
+/// Compute the interest accrued by an asset up until the current block, without changing storage.
+/// Returns none if the asset has already been accrued this block.
+pub fn get_asset_accrual<T: Config>(asset: ChainAsset) -> Result<Option<AssetAccrual>, Reason> {
+    let asset_info = SupportedAssets::get(asset).ok_or(Reason::AssetNotSupported)?;
+    let now = LastBlockTimestamp::get();
+    let last_accrual_timestamp = LastAccrualTimestamps::get(asset);
+    let supply_index = SupplyIndices::get(asset);
+    let borrow_index = BorrowIndices::get(asset);
+    let protocol_reserves = ProtocolReserves::get(asset);
+    let borrow_rate = BorrowRates::get(asset);
+
+    if now == last_accrual_timestamp {
+        return Ok(None);
+    }
+
+    // If the asset has never been accrued, no interest is accrued, just record the timestamp
+    if last_accrual_timestamp == 0 {
+        return Ok(Some(AssetAccrual {
+            supply_index,
+            borrow_index,
+            cash_principal_borrow_increase: CashPrincipalAmount::ZERO,
+            miner_share_principal: CashPrincipalAmount::ZERO,
+            protocol_reserves,
+            borrow_rate,
+            timestamp: now,
+        }));
+    }
+
+    let dt_since_last_accrual = now
+        .checked_sub(last_accrual_timestamp)
+        .ok_or(Reason::TimeTravelNotAllowed)?;
+
+    let (asset_cost, asset_yield) = internal::assets::get_rates::<T>(asset)?;
+    let utilization = internal::assets::get_utilization::<T>(asset)?;
+    let next_borrow_rate = asset_info.rate_model.get_next_borrow_rate(
+        utilization,
+        borrow_rate,
+        dt_since_last_accrual,
+    )?;
+    let asset_units = asset_info.units();
+    let price_asset = internal::assets::get_price_or_zero::<T>(asset_units);
+    let price_cash = internal::assets::get_price_or_zero::<T>(CASH);
+    let price_ratio = Factor::ratio(price_asset, price_cash)?;
+    let cash_index = GlobalCashIndex::get();
+    let cash_borrow_principal_per_asset = cash_index
+        .cash_principal_per_asset(asset_cost.simple(dt_since_last_accrual)?, price_ratio)?;
+    let cash_hold_principal_per_asset = cash_index
+        .cash_principal_per_asset(asset_yield.simple(dt_since_last_accrual)?, price_ratio)?;
+
+    let supply_asset = Quantity::new(TotalSupplyAssets::get(asset), asset_units);
+    let borrow_asset = Quantity::new(TotalBorrowAssets::get(asset), asset_units);
+    let cash_principal_supply_increase =
+        cash_hold_principal_per_asset.cash_principal_amount(supply_asset)?;
+    let cash_principal_borrow_increase =
+        cash_borrow_principal_per_asset.cash_principal_amount(borrow_asset)?;
+    let cash_principal_reserve_increase = CashPrincipalAmount(
+        asset_info
+            .reserve_factor
+            .mul_uint(cash_principal_borrow_increase.0)
+            .div_uint(Factor::ONE.0)?
+            .to_uint()?,
+    );
+
+    Ok(Some(AssetAccrual {
+        supply_index: supply_index.increment(cash_hold_principal_per_asset)?,
+        borrow_index: borrow_index.increment(cash_borrow_principal_per_asset)?,
+        cash_principal_borrow_increase,
+        miner_share_principal: cash_principal_borrow_increase
+            .sub(cash_principal_supply_increase)?
+            .sub(cash_principal_reserve_increase)?,
+        protocol_reserves: protocol_reserves.add(cash_principal_reserve_increase)?,
+        borrow_rate: next_borrow_rate,
+        timestamp: now,
+    }))
+}
+
+/// Return the supply and borrow indices of an asset as if it were accrued up until the current block.
+pub fn get_accrued_indices<T: Config>(
+    asset: ChainAsset,
+) -> Result<(AssetIndex, AssetIndex), Reason> {
+    match get_asset_accrual::<T>(asset)? {
+        Some(accrual) => Ok((accrual.supply_index, accrual.borrow_index)),
+        None => Ok((SupplyIndices::get(asset), BorrowIndices::get(asset))),
+    }
+}
+
+/// Accrue interest on an asset up until the current block, directly in storage.
+/// Must be called before any change to the totals or rate model of an asset outside of the pipeline.
+pub fn accrue_asset<T: Config>(asset: ChainAsset) -> Result<(), Reason> {
+    if let Some(accrual) = get_asset_accrual::<T>(asset)? {
+        let total_cash_principal_new =
+            TotalCashPrincipal::get().add(accrual.cash_principal_borrow_increase)?;
+        let miner_share_principal_new =
+            LastMinerSharePrincipal::get().add(accrual.miner_share_principal)?;
+
+        // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+        put_asset_accrual(asset, accrual);
+        TotalCashPrincipal::put(total_cash_principal_new);
+        LastMinerSharePrincipal::put(miner_share_principal_new);
+    }
+    Ok(())
+}
+
+/// Store the indices, reserves, rate and timestamp of an asset accrual.
+pub fn put_asset_accrual(asset: ChainAsset, accrual: AssetAccrual) {
+    SupplyIndices::insert(asset, accrual.supply_index);
+    BorrowIndices::insert(asset, accrual.borrow_index);
+    ProtocolReserves::insert(asset, accrual.protocol_reserves);
+    BorrowRates::insert(asset, accrual.borrow_rate);
+    LastAccrualTimestamps::insert(asset, accrual.timestamp);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_accrue_asset() {
+        new_test_ext().execute_with(|| {
+            let asset = Eth;
+            let asset_info = AssetInfo {
+                rate_model: InterestRateModel::new_kink(0, 2500, Factor::from_nominal("0.5"), 5000),
+                miner_shares: MinerShares::from_nominal("0.02"),
+                ..AssetInfo::minimal(asset, ETH)
+            };
+            let last_accrual_timestamp = 10;
+            let now = last_accrual_timestamp + MILLISECONDS_PER_YEAR / 4; // 3 months go by
+
+            LastBlockTimestamp::put(now);
+            LastAccrualTimestamps::insert(&asset, last_accrual_timestamp);
+            SupportedAssets::insert(&asset, asset_info);
+            GlobalCashIndex::put(CashIndex::from_nominal("1.123"));
+            SupplyIndices::insert(&asset, AssetIndex::from_nominal("1234"));
+            BorrowIndices::insert(&asset, AssetIndex::from_nominal("1345"));
+            TotalSupplyAssets::insert(asset.clone(), asset_info.as_quantity_nominal("300").value);
+            TotalBorrowAssets::insert(asset.clone(), asset_info.as_quantity_nominal("150").value);
+            TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("450000")); // 450k cash principal
+            pallet_oracle::Prices::insert(
+                asset_info.ticker,
+                1450_000000 as pallet_oracle::types::AssetPrice,
+            ); // $1450 eth
+
+            let supply_index = AssetIndex::from_nominal("1273.542520035618878005");
+            let borrow_index = AssetIndex::from_nominal("1425.699020480854853072");
+            assert_eq!(
+                get_accrued_indices::<Test>(asset),
+                Ok((supply_index, borrow_index))
+            );
+            assert_eq!(SupplyIndices::get(&asset), AssetIndex::from_nominal("1234"));
+
+            assert_eq!(accrue_asset::<Test>(asset), Ok(()));
+            assert_eq!(SupplyIndices::get(&asset), supply_index);
+            assert_eq!(BorrowIndices::get(&asset), borrow_index);
+            assert_eq!(LastAccrualTimestamps::get(&asset), now);
+            assert_eq!(
+                TotalCashPrincipal::get(),
+                CashPrincipalAmount::from_nominal("462104.853072")
+            );
+            assert_eq!(
+                LastMinerSharePrincipal::get(),
+                CashPrincipalAmount(242097062)
+            );
+
+            // Touching the asset again within the same block accrues nothing
+            assert_eq!(accrue_asset::<Test>(asset), Ok(()));
+            assert_eq!(SupplyIndices::get(&asset), supply_index);
+            assert_eq!(BorrowIndices::get(&asset), borrow_index);
+            assert_eq!(
+                TotalCashPrincipal::get(),
+                CashPrincipalAmount::from_nominal("462104.853072")
+            );
+            assert_eq!(
+                LastMinerSharePrincipal::get(),
+                CashPrincipalAmount(242097062)
+            );
+        });
+    }
+
+    #[test]
+    fn test_accrue_asset_reserves() {
+        new_test_ext().execute_with(|| {
+            let asset = Eth;
+            let asset_info = AssetInfo {
+                rate_model: InterestRateModel::new_kink(0, 2500, Factor::from_nominal("0.5"), 5000),
+                miner_shares: MinerShares::from_nominal("0.02"),
+                reserve_factor: ReserveFactor::from_nominal("0.1"),
+                ..AssetInfo::minimal(asset, ETH)
+            };
+            let last_accrual_timestamp = 10;
+            let now = last_accrual_timestamp + MILLISECONDS_PER_YEAR / 4; // 3 months go by
+
+            LastBlockTimestamp::put(now);
+            LastAccrualTimestamps::insert(&asset, last_accrual_timestamp);
+            SupportedAssets::insert(&asset, asset_info);
+            GlobalCashIndex::put(CashIndex::from_nominal("1.123"));
+            SupplyIndices::insert(&asset, AssetIndex::from_nominal("1234"));
+            BorrowIndices::insert(&asset, AssetIndex::from_nominal("1345"));
+            TotalSupplyAssets::insert(asset.clone(), asset_info.as_quantity_nominal("300").value);
+            TotalBorrowAssets::insert(asset.clone(), asset_info.as_quantity_nominal("150").value);
+            TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("450000")); // 450k cash principal
+            ProtocolReserves::insert(&asset, CashPrincipalAmount::from_nominal("1"));
+            pallet_oracle::Prices::insert(
+                asset_info.ticker,
+                1450_000000 as pallet_oracle::types::AssetPrice,
+            ); // $1450 eth
+
+            let accrual = get_asset_accrual::<Test>(asset)
+                .expect("get_asset_accrual failed")
+                .expect("nothing to accrue");
+            assert_eq!(
+                accrual.cash_principal_borrow_increase,
+                CashPrincipalAmount::from_nominal("12104.853072")
+            );
+            assert_eq!(
+                accrual.protocol_reserves,
+                CashPrincipalAmount::from_nominal("1211.485307")
+            );
+
+            // Borrowers pay the same, suppliers and miners share what is left after reserves
+            assert_eq!(accrue_asset::<Test>(asset), Ok(()));
+            assert_eq!(
+                BorrowIndices::get(&asset),
+                AssetIndex::from_nominal("1425.699020480854853072")
+            );
+            assert_eq!(
+                TotalCashPrincipal::get(),
+                CashPrincipalAmount::from_nominal("462104.853072")
+            );
+            assert_eq!(
+                ProtocolReserves::get(&asset),
+                CashPrincipalAmount::from_nominal("1211.485307")
+            );
+            assert!(
+                SupplyIndices::get(&asset) < AssetIndex::from_nominal("1273.542520035618878005")
+            );
+        });
+    }
+
+    #[test]
+    fn test_accrue_asset_dynamic_rate() {
+        new_test_ext().execute_with(|| {
+            let asset = Eth;
+            let fixed_info = AssetInfo {
+                rate_model: InterestRateModel::Fixed { rate: 2000.into() },
+                ..AssetInfo::minimal(asset, ETH)
+            };
+            let dynamic_info = AssetInfo {
+                rate_model: InterestRateModel::Dynamic {
+                    target_utilization: Factor::from_nominal("0.8"),
+                    min_rate: 100.into(),
+                    max_rate: 3000.into(),
+                    max_change: 1000.into(),
+                },
+                ..fixed_info
+            };
+            let last_accrual_timestamp = 10;
+            let now = last_accrual_timestamp + MILLISECONDS_PER_YEAR / 4; // 3 months go by
+
+            LastBlockTimestamp::put(now);
+            LastAccrualTimestamps::insert(&asset, last_accrual_timestamp);
+            BorrowRates::insert(&asset, APR::from(2000));
+            TotalSupplyAssets::insert(asset, fixed_info.as_quantity_nominal("300").value);
+            TotalBorrowAssets::insert(asset, fixed_info.as_quantity_nominal("120").value);
+            pallet_oracle::Prices::insert(
+                fixed_info.ticker,
+                1450_000000 as pallet_oracle::types::AssetPrice,
+            ); // $1450 eth
+
+            SupportedAssets::insert(&asset, fixed_info);
+            let fixed = get_asset_accrual::<Test>(asset)
+                .expect("get_asset_accrual failed")
+                .expect("nothing to accrue");
+            SupportedAssets::insert(&asset, dynamic_info);
+            let dynamic = get_asset_accrual::<Test>(asset)
+                .expect("get_asset_accrual failed")
+                .expect("nothing to accrue");
+
+            // Interest accrues at the current rate, which then moves towards the target utilization
+            assert_eq!(dynamic.borrow_index, fixed.borrow_index);
+            assert_eq!(dynamic.supply_index, fixed.supply_index);
+            assert_eq!(fixed.borrow_rate, APR::from(2000));
+            assert_eq!(dynamic.borrow_rate, APR::from(1875));
+
+            assert_eq!(accrue_asset::<Test>(asset), Ok(()));
+            assert_eq!(BorrowRates::get(&asset), APR::from(1875));
+        });
+    }
+
+    #[test]
+    fn test_accrue_asset_first_touch() {
+        new_test_ext().execute_with(|| {
+            let asset_info = AssetInfo::minimal(Eth, ETH);
+            SupportedAssets::insert(&Eth, asset_info);
+            SupplyIndices::insert(&Eth, AssetIndex::from_nominal("1234"));
+            LastBlockTimestamp::put(1000);
+
+            assert_eq!(accrue_asset::<Test>(Eth), Ok(()));
+            assert_eq!(LastAccrualTimestamps::get(&Eth), 1000);
+            assert_eq!(SupplyIndices::get(&Eth), AssetIndex::from_nominal("1234"));
+            assert_eq!(LastMinerSharePrincipal::get(), CashPrincipalAmount::ZERO);
+
+            LastAccrualTimestamps::insert(&Eth, 2000);
+            assert_eq!(accrue_asset::<Test>(Eth), Err(Reason::TimeTravelNotAllowed));
+        });
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{
+    chains::{self, ChainSignature},
+    core::{get_recent_timestamp, get_validator_set, recover_validator, validator_sign},
+    error,
+    events::fetch_anchor_tick,
+    log,
+    params::ANCHOR_POLL_INTERVAL_BLOCKS,
+    reason::Reason,
+    types::{SignersSet, Ticker, Timestamp, ValidatorIdentity},
+    AnchorReports, Call, Config, QuorumThreshold,
+};
+use codec::Encode;
+use frame_support::storage::{
+    IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
+};
+use frame_system::offchain::SubmitTransaction;
+use our_std::{collections::btree_map::BTreeMap, vec::Vec};
+use pallet_oracle::{
+    anchor::{check_anchor_price, price_at_tick, put_anchor_price},
+    types::{AnchorConfig, AnchorReport, AssetPrice},
+    AnchorConfigs, AnchorPrices,
+};
+use sp_runtime::traits::UniqueSaturatedInto;
+
+/// Observe the anchor price of every ticker with an anchor pool.
+/// Every validator reports, as an anchor price is only posted once a quorum agrees on it.
+pub fn track_anchor_prices<T: Config>(block_number: T::BlockNumber) -> Result<(), Reason> {
+    let block_number: u64 = block_number.unique_saturated_into();
+    if block_number % ANCHOR_POLL_INTERVAL_BLOCKS != 0 {
+        return Ok(());
+    }
+
+    let configs: Vec<_> = AnchorConfigs::iter().collect();
+    if configs.is_empty() {
+        return Ok(());
+    }
+
+    let mut prices = Vec::with_capacity(configs.len());
+    for (ticker, config) in configs {
+        match fetch_anchor_price(&config) {
+            Ok(price) => prices.push((ticker, price)),
+            Err(err) => error!("Failed to observe anchor price for {:?}: {:?}", ticker, err),
+        }
+    }
+
+    let report = AnchorReport {
+        timestamp: get_recent_timestamp::<T>()?,
+        prices,
+    };
+    submit_anchor_report::<T>(&report)
+}
+
+/// Read the current anchor price from the Uniswap V3 pool of the given config.
+pub fn fetch_anchor_price(config: &AnchorConfig) -> Result<AssetPrice, Reason> {
+    let tick = fetch_anchor_tick(&config.pool, config.twap_period)?;
+    Ok(price_at_tick(config, tick)?)
+}
+
+/// Submit the anchor prices the worker observed.
+pub fn submit_anchor_report<T: Config>(report: &AnchorReport) -> Result<(), Reason> {
+    if report.prices.len() > 0 {
+        log!("Submitting anchor prices extrinsic: {:?}", report);
+        let signature = validator_sign::<T>(&report.encode()[..])?;
+        let call = Call::receive_anchor_prices(report.clone(), signature);
+        if let Err(e) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
+            log!("Error while submitting anchor prices: {:?}", e);
+            return Err(Reason::FailedToSubmitExtrinsic);
+        }
+    }
+    Ok(())
+}
+
+/// Return the median of the anchor prices reported for a ticker by current validators, once a quorum has reported.
+/// The given report of a validator counts in place of any it reported before.
+/// Only reports observed since the anchor price was last posted count, and the earliest observation is used as its time.
+fn get_agreed_anchor_price(
+    ticker: Ticker,
+    validator_set: &SignersSet,
+    quorum: usize,
+    reporter: &ValidatorIdentity,
+    report: (AssetPrice, Timestamp),
+) -> Option<(AssetPrice, Timestamp)> {
+    let last_updated = AnchorPrices::get(ticker).map(|(_, timestamp)| timestamp);
+    let mut reports: Vec<(AssetPrice, Timestamp)> = AnchorReports::iter_prefix(ticker)
+        .filter(|(validator, _)| validator != reporter)
+        .chain(our_std::iter::once((reporter.clone(), report)))
+        .filter(|(validator, (_, timestamp))| {
+            validator_set.contains(validator) && Some(*timestamp) > last_updated
+        })
+        .map(|(_, report)| report)
+        .collect();
+    if reports.is_empty() || reports.len() < quorum {
+        return None;
+    }
+
+    reports.sort();
+    let timestamp = reports.iter().map(|(_, timestamp)| *timestamp).min()?;
+    Some((reports[(reports.len() - 1) / 2].0, timestamp))
+}
+
+/// Receive the anchor prices observed by a validator, recording them as its report for each ticker.
+/// Once a quorum of validators has reported a ticker, the median of their prices is posted to the oracle.
+pub fn receive_anchor_prices<T: Config>(
+    report: AnchorReport,
+    signature: ChainSignature,
+) -> Result<(), Reason> {
+    let validator = recover_validator::<T>(&report.encode(), signature)?;
+    let validator_set = get_validator_set::<T>()?;
+    let quorum = chains::super_majority(validator_set.len(), QuorumThreshold::get());
+
+    // a later price for the same ticker replaces an earlier one
+    let prices: BTreeMap<Ticker, AssetPrice> = report.prices.into_iter().collect();
+    let mut agreed = Vec::new();
+    for (ticker, price) in prices.iter() {
+        check_anchor_price::<T>(*ticker, report.timestamp)?;
+        let reported = (*price, report.timestamp);
+        if let Some((price, timestamp)) = get_agreed_anchor_price(
+            *ticker,
+            &validator_set,
+            quorum,
+            &validator.substrate_id,
+            reported,
+        ) {
+            check_anchor_price::<T>(*ticker, timestamp)?;
+            agreed.push((*ticker, price, timestamp));
+        }
+    }
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    for (ticker, price) in prices {
+        AnchorReports::insert(ticker, &validator.substrate_id, (price, report.timestamp));
+    }
+    for (ticker, price, timestamp) in agreed {
+        put_anchor_price::<T>(ticker, price, timestamp);
+        AnchorReports::remove_prefix(ticker);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use pallet_oracle::error::OracleError;
+    use sp_runtime::Permill;
+
+    #[test]
+    fn test_receive_anchor_prices() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let ticker = ETH.ticker;
+            let config = AnchorConfig {
+                pool: [0x8a; 20],
+                base_is_token0: false,
+                base_decimals: 18,
+                quote_decimals: 6,
+                twap_period: 1800,
+                band_bps: 1000,
+            };
+            let report = AnchorReport {
+                timestamp: 1000,
+                prices: vec![(ticker, 2000_000000)],
+            };
+            let signature = validator_a_sign(&report.encode()).unwrap();
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1000);
+
+            assert_eq!(
+                receive_anchor_prices::<Test>(report.clone(), signature),
+                Err(Reason::OracleError(OracleError::NoAnchor))
+            );
+            assert_eq!(
+                pallet_oracle::anchor::set_anchor_config::<Test>(ticker, Some(config)),
+                Ok(())
+            );
+            assert_eq!(
+                receive_anchor_prices::<Test>(
+                    AnchorReport {
+                        timestamp: 2000,
+                        ..report.clone()
+                    },
+                    signature
+                ),
+                Err(Reason::UnknownValidator)
+            );
+            assert_eq!(
+                receive_anchor_prices::<Test>(report.clone(), signature),
+                Ok(())
+            );
+
+            // a single validator cannot set the anchor price on its own
+            assert_eq!(AnchorPrices::get(ticker), None);
+            assert_eq!(
+                AnchorReports::get(ticker, val_a().substrate_id),
+                Some((2000_000000, 1000))
+            );
+
+            let report_b = AnchorReport {
+                timestamp: 1100,
+                prices: vec![(ticker, 2100_000000)],
+            };
+            let signature_b = validator_b_sign(&report_b.encode()).unwrap();
+            assert_eq!(receive_anchor_prices::<Test>(report_b, signature_b), Ok(()));
+            assert_eq!(AnchorPrices::get(ticker), Some((2000_000000, 1000)));
+            assert_eq!(AnchorReports::iter_prefix(ticker).count(), 0);
+
+            assert_eq!(
+                receive_anchor_prices::<Test>(report, signature),
+                Err(Reason::OracleError(OracleError::StalePrice))
+            );
+        });
+    }
+
+    #[test]
+    fn test_receive_anchor_prices_quorum_threshold() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let ticker = ETH.ticker;
+            let config = AnchorConfig {
+                pool: [0x8a; 20],
+                base_is_token0: false,
+                base_decimals: 18,
+                quote_decimals: 6,
+                twap_period: 1800,
+                band_bps: 1000,
+            };
+            assert_ok!(pallet_oracle::anchor::set_anchor_config::<Test>(
+                ticker,
+                Some(config)
+            ));
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1000);
+
+            // with a threshold of half the validators, one of two is a quorum
+            QuorumThreshold::put(Permill::from_percent(50));
+            let report = AnchorReport {
+                timestamp: 1000,
+                prices: vec![(ticker, 2000_000000), (ticker, 2050_000000)],
+            };
+            let signature = validator_a_sign(&report.encode()).unwrap();
+            assert_eq!(receive_anchor_prices::<Test>(report, signature), Ok(()));
+            assert_eq!(AnchorPrices::get(ticker), Some((2050_000000, 1000)));
+            assert_eq!(AnchorReports::iter_prefix(ticker).count(), 0);
+        });
+    }
+}
@@ -1,15 +1,21 @@
 use crate::{
     chains::ChainAsset,
-    rates::{InterestRateModel, APR},
-    reason::Reason,
+    internal,
+    params::MAX_ASSET_GROUP_SIZE,
+    rates::{InterestRateModel, RatesError, APR},
+    reason::{MathError, Reason},
+    require,
+    symbol::{Symbol, CASH},
     types::{
-        AssetAmount, AssetInfo, AssetQuantity, CashPrincipalAmount, Factor, LiquidityFactor,
-        Quantity, USDQuantity, Units,
+        AssetAmount, AssetInfo, AssetQuantity, CashOrChainAsset, CashPrincipalAmount, Factor,
+        LiquidityFactor, Quantity, ReserveFactor, Ticker, USDQuantity, Units,
     },
-    Config, Event, GlobalCashIndex, Module, SupportedAssets, TotalBorrowAssets, TotalSupplyAssets,
+    AssetGroups, AssetSymbols, BorrowRates, Config, Event, GlobalCashIndex, LastAccrualTimestamps,
+    LastBlockTimestamp, Module, PriceKeyMapping, SupportedAssets, TotalBorrowAssets,
+    TotalSupplyAssets,
 };
 use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
-use pallet_oracle::types::Price;
+use pallet_oracle::{error::OracleError, types::Price};
 
 /// Set the liquidity factor for a supported asset.
 pub fn set_liquidity_factor<T: Config>(
@@ -24,6 +30,7 @@ pub fn set_liquidity_factor<T: Config>(
 }
 
 /// Set the rate model for a supported asset.
+/// Interest is accrued under the previous model first, and a dynamic model starts from the rate it left off at.
 pub fn set_rate_model<T: Config>(
     asset: ChainAsset,
     model: InterestRateModel,
@@ -36,13 +43,160 @@ pub fn set_rate_model<T: Config>(
     })
 }
 
+/// Set the reserve factor for a supported asset.
+/// Together with the miner shares, it may not take more than all of the interest paid by borrowers.
+pub fn set_reserve_factor<T: Config>(
+    asset: ChainAsset,
+    factor: ReserveFactor,
+) -> Result<(), Reason> {
+    let asset_info = get_asset::<T>(asset)?;
+    match asset_info.miner_shares.0.checked_add(factor.0) {
+        Some(total) if total <= Factor::ONE.0 => (),
+        _ => return Err(RatesError::ReserveFactorTooHigh.into()),
+    }
+    support_asset::<T>(AssetInfo {
+        reserve_factor: factor,
+        ..asset_info
+    })
+}
+
 /// Support an asset by defining its metadata.
+/// Interest on an already supported asset is accrued under its previous metadata first.
+/// The rate model and liquidity factor of a grouped asset are shared by the rest of its group.
 pub fn support_asset<T: Config>(asset_info: AssetInfo) -> Result<(), Reason> {
+    check_price_key::<T>(&asset_info)?;
+    if let Some(symbol) = AssetSymbols::get(&asset_info.asset) {
+        let current = get_asset::<T>(asset_info.asset)?;
+        if asset_info.symbol != symbol || asset_info.units() != current.units() {
+            return Err(Reason::InvalidAssetGroup);
+        }
+    }
+    for asset in get_asset_group::<T>(asset_info.asset) {
+        if asset == asset_info.asset {
+            update_asset::<T>(asset_info)?;
+        } else {
+            update_asset::<T>(AssetInfo {
+                rate_model: asset_info.rate_model,
+                liquidity_factor: asset_info.liquidity_factor,
+                ..get_asset::<T>(asset)?
+            })?;
+        }
+    }
+    Ok(())
+}
+
+fn update_asset<T: Config>(asset_info: AssetInfo) -> Result<(), Reason> {
+    if SupportedAssets::contains_key(&asset_info.asset) {
+        internal::accrual::accrue_asset::<T>(asset_info.asset)?;
+    } else {
+        LastAccrualTimestamps::insert(&asset_info.asset, LastBlockTimestamp::get());
+    }
     SupportedAssets::insert(&asset_info.asset, asset_info);
     <Module<T>>::deposit_event(Event::AssetModified(asset_info));
     Ok(())
 }
 
+/// Group the same asset on several chains under its symbol, or ungroup the symbol given no assets.
+/// Grouped assets must share the symbol and units, and take the rate model and liquidity factor of the first.
+pub fn set_asset_group<T: Config>(symbol: Symbol, assets: Vec<ChainAsset>) -> Result<(), Reason> {
+    if assets.len() > MAX_ASSET_GROUP_SIZE {
+        return Err(Reason::InvalidAssetGroup);
+    }
+    let infos = assets
+        .iter()
+        .map(|asset| get_asset::<T>(*asset))
+        .collect::<Result<Vec<AssetInfo>, Reason>>()?;
+    if let Some(first) = infos.first() {
+        for (i, info) in infos.iter().enumerate() {
+            let other_group = AssetSymbols::get(&info.asset).map_or(false, |s| s != symbol);
+            let duplicate = infos[..i].iter().any(|other| other.asset == info.asset);
+            if info.symbol != symbol || info.units() != first.units() || other_group || duplicate {
+                return Err(Reason::InvalidAssetGroup);
+            }
+        }
+    }
+
+    for asset in &assets {
+        internal::accrual::accrue_asset::<T>(*asset)?;
+    }
+
+    // * WARNING begin storage - all checks must happen above * //
+
+    for asset in AssetGroups::take(symbol) {
+        AssetSymbols::remove(&asset);
+    }
+    if !assets.is_empty() {
+        for asset in &assets {
+            AssetSymbols::insert(asset, symbol);
+        }
+        AssetGroups::insert(symbol, &assets);
+    }
+    if let Some(first) = infos.first() {
+        support_asset::<T>(*first)?;
+    }
+    <Module<T>>::deposit_event(Event::SetAssetGroup(symbol, assets));
+    Ok(())
+}
+
+/// Set the market an oracle ticker is expected to price, or clear it given none.
+/// Every asset already listed with the ticker must belong to the market, which must itself use the ticker.
+pub fn set_price_key<T: Config>(
+    ticker: Ticker,
+    market: Option<CashOrChainAsset>,
+) -> Result<(), Reason> {
+    if let Some(market) = market {
+        let priced = match market {
+            CashOrChainAsset::Cash => {
+                require!(ticker == CASH.ticker, Reason::PriceKeyMismatch);
+                vec![]
+            }
+            CashOrChainAsset::ChainAsset(asset) => {
+                require!(
+                    get_asset::<T>(asset)?.ticker == ticker,
+                    Reason::PriceKeyMismatch
+                );
+                get_asset_group::<T>(asset)
+            }
+        };
+        for (asset, info) in SupportedAssets::iter() {
+            require!(
+                info.ticker != ticker || priced.contains(&asset),
+                Reason::PriceKeyMismatch
+            );
+        }
+    }
+
+    // * WARNING begin storage - all checks must happen above * //
+
+    match market {
+        Some(market) => PriceKeyMapping::insert(ticker, market),
+        None => PriceKeyMapping::remove(ticker),
+    }
+    <Module<T>>::deposit_event(Event::SetPriceKey(ticker, market));
+    Ok(())
+}
+
+/// Check that the ticker of an asset is not expected to price some other market.
+fn check_price_key<T: Config>(asset_info: &AssetInfo) -> Result<(), Reason> {
+    match PriceKeyMapping::get(asset_info.ticker) {
+        None => Ok(()),
+        Some(CashOrChainAsset::ChainAsset(asset))
+            if get_asset_group::<T>(asset).contains(&asset_info.asset) =>
+        {
+            Ok(())
+        }
+        Some(_) => Err(Reason::PriceKeyMismatch),
+    }
+}
+
+/// Return the assets grouped together with an asset, including itself.
+pub fn get_asset_group<T: Config>(asset: ChainAsset) -> Vec<ChainAsset> {
+    match AssetSymbols::get(&asset) {
+        Some(symbol) => AssetGroups::get(symbol),
+        None => vec![asset],
+    }
+}
+
 /// Return the full asset info for an asset.
 pub fn get_asset<T: Config>(asset: ChainAsset) -> Result<AssetInfo, Reason> {
     Ok(SupportedAssets::get(asset).ok_or(Reason::AssetNotSupported)?)
@@ -53,6 +207,15 @@ pub fn get_price<T: pallet_oracle::Config>(units: Units) -> Result<Price, Reason
     pallet_oracle::get_price_by_ticker::<T>(units.ticker).ok_or(Reason::NoPrice)
 }
 
+/// Return the USD price associated with the given units, if it is recent enough to borrow against.
+pub fn get_fresh_price<T: pallet_oracle::Config>(units: Units) -> Result<Price, Reason> {
+    pallet_oracle::check_price_fresh::<T>(units.ticker).map_err(|err| match err {
+        OracleError::StalePrice => Reason::StalePrice,
+        err => err.into(),
+    })?;
+    get_price::<T>(units)
+}
+
 /// Return the price or zero if not given
 pub fn get_price_or_zero<T: pallet_oracle::Config>(units: Units) -> Price {
     pallet_oracle::get_price_by_ticker::<T>(units.ticker).unwrap_or(Price::new(units.ticker, 0))
@@ -75,11 +238,19 @@ pub fn get_value<T: Config>(amount: AssetQuantity) -> Result<USDQuantity, Reason
     Ok(amount.mul_price(get_price::<T>(amount.units)?)?)
 }
 
-/// Return the current utilization for the asset.
+/// Return the current utilization for the asset, across its whole group.
 pub fn get_utilization<T: Config>(asset: ChainAsset) -> Result<Factor, Reason> {
     let _info = SupportedAssets::get(asset).ok_or(Reason::AssetNotSupported)?;
-    let total_supply = TotalSupplyAssets::get(asset);
-    let total_borrow = TotalBorrowAssets::get(asset);
+    let mut total_supply: AssetAmount = 0;
+    let mut total_borrow: AssetAmount = 0;
+    for member in get_asset_group::<T>(asset) {
+        total_supply = total_supply
+            .checked_add(TotalSupplyAssets::get(member))
+            .ok_or(MathError::Overflow)?;
+        total_borrow = total_borrow
+            .checked_add(TotalBorrowAssets::get(member))
+            .ok_or(MathError::Overflow)?;
+    }
     Ok(crate::rates::get_utilization(total_supply, total_borrow)?)
 }
 
@@ -87,9 +258,12 @@ pub fn get_utilization<T: Config>(asset: ChainAsset) -> Result<Factor, Reason> {
 pub fn get_rates<T: Config>(asset: ChainAsset) -> Result<(APR, APR), Reason> {
     let info = SupportedAssets::get(asset).ok_or(Reason::AssetNotSupported)?;
     let utilization = get_utilization::<T>(asset)?;
-    Ok(info
-        .rate_model
-        .get_rates(utilization, APR::ZERO, info.miner_shares)?)
+    Ok(info.rate_model.get_rates(
+        utilization,
+        BorrowRates::get(asset),
+        info.miner_shares,
+        info.reserve_factor,
+    )?)
 }
 
 /// Return the current list of assets.
@@ -168,6 +342,54 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_set_rate_model_accrues_interest() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+            let now = 10 + MILLISECONDS_PER_YEAR / 4;
+            LastBlockTimestamp::put(now);
+            LastAccrualTimestamps::insert(&Eth, 10);
+            TotalSupplyAssets::insert(&Eth, eth.as_quantity_nominal("300").value);
+            TotalBorrowAssets::insert(&Eth, eth.as_quantity_nominal("150").value);
+            let accrual = internal::accrual::get_asset_accrual::<Test>(Eth)
+                .expect("get_asset_accrual failed")
+                .expect("nothing to accrue");
+
+            assert_ok!(super::set_rate_model::<Test>(
+                Eth,
+                InterestRateModel::Fixed {
+                    rate: APR::from_nominal("0.10")
+                }
+            ));
+            assert_eq!(SupplyIndices::get(&Eth), accrual.supply_index);
+            assert_eq!(BorrowIndices::get(&Eth), accrual.borrow_index);
+            assert_eq!(LastAccrualTimestamps::get(&Eth), now);
+        });
+    }
+
+    #[test]
+    fn test_set_reserve_factor() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                super::set_reserve_factor::<Test>(Eth, Factor::from_nominal("0.1")),
+                Err(Reason::AssetNotSupported)
+            );
+            assert_ok!(init_eth_asset());
+            assert_eq!(
+                super::set_reserve_factor::<Test>(Eth, Factor::from_nominal("0.96")),
+                Err(Reason::RatesError(RatesError::ReserveFactorTooHigh))
+            );
+            assert_ok!(super::set_reserve_factor::<Test>(
+                Eth,
+                Factor::from_nominal("0.1")
+            ));
+            assert_eq!(
+                SupportedAssets::get(Eth).unwrap().reserve_factor,
+                Factor::from_nominal("0.1")
+            );
+        });
+    }
+
     #[test]
     fn test_support_asset() {
         new_test_ext().execute_with(|| {
@@ -274,4 +496,165 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_set_asset_group() {
+        new_test_ext().execute_with(|| {
+            let matic_usdc = AssetInfo {
+                asset: ChainAsset::Matic([0xcc; 20]),
+                liquidity_factor: LiquidityFactor::from_nominal("0.5"),
+                rate_model: InterestRateModel::Fixed {
+                    rate: APR::from_nominal("0.10"),
+                },
+                ..usdc
+            };
+            assert_ok!(super::support_asset::<Test>(usdc));
+            assert_ok!(super::support_asset::<Test>(matic_usdc));
+            assert_ok!(super::support_asset::<Test>(wbtc));
+
+            assert_eq!(
+                super::set_asset_group::<Test>(usdc.symbol, vec![Usdc, Wbtc]),
+                Err(Reason::InvalidAssetGroup)
+            );
+            assert_eq!(
+                super::set_asset_group::<Test>(usdc.symbol, vec![Usdc, Usdc]),
+                Err(Reason::InvalidAssetGroup)
+            );
+            assert_eq!(
+                super::set_asset_group::<Test>(usdc.symbol, vec![Usdc, Eth]),
+                Err(Reason::AssetNotSupported)
+            );
+
+            assert_ok!(super::set_asset_group::<Test>(
+                usdc.symbol,
+                vec![Usdc, matic_usdc.asset]
+            ));
+            assert_eq!(AssetSymbols::get(matic_usdc.asset), Some(usdc.symbol));
+            assert_eq!(super::get_asset_group::<Test>(Wbtc), vec![Wbtc]);
+            let grouped = SupportedAssets::get(matic_usdc.asset).unwrap();
+            assert_eq!(grouped.rate_model, usdc.rate_model);
+            assert_eq!(grouped.liquidity_factor, usdc.liquidity_factor);
+
+            // changes to one asset in the group apply to all of them
+            let model = InterestRateModel::Fixed {
+                rate: APR::from_nominal("0.05"),
+            };
+            assert_ok!(super::set_rate_model::<Test>(matic_usdc.asset, model));
+            assert_eq!(SupportedAssets::get(Usdc).unwrap().rate_model, model);
+            assert_eq!(
+                super::support_asset::<Test>(AssetInfo {
+                    decimals: 18,
+                    ..usdc
+                }),
+                Err(Reason::InvalidAssetGroup)
+            );
+
+            // utilization is shared across the group
+            TotalSupplyAssets::insert(Usdc, 100);
+            TotalBorrowAssets::insert(matic_usdc.asset, 50);
+            assert_eq!(
+                super::get_utilization::<Test>(Usdc),
+                Ok(Factor::from_nominal("0.5"))
+            );
+            assert_eq!(
+                super::get_utilization::<Test>(matic_usdc.asset),
+                Ok(Factor::from_nominal("0.5"))
+            );
+
+            // positions in the group are aggregated by symbol
+            let account = ChainAccount::Eth([1; 20]);
+            AssetBalances::insert(Usdc, account, 100_000000);
+            AssetBalances::insert(matic_usdc.asset, account, -40_000000);
+            AssetsWithNonZeroBalance::insert(account, Usdc, ());
+            AssetsWithNonZeroBalance::insert(account, matic_usdc.asset, ());
+            let portfolio = pipeline::load_portfolio::<Test>(account).unwrap();
+            assert_eq!(portfolio.positions.len(), 1);
+            assert_eq!(portfolio.positions[0].1, usdc.as_balance(60_000000));
+
+            assert_ok!(super::set_asset_group::<Test>(usdc.symbol, vec![]));
+            assert_eq!(AssetSymbols::get(Usdc), None);
+            assert_eq!(AssetGroups::get(usdc.symbol), vec![]);
+            let portfolio = pipeline::load_portfolio::<Test>(account).unwrap();
+            assert_eq!(portfolio.positions.len(), 2);
+        });
+    }
+
+    #[test]
+    fn test_set_price_key() {
+        new_test_ext().execute_with(|| {
+            let matic_eth = AssetInfo {
+                asset: ChainAsset::Matic([0xee; 20]),
+                ..eth
+            };
+            assert_ok!(super::support_asset::<Test>(eth));
+            assert_ok!(super::support_asset::<Test>(matic_eth));
+            assert_ok!(super::support_asset::<Test>(wbtc));
+
+            assert_eq!(
+                super::set_price_key::<Test>(wbtc.ticker, Some(CashOrChainAsset::ChainAsset(Eth))),
+                Err(Reason::PriceKeyMismatch)
+            );
+            assert_eq!(
+                super::set_price_key::<Test>(eth.ticker, Some(CashOrChainAsset::Cash)),
+                Err(Reason::PriceKeyMismatch)
+            );
+            assert_eq!(
+                super::set_price_key::<Test>(eth.ticker, Some(CashOrChainAsset::ChainAsset(Usdc))),
+                Err(Reason::AssetNotSupported)
+            );
+            // the ticker also prices the asset on matic, until they are grouped
+            assert_eq!(
+                super::set_price_key::<Test>(eth.ticker, Some(CashOrChainAsset::ChainAsset(Eth))),
+                Err(Reason::PriceKeyMismatch)
+            );
+
+            assert_ok!(super::set_asset_group::<Test>(
+                eth.symbol,
+                vec![Eth, matic_eth.asset]
+            ));
+            assert_ok!(super::set_price_key::<Test>(
+                eth.ticker,
+                Some(CashOrChainAsset::ChainAsset(Eth))
+            ));
+            assert_eq!(
+                PriceKeyMapping::get(eth.ticker),
+                Some(CashOrChainAsset::ChainAsset(Eth))
+            );
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::SetPriceKey(
+                    eth.ticker,
+                    Some(CashOrChainAsset::ChainAsset(Eth))
+                )))
+            );
+            assert_ok!(super::set_price_key::<Test>(
+                crate::symbol::CASH.ticker,
+                Some(CashOrChainAsset::Cash)
+            ));
+
+            // assets listed with a mapped ticker must belong to the market it prices
+            assert_ok!(super::support_asset::<Test>(matic_eth));
+            assert_eq!(
+                super::support_asset::<Test>(AssetInfo {
+                    asset: ChainAsset::Eth([0xef; 20]),
+                    ..eth
+                }),
+                Err(Reason::PriceKeyMismatch)
+            );
+            assert_eq!(
+                super::support_asset::<Test>(AssetInfo {
+                    ticker: crate::symbol::CASH.ticker,
+                    ..usdc
+                }),
+                Err(Reason::PriceKeyMismatch)
+            );
+
+            assert_ok!(super::set_price_key::<Test>(eth.ticker, None));
+            assert_eq!(PriceKeyMapping::get(eth.ticker), None);
+            assert_ok!(super::support_asset::<Test>(AssetInfo {
+                asset: ChainAsset::Eth([0xef; 20]),
+                ..eth
+            }));
+        });
+    }
 }
@@ -0,0 +1,52 @@
+use crate::{
+    chains::ChainAsset,
+    internal,
+    reason::Reason,
+    types::{AssetAmount, AssetInfo},
+    Config,
+};
+
+/// Set the maximum total amount of an asset which may be borrowed.
+/// The cap only applies to new borrowing, a cap of zero winds the market down without blocking repayments.
+pub fn set_borrow_cap<T: Config>(asset: ChainAsset, cap: AssetAmount) -> Result<(), Reason> {
+    let asset_info = internal::assets::get_asset::<T>(asset)?;
+    internal::assets::support_asset::<T>(AssetInfo {
+        borrow_cap: cap,
+        ..asset_info
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::*, SupportedAssets};
+    use frame_support::storage::StorageMap;
+
+    #[test]
+    fn test_set_borrow_cap() {
+        new_test_ext().execute_with(|| {
+            let asset = ChainAsset::Eth([100; 20]);
+            assert_eq!(
+                set_borrow_cap::<Test>(asset, 1000),
+                Err(Reason::AssetNotSupported)
+            );
+
+            let asset_info = AssetInfo::minimal(asset, USD);
+            assert_eq!(asset_info.borrow_cap, AssetAmount::MAX);
+            SupportedAssets::insert(asset, asset_info);
+            assert_eq!(set_borrow_cap::<Test>(asset, 1000), Ok(()));
+
+            let new_asset_info = AssetInfo {
+                borrow_cap: 1000,
+                ..asset_info
+            };
+            assert_eq!(SupportedAssets::get(asset), Some(new_asset_info));
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::AssetModified(
+                    new_asset_info
+                )))
+            );
+        });
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{
+    chains::{ChainId, ChainStatus},
+    internal,
+    reason::Reason,
+    require, ChainStatuses, Config, Event, Module,
+};
+use frame_support::storage::StorageMap;
+
+/// Set the status of an underlying chain, e.g. to halt a chain whose starport is compromised.
+/// Events which failed to apply while the chain was halted are retried once it is ingested again.
+pub fn set_chain_status<T: Config>(chain_id: ChainId, status: ChainStatus) -> Result<(), Reason> {
+    require!(chain_id != ChainId::Gate, Reason::InvalidChain);
+    ChainStatuses::insert(chain_id, status);
+    if is_ingesting(chain_id) {
+        internal::events::reschedule_failed_events::<T>(chain_id, Reason::ChainDisabled);
+    }
+    <Module<T>>::deposit_event(Event::SetChainStatus(chain_id, status));
+    Ok(())
+}
+
+/// Whether events from the chain may be applied.
+pub fn is_ingesting(chain_id: ChainId) -> bool {
+    ChainStatuses::get(chain_id) != ChainStatus::Halted
+}
+
+/// Whether anything may be extracted to the chain, or notices sent to it.
+pub fn is_active(chain_id: ChainId) -> bool {
+    ChainStatuses::get(chain_id) == ChainStatus::Active
+}
+
+pub fn require_ingesting(chain_id: ChainId) -> Result<(), Reason> {
+    require!(is_ingesting(chain_id), Reason::ChainDisabled);
+    Ok(())
+}
+
+pub fn require_active(chain_id: ChainId) -> Result<(), Reason> {
+    require!(is_active(chain_id), Reason::ChainDisabled);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{chains::*, tests::*, FailedChainBlockEvents};
+    use frame_support::storage::StorageValue;
+
+    #[test]
+    fn test_set_chain_status() {
+        new_test_ext().execute_with(|| {
+            assert!(is_active(ChainId::Eth));
+            assert_eq!(
+                set_chain_status::<Test>(ChainId::Gate, ChainStatus::Halted),
+                Err(Reason::InvalidChain)
+            );
+
+            assert_ok!(set_chain_status::<Test>(
+                ChainId::Eth,
+                ChainStatus::IngestOnly
+            ));
+            assert!(is_ingesting(ChainId::Eth));
+            assert_eq!(require_active(ChainId::Eth), Err(Reason::ChainDisabled));
+            assert!(is_active(ChainId::Matic));
+
+            assert_ok!(set_chain_status::<Test>(ChainId::Eth, ChainStatus::Halted));
+            assert_eq!(require_ingesting(ChainId::Eth), Err(Reason::ChainDisabled));
+            assert_eq!(
+                System::events().last().unwrap().event,
+                mock::Event::pallet_cash(crate::Event::SetChainStatus(
+                    ChainId::Eth,
+                    ChainStatus::Halted
+                ))
+            );
+        });
+    }
+
+    #[test]
+    fn test_halted_events_resume() {
+        new_test_ext().execute_with(|| {
+            let event = ChainBlockEvent::Eth(
+                1,
+                ethereum_client::EthereumEvent::Lock {
+                    asset: [1; 20],
+                    sender: [3; 20],
+                    chain: String::from("ETH"),
+                    recipient: [4; 32],
+                    amount: 100,
+                },
+            );
+
+            // events from a halted chain are not applied, but kept to retry
+            assert_ok!(set_chain_status::<Test>(ChainId::Eth, ChainStatus::Halted));
+            assert_eq!(
+                crate::core::apply_chain_event_internal::<Test>(&event),
+                Err(Reason::ChainDisabled)
+            );
+            internal::events::record_failed_event::<Test>(&event, Reason::ChainDisabled);
+            FailedChainBlockEvents::mutate(|failed| failed[0].retries = 8);
+
+            // resuming ingestion retries them right away, however often they were retried
+            System::set_block_number(5);
+            assert_ok!(set_chain_status::<Test>(ChainId::Eth, ChainStatus::Active));
+            let failed = FailedChainBlockEvents::get();
+            assert_eq!(failed[0].retries, 0);
+            assert_eq!(failed[0].next_retry_block, 5);
+        });
+    }
+}
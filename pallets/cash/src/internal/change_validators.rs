@@ -1,11 +1,32 @@
 use crate::{
-    internal, reason::Reason, require, types::ValidatorKeys, Config, Event, Module, NextValidators,
-    NoticeHolds, SessionInterface,
+    internal, params::MIN_QUORUM_THRESHOLD, reason::Reason, require, types::ValidatorKeys,
+    ChilledValidators, Config, Event, Module, NextValidators, NoticeHolds, QuorumThreshold,
+    SessionInterface,
 };
-use frame_support::storage::{IterableStorageMap, StorageMap};
+use frame_support::{
+    storage::{IterableStorageMap, StorageMap, StorageValue},
+    traits::Get,
+};
+use sp_runtime::Permill;
+
+/// Set the fraction of the validators which must sign to accept a chain block or authorize a notice.
+/// The threshold must exceed half, otherwise two disjoint sets of validators could each reach a quorum.
+pub fn set_quorum_threshold<T: Config>(threshold: Permill) -> Result<(), Reason> {
+    require!(
+        threshold > MIN_QUORUM_THRESHOLD,
+        Reason::InvalidQuorumThreshold
+    );
+    QuorumThreshold::put(threshold);
+    <Module<T>>::deposit_event(Event::SetQuorumThreshold(threshold));
+    Ok(())
+}
 
 pub fn change_validators<T: Config>(validators: Vec<ValidatorKeys>) -> Result<(), Reason> {
     require!(NoticeHolds::iter().count() == 0, Reason::PendingAuthNotice);
+    require!(
+        validators.len() <= T::MaxValidators::get() as usize,
+        Reason::TooManyValidators
+    );
 
     for validator in validators.iter() {
         require!(
@@ -25,6 +46,12 @@ pub fn change_validators<T: Config>(validators: Vec<ValidatorKeys>) -> Result<()
 
     internal::notices::dispatch_change_authority_notice::<T>(validators);
 
+    // governance has chosen the next validators, so offenders from the era just closed are no longer chilled
+    let chilled: Vec<_> = ChilledValidators::iter().map(|(id, _)| id).collect();
+    for id in chilled {
+        ChilledValidators::remove(&id);
+    }
+
     // rotate to the currently queued session, and queue a new session with the new validators in NextValidators
     <T>::SessionInterface::rotate_session();
 
@@ -103,7 +130,7 @@ mod tests {
                     ChainId::Eth,
                     expected_notice_id,
                     NoticeState::Pending {
-                        signature_pairs: ChainSignatureList::Eth(vec![])
+                        signature_pairs: ChainSignatureList::Eth(BoundedVec::default())
                     }
                 ),
                 notice_state
@@ -162,4 +189,42 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn test_change_validators_too_many() {
+        new_test_ext().execute_with(|| {
+            let vals: Vec<ValidatorKeys> = (0..=<Test as Config>::MaxValidators::get())
+                .map(|i| ValidatorKeys {
+                    substrate_id: AccountId32::new([i as u8; 32]),
+                    eth_address: [i as u8; 20],
+                })
+                .collect();
+            assert_eq!(
+                change_validators::<Test>(vals),
+                Err(Reason::TooManyValidators)
+            );
+        });
+    }
+
+    #[test]
+    fn test_set_quorum_threshold() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(QuorumThreshold::get(), DEFAULT_QUORUM_THRESHOLD);
+            assert_eq!(
+                set_quorum_threshold::<Test>(Permill::from_percent(50)),
+                Err(Reason::InvalidQuorumThreshold)
+            );
+            assert_eq!(QuorumThreshold::get(), DEFAULT_QUORUM_THRESHOLD);
+
+            let threshold = Permill::from_percent(75);
+            assert_ok!(set_quorum_threshold::<Test>(threshold));
+            assert_eq!(QuorumThreshold::get(), threshold);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::SetQuorumThreshold(
+                    threshold
+                )))
+            );
+        });
+    }
 }
@@ -0,0 +1,169 @@
+use crate::{
+    chains::ChainAccount,
+    internal::{assets::get_value, holdings::require_holdings},
+    params::MIN_TX_VALUE,
+    pipeline::CashPipeline,
+    reason::Reason,
+    require, require_min_tx_value,
+    types::{AssetInfo, AssetQuantity},
+    AssetSymbols, Config, Event, Module,
+};
+use frame_support::storage::StorageMap;
+
+/// Convert a balance of an asset into the same asset on another chain, within the group of its symbol.
+/// The target starport must hold enough of the asset to honor an extraction of the converted amount.
+pub fn convert_internal<T: Config>(
+    from_asset: AssetInfo,
+    to_asset: AssetInfo,
+    account: ChainAccount,
+    quantity: AssetQuantity,
+) -> Result<(), Reason> {
+    let symbol = AssetSymbols::get(from_asset.asset);
+    require!(
+        from_asset.asset != to_asset.asset
+            && symbol.is_some()
+            && symbol == AssetSymbols::get(to_asset.asset),
+        Reason::InvalidAssetGroup
+    );
+    require_min_tx_value!(get_value::<T>(quantity)?);
+    require_holdings(to_asset.asset, quantity.value)?;
+
+    CashPipeline::new()
+        .extract_asset::<T>(account, from_asset.asset, quantity)?
+        .lock_asset::<T>(
+            account,
+            to_asset.asset,
+            to_asset.as_quantity(quantity.value),
+        )?
+        .check_collateralized_fresh::<T>(account)?
+        .check_sufficient_total_funds::<T>(from_asset)?
+        .commit::<T>();
+
+    <Module<T>>::deposit_event(Event::Converted(
+        from_asset.asset,
+        to_asset.asset,
+        account,
+        quantity.value,
+    ));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tests::{assets::*, common::*, mock::*, *},
+        types::*,
+        *,
+    };
+
+    #[allow(non_upper_case_globals)]
+    const account_a: ChainAccount = ChainAccount::Eth([1u8; 20]);
+
+    #[allow(non_upper_case_globals)]
+    const matic_usdc: AssetInfo = AssetInfo {
+        asset: ChainAsset::Matic([0xcc; 20]),
+        ..usdc
+    };
+
+    fn init_usdc_group() {
+        assert_ok!(internal::assets::support_asset::<Test>(usdc));
+        assert_ok!(internal::assets::support_asset::<Test>(matic_usdc));
+        assert_ok!(internal::assets::set_asset_group::<Test>(
+            usdc.symbol,
+            vec![Usdc, matic_usdc.asset]
+        ));
+    }
+
+    #[test]
+    fn test_convert_internal_ungrouped() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(internal::assets::support_asset::<Test>(usdc));
+            assert_ok!(internal::assets::support_asset::<Test>(matic_usdc));
+            assert_eq!(
+                convert_internal::<Test>(
+                    usdc,
+                    matic_usdc,
+                    account_a,
+                    usdc.as_quantity_nominal("1")
+                ),
+                Err(Reason::InvalidAssetGroup)
+            );
+        });
+    }
+
+    #[test]
+    fn test_convert_internal_insufficient_starport_liquidity() {
+        new_test_ext().execute_with(|| {
+            init_usdc_group();
+            init_asset_balance(
+                Usdc,
+                account_a,
+                usdc.as_quantity_nominal("10").value as i128,
+            );
+            StarportHoldings::insert(matic_usdc.asset, usdc.as_quantity_nominal("5").value);
+            assert_eq!(
+                convert_internal::<Test>(
+                    usdc,
+                    matic_usdc,
+                    account_a,
+                    usdc.as_quantity_nominal("6")
+                ),
+                Err(Reason::InsufficientStarportLiquidity)
+            );
+        });
+    }
+
+    #[test]
+    fn test_convert_internal() {
+        new_test_ext().execute_with(|| {
+            init_usdc_group();
+            init_asset_balance(
+                Usdc,
+                account_a,
+                usdc.as_quantity_nominal("10").value as i128,
+            );
+            StarportHoldings::insert(Usdc, usdc.as_quantity_nominal("10").value);
+            StarportHoldings::insert(matic_usdc.asset, usdc.as_quantity_nominal("5").value);
+
+            assert_ok!(convert_internal::<Test>(
+                usdc,
+                matic_usdc,
+                account_a,
+                usdc.as_quantity_nominal("4")
+            ));
+            assert_eq!(
+                AssetBalances::get(Usdc, account_a),
+                usdc.as_quantity_nominal("6").value as i128
+            );
+            assert_eq!(
+                AssetBalances::get(matic_usdc.asset, account_a),
+                usdc.as_quantity_nominal("4").value as i128
+            );
+            assert_eq!(
+                TotalSupplyAssets::get(matic_usdc.asset),
+                usdc.as_quantity_nominal("4").value
+            );
+
+            // conversion only changes what is owed on each chain, not what each starport holds
+            assert_eq!(
+                StarportHoldings::get(matic_usdc.asset),
+                usdc.as_quantity_nominal("5").value
+            );
+            assert_eq!(
+                internal::extract::extract_internal::<Test>(
+                    matic_usdc,
+                    account_a,
+                    account_a,
+                    usdc.as_quantity_nominal("4")
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                StarportHoldings::get(matic_usdc.asset),
+                usdc.as_quantity_nominal("1").value
+            );
+        });
+    }
+}
@@ -0,0 +1,343 @@
+use crate::{
+    chains::{
+        ChainAccount, ChainBlock, ChainBlockNumber, ChainBlocks, ChainHash, ChainId, ChainSignature,
+    },
+    core::recover_validator,
+    internal::{account_history::deposit_account_event, miner::get_some_miner},
+    params::{ATTESTATION_RETENTION_BLOCKS, EQUIVOCATION_SLASH_FRACTION},
+    pipeline::CashPipeline,
+    reason::Reason,
+    require,
+    types::{CashPrincipalAmount, Offense, ValidatorKeys},
+    AttestedPayloads, CashPrincipals, ChilledValidators, Config, Event, GlobalCashIndex, Module,
+    PendingChainBlocks,
+};
+use codec::Encode;
+use frame_support::storage::{StorageMap, StorageValue};
+
+/// Return the hash of the payload a validator attests to for a chain block.
+pub fn payload_hash(block: &ChainBlock) -> ChainHash {
+    block.chain_id().hash_bytes(&block.encode())
+}
+
+/// Record the first payload the validator attests to for a chain block, to prove any later equivocation.
+pub fn record_attestation(block: &ChainBlock, validator: &ValidatorKeys) {
+    let event_id = (block.chain_id(), block.number());
+    let mut attested = AttestedPayloads::get(event_id);
+    if !attested.iter().any(|(id, _)| *id == validator.substrate_id) {
+        attested.push((validator.substrate_id.clone(), payload_hash(block)));
+        AttestedPayloads::insert(event_id, attested);
+    }
+}
+
+/// Forget the attestations to a chain block which was dropped by a dissent or reverted by a reorg.
+/// Validators may then attest to another block at the same height, on the fork which replaces it, without equivocating.
+pub fn forget_attestations(block: &ChainBlock) {
+    let event_id = (block.chain_id(), block.number());
+    let hash = payload_hash(block);
+    let mut attested = AttestedPayloads::get(event_id);
+    attested.retain(|(_, attested_hash)| *attested_hash != hash);
+    if attested.is_empty() {
+        AttestedPayloads::remove(event_id);
+    } else {
+        AttestedPayloads::insert(event_id, attested);
+    }
+}
+
+/// Forget the attestations which are no longer retained, once a chain block has been processed.
+pub fn prune_attestations(chain_id: ChainId, last_block_number: ChainBlockNumber) {
+    if let Some(number) = last_block_number.checked_sub(ATTESTATION_RETENTION_BLOCKS) {
+        AttestedPayloads::remove((chain_id, number));
+    }
+}
+
+/// Check that the signed blocks conflict with an attestation previously recorded for the signer.
+pub fn check_equivocation<T: Config>(
+    blocks: &ChainBlocks,
+    signature: ChainSignature,
+) -> Result<(ValidatorKeys, Offense), Reason> {
+    let validator = recover_validator::<T>(&blocks.encode(), signature)?;
+    require!(
+        !ChilledValidators::contains_key(&validator.substrate_id),
+        Reason::ValidatorChilled
+    );
+
+    for block in blocks.blocks() {
+        let event_id = (block.chain_id(), block.number());
+        let attested = AttestedPayloads::get(event_id)
+            .into_iter()
+            .find(|(id, _)| *id == validator.substrate_id);
+        if let Some((_, hash)) = attested {
+            if hash != payload_hash(&block) {
+                return Ok((validator, Offense::Equivocation(event_id)));
+            }
+        }
+    }
+
+    Err(Reason::NoEquivocation)
+}
+
+/// Slash and chill a validator which attested to conflicting chain blocks.
+/// A fraction of the CASH principal of the validator is paid to the miner, and its attestations are ignored.
+pub fn report_equivocation<T: Config>(
+    blocks: ChainBlocks,
+    signature: ChainSignature,
+) -> Result<(), Reason> {
+    let (validator, offense) = check_equivocation::<T>(&blocks, signature)?;
+    let offender = ChainAccount::Gate(validator.substrate_id.clone().into());
+    let miner = get_some_miner::<T>();
+    let principal = CashPrincipals::get(offender).amount_withdrawable()?;
+    let slashed = CashPrincipalAmount(
+        EQUIVOCATION_SLASH_FRACTION
+            .mul_uint(principal.0)
+            .to_uint()?,
+    );
+    let transfers = slashed != CashPrincipalAmount::ZERO && offender != miner;
+    let pipeline = if transfers {
+        CashPipeline::new().transfer_cash::<T>(offender, miner, slashed)?
+    } else {
+        CashPipeline::new()
+    };
+
+    // The offender no longer supports any pending blocks
+    let chain_id = blocks.chain_id();
+    let mut pending_blocks = PendingChainBlocks::get(chain_id);
+    for tally in pending_blocks.iter_mut() {
+        tally.support.remove(&validator.substrate_id);
+    }
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    ChilledValidators::insert(&validator.substrate_id, offense);
+    PendingChainBlocks::insert(chain_id, pending_blocks);
+    pipeline.commit::<T>();
+
+    if transfers {
+        let index = GlobalCashIndex::get();
+        deposit_account_event::<T>(Event::TransferCash(offender, miner, slashed, index));
+    }
+    <Module<T>>::deposit_event(Event::ValidatorOffense(
+        validator.substrate_id,
+        offense,
+        slashed,
+    ));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chains::ChainBlockTally,
+        core::{get_validator_set, validator_sign},
+        tests::{common::*, *},
+        LastProcessedBlock, Validators,
+    };
+    use ethereum_client::EthereumBlock;
+
+    fn eth_block(number: ChainBlockNumber, hash: u8, parent_hash: u8) -> EthereumBlock {
+        EthereumBlock {
+            hash: [hash; 32],
+            parent_hash: [parent_hash; 32],
+            number,
+            events: vec![],
+        }
+    }
+
+    fn init_validator() -> ValidatorKeys {
+        let substrate_id = AccountId32::new([1u8; 32]);
+        let validator = ValidatorKeys {
+            substrate_id: substrate_id.clone(),
+            eth_address: <Ethereum as Chain>::signer_address().unwrap(),
+        };
+        Validators::insert(substrate_id, validator.clone());
+        Validators::insert(
+            AccountId32::new([2u8; 32]),
+            ValidatorKeys {
+                substrate_id: AccountId32::new([2u8; 32]),
+                eth_address: [2; 20],
+            },
+        );
+        validator
+    }
+
+    #[test]
+    fn test_record_and_prune_attestations() {
+        new_test_ext().execute_with(|| {
+            let validator = init_validator();
+            let block = ChainBlock::Eth(eth_block(5, 5, 4));
+            let conflicting = ChainBlock::Eth(eth_block(5, 6, 4));
+
+            record_attestation(&block, &validator);
+            record_attestation(&conflicting, &validator);
+            assert_eq!(
+                AttestedPayloads::get((ChainId::Eth, 5)),
+                vec![(validator.substrate_id.clone(), payload_hash(&block))]
+            );
+
+            prune_attestations(ChainId::Eth, 5 + ATTESTATION_RETENTION_BLOCKS - 1);
+            assert_eq!(AttestedPayloads::get((ChainId::Eth, 5)).len(), 1);
+            prune_attestations(ChainId::Eth, 5 + ATTESTATION_RETENTION_BLOCKS);
+            assert_eq!(AttestedPayloads::get((ChainId::Eth, 5)), vec![]);
+        });
+    }
+
+    #[test]
+    fn test_forget_attestations() {
+        new_test_ext().execute_with(|| {
+            let validator = init_validator();
+            let other = ValidatorKeys {
+                substrate_id: AccountId32::new([2u8; 32]),
+                eth_address: [2; 20],
+            };
+            let dropped = ChainBlock::Eth(eth_block(5, 5, 4));
+            let replacement = ChainBlock::Eth(eth_block(5, 6, 4));
+
+            record_attestation(&dropped, &validator);
+            record_attestation(&replacement, &other);
+            forget_attestations(&dropped);
+            assert_eq!(
+                AttestedPayloads::get((ChainId::Eth, 5)),
+                vec![(other.substrate_id.clone(), payload_hash(&replacement))]
+            );
+
+            // the validator may now attest to the replacement without equivocating
+            record_attestation(&replacement, &validator);
+            let blocks = ChainBlocks::Eth(vec![eth_block(5, 6, 4)]);
+            let signature = validator_sign::<Test>(&blocks.encode()).unwrap();
+            assert_eq!(
+                check_equivocation::<Test>(&blocks, signature).map(|_| ()),
+                Err(Reason::NoEquivocation)
+            );
+        });
+    }
+
+    #[test]
+    fn test_dissent_forgets_attestations() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let last_block = LastProcessedBlock::get(ChainId::Eth).unwrap();
+            let parent = last_block.hash();
+            let (dropped, replacement) = match parent {
+                ChainHash::Eth(parent) => (
+                    EthereumBlock {
+                        hash: [5; 32],
+                        parent_hash: parent,
+                        number: last_block.number() + 1,
+                        events: vec![],
+                    },
+                    EthereumBlock {
+                        hash: [6; 32],
+                        parent_hash: parent,
+                        number: last_block.number() + 1,
+                        events: vec![],
+                    },
+                ),
+                _ => panic!("expected an eth block"),
+            };
+
+            // a third validator proposed the block which the others dissent from
+            let other = ValidatorKeys {
+                substrate_id: AccountId32::new([3u8; 32]),
+                eth_address: [3; 20],
+            };
+            Validators::insert(other.substrate_id.clone(), other.clone());
+            let dropped = ChainBlock::Eth(dropped);
+            PendingChainBlocks::insert(
+                ChainId::Eth,
+                vec![ChainBlockTally::new(dropped.clone(), &other).unwrap()],
+            );
+            record_attestation(&dropped, &other);
+
+            let blocks = ChainBlocks::Eth(vec![replacement]);
+            assert_ok!(a_receive_chain_blocks(&blocks));
+            assert_ok!(b_receive_chain_blocks(&blocks));
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth), vec![]);
+            assert!(AttestedPayloads::get((ChainId::Eth, dropped.number()))
+                .iter()
+                .all(|(id, _)| *id != other.substrate_id));
+        });
+    }
+
+    #[test]
+    fn test_chilled_validators_leave_quorum() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            ChilledValidators::insert(
+                &val_b().substrate_id,
+                Offense::Equivocation((ChainId::Eth, 1)),
+            );
+            assert_eq!(get_validator_set::<Test>().map(|set| set.len()), Ok(1));
+        });
+    }
+
+    #[test]
+    fn test_report_equivocation() {
+        new_test_ext().execute_with(|| {
+            let validator = init_validator();
+            let offender = ChainAccount::Gate(validator.substrate_id.clone().into());
+            let miner = ChainAccount::Eth([0; 20]);
+            LastProcessedBlock::insert(ChainId::Eth, ChainBlock::Eth(eth_block(0, 0, 0)));
+            init_cash(offender, CashPrincipal::from_nominal("100"));
+
+            let blocks = ChainBlocks::Eth(vec![eth_block(1, 1, 0)]);
+            let signature = validator_sign::<Test>(&blocks.encode()).unwrap();
+            assert_ok!(internal::events::receive_chain_blocks::<Test>(
+                blocks.clone(),
+                signature
+            ));
+            assert!(PendingChainBlocks::get(ChainId::Eth)[0].has_supporter(&validator.substrate_id));
+            assert_eq!(
+                report_equivocation::<Test>(blocks, signature),
+                Err(Reason::NoEquivocation)
+            );
+
+            let conflicting = ChainBlocks::Eth(vec![eth_block(1, 2, 0)]);
+            let signature = validator_sign::<Test>(&conflicting.encode()).unwrap();
+            assert_ok!(report_equivocation::<Test>(conflicting.clone(), signature));
+
+            let offense = Offense::Equivocation((ChainId::Eth, 1));
+            assert_eq!(
+                ChilledValidators::get(&validator.substrate_id),
+                Some(offense)
+            );
+            assert!(!PendingChainBlocks::get(ChainId::Eth)[0].has_supporter(&validator.substrate_id));
+            assert_eq!(
+                CashPrincipals::get(offender),
+                CashPrincipal::from_nominal("90")
+            );
+            assert_eq!(
+                CashPrincipals::get(miner),
+                CashPrincipal::from_nominal("10")
+            );
+            let events: Vec<_> = System::events().into_iter().map(|e| e.event).collect();
+            assert_eq!(
+                events[events.len() - 2],
+                mock::Event::pallet_cash(crate::Event::TransferCash(
+                    offender,
+                    miner,
+                    CashPrincipalAmount::from_nominal("10"),
+                    GlobalCashIndex::get()
+                ))
+            );
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::ValidatorOffense(
+                    validator.substrate_id.clone(),
+                    offense,
+                    CashPrincipalAmount::from_nominal("10")
+                )))
+            );
+
+            assert_eq!(
+                report_equivocation::<Test>(conflicting.clone(), signature),
+                Err(Reason::ValidatorChilled)
+            );
+            assert_eq!(
+                internal::events::receive_chain_blocks::<Test>(conflicting, signature),
+                Err(Reason::ValidatorChilled)
+            );
+        });
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     chains::{
         ChainAsset, ChainBlock, ChainBlockEvent, ChainBlockEvents, ChainBlockNumber,
         ChainBlockTally, ChainBlocks, ChainHash, ChainId, ChainReorg, ChainReorgTally,
-        ChainSignature, ChainStarport,
+        ChainSignature, ChainStarport, FailedChainBlockEvent,
     },
     core::{
         self, get_current_validator, get_event_queue, get_first_block, get_last_block,
@@ -10,26 +10,49 @@ use crate::{
     },
     debug, error,
     events::{fetch_chain_block, fetch_chain_block_by_hash, fetch_chain_blocks},
-    internal::assets::{get_cash_quantity, get_quantity, get_value},
+    internal::{
+        assets::{get_cash_quantity, get_value},
+        equivocation::{forget_attestations, prune_attestations, record_attestation},
+    },
     log,
-    params::{INGRESS_LARGE, INGRESS_QUOTA, INGRESS_SLACK, MAX_EVENT_BLOCKS, MIN_EVENT_BLOCKS},
+    metrics::{self, EVENTS_FETCHED, SUBMISSION_FAILURES},
+    params::{
+        FAILED_EVENT_MAX_RETRIES, FAILED_EVENT_RETRY_BATCH_SIZE, FAILED_EVENT_RETRY_BLOCKS,
+        INGRESS_LARGE, INGRESS_QUOTA, INGRESS_SLACK, MAX_EVENT_BLOCKS, MAX_FAILED_EVENTS,
+        MIN_EVENT_BLOCKS, WORKER_LEADERS_PER_BLOCK, WORKER_MAX_BLOCKS_PER_SCAN,
+        WORKER_MAX_EVENTS_PER_SUBMISSION,
+    },
     reason::{MathError, Reason},
     require,
-    types::{CashPrincipalAmount, Quantity, USDQuantity, USD},
-    Call, Config, Event as EventT, IngressionQueue, LastProcessedBlock, Module, PendingChainBlocks,
-    PendingChainReorgs,
+    types::{AssetAmount, CashPrincipalAmount, Quantity, USDQuantity, ValidatorIdentity, USD},
+    Call, ChilledValidators, Config, Event as EventT, EventExpiration, EventProofMode,
+    FailedChainBlockEvents, IngressionQueue, LastProcessedBlock, Module, PendingChainBlocks,
+    PendingChainBlocksSince, PendingChainEvents, PendingChainReorgs, QuorumThreshold,
+    SupportedAssets,
 };
 use codec::Encode;
 use ethereum_client::EthereumEvent;
-use frame_support::storage::StorageMap;
+use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
 use frame_system::offchain::SubmitTransaction;
-use our_std::{cmp::max, convert::TryInto};
+use our_std::{
+    cmp::{max, min},
+    convert::TryInto,
+};
 use sp_core::offchain::Duration;
-use sp_runtime::offchain::{
-    storage::StorageValueRef,
-    storage_lock::{StorageLock, Time},
+use sp_runtime::{
+    offchain::{
+        storage::StorageValueRef,
+        storage_lock::{StorageLock, Time},
+    },
+    traits::UniqueSaturatedInto,
 };
 
+/// Validator config key overriding the maximum number of blocks a worker scans per invocation.
+const WORKER_MAX_BLOCKS_PER_SCAN_KEY: &str = "WORKER_MAX_BLOCKS_PER_SCAN";
+
+/// Validator config key overriding the maximum number of events a worker submits at once.
+const WORKER_MAX_EVENTS_PER_SUBMISSION_KEY: &str = "WORKER_MAX_EVENTS_PER_SUBMISSION";
+
 trait CollectRev: Iterator {
     fn collect_rev(self) -> Vec<Self::Item>
     where
@@ -49,6 +72,17 @@ pub fn queue_slack(event_queue: &ChainBlockEvents) -> u32 {
     max(INGRESS_SLACK.saturating_sub(queue_len), 1)
 }
 
+/// Determine the value of a lock, which is nothing for an unsupported asset as it is only refunded.
+fn get_lock_value<T: Config>(
+    asset: ChainAsset,
+    amount: AssetAmount,
+) -> Result<USDQuantity, Reason> {
+    match SupportedAssets::get(asset) {
+        Some(info) => get_value::<T>(info.as_quantity(amount)),
+        None => Ok(Quantity::new(0, USD)),
+    }
+}
+
 /// Determine the risk-adjusted value of a particular event, given the current block number.
 pub fn risk_adjusted_value<T: Config>(
     block_event: &ChainBlockEvent,
@@ -61,8 +95,7 @@ pub fn risk_adjusted_value<T: Config>(
         ChainBlockEvent::Reserved => panic!("reserved"),
         ChainBlockEvent::Eth(_block_num, eth_event) => match eth_event {
             EthereumEvent::Lock { asset, amount, .. } => {
-                let quantity = get_quantity::<T>(ChainAsset::Eth(*asset), *amount)?;
-                let usd_quantity = get_value::<T>(quantity)?;
+                let usd_quantity = get_lock_value::<T>(ChainAsset::Eth(*asset), *amount)?;
                 Ok(usd_quantity.decay(elapsed_blocks)?)
             }
 
@@ -81,8 +114,7 @@ pub fn risk_adjusted_value<T: Config>(
         },
         ChainBlockEvent::Matic(_block_num, eth_event) => match eth_event {
             EthereumEvent::Lock { asset, amount, .. } => {
-                let quantity = get_quantity::<T>(ChainAsset::Matic(*asset), *amount)?;
-                let usd_quantity = get_value::<T>(quantity)?;
+                let usd_quantity = get_lock_value::<T>(ChainAsset::Matic(*asset), *amount)?;
                 debug!("matic lock detected usd_quantity={:?}", usd_quantity);
                 Ok(usd_quantity.decay(elapsed_blocks)?)
             }
@@ -98,6 +130,26 @@ pub fn risk_adjusted_value<T: Config>(
                 Ok(usd_quantity.decay(elapsed_blocks)?)
             }
 
+            _ => Ok(Quantity::new(0, USD)),
+        },
+        #[cfg(feature = "runtime-dev")]
+        ChainBlockEvent::Mock(_block_num, mock_event) => match mock_event {
+            EthereumEvent::Lock { asset, amount, .. } => {
+                let usd_quantity = get_lock_value::<T>(ChainAsset::Mock(*asset), *amount)?;
+                Ok(usd_quantity.decay(elapsed_blocks)?)
+            }
+
+            EthereumEvent::LockCash { principal, .. } => {
+                let quantity = get_cash_quantity::<T>(CashPrincipalAmount(*principal))?;
+                let usd_quantity = get_value::<T>(quantity)?;
+                Ok(usd_quantity.decay(elapsed_blocks)?)
+            }
+
+            EthereumEvent::ExecuteProposal { .. } => {
+                let usd_quantity = get_value::<T>(INGRESS_LARGE)?;
+                Ok(usd_quantity.decay(elapsed_blocks)?)
+            }
+
             _ => Ok(Quantity::new(0, USD)),
         },
     }
@@ -110,7 +162,8 @@ fn is_starport_enabled<T: Config>(chain_id: ChainId) -> bool {
 }
 
 /// Incrementally perform the next step of tracking events from all the underlying chains.
-pub fn track_chain_events<T: Config>() -> Result<(), Reason> {
+pub fn track_chain_events<T: Config>(block_number: T::BlockNumber) -> Result<(), Reason> {
+    let block_number: u64 = block_number.unique_saturated_into();
     // Note: The way this is written might look pointless, but its very important to the lock
     //  Do not modify lightly and without discussion / further testing.
     let deadline = Duration::from_millis(120_000);
@@ -118,10 +171,15 @@ pub fn track_chain_events<T: Config>() -> Result<(), Reason> {
     let result = match lock.try_lock() {
         Ok(_guard) => {
             // Note: chains could be parallelized
-            track_chain_events_on::<T>(ChainId::Eth)?;
+            track_chain_events_on::<T>(ChainId::Eth, block_number)?;
 
             if is_starport_enabled::<T>(ChainId::Matic) {
-                track_chain_events_on::<T>(ChainId::Matic)?;
+                track_chain_events_on::<T>(ChainId::Matic, block_number)?;
+            }
+
+            #[cfg(feature = "runtime-dev")]
+            if is_starport_enabled::<T>(ChainId::Mock) {
+                track_chain_events_on::<T>(ChainId::Mock, block_number)?;
             }
 
             Ok(())
@@ -133,9 +191,30 @@ pub fn track_chain_events<T: Config>() -> Result<(), Reason> {
 }
 
 /// Perform the next step of tracking events from an underlying chain.
-pub fn track_chain_events_on<T: Config>(chain_id: ChainId) -> Result<(), Reason> {
+/// Only the worker leaders for the block propose new blocks, but every worker supports pending ones.
+pub fn track_chain_events_on<T: Config>(
+    chain_id: ChainId,
+    block_number: u64,
+) -> Result<(), Reason> {
     let starport = get_starport::<T>(chain_id)?;
     let me = get_current_validator::<T>()?;
+    metrics::track_quorum_wait(
+        chain_id,
+        PendingChainBlocks::get(chain_id)
+            .iter()
+            .map(|tally| tally.block.number())
+            .min(),
+    );
+    if PendingChainBlocks::get(chain_id).is_empty()
+        && PendingChainReorgs::get(chain_id).is_empty()
+        && !is_worker_leader::<T>(&me.substrate_id, block_number)?
+    {
+        debug!(
+            "Worker is not a leader for block {}, nothing to support on {:?}",
+            block_number, chain_id
+        );
+        return Ok(());
+    }
     let last_block = get_last_block::<T>(chain_id)?;
     let next_block_number = last_block
         .number()
@@ -149,23 +228,56 @@ pub fn track_chain_events_on<T: Config>(chain_id: ChainId) -> Result<(), Reason>
         );
         let pending_blocks = PendingChainBlocks::get(chain_id);
         let event_queue = get_event_queue::<T>(chain_id)?;
-        let slack = queue_slack(&event_queue) as u64;
-        let blocks = next_block
-            .concat(fetch_chain_blocks(
+        let max_blocks = min(
+            queue_slack(&event_queue) as u64,
+            get_worker_limit(WORKER_MAX_BLOCKS_PER_SCAN_KEY, WORKER_MAX_BLOCKS_PER_SCAN),
+        );
+        let max_events = get_worker_limit(
+            WORKER_MAX_EVENTS_PER_SUBMISSION_KEY,
+            WORKER_MAX_EVENTS_PER_SUBMISSION,
+        );
+        let cursor_key = format!("cash::track_chain_events::cursor::{:?}", chain_id);
+        let cursor = StorageValueRef::persistent(cursor_key.as_bytes());
+        let scan_from = get_scan_start(
+            cursor.get::<ChainBlockNumber>().flatten(),
+            next_block_number,
+            &pending_blocks,
+            &me.substrate_id,
+        );
+        let scan_to = scan_from
+            .checked_add(max_blocks)
+            .ok_or(MathError::Overflow)?;
+        let scanned = if scan_from == next_block_number {
+            next_block.concat(fetch_chain_blocks(
                 chain_id,
                 next_block_number
                     .checked_add(1)
                     .ok_or(MathError::Overflow)?,
-                next_block_number
-                    .checked_add(1)
-                    .ok_or(MathError::Overflow)?
-                    .checked_add(slack)
-                    .ok_or(MathError::Overflow)?,
+                scan_to,
                 starport,
             )?)?
-            .filter_already_supported(&me.substrate_id, pending_blocks);
+        } else {
+            debug!(
+                "Worker resuming scan of {:?} from {} (next={})",
+                chain_id, scan_from, next_block_number
+            );
+            fetch_chain_blocks(chain_id, scan_from, scan_to, starport)?
+        };
+        let blocks = scanned
+            .filter_already_supported(&me.substrate_id, pending_blocks)
+            .limit_events(max_events);
+        let num_events = blocks
+            .blocks()
+            .iter()
+            .map(|b| b.events().len() as u64)
+            .sum();
+        metrics::increment(EVENTS_FETCHED, chain_id, num_events);
         memorize_chain_blocks::<T>(&blocks)?;
-        submit_chain_blocks::<T>(&blocks)
+        submit_chain_blocks::<T>(&blocks)?;
+        if let Some(last_submitted) = blocks.block_numbers().last() {
+            cursor.set(last_submitted);
+        }
+        Ok(())
     } else {
         debug!(
             "Worker sees a different fork: next={:?} last={:?}",
@@ -186,6 +298,62 @@ pub fn track_chain_events_on<T: Config>(chain_id: ChainId) -> Result<(), Reason>
     }
 }
 
+/// Read a limit for the worker from the validator config, falling back to the default if unset.
+fn get_worker_limit(key: &str, default: u64) -> u64 {
+    match runtime_interfaces::validator_config_interface::get(key).map(|v| v.parse::<u64>()) {
+        Some(Ok(limit)) if limit > 0 => limit,
+        Some(_) => {
+            log!("Invalid worker config {}, using default {}", key, default);
+            default
+        }
+        None => default,
+    }
+}
+
+/// Determine the first underlying chain block to scan, given the last block the worker submitted.
+/// The worker only resumes after its cursor while that block is still pending with its support,
+///  otherwise it scans again from the next block the chain needs.
+fn get_scan_start(
+    cursor: Option<ChainBlockNumber>,
+    next_block_number: ChainBlockNumber,
+    pending_blocks: &[ChainBlockTally],
+    signer: &ValidatorIdentity,
+) -> ChainBlockNumber {
+    match cursor {
+        Some(last_submitted) if last_submitted >= next_block_number => {
+            let offset = (last_submitted - next_block_number) as usize;
+            match pending_blocks.get(offset) {
+                Some(tally) if tally.has_supporter(signer) => last_submitted + 1,
+                _ => next_block_number,
+            }
+        }
+        _ => next_block_number,
+    }
+}
+
+/// Determine whether the given validator is one of the workers chosen to propose new blocks at a block.
+/// Leaders rotate through the validator set every block, so an offline leader only delays proposals.
+pub fn is_worker_leader<T: Config>(
+    validator: &ValidatorIdentity,
+    block_number: u64,
+) -> Result<bool, Reason> {
+    let validators = get_validator_set::<T>()?;
+    match validators.iter().position(|v| v == validator) {
+        Some(index) => Ok(is_leader_index(
+            index as u64,
+            validators.len() as u64,
+            block_number,
+        )),
+        None => Ok(false),
+    }
+}
+
+/// Determine whether the validator at the given index in the set leads at a block.
+fn is_leader_index(index: u64, num_validators: u64, block_number: u64) -> bool {
+    let slot = block_number % num_validators;
+    (index + num_validators - slot) % num_validators < WORKER_LEADERS_PER_BLOCK
+}
+
 /// Ingress a single round (quota per underlying chain block ingested).
 pub fn ingress_queue<T: Config>(
     last_block: &ChainBlock,
@@ -213,19 +381,8 @@ pub fn ingress_queue<T: Config>(
                     );
                     if value <= available {
                         available = available.sub(value).unwrap();
-                        match core::apply_chain_event_internal::<T>(event) {
-                            Ok(()) => {
-                                <Module<T>>::deposit_event(EventT::ProcessedChainBlockEvent(
-                                    event.clone(),
-                                ));
-                            }
-
-                            Err(reason) => {
-                                <Module<T>>::deposit_event(
-                                    EventT::FailedProcessingChainBlockEvent(event.clone(), reason),
-                                );
-                            }
-                        }
+                        // applied in order with any other accepted events, once the block is finalized
+                        PendingChainEvents::append(event);
                         return false; // remove from queue
                     } else {
                         return true; // retain on queue
@@ -240,7 +397,7 @@ pub fn ingress_queue<T: Config>(
                     // note that we keep the event if we cannot compute the risk adjusted value,
                     //  there's not an obviously more reasonable thing to do right now
                     // there's no reason this should fail normally but it can
-                    //  e.g. if somehow someone locks an unsupported asset in the starport
+                    //  e.g. if there is no price for an asset locked in the starport
                     // we need to take separate measures to forcefully limit the queue size
                     //  e.g. reject new blocks once the event queue reaches a certain size
                     return true; // retain on queue
@@ -257,6 +414,209 @@ pub fn ingress_queue<T: Config>(
     Ok(())
 }
 
+/// Apply the events accepted during the block, in order of their chain event id.
+/// Events are accepted as blocks reach quorum, which depends on the order of extrinsics in the block;
+/// sorting them by chain, underlying block and event hash lets every validator converge on the same state regardless.
+pub fn apply_chain_events<T: Config>() {
+    let mut events = PendingChainEvents::take();
+    events.sort_by_cached_key(core::get_chain_event_id);
+
+    for event in events.iter() {
+        match core::apply_chain_event_internal::<T>(event) {
+            Ok(()) => {
+                <Module<T>>::deposit_event(EventT::ProcessedChainBlockEvent(event.clone()));
+            }
+
+            Err(reason) => {
+                record_failed_event::<T>(event, reason);
+            }
+        }
+    }
+}
+
+/// Stop tracking an accepted event which has not yet been applied, returning whether it was pending.
+fn forget_pending_event(event: &ChainBlockEvent) -> bool {
+    let mut events = PendingChainEvents::get();
+    match events.iter().position(|pending| pending == event) {
+        Some(pos) => {
+            events.remove(pos);
+            PendingChainEvents::put(events);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Return the current substrate block number, as used for scheduling retries.
+fn current_block_number<T: Config>() -> u64 {
+    <frame_system::Pallet<T>>::block_number().unique_saturated_into()
+}
+
+/// Determine the block at which to next retry a failed event, backing off exponentially.
+fn next_retry_block(now: u64, retries: u32) -> u64 {
+    let backoff = 1u64.checked_shl(retries).map_or(u64::MAX, |factor| {
+        FAILED_EVENT_RETRY_BLOCKS.saturating_mul(factor)
+    });
+    now.saturating_add(backoff)
+}
+
+/// Record an event which failed to apply, so that it can be retried later.
+/// Once too many failed events are kept, any more are only reported.
+pub fn record_failed_event<T: Config>(event: &ChainBlockEvent, reason: Reason) {
+    <Module<T>>::deposit_event(EventT::FailedProcessingChainBlockEvent(
+        event.clone(),
+        reason,
+    ));
+    if FailedChainBlockEvents::decode_len().unwrap_or(0) >= MAX_FAILED_EVENTS as usize {
+        error!("Too many failed events to retry {:?}: {:?}", event, reason);
+        return;
+    }
+    FailedChainBlockEvents::append(FailedChainBlockEvent {
+        event: event.clone(),
+        reason,
+        retries: 0,
+        next_retry_block: next_retry_block(current_block_number::<T>(), 0),
+    });
+}
+
+/// Retry applying the failed events which are due, up to a batch at a time.
+pub fn retry_failed_events<T: Config>() {
+    let now = current_block_number::<T>();
+    let mut retried = 0;
+    let mut failed_events = vec![];
+
+    for mut failed in FailedChainBlockEvents::get() {
+        if failed.retries >= FAILED_EVENT_MAX_RETRIES
+            || failed.next_retry_block > now
+            || retried >= FAILED_EVENT_RETRY_BATCH_SIZE
+        {
+            failed_events.push(failed);
+            continue;
+        }
+
+        retried += 1;
+        match core::apply_chain_event_internal::<T>(&failed.event) {
+            Ok(()) => {
+                <Module<T>>::deposit_event(EventT::ProcessedChainBlockEvent(failed.event));
+            }
+
+            Err(reason) => {
+                <Module<T>>::deposit_event(EventT::FailedProcessingChainBlockEvent(
+                    failed.event.clone(),
+                    reason,
+                ));
+                failed.reason = reason;
+                failed.retries = failed.retries.saturating_add(1);
+                failed.next_retry_block = next_retry_block(now, failed.retries);
+                failed_events.push(failed);
+            }
+        }
+    }
+
+    if retried > 0 {
+        FailedChainBlockEvents::put(failed_events);
+    }
+}
+
+/// Retry the failed events of a chain which failed for the given reason as soon as possible, once it no longer applies.
+pub fn reschedule_failed_events<T: Config>(chain_id: ChainId, reason: Reason) {
+    let now = current_block_number::<T>();
+    FailedChainBlockEvents::mutate(|failed_events| {
+        for failed in failed_events.iter_mut() {
+            if failed.event.chain_id() == chain_id && failed.reason == reason {
+                failed.retries = 0;
+                failed.next_retry_block = now;
+            }
+        }
+    });
+}
+
+/// Stop tracking a failed event, returning whether it was being tracked.
+fn forget_failed_event(event: &ChainBlockEvent) -> bool {
+    let mut failed_events = FailedChainBlockEvents::get();
+    match failed_events
+        .iter()
+        .position(|failed| failed.event == *event)
+    {
+        Some(pos) => {
+            failed_events.remove(pos);
+            FailedChainBlockEvents::put(failed_events);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Discard a failed event which is not expected to ever succeed.
+pub fn discard_failed_event<T: Config>(event: ChainBlockEvent) -> Result<(), Reason> {
+    require!(forget_failed_event(&event), Reason::UnknownFailedEvent);
+    <Module<T>>::deposit_event(EventT::DiscardedFailedChainBlockEvent(event));
+    Ok(())
+}
+
+/// Set the number of blocks after which pending chain blocks which have not reached a quorum expire.
+/// Zero means pending blocks never expire.
+pub fn set_event_expiration<T: Config>(blocks: u32) -> Result<(), Reason> {
+    EventExpiration::put(blocks);
+    <Module<T>>::deposit_event(EventT::SetEventExpiration(blocks));
+    Ok(())
+}
+
+/// Start the expiration clock whenever a new block heads the pending queue of a chain.
+fn track_pending_since<T: Config>(
+    chain_id: ChainId,
+    head_pre: Option<ChainHash>,
+    pending_blocks: &Vec<ChainBlockTally>,
+) {
+    match pending_blocks.first().map(|tally| tally.block.hash()) {
+        None => PendingChainBlocksSince::remove(chain_id),
+        Some(head) => {
+            if Some(head) != head_pre || !PendingChainBlocksSince::contains_key(chain_id) {
+                PendingChainBlocksSince::insert(chain_id, current_block_number::<T>());
+            }
+        }
+    }
+}
+
+/// Drop the pending blocks of any chain whose head has waited longer than the expiration for a quorum.
+/// The events in them are recorded as timed out, and workers propose the blocks afresh,
+///  so an event which is still canonical will be attested again.
+pub fn expire_pending_blocks<T: Config>() {
+    let expiration = EventExpiration::get() as u64;
+    if expiration == 0 {
+        return;
+    }
+
+    let now = current_block_number::<T>();
+    let expired: Vec<ChainId> = PendingChainBlocksSince::iter()
+        .filter(|(_, since)| now.saturating_sub(*since) >= expiration)
+        .map(|(chain_id, _)| chain_id)
+        .collect();
+    for chain_id in expired {
+        for tally in PendingChainBlocks::take(chain_id) {
+            for event in tally.block.events() {
+                <Module<T>>::deposit_event(EventT::ExpiredChainBlockEvent(
+                    event,
+                    Reason::EventTimedOut,
+                ));
+            }
+        }
+        PendingChainBlocksSince::remove(chain_id);
+    }
+}
+
+/// Set whether events of an underlying chain must be proven by the receipts of each block.
+/// Only chains whose blocks carry Ethereum-style receipts can be proven.
+pub fn set_event_proof_mode<T: Config>(chain_id: ChainId, enabled: bool) -> Result<(), Reason> {
+    require!(
+        matches!(chain_id, ChainId::Eth | ChainId::Matic),
+        Reason::InvalidChain
+    );
+    EventProofMode::insert(chain_id, enabled);
+    <Module<T>>::deposit_event(EventT::SetEventProofMode(chain_id, enabled));
+    Ok(())
+}
+
 /// Submit the underlying chain blocks the worker calculates are needed by the chain next.
 pub fn submit_chain_blocks<T: Config>(blocks: &ChainBlocks) -> Result<(), Reason> {
     if blocks.len() > 0 {
@@ -265,6 +625,7 @@ pub fn submit_chain_blocks<T: Config>(blocks: &ChainBlocks) -> Result<(), Reason
         let call = Call::receive_chain_blocks(blocks.clone(), signature);
         if let Err(e) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
             log!("Error while submitting chain blocks: {:?}", e);
+            metrics::increment(SUBMISSION_FAILURES, blocks.chain_id(), 1);
             return Err(Reason::FailedToSubmitExtrinsic);
         }
     }
@@ -352,6 +713,8 @@ pub fn formulate_reorg<T: Config>(
                 .filter_map(|b| match b {
                     ChainBlock::Eth(eth_block) => Some(eth_block),
                     ChainBlock::Matic(block) => Some(block),
+                    #[cfg(feature = "runtime-dev")]
+                    ChainBlock::Mock(block) => Some(block),
                 })
                 .collect(),
             forward_blocks: drawrof_blocks
@@ -359,6 +722,8 @@ pub fn formulate_reorg<T: Config>(
                 .filter_map(|b| match b {
                     ChainBlock::Eth(eth_block) => Some(eth_block),
                     ChainBlock::Matic(block) => Some(block),
+                    #[cfg(feature = "runtime-dev")]
+                    ChainBlock::Mock(block) => Some(block),
                 })
                 .collect_rev(),
         }),
@@ -374,6 +739,7 @@ pub fn submit_chain_reorg<T: Config>(reorg: &ChainReorg) -> Result<(), Reason> {
     let call = Call::receive_chain_reorg(reorg.clone(), signature);
     if let Err(e) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
         log!("Error while submitting chain blocks: {:?}", e);
+        metrics::increment(SUBMISSION_FAILURES, reorg.chain_id(), 1);
         return Err(Reason::FailedToSubmitExtrinsic);
     }
     Ok(())
@@ -385,17 +751,25 @@ pub fn receive_chain_blocks<T: Config>(
     signature: ChainSignature,
 ) -> Result<(), Reason> {
     let validator_set = get_validator_set::<T>()?;
+    let threshold = QuorumThreshold::get();
     let validator = recover_validator::<T>(&blocks.encode(), signature)?;
+    require!(
+        !ChilledValidators::contains_key(&validator.substrate_id),
+        Reason::ValidatorChilled
+    );
     let chain_id = blocks.chain_id();
     let mut event_queue = get_event_queue::<T>(chain_id)?;
     let mut last_block = get_last_block::<T>(chain_id)?;
     let mut pending_blocks = PendingChainBlocks::get(chain_id);
+    let pending_head = pending_blocks.first().map(|tally| tally.block.hash());
+    let mut attested_blocks = vec![];
 
     debug!("Pending blocks: {:?}", pending_blocks);
     debug!("Event queue: {:?}", event_queue);
 
     for block in blocks.blocks() {
         if block.number() >= last_block.number() + 1 {
+            attested_blocks.push(block.clone());
             let offset = (block.number() - last_block.number() - 1) as usize;
             if let Some(prior) = pending_blocks.get_mut(offset) {
                 if block != prior.block {
@@ -403,10 +777,10 @@ pub fn receive_chain_blocks<T: Config>(
                         "Received conflicting block, dissenting: {:?} ({:?})",
                         block, prior
                     );
-                    prior.add_dissent(&validator);
+                    prior.add_dissent(&validator)?;
                 } else {
                     debug!("Received support for existing block: {:?}", block);
-                    prior.add_support(&validator);
+                    prior.add_support(&validator)?;
                 }
             } else if offset == 0 {
                 if block.parent_hash() != last_block.hash() {
@@ -423,7 +797,7 @@ pub fn receive_chain_blocks<T: Config>(
                     debug!("Received valid first next pending block: {:?}", block);
                     // write to pending_blocks[offset]
                     //  we already checked offset doesn't exist, this is the first element
-                    pending_blocks.push(ChainBlockTally::new(block, &validator));
+                    pending_blocks.push(ChainBlockTally::new(block, &validator)?);
                 }
             } else if let Some(parent) = pending_blocks.get(offset - 1) {
                 if block.parent_hash() != parent.block.hash() {
@@ -441,7 +815,7 @@ pub fn receive_chain_blocks<T: Config>(
                     debug!("Received valid pending block: {:?}", block);
                     // write to pending_blocks[offset]
                     //  we already checked offset doesn't exist, but offset - 1 does
-                    pending_blocks.push(ChainBlockTally::new(block, &validator));
+                    pending_blocks.push(ChainBlockTally::new(block, &validator)?);
                 }
             } else {
                 debug!("Received disconnected block: {:?} ({:?})", block, offset);
@@ -461,16 +835,22 @@ pub fn receive_chain_blocks<T: Config>(
     }
 
     for tally in pending_blocks.clone().iter() {
-        if tally.has_enough_support(&validator_set) {
+        if tally.has_enough_support(&validator_set, threshold) {
             // remove tally from block queue
             //  add events to event queue, advance the block, and process a round of events
             pending_blocks.remove(0); // note: tally is first on queue
             event_queue.push(&tally.block);
             last_block = tally.block.clone();
             ingress_queue::<T>(&last_block, &mut event_queue)?;
+            prune_attestations(chain_id, last_block.number());
+            internal::lock::prune_lock_outcomes(chain_id, last_block.number());
             continue;
-        } else if tally.has_enough_dissent(&validator_set) {
+        } else if tally.has_enough_dissent(&validator_set, threshold) {
             // remove tally and everything after from queue
+            //  validators may then attest to other blocks at those heights
+            for dropped in pending_blocks.iter() {
+                forget_attestations(&dropped.block);
+            }
             pending_blocks = vec![];
             break;
         } else {
@@ -478,6 +858,11 @@ pub fn receive_chain_blocks<T: Config>(
         }
     }
 
+    for block in attested_blocks.iter() {
+        record_attestation(block, &validator);
+    }
+
+    track_pending_since::<T>(chain_id, pending_head, &pending_blocks);
     LastProcessedBlock::insert(chain_id, last_block);
     PendingChainBlocks::insert(chain_id, pending_blocks);
     IngressionQueue::insert(chain_id, event_queue);
@@ -491,6 +876,7 @@ pub fn receive_chain_reorg<T: Config>(
     signature: ChainSignature,
 ) -> Result<(), Reason> {
     let validator_set = get_validator_set::<T>()?;
+    let threshold = QuorumThreshold::get();
     let validator = recover_validator::<T>(&reorg.encode(), signature)?;
     let chain_id = reorg.chain_id();
     let mut event_queue = get_event_queue::<T>(chain_id)?;
@@ -501,25 +887,28 @@ pub fn receive_chain_reorg<T: Config>(
     require!(reorg.from_hash() == last_block.hash(), Reason::HashMismatch);
 
     let tally = if let Some(prior) = pending_reorgs.iter_mut().find(|r| r.reorg == reorg) {
-        prior.add_support(&validator);
+        prior.add_support(&validator)?;
         prior
     } else {
-        pending_reorgs.push(ChainReorgTally::new(chain_id, reorg, &validator));
+        pending_reorgs.push(ChainReorgTally::new(chain_id, reorg, &validator)?);
         pending_reorgs.last_mut().unwrap()
     };
 
     // Note: whenever there's a race to be the last signer, this will be suboptimal
     //  we don't currently keep a tombstone marking that the reorg was recently processed
-    if tally.has_enough_support(&validator_set) {
+    if tally.has_enough_support(&validator_set, threshold) {
         // if we have enough support, perform actual reorg
         // for each block going backwards
         //  remove events from queue, or unapply them if already applied
         for block in tally.reorg.reverse_blocks().blocks() {
+            forget_attestations(&block);
             for event in block.events() {
                 // Note: this could be made significantly more efficient
                 //  at the cost of significant complexity
                 if let Some(pos) = event_queue.position(&event) {
                     event_queue.remove(pos);
+                } else if forget_pending_event(&event) || forget_failed_event(&event) {
+                    // the event was never applied, so there is nothing to unapply
                 } else {
                     core::unapply_chain_event_internal::<T>(&event)?
                 }
@@ -534,9 +923,15 @@ pub fn receive_chain_reorg<T: Config>(
             ingress_queue::<T>(&last_block, &mut event_queue)?;
         }
 
+        // the pending blocks were on the abandoned fork too
+        for dropped in PendingChainBlocks::get(chain_id).iter() {
+            forget_attestations(&dropped.block);
+        }
+
         // write the new state back to storage
         LastProcessedBlock::insert(chain_id, last_block);
         PendingChainBlocks::insert(chain_id, Vec::<ChainBlockTally>::new());
+        PendingChainBlocksSince::remove(chain_id);
         PendingChainReorgs::insert(chain_id, Vec::<ChainReorgTally>::new());
         IngressionQueue::insert(chain_id, event_queue);
     } else {
@@ -601,10 +996,10 @@ mod tests {
 
             LastProcessedBlock::insert(ChainId::Eth, ChainBlock::Eth(last_block));
             memorize_chain_blocks::<Test>(&ChainBlocks::Eth(old_chain.clone())).unwrap();
-            track_chain_events_on::<Test>(ChainId::Eth).unwrap();
+            track_chain_events_on::<Test>(ChainId::Eth, 1).unwrap();
 
             LastProcessedBlock::insert(ChainId::Eth, ChainBlock::Eth(true_block));
-            track_chain_events_on::<Test>(ChainId::Eth).unwrap();
+            track_chain_events_on::<Test>(ChainId::Eth, 1).unwrap();
         });
     }
 
@@ -842,7 +1237,7 @@ mod tests {
             assert_ok!(a_receive_chain_reorg(&reorg), ());
             assert_eq!(
                 PendingChainReorgs::get(ChainId::Eth),
-                vec![ChainReorgTally::new(ChainId::Eth, reorg.clone(), &val_a())]
+                vec![ChainReorgTally::new(ChainId::Eth, reorg.clone(), &val_a()).unwrap()]
             );
 
             // val b sends reorg and show reorg is executed and the new event is applied and the old one is reverted
@@ -1049,4 +1444,286 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_set_event_proof_mode() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(EventProofMode::get(ChainId::Eth), false);
+            assert_ok!(set_event_proof_mode::<Test>(ChainId::Eth, true));
+            assert_eq!(EventProofMode::get(ChainId::Eth), true);
+            assert_eq!(EventProofMode::get(ChainId::Matic), false);
+            assert_eq!(
+                set_event_proof_mode::<Test>(ChainId::Dot, true),
+                Err(Reason::InvalidChain)
+            );
+            assert_ok!(set_event_proof_mode::<Test>(ChainId::Eth, false));
+            assert_eq!(EventProofMode::get(ChainId::Eth), false);
+        });
+    }
+
+    #[test]
+    fn test_expire_pending_blocks() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            assert_ok!(CashModule::set_event_expiration(Origin::root(), 10));
+            assert_eq!(EventExpiration::get(), 10);
+
+            let event = ethereum_client::EthereumEvent::Lock {
+                asset: [238; 20],
+                sender: [3; 20],
+                chain: String::from("ETH"),
+                recipient: [2; 32],
+                amount: qty!("75", ETH).value,
+            };
+            let blocks = ChainBlocks::Eth(vec![ethereum_client::EthereumBlock {
+                hash: [2; 32],
+                parent_hash: premined_block().hash,
+                number: 2,
+                events: vec![event.clone()],
+            }]);
+
+            // only seen by a minority, so the block waits for a quorum
+            assert_ok!(a_receive_chain_blocks(&blocks));
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth).len(), 1);
+            assert_eq!(PendingChainBlocksSince::get(ChainId::Eth), Some(1));
+
+            System::set_block_number(10);
+            expire_pending_blocks::<Test>();
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth).len(), 1);
+
+            // the block expires, and its event is recorded as timed out
+            System::set_block_number(11);
+            expire_pending_blocks::<Test>();
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth), vec![]);
+            assert_eq!(PendingChainBlocksSince::get(ChainId::Eth), None);
+            assert_eq!(
+                System::events().last().unwrap().event,
+                mock::Event::pallet_cash(crate::Event::ExpiredChainBlockEvent(
+                    ChainBlockEvent::Eth(2, event.clone()),
+                    Reason::EventTimedOut
+                ))
+            );
+
+            // the event reappears canonically, and is attested afresh
+            assert_ok!(a_receive_chain_blocks(&blocks));
+            assert_eq!(PendingChainBlocksSince::get(ChainId::Eth), Some(11));
+            assert_ok!(b_receive_chain_blocks(&blocks));
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth), vec![]);
+            assert_eq!(PendingChainBlocksSince::get(ChainId::Eth), None);
+            let event_queue = get_event_queue::<Test>(ChainId::Eth)?;
+            assert_eq!(event_queue, ChainBlockEvents::Eth(vec![(2, event)]));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_failed_event_retry_and_discard() {
+        new_test_ext().execute_with(|| {
+            let event = ChainBlockEvent::Eth(
+                1,
+                EthereumEvent::Lock {
+                    asset: [1; 20],
+                    sender: [3; 20],
+                    chain: String::from("BTC"),
+                    recipient: [4; 32],
+                    amount: 100,
+                },
+            );
+
+            // the recipient chain is not valid, so the event fails and is recorded
+            record_failed_event::<Test>(&event, Reason::InvalidChain);
+            let failed = FailedChainBlockEvents::get();
+            assert_eq!(failed.len(), 1);
+            assert_eq!(failed[0].retries, 0);
+            assert_eq!(failed[0].next_retry_block, 1 + FAILED_EVENT_RETRY_BLOCKS);
+
+            // not yet due for a retry
+            retry_failed_events::<Test>();
+            assert_eq!(FailedChainBlockEvents::get(), failed);
+
+            // retried and failed again, so the backoff doubles
+            System::set_block_number(1 + FAILED_EVENT_RETRY_BLOCKS);
+            retry_failed_events::<Test>();
+            let failed = FailedChainBlockEvents::get();
+            assert_eq!(failed.len(), 1);
+            assert_eq!(failed[0].retries, 1);
+            assert_eq!(
+                failed[0].next_retry_block,
+                1 + 3 * FAILED_EVENT_RETRY_BLOCKS
+            );
+
+            // governance discards the event
+            assert_ok!(CashModule::discard_failed_event(
+                Origin::root(),
+                event.clone()
+            ));
+            assert_eq!(FailedChainBlockEvents::get(), vec![]);
+            assert_err!(
+                CashModule::discard_failed_event(Origin::root(), event),
+                Reason::UnknownFailedEvent
+            );
+        });
+    }
+
+    #[test]
+    fn test_failed_events_are_bounded_and_retried_in_batches() {
+        new_test_ext().execute_with(|| {
+            let lock = |block| {
+                ChainBlockEvent::Eth(
+                    block,
+                    EthereumEvent::Lock {
+                        asset: [1; 20],
+                        sender: [3; 20],
+                        chain: String::from("BTC"),
+                        recipient: [4; 32],
+                        amount: 100,
+                    },
+                )
+            };
+            for block in 0..MAX_FAILED_EVENTS as u64 {
+                record_failed_event::<Test>(&lock(block), Reason::InvalidChain);
+            }
+
+            // the next failure is still reported, but not kept
+            record_failed_event::<Test>(&lock(MAX_FAILED_EVENTS as u64), Reason::InvalidChain);
+            assert_eq!(
+                System::events().last().unwrap().event,
+                mock::Event::pallet_cash(crate::Event::FailedProcessingChainBlockEvent(
+                    lock(MAX_FAILED_EVENTS as u64),
+                    Reason::InvalidChain
+                ))
+            );
+            let failed = FailedChainBlockEvents::get();
+            assert_eq!(failed.len(), MAX_FAILED_EVENTS as usize);
+            assert_eq!(
+                failed.last().unwrap().event,
+                lock(MAX_FAILED_EVENTS as u64 - 1)
+            );
+
+            // only a batch is retried at a time
+            System::set_block_number(1 + FAILED_EVENT_RETRY_BLOCKS);
+            retry_failed_events::<Test>();
+            let failed = FailedChainBlockEvents::get();
+            let batch = FAILED_EVENT_RETRY_BATCH_SIZE as usize;
+            assert!(failed[..batch].iter().all(|failed| failed.retries == 1));
+            assert!(failed[batch..].iter().all(|failed| failed.retries == 0));
+
+            retry_failed_events::<Test>();
+            let failed = FailedChainBlockEvents::get();
+            assert!(failed[..2 * batch].iter().all(|failed| failed.retries == 1));
+            assert!(failed[2 * batch..].iter().all(|failed| failed.retries == 0));
+        });
+    }
+
+    #[test]
+    fn test_apply_chain_events_in_order() {
+        new_test_ext().execute_with(|| {
+            let lock = |block, amount| {
+                ChainBlockEvent::Eth(
+                    block,
+                    EthereumEvent::Lock {
+                        asset: [1; 20],
+                        sender: [3; 20],
+                        chain: String::from("BTC"),
+                        recipient: [4; 32],
+                        amount,
+                    },
+                )
+            };
+            // the recipient chain is not valid, so the events fail in the order they are applied
+            let apply = |events: Vec<ChainBlockEvent>| {
+                FailedChainBlockEvents::kill();
+                PendingChainEvents::put(events);
+                apply_chain_events::<Test>();
+                assert_eq!(PendingChainEvents::get(), vec![]);
+                FailedChainBlockEvents::get()
+                    .into_iter()
+                    .map(|failed| failed.event)
+                    .collect::<Vec<_>>()
+            };
+
+            let applied = apply(vec![lock(3, 1), lock(2, 2), lock(3, 3), lock(1, 4)]);
+            assert_eq!(applied[..2], [lock(1, 4), lock(2, 2)]);
+
+            // events in the same underlying block are ordered by hash, not the order they were accepted in
+            assert_eq!(
+                apply(vec![lock(3, 3), lock(1, 4), lock(3, 1), lock(2, 2)]),
+                applied
+            );
+        });
+    }
+
+    #[test]
+    fn test_is_leader_index() {
+        // two of five validators lead each block, rotating every block
+        let leaders = |block_number| {
+            (0..5)
+                .filter(|index| is_leader_index(*index, 5, block_number))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(leaders(0), vec![0, 1]);
+        assert_eq!(leaders(1), vec![1, 2]);
+        assert_eq!(leaders(4), vec![0, 4]);
+        assert_eq!(leaders(5), vec![0, 1]);
+
+        // small sets are always led by everyone
+        assert!(is_leader_index(0, 1, 7));
+        assert!(is_leader_index(0, 2, 7));
+        assert!(is_leader_index(1, 2, 8));
+    }
+
+    #[test]
+    fn test_is_worker_leader() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            assert_eq!(is_worker_leader::<Test>(&val_a().substrate_id, 1), Ok(true));
+            assert_eq!(is_worker_leader::<Test>(&val_b().substrate_id, 1), Ok(true));
+            assert_eq!(
+                is_worker_leader::<Test>(&AccountId32::new([9u8; 32]), 1),
+                Ok(false)
+            );
+        });
+    }
+
+    #[test]
+    fn test_get_worker_limit() {
+        new_test_ext().execute_with(|| {
+            let key = "TEST_GET_WORKER_LIMIT";
+            assert_eq!(get_worker_limit(key, 20), 20);
+            std::env::set_var(key, "5");
+            assert_eq!(get_worker_limit(key, 20), 5);
+            std::env::set_var(key, "0");
+            assert_eq!(get_worker_limit(key, 20), 20);
+            std::env::set_var(key, "lots");
+            assert_eq!(get_worker_limit(key, 20), 20);
+            std::env::remove_var(key);
+        });
+    }
+
+    #[test]
+    fn test_get_scan_start() -> Result<(), Reason> {
+        let blocks = gen_blocks(10, 13, 0);
+        let pending_blocks = vec![
+            ChainBlockTally::new(ChainBlock::Eth(blocks[0].clone()), &val_a())?,
+            ChainBlockTally::new(ChainBlock::Eth(blocks[1].clone()), &val_a())?,
+            ChainBlockTally::new(ChainBlock::Eth(blocks[2].clone()), &val_b())?,
+        ];
+        let me = val_a().substrate_id;
+
+        // nothing submitted yet, or the cursor is behind the chain
+        assert_eq!(get_scan_start(None, 10, &pending_blocks, &me), 10);
+        assert_eq!(get_scan_start(Some(9), 10, &pending_blocks, &me), 10);
+
+        // resume after the last block we support
+        assert_eq!(get_scan_start(Some(10), 10, &pending_blocks, &me), 11);
+        assert_eq!(get_scan_start(Some(11), 10, &pending_blocks, &me), 12);
+
+        // start over if our submission never made it
+        assert_eq!(get_scan_start(Some(12), 10, &pending_blocks, &me), 10);
+        assert_eq!(get_scan_start(Some(13), 10, &pending_blocks, &me), 10);
+        assert_eq!(get_scan_start(Some(11), 10, &[], &me), 10);
+
+        Ok(())
+    }
 }
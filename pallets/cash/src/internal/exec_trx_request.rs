@@ -1,23 +1,32 @@
 use crate::{
     chains::{ChainAccount, ChainAccountSignature},
+    core::get_balance,
     internal::{
         assets::get_asset,
+        convert::convert_internal,
+        exodus::in_exodus,
         extract::{extract_cash_principal_internal, extract_internal},
         liquidate::{
             liquidate_cash_collateral_internal, liquidate_cash_principal_internal,
-            liquidate_internal,
+            liquidate_internal, start_liquidation_auction_internal,
+        },
+        recovery::{approve_recovery_internal, cancel_recovery_internal, set_guardians_internal},
+        transfer::{
+            accept_portfolio_internal, transfer_cash_principal_internal, transfer_internal,
+            transfer_portfolio_internal,
         },
-        transfer::{transfer_cash_principal_internal, transfer_internal},
     },
     log,
     params::TRANSFER_FEE,
-    reason::Reason,
+    reason::{MathError, Reason},
     require,
     symbol::CASH,
-    types::{CashIndex, CashOrChainAsset, CashPrincipalAmount, Nonce, Quantity},
-    CashPrincipals, Config, GlobalCashIndex, Nonces,
+    types::{AssetBalance, CashIndex, CashOrChainAsset, CashPrincipalAmount, Nonce, Quantity},
+    CashPrincipals, Config, GlobalCashIndex, Nonces, SupportedAssets,
+};
+use frame_support::storage::{
+    with_transaction, IterableStorageMap, StorageMap, StorageValue, TransactionOutcome,
 };
-use frame_support::storage::{StorageMap, StorageValue};
 use our_std::{convert::TryInto, str};
 
 pub fn prepend_nonce(payload: &Vec<u8>, nonce: Nonce) -> Vec<u8> {
@@ -35,7 +44,7 @@ pub fn exec<T: Config>(
 ) -> Result<(), Reason> {
     log!("exec: {}", nonce);
     let request_str: &str = str::from_utf8(&request[..]).map_err(|_| Reason::InvalidUTF8)?;
-    let sender = signature.recover_account(&prepend_nonce(&request, nonce)[..])?;
+    let sender = signature.verify_account(&prepend_nonce(&request, nonce)[..])?;
     exec_trx_request::<T>(request_str, sender, Some(nonce))
 }
 
@@ -47,6 +56,7 @@ pub fn is_minimally_valid_trx_request<T: Config>(
     if request.len() > crate::params::MAX_TRX_REQUEST_LEN {
         return Err(Reason::TrxRequestTooLong);
     }
+    require!(!in_exodus(), Reason::ExodusStarted);
 
     // Basic request validity checks - valid symbols and parsable request
     let request_str: &str = str::from_utf8(&request[..]).map_err(|_| Reason::InvalidUTF8)?;
@@ -54,7 +64,7 @@ pub fn is_minimally_valid_trx_request<T: Config>(
 
     // Signature check
     let sender = signature
-        .recover_account(&prepend_nonce(&request, nonce)[..])
+        .verify_account(&prepend_nonce(&request, nonce)[..])
         .map_err(|_| Reason::SignatureAccountMismatch)?;
 
     let current_nonce = Nonces::get(sender);
@@ -69,6 +79,7 @@ pub fn exec_trx_request<T: Config>(
     log!("exec_trx_request: {}", request_str);
     // Match TrxReq against known Transaction Requests
     let trx_request = trx_request::parse_request(request_str)?;
+    require!(!in_exodus(), Reason::ExodusStarted);
 
     if let Some(nonce) = nonce_opt {
         // Read Require Nonce=Nonce_Account+1
@@ -228,6 +239,55 @@ pub fn exec_trx_request<T: Config>(
 
             _ => return Err(Reason::InvalidLiquidation), // Probably isn't possible
         },
+
+        trx_request::TrxRequest::TransferPortfolio(account) => {
+            transfer_portfolio_internal::<T>(sender, account.into())?;
+        }
+
+        trx_request::TrxRequest::AcceptPortfolio(account) => {
+            accept_portfolio_internal::<T>(sender, account.into())?;
+        }
+
+        trx_request::TrxRequest::SetGuardians(threshold, guardians) => {
+            let guardians = guardians.into_iter().map(|g| g.into()).collect();
+            set_guardians_internal::<T>(sender, threshold, guardians)?;
+        }
+
+        trx_request::TrxRequest::RecoverAccount(account, new_account) => {
+            approve_recovery_internal::<T>(sender, account.into(), new_account.into())?;
+        }
+
+        trx_request::TrxRequest::CancelRecovery => {
+            cancel_recovery_internal::<T>(sender)?;
+        }
+
+        trx_request::TrxRequest::Convert(max_amount, from_asset, to_asset) => {
+            match (
+                CashOrChainAsset::from(from_asset),
+                CashOrChainAsset::from(to_asset),
+            ) {
+                (
+                    CashOrChainAsset::ChainAsset(from_asset),
+                    CashOrChainAsset::ChainAsset(to_asset),
+                ) => match max_amount {
+                    trx_request::MaxAmount::Max => {
+                        return Err(Reason::MaxForNonCashAsset);
+                    }
+                    trx_request::MaxAmount::Amount(amount) => {
+                        let from_asset = get_asset::<T>(from_asset)?;
+                        let to_asset = get_asset::<T>(to_asset)?;
+                        let asset_amount = from_asset.as_quantity(amount.into());
+                        convert_internal::<T>(from_asset, to_asset, sender, asset_amount)?;
+                    }
+                },
+
+                _ => return Err(Reason::InvalidAssetGroup),
+            }
+        }
+
+        trx_request::TrxRequest::StartAuction(borrower) => {
+            start_liquidation_auction_internal::<T>(borrower.into())?;
+        }
     }
 
     if let Some(nonce) = nonce_opt {
@@ -238,6 +298,71 @@ pub fn exec_trx_request<T: Config>(
     Ok(())
 }
 
+/// Return the accounts whose balances a trx request may change, besides the sender.
+fn trx_request_accounts(trx_request: trx_request::TrxRequest) -> Vec<ChainAccount> {
+    match trx_request {
+        trx_request::TrxRequest::Transfer(_, _, account)
+        | trx_request::TrxRequest::Liquidate(_, _, _, account)
+        | trx_request::TrxRequest::TransferPortfolio(account)
+        | trx_request::TrxRequest::AcceptPortfolio(account) => vec![account.into()],
+        _ => vec![],
+    }
+}
+
+/// Return the balance of each account in CASH and in each supported asset.
+fn get_balances<T: Config>(
+    accounts: &[ChainAccount],
+    assets: &[CashOrChainAsset],
+) -> Result<Vec<(ChainAccount, CashOrChainAsset, AssetBalance)>, Reason> {
+    let mut balances = Vec::new();
+    for account in accounts {
+        for asset in assets {
+            balances.push((*account, *asset, get_balance::<T>(*account, *asset)?.value));
+        }
+    }
+    Ok(balances)
+}
+
+/// Execute a signed trx request against the current state, as it would be executed with the sender's current nonce,
+/// without committing any of its changes.
+/// Returns the change in the balance of each asset for every account the request touches, or why it would fail.
+pub fn dry_run<T: Config>(
+    request: Vec<u8>,
+    signature: ChainAccountSignature,
+) -> Result<Vec<(ChainAccount, CashOrChainAsset, AssetBalance)>, Reason> {
+    let nonce = Nonces::get(signature.claimed_account());
+    let (sender, _) = is_minimally_valid_trx_request::<T>(request.clone(), signature, nonce)?;
+    let request_str: &str = str::from_utf8(&request[..]).map_err(|_| Reason::InvalidUTF8)?;
+
+    let mut accounts = vec![sender];
+    for account in trx_request_accounts(trx_request::parse_request(request_str)?) {
+        if !accounts.contains(&account) {
+            accounts.push(account);
+        }
+    }
+    let mut assets = vec![CashOrChainAsset::Cash];
+    assets.extend(SupportedAssets::iter().map(|(asset, _)| CashOrChainAsset::ChainAsset(asset)));
+
+    let before = get_balances::<T>(&accounts, &assets)?;
+    let after = with_transaction(|| {
+        TransactionOutcome::Rollback(
+            exec::<T>(request, signature, nonce)
+                .and_then(|_| get_balances::<T>(&accounts, &assets)),
+        )
+    })?;
+
+    let mut changes = Vec::new();
+    for ((account, asset, balance_before), (_, _, balance_after)) in before.into_iter().zip(after) {
+        let change = balance_after
+            .checked_sub(balance_before)
+            .ok_or(MathError::Overflow)?;
+        if change != 0 {
+            changes.push((account, asset, change));
+        }
+    }
+    Ok(changes)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -284,6 +409,7 @@ mod tests {
                 parent: [0u8; 32],
                 account: [1; 20],
                 principal: 3000000,
+                index: GlobalCashIndex::get().0,
             });
 
             // Check Notice
@@ -296,7 +422,7 @@ mod tests {
             assert_eq!(
                 notice_state,
                 NoticeState::Pending {
-                    signature_pairs: ChainSignatureList::Eth([].to_vec())
+                    signature_pairs: ChainSignatureList::Eth(BoundedVec::default())
                 }
             );
             let (hash_notice_id, _) = LatestNotice::get(ChainId::Eth).unwrap();
@@ -358,6 +484,7 @@ mod tests {
                 parent: [0u8; 32],
                 account: [1; 20],
                 principal: 4000000,
+                index: GlobalCashIndex::get().0,
             });
 
             // Check Notice
@@ -370,7 +497,7 @@ mod tests {
             assert_eq!(
                 notice_state,
                 NoticeState::Pending {
-                    signature_pairs: ChainSignatureList::Eth([].to_vec())
+                    signature_pairs: ChainSignatureList::Eth(BoundedVec::default())
                 }
             );
             let (hash_notice_id, _) = LatestNotice::get(ChainId::Eth).unwrap();
@@ -449,7 +576,7 @@ mod tests {
             assert_eq!(
                 notice_state,
                 NoticeState::Pending {
-                    signature_pairs: ChainSignatureList::Eth([].to_vec())
+                    signature_pairs: ChainSignatureList::Eth(BoundedVec::default())
                 }
             );
             let (hash_notice_id, _) = LatestNotice::get(ChainId::Eth).unwrap();
@@ -551,7 +678,8 @@ mod tests {
             assert_eq!(
                 mock::Event::pallet_cash(crate::Event::MinerPaid(
                     miner,
-                    index.cash_principal_amount(TRANSFER_FEE).unwrap()
+                    index.cash_principal_amount(TRANSFER_FEE).unwrap(),
+                    index
                 )),
                 miner_paid_event.event
             );
@@ -618,7 +746,8 @@ mod tests {
             assert_eq!(
                 mock::Event::pallet_cash(crate::Event::MinerPaid(
                     miner,
-                    index.cash_principal_amount(TRANSFER_FEE).unwrap()
+                    index.cash_principal_amount(TRANSFER_FEE).unwrap(),
+                    index
                 )),
                 miner_paid_event.event
             );
@@ -771,6 +900,20 @@ mod tests {
         });
     }
 
+    #[test]
+    fn exec_trx_start_auction_not_allowed() {
+        new_test_ext().execute_with(|| {
+            let req_str = "(StartAuction Eth:0x0101010101010101010101010101010101010101)";
+            let account = ChainAccount::Eth([2; 20]);
+            let nonce = Some(0);
+
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, nonce),
+                Err(Reason::LiquidationAuctionNotAllowed)
+            );
+        });
+    }
+
     #[test]
     fn exec_trx_liquidate_cash_collateral_self_transfer() {
         new_test_ext().execute_with(|| {
@@ -1147,6 +1290,56 @@ mod tests {
         });
     }
 
+    fn sign_request(request: &Vec<u8>, nonce: Nonce) -> ChainAccountSignature {
+        match validator_a_sign(&prepend_nonce(request, nonce)).unwrap() {
+            ChainSignature::Eth(sig) => ChainAccountSignature::Eth(val_a().eth_address, sig),
+            _ => panic!("unexpected signature"),
+        }
+    }
+
+    #[test]
+    fn test_dry_run() {
+        new_test_ext().execute_with(|| {
+            let request =
+                b"(Transfer 3000000 CASH Eth:0x0101010101010101010101010101010101010101)".to_vec();
+            let account = ChainAccount::Eth(val_a().eth_address);
+            let to_account = ChainAccount::Eth([1; 20]);
+            init_cash(account, CashPrincipal::from_nominal("4"));
+            Miner::put(ChainAccount::Eth([3; 20]));
+
+            assert_eq!(
+                dry_run::<Test>(request.clone(), sign_request(&request, 1)),
+                Err(Reason::SignatureAccountMismatch)
+            );
+            assert_eq!(
+                dry_run::<Test>(request.clone(), sign_request(&request, 0)),
+                Ok(vec![
+                    (account, CashOrChainAsset::Cash, -3_010_000),
+                    (to_account, CashOrChainAsset::Cash, 3_000_000),
+                ])
+            );
+
+            // nothing is actually changed
+            assert_eq!(
+                CashPrincipals::get(account),
+                CashPrincipal::from_nominal("4")
+            );
+            assert_eq!(
+                CashPrincipals::get(to_account),
+                CashPrincipal::from_nominal("0")
+            );
+            assert_eq!(Nonces::get(account), 0);
+
+            let request = b"(Transfer 1 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x0101010101010101010101010101010101010101)"
+                .to_vec();
+            assert_eq!(
+                dry_run::<Test>(request.clone(), sign_request(&request, 0)),
+                Err(Reason::AssetNotSupported)
+            );
+        });
+    }
+
     #[test]
     fn exec_trx_request_does_not_crash() {
         new_test_ext().execute_with(|| {
@@ -0,0 +1,147 @@
+use crate::{
+    chains::{ChainId, ChainSignature},
+    core::{get_current_validator, get_recent_timestamp, recover_validator, validator_sign},
+    debug, error,
+    events::fetch_gas_price,
+    internal::events::is_worker_leader,
+    log,
+    params::{EXECUTION_FEE_POLL_INTERVAL_BLOCKS, EXTRACTION_GAS_ESTIMATE},
+    reason::{MathError, Reason},
+    require,
+    types::{AssetAmount, ExecutionFeeReport, Timestamp},
+    Call, Config, ExecutionFees, Starports,
+};
+use codec::Encode;
+use frame_support::storage::{IterableStorageMap, StorageMap};
+use frame_system::offchain::SubmitTransaction;
+use our_std::vec::Vec;
+use sp_runtime::traits::UniqueSaturatedInto;
+
+/// Observe the execution fee of every chain with a Starport, if the worker leads at this block.
+pub fn track_execution_fees<T: Config>(block_number: T::BlockNumber) -> Result<(), Reason> {
+    let block_number: u64 = block_number.unique_saturated_into();
+    if block_number % EXECUTION_FEE_POLL_INTERVAL_BLOCKS != 0 {
+        return Ok(());
+    }
+
+    let me = get_current_validator::<T>()?;
+    if !is_worker_leader::<T>(&me.substrate_id, block_number)? {
+        debug!(
+            "Worker is not a leader for block {}, not observing execution fees",
+            block_number
+        );
+        return Ok(());
+    }
+
+    let mut fees = Vec::new();
+    for (chain_id, _) in Starports::iter() {
+        match fetch_execution_fee(chain_id) {
+            Ok(fee) => fees.push((chain_id, fee)),
+            Err(err) => error!(
+                "Failed to observe execution fee for {:?}: {:?}",
+                chain_id, err
+            ),
+        }
+    }
+
+    let report = ExecutionFeeReport {
+        timestamp: get_recent_timestamp::<T>()?,
+        fees,
+    };
+    submit_execution_fee_report::<T>(&report)
+}
+
+/// Estimate the fee to execute an extraction on the chain, in its native asset, from its gas price.
+pub fn fetch_execution_fee(chain_id: ChainId) -> Result<AssetAmount, Reason> {
+    let gas_price = fetch_gas_price(chain_id)?;
+    Ok(EXTRACTION_GAS_ESTIMATE
+        .checked_mul(gas_price as AssetAmount)
+        .ok_or(MathError::Overflow)?)
+}
+
+/// Submit the execution fees the worker observed.
+pub fn submit_execution_fee_report<T: Config>(report: &ExecutionFeeReport) -> Result<(), Reason> {
+    if report.fees.len() > 0 {
+        log!("Submitting execution fees extrinsic: {:?}", report);
+        let signature = validator_sign::<T>(&report.encode()[..])?;
+        let call = Call::receive_execution_fees(report.clone(), signature);
+        if let Err(e) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
+            log!("Error while submitting execution fees: {:?}", e);
+            return Err(Reason::FailedToSubmitExtrinsic);
+        }
+    }
+    Ok(())
+}
+
+/// Check that the fee observed for a chain at the given time is newer than the one already recorded.
+pub fn check_execution_fee(chain_id: ChainId, timestamp: Timestamp) -> Result<(), Reason> {
+    if let Some((_, last_timestamp)) = ExecutionFees::get(chain_id) {
+        require!(timestamp > last_timestamp, Reason::StaleExecutionFee);
+    }
+    Ok(())
+}
+
+/// Receive the execution fees observed by a validator.
+pub fn receive_execution_fees<T: Config>(
+    report: ExecutionFeeReport,
+    signature: ChainSignature,
+) -> Result<(), Reason> {
+    recover_validator::<T>(&report.encode(), signature)?;
+    for (chain_id, _) in report.fees.iter() {
+        check_execution_fee(*chain_id, report.timestamp)?;
+    }
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    for (chain_id, fee) in report.fees {
+        ExecutionFees::insert(chain_id, (fee, report.timestamp));
+    }
+    Ok(())
+}
+
+/// Return the most recent estimate of the fee to execute an extraction on the chain, and when it was observed.
+pub fn get_execution_fee(chain_id: ChainId) -> Result<(AssetAmount, Timestamp), Reason> {
+    ExecutionFees::get(chain_id).ok_or(Reason::NoExecutionFee)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_receive_execution_fees() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let report = ExecutionFeeReport {
+                timestamp: 1000,
+                fees: vec![(ChainId::Eth, 3_000_000_000_000_000)],
+            };
+            let signature = validator_sign::<Test>(&report.encode()).unwrap();
+
+            assert_eq!(get_execution_fee(ChainId::Eth), Err(Reason::NoExecutionFee));
+            assert_eq!(
+                receive_execution_fees::<Test>(
+                    ExecutionFeeReport {
+                        timestamp: 2000,
+                        ..report.clone()
+                    },
+                    signature
+                ),
+                Err(Reason::UnknownValidator)
+            );
+            assert_eq!(
+                receive_execution_fees::<Test>(report.clone(), signature),
+                Ok(())
+            );
+            assert_eq!(
+                get_execution_fee(ChainId::Eth),
+                Ok((3_000_000_000_000_000, 1000))
+            );
+            assert_eq!(
+                receive_execution_fees::<Test>(report, signature),
+                Err(Reason::StaleExecutionFee)
+            );
+        });
+    }
+}
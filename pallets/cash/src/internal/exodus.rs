@@ -0,0 +1,265 @@
+use crate::{
+    chains::{ChainAccount, ChainAsset, ChainId},
+    core::{get_accounts, get_recent_timestamp},
+    factor::{BigUint, Factor},
+    internal::{
+        self,
+        account_history::deposit_account_event,
+        assets::{get_asset, get_price},
+        balance_helpers::{neg_balance, pos_balance},
+    },
+    log,
+    params::EXODUS_BATCH_SIZE,
+    pipeline::{load_portfolio, CashPipeline},
+    portfolio::Portfolio,
+    reason::{MathError, Reason},
+    require,
+    types::{AssetAmount, CashPrincipalAmount, Uint},
+    AssetBalances, AssetsWithNonZeroBalance, Config, Event, ExodusAccounts, ExodusCursor,
+    ExodusStarted, GlobalCashIndex, Module, StarportHoldings,
+};
+use frame_support::storage::{IterableStorageDoubleMap, StorageMap, StorageValue};
+use our_std::vec::Vec;
+
+/// Whether the protocol is winding down, in which case no new activity is accepted.
+pub fn in_exodus() -> bool {
+    ExodusStarted::get().is_some()
+}
+
+/// Whether extraction notices can be sent to the chain, i.e. it has a starport.
+fn has_starport(chain_id: ChainId) -> bool {
+    match chain_id {
+        ChainId::Eth | ChainId::Matic => true,
+        #[cfg(feature = "runtime-dev")]
+        ChainId::Mock => true,
+        _ => false,
+    }
+}
+
+/// Freeze the protocol and begin returning the net collateral of every account, as a last resort.
+/// The accounts are queued in a deterministic order, and a batch of them is processed each block.
+/// There is no way back from an exodus, short of a runtime upgrade.
+pub fn start_exodus<T: Config>() -> Result<(), Reason> {
+    require!(!in_exodus(), Reason::ExodusStarted);
+    let now = get_recent_timestamp::<T>()?;
+    let accounts = get_accounts::<T>()?;
+    let count = accounts.len() as u32;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    for (index, account) in accounts.into_iter().enumerate() {
+        ExodusAccounts::insert(index as u32, account);
+    }
+    ExodusCursor::put((0, count));
+    ExodusStarted::put(now);
+
+    <Module<T>>::deposit_event(Event::ExodusStarted(count));
+
+    Ok(())
+}
+
+/// Return the net collateral of the next batch of queued accounts.
+/// An account which cannot be processed is skipped, leaving its balances in place.
+/// Called when a block is initialized, and thus may not fail.
+pub fn process_exodus<T: Config>() {
+    let (next, count) = ExodusCursor::get();
+    if next >= count {
+        return;
+    }
+
+    let end = count.min(next.saturating_add(EXODUS_BATCH_SIZE));
+    for index in next..end {
+        if let Some(account) = ExodusAccounts::take(index) {
+            if let Err(err) = return_net_collateral::<T>(account) {
+                log!("Could not return collateral of {:?}: {:?}", account, err);
+                <Module<T>>::deposit_event(Event::ExodusSkipped(account, err));
+            }
+        }
+    }
+    ExodusCursor::put((end, count));
+
+    if end == count {
+        <Module<T>>::deposit_event(Event::ExodusCompleted(count));
+    }
+}
+
+/// Return the value of the collateral and debts in a portfolio, at face value rather than after liquidity factors.
+pub fn get_collateral_and_debt<T: Config>(portfolio: &Portfolio) -> Result<(Uint, Uint), Reason> {
+    let mut collateral: Uint = 0;
+    let mut debt: Uint = 0;
+    let balances = portfolio
+        .positions
+        .iter()
+        .map(|(_, balance)| *balance)
+        .chain(our_std::iter::once(portfolio.cash));
+    for balance in balances {
+        let worth = balance.mul_price(get_price::<T>(balance.units)?)?;
+        collateral = collateral
+            .checked_add(pos_balance(worth.value))
+            .ok_or(MathError::Overflow)?;
+        debt = debt
+            .checked_add(neg_balance(worth.value)?)
+            .ok_or(MathError::Overflow)?;
+    }
+    Ok((collateral, debt))
+}
+
+/// Extract the share of an account's collateral which exceeds its debts, back to its own chain.
+/// Collateral held on other chains helps cover its debts, but is left in place, as are the debts.
+/// No more of an asset is extracted than its starport holds.
+fn return_net_collateral<T: Config>(account: ChainAccount) -> Result<(), Reason> {
+    let chain_id = account.chain_id();
+    if !has_starport(chain_id) || !internal::chain_status::is_active(chain_id) {
+        return Ok(());
+    }
+
+    let (collateral, debt) = get_collateral_and_debt::<T>(&load_portfolio::<T>(account)?)?;
+    if collateral <= debt {
+        return Ok(());
+    }
+    let share = Factor::from_fraction(collateral - debt, collateral)?;
+
+    let mut pipeline = CashPipeline::new();
+    let mut extractions: Vec<(ChainAsset, AssetAmount)> = Vec::new();
+    for (asset, _) in AssetsWithNonZeroBalance::iter_prefix(account) {
+        if asset.chain_id() != chain_id {
+            continue;
+        }
+        let info = get_asset::<T>(asset)?;
+        let amount = info
+            .as_quantity(pos_balance(AssetBalances::get(asset, account)))
+            .mul_factor(share)?
+            .value
+            .min(StarportHoldings::get(asset));
+        if amount > 0 {
+            pipeline = pipeline.extract_asset::<T>(account, asset, info.as_quantity(amount))?;
+            extractions.push((asset, amount));
+        }
+    }
+
+    // the cash principal is only settled once the interest on each asset has been applied
+    let principal = pipeline
+        .state
+        .get_cash_principal::<T>(account)
+        .amount_withdrawable()?;
+    let principal = CashPrincipalAmount(
+        BigUint::from_uint(principal.0)
+            .mul_decimal(share.0, Factor::DECIMALS)
+            .to_uint()?,
+    );
+    if principal.0 > 0 {
+        pipeline = pipeline.extract_cash::<T>(account, principal)?;
+    }
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    pipeline.commit::<T>();
+
+    for (asset, amount) in extractions {
+        internal::holdings::sub_holdings(asset, amount);
+        internal::notices::dispatch_extraction_notice::<T>(asset, account, amount);
+        deposit_account_event::<T>(Event::Extract(asset, account, account, amount));
+    }
+
+    if principal.0 > 0 {
+        let index = GlobalCashIndex::get();
+        internal::notices::dispatch_cash_extraction_notice::<T>(account, principal, index);
+        deposit_account_event::<T>(Event::ExtractCash(account, account, principal, index));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{common::*, *};
+
+    #[allow(non_upper_case_globals)]
+    const account_a: ChainAccount = ChainAccount::Eth([1u8; 20]);
+    #[allow(non_upper_case_globals)]
+    const account_b: ChainAccount = ChainAccount::Eth([2u8; 20]);
+
+    #[test]
+    fn test_start_exodus() {
+        new_test_ext().execute_with(|| {
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1000);
+            init_eth_asset().unwrap();
+            init_asset_balance(Eth, account_a, eth.as_quantity_nominal("1").value as i128);
+            init_asset_balance(Eth, account_b, eth.as_quantity_nominal("2").value as i128);
+
+            assert!(!in_exodus());
+            assert_eq!(start_exodus::<Test>(), Ok(()));
+            assert!(in_exodus());
+            assert_eq!(ExodusStarted::get(), Some(1000));
+            assert_eq!(ExodusCursor::get(), (0, 2));
+            assert_eq!(ExodusAccounts::get(0), Some(account_a));
+            assert_eq!(ExodusAccounts::get(1), Some(account_b));
+            assert_eq!(start_exodus::<Test>(), Err(Reason::ExodusStarted));
+
+            // nothing more happens once the protocol is frozen
+            assert_eq!(
+                internal::exec_trx_request::exec_trx_request::<Test>(
+                    "(Transfer 1000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0202020202020202020202020202020202020202)",
+                    account_a,
+                    None
+                ),
+                Err(Reason::ExodusStarted)
+            );
+        });
+    }
+
+    #[test]
+    fn test_process_exodus() {
+        new_test_ext().execute_with(|| {
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1000);
+            init_eth_asset().unwrap();
+            init_asset_balance(Eth, account_a, eth.as_quantity_nominal("1").value as i128);
+            init_asset_balance(Eth, account_b, eth.as_quantity_nominal("4").value as i128);
+            init_cash(account_b, CashPrincipal::from_nominal("-2000"));
+
+            assert_eq!(start_exodus::<Test>(), Ok(()));
+            assert_eq!(ExodusCursor::get(), (0, 3));
+            process_exodus::<Test>();
+
+            // without debts, all the collateral is returned, including CASH
+            assert_eq!(AssetBalances::get(Eth, account_a), 0);
+            assert_eq!(
+                CashPrincipals::get(ChainAccount::Eth([0u8; 20])),
+                CashPrincipal::ZERO
+            );
+
+            // with debts worth a quarter of the collateral, three quarters of it are returned
+            assert_eq!(
+                AssetBalances::get(Eth, account_b),
+                eth.as_quantity_nominal("1").value as i128
+            );
+            assert_eq!(
+                CashPrincipals::get(account_b),
+                CashPrincipal::from_nominal("-2000")
+            );
+            assert_eq!(
+                StarportHoldings::get(Eth),
+                eth.as_quantity_nominal("1").value
+            );
+            assert_eq!(ExodusCursor::get(), (3, 3));
+            assert_eq!(ExodusAccounts::get(0), None);
+
+            let events = System::events();
+            assert_eq!(
+                events.last().unwrap().event,
+                mock::Event::pallet_cash(crate::Event::ExodusCompleted(3))
+            );
+            assert_eq!(
+                events
+                    .iter()
+                    .filter(|e| matches!(
+                        e.event,
+                        mock::Event::pallet_cash(crate::Event::Extract(..))
+                    ))
+                    .count(),
+                2
+            );
+        });
+    }
+}
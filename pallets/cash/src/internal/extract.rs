@@ -1,16 +1,27 @@
 use crate::{
     chains::ChainAccount,
-    internal,
+    core::get_recent_timestamp,
+    internal::{self, account_history::deposit_account_event},
     params::MIN_TX_VALUE,
     pipeline::CashPipeline,
     reason::Reason,
     require, require_min_tx_value,
     types::{AssetInfo, AssetQuantity, CashIndex, CashPrincipalAmount},
-    Config, Event, GlobalCashIndex, Module,
+    Config, Event, ExtractionLimits, GlobalCashIndex, Starports,
 };
-use frame_support::storage::StorageValue;
+use frame_support::storage::{StorageMap, StorageValue};
 use our_std::log;
 
+/// Refuse to extract to an account which could never release the funds, such as the starport.
+pub fn require_valid_recipient<T: Config>(recipient: ChainAccount) -> Result<(), Reason> {
+    recipient.validate_recipient()?;
+    require!(
+        Starports::get(recipient.chain_id()) != Some(recipient),
+        Reason::InvalidRecipient
+    );
+    Ok(())
+}
+
 pub fn extract_internal<T: Config>(
     asset: AssetInfo,
     sender: ChainAccount,
@@ -18,17 +29,49 @@ pub fn extract_internal<T: Config>(
     quantity: AssetQuantity,
 ) -> Result<(), Reason> {
     log!("extract_cash_principal_internal");
+    internal::chain_status::require_active(recipient.chain_id())?;
+    require_valid_recipient::<T>(recipient)?;
     require_min_tx_value!(internal::assets::get_value::<T>(quantity)?);
 
-    CashPipeline::new()
+    let pipeline = CashPipeline::new()
         .extract_asset::<T>(sender, asset.asset, quantity)?
-        .check_collateralized::<T>(sender)?
-        .check_sufficient_total_funds::<T>(asset)?
-        .commit::<T>();
-
-    internal::notices::dispatch_extraction_notice::<T>(asset.asset, recipient, quantity);
+        .check_collateralized_fresh::<T>(sender)?
+        .check_sufficient_total_funds::<T>(asset)?;
+
+    // the starport must still hold what is extracted, even if the protocol owes more of the asset
+    internal::holdings::require_holdings(asset.asset, quantity.value)?;
+    let limited_at = if ExtractionLimits::contains_key(asset.asset) {
+        internal::extraction_limit::require_queue_capacity(asset.asset)?;
+        Some(get_recent_timestamp::<T>()?)
+    } else {
+        None
+    };
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    pipeline.commit::<T>();
+    internal::holdings::sub_holdings(asset.asset, quantity.value);
+
+    match limited_at {
+        Some(now) => {
+            internal::extraction_limit::dispatch_or_queue_extraction::<T>(
+                asset.asset,
+                sender,
+                recipient,
+                quantity,
+                now,
+            );
+        }
+        None => {
+            internal::notices::dispatch_extraction_notice::<T>(
+                asset.asset,
+                recipient,
+                quantity.value,
+            );
+        }
+    }
 
-    <Module<T>>::deposit_event(Event::Extract(
+    deposit_account_event::<T>(Event::Extract(
         asset.asset,
         sender,
         recipient,
@@ -43,18 +86,20 @@ pub fn extract_cash_principal_internal<T: Config>(
     recipient: ChainAccount,
     principal: CashPrincipalAmount,
 ) -> Result<(), Reason> {
+    internal::chain_status::require_active(recipient.chain_id())?;
+    require_valid_recipient::<T>(recipient)?;
     let index: CashIndex = GlobalCashIndex::get();
     let amount = index.cash_quantity(principal)?;
     require_min_tx_value!(internal::assets::get_value::<T>(amount)?);
 
     CashPipeline::new()
         .extract_cash::<T>(sender, principal)?
-        .check_collateralized::<T>(sender)?
+        .check_collateralized_fresh::<T>(sender)?
         .commit::<T>();
 
-    internal::notices::dispatch_cash_extraction_notice::<T>(recipient, principal);
+    internal::notices::dispatch_cash_extraction_notice::<T>(recipient, principal, index);
 
-    <Module<T>>::deposit_event(Event::ExtractCash(sender, recipient, principal, index));
+    deposit_account_event::<T>(Event::ExtractCash(sender, recipient, principal, index));
 
     Ok(())
 }
@@ -84,12 +129,42 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_extract_to_invalid_recipient() -> Result<(), Reason> {
+        let jared = ChainAccount::from_str("Eth:0x18c8F1222083997405F2E482338A4650ac02e1d6")?;
+        let starport = ChainAccount::from_str("Eth:0x7f89077b122afaaf6ab50aa12e9cb46bb9a058c4")?;
+
+        new_test_ext().execute_with(|| {
+            Starports::insert(ChainId::Eth, starport);
+            CashPrincipals::insert(&jared, CashPrincipal::from_nominal("10000"));
+
+            assert_err!(
+                super::extract_cash_principal_internal::<Test>(
+                    jared,
+                    ChainAccount::Eth([0; 20]),
+                    CashPrincipalAmount(1_000_000)
+                ),
+                Reason::InvalidRecipient
+            );
+            assert_err!(
+                super::extract_internal::<Test>(eth, jared, starport, qty!("1", ETH)),
+                Reason::InvalidRecipient
+            );
+            assert_eq!(
+                CashPrincipals::get(&jared),
+                CashPrincipal::from_nominal("10000")
+            );
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_extract_internal_min_value() -> Result<(), Reason> {
         let asset = ChainAsset::Eth([238; 20]);
         let asset_info = AssetInfo::minimal(asset, ETH);
         let holder = ChainAccount::Eth([0; 20]);
-        let recipient = ChainAccount::Eth([0; 20]);
+        let recipient = ChainAccount::Eth([1; 20]);
 
         new_test_ext().execute_with(|| {
             SupportedAssets::insert(&asset, asset_info);
@@ -139,6 +214,94 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_extract_internal_stale_price() -> Result<(), Reason> {
+        let asset = ChainAsset::Eth([238; 20]);
+        let asset_info = AssetInfo {
+            liquidity_factor: LiquidityFactor::from_nominal("1"),
+            ..AssetInfo::minimal(asset, ETH)
+        };
+        let holder = ChainAccount::Eth([0; 20]);
+        let recipient = ChainAccount::Eth([1; 20]);
+
+        new_test_ext().execute_with(|| {
+            SupportedAssets::insert(&asset, asset_info);
+            Prices::insert(asset_info.ticker, 100_000); // $0.10
+            let quantity = get_quantity::<Test>(asset, 50_000_000_000_000_000_000).unwrap();
+            let hodl_balance = quantity.value * 5;
+            AssetBalances::insert(asset, holder, hodl_balance as AssetBalance);
+            AssetsWithNonZeroBalance::insert(holder, asset, ());
+            TotalSupplyAssets::insert(&asset, hodl_balance);
+            StarportHoldings::insert(&asset, hodl_balance);
+
+            pallet_oracle::PriceGuards::insert(
+                asset_info.ticker,
+                pallet_oracle::types::PriceGuard {
+                    max_age: 1000,
+                    max_deviation_bps: 0,
+                },
+            );
+            pallet_oracle::PriceTimes::insert(asset_info.ticker, 1000);
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(3000);
+
+            assert_err!(
+                super::extract_internal::<Test>(asset_info, holder, recipient, quantity),
+                Reason::StalePrice
+            );
+            assert_eq!(
+                AssetBalances::get(asset, holder),
+                hodl_balance as AssetBalance
+            );
+
+            pallet_oracle::PriceTimes::insert(asset_info.ticker, 2500);
+            assert_ok!(super::extract_internal::<Test>(
+                asset_info, holder, recipient, quantity
+            ));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_extract_internal_insufficient_starport_holdings() -> Result<(), Reason> {
+        let asset = ChainAsset::Eth([238; 20]);
+        let asset_info = AssetInfo {
+            liquidity_factor: LiquidityFactor::from_nominal("1"),
+            ..AssetInfo::minimal(asset, ETH)
+        };
+        let holder = ChainAccount::Eth([0; 20]);
+        let recipient = ChainAccount::Eth([1; 20]);
+
+        new_test_ext().execute_with(|| {
+            SupportedAssets::insert(&asset, asset_info);
+            Prices::insert(asset_info.ticker, 100_000); // $0.10
+            let quantity = get_quantity::<Test>(asset, 50_000_000_000_000_000_000).unwrap();
+            let hodl_balance = quantity.value * 5;
+            AssetBalances::insert(asset, holder, hodl_balance as AssetBalance);
+            AssetsWithNonZeroBalance::insert(holder, asset, ());
+            TotalSupplyAssets::insert(&asset, hodl_balance);
+            StarportHoldings::insert(&asset, quantity.value - 1);
+
+            assert_err!(
+                super::extract_internal::<Test>(asset_info, holder, recipient, quantity),
+                Reason::InsufficientStarportLiquidity
+            );
+            assert_eq!(
+                AssetBalances::get(asset, holder),
+                hodl_balance as AssetBalance
+            );
+            assert_eq!(StarportHoldings::get(&asset), quantity.value - 1);
+
+            StarportHoldings::insert(&asset, quantity.value);
+            assert_ok!(super::extract_internal::<Test>(
+                asset_info, holder, recipient, quantity
+            ));
+            assert_eq!(StarportHoldings::get(&asset), 0);
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_extract_internal_sufficient_value() -> Result<(), Reason> {
         let eth_asset = [238; 20];
@@ -148,7 +311,7 @@ mod tests {
             ..AssetInfo::minimal(asset, ETH)
         };
         let eth_holder = [0; 20];
-        let eth_recipient = [0; 20];
+        let eth_recipient = [1; 20];
         let holder = ChainAccount::Eth(eth_holder);
         let recipient = ChainAccount::Eth(eth_recipient);
 
@@ -160,6 +323,7 @@ mod tests {
             AssetBalances::insert(asset, holder, hodl_balance as AssetBalance);
             AssetsWithNonZeroBalance::insert(holder, asset, ());
             TotalSupplyAssets::insert(&asset, hodl_balance);
+            StarportHoldings::insert(&asset, hodl_balance);
 
             let asset_balances_pre = AssetBalances::get(asset, holder);
             let total_supply_pre = TotalSupplyAssets::get(asset);
@@ -229,7 +393,7 @@ mod tests {
                     ChainId::Eth,
                     expected_notice_id,
                     NoticeState::Pending {
-                        signature_pairs: ChainSignatureList::Eth(vec![])
+                        signature_pairs: ChainSignatureList::Eth(BoundedVec::default())
                     }
                 ),
                 notice_state
@@ -260,7 +424,7 @@ mod tests {
             ..AssetInfo::minimal(asset, ETH)
         };
         let eth_holder = [0; 20];
-        let eth_recipient = [0; 20];
+        let eth_recipient = [1; 20];
         let holder = ChainAccount::Eth(eth_holder);
         let recipient = ChainAccount::Eth(eth_recipient);
 
@@ -272,6 +436,7 @@ mod tests {
             AssetBalances::insert(asset, holder, hodl_balance as AssetBalance);
             AssetsWithNonZeroBalance::insert(holder, asset, ());
             TotalSupplyAssets::insert(&asset, hodl_balance);
+            StarportHoldings::insert(&asset, hodl_balance);
 
             let notices_pre: Vec<(NoticeId, Notice)> = Notices::iter_prefix(ChainId::Eth).collect();
             let notice_states_pre: Vec<(ChainId, NoticeId, NoticeState)> =
@@ -305,7 +470,7 @@ mod tests {
                     ChainId::Eth,
                     expected_notice_id,
                     NoticeState::Pending {
-                        signature_pairs: ChainSignatureList::Eth(vec![])
+                        signature_pairs: ChainSignatureList::Eth(BoundedVec::default())
                     }
                 ),
                 notice_state
@@ -356,7 +521,7 @@ mod tests {
             assert_eq!(notice_2, expected_notice_2.clone());
             assert_eq!(
                 NoticeState::Pending {
-                    signature_pairs: ChainSignatureList::Eth(vec![])
+                    signature_pairs: ChainSignatureList::Eth(BoundedVec::default())
                 },
                 notice_state_2
             );
@@ -0,0 +1,350 @@
+use crate::{
+    chains::{ChainAccount, ChainAsset},
+    internal,
+    params::{
+        EXTRACTION_BUCKET, EXTRACTION_RELEASE_BATCH_SIZE, EXTRACTION_WINDOW, MAX_QUEUED_EXTRACTIONS,
+    },
+    reason::Reason,
+    require,
+    types::{AssetAmount, AssetQuantity, Timestamp},
+    Config, Event, ExtractionLimits, ExtractionWindows, Module, QueuedExtractions,
+};
+use frame_support::storage::{IterableStorageMap, StorageMap};
+use our_std::{log, vec::Vec};
+
+/// Set (or remove) the maximum amount of an asset which may be extracted per window.
+pub fn set_extraction_limit<T: Config>(
+    asset: ChainAsset,
+    limit: Option<AssetAmount>,
+) -> Result<(), Reason> {
+    internal::assets::get_asset::<T>(asset)?;
+
+    match limit {
+        Some(amount) => ExtractionLimits::insert(asset, amount),
+        None => ExtractionLimits::remove(asset),
+    }
+
+    <Module<T>>::deposit_event(Event::SetExtractionLimit(asset, limit));
+
+    Ok(())
+}
+
+/// Return the amounts extracted within the rolling window for the asset as of now, in order.
+/// A bucket stops counting against the limit once a full window has passed since it closed.
+pub fn get_extraction_window(asset: ChainAsset, now: Timestamp) -> Vec<(Timestamp, AssetAmount)> {
+    ExtractionWindows::get(asset)
+        .into_iter()
+        .filter(|(bucket, _)| {
+            bucket
+                .saturating_add(EXTRACTION_BUCKET)
+                .saturating_add(EXTRACTION_WINDOW)
+                > now
+        })
+        .collect()
+}
+
+/// Return the total amount extracted within the rolling window.
+fn get_extracted(window: &[(Timestamp, AssetAmount)]) -> AssetAmount {
+    window
+        .iter()
+        .fold(0, |total, (_, amount)| total.saturating_add(*amount))
+}
+
+/// Tally an extraction in the bucket for now, within the rolling window.
+/// Saturates rather than fails, as extractions are tallied once they are already committed.
+fn add_extracted(window: &mut Vec<(Timestamp, AssetAmount)>, now: Timestamp, amount: AssetAmount) {
+    let bucket = now - now % EXTRACTION_BUCKET;
+    match window.last_mut() {
+        Some((last, extracted)) if *last == bucket => {
+            *extracted = extracted.saturating_add(amount);
+        }
+        _ => window.push((bucket, amount)),
+    }
+}
+
+/// Whether an amount may be extracted given the limit and the amount already extracted within the window.
+/// An empty window always admits the next extraction, so that a single request above the limit
+///  is delayed until it has a window to itself, rather than being stuck forever.
+fn within_limit(limit: Option<AssetAmount>, extracted: AssetAmount, amount: AssetAmount) -> bool {
+    match limit {
+        None => true,
+        Some(limit) => {
+            extracted == 0
+                || extracted
+                    .checked_add(amount)
+                    .map_or(false, |total| total <= limit)
+        }
+    }
+}
+
+/// Refuse an extraction of the asset if it would have to wait, but too many extractions already are.
+pub fn require_queue_capacity(asset: ChainAsset) -> Result<(), Reason> {
+    require!(
+        QueuedExtractions::decode_len(asset).unwrap_or(0) < MAX_QUEUED_EXTRACTIONS as usize,
+        Reason::ExtractionQueueFull
+    );
+    Ok(())
+}
+
+/// Either dispatch the extraction notice immediately, or queue it until the window has room.
+/// Called once the extraction is committed, so the queue must already be known to have capacity.
+/// Returns true if the extraction was queued.
+pub fn dispatch_or_queue_extraction<T: Config>(
+    asset: ChainAsset,
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    quantity: AssetQuantity,
+    now: Timestamp,
+) -> bool {
+    let limit = ExtractionLimits::get(asset);
+    if limit.is_none() {
+        internal::notices::dispatch_extraction_notice::<T>(asset, recipient, quantity.value);
+        return false;
+    }
+
+    let mut window = get_extraction_window(asset, now);
+    let extracted = get_extracted(&window);
+
+    // Never jump ahead of anything already waiting
+    if !QueuedExtractions::contains_key(asset) && within_limit(limit, extracted, quantity.value) {
+        add_extracted(&mut window, now, quantity.value);
+        ExtractionWindows::insert(asset, window);
+        internal::notices::dispatch_extraction_notice::<T>(asset, recipient, quantity.value);
+        false
+    } else {
+        log!(
+            "Queueing extraction of {:?} {:?} to {:?}",
+            quantity.value,
+            asset,
+            recipient
+        );
+        QueuedExtractions::append(asset, (recipient, quantity.value));
+        <Module<T>>::deposit_event(Event::ExtractionQueued(
+            asset,
+            sender,
+            recipient,
+            quantity.value,
+        ));
+        true
+    }
+}
+
+/// Release the queued extractions of an asset which the window now has room for, in order,
+///  up to the given number of extractions. Returns the number released.
+fn release_asset_extractions<T: Config>(
+    asset: ChainAsset,
+    queue: Vec<(ChainAccount, AssetAmount)>,
+    now: Timestamp,
+    max_released: usize,
+) -> usize {
+    let limit = ExtractionLimits::get(asset);
+    let mut window = get_extraction_window(asset, now);
+    let mut extracted = get_extracted(&window);
+    let mut released = 0;
+
+    for &(recipient, amount) in queue.iter().take(max_released) {
+        if !within_limit(limit, extracted, amount) {
+            break;
+        }
+        add_extracted(&mut window, now, amount);
+        extracted = extracted.saturating_add(amount);
+        released += 1;
+        internal::notices::dispatch_extraction_notice::<T>(asset, recipient, amount);
+        <Module<T>>::deposit_event(Event::ExtractionReleased(asset, recipient, amount));
+    }
+
+    if released > 0 {
+        ExtractionWindows::insert(asset, window);
+        if released == queue.len() {
+            QueuedExtractions::remove(asset);
+        } else {
+            QueuedExtractions::insert(asset, queue[released..].to_vec());
+        }
+    }
+
+    released
+}
+
+/// Release the queued extractions which each asset's window now has room for, in order.
+/// Reading a queue or releasing an extraction each count against the batch size, bounding the work per block.
+pub fn release_queued_extractions<T: Config>(now: Timestamp) {
+    let mut remaining = EXTRACTION_RELEASE_BATCH_SIZE as usize;
+    let queues: Vec<(ChainAsset, Vec<(ChainAccount, AssetAmount)>)> =
+        QueuedExtractions::iter().take(remaining).collect();
+
+    for (asset, queue) in queues {
+        if remaining == 0 {
+            break;
+        }
+        remaining -= 1;
+
+        if !internal::chain_status::is_active(asset.chain_id()) {
+            continue;
+        }
+
+        remaining -= release_asset_extractions::<T>(asset, queue, now, remaining);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::*, types::*};
+
+    const HOUR: Timestamp = 60 * 60 * 1000;
+    const DAY: Timestamp = 24 * HOUR;
+
+    fn init_limited_asset(limit: AssetAmount) -> (ChainAsset, AssetInfo) {
+        let asset = ChainAsset::Eth([238; 20]);
+        let asset_info = AssetInfo::minimal(asset, ETH);
+        SupportedAssets::insert(&asset, asset_info);
+        ExtractionLimits::insert(asset, limit);
+        (asset, asset_info)
+    }
+
+    #[test]
+    fn test_set_extraction_limit_not_supported() {
+        new_test_ext().execute_with(|| {
+            let asset = ChainAsset::Eth([100; 20]);
+            assert_eq!(
+                set_extraction_limit::<Test>(asset, Some(1000)),
+                Err(Reason::AssetNotSupported)
+            );
+            assert_eq!(ExtractionLimits::get(asset), None);
+        });
+    }
+
+    #[test]
+    fn test_set_extraction_limit_and_remove() {
+        new_test_ext().execute_with(|| {
+            let (asset, _) = init_limited_asset(1);
+            assert_eq!(set_extraction_limit::<Test>(asset, Some(1000)), Ok(()));
+            assert_eq!(ExtractionLimits::get(asset), Some(1000));
+            assert_eq!(set_extraction_limit::<Test>(asset, None), Ok(()));
+            assert_eq!(ExtractionLimits::get(asset), None);
+        });
+    }
+
+    #[test]
+    fn test_extraction_queued_over_limit_and_released_when_window_rolls() {
+        new_test_ext().execute_with(|| {
+            let (asset, asset_info) = init_limited_asset(100);
+            let sender = ChainAccount::Eth([1; 20]);
+            let recipient = ChainAccount::Eth([2; 20]);
+            let now = 1_000;
+
+            assert_eq!(
+                dispatch_or_queue_extraction::<Test>(
+                    asset,
+                    sender,
+                    recipient,
+                    asset_info.as_quantity(60),
+                    now
+                ),
+                false
+            );
+            assert_eq!(ExtractionWindows::get(asset), vec![(0, 60)]);
+
+            assert_eq!(
+                dispatch_or_queue_extraction::<Test>(
+                    asset,
+                    sender,
+                    recipient,
+                    asset_info.as_quantity(30),
+                    now + HOUR * 12
+                ),
+                false
+            );
+            assert_eq!(
+                ExtractionWindows::get(asset),
+                vec![(0, 60), (HOUR * 12, 30)]
+            );
+
+            assert_eq!(
+                dispatch_or_queue_extraction::<Test>(
+                    asset,
+                    sender,
+                    recipient,
+                    asset_info.as_quantity(50),
+                    now + HOUR * 12
+                ),
+                true
+            );
+            assert_eq!(QueuedExtractions::get(asset), vec![(recipient, 50)]);
+
+            // The first extraction still counts until a full window has passed since its bucket closed
+            release_queued_extractions::<Test>(DAY + HOUR - 1);
+            assert_eq!(QueuedExtractions::get(asset), vec![(recipient, 50)]);
+
+            // Then only the second extraction remains in the window, leaving room for the queued one
+            release_queued_extractions::<Test>(DAY + HOUR);
+            assert_eq!(QueuedExtractions::get(asset), vec![]);
+            assert_eq!(
+                ExtractionWindows::get(asset),
+                vec![(HOUR * 12, 30), (DAY + HOUR, 50)]
+            );
+        })
+    }
+
+    #[test]
+    fn test_extraction_above_limit_waits_for_empty_window() {
+        new_test_ext().execute_with(|| {
+            let (asset, asset_info) = init_limited_asset(100);
+            let recipient = ChainAccount::Eth([2; 20]);
+            ExtractionWindows::insert(asset, vec![(0, 1)]);
+
+            assert_eq!(
+                dispatch_or_queue_extraction::<Test>(
+                    asset,
+                    recipient,
+                    recipient,
+                    asset_info.as_quantity(500),
+                    2_000
+                ),
+                true
+            );
+
+            release_queued_extractions::<Test>(DAY);
+            assert_eq!(QueuedExtractions::get(asset), vec![(recipient, 500)]);
+
+            release_queued_extractions::<Test>(DAY + HOUR);
+            assert_eq!(QueuedExtractions::get(asset), vec![]);
+            assert_eq!(ExtractionWindows::get(asset), vec![(DAY + HOUR, 500)]);
+        })
+    }
+
+    #[test]
+    fn test_extraction_queue_is_bounded() {
+        new_test_ext().execute_with(|| {
+            let (asset, _) = init_limited_asset(100);
+            let recipient = ChainAccount::Eth([2; 20]);
+            assert_eq!(require_queue_capacity(asset), Ok(()));
+
+            QueuedExtractions::insert(asset, vec![(recipient, 1); MAX_QUEUED_EXTRACTIONS as usize]);
+            assert_eq!(
+                require_queue_capacity(asset),
+                Err(Reason::ExtractionQueueFull)
+            );
+        })
+    }
+
+    #[test]
+    fn test_release_queued_extractions_is_batched() {
+        new_test_ext().execute_with(|| {
+            let (asset, _) = init_limited_asset(1_000_000);
+            let recipient = ChainAccount::Eth([2; 20]);
+            QueuedExtractions::insert(asset, vec![(recipient, 1); MAX_QUEUED_EXTRACTIONS as usize]);
+
+            // Reading the queue counts against the batch too
+            release_queued_extractions::<Test>(2_000);
+            assert_eq!(
+                QueuedExtractions::decode_len(asset),
+                Some((MAX_QUEUED_EXTRACTIONS - EXTRACTION_RELEASE_BATCH_SIZE + 1) as usize)
+            );
+            assert_eq!(
+                ExtractionWindows::get(asset),
+                vec![(0, (EXTRACTION_RELEASE_BATCH_SIZE - 1) as AssetAmount)]
+            );
+        })
+    }
+}
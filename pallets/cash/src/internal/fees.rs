@@ -0,0 +1,234 @@
+use crate::{
+    chains::ChainAccount,
+    factor::{BigUint, Factor},
+    internal::{account_history::deposit_account_event, miner::get_some_miner},
+    pipeline::CashPipeline,
+    reason::Reason,
+    symbol::CASH,
+    types::{CashIndex, CashPrincipalAmount, Quantity, Uint},
+    CashFeePerWeight, Config, Event, GlobalCashIndex, Module,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+    storage::StorageValue,
+    weights::{DispatchInfo, Pays},
+};
+use our_std::{fmt, marker::PhantomData};
+use sp_runtime::{
+    traits::{DispatchInfoOf, Dispatchable, SaturatedConversion, SignedExtension},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
+};
+
+/// Set the rate at which the weight of a signed extrinsic is converted to a fee in CASH.
+pub fn set_fee_rate<T: Config>(rate: Factor) -> Result<(), Reason> {
+    CashFeePerWeight::put(rate);
+    <Module<T>>::deposit_event(Event::SetFeeRate(rate));
+    Ok(())
+}
+
+/// Get the fee in CASH for a signed extrinsic, which is free if it does not pay fees.
+pub fn get_fee<T: Config>(info: &DispatchInfo) -> Result<Quantity, Reason> {
+    if info.pays_fee == Pays::No {
+        return Ok(Quantity::new(0, CASH));
+    }
+    let value = BigUint::from_uint(info.weight as Uint)
+        .mul_decimal(CashFeePerWeight::get().0, Factor::DECIMALS)
+        .to_uint()?;
+    Ok(Quantity::new(value, CASH))
+}
+
+fn prepare_fee<T: Config>(
+    payer: ChainAccount,
+    fee: Quantity,
+) -> Result<Option<(CashPipeline, ChainAccount, CashPrincipalAmount, CashIndex)>, Reason> {
+    let miner = get_some_miner::<T>();
+    if fee.value == 0 || payer == miner {
+        return Ok(None);
+    }
+    let index = GlobalCashIndex::get();
+    let fee_principal = index.cash_principal_amount(fee)?;
+    let pipeline = CashPipeline::new()
+        .transfer_cash::<T>(payer, miner, fee_principal)?
+        .check_collateralized::<T>(payer)?;
+    Ok(Some((pipeline, miner, fee_principal, index)))
+}
+
+/// Check that the payer can afford the fee for a signed extrinsic, without charging it.
+pub fn check_fee<T: Config>(payer: ChainAccount, info: &DispatchInfo) -> Result<Quantity, Reason> {
+    let fee = get_fee::<T>(info)?;
+    prepare_fee::<T>(payer, fee)?;
+    Ok(fee)
+}
+
+/// Charge the fee for a signed extrinsic, paying it from the payer to the miner.
+/// Like transfer fees, the fee may be borrowed as long as the payer remains collateralized.
+pub fn charge_fee<T: Config>(payer: ChainAccount, info: &DispatchInfo) -> Result<Quantity, Reason> {
+    let fee = get_fee::<T>(info)?;
+    if let Some((pipeline, miner, fee_principal, index)) = prepare_fee::<T>(payer, fee)? {
+        pipeline.commit::<T>();
+
+        deposit_account_event::<T>(Event::TransferCash(payer, miner, fee_principal, index));
+        <Module<T>>::deposit_event(Event::MinerPaid(miner, fee_principal, index));
+    }
+    Ok(fee)
+}
+
+/// Signed extension which charges the fee for a signed extrinsic in CASH,
+/// from the Gateway account of the signer, in place of `ChargeTransactionPayment`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeCashFee<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> ChargeCashFee<T> {
+    pub fn new() -> Self {
+        ChargeCashFee(PhantomData)
+    }
+
+    fn payer(who: &T::AccountId) -> ChainAccount
+    where
+        T::AccountId: AsRef<[u8; 32]>,
+    {
+        ChainAccount::Gate(*who.as_ref())
+    }
+}
+
+impl<T: Config + Send + Sync> fmt::Debug for ChargeCashFee<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ChargeCashFee")
+    }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for ChargeCashFee<T>
+where
+    <T as frame_system::Config>::Call: Dispatchable<Info = DispatchInfo>,
+    T::AccountId: AsRef<[u8; 32]>,
+{
+    const IDENTIFIER: &'static str = "ChargeCashFee";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        _call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        let fee =
+            check_fee::<T>(Self::payer(who), info).map_err(|_| InvalidTransaction::Payment)?;
+        Ok(ValidTransaction {
+            priority: fee.value.saturated_into(),
+            ..Default::default()
+        })
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        _call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> Result<(), TransactionValidityError> {
+        charge_fee::<T>(Self::payer(who), info).map_err(|_| InvalidTransaction::Payment)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tests::{common::*, *},
+        CashPrincipals,
+    };
+    use frame_support::storage::StorageMap;
+
+    #[test]
+    fn test_set_fee_rate() {
+        new_test_ext().execute_with(|| {
+            let rate = Factor::from_nominal("0.002");
+            assert_ok!(set_fee_rate::<Test>(rate));
+            assert_eq!(CashFeePerWeight::get(), rate);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::SetFeeRate(rate)))
+            );
+        });
+    }
+
+    #[test]
+    fn test_get_fee() {
+        new_test_ext().execute_with(|| {
+            CashFeePerWeight::put(Factor::from_nominal("0.001"));
+            let info = DispatchInfo {
+                weight: 25_000_000,
+                ..Default::default()
+            };
+            assert_eq!(get_fee::<Test>(&info), Ok(Quantity::new(25_000, CASH)));
+
+            let free = DispatchInfo {
+                pays_fee: Pays::No,
+                ..info
+            };
+            assert_eq!(get_fee::<Test>(&free), Ok(Quantity::new(0, CASH)));
+        });
+    }
+
+    #[test]
+    fn test_charge_fee() {
+        new_test_ext().execute_with(|| {
+            let payer = ChainAccount::Gate([1; 32]);
+            let miner = ChainAccount::Eth([0; 20]);
+            let info = DispatchInfo {
+                weight: 10_000_000,
+                ..Default::default()
+            };
+            CashFeePerWeight::put(Factor::from_nominal("0.001"));
+            GlobalCashIndex::put(CashIndex::from_nominal("1"));
+
+            assert_eq!(
+                check_fee::<Test>(payer, &info),
+                Err(Reason::InsufficientLiquidity)
+            );
+            assert_eq!(
+                charge_fee::<Test>(payer, &info),
+                Err(Reason::InsufficientLiquidity)
+            );
+
+            init_cash(payer, CashPrincipal::from_nominal("1"));
+            assert_eq!(
+                check_fee::<Test>(payer, &info),
+                Ok(Quantity::from_nominal("0.01", CASH))
+            );
+            assert_eq!(CashPrincipals::get(payer), CashPrincipal::from_nominal("1"));
+
+            assert_eq!(
+                charge_fee::<Test>(payer, &info),
+                Ok(Quantity::from_nominal("0.01", CASH))
+            );
+            assert_eq!(
+                CashPrincipals::get(payer),
+                CashPrincipal::from_nominal("0.99")
+            );
+            assert_eq!(
+                CashPrincipals::get(miner),
+                CashPrincipal::from_nominal("0.01")
+            );
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::MinerPaid(
+                    miner,
+                    CashPrincipalAmount::from_nominal("0.01"),
+                    CashIndex::from_nominal("1")
+                )))
+            );
+        });
+    }
+}
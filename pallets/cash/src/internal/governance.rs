@@ -0,0 +1,363 @@
+use crate::{
+    chains::ChainBlockEvent,
+    core::{dispatch_extrinsic_internal, get_chain_event_id, get_recent_timestamp},
+    internal::events::record_failed_event,
+    params::GOVERNANCE_RELEASE_BATCH_SIZE,
+    reason::{MathError, Reason},
+    require,
+    types::{GovernanceEventId, GovernanceId, GovernanceResult, Timestamp},
+    Call, Config, Event, GovernanceCallResults, GovernanceDelay, Module, NextGovernanceId,
+    QueuedGovernance,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+    storage::{
+        IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
+    },
+    traits::IsSubType,
+};
+use our_std::log;
+
+/// Set the delay before governance proposals containing sensitive changes execute.
+pub fn set_governance_delay<T: Config>(delay: Timestamp) -> Result<(), Reason> {
+    GovernanceDelay::put(delay);
+    <Module<T>>::deposit_event(Event::SetGovernanceDelay(delay));
+    Ok(())
+}
+
+/// Whether the encoded call makes a change risky enough that users should have time to exit first.
+/// Changing the delay itself is sensitive, so that it cannot be lifted and bypassed at once.
+fn is_sensitive<T: Config>(payload: &[u8]) -> bool {
+    match <T as Config>::Call::decode(&mut &payload[..]) {
+        Ok(call) => match call.is_sub_type() {
+            Some(Call::set_rate_model(..))
+            | Some(Call::set_liquidity_factor(..))
+            | Some(Call::support_asset(..))
+            | Some(Call::set_asset_group(..))
+            | Some(Call::change_validators(..))
+            | Some(Call::set_governance_delay(..))
+            | Some(Call::set_quorum_threshold(..)) => true,
+            _ => false,
+        },
+        Err(_) => false,
+    }
+}
+
+/// Return the identifier of a governance proposal received from a chain.
+pub fn get_governance_event_id(event: &ChainBlockEvent) -> GovernanceEventId {
+    get_chain_event_id(event)
+}
+
+/// Dispatch each call of a governance proposal received from a chain, skipping those which already succeeded.
+/// The result of each call is recorded, and if any fail the event fails too, so that it is retried later.
+pub fn dispatch_governance_event<T: Config>(
+    event_id: GovernanceEventId,
+    extrinsics: Vec<Vec<u8>>,
+) -> Result<(), Reason> {
+    let mut failed = false;
+    let mut results = Vec::new();
+    for (index, payload) in extrinsics.into_iter().enumerate() {
+        let index = index as u32;
+        if GovernanceCallResults::get(event_id, index) == Some(GovernanceResult::DispatchSuccess) {
+            log!(
+                "Skipping governance call {} of {:?}, already executed",
+                index,
+                event_id
+            );
+            continue;
+        }
+
+        let result = dispatch_extrinsic_internal::<T>(&payload);
+        GovernanceCallResults::insert(event_id, index, result.clone());
+        if result == GovernanceResult::DispatchSuccess {
+            <Module<T>>::deposit_event(Event::GovernanceCallSucceeded(event_id, index));
+        } else {
+            failed = true;
+            <Module<T>>::deposit_event(Event::GovernanceCallFailed(
+                event_id,
+                index,
+                result.clone(),
+            ));
+        }
+        results.push((payload, result));
+    }
+    <Module<T>>::deposit_event(Event::ExecutedGovernance(results));
+
+    if failed {
+        Err(Reason::GovernanceCallsFailed)
+    } else {
+        Ok(())
+    }
+}
+
+/// Either dispatch the governance proposal received from a chain immediately, or queue it until the delay has passed.
+/// A proposal is queued as a whole if any of its extrinsics is sensitive, so it still applies atomically.
+/// A proposal which already started executing is being retried, and is never queued.
+pub fn dispatch_or_queue_governance<T: Config>(
+    event: &ChainBlockEvent,
+    extrinsics: Vec<Vec<u8>>,
+) -> Result<(), Reason> {
+    let event_id = get_governance_event_id(event);
+    let delay = GovernanceDelay::get();
+    if delay == 0
+        || !extrinsics.iter().any(|payload| is_sensitive::<T>(payload))
+        || GovernanceCallResults::iter_prefix(event_id)
+            .next()
+            .is_some()
+    {
+        return dispatch_governance_event::<T>(event_id, extrinsics);
+    }
+
+    let now = get_recent_timestamp::<T>()?;
+    let eta = now.checked_add(delay).ok_or(MathError::Overflow)?;
+    let id = NextGovernanceId::get();
+    let next_id = id.checked_add(1).ok_or(MathError::Overflow)?;
+
+    log!("Queueing governance proposal {} until {}", id, eta);
+    NextGovernanceId::put(next_id);
+    QueuedGovernance::insert(id, (eta, event.clone(), extrinsics.clone()));
+    <Module<T>>::deposit_event(Event::GovernanceQueued(id, eta, extrinsics));
+
+    Ok(())
+}
+
+/// Cancel a queued governance proposal, before it executes.
+pub fn cancel_governance<T: Config>(id: GovernanceId) -> Result<(), Reason> {
+    require!(
+        QueuedGovernance::contains_key(id),
+        Reason::UnknownGovernance
+    );
+    QueuedGovernance::remove(id);
+    <Module<T>>::deposit_event(Event::GovernanceCancelled(id));
+    Ok(())
+}
+
+/// Execute the queued governance proposals which have waited out the delay, in the order they were queued, up to a batch at a time.
+/// The calls are recorded against the event the proposal came from, and if any fail the event is retried like any other failed event.
+pub fn release_queued_governance<T: Config>(now: Timestamp) {
+    let mut ready: Vec<(GovernanceId, ChainBlockEvent, Vec<Vec<u8>>)> = QueuedGovernance::iter()
+        .filter(|(_, (eta, _, _))| *eta <= now)
+        .map(|(id, (_, event, extrinsics))| (id, event, extrinsics))
+        .collect();
+    ready.sort_by_key(|(id, _, _)| *id);
+
+    for (id, event, extrinsics) in ready
+        .into_iter()
+        .take(GOVERNANCE_RELEASE_BATCH_SIZE as usize)
+    {
+        QueuedGovernance::remove(id);
+        if let Err(reason) =
+            dispatch_governance_event::<T>(get_governance_event_id(&event), extrinsics)
+        {
+            log!("Queued governance {} failed: {:?}", id, reason);
+            record_failed_event::<T>(&event, reason);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::*, types::*};
+
+    const DAY: Timestamp = 24 * 60 * 60 * 1000;
+
+    fn proposal(block_number: ChainBlockNumber, extrinsics: Vec<Vec<u8>>) -> ChainBlockEvent {
+        ChainBlockEvent::Eth(
+            block_number,
+            ethereum_client::EthereumEvent::ExecuteProposal {
+                title: String::from("Proposal"),
+                extrinsics,
+            },
+        )
+    }
+
+    fn set_liquidity_factor_call(factor: &'static str) -> Vec<u8> {
+        mock::Call::Cash(crate::Call::set_liquidity_factor(
+            Eth,
+            LiquidityFactor::from_nominal(factor),
+        ))
+        .encode()
+    }
+
+    fn liquidity_factor() -> LiquidityFactor {
+        SupportedAssets::get(Eth).unwrap().liquidity_factor
+    }
+
+    #[test]
+    fn test_set_governance_delay() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(GovernanceDelay::get(), 0);
+            assert_ok!(set_governance_delay::<Test>(2 * DAY));
+            assert_eq!(GovernanceDelay::get(), 2 * DAY);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::SetGovernanceDelay(
+                    2 * DAY
+                )))
+            );
+        });
+    }
+
+    #[test]
+    fn test_sensitive_governance_queued_until_delay() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            common::init_eth_asset()?;
+            GovernanceDelay::put(DAY);
+            let now = 500;
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(now);
+
+            // Not sensitive, so executes immediately
+            let fraction = Factor::from_nominal("0.25");
+            let call = mock::Call::Cash(crate::Call::set_max_missed_signatures(fraction)).encode();
+            dispatch_or_queue_governance::<Test>(&proposal(1, vec![call.clone()]), vec![call])?;
+            assert_eq!(MaxMissedSignatures::get(), fraction);
+            assert_eq!(NextGovernanceId::get(), 0);
+
+            let extrinsics = vec![set_liquidity_factor_call("0.5")];
+            let event = proposal(2, extrinsics.clone());
+            dispatch_or_queue_governance::<Test>(&event, extrinsics.clone())?;
+            assert_eq!(liquidity_factor(), LiquidityFactor::from_nominal("0.8"));
+            assert_eq!(
+                QueuedGovernance::get(0),
+                Some((now + DAY, event.clone(), extrinsics.clone()))
+            );
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::GovernanceQueued(
+                    0,
+                    now + DAY,
+                    extrinsics
+                )))
+            );
+
+            release_queued_governance::<Test>(now + DAY - 1);
+            assert_eq!(liquidity_factor(), LiquidityFactor::from_nominal("0.8"));
+
+            release_queued_governance::<Test>(now + DAY);
+            assert_eq!(liquidity_factor(), LiquidityFactor::from_nominal("0.5"));
+            assert_eq!(QueuedGovernance::get(0), None);
+            assert_eq!(
+                GovernanceCallResults::get(get_governance_event_id(&event), 0),
+                Some(GovernanceResult::DispatchSuccess)
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_governance_event_retried_per_call() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            let fraction = Factor::from_nominal("0.25");
+            let extrinsics = vec![
+                mock::Call::Cash(crate::Call::set_max_missed_signatures(fraction)).encode(),
+                set_liquidity_factor_call("0.5"),
+            ];
+            let event = proposal(1, extrinsics.clone());
+            let id = get_governance_event_id(&event);
+
+            // the asset is not yet supported, so only the first call applies
+            assert_eq!(
+                dispatch_or_queue_governance::<Test>(&event, extrinsics.clone()),
+                Err(Reason::GovernanceCallsFailed)
+            );
+            assert_eq!(MaxMissedSignatures::get(), fraction);
+            assert_eq!(
+                GovernanceCallResults::get(id, 0),
+                Some(GovernanceResult::DispatchSuccess)
+            );
+            assert!(matches!(
+                GovernanceCallResults::get(id, 1),
+                Some(GovernanceResult::DispatchFailure(_))
+            ));
+            assert!(System::events().iter().any(|e| matches!(
+                e.event,
+                mock::Event::pallet_cash(crate::Event::GovernanceCallFailed(i, 1, _)) if i == id
+            )));
+
+            // on retry, the call which succeeded is skipped
+            common::init_eth_asset()?;
+            MaxMissedSignatures::put(Factor::from_nominal("0.5"));
+            dispatch_or_queue_governance::<Test>(&event, extrinsics)?;
+            assert_eq!(MaxMissedSignatures::get(), Factor::from_nominal("0.5"));
+            assert_eq!(liquidity_factor(), LiquidityFactor::from_nominal("0.5"));
+            assert_eq!(
+                GovernanceCallResults::get(id, 1),
+                Some(GovernanceResult::DispatchSuccess)
+            );
+            assert!(System::events().iter().any(|e| e.event
+                == mock::Event::pallet_cash(crate::Event::GovernanceCallSucceeded(id, 1))));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_queued_governance_failure_retried() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            GovernanceDelay::put(DAY);
+            let now = 500;
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(now);
+
+            let extrinsics = vec![set_liquidity_factor_call("0.5")];
+            let event = proposal(1, extrinsics.clone());
+            let id = get_governance_event_id(&event);
+            dispatch_or_queue_governance::<Test>(&event, extrinsics)?;
+            assert_eq!(NextGovernanceId::get(), 1);
+
+            // the asset is not yet supported, so the released call fails and the event is kept to retry
+            release_queued_governance::<Test>(now + DAY);
+            assert_eq!(QueuedGovernance::get(0), None);
+            assert!(matches!(
+                GovernanceCallResults::get(id, 0),
+                Some(GovernanceResult::DispatchFailure(_))
+            ));
+            assert!(System::events().iter().any(|e| matches!(
+                e.event,
+                mock::Event::pallet_cash(crate::Event::GovernanceCallFailed(i, 0, _)) if i == id
+            )));
+            let failed = FailedChainBlockEvents::get();
+            assert_eq!(failed.len(), 1);
+            assert_eq!(failed[0].event, event);
+            assert_eq!(failed[0].reason, Reason::GovernanceCallsFailed);
+
+            // the retry executes right away, rather than being queued again
+            common::init_eth_asset()?;
+            System::set_block_number(failed[0].next_retry_block);
+            internal::events::retry_failed_events::<Test>();
+            assert_eq!(liquidity_factor(), LiquidityFactor::from_nominal("0.5"));
+            assert_eq!(
+                GovernanceCallResults::get(id, 0),
+                Some(GovernanceResult::DispatchSuccess)
+            );
+            assert_eq!(NextGovernanceId::get(), 1);
+            assert_eq!(FailedChainBlockEvents::get(), vec![]);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_cancel_governance() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            common::init_eth_asset()?;
+            GovernanceDelay::put(DAY);
+            let now = 500;
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(now);
+
+            let extrinsics = vec![set_liquidity_factor_call("0.5")];
+            dispatch_or_queue_governance::<Test>(&proposal(1, extrinsics.clone()), extrinsics)?;
+            let extrinsics = vec![set_liquidity_factor_call("0.6")];
+            dispatch_or_queue_governance::<Test>(&proposal(2, extrinsics.clone()), extrinsics)?;
+            assert_ok!(cancel_governance::<Test>(1));
+            assert_eq!(cancel_governance::<Test>(1), Err(Reason::UnknownGovernance));
+
+            release_queued_governance::<Test>(now + DAY);
+            assert_eq!(liquidity_factor(), LiquidityFactor::from_nominal("0.5"));
+            assert_eq!(QueuedGovernance::iter().count(), 0);
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    chains::ChainAsset, internal::assets::get_asset, reason::Reason, require, types::AssetAmount,
+    Config, StarportHoldings,
+};
+use frame_support::storage::StorageMap;
+
+/// Credit the starport of an asset with an amount locked into it.
+pub fn add_holdings(asset: ChainAsset, amount: AssetAmount) {
+    StarportHoldings::mutate(asset, |holdings| {
+        *holdings = holdings.saturating_add(amount)
+    });
+}
+
+/// Debit the starport of an asset by an amount which it now owes, or which was never really locked.
+pub fn sub_holdings(asset: ChainAsset, amount: AssetAmount) {
+    StarportHoldings::mutate(asset, |holdings| {
+        *holdings = holdings.saturating_sub(amount)
+    });
+}
+
+/// Return the amount by which the starport of an asset falls short of an extraction, zero if it is covered.
+pub fn get_shortfall(asset: ChainAsset, amount: AssetAmount) -> AssetAmount {
+    amount.saturating_sub(StarportHoldings::get(asset))
+}
+
+/// Require the starport of an asset to hold enough to honor an extraction of the amount.
+pub fn require_holdings(asset: ChainAsset, amount: AssetAmount) -> Result<(), Reason> {
+    require!(
+        get_shortfall(asset, amount) == 0,
+        Reason::InsufficientStarportLiquidity
+    );
+    Ok(())
+}
+
+/// Return the amount of a supported asset held by its starport, and its shortfall for extracting the amount.
+/// Lets a relayer or user holding the same asset on several chains pick one which can honor the extraction.
+pub fn get_starport_liquidity<T: Config>(
+    asset: ChainAsset,
+    amount: AssetAmount,
+) -> Result<(AssetAmount, AssetAmount), Reason> {
+    get_asset::<T>(asset)?;
+    Ok((StarportHoldings::get(asset), get_shortfall(asset, amount)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_holdings() {
+        new_test_ext().execute_with(|| {
+            add_holdings(Eth, 100);
+            add_holdings(Eth, 50);
+            sub_holdings(Eth, 30);
+            assert_eq!(StarportHoldings::get(Eth), 120);
+            assert_eq!(get_shortfall(Eth, 120), 0);
+            assert_eq!(get_shortfall(Eth, 200), 80);
+            assert_eq!(require_holdings(Eth, 120), Ok(()));
+            assert_eq!(
+                require_holdings(Eth, 121),
+                Err(Reason::InsufficientStarportLiquidity)
+            );
+
+            sub_holdings(Eth, 500);
+            assert_eq!(StarportHoldings::get(Eth), 0);
+        });
+    }
+
+    #[test]
+    fn test_get_starport_liquidity() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                get_starport_liquidity::<Test>(Eth, 10),
+                Err(Reason::AssetNotSupported)
+            );
+
+            SupportedAssets::insert(&Eth, eth);
+            StarportHoldings::insert(&Eth, 40);
+            assert_eq!(get_starport_liquidity::<Test>(Eth, 10), Ok((40, 0)));
+            assert_eq!(get_starport_liquidity::<Test>(Eth, 100), Ok((40, 60)));
+        });
+    }
+}
@@ -1,24 +1,32 @@
 use crate::{
-    chains::ChainAsset,
     core::get_recent_timestamp,
-    factor::Factor,
-    internal,
+    internal::{self, account_history::deposit_account_event},
     params::GATEWAY_VOID,
     reason::Reason,
-    types::{AssetIndex, CashPrincipalAmount, Quantity, Timestamp, CASH},
-    BorrowIndices, CashPrincipals, CashYield, CashYieldNext, Config, Event, GlobalCashIndex,
-    LastBlockTimestamp, LastMinerSharePrincipal, LastYieldCashIndex, LastYieldTimestamp,
-    MinerCumulative, Module, SupplyIndices, SupportedAssets, TotalBorrowAssets, TotalCashPrincipal,
-    TotalSupplyAssets,
+    types::{CashPrincipalAmount, Timestamp},
+    CashPrincipals, CashYield, CashYieldNext, Config, Event, GlobalCashIndex,
+    InitializeWeightBudget, LastBlockTimestamp, LastMinerSharePrincipal, LastYieldCashIndex,
+    LastYieldTimestamp, MinerCumulative, Module,
+};
+use frame_support::{
+    storage::{StorageMap, StorageValue},
+    weights::Weight,
 };
-use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
 
 /// Block initialization hook
-pub fn on_initialize<T: Config>() -> Result<(), Reason> {
-    initialize_block::<T>(get_recent_timestamp::<T>()?)
+/// Accruing the CASH index always happens, the rest of the work only as the weight budget allows.
+/// Returns the weight used by the deferrable work.
+pub fn on_initialize<T: Config>() -> Result<Weight, Reason> {
+    let now = get_recent_timestamp::<T>()?;
+    initialize_block::<T>(now)?;
+    Ok(internal::work::do_block_work::<T>(
+        now,
+        InitializeWeightBudget::get(),
+    ))
 }
 
 /// Initialize block, given now
+/// Asset interest is accrued lazily as each asset is touched, so only the CASH index is updated here.
 pub fn initialize_block<T: Config>(now: Timestamp) -> Result<(), Reason> {
     let last_yield_timestamp = LastYieldTimestamp::get();
     let last_block_timestamp = LastBlockTimestamp::get();
@@ -29,55 +37,17 @@ pub fn initialize_block<T: Config>(now: Timestamp) -> Result<(), Reason> {
         return Ok(());
     }
 
-    // Iterate through listed assets, summing the CASH principal they generated/paid last block
-    let dt_since_last_block = now
-        .checked_sub(last_block_timestamp)
-        .ok_or(Reason::TimeTravelNotAllowed)?;
     let dt_since_last_yield = now
         .checked_sub(last_yield_timestamp)
         .ok_or(Reason::TimeTravelNotAllowed)?;
-    let mut cash_principal_supply_increase = CashPrincipalAmount::ZERO;
-    let mut cash_principal_borrow_increase = CashPrincipalAmount::ZERO;
-
-    let last_block_cash_index = GlobalCashIndex::get();
-    let last_yield_cash_index = LastYieldCashIndex::get();
-    let cash_yield = CashYield::get();
-    let price_cash = internal::assets::get_price_or_zero::<T>(CASH);
-
-    let mut asset_updates: Vec<(ChainAsset, AssetIndex, AssetIndex)> = Vec::new();
-    for (asset, asset_info) in SupportedAssets::iter() {
-        let (asset_cost, asset_yield) = internal::assets::get_rates::<T>(asset)?;
-        let asset_units = asset_info.units();
-        let price_asset = internal::assets::get_price_or_zero::<T>(asset_units);
-        let price_ratio = Factor::ratio(price_asset, price_cash)?;
-        let cash_borrow_principal_per_asset = last_block_cash_index
-            .cash_principal_per_asset(asset_cost.simple(dt_since_last_block)?, price_ratio)?;
-        let cash_hold_principal_per_asset = last_block_cash_index
-            .cash_principal_per_asset(asset_yield.simple(dt_since_last_block)?, price_ratio)?;
-
-        let supply_index = SupplyIndices::get(&asset);
-        let borrow_index = BorrowIndices::get(&asset);
-        let supply_index_new = supply_index.increment(cash_hold_principal_per_asset)?;
-        let borrow_index_new = borrow_index.increment(cash_borrow_principal_per_asset)?;
-
-        let supply_asset = Quantity::new(TotalSupplyAssets::get(asset), asset_units);
-        let borrow_asset = Quantity::new(TotalBorrowAssets::get(asset), asset_units);
-        cash_principal_supply_increase = cash_principal_supply_increase
-            .add(cash_hold_principal_per_asset.cash_principal_amount(supply_asset)?)?;
-        cash_principal_borrow_increase = cash_principal_borrow_increase
-            .add(cash_borrow_principal_per_asset.cash_principal_amount(borrow_asset)?)?;
-
-        asset_updates.push((asset.clone(), supply_index_new, borrow_index_new));
+    if now < last_block_timestamp {
+        return Err(Reason::TimeTravelNotAllowed);
     }
 
     // Pay miners and update the CASH interest index on CASH itself
-    let total_cash_principal = TotalCashPrincipal::get();
-
-    let increment = cash_yield.compound(dt_since_last_yield)?;
-    let cash_index_new = last_yield_cash_index.increment(increment.into())?;
-    let total_cash_principal_new = total_cash_principal.add(cash_principal_borrow_increase)?;
-    let miner_share_principal =
-        cash_principal_borrow_increase.sub(cash_principal_supply_increase)?;
+    let last_yield_cash_index = LastYieldCashIndex::get();
+    let cash_yield = CashYield::get();
+    let cash_index_new = cash_yield.accrue(last_yield_cash_index, dt_since_last_yield)?;
 
     let last_miner = internal::miner::get_some_miner::<T>(); // Miner not yet set for this block, so this is "last miner"
     let last_miner_share_principal = LastMinerSharePrincipal::get();
@@ -92,14 +62,8 @@ pub fn initialize_block<T: Config>(now: Timestamp) -> Result<(), Reason> {
 
     CashPrincipals::insert(last_miner, miner_cash_principal_new);
 
-    for (asset, new_supply_index, new_borrow_index) in asset_updates.drain(..) {
-        SupplyIndices::insert(asset.clone(), new_supply_index);
-        BorrowIndices::insert(asset, new_borrow_index);
-    }
-
     GlobalCashIndex::put(cash_index_new);
-    TotalCashPrincipal::put(total_cash_principal_new);
-    LastMinerSharePrincipal::put(miner_share_principal);
+    LastMinerSharePrincipal::put(CashPrincipalAmount::ZERO);
     LastBlockTimestamp::put(now);
 
     // Auxiliary cumulative values
@@ -117,7 +81,7 @@ pub fn initialize_block<T: Config>(now: Timestamp) -> Result<(), Reason> {
 
     if last_miner_share_principal != CashPrincipalAmount::ZERO {
         // No need to emit events when nothing happens
-        <Module<T>>::deposit_event(Event::TransferCash(
+        deposit_account_event::<T>(Event::TransferCash(
             GATEWAY_VOID,
             last_miner,
             last_miner_share_principal,
@@ -125,7 +89,11 @@ pub fn initialize_block<T: Config>(now: Timestamp) -> Result<(), Reason> {
         ));
     }
 
-    <Module<T>>::deposit_event(Event::MinerPaid(last_miner, last_miner_share_principal));
+    <Module<T>>::deposit_event(Event::MinerPaid(
+        last_miner,
+        last_miner_share_principal,
+        cash_index_new,
+    ));
 
     Ok(())
 }
@@ -140,45 +108,30 @@ mod tests {
         new_test_ext().execute_with(|| {
             let miner = ChainAccount::Eth([0; 20]);
             let asset = Eth;
-            let asset_info = AssetInfo {
-                rate_model: InterestRateModel::new_kink(0, 2500, Factor::from_nominal("0.5"), 5000),
-                miner_shares: MinerShares::from_nominal("0.02"),
-                ..AssetInfo::minimal(asset, ETH)
-            };
             let last_yield_timestamp = 10;
             let now = last_yield_timestamp + MILLISECONDS_PER_YEAR / 4; // 3 months go by
+            let shares = CashPrincipalAmount(242097062);
 
             Miner::put(miner);
             LastBlockTimestamp::put(last_yield_timestamp);
             LastYieldTimestamp::put(last_yield_timestamp);
-            SupportedAssets::insert(&asset, asset_info);
+            SupportedAssets::insert(&asset, AssetInfo::minimal(asset, ETH));
             GlobalCashIndex::put(CashIndex::from_nominal("1.123"));
             LastYieldCashIndex::put(CashIndex::from_nominal("1.123"));
             SupplyIndices::insert(&asset, AssetIndex::from_nominal("1234"));
             BorrowIndices::insert(&asset, AssetIndex::from_nominal("1345"));
-            TotalSupplyAssets::insert(asset.clone(), asset_info.as_quantity_nominal("300").value);
-            TotalBorrowAssets::insert(asset.clone(), asset_info.as_quantity_nominal("150").value);
             CashYield::put(APR::from_nominal("0.24")); // 24% APR big number for easy to see interest
             TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("450000")); // 450k cash principal
             CashPrincipals::insert(&miner, CashPrincipal::from_nominal("1"));
-            pallet_oracle::Prices::insert(
-                asset_info.ticker,
-                1450_000000 as pallet_oracle::types::AssetPrice,
-            ); // $1450 eth
+            LastMinerSharePrincipal::put(shares);
 
             let result = initialize_block::<Test>(now);
-            let shares = CashPrincipalAmount(242097062);
             let cash_index = CashIndex::from_nominal("1.192441828000000000");
             assert_eq!(result, Ok(()));
 
-            assert_eq!(
-                SupplyIndices::get(&asset),
-                AssetIndex::from_nominal("1273.542520035618878005")
-            );
-            assert_eq!(
-                BorrowIndices::get(&asset),
-                AssetIndex::from_nominal("1425.699020480854853072")
-            );
+            // asset indices are only accrued when the asset is touched
+            assert_eq!(SupplyIndices::get(&asset), AssetIndex::from_nominal("1234"));
+            assert_eq!(BorrowIndices::get(&asset), AssetIndex::from_nominal("1345"));
 
             // note - the cash index number below is quite round due to the polynomial nature of
             // our approximation and the fact that the ratio in this case worked out to be a
@@ -186,29 +139,28 @@ mod tests {
             assert_eq!(GlobalCashIndex::get(), cash_index);
             assert_eq!(
                 TotalCashPrincipal::get(),
-                CashPrincipalAmount::from_nominal("462104.853072")
+                CashPrincipalAmount::from_nominal("450000")
+            );
+            assert_eq!(
+                CashPrincipals::get(&miner),
+                CashPrincipal::from_nominal("243.097062")
             );
-            assert_eq!(CashPrincipals::get(&miner), CashPrincipal::ONE);
-            assert_eq!(LastMinerSharePrincipal::get(), shares);
-            assert_eq!(MinerCumulative::get(&miner), CashPrincipalAmount(0));
+            assert_eq!(LastMinerSharePrincipal::get(), CashPrincipalAmount(0));
+            assert_eq!(MinerCumulative::get(&miner), shares);
+            assert_eq!(LastBlockTimestamp::get(), now);
 
-            // Run again to give last block principal to miner
+            // Run again with nothing accrued for the miner
             assert_eq!(initialize_block::<Test>(now), Ok(()));
             assert_eq!(
                 CashPrincipals::get(&miner),
                 CashPrincipal::from_nominal("243.097062")
             );
-            assert_eq!(LastMinerSharePrincipal::get(), CashPrincipalAmount(0));
             assert_eq!(MinerCumulative::get(&miner), shares);
 
             let mut events_iter = System::events().into_iter();
-            let miner_paid_event_1 = events_iter.next().unwrap();
             let transfer_cash_event_1 = events_iter.next().unwrap();
+            let miner_paid_event_1 = events_iter.next().unwrap();
             let miner_paid_event_2 = events_iter.next().unwrap();
-            assert_eq!(
-                mock::Event::pallet_cash(crate::Event::MinerPaid(miner, CashPrincipalAmount(0))),
-                miner_paid_event_1.event
-            );
             assert_eq!(
                 mock::Event::pallet_cash(crate::Event::TransferCash(
                     GATEWAY_VOID,
@@ -219,7 +171,15 @@ mod tests {
                 transfer_cash_event_1.event
             );
             assert_eq!(
-                mock::Event::pallet_cash(crate::Event::MinerPaid(miner, shares)),
+                mock::Event::pallet_cash(crate::Event::MinerPaid(miner, shares, cash_index)),
+                miner_paid_event_1.event
+            );
+            assert_eq!(
+                mock::Event::pallet_cash(crate::Event::MinerPaid(
+                    miner,
+                    CashPrincipalAmount(0),
+                    cash_index
+                )),
                 miner_paid_event_2.event
             );
             // should be exactly 3 events
@@ -1,25 +1,220 @@
 use crate::{
-    chains::ChainAccount,
+    chains::{ChainAccount, ChainAsset},
     core,
-    factor::Factor,
-    internal::assets::{get_price, get_value},
+    factor::{BigUint, Factor},
+    internal::{
+        account_history::deposit_account_event,
+        assets::{get_asset, get_price, get_value},
+        solvency::check_bad_debt,
+    },
     must,
-    params::MIN_TX_VALUE,
+    params::{BIPS_PER_UNIT, MAX_LIQUIDATION_INCENTIVE, MIN_TX_VALUE},
     pipeline::CashPipeline,
     reason::Reason,
     require, require_min_tx_value,
     symbol::Units,
-    types::{AssetInfo, AssetQuantity, CashPrincipalAmount, Quantity, CASH},
-    Config, Event, GlobalCashIndex, Module,
+    types::{
+        AssetAmount, AssetBalance, AssetInfo, AssetQuantity, Bips, CashPrincipalAmount,
+        LiquidationAuction, LiquidationAuctionConfig, MathError, Quantity, Uint, CASH,
+    },
+    AssetBalances, AssetsWithNonZeroBalance, CashPrincipals, Config, Event, GlobalCashIndex,
+    GlobalLiquidationIncentive, LiquidationAuctionParams, LiquidationAuctions,
+    LiquidationCloseFactor, Module, SeizePaused,
+};
+use frame_support::storage::{
+    IterableStorageDoubleMap, StorageDoubleMap, StorageMap, StorageValue,
 };
-use frame_support::storage::StorageValue;
 use our_std::result::Result;
+use sp_runtime::traits::UniqueSaturatedInto;
+
+/// Set the liquidation incentive on seized collateral, in bips.
+pub fn set_liquidation_incentive<T: Config>(incentive: Bips) -> Result<(), Reason> {
+    require!(
+        incentive <= MAX_LIQUIDATION_INCENTIVE,
+        Reason::InvalidLiquidation
+    );
+    GlobalLiquidationIncentive::put(incentive);
+    <Module<T>>::deposit_event(Event::SetLiquidationIncentive(incentive));
+    Ok(())
+}
+
+/// Set the fraction of a borrow which may be repaid in a single liquidation.
+pub fn set_close_factor<T: Config>(close_factor: Factor) -> Result<(), Reason> {
+    require!(
+        close_factor > Factor::ZERO && close_factor <= Factor::ONE,
+        Reason::InvalidLiquidation
+    );
+    LiquidationCloseFactor::put(close_factor);
+    <Module<T>>::deposit_event(Event::SetCloseFactor(close_factor));
+    Ok(())
+}
+
+/// Pause or unpause seizing an asset as collateral in liquidations.
+pub fn set_seize_paused<T: Config>(asset: ChainAsset, paused: bool) -> Result<(), Reason> {
+    get_asset::<T>(asset)?;
+    if paused {
+        SeizePaused::insert(asset, true);
+    } else {
+        SeizePaused::remove(asset);
+    }
+    <Module<T>>::deposit_event(Event::SetSeizePaused(asset, paused));
+    Ok(())
+}
+
+/// Set the parameters for liquidating large borrows by auction, or disable auctions given none.
+pub fn set_liquidation_auction<T: Config>(
+    config: Option<LiquidationAuctionConfig>,
+) -> Result<(), Reason> {
+    if let Some(config) = config {
+        require!(
+            config.start_incentive <= config.max_incentive
+                && config.max_incentive <= MAX_LIQUIDATION_INCENTIVE
+                && config.ramp_blocks > 0,
+            Reason::InvalidLiquidation
+        );
+    }
+    LiquidationAuctionParams::set(config);
+    <Module<T>>::deposit_event(Event::SetLiquidationAuction(config));
+    Ok(())
+}
+
+/// Return the incentive of a liquidation auction in the given block, in bips.
+/// The incentive ramps linearly from the start to the max over the ramp blocks, then stays at the max.
+pub fn get_auction_incentive(
+    config: LiquidationAuctionConfig,
+    auction: LiquidationAuction,
+    block_number: u64,
+) -> Bips {
+    let ramp_blocks = config.ramp_blocks.max(1) as u64;
+    let elapsed = block_number
+        .saturating_sub(auction.start_block)
+        .min(ramp_blocks);
+    let ramp = config
+        .max_incentive
+        .saturating_sub(config.start_incentive)
+        .saturating_mul(elapsed as Bips)
+        / ramp_blocks as Bips;
+    config.start_incentive.saturating_add(ramp)
+}
+
+/// Return the liquidation incentive for a borrower, in bips, and the auction it is taken from, if any.
+/// Borrows worth at least the auction minimum are liquidated by auction, starting one if none was started already.
+/// Auctions go on at the max incentive once ramped up, until the borrower is no longer underwater.
+fn get_liquidation_incentive<T: Config>(
+    borrower: ChainAccount,
+    owed: AssetQuantity,
+) -> Result<(Bips, Option<LiquidationAuction>), Reason> {
+    let config = match LiquidationAuctionParams::get() {
+        Some(config) => config,
+        None => return Ok((GlobalLiquidationIncentive::get(), None)),
+    };
+    let now: u64 = <frame_system::Pallet<T>>::block_number().unique_saturated_into();
+    let auction = match LiquidationAuctions::get(borrower) {
+        Some(auction) => auction,
+        None if get_value::<T>(owed)?.value >= config.min_value => {
+            LiquidationAuction { start_block: now }
+        }
+        None => return Ok((GlobalLiquidationIncentive::get(), None)),
+    };
+    Ok((get_auction_incentive(config, auction, now), Some(auction)))
+}
+
+/// Start the liquidation auction of an underwater borrower without seizing anything, so its incentive ramps up before anyone liquidates.
+/// Anyone may start the auction of a borrower owing at least the auction minimum in CASH or any one asset.
+/// An auction left over from a borrower who is no longer underwater is ended instead, so it does not resume at the max incentive.
+pub fn start_liquidation_auction_internal<T: Config>(borrower: ChainAccount) -> Result<(), Reason> {
+    let config = LiquidationAuctionParams::get().ok_or(Reason::LiquidationAuctionNotAllowed)?;
+    let underwater = core::get_liquidity::<T>(borrower)?.value <= 0;
+    let ongoing = LiquidationAuctions::contains_key(borrower);
+    require!(underwater || ongoing, Reason::SufficientLiquidity);
+    require!(!(underwater && ongoing), Reason::LiquidationAuctionOngoing);
+    if underwater {
+        require!(
+            get_largest_owed_value::<T>(borrower)? >= config.min_value,
+            Reason::LiquidationAuctionNotAllowed
+        );
+    }
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    if underwater {
+        let now: u64 = <frame_system::Pallet<T>>::block_number().unique_saturated_into();
+        LiquidationAuctions::insert(borrower, LiquidationAuction { start_block: now });
+        <Module<T>>::deposit_event(Event::LiquidationAuctionStarted(borrower, now));
+    } else {
+        LiquidationAuctions::remove(borrower);
+        <Module<T>>::deposit_event(Event::LiquidationAuctionEnded(borrower));
+    }
+
+    Ok(())
+}
+
+/// Keep the auction of a borrower going while they remain underwater, or end it once they are not.
+fn update_liquidation_auction<T: Config>(
+    borrower: ChainAccount,
+    auction: Option<LiquidationAuction>,
+) {
+    if let Some(auction) = auction {
+        let underwater =
+            core::get_liquidity::<T>(borrower).map_or(true, |liquidity| liquidity.value <= 0);
+        let ongoing = LiquidationAuctions::get(borrower) == Some(auction);
+        if underwater && !ongoing {
+            LiquidationAuctions::insert(borrower, auction);
+            <Module<T>>::deposit_event(Event::LiquidationAuctionStarted(
+                borrower,
+                auction.start_block,
+            ));
+        } else if !underwater && LiquidationAuctions::take(borrower).is_some() {
+            <Module<T>>::deposit_event(Event::LiquidationAuctionEnded(borrower));
+        }
+    }
+}
+
+/// Return how much of an asset a borrower owes.
+fn get_owed_asset(asset: AssetInfo, borrower: ChainAccount) -> Result<AssetQuantity, Reason> {
+    let balance = AssetBalances::get(asset.asset, borrower).min(0);
+    let owed = balance.checked_neg().ok_or(MathError::Overflow)? as Uint;
+    Ok(Quantity::new(owed, asset.units()))
+}
+
+/// Return how much CASH a borrower owes.
+fn get_owed_cash(borrower: ChainAccount) -> Result<AssetQuantity, Reason> {
+    let principal = CashPrincipals::get(borrower).0.min(0);
+    let owed = principal.checked_neg().ok_or(MathError::Overflow)? as Uint;
+    Ok(GlobalCashIndex::get().cash_quantity(CashPrincipalAmount(owed))?)
+}
+
+/// Return the value in USD of the largest borrow of a borrower, in CASH or any one asset.
+fn get_largest_owed_value<T: Config>(borrower: ChainAccount) -> Result<AssetAmount, Reason> {
+    let mut owed = vec![get_owed_cash(borrower)?];
+    for (asset, _) in AssetsWithNonZeroBalance::iter_prefix(borrower) {
+        owed.push(get_owed_asset(get_asset::<T>(asset)?, borrower)?);
+    }
+
+    let mut largest = 0;
+    for quantity in owed.into_iter().filter(|quantity| quantity.value > 0) {
+        largest = largest.max(get_value::<T>(quantity)?.value);
+    }
+    Ok(largest)
+}
+
+/// Check that the amount repaid is within the close factor of what was owed before repaying.
+fn check_close_factor(repaid: Uint, balance_after: AssetBalance) -> Result<(), Reason> {
+    let owed_after = balance_after.checked_neg().ok_or(MathError::Overflow)? as Uint;
+    let owed_before = repaid.checked_add(owed_after).ok_or(MathError::Overflow)?;
+    let max_repay = BigUint::from_uint(owed_before)
+        .mul_decimal(LiquidationCloseFactor::get().0, Factor::DECIMALS)
+        .to_uint()?;
+    must!(repaid <= max_repay, Reason::CloseFactorExceeded)
+}
 
 fn calculate_seize_quantity<T: Config>(
     quantity: AssetQuantity,
     collateral_units: Units,
+    incentive: Bips,
 ) -> Result<Quantity, Reason> {
-    let liquidation_incentive = Factor::from_nominal("1.08"); // XXX spec first
+    let liquidation_incentive =
+        Factor::from_fraction(BIPS_PER_UNIT.saturating_add(incentive), BIPS_PER_UNIT)?;
     let asset_price = get_price::<T>(quantity.units)?;
     let collateral_price = get_price::<T>(collateral_units)?;
 
@@ -41,15 +236,23 @@ pub fn liquidate_internal<T: Config>(
     quantity: AssetQuantity,
 ) -> Result<(), Reason> {
     require!(asset != collateral_asset, Reason::InKindLiquidation);
+    require!(
+        !SeizePaused::get(collateral_asset.asset),
+        Reason::SeizePaused
+    );
     require_min_tx_value!(get_value::<T>(quantity)?);
-    let seize_quantity = calculate_seize_quantity::<T>(quantity, collateral_asset.units())?;
+    let (incentive, auction) =
+        get_liquidation_incentive::<T>(borrower, get_owed_asset(asset, borrower)?)?;
+    let seize_quantity =
+        calculate_seize_quantity::<T>(quantity, collateral_asset.units(), incentive)?;
 
     CashPipeline::new()
         .check_underwater::<T>(borrower)?
         .transfer_asset::<T>(liquidator, borrower, asset.asset, quantity)?
         .transfer_asset::<T>(borrower, liquidator, collateral_asset.asset, seize_quantity)?
         .check_asset_balance::<T, _>(borrower, asset, |asset_balance| {
-            must!(asset_balance.lte(0), Reason::RepayTooMuch)
+            require!(asset_balance.lte(0), Reason::RepayTooMuch);
+            check_close_factor(quantity.value, asset_balance.value)
         })?
         .check_asset_balance::<T, _>(borrower, collateral_asset, |collateral_balance| {
             must!(collateral_balance.gte(0), Reason::InsufficientCollateral)
@@ -57,13 +260,15 @@ pub fn liquidate_internal<T: Config>(
         .check_collateralized::<T>(liquidator)?
         .commit::<T>();
 
-    <Module<T>>::deposit_event(Event::Liquidate(
+    update_liquidation_auction::<T>(borrower, auction);
+    deposit_account_event::<T>(Event::Liquidate(
         asset.asset,
         collateral_asset.asset,
         liquidator,
         borrower,
         quantity.value,
     ));
+    check_bad_debt::<T>(borrower);
 
     Ok(())
 }
@@ -77,15 +282,22 @@ pub fn liquidate_cash_principal_internal<T: Config>(
     let index = GlobalCashIndex::get();
     let quantity = index.cash_quantity(principal)?;
 
+    require!(
+        !SeizePaused::get(collateral_asset.asset),
+        Reason::SeizePaused
+    );
     require_min_tx_value!(get_value::<T>(quantity)?);
-    let seize_quantity = calculate_seize_quantity::<T>(quantity, collateral_asset.units())?;
+    let (incentive, auction) = get_liquidation_incentive::<T>(borrower, get_owed_cash(borrower)?)?;
+    let seize_quantity =
+        calculate_seize_quantity::<T>(quantity, collateral_asset.units(), incentive)?;
 
     CashPipeline::new()
         .check_underwater::<T>(borrower)?
         .transfer_cash::<T>(liquidator, borrower, principal)?
         .transfer_asset::<T>(borrower, liquidator, collateral_asset.asset, seize_quantity)?
         .check_cash_principal::<T, _>(borrower, |cash_principal| {
-            must!(cash_principal.lte(0), Reason::RepayTooMuch)
+            require!(cash_principal.lte(0), Reason::RepayTooMuch);
+            check_close_factor(principal.0, cash_principal.0)
         })?
         .check_asset_balance::<T, _>(borrower, collateral_asset, |collateral_balance| {
             must!(collateral_balance.gte(0), Reason::InsufficientCollateral)
@@ -93,13 +305,15 @@ pub fn liquidate_cash_principal_internal<T: Config>(
         .check_collateralized::<T>(liquidator)?
         .commit::<T>();
 
-    <Module<T>>::deposit_event(Event::LiquidateCash(
+    update_liquidation_auction::<T>(borrower, auction);
+    deposit_account_event::<T>(Event::LiquidateCash(
         collateral_asset.asset,
         liquidator,
         borrower,
         principal,
         index,
     ));
+    check_bad_debt::<T>(borrower);
 
     Ok(())
 }
@@ -113,7 +327,9 @@ pub fn liquidate_cash_collateral_internal<T: Config>(
     let index = GlobalCashIndex::get();
 
     require_min_tx_value!(get_value::<T>(quantity)?);
-    let seize_quantity = calculate_seize_quantity::<T>(quantity, CASH)?;
+    let (incentive, auction) =
+        get_liquidation_incentive::<T>(borrower, get_owed_asset(asset, borrower)?)?;
+    let seize_quantity = calculate_seize_quantity::<T>(quantity, CASH, incentive)?;
     let seize_principal = index.cash_principal_amount(seize_quantity)?;
 
     CashPipeline::new()
@@ -121,7 +337,8 @@ pub fn liquidate_cash_collateral_internal<T: Config>(
         .transfer_asset::<T>(liquidator, borrower, asset.asset, quantity)?
         .transfer_cash::<T>(borrower, liquidator, seize_principal)?
         .check_asset_balance::<T, _>(borrower, asset, |asset_balance| {
-            must!(asset_balance.lte(0), Reason::RepayTooMuch)
+            require!(asset_balance.lte(0), Reason::RepayTooMuch);
+            check_close_factor(quantity.value, asset_balance.value)
         })?
         .check_cash_principal::<T, _>(borrower, |cash_principal| {
             must!(cash_principal.gte(0), Reason::InsufficientCollateral)
@@ -129,12 +346,14 @@ pub fn liquidate_cash_collateral_internal<T: Config>(
         .check_collateralized::<T>(liquidator)?
         .commit::<T>();
 
-    <Module<T>>::deposit_event(Event::LiquidateCashCollateral(
+    update_liquidation_auction::<T>(borrower, auction);
+    deposit_account_event::<T>(Event::LiquidateCashCollateral(
         asset.asset,
         liquidator,
         borrower,
         quantity.value,
     ));
+    check_bad_debt::<T>(borrower);
 
     Ok(())
 }
@@ -143,6 +362,7 @@ pub fn liquidate_cash_collateral_internal<T: Config>(
 mod tests {
     use super::*;
     use crate::{
+        symbol::USD,
         tests::{assert_ok, assets::*, common::*, mock::*},
         types::*,
         *,
@@ -166,7 +386,7 @@ mod tests {
             init_wbtc_asset().unwrap();
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, GlobalLiquidationIncentive::get()),
                 Err(Reason::NoPrice)
             );
         })
@@ -180,7 +400,7 @@ mod tests {
             init_eth_asset().unwrap();
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, GlobalLiquidationIncentive::get()),
                 Err(Reason::NoPrice)
             );
         })
@@ -198,7 +418,7 @@ mod tests {
             init_wbtc_asset().unwrap();
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, GlobalLiquidationIncentive::get()),
                 Err(Reason::MathError(MathError::Overflow))
             );
         })
@@ -216,7 +436,7 @@ mod tests {
             init_wbtc_asset().unwrap();
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, GlobalLiquidationIncentive::get()),
                 Err(Reason::MathError(MathError::Overflow))
             );
         })
@@ -232,7 +452,7 @@ mod tests {
             pallet_oracle::Prices::insert(ETH.ticker, Price::from_nominal(ETH.ticker, "0").value);
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, GlobalLiquidationIncentive::get()),
                 Err(Reason::NoPrice)
             );
         })
@@ -248,7 +468,7 @@ mod tests {
             pallet_oracle::Prices::insert(WBTC.ticker, Price::from_nominal(WBTC.ticker, "0").value);
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, GlobalLiquidationIncentive::get()),
                 Err(Reason::NoPrice)
             );
         })
@@ -271,7 +491,7 @@ mod tests {
             );
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, GlobalLiquidationIncentive::get()),
                 Ok(Quantity {
                     value: 3600000, // 1.08 * 1 * 2000 / 60000 = 0.036e8
                     units: WBTC
@@ -284,7 +504,7 @@ mod tests {
             );
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, GlobalLiquidationIncentive::get()),
                 Ok(Quantity {
                     value: 4320000, // 1.08 * 1 * 2000 / 50000 = 0.0432e8
                     units: WBTC
@@ -292,7 +512,7 @@ mod tests {
             );
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, CASH),
+                calculate_seize_quantity::<Test>(quantity, CASH, GlobalLiquidationIncentive::get()),
                 Ok(Quantity {
                     value: 2160000000, // 1.08 * 1 * 2000 / 1 = 2160e6
                     units: CASH
@@ -305,7 +525,8 @@ mod tests {
                         value: 1000000000,
                         units: CASH
                     },
-                    ETH
+                    ETH,
+                    GlobalLiquidationIncentive::get()
                 ),
                 Ok(Quantity {
                     value: 540000000000000000, // 1.08 * 1 * 1000 / 2000 = 0.54e18
@@ -315,8 +536,214 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_calculate_seize_quantity_governance_incentive() {
+        new_test_ext().execute_with(|| {
+            let quantity: AssetQuantity = eth.as_quantity_nominal("1");
+
+            init_eth_asset().unwrap();
+            init_wbtc_asset().unwrap();
+            pallet_oracle::Prices::insert(
+                ETH.ticker,
+                Price::from_nominal(ETH.ticker, "2000").value,
+            );
+            pallet_oracle::Prices::insert(
+                WBTC.ticker,
+                Price::from_nominal(WBTC.ticker, "50000").value,
+            );
+            assert_ok!(set_liquidation_incentive::<Test>(1000));
+
+            assert_eq!(
+                calculate_seize_quantity::<Test>(quantity, WBTC, GlobalLiquidationIncentive::get()),
+                Ok(Quantity {
+                    value: 4400000, // 1.10 * 1 * 2000 / 50000 = 0.044e8
+                    units: WBTC
+                })
+            );
+        })
+    }
+
+    #[test]
+    fn test_set_liquidation_incentive() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(GlobalLiquidationIncentive::get(), 800);
+            assert_eq!(
+                set_liquidation_incentive::<Test>(5001),
+                Err(Reason::InvalidLiquidation)
+            );
+            assert_ok!(set_liquidation_incentive::<Test>(500));
+            assert_eq!(GlobalLiquidationIncentive::get(), 500);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(
+                    crate::Event::SetLiquidationIncentive(500)
+                ))
+            );
+        })
+    }
+
+    #[test]
+    fn test_set_close_factor() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(LiquidationCloseFactor::get(), Factor::ONE);
+            assert_eq!(
+                set_close_factor::<Test>(Factor::ZERO),
+                Err(Reason::InvalidLiquidation)
+            );
+            assert_eq!(
+                set_close_factor::<Test>(Factor::from_nominal("1.01")),
+                Err(Reason::InvalidLiquidation)
+            );
+            assert_ok!(set_close_factor::<Test>(Factor::from_nominal("0.5")));
+            assert_eq!(LiquidationCloseFactor::get(), Factor::from_nominal("0.5"));
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::SetCloseFactor(
+                    Factor::from_nominal("0.5")
+                )))
+            );
+        })
+    }
+
+    #[test]
+    fn test_set_seize_paused() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                set_seize_paused::<Test>(Wbtc, true),
+                Err(Reason::AssetNotSupported)
+            );
+            init_wbtc_asset().unwrap();
+            assert_ok!(set_seize_paused::<Test>(Wbtc, true));
+            assert_eq!(SeizePaused::get(Wbtc), true);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::SetSeizePaused(
+                    Wbtc, true
+                )))
+            );
+            assert_ok!(set_seize_paused::<Test>(Wbtc, false));
+            assert_eq!(SeizePaused::contains_key(Wbtc), false);
+        })
+    }
+
+    #[allow(non_upper_case_globals)]
+    const auction_config: LiquidationAuctionConfig = LiquidationAuctionConfig {
+        min_value: 100000_000000, // $100k
+        start_incentive: 0,
+        max_incentive: 1000,
+        ramp_blocks: 10,
+    };
+
+    #[test]
+    fn test_set_liquidation_auction() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                set_liquidation_auction::<Test>(Some(LiquidationAuctionConfig {
+                    start_incentive: 1001,
+                    ..auction_config
+                })),
+                Err(Reason::InvalidLiquidation)
+            );
+            assert_eq!(
+                set_liquidation_auction::<Test>(Some(LiquidationAuctionConfig {
+                    max_incentive: MAX_LIQUIDATION_INCENTIVE + 1,
+                    ..auction_config
+                })),
+                Err(Reason::InvalidLiquidation)
+            );
+            assert_eq!(
+                set_liquidation_auction::<Test>(Some(LiquidationAuctionConfig {
+                    ramp_blocks: 0,
+                    ..auction_config
+                })),
+                Err(Reason::InvalidLiquidation)
+            );
+            assert_ok!(set_liquidation_auction::<Test>(Some(auction_config)));
+            assert_eq!(LiquidationAuctionParams::get(), Some(auction_config));
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(
+                    crate::Event::SetLiquidationAuction(Some(auction_config))
+                ))
+            );
+            assert_ok!(set_liquidation_auction::<Test>(None));
+            assert_eq!(LiquidationAuctionParams::get(), None);
+        })
+    }
+
+    #[test]
+    fn test_get_auction_incentive() {
+        let auction = LiquidationAuction { start_block: 100 };
+        assert_eq!(get_auction_incentive(auction_config, auction, 100), 0);
+        assert_eq!(get_auction_incentive(auction_config, auction, 103), 300);
+        assert_eq!(get_auction_incentive(auction_config, auction, 110), 1000);
+        assert_eq!(get_auction_incentive(auction_config, auction, 200), 1000);
+        assert_eq!(
+            get_auction_incentive(
+                LiquidationAuctionConfig {
+                    start_incentive: 500,
+                    ramp_blocks: 3,
+                    ..auction_config
+                },
+                auction,
+                101
+            ),
+            666
+        );
+    }
+
     // liquidate_internal
 
+    #[test]
+    fn test_liquidate_internal_seize_paused() {
+        new_test_ext().execute_with(|| {
+            let amount: AssetQuantity = eth.as_quantity_nominal("1");
+
+            init_eth_asset().unwrap();
+            init_wbtc_asset().unwrap();
+            assert_ok!(set_seize_paused::<Test>(Wbtc, true));
+
+            assert_eq!(
+                liquidate_internal::<Test>(asset, collateral_asset, liquidator, borrower, amount),
+                Err(Reason::SeizePaused)
+            );
+        })
+    }
+
+    #[test]
+    fn test_liquidate_internal_close_factor_exceeded() {
+        new_test_ext().execute_with(|| {
+            let amount: AssetQuantity = eth.as_quantity_nominal("1");
+
+            init_eth_asset().unwrap();
+            init_wbtc_asset().unwrap();
+
+            init_asset_balance(Eth, borrower, Balance::from_nominal("-80", ETH).value); // -80 * 2000 / 0.8 = -200000
+            init_asset_balance(Wbtc, borrower, Balance::from_nominal("2", WBTC).value); // 2 * 60000 * 0.6 = 72000
+            init_cash(borrower, CashPrincipal::from_nominal("100000")); // 100000 + 72000 - 200000 = -28000
+
+            init_asset_balance(Wbtc, liquidator, Balance::from_nominal("1", WBTC).value);
+            init_asset_balance(Eth, liquidator, Balance::from_nominal("0.5", ETH).value);
+            init_cash(liquidator, CashPrincipal::from_nominal("100000"));
+
+            // At most 0.01 * 80 = 0.8 ETH may be repaid
+            assert_ok!(set_close_factor::<Test>(Factor::from_nominal("0.01")));
+            assert_eq!(
+                liquidate_internal::<Test>(asset, collateral_asset, liquidator, borrower, amount),
+                Err(Reason::CloseFactorExceeded)
+            );
+
+            assert_ok!(set_close_factor::<Test>(Factor::from_nominal("0.0125")));
+            assert_ok!(liquidate_internal::<Test>(
+                asset,
+                collateral_asset,
+                liquidator,
+                borrower,
+                amount
+            ));
+        })
+    }
+
     #[test]
     fn test_liquidate_internal_self_liquidate() {
         new_test_ext().execute_with(|| {
@@ -724,6 +1151,236 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_liquidate_internal_auction() {
+        new_test_ext().execute_with(|| {
+            let amount: AssetQuantity = eth.as_quantity_nominal("1");
+
+            init_eth_asset().unwrap();
+            init_wbtc_asset().unwrap();
+            assert_ok!(set_liquidation_auction::<Test>(Some(auction_config)));
+
+            init_asset_balance(Eth, borrower, Balance::from_nominal("-80", ETH).value); // -80 * 2000 = -160000, above the auction minimum
+            init_asset_balance(Wbtc, borrower, Balance::from_nominal("2", WBTC).value);
+            init_cash(borrower, CashPrincipal::from_nominal("100000"));
+
+            init_asset_balance(Wbtc, liquidator, Balance::from_nominal("1", WBTC).value);
+            init_asset_balance(Eth, liquidator, Balance::from_nominal("5", ETH).value);
+            init_cash(liquidator, CashPrincipal::from_nominal("100000"));
+
+            // The first liquidation starts the auction, seizing 1.00 * 1 * 2000 / 60000 = 0.03333333 WBTC
+            System::set_block_number(1);
+            assert_ok!(liquidate_internal::<Test>(
+                asset,
+                collateral_asset,
+                liquidator,
+                borrower,
+                amount
+            ));
+            assert_eq!(
+                LiquidationAuctions::get(borrower),
+                Some(LiquidationAuction { start_block: 1 })
+            );
+            assert!(System::events().iter().any(|e| e.event
+                == mock::Event::pallet_cash(crate::Event::LiquidationAuctionStarted(borrower, 1))));
+            assert_eq!(
+                AssetBalances::get(Wbtc, liquidator),
+                Balance::from_nominal("1.03333333", WBTC).value
+            );
+
+            // Halfway through the ramp, partial fills seize 1.05 * 1 * 2000 / 60000 = 0.035 WBTC
+            System::set_block_number(6);
+            assert_ok!(liquidate_internal::<Test>(
+                asset,
+                collateral_asset,
+                liquidator,
+                borrower,
+                amount
+            ));
+            assert_eq!(
+                AssetBalances::get(Wbtc, liquidator),
+                Balance::from_nominal("1.06833333", WBTC).value
+            );
+
+            // At the end of the ramp, they seize 1.10 * 1 * 2000 / 60000 = 0.03666666 WBTC
+            System::set_block_number(11);
+            assert_ok!(liquidate_internal::<Test>(
+                asset,
+                collateral_asset,
+                liquidator,
+                borrower,
+                amount
+            ));
+            assert_eq!(
+                AssetBalances::get(Wbtc, liquidator),
+                Balance::from_nominal("1.10499999", WBTC).value
+            );
+            assert_eq!(
+                LiquidationAuctions::get(borrower),
+                Some(LiquidationAuction { start_block: 1 })
+            );
+
+            // While the borrower stays underwater, the auction goes on at the max incentive
+            System::set_block_number(100);
+            assert_ok!(liquidate_internal::<Test>(
+                asset,
+                collateral_asset,
+                liquidator,
+                borrower,
+                amount
+            ));
+            assert_eq!(
+                LiquidationAuctions::get(borrower),
+                Some(LiquidationAuction { start_block: 1 })
+            );
+            assert_eq!(
+                AssetBalances::get(Wbtc, liquidator),
+                Balance::from_nominal("1.14166665", WBTC).value
+            );
+        })
+    }
+
+    #[test]
+    fn test_liquidate_internal_auction_below_minimum() {
+        new_test_ext().execute_with(|| {
+            let amount: AssetQuantity = eth.as_quantity_nominal("1");
+
+            init_eth_asset().unwrap();
+            init_wbtc_asset().unwrap();
+            assert_ok!(set_liquidation_auction::<Test>(Some(
+                LiquidationAuctionConfig {
+                    min_value: 200000_000000,
+                    ..auction_config
+                }
+            )));
+
+            init_asset_balance(Eth, borrower, Balance::from_nominal("-80", ETH).value); // -80 * 2000 = -160000, below the auction minimum
+            init_asset_balance(Wbtc, borrower, Balance::from_nominal("2", WBTC).value);
+            init_cash(borrower, CashPrincipal::from_nominal("100000"));
+
+            init_asset_balance(Wbtc, liquidator, Balance::from_nominal("1", WBTC).value);
+            init_cash(liquidator, CashPrincipal::from_nominal("100000"));
+
+            // Seize amount = 1.08 * 1 * 2000 / 60000 = 0.036 WBTC, at the fixed incentive
+            assert_ok!(liquidate_internal::<Test>(
+                asset,
+                collateral_asset,
+                liquidator,
+                borrower,
+                amount
+            ));
+            assert_eq!(LiquidationAuctions::get(borrower), None);
+            assert_eq!(
+                AssetBalances::get(Wbtc, liquidator),
+                Balance::from_nominal("1.036", WBTC).value
+            );
+        })
+    }
+
+    #[test]
+    fn test_start_liquidation_auction() {
+        new_test_ext().execute_with(|| {
+            init_eth_asset().unwrap();
+            init_wbtc_asset().unwrap();
+            init_asset_balance(Eth, borrower, Balance::from_nominal("-80", ETH).value); // -80 * 2000 = -160000, above the auction minimum
+            init_asset_balance(Wbtc, borrower, Balance::from_nominal("2", WBTC).value);
+            init_cash(borrower, CashPrincipal::from_nominal("100000"));
+            init_asset_balance(Wbtc, liquidator, Balance::from_nominal("1", WBTC).value);
+            init_asset_balance(Eth, liquidator, Balance::from_nominal("5", ETH).value);
+            init_cash(liquidator, CashPrincipal::from_nominal("100000"));
+
+            assert_eq!(
+                start_liquidation_auction_internal::<Test>(borrower),
+                Err(Reason::LiquidationAuctionNotAllowed)
+            );
+            assert_ok!(set_liquidation_auction::<Test>(Some(
+                LiquidationAuctionConfig {
+                    min_value: 200000_000000,
+                    ..auction_config
+                }
+            )));
+            assert_eq!(
+                start_liquidation_auction_internal::<Test>(borrower),
+                Err(Reason::LiquidationAuctionNotAllowed)
+            );
+            assert_eq!(
+                start_liquidation_auction_internal::<Test>(liquidator),
+                Err(Reason::SufficientLiquidity)
+            );
+
+            // Starting the auction seizes nothing
+            assert_ok!(set_liquidation_auction::<Test>(Some(auction_config)));
+            System::set_block_number(1);
+            assert_ok!(start_liquidation_auction_internal::<Test>(borrower));
+            assert_eq!(
+                LiquidationAuctions::get(borrower),
+                Some(LiquidationAuction { start_block: 1 })
+            );
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(
+                    crate::Event::LiquidationAuctionStarted(borrower, 1)
+                ))
+            );
+            assert_eq!(
+                AssetBalances::get(Wbtc, borrower),
+                Balance::from_nominal("2", WBTC).value
+            );
+            assert_eq!(
+                start_liquidation_auction_internal::<Test>(borrower),
+                Err(Reason::LiquidationAuctionOngoing)
+            );
+
+            // Once ramped up, the first liquidation seizes 1.10 * 1 * 2000 / 60000 = 0.03666666 WBTC
+            System::set_block_number(11);
+            assert_ok!(liquidate_internal::<Test>(
+                asset,
+                collateral_asset,
+                liquidator,
+                borrower,
+                eth.as_quantity_nominal("1")
+            ));
+            assert_eq!(
+                AssetBalances::get(Wbtc, liquidator),
+                Balance::from_nominal("1.03666666", WBTC).value
+            );
+
+            // An auction left over once the borrower is no longer underwater is ended
+            init_cash(borrower, CashPrincipal::from_nominal("1000000"));
+            assert_ok!(start_liquidation_auction_internal::<Test>(borrower));
+            assert_eq!(LiquidationAuctions::get(borrower), None);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(
+                    crate::Event::LiquidationAuctionEnded(borrower)
+                ))
+            );
+            assert_eq!(
+                start_liquidation_auction_internal::<Test>(borrower),
+                Err(Reason::SufficientLiquidity)
+            );
+        })
+    }
+
+    #[test]
+    fn test_update_liquidation_auction_ends() {
+        new_test_ext().execute_with(|| {
+            init_eth_asset().unwrap();
+            let auction = LiquidationAuction { start_block: 1 };
+            LiquidationAuctions::insert(borrower, auction);
+            init_cash(borrower, CashPrincipal::from_nominal("100"));
+
+            update_liquidation_auction::<Test>(borrower, Some(auction));
+            assert_eq!(LiquidationAuctions::get(borrower), None);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(
+                    crate::Event::LiquidationAuctionEnded(borrower)
+                ))
+            );
+        })
+    }
+
     // liquidate_cash_principal
 
     #[test]
@@ -1004,6 +1661,26 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_liquidate_cash_principal_internal_seize_paused() {
+        new_test_ext().execute_with(|| {
+            let principal: CashPrincipalAmount = CashPrincipalAmount::from_nominal("1000");
+
+            init_wbtc_asset().unwrap();
+            assert_ok!(set_seize_paused::<Test>(Wbtc, true));
+
+            assert_eq!(
+                liquidate_cash_principal_internal::<Test>(
+                    collateral_asset,
+                    liquidator,
+                    borrower,
+                    principal
+                ),
+                Err(Reason::SeizePaused)
+            );
+        })
+    }
+
     #[test]
     fn test_liquidate_cash_principal_internal_ok() {
         new_test_ext().execute_with(|| {
@@ -1078,6 +1755,11 @@ mod tests {
                 CashPrincipals::get(liquidator),
                 CashPrincipal::from_nominal("40000")
             );
+            // 240000 - 80 * 2000 - 0.92 * 60000 = 24800
+            assert_eq!(
+                BadDebts::get(borrower),
+                Some(Quantity::from_nominal("24800", USD).value)
+            );
             // TODO: Check this number
             assert_eq!(
                 TotalCashPrincipal::get(),
@@ -0,0 +1,240 @@
+use crate::{
+    chains::{ChainId, ChainSignatureList},
+    factor::Factor,
+    notices::{EraId, NoticeId, NoticeState},
+    params::MIN_LIVENESS_NOTICES,
+    reason::Reason,
+    types::{CashPrincipalAmount, Offense},
+    ChilledValidators, Config, EraNoticeCounts, Event, MaxMissedSignatures, Module, NoticeStates,
+    Validators,
+};
+use frame_support::storage::{IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue};
+
+/// Set the maximum fraction of the notices in an era which a validator may fail to sign, before being reported.
+pub fn set_max_missed_signatures<T: Config>(fraction: Factor) -> Result<(), Reason> {
+    MaxMissedSignatures::put(fraction);
+    <Module<T>>::deposit_event(Event::SetMaxMissedSignatures(fraction));
+    Ok(())
+}
+
+/// Count a notice dispatched within its era.
+pub fn track_notice(chain_id: ChainId, notice_id: NoticeId) {
+    EraNoticeCounts::mutate((chain_id, notice_id.era_id()), |count| {
+        *count = count.saturating_add(1)
+    });
+}
+
+/// Report the validators which missed too many signatures during a completed era, and forget the era.
+/// Only notices still pending when the era ends count, executed or revoked notices needed no more signatures.
+/// Eras with fewer than the minimum number of pending notices are too small a sample to judge by.
+/// Unresponsive validators are only reported, they still count towards every quorum,
+///  as starports keep verifying against them until the validators are changed.
+/// Called when the next era begins, and thus may not fail.
+pub fn check_liveness<T: Config>(chain_id: ChainId, era_id: EraId) {
+    let count = EraNoticeCounts::take((chain_id, era_id));
+    let pending: Vec<ChainSignatureList> = (0..count)
+        .filter_map(
+            |index| match NoticeStates::get(chain_id, NoticeId(era_id, index)) {
+                NoticeState::Pending { signature_pairs } => Some(signature_pairs),
+                _ => None,
+            },
+        )
+        .collect();
+    let notices = pending.len() as u32;
+    if notices < MIN_LIVENESS_NOTICES {
+        return;
+    }
+
+    let max_missed = MaxMissedSignatures::get();
+    for (_, validator) in Validators::iter() {
+        if ChilledValidators::contains_key(&validator.substrate_id) {
+            continue;
+        }
+        let missed = pending
+            .iter()
+            .filter(|signature_pairs| {
+                !signature_pairs.has_validator_signature(chain_id, &validator)
+            })
+            .count() as u32;
+        let missed_fraction = match Factor::from_fraction(missed, notices) {
+            Ok(fraction) => fraction,
+            Err(_) => continue,
+        };
+        if missed_fraction > max_missed {
+            <Module<T>>::deposit_event(Event::ValidatorOffense(
+                validator.substrate_id,
+                Offense::Unresponsive(chain_id, era_id),
+                CashPrincipalAmount::ZERO,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn init_validators() -> (ValidatorKeys, ValidatorKeys) {
+        let active = ValidatorKeys {
+            substrate_id: AccountId32::new([1u8; 32]),
+            eth_address: [1; 20],
+        };
+        let offline = ValidatorKeys {
+            substrate_id: AccountId32::new([2u8; 32]),
+            eth_address: [2; 20],
+        };
+        Validators::insert(&active.substrate_id, active.clone());
+        Validators::insert(&offline.substrate_id, offline.clone());
+        (active, offline)
+    }
+
+    fn signed_by(signers: Vec<&ValidatorKeys>) -> NoticeState {
+        NoticeState::Pending {
+            signature_pairs: ChainSignatureList::Eth(
+                signers
+                    .iter()
+                    .map(|v| (v.eth_address, [7u8; 65]))
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap(),
+            ),
+        }
+    }
+
+    fn reported() -> Vec<ValidatorIdentity> {
+        System::events()
+            .into_iter()
+            .filter_map(|e| match e.event {
+                mock::Event::pallet_cash(crate::Event::ValidatorOffense(id, _, _)) => Some(id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn dispatch_pending(era_id: EraId, index: u32, state: NoticeState) {
+        let notice_id = NoticeId(era_id, index);
+        track_notice(ChainId::Eth, notice_id);
+        NoticeStates::insert(ChainId::Eth, notice_id, state);
+    }
+
+    #[test]
+    fn test_set_max_missed_signatures() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(MaxMissedSignatures::get(), DEFAULT_MAX_MISSED_SIGNATURES);
+            let fraction = Factor::from_nominal("0.25");
+            assert_ok!(set_max_missed_signatures::<Test>(fraction));
+            assert_eq!(MaxMissedSignatures::get(), fraction);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(
+                    crate::Event::SetMaxMissedSignatures(fraction)
+                ))
+            );
+        });
+    }
+
+    #[test]
+    fn test_check_liveness() {
+        new_test_ext().execute_with(|| {
+            let (active, offline) = init_validators();
+            MaxMissedSignatures::put(Factor::from_nominal("0.5"));
+
+            dispatch_pending(1, 0, signed_by(vec![&active, &offline]));
+            for index in 1..4 {
+                dispatch_pending(1, index, signed_by(vec![&active]));
+            }
+            track_notice(ChainId::Eth, NoticeId(2, 0));
+            assert_eq!(EraNoticeCounts::get((ChainId::Eth, 1)), 4);
+
+            check_liveness::<Test>(ChainId::Eth, 1);
+
+            assert_eq!(reported(), vec![offline.substrate_id.clone()]);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::ValidatorOffense(
+                    offline.substrate_id.clone(),
+                    Offense::Unresponsive(ChainId::Eth, 1),
+                    CashPrincipalAmount::ZERO
+                )))
+            );
+
+            // being unresponsive does not take the validator out of any quorum
+            assert_eq!(ChilledValidators::get(&offline.substrate_id), None);
+            assert_eq!(get_validator_set::<Test>().map(|set| set.len()), Ok(2));
+            assert_eq!(EraNoticeCounts::get((ChainId::Eth, 1)), 0);
+            assert_eq!(EraNoticeCounts::get((ChainId::Eth, 2)), 1);
+        });
+    }
+
+    #[test]
+    fn test_check_liveness_within_threshold() {
+        new_test_ext().execute_with(|| {
+            let (active, offline) = init_validators();
+            MaxMissedSignatures::put(Factor::from_nominal("0.5"));
+
+            for index in 0..2 {
+                dispatch_pending(1, index, signed_by(vec![&active, &offline]));
+            }
+            for index in 2..4 {
+                dispatch_pending(1, index, signed_by(vec![&active]));
+            }
+
+            check_liveness::<Test>(ChainId::Eth, 1);
+            assert_eq!(reported(), vec![]);
+        });
+    }
+
+    #[test]
+    fn test_check_liveness_ignores_closed_notices() {
+        new_test_ext().execute_with(|| {
+            let (active, offline) = init_validators();
+            MaxMissedSignatures::put(Factor::from_nominal("0.5"));
+
+            for index in 0..4 {
+                dispatch_pending(1, index, signed_by(vec![&active, &offline]));
+            }
+            for index in 4..8 {
+                dispatch_pending(1, index, NoticeState::Executed);
+            }
+            dispatch_pending(1, 8, NoticeState::Missing);
+
+            check_liveness::<Test>(ChainId::Eth, 1);
+            assert_eq!(reported(), vec![]);
+        });
+    }
+
+    #[test]
+    fn test_check_liveness_requires_sample() {
+        new_test_ext().execute_with(|| {
+            let (active, offline) = init_validators();
+            MaxMissedSignatures::put(Factor::from_nominal("0.5"));
+
+            for index in 0..MIN_LIVENESS_NOTICES - 1 {
+                dispatch_pending(1, index, signed_by(vec![&active]));
+            }
+
+            check_liveness::<Test>(ChainId::Eth, 1);
+            assert_eq!(reported(), vec![]);
+            assert_eq!(EraNoticeCounts::get((ChainId::Eth, 1)), 0);
+        });
+    }
+
+    #[test]
+    fn test_check_liveness_skips_chilled() {
+        new_test_ext().execute_with(|| {
+            let (active, offline) = init_validators();
+            MaxMissedSignatures::put(Factor::from_nominal("0.5"));
+            let offense = Offense::Equivocation((ChainId::Eth, 0));
+            ChilledValidators::insert(&offline.substrate_id, offense);
+
+            for index in 0..4 {
+                dispatch_pending(1, index, signed_by(vec![&active]));
+            }
+
+            check_liveness::<Test>(ChainId::Eth, 1);
+            assert_eq!(reported(), vec![]);
+            assert_eq!(ChilledValidators::get(&offline.substrate_id), Some(offense));
+        });
+    }
+}
@@ -1,11 +1,82 @@
 use crate::{
-    chains::ChainAccount,
+    chains::{ChainAccount, ChainAsset, ChainBlockEvent, ChainBlockNumber, ChainId},
+    core::get_chain_event_id,
+    internal::{self, account_history::deposit_account_event},
+    log,
+    params::ATTESTATION_RETENTION_BLOCKS,
     pipeline::CashPipeline,
     reason::Reason,
-    types::{AssetInfo, AssetQuantity, CashIndex, CashPrincipalAmount},
-    Config, Event, GlobalCashIndex, Module,
+    types::{AssetAmount, AssetInfo, AssetQuantity, CashIndex, CashPrincipalAmount, LockOutcome},
+    Config, Event, GlobalCashIndex, LockOutcomes, Module, SupportedAssets,
 };
-use frame_support::storage::StorageValue;
+use frame_support::storage::{StorageDoubleMap, StorageMap, StorageValue};
+
+/// Return the asset being locked, if the lock should be credited rather than refunded.
+/// Nothing is credited once the protocol is frozen for an exodus.
+fn get_creditable_asset(asset: ChainAsset) -> Option<AssetInfo> {
+    SupportedAssets::get(asset).filter(|_| !internal::exodus::in_exodus())
+}
+
+/// Record how a lock event was applied, so that a reorg reverts it the same way.
+/// The same event may occur more than once in a block, so each application is recorded.
+pub fn record_lock_outcome(event: &ChainBlockEvent, outcome: LockOutcome) {
+    let (event_id, event_hash) = get_chain_event_id(event);
+    LockOutcomes::append(event_id, event_hash, outcome);
+}
+
+/// Forget how the locks were applied once a chain block has been processed, and can no longer be reorganized.
+pub fn prune_lock_outcomes(chain_id: ChainId, last_block_number: ChainBlockNumber) {
+    if let Some(number) = last_block_number.checked_sub(ATTESTATION_RETENTION_BLOCKS) {
+        LockOutcomes::remove_prefix((chain_id, number));
+    }
+}
+
+/// Revert the last recorded application of a lock event, forgetting it once reverted.
+/// A lock whose outcome is no longer recorded cannot be reverted safely, and is left alone.
+fn undo_lock_outcome(
+    event: &ChainBlockEvent,
+    undo: impl FnOnce(LockOutcome) -> Result<(), Reason>,
+) -> Result<(), Reason> {
+    let (event_id, event_hash) = get_chain_event_id(event);
+    let mut outcomes = LockOutcomes::get(event_id, event_hash);
+    match outcomes.pop() {
+        Some(outcome) => {
+            undo(outcome)?;
+            if outcomes.is_empty() {
+                LockOutcomes::remove(event_id, event_hash);
+            } else {
+                LockOutcomes::insert(event_id, event_hash, outcomes);
+            }
+        }
+        None => log!("No outcome recorded for lock {:?}, not reverting", event),
+    }
+    Ok(())
+}
+
+/// Credit a lock of a supported asset to the recipient, or refund a lock of any other asset to the sender.
+/// Locks of any asset during an exodus are refunded too.
+/// Refunding means users who lock a token the protocol does not support can still get it back.
+/// Refunds are never batched, so that each can be revoked on its own if the lock is reorganized away.
+pub fn lock_or_refund_internal<T: Config>(
+    asset: ChainAsset,
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    amount: AssetAmount,
+) -> Result<LockOutcome, Reason> {
+    match get_creditable_asset(asset) {
+        Some(info) => {
+            lock_internal::<T>(info, sender, recipient, info.as_quantity(amount))?;
+            Ok(LockOutcome::Credited)
+        }
+        None => {
+            log!("Refunding lock of {:?} to {:?}", asset, sender);
+            let notice_id =
+                internal::notices::dispatch_unbatched_extraction_notice::<T>(asset, sender, amount);
+            <Module<T>>::deposit_event(Event::LockRefunded(asset, sender, recipient, amount));
+            Ok(LockOutcome::Refunded(notice_id))
+        }
+    }
+}
 
 pub fn lock_internal<T: Config>(
     asset: AssetInfo,
@@ -16,8 +87,9 @@ pub fn lock_internal<T: Config>(
     CashPipeline::new()
         .lock_asset::<T>(recipient, asset.asset, quantity)?
         .commit::<T>();
+    internal::holdings::add_holdings(asset.asset, quantity.value);
 
-    <Module<T>>::deposit_event(Event::Locked(
+    deposit_account_event::<T>(Event::Locked(
         asset.asset,
         sender,
         recipient,
@@ -31,18 +103,46 @@ pub fn lock_cash_principal_internal<T: Config>(
     sender: ChainAccount,
     recipient: ChainAccount,
     principal: CashPrincipalAmount,
-) -> Result<(), Reason> {
+) -> Result<LockOutcome, Reason> {
+    if internal::exodus::in_exodus() {
+        let index: CashIndex = GlobalCashIndex::get();
+        let notice_id =
+            internal::notices::dispatch_cash_extraction_notice::<T>(sender, principal, index);
+        <Module<T>>::deposit_event(Event::LockCashRefunded(sender, recipient, principal, index));
+        return Ok(LockOutcome::Refunded(notice_id));
+    }
+
     CashPipeline::new()
         .lock_cash::<T>(recipient, principal)?
         .commit::<T>();
 
     let index: CashIndex = GlobalCashIndex::get(); // Grab cash index just for event
-    <Module<T>>::deposit_event(Event::LockedCash(sender, recipient, principal, index));
+    deposit_account_event::<T>(Event::LockedCash(sender, recipient, principal, index));
 
-    Ok(())
+    Ok(LockOutcome::Credited)
+}
+
+/// Revert a lock while handling a chain re-organization, the same way it was applied.
+/// A credited lock is debited even if the asset could no longer be locked, and a refund is revoked instead.
+pub fn undo_lock_or_refund_internal<T: Config>(
+    event: &ChainBlockEvent,
+    asset: ChainAsset,
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    amount: AssetAmount,
+) -> Result<(), Reason> {
+    undo_lock_outcome(event, |outcome| match outcome {
+        LockOutcome::Credited => {
+            let info = SupportedAssets::get(asset).ok_or(Reason::AssetNotSupported)?;
+            undo_lock_internal::<T>(info, sender, recipient, info.as_quantity(amount))
+        }
+        LockOutcome::Refunded(notice_id) => {
+            internal::notices::revoke_notice::<T>(sender.chain_id(), notice_id);
+            Ok(())
+        }
+    })
 }
 
-// TODO: Test
 pub fn undo_lock_internal<T: Config>(
     asset: AssetInfo,
     sender: ChainAccount,
@@ -53,8 +153,9 @@ pub fn undo_lock_internal<T: Config>(
     CashPipeline::new()
         .extract_asset::<T>(recipient, asset.asset, quantity)?
         .commit::<T>();
+    internal::holdings::sub_holdings(asset.asset, quantity.value);
 
-    <Module<T>>::deposit_event(Event::ReorgRevertLocked(
+    deposit_account_event::<T>(Event::ReorgRevertLocked(
         asset.asset,
         sender,
         recipient,
@@ -64,31 +165,45 @@ pub fn undo_lock_internal<T: Config>(
     Ok(())
 }
 
-// TODO: Test
+/// Revert a lock of CASH while handling a chain re-organization, the same way it was applied.
+/// A lock refunded during the exodus has its refund revoked instead.
 pub fn undo_lock_cash_principal_internal<T: Config>(
+    event: &ChainBlockEvent,
     sender: ChainAccount,
     recipient: ChainAccount,
     principal: CashPrincipalAmount,
 ) -> Result<(), Reason> {
-    CashPipeline::new()
-        .extract_cash::<T>(recipient, principal)?
-        .commit::<T>();
-
-    let index: CashIndex = GlobalCashIndex::get(); // Grab cash index just for event
-    <Module<T>>::deposit_event(Event::ReorgRevertLockedCash(
-        sender, recipient, principal, index,
-    ));
+    undo_lock_outcome(event, |outcome| match outcome {
+        LockOutcome::Credited => {
+            CashPipeline::new()
+                .extract_cash::<T>(recipient, principal)?
+                .commit::<T>();
+
+            let index: CashIndex = GlobalCashIndex::get(); // Grab cash index just for event
+            deposit_account_event::<T>(Event::ReorgRevertLockedCash(
+                sender, recipient, principal, index,
+            ));
 
-    Ok(())
+            Ok(())
+        }
+        LockOutcome::Refunded(notice_id) => {
+            internal::notices::revoke_notice::<T>(sender.chain_id(), notice_id);
+            Ok(())
+        }
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{chains::ChainId, tests::*, types::CashPrincipal};
+    use crate::{
+        chains::ChainId,
+        tests::{assets::*, common::*, *},
+        types::CashPrincipal,
+    };
     use frame_support::{
         assert_err, assert_ok,
-        storage::{StorageMap, StorageValue},
+        storage::{IterableStorageDoubleMap, StorageMap, StorageValue},
     };
     use our_std::{convert::TryInto, str::FromStr};
 
@@ -100,6 +215,46 @@ mod tests {
         129, 105, 82, 44, 44, 87, 136, 62, 142, 248, 12, 73, 138, 171, 120, 32, 218, 83, 152, 6,
     ]);
 
+    fn eth_address(account: ChainAccount) -> [u8; 20] {
+        match account {
+            ChainAccount::Eth(address) => address,
+            _ => panic!("expected an eth account"),
+        }
+    }
+
+    fn lock_event(asset: ChainAsset, amount: AssetAmount) -> ChainBlockEvent {
+        let mut recipient = [0; 32];
+        recipient[..20].copy_from_slice(&eth_address(GEOFF));
+        ChainBlockEvent::Eth(
+            1,
+            ethereum_client::EthereumEvent::Lock {
+                asset: match asset {
+                    ChainAsset::Eth(address) => address,
+                    _ => panic!("expected an eth asset"),
+                },
+                sender: eth_address(JARED),
+                chain: String::from("ETH"),
+                recipient,
+                amount,
+            },
+        )
+    }
+
+    fn lock_cash_event(principal: CashPrincipalAmount) -> ChainBlockEvent {
+        let mut recipient = [0; 32];
+        recipient[..20].copy_from_slice(&eth_address(GEOFF));
+        ChainBlockEvent::Eth(
+            1,
+            ethereum_client::EthereumEvent::LockCash {
+                sender: eth_address(JARED),
+                chain: String::from("ETH"),
+                recipient,
+                amount: 0,
+                principal: principal.0,
+            },
+        )
+    }
+
     #[test]
     fn test_lock_cash_insufficient_chain_cash() {
         new_test_ext().execute_with(|| {
@@ -149,7 +304,7 @@ mod tests {
 
             assert_eq!(
                 lock_cash_principal_internal::<Test>(GEOFF, JARED, once_principal_amount),
-                Ok(())
+                Ok(LockOutcome::Credited)
             );
 
             assert_eq!(
@@ -190,7 +345,7 @@ mod tests {
 
             assert_eq!(
                 lock_cash_principal_internal::<Test>(GEOFF, JARED, once_principal_amount),
-                Ok(())
+                Ok(LockOutcome::Credited)
             );
 
             let events_post: Vec<_> = System::events().into_iter().collect();
@@ -274,4 +429,196 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_lock_supported_asset() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            init_eth_asset()?;
+            let amount = Balance::from_nominal("1", ETH).value;
+
+            assert_ok!(lock_or_refund_internal::<Test>(Eth, JARED, GEOFF, amount));
+            assert_eq!(AssetBalances::get(Eth, GEOFF), amount);
+            assert_eq!(LatestNotice::get(ChainId::Eth), None);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_lock_unsupported_asset_refunds() {
+        new_test_ext().execute_with(|| {
+            let asset = ChainAsset::Eth([1; 20]);
+
+            assert_ok!(lock_or_refund_internal::<Test>(asset, JARED, GEOFF, 100));
+            assert_eq!(AssetBalances::get(asset, GEOFF), 0);
+
+            let (notice_id, _) = LatestNotice::get(ChainId::Eth).unwrap();
+            match Notices::get(ChainId::Eth, notice_id) {
+                Some(Notice::ExtractionNotice(ExtractionNotice::Eth {
+                    asset: notice_asset,
+                    account,
+                    amount,
+                    ..
+                })) => {
+                    assert_eq!(ChainAsset::Eth(notice_asset), asset);
+                    assert_eq!(ChainAccount::Eth(account), JARED);
+                    assert_eq!(amount, 100);
+                }
+                notice => panic!("expected a refund notice, got {:?}", notice),
+            }
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::LockRefunded(
+                    asset, JARED, GEOFF, 100
+                )))
+            );
+        });
+    }
+
+    #[test]
+    fn test_lock_during_exodus_refunds() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            init_eth_asset()?;
+            ExodusStarted::put(1000);
+            let amount = Balance::from_nominal("1", ETH).value;
+
+            assert_ok!(lock_or_refund_internal::<Test>(Eth, JARED, GEOFF, amount));
+            assert_eq!(AssetBalances::get(Eth, GEOFF), 0);
+            assert_eq!(StarportHoldings::get(Eth), 0);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::LockRefunded(
+                    Eth, JARED, GEOFF, amount
+                )))
+            );
+
+            let principal = CashPrincipalAmount::from_nominal("10");
+            assert_ok!(lock_cash_principal_internal::<Test>(
+                JARED, GEOFF, principal
+            ));
+            assert_eq!(CashPrincipals::get(GEOFF), CashPrincipal::ZERO);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::LockCashRefunded(
+                    JARED,
+                    GEOFF,
+                    principal,
+                    GlobalCashIndex::get()
+                )))
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_reorg_credited_lock_is_debited_after_exodus() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            init_eth_asset()?;
+            let amount = Balance::from_nominal("1", ETH).value;
+            let event = lock_event(Eth, amount);
+
+            assert_ok!(apply_chain_event_internal::<Test>(&event));
+            assert_eq!(AssetBalances::get(Eth, GEOFF), amount);
+
+            // the lock is debited even though it would now be refunded
+            ExodusStarted::put(1000);
+            assert_ok!(unapply_chain_event_internal::<Test>(&event));
+            assert_eq!(AssetBalances::get(Eth, GEOFF), 0);
+            assert_eq!(StarportHoldings::get(Eth), 0);
+            assert_eq!(LatestNotice::get(ChainId::Eth), None);
+            assert_eq!(LockOutcomes::iter().count(), 0);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_reorg_refunded_lock_revokes_notice() {
+        new_test_ext().execute_with(|| {
+            let asset = ChainAsset::Eth([1; 20]);
+            let event = lock_event(asset, 100);
+
+            assert_ok!(apply_chain_event_internal::<Test>(&event));
+            let (notice_id, _) = LatestNotice::get(ChainId::Eth).unwrap();
+            assert!(matches!(
+                NoticeStates::get(ChainId::Eth, notice_id),
+                NoticeState::Pending { .. }
+            ));
+
+            // the lock is not debited even though it would now be credited
+            SupportedAssets::insert(asset, AssetInfo { asset, ..eth });
+            assert_ok!(unapply_chain_event_internal::<Test>(&event));
+            assert_eq!(AssetBalances::get(asset, GEOFF), 0);
+            assert_eq!(
+                NoticeStates::get(ChainId::Eth, notice_id),
+                NoticeState::Missing
+            );
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::NoticeRevoked(
+                    ChainId::Eth,
+                    notice_id
+                )))
+            );
+            assert_eq!(LockOutcomes::iter().count(), 0);
+        });
+    }
+
+    #[test]
+    fn test_reorg_refunded_lock_revokes_notice_while_batching() {
+        new_test_ext().execute_with(|| {
+            let asset = ChainAsset::Eth([1; 20]);
+            let event = lock_event(asset, 100);
+            ExtractionBatchBudgets::insert(ChainId::Eth, 1000);
+
+            // the refund is dispatched by itself rather than batched
+            assert_ok!(apply_chain_event_internal::<Test>(&event));
+            assert_eq!(PendingBatchExtractions::get(ChainId::Eth), vec![]);
+            let (notice_id, _) = LatestNotice::get(ChainId::Eth).unwrap();
+
+            assert_ok!(unapply_chain_event_internal::<Test>(&event));
+            assert_eq!(
+                NoticeStates::get(ChainId::Eth, notice_id),
+                NoticeState::Missing
+            );
+            assert_eq!(LockOutcomes::iter().count(), 0);
+        });
+    }
+
+    #[test]
+    fn test_reorg_refunded_lock_cash_revokes_notice() {
+        new_test_ext().execute_with(|| {
+            ExodusStarted::put(1000);
+            let principal = CashPrincipalAmount::from_nominal("10");
+            let event = lock_cash_event(principal);
+
+            assert_ok!(apply_chain_event_internal::<Test>(&event));
+            let (notice_id, _) = LatestNotice::get(ChainId::Eth).unwrap();
+
+            assert_ok!(unapply_chain_event_internal::<Test>(&event));
+            assert_eq!(CashPrincipals::get(GEOFF), CashPrincipal::ZERO);
+            assert_eq!(
+                NoticeStates::get(ChainId::Eth, notice_id),
+                NoticeState::Missing
+            );
+            assert_eq!(LockOutcomes::iter().count(), 0);
+        });
+    }
+
+    #[test]
+    fn test_prune_lock_outcomes() {
+        new_test_ext().execute_with(|| {
+            let event = lock_event(ChainAsset::Eth([1; 20]), 100);
+            record_lock_outcome(&event, LockOutcome::Credited);
+
+            prune_lock_outcomes(ChainId::Eth, 1 + ATTESTATION_RETENTION_BLOCKS - 1);
+            assert_eq!(LockOutcomes::iter().count(), 1);
+            prune_lock_outcomes(ChainId::Eth, 1 + ATTESTATION_RETENTION_BLOCKS);
+            assert_eq!(LockOutcomes::iter().count(), 0);
+
+            // a lock whose outcome was pruned is left alone
+            assert_ok!(unapply_chain_event_internal::<Test>(&event));
+        });
+    }
 }
@@ -0,0 +1,765 @@
+use crate::{
+    chains::{Chain, ChainAsset, ChainId, ChainTagged, Ethereum, MaybeKnownChain, Polygon},
+    log,
+    notices::{
+        BatchExtractionNotice, CashExtractionNotice, ChangeAuthorityNotice, ExtractionNotice,
+        FutureYieldNotice, Notice, NoticeId, NoticeState, SetSupplyCapNotice,
+    },
+    params::CURRENT_STORAGE_VERSION,
+    rates::InterestRateModel,
+    symbol::Symbol,
+    types::{
+        AssetAmount, AssetInfo, Decimals, LiquidityFactor, MinerShares, ReserveFactor, Ticker,
+    },
+    Config, GlobalCashIndex, LastAccrualTimestamps, LastBlockTimestamp, LatestNotice, NoticeHashes,
+    NoticeStates, Notices, StarportHoldings, StorageVersion, SupportedAssets, TotalBorrowAssets,
+    TotalSupplyAssets,
+};
+use codec::{Decode, Encode, FullCodec};
+use frame_support::{
+    storage::{
+        migration::{put_storage_value, storage_iter, storage_key_iter, take_storage_value},
+        IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
+    },
+    weights::Weight,
+    Blake2_128Concat, StorageHasher,
+};
+use our_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+/// The storage prefix of the pallet, as declared in `decl_storage!`.
+pub const MODULE_PREFIX: &[u8] = b"Cash";
+
+/// Run each migration needed to bring storage from its stored version up to the current version.
+pub fn on_runtime_upgrade<T: Config>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+    let mut version = StorageVersion::get();
+    while version < CURRENT_STORAGE_VERSION {
+        weight = weight.saturating_add(match version {
+            0 => migrate_v0_to_v1::<T>(),
+            1 => migrate_v1_to_v2::<T>(),
+            2 => migrate_v2_to_v3::<T>(),
+            3 => migrate_v3_to_v4::<T>(),
+            4 => migrate_v4_to_v5::<T>(),
+            5 => migrate_v5_to_v6::<T>(),
+            _ => 0,
+        });
+        version += 1;
+        log!("Migrated storage to version {}", version);
+    }
+    StorageVersion::put(version);
+    weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Version 1 introduces the storage version itself, the layout of chain keys is unchanged.
+fn migrate_v0_to_v1<T: Config>() -> Weight {
+    0
+}
+
+/// Version 2 accrues asset interest lazily, starting from the last block for every supported asset.
+fn migrate_v1_to_v2<T: Config>() -> Weight {
+    let last_block_timestamp = LastBlockTimestamp::get();
+    let mut count = 0;
+    for (asset, _asset_info) in SupportedAssets::iter() {
+        LastAccrualTimestamps::insert(asset, last_block_timestamp);
+        count += 1;
+    }
+    T::DbWeight::get().reads_writes(count + 1, count)
+}
+
+/// The shape of `AssetInfo` up to version 2, before borrow caps.
+#[derive(Encode, Decode)]
+struct AssetInfoV2 {
+    asset: ChainAsset,
+    decimals: Decimals,
+    liquidity_factor: LiquidityFactor,
+    rate_model: InterestRateModel,
+    miner_shares: MinerShares,
+    supply_cap: AssetAmount,
+    symbol: Symbol,
+    ticker: Ticker,
+}
+
+/// The shape of `AssetInfo` in version 3, before reserve factors.
+#[derive(Encode, Decode)]
+struct AssetInfoV3 {
+    asset: ChainAsset,
+    decimals: Decimals,
+    liquidity_factor: LiquidityFactor,
+    rate_model: InterestRateModel,
+    miner_shares: MinerShares,
+    supply_cap: AssetAmount,
+    borrow_cap: AssetAmount,
+    symbol: Symbol,
+    ticker: Ticker,
+}
+
+/// Version 3 adds a borrow cap to every supported asset, leaving existing assets uncapped.
+fn migrate_v2_to_v3<T: Config>() -> Weight {
+    let count =
+        translate_map_values::<AssetInfoV2, AssetInfoV3>(b"SupportedAssets", |old| AssetInfoV3 {
+            asset: old.asset,
+            decimals: old.decimals,
+            liquidity_factor: old.liquidity_factor,
+            rate_model: old.rate_model,
+            miner_shares: old.miner_shares,
+            supply_cap: old.supply_cap,
+            borrow_cap: AssetAmount::MAX,
+            symbol: old.symbol,
+            ticker: old.ticker,
+        });
+    T::DbWeight::get().reads_writes(count, count)
+}
+
+/// Version 4 adds a reserve factor to every supported asset, with no reserves for existing assets.
+fn migrate_v3_to_v4<T: Config>() -> Weight {
+    let mut count = 0;
+    SupportedAssets::translate::<AssetInfoV3, _>(|_asset, old| {
+        count += 1;
+        Some(AssetInfo {
+            asset: old.asset,
+            decimals: old.decimals,
+            liquidity_factor: old.liquidity_factor,
+            rate_model: old.rate_model,
+            miner_shares: old.miner_shares,
+            reserve_factor: ReserveFactor::ZERO,
+            supply_cap: old.supply_cap,
+            borrow_cap: old.borrow_cap,
+            symbol: old.symbol,
+            ticker: old.ticker,
+        })
+    });
+    T::DbWeight::get().reads_writes(count, count)
+}
+
+/// Version 5 tracks the amount of each asset held by its starport.
+/// Until assets could be converted between chains, that was exactly what was supplied and not borrowed.
+fn migrate_v4_to_v5<T: Config>() -> Weight {
+    let mut count = 0;
+    for (asset, _asset_info) in SupportedAssets::iter() {
+        let holdings = TotalSupplyAssets::get(asset).saturating_sub(TotalBorrowAssets::get(asset));
+        StarportHoldings::insert(asset, holdings);
+        count += 1;
+    }
+    T::DbWeight::get().reads_writes(3 * count + 1, count)
+}
+
+/// The shape of `CashExtractionNotice` up to version 5, before the CASH index was included.
+#[derive(Encode, Decode)]
+enum CashExtractionNoticeV5 {
+    Eth {
+        id: NoticeId,
+        parent: <Ethereum as Chain>::Hash,
+        account: <Ethereum as Chain>::Address,
+        principal: <Ethereum as Chain>::Amount,
+    },
+    Matic {
+        id: NoticeId,
+        parent: <Polygon as Chain>::Hash,
+        account: <Polygon as Chain>::Address,
+        principal: <Polygon as Chain>::Amount,
+    },
+}
+
+/// The shape of `Notice` up to version 5.
+#[derive(Encode, Decode)]
+enum NoticeV5 {
+    ExtractionNotice(ExtractionNotice),
+    CashExtractionNotice(CashExtractionNoticeV5),
+    FutureYieldNotice(FutureYieldNotice),
+    SetSupplyCapNotice(SetSupplyCapNotice),
+    ChangeAuthorityNotice(ChangeAuthorityNotice),
+    BatchExtractionNotice(BatchExtractionNotice),
+}
+
+impl NoticeV5 {
+    fn with_index(self, index: u128) -> Notice {
+        match self {
+            NoticeV5::ExtractionNotice(n) => Notice::ExtractionNotice(n),
+            NoticeV5::CashExtractionNotice(CashExtractionNoticeV5::Eth {
+                id,
+                parent,
+                account,
+                principal,
+            }) => Notice::CashExtractionNotice(CashExtractionNotice::Eth {
+                id,
+                parent,
+                account,
+                principal,
+                index,
+            }),
+            NoticeV5::CashExtractionNotice(CashExtractionNoticeV5::Matic {
+                id,
+                parent,
+                account,
+                principal,
+            }) => Notice::CashExtractionNotice(CashExtractionNotice::Matic {
+                id,
+                parent,
+                account,
+                principal,
+                index,
+            }),
+            NoticeV5::FutureYieldNotice(n) => Notice::FutureYieldNotice(n),
+            NoticeV5::SetSupplyCapNotice(n) => Notice::SetSupplyCapNotice(n),
+            NoticeV5::ChangeAuthorityNotice(n) => Notice::ChangeAuthorityNotice(n),
+            NoticeV5::BatchExtractionNotice(n) => Notice::BatchExtractionNotice(n),
+        }
+    }
+}
+
+/// Version 6 includes the CASH index in cash extraction notices.
+/// The index at the time of an earlier extraction is not kept, the current index is the closest to it.
+/// Starports only execute cash extraction notices with the index, so pending ones get a new hash and are signed again.
+fn migrate_v5_to_v6<T: Config>() -> Weight {
+    let index = GlobalCashIndex::get().0;
+    let count = translate_map_values::<NoticeV5, Notice>(b"Notices", |old| old.with_index(index));
+
+    let pending: Vec<(ChainId, NoticeId, Notice)> = Notices::iter()
+        .filter(|(chain_id, notice_id, notice)| match notice {
+            Notice::CashExtractionNotice(_) => match NoticeStates::get(chain_id, notice_id) {
+                NoticeState::Pending { .. } => true,
+                _ => false,
+            },
+            _ => false,
+        })
+        .collect();
+    let pending_ids: BTreeSet<(ChainId, NoticeId)> = pending
+        .iter()
+        .map(|(chain_id, notice_id, _notice)| (*chain_id, *notice_id))
+        .collect();
+    let old_hashes: Vec<_> = NoticeHashes::iter()
+        .filter(|(hash, notice_id)| pending_ids.contains(&(hash.chain_id(), *notice_id)))
+        .collect();
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    for (old_hash, _notice_id) in old_hashes.iter() {
+        NoticeHashes::remove(old_hash);
+    }
+    for (chain_id, notice_id, notice) in pending.iter() {
+        let hash = notice.hash();
+        NoticeHashes::insert(hash, notice_id);
+        NoticeStates::insert(chain_id, notice_id, NoticeState::pending(notice));
+        if let Some((latest_id, _old_hash)) = LatestNotice::get(chain_id) {
+            if latest_id == *notice_id {
+                LatestNotice::insert(chain_id, (*notice_id, hash));
+            }
+        }
+    }
+
+    let updated = pending.len() as u64;
+    T::DbWeight::get().reads_writes(
+        2 * count + old_hashes.len() as u64 + updated + 1,
+        count + old_hashes.len() as u64 + 3 * updated,
+    )
+}
+
+/// Re-encode the values of a `blake2_128_concat` map in place, e.g. when the shape of a stored type changes.
+/// Entries whose old value fails to decode are left out by the storage iterator, and so left in place.
+/// Returns the number of entries which were translated.
+pub fn translate_map_values<V0: Decode, V1: Encode>(
+    item: &[u8],
+    translate: impl Fn(V0) -> V1,
+) -> u64 {
+    let entries: Vec<(Vec<u8>, V0)> = storage_iter::<V0>(MODULE_PREFIX, item).collect();
+    let mut translated = 0;
+    for (hashed_key, value) in entries {
+        put_storage_value(MODULE_PREFIX, item, &hashed_key, translate(value));
+        translated += 1;
+    }
+    translated
+}
+
+/// Put each moved entry under its new key, once every old entry has been taken.
+/// A new key may be the old key of another entry, e.g. when variants are reordered, so no entry is put before all are taken.
+/// Returns the number of entries which were moved.
+fn put_moved_entries<V: Encode>(item: &[u8], moved: Vec<(Vec<u8>, V)>) -> u64 {
+    let count = moved.len() as u64;
+    for (new_hashed_key, value) in moved {
+        put_storage_value(MODULE_PREFIX, item, &new_hashed_key, value);
+    }
+    count
+}
+
+/// Re-encode the keys of a `blake2_128_concat` map, e.g. when the shape of `ChainAccount` or `ChainAsset` changes.
+/// Entries whose old key fails to decode are left in place.
+/// Returns the number of entries which were moved.
+pub fn translate_map_keys<K0: Decode, K1: Encode, V: FullCodec>(
+    item: &[u8],
+    translate: impl Fn(K0) -> K1,
+) -> u64 {
+    let entries: Vec<(Vec<u8>, V)> = storage_iter::<V>(MODULE_PREFIX, item).collect();
+    let mut moved = Vec::new();
+    for (hashed_key, value) in entries {
+        let mut raw_key = &hashed_key[16..];
+        match K0::decode(&mut raw_key) {
+            Ok(old_key) => {
+                let new_hashed_key = Blake2_128Concat::hash(&translate(old_key).encode());
+                take_storage_value::<V>(MODULE_PREFIX, item, &hashed_key);
+                moved.push((new_hashed_key, value));
+            }
+            Err(_) => {
+                log!("Could not decode key for migration {:?}", hashed_key);
+            }
+        }
+    }
+    put_moved_entries(item, moved)
+}
+
+/// Re-encode the keys of a `blake2_128_concat` double map, e.g. when the shape of `ChainAccount` or `ChainAsset` changes.
+/// Entries whose old keys fail to decode are left in place.
+/// Returns the number of entries which were moved.
+pub fn translate_double_map_keys<J0: Decode, K0: Decode, J1: Encode, K1: Encode, V: FullCodec>(
+    item: &[u8],
+    translate: impl Fn(J0, K0) -> (J1, K1),
+) -> u64 {
+    let entries: Vec<(Vec<u8>, V)> = storage_iter::<V>(MODULE_PREFIX, item).collect();
+    let mut moved = Vec::new();
+    for (hashed_key, value) in entries {
+        let mut raw_key = &hashed_key[16..];
+        let old_keys = J0::decode(&mut raw_key).and_then(|key1| {
+            let mut raw_key2 = raw_key.get(16..).unwrap_or(&[]);
+            K0::decode(&mut raw_key2).map(|key2| (key1, key2))
+        });
+        match old_keys {
+            Ok((old_key1, old_key2)) => {
+                let (new_key1, new_key2) = translate(old_key1, old_key2);
+                let new_hashed_key = [
+                    Blake2_128Concat::hash(&new_key1.encode()),
+                    Blake2_128Concat::hash(&new_key2.encode()),
+                ]
+                .concat();
+                take_storage_value::<V>(MODULE_PREFIX, item, &hashed_key);
+                moved.push((new_hashed_key, value));
+            }
+            Err(_) => {
+                log!("Could not decode keys for migration {:?}", hashed_key);
+            }
+        }
+    }
+    put_moved_entries(item, moved)
+}
+
+/// Iterate the entries of a `blake2_128_concat` map keyed by a chain, or by an account or asset tied to one.
+/// Unlike the iterator of the map itself, which skips keys it cannot decode,
+///  entries tied to chains this runtime does not know are kept as unknown.
+pub fn iter_chain_keyed_map<K: ChainTagged, V: Decode>(
+    item: &[u8],
+) -> impl Iterator<Item = (MaybeKnownChain<K>, V)> {
+    storage_key_iter::<MaybeKnownChain<K>, V, Blake2_128Concat>(MODULE_PREFIX, item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chains::{ChainAccount, ChainAsset},
+        tests::*,
+        types::{AssetBalance, CashPrincipal},
+        AssetBalances, CashPrincipals,
+    };
+    use frame_support::storage::{migration::get_storage_value, StorageDoubleMap, StorageMap};
+
+    /// A pre-upgrade shape of `ChainAccount`, with fewer variants in a different order.
+    #[derive(Encode, Decode)]
+    enum OldChainAccount {
+        Eth([u8; 20]),
+        Gate([u8; 32]),
+    }
+
+    impl From<OldChainAccount> for ChainAccount {
+        fn from(account: OldChainAccount) -> Self {
+            match account {
+                OldChainAccount::Eth(address) => ChainAccount::Eth(address),
+                OldChainAccount::Gate(address) => ChainAccount::Gate(address),
+            }
+        }
+    }
+
+    /// A pre-upgrade shape of `ChainAsset`, with fewer variants.
+    #[derive(Encode, Decode)]
+    enum OldChainAsset {
+        Eth([u8; 20]),
+    }
+
+    impl From<OldChainAsset> for ChainAsset {
+        fn from(asset: OldChainAsset) -> Self {
+            match asset {
+                OldChainAsset::Eth(address) => ChainAsset::Eth(address),
+            }
+        }
+    }
+
+    /// A key whose variants are swapped by an upgrade, so that each new key is the old key of another entry.
+    #[derive(Encode, Decode)]
+    enum OldSide {
+        Left(u8),
+        Right(u8),
+    }
+
+    #[derive(Encode, Decode)]
+    enum NewSide {
+        Right(u8),
+        Left(u8),
+    }
+
+    impl From<OldSide> for NewSide {
+        fn from(side: OldSide) -> Self {
+            match side {
+                OldSide::Left(x) => NewSide::Left(x),
+                OldSide::Right(x) => NewSide::Right(x),
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_key_encodings_are_stable() {
+        // These fixtures pin the encoding of chain keys already in storage,
+        //  new variants may only be added at the end without a migration.
+        assert_eq!(
+            ChainAccount::decode(&mut &hex!("010101010101010101010101010101010101010101")[..]),
+            Ok(ChainAccount::Eth([1u8; 20]))
+        );
+        assert_eq!(
+            ChainAccount::decode(&mut &hex!("030202020202020202020202020202020202020202")[..]),
+            Ok(ChainAccount::Matic([2u8; 20]))
+        );
+        assert_eq!(
+            ChainAsset::decode(&mut &hex!("01eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee")[..]),
+            Ok(ChainAsset::Eth([0xee; 20]))
+        );
+    }
+
+    #[test]
+    fn test_on_runtime_upgrade_sets_version() {
+        new_test_ext().execute_with(|| {
+            StorageVersion::kill();
+            on_runtime_upgrade::<Test>();
+            assert_eq!(StorageVersion::get(), CURRENT_STORAGE_VERSION);
+        });
+    }
+
+    #[test]
+    fn test_migrate_v1_to_v2() {
+        new_test_ext().execute_with(|| {
+            StorageVersion::put(1);
+            LastBlockTimestamp::put(1000);
+            SupportedAssets::insert(&Eth, AssetInfo::minimal(Eth, ETH));
+            on_runtime_upgrade::<Test>();
+            assert_eq!(LastAccrualTimestamps::get(&Eth), 1000);
+            assert_eq!(StorageVersion::get(), CURRENT_STORAGE_VERSION);
+        });
+    }
+
+    #[test]
+    fn test_migrate_v2_to_v3() {
+        new_test_ext().execute_with(|| {
+            StorageVersion::put(2);
+            let old = AssetInfoV2 {
+                asset: Eth,
+                decimals: eth.decimals,
+                liquidity_factor: eth.liquidity_factor,
+                rate_model: eth.rate_model,
+                miner_shares: eth.miner_shares,
+                supply_cap: eth.supply_cap,
+                symbol: eth.symbol,
+                ticker: eth.ticker,
+            };
+            put_storage_value(
+                MODULE_PREFIX,
+                b"SupportedAssets",
+                &Blake2_128Concat::hash(&Eth.encode()),
+                old,
+            );
+            on_runtime_upgrade::<Test>();
+            assert_eq!(
+                SupportedAssets::get(&Eth),
+                Some(AssetInfo {
+                    borrow_cap: AssetAmount::MAX,
+                    ..eth
+                })
+            );
+            assert_eq!(StorageVersion::get(), CURRENT_STORAGE_VERSION);
+        });
+    }
+
+    #[test]
+    fn test_migrate_v3_to_v4() {
+        new_test_ext().execute_with(|| {
+            StorageVersion::put(3);
+            let old = AssetInfoV3 {
+                asset: Eth,
+                decimals: eth.decimals,
+                liquidity_factor: eth.liquidity_factor,
+                rate_model: eth.rate_model,
+                miner_shares: eth.miner_shares,
+                supply_cap: eth.supply_cap,
+                borrow_cap: 1000,
+                symbol: eth.symbol,
+                ticker: eth.ticker,
+            };
+            put_storage_value(
+                MODULE_PREFIX,
+                b"SupportedAssets",
+                &Blake2_128Concat::hash(&Eth.encode()),
+                old,
+            );
+            on_runtime_upgrade::<Test>();
+            assert_eq!(
+                SupportedAssets::get(&Eth),
+                Some(AssetInfo {
+                    reserve_factor: ReserveFactor::ZERO,
+                    borrow_cap: 1000,
+                    ..eth
+                })
+            );
+            assert_eq!(StorageVersion::get(), CURRENT_STORAGE_VERSION);
+        });
+    }
+
+    #[test]
+    fn test_migrate_v4_to_v5() {
+        new_test_ext().execute_with(|| {
+            StorageVersion::put(4);
+            SupportedAssets::insert(&Eth, eth);
+            TotalSupplyAssets::insert(&Eth, 300);
+            TotalBorrowAssets::insert(&Eth, 120);
+            on_runtime_upgrade::<Test>();
+            assert_eq!(StarportHoldings::get(&Eth), 180);
+            assert_eq!(StorageVersion::get(), CURRENT_STORAGE_VERSION);
+        });
+    }
+
+    #[test]
+    fn test_migrate_v5_to_v6() {
+        new_test_ext().execute_with(|| {
+            StorageVersion::put(5);
+            GlobalCashIndex::put(CashIndex::from_nominal("1.5"));
+            let hashed = |notice_id: NoticeId| {
+                [
+                    Blake2_128Concat::hash(&ChainId::Eth.encode()),
+                    Blake2_128Concat::hash(&notice_id.encode()),
+                ]
+                .concat()
+            };
+            let signed = NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![([0x55; 20], [0x66; 65])].try_into().unwrap(),
+                ),
+            };
+
+            // A pending cash extraction, the latest notice, and an executed one
+            let old_hash = ChainHash::Eth([0x77; 32]);
+            put_storage_value(
+                MODULE_PREFIX,
+                b"Notices",
+                &hashed(NoticeId(0, 1)),
+                NoticeV5::CashExtractionNotice(CashExtractionNoticeV5::Eth {
+                    id: NoticeId(0, 1),
+                    parent: [0x11; 32],
+                    account: [0x33; 20],
+                    principal: 500,
+                }),
+            );
+            NoticeStates::insert(ChainId::Eth, NoticeId(0, 1), signed.clone());
+            NoticeHashes::insert(old_hash, NoticeId(0, 1));
+            LatestNotice::insert(ChainId::Eth, (NoticeId(0, 1), old_hash));
+            put_storage_value(
+                MODULE_PREFIX,
+                b"Notices",
+                &hashed(NoticeId(0, 0)),
+                NoticeV5::CashExtractionNotice(CashExtractionNoticeV5::Eth {
+                    id: NoticeId(0, 0),
+                    parent: [0x00; 32],
+                    account: [0x33; 20],
+                    principal: 100,
+                }),
+            );
+            NoticeStates::insert(ChainId::Eth, NoticeId(0, 0), NoticeState::Executed);
+            NoticeHashes::insert(ChainHash::Eth([0x11; 32]), NoticeId(0, 0));
+
+            on_runtime_upgrade::<Test>();
+
+            let notice = Notice::CashExtractionNotice(CashExtractionNotice::Eth {
+                id: NoticeId(0, 1),
+                parent: [0x11; 32],
+                account: [0x33; 20],
+                principal: 500,
+                index: CashIndex::from_nominal("1.5").0,
+            });
+            assert_eq!(
+                Notices::get(ChainId::Eth, NoticeId(0, 1)),
+                Some(notice.clone())
+            );
+            assert_eq!(
+                NoticeStates::get(ChainId::Eth, NoticeId(0, 1)),
+                NoticeState::pending(&notice)
+            );
+            assert_eq!(NoticeHashes::get(old_hash), None);
+            assert_eq!(NoticeHashes::get(notice.hash()), Some(NoticeId(0, 1)));
+            assert_eq!(
+                LatestNotice::get(ChainId::Eth),
+                Some((NoticeId(0, 1), notice.hash()))
+            );
+
+            // Executed notices keep the hash they were executed with
+            assert_eq!(
+                Notices::get(ChainId::Eth, NoticeId(0, 0)),
+                Some(Notice::CashExtractionNotice(CashExtractionNotice::Eth {
+                    id: NoticeId(0, 0),
+                    parent: [0x00; 32],
+                    account: [0x33; 20],
+                    principal: 100,
+                    index: CashIndex::from_nominal("1.5").0,
+                }))
+            );
+            assert_eq!(
+                NoticeStates::get(ChainId::Eth, NoticeId(0, 0)),
+                NoticeState::Executed
+            );
+            assert_eq!(
+                NoticeHashes::get(ChainHash::Eth([0x11; 32])),
+                Some(NoticeId(0, 0))
+            );
+            assert_eq!(StorageVersion::get(), CURRENT_STORAGE_VERSION);
+        });
+    }
+
+    #[test]
+    fn test_translate_map_keys() {
+        new_test_ext().execute_with(|| {
+            // Pre-upgrade fixture: `OldChainAccount::Gate` encoded at index 1 (now `Eth`)
+            let old_key = OldChainAccount::Gate([3u8; 32]).encode();
+            put_storage_value(
+                MODULE_PREFIX,
+                b"CashPrincipals",
+                &Blake2_128Concat::hash(&old_key),
+                CashPrincipal(100),
+            );
+
+            let moved = translate_map_keys::<OldChainAccount, ChainAccount, CashPrincipal>(
+                b"CashPrincipals",
+                ChainAccount::from,
+            );
+
+            assert_eq!(moved, 1);
+            assert_eq!(
+                CashPrincipals::get(ChainAccount::Gate([3u8; 32])),
+                CashPrincipal(100)
+            );
+            assert_eq!(CashPrincipals::iter().count(), 1);
+        });
+    }
+
+    #[test]
+    fn test_translate_double_map_keys() {
+        new_test_ext().execute_with(|| {
+            let old_asset = OldChainAsset::Eth([4u8; 20]).encode();
+            let old_account = OldChainAccount::Gate([5u8; 32]).encode();
+            put_storage_value(
+                MODULE_PREFIX,
+                b"AssetBalances",
+                &[
+                    Blake2_128Concat::hash(&old_asset),
+                    Blake2_128Concat::hash(&old_account),
+                ]
+                .concat(),
+                -7 as AssetBalance,
+            );
+
+            let moved = translate_double_map_keys::<
+                OldChainAsset,
+                OldChainAccount,
+                ChainAsset,
+                ChainAccount,
+                AssetBalance,
+            >(b"AssetBalances", |asset, account| {
+                (asset.into(), account.into())
+            });
+
+            assert_eq!(moved, 1);
+            assert_eq!(
+                AssetBalances::get(ChainAsset::Eth([4u8; 20]), ChainAccount::Gate([5u8; 32])),
+                -7
+            );
+        });
+    }
+
+    #[test]
+    fn test_translate_map_keys_swapped_variants() {
+        new_test_ext().execute_with(|| {
+            let item = b"SwappedKeys";
+            let hashed = |key: Vec<u8>| Blake2_128Concat::hash(&key);
+            put_storage_value(
+                MODULE_PREFIX,
+                item,
+                &hashed(OldSide::Left(1).encode()),
+                10u32,
+            );
+            put_storage_value(
+                MODULE_PREFIX,
+                item,
+                &hashed(OldSide::Right(1).encode()),
+                20u32,
+            );
+
+            let moved = translate_map_keys::<OldSide, NewSide, u32>(item, NewSide::from);
+
+            assert_eq!(moved, 2);
+            assert_eq!(
+                get_storage_value::<u32>(MODULE_PREFIX, item, &hashed(NewSide::Left(1).encode())),
+                Some(10)
+            );
+            assert_eq!(
+                get_storage_value::<u32>(MODULE_PREFIX, item, &hashed(NewSide::Right(1).encode())),
+                Some(20)
+            );
+        });
+    }
+
+    #[test]
+    fn test_translate_double_map_keys_swapped_variants() {
+        new_test_ext().execute_with(|| {
+            let item = b"SwappedDoubleKeys";
+            let hashed = |key1: Vec<u8>, key2: Vec<u8>| {
+                [Blake2_128Concat::hash(&key1), Blake2_128Concat::hash(&key2)].concat()
+            };
+            let key2 = 7u8.encode();
+            put_storage_value(
+                MODULE_PREFIX,
+                item,
+                &hashed(OldSide::Left(1).encode(), key2.clone()),
+                10u32,
+            );
+            put_storage_value(
+                MODULE_PREFIX,
+                item,
+                &hashed(OldSide::Right(1).encode(), key2.clone()),
+                20u32,
+            );
+
+            let moved =
+                translate_double_map_keys::<OldSide, u8, NewSide, u8, u32>(item, |side, x| {
+                    (side.into(), x)
+                });
+
+            assert_eq!(moved, 2);
+            assert_eq!(
+                get_storage_value::<u32>(
+                    MODULE_PREFIX,
+                    item,
+                    &hashed(NewSide::Left(1).encode(), key2.clone())
+                ),
+                Some(10)
+            );
+            assert_eq!(
+                get_storage_value::<u32>(
+                    MODULE_PREFIX,
+                    item,
+                    &hashed(NewSide::Right(1).encode(), key2)
+                ),
+                Some(20)
+            );
+        });
+    }
+}
@@ -1,16 +1,38 @@
+pub mod account_history;
+pub mod accrual;
+pub mod anchors;
 pub mod assets;
 pub mod balance_helpers;
+pub mod borrow_cap;
+pub mod chain_status;
 pub mod change_validators;
+pub mod convert;
+pub mod equivocation;
 pub mod events;
 pub mod exec_trx_request;
+pub mod execution_fees;
+pub mod exodus;
 pub mod extract;
+pub mod extraction_limit;
+pub mod fees;
+pub mod governance;
+pub mod holdings;
 pub mod initialize;
 pub mod liquidate;
+pub mod liveness;
 pub mod lock;
+pub mod migrations;
 pub mod miner;
 pub mod next_code;
 pub mod notices;
+pub mod peg;
+pub mod rate_history;
+pub mod recovery;
+pub mod reserves;
 pub mod set_yield_next;
+pub mod solvency;
 pub mod supply_cap;
 pub mod transfer;
 pub mod validate_trx;
+pub mod validator_metadata;
+pub mod work;
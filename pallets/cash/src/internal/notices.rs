@@ -1,27 +1,50 @@
 use crate::{
     chains::{ChainAccount, ChainAsset, ChainHash, ChainId, ChainSignature},
     core::recover_validator,
-    log,
+    internal, log,
+    metrics::{self, NOTICES_SIGNED, SUBMISSION_FAILURES},
     notices::{
-        CashExtractionNotice, ChangeAuthorityNotice, EncodeNotice, ExtractionNotice,
-        FutureYieldNotice, Notice, NoticeId, NoticeState, SetSupplyCapNotice,
+        BatchExtractionNotice, CashExtractionNotice, ChangeAuthorityNotice, EncodeNotice,
+        ExtractionNotice, FutureYieldNotice, Notice, NoticeId, NoticeState, SetSupplyCapNotice,
     },
+    params::{MAX_BATCHED_EXTRACTIONS, MAX_PRUNE_NOTICES},
     require,
-    types::{
-        AssetAmount, AssetQuantity, CashIndex, CashPrincipalAmount, Reason, Timestamp,
-        ValidatorKeys, APR,
-    },
-    AccountNotices, Call, Config, Event, LatestNotice, Module, NoticeHashes, NoticeHolds,
-    NoticeStates, Notices,
+    types::{AssetAmount, CashIndex, CashPrincipalAmount, Reason, Timestamp, ValidatorKeys, APR},
+    AccountNotices, Call, Config, Event, ExecutedNoticeRange, ExecutedNotices,
+    ExtractionBatchBudgets, ExtractionBatches, LatestNotice, Module, NoticeHashes, NoticeHolds,
+    NoticeRetentionEras, NoticeStates, Notices, PendingBatchExtractions, PrunedNoticeFilters,
+    Validators,
+};
+use frame_support::storage::{
+    IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
 };
-use frame_support::storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageMap};
 use frame_system::offchain::SubmitTransaction;
 
+/// Dispatch a notice extracting an asset to the recipient, returning its id.
+/// Returns none if the extraction was left pending for the next batch instead.
 pub fn dispatch_extraction_notice<T: Config>(
     asset: ChainAsset,
     recipient: ChainAccount,
-    amount: AssetQuantity,
-) {
+    amount: AssetAmount,
+) -> Option<NoticeId> {
+    let chain_id = recipient.chain_id();
+    if ExtractionBatchBudgets::get(chain_id).is_some() {
+        PendingBatchExtractions::append(chain_id, (asset, recipient, amount));
+        return None;
+    }
+
+    Some(dispatch_unbatched_extraction_notice::<T>(
+        asset, recipient, amount,
+    ))
+}
+
+/// Dispatch a notice extracting an asset to the recipient by itself, even if its chain batches extractions.
+/// Used for refunds, which must remain revocable on their own if the lock they refund is reorganized away.
+pub fn dispatch_unbatched_extraction_notice<T: Config>(
+    asset: ChainAsset,
+    recipient: ChainAccount,
+    amount: AssetAmount,
+) -> NoticeId {
     dispatch_notice::<T>(
         recipient.chain_id(),
         Some(recipient),
@@ -37,7 +60,7 @@ pub fn dispatch_extraction_notice<T: Config>(
                     parent: eth_parent_hash,
                     asset: eth_asset,
                     account: eth_account,
-                    amount: amount.value,
+                    amount: amount,
                 },
                 (
                     ChainAsset::Matic(eth_asset),
@@ -48,7 +71,19 @@ pub fn dispatch_extraction_notice<T: Config>(
                     parent: eth_parent_hash,
                     asset: eth_asset,
                     account: eth_account,
-                    amount: amount.value,
+                    amount: amount,
+                },
+                #[cfg(feature = "runtime-dev")]
+                (
+                    ChainAsset::Mock(mock_asset),
+                    ChainAccount::Mock(mock_account),
+                    ChainHash::Mock(mock_parent_hash),
+                ) => ExtractionNotice::Mock {
+                    id: notice_id,
+                    parent: mock_parent_hash,
+                    asset: mock_asset,
+                    account: mock_account,
+                    amount: amount,
                 },
 
                 _ => panic!("XXX not implemented"), // generate these w/ macros?
@@ -57,10 +92,114 @@ pub fn dispatch_extraction_notice<T: Config>(
     )
 }
 
+/// Set (or remove) the maximum encoded length of a batch of extraction notices for a chain.
+/// Extractions on the chain are dispatched in batches at the start of the next block while a budget is set.
+pub fn set_extraction_batch_budget<T: Config>(
+    chain_id: ChainId,
+    budget: Option<u32>,
+) -> Result<(), Reason> {
+    require!(
+        chain_id == ChainId::Eth || chain_id == ChainId::Matic,
+        Reason::InvalidChain
+    );
+
+    match budget {
+        Some(budget) => ExtractionBatchBudgets::insert(chain_id, budget),
+        None => ExtractionBatchBudgets::remove(chain_id),
+    }
+
+    <Module<T>>::deposit_event(Event::SetExtractionBatchBudget(chain_id, budget));
+
+    Ok(())
+}
+
+/// Dispatch the pending extractions for each chain, packed into as few notices as fit within its budget.
+/// A single extraction which exceeds the budget on its own is still dispatched, in a batch by itself.
+/// At most a bounded number of extractions are dispatched per block, the rest wait for the next.
+/// Extractions to a chain which is not active wait until it is again.
+/// Called when a block is initialized, and thus may not fail.
+pub fn dispatch_extraction_batches<T: Config>() {
+    let mut remaining = MAX_BATCHED_EXTRACTIONS as usize;
+    for (chain_id, mut pending) in PendingBatchExtractions::iter().collect::<Vec<_>>() {
+        if remaining == 0 {
+            break;
+        }
+        if !internal::chain_status::is_active(chain_id) {
+            continue;
+        }
+        let count = pending.len().min(remaining);
+        let rest = pending.split_off(count);
+        remaining -= count;
+        if rest.is_empty() {
+            PendingBatchExtractions::remove(chain_id);
+        } else {
+            PendingBatchExtractions::insert(chain_id, rest);
+        }
+
+        let budget = ExtractionBatchBudgets::get(chain_id).unwrap_or(0) as usize;
+        let mut batch = vec![];
+        for extraction in pending {
+            if !batch.is_empty() && BatchExtractionNotice::encoded_len(batch.len() + 1) > budget {
+                dispatch_extraction_batch::<T>(chain_id, batch);
+                batch = vec![];
+            }
+            batch.push(extraction);
+        }
+        if !batch.is_empty() {
+            dispatch_extraction_batch::<T>(chain_id, batch);
+        }
+    }
+}
+
+/// Dispatch a single batch notice, remembering which extractions it covers until it executes.
+fn dispatch_extraction_batch<T: Config>(
+    chain_id: ChainId,
+    extractions: Vec<(ChainAsset, ChainAccount, AssetAmount)>,
+) {
+    let notice_id = dispatch_notice::<T>(chain_id, None, false, &|notice_id, parent_hash| {
+        Notice::BatchExtractionNotice(match parent_hash {
+            ChainHash::Eth(eth_parent_hash) => BatchExtractionNotice::Eth {
+                id: notice_id,
+                parent: eth_parent_hash,
+                extractions: extractions
+                    .iter()
+                    .map(|(asset, account, amount)| match (asset, account) {
+                        (ChainAsset::Eth(eth_asset), ChainAccount::Eth(eth_account)) => {
+                            (*eth_asset, *eth_account, *amount)
+                        }
+                        _ => panic!("XXX not implemented"),
+                    })
+                    .collect(),
+            },
+            ChainHash::Matic(eth_parent_hash) => BatchExtractionNotice::Matic {
+                id: notice_id,
+                parent: eth_parent_hash,
+                extractions: extractions
+                    .iter()
+                    .map(|(asset, account, amount)| match (asset, account) {
+                        (ChainAsset::Matic(eth_asset), ChainAccount::Matic(eth_account)) => {
+                            (*eth_asset, *eth_account, *amount)
+                        }
+                        _ => panic!("XXX not implemented"),
+                    })
+                    .collect(),
+            },
+
+            _ => panic!("XXX not implemented"), // generate these w/ macros?
+        })
+    });
+
+    for (_, recipient, _) in extractions.iter() {
+        AccountNotices::append(recipient, notice_id);
+    }
+    ExtractionBatches::insert(chain_id, notice_id, extractions);
+}
+
 pub fn dispatch_cash_extraction_notice<T: Config>(
     recipient: ChainAccount,
     principal: CashPrincipalAmount,
-) {
+    index: CashIndex,
+) -> NoticeId {
     dispatch_notice::<T>(
         recipient.chain_id(),
         Some(recipient),
@@ -73,6 +212,7 @@ pub fn dispatch_cash_extraction_notice<T: Config>(
                         parent: eth_parent_hash,
                         account: eth_account,
                         principal: principal.0,
+                        index: index.0,
                     }
                 }
                 (ChainAccount::Matic(eth_account), ChainHash::Matic(eth_parent_hash)) => {
@@ -81,6 +221,17 @@ pub fn dispatch_cash_extraction_notice<T: Config>(
                         parent: eth_parent_hash,
                         account: eth_account,
                         principal: principal.0,
+                        index: index.0,
+                    }
+                }
+                #[cfg(feature = "runtime-dev")]
+                (ChainAccount::Mock(mock_account), ChainHash::Mock(mock_parent_hash)) => {
+                    CashExtractionNotice::Mock {
+                        id: notice_id,
+                        parent: mock_parent_hash,
+                        account: mock_account,
+                        principal: principal.0,
+                        index: index.0,
                     }
                 }
 
@@ -117,9 +268,10 @@ pub fn dispatch_supply_cap_notice<T: Config>(chain_asset: ChainAsset, cap: Asset
                 _ => panic!("XXX not implemented"), // generate these w/ macros?
             })
         },
-    )
+    );
 }
 
+/// Dispatch the next CASH yield to each chain, except those which are not active.
 pub fn dispatch_future_yield_notice<T: Config>(
     next_yield: APR,
     next_yield_index: CashIndex,
@@ -127,63 +279,73 @@ pub fn dispatch_future_yield_notice<T: Config>(
 ) {
     // XXX for each chain id
     let chain_id = ChainId::Eth;
-    dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
-        Notice::FutureYieldNotice(match parent_hash {
-            ChainHash::Eth(eth_parent_hash) => FutureYieldNotice::Eth {
-                id: notice_id,
-                parent: eth_parent_hash,
-                next_cash_yield: next_yield.0,
-                next_cash_index: next_yield_index.0,
-                next_cash_yield_start: next_yield_start,
-            },
+    if internal::chain_status::is_active(chain_id) {
+        dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
+            Notice::FutureYieldNotice(match parent_hash {
+                ChainHash::Eth(eth_parent_hash) => FutureYieldNotice::Eth {
+                    id: notice_id,
+                    parent: eth_parent_hash,
+                    next_cash_yield: next_yield.0,
+                    next_cash_index: next_yield_index.0,
+                    next_cash_yield_start: next_yield_start,
+                },
 
-            _ => panic!("XXX not implemented"), // generate these w/ macros?
-        })
-    });
+                _ => panic!("XXX not implemented"), // generate these w/ macros?
+            })
+        });
+    }
 
     let chain_id = ChainId::Matic;
-    dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
-        Notice::FutureYieldNotice(match parent_hash {
-            ChainHash::Matic(eth_parent_hash) => FutureYieldNotice::Matic {
-                id: notice_id,
-                parent: eth_parent_hash,
-                next_cash_yield: next_yield.0,
-                next_cash_index: next_yield_index.0,
-                next_cash_yield_start: next_yield_start,
-            },
+    if internal::chain_status::is_active(chain_id) {
+        dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
+            Notice::FutureYieldNotice(match parent_hash {
+                ChainHash::Matic(eth_parent_hash) => FutureYieldNotice::Matic {
+                    id: notice_id,
+                    parent: eth_parent_hash,
+                    next_cash_yield: next_yield.0,
+                    next_cash_index: next_yield_index.0,
+                    next_cash_yield_start: next_yield_start,
+                },
 
-            _ => panic!("XXX not implemented"), // generate these w/ macros?
-        })
-    })
+                _ => panic!("XXX not implemented"), // generate these w/ macros?
+            })
+        });
+    }
 }
 
+/// Dispatch the new authorities to each chain, except those which are not active.
+/// A chain which is not active does not hold up the change, and keeps its old authorities until updated.
 pub fn dispatch_change_authority_notice<T: Config>(validators: Vec<ValidatorKeys>) {
     // XXX for each chain id
     let chain_id = ChainId::Eth;
-    dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
-        Notice::ChangeAuthorityNotice(match parent_hash {
-            ChainHash::Eth(eth_parent_hash) => ChangeAuthorityNotice::Eth {
-                id: notice_id,
-                parent: eth_parent_hash,
-                new_authorities: validators.iter().map(|x| x.eth_address).collect::<Vec<_>>(),
-            },
+    if internal::chain_status::is_active(chain_id) {
+        dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
+            Notice::ChangeAuthorityNotice(match parent_hash {
+                ChainHash::Eth(eth_parent_hash) => ChangeAuthorityNotice::Eth {
+                    id: notice_id,
+                    parent: eth_parent_hash,
+                    new_authorities: validators.iter().map(|x| x.eth_address).collect::<Vec<_>>(),
+                },
 
-            _ => panic!("XXX not implemented"), // generate these w/ macros?
-        })
-    });
+                _ => panic!("XXX not implemented"), // generate these w/ macros?
+            })
+        });
+    }
 
     let chain_id = ChainId::Matic;
-    dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
-        Notice::ChangeAuthorityNotice(match parent_hash {
-            ChainHash::Matic(eth_parent_hash) => ChangeAuthorityNotice::Matic {
-                id: notice_id,
-                parent: eth_parent_hash,
-                new_authorities: validators.iter().map(|x| x.eth_address).collect::<Vec<_>>(),
-            },
+    if internal::chain_status::is_active(chain_id) {
+        dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
+            Notice::ChangeAuthorityNotice(match parent_hash {
+                ChainHash::Matic(eth_parent_hash) => ChangeAuthorityNotice::Matic {
+                    id: notice_id,
+                    parent: eth_parent_hash,
+                    new_authorities: validators.iter().map(|x| x.eth_address).collect::<Vec<_>>(),
+                },
 
-            _ => panic!("XXX not implemented"), // generate these w/ macros?
-        })
-    });
+                _ => panic!("XXX not implemented"), // generate these w/ macros?
+            })
+        });
+    }
 }
 
 /// Add a notice to the queue and all the secondary indices.
@@ -193,15 +355,17 @@ fn dispatch_notice<T: Config>(
     maybe_recipient: Option<ChainAccount>,
     should_increment_era: bool,
     notice_fn: &dyn Fn(NoticeId, ChainHash) -> Notice,
-) {
+) -> NoticeId {
     let (latest_notice_id, parent_hash) =
         LatestNotice::get(chain_id).unwrap_or((NoticeId(0, 0), chain_id.zero_hash()));
 
     let notice_id = if should_increment_era {
+        internal::liveness::check_liveness::<T>(chain_id, latest_notice_id.era_id());
         latest_notice_id.seq_era()
     } else {
         latest_notice_id.seq()
     };
+    internal::liveness::track_notice(chain_id, notice_id);
 
     // Add to notices, notice states, track the latest notice and index by account
     let notice = notice_fn(notice_id, parent_hash);
@@ -221,46 +385,148 @@ fn dispatch_notice<T: Config>(
     // Deposit Notice Event
     let encoded_notice = notice.encode_notice();
     Module::<T>::deposit_event(Event::Notice(notice_id, notice, encoded_notice));
+
+    notice_id
+}
+
+/// Revoke a notice which has not yet executed, so that validators no longer sign it.
+/// Signatures already published may still be used to invoke it, in which case it is not revoked.
+pub fn revoke_notice<T: Config>(chain_id: ChainId, notice_id: NoticeId) {
+    match NoticeStates::get(chain_id, notice_id) {
+        NoticeState::Pending { .. } => {
+            NoticeStates::remove(chain_id, notice_id);
+            <Module<T>>::deposit_event(Event::NoticeRevoked(chain_id, notice_id));
+        }
+        state => log!("Cannot revoke notice {:?} in state {:?}", notice_id, state),
+    }
 }
 
 pub fn handle_notice_invoked<T: Config>(
     chain_id: ChainId,
     notice_id: NoticeId,
     notice_hash: ChainHash,
-    _result: Vec<u8>,
+    result: Vec<u8>,
 ) -> Result<(), Reason> {
-    require!(
-        NoticeHashes::get(notice_hash) == Some(notice_id),
-        Reason::HashMismatch
-    );
+    let is_known = NoticeHashes::get(notice_hash) == Some(notice_id);
+    if !is_known && PrunedNoticeFilters::get(chain_id).contains(&notice_hash) {
+        log!("Ignoring invocation of pruned notice {:?}", notice_id);
+        return Ok(());
+    }
+    require!(is_known, Reason::HashMismatch);
     Notices::take(chain_id, notice_id);
+    ExtractionBatches::remove(chain_id, notice_id);
     if let Some(notice_hold_id) = NoticeHolds::get(chain_id) {
         if notice_hold_id == notice_id {
             log!("Removing notice hold as executed");
             NoticeHolds::take(chain_id);
         }
     }
-    NoticeStates::insert(chain_id, notice_id, NoticeState::Executed);
+    if NoticeStates::get(chain_id, notice_id) != NoticeState::Executed {
+        NoticeStates::insert(chain_id, notice_id, NoticeState::Executed);
+        record_executed_notice(chain_id, notice_id, notice_hash);
+        <Module<T>>::deposit_event(Event::NoticeExecuted(
+            chain_id,
+            notice_id,
+            notice_hash,
+            result,
+        ));
+    }
     Ok(())
 }
 
+/// Set the number of notice eras for which executed notices are retained.
+pub fn set_notice_retention<T: Config>(eras: u32) -> Result<(), Reason> {
+    NoticeRetentionEras::put(eras);
+    <Module<T>>::deposit_event(Event::SetNoticeRetention(eras));
+    Ok(())
+}
+
+/// Remember an executed notice, after those executed before it, until it is pruned.
+fn record_executed_notice(chain_id: ChainId, notice_id: NoticeId, notice_hash: ChainHash) {
+    let (first, next) = ExecutedNoticeRange::get(chain_id);
+    ExecutedNotices::insert(chain_id, next, (notice_id, notice_hash));
+    ExecutedNoticeRange::insert(chain_id, (first, next.saturating_add(1)));
+}
+
+/// Prune the executed notices which are older than the retention period, in order of execution, up to a batch at a time.
+/// The hashes of pruned notices are remembered in a filter, so that they cannot be replayed.
+pub fn prune_notices<T: Config>() {
+    let retention = NoticeRetentionEras::get();
+    let mut remaining = MAX_PRUNE_NOTICES;
+
+    for (chain_id, (first, next)) in ExecutedNoticeRange::iter().collect::<Vec<_>>() {
+        if first >= next || remaining == 0 {
+            continue;
+        }
+        let current_era = match LatestNotice::get(chain_id) {
+            Some((latest_notice_id, _)) => latest_notice_id.era_id(),
+            None => continue,
+        };
+
+        let mut filter = PrunedNoticeFilters::get(chain_id);
+        let mut position = first;
+        while position < next && remaining > 0 {
+            if let Some((notice_id, notice_hash)) = ExecutedNotices::get(chain_id, position) {
+                if notice_id.era_id().saturating_add(retention) >= current_era {
+                    break;
+                }
+                ExecutedNotices::remove(chain_id, position);
+                NoticeStates::remove(chain_id, notice_id);
+                NoticeHashes::remove(notice_hash);
+                filter.insert(&notice_hash);
+            }
+            position += 1;
+            remaining -= 1;
+        }
+
+        if position > first {
+            log!(
+                "Pruned {} executed notices for {:?}",
+                position - first,
+                chain_id
+            );
+            ExecutedNoticeRange::insert(chain_id, (position, next));
+            PrunedNoticeFilters::insert(chain_id, filter);
+        }
+    }
+}
+
+/// Whether the signer is one of the validators, by the address it signs notices for its chain with.
+fn is_validator_signer(signer: ChainAccount, validators: &[ValidatorKeys]) -> bool {
+    validators.iter().any(|v| match signer {
+        ChainAccount::Eth(address) | ChainAccount::Matic(address) => v.eth_address == address,
+        #[cfg(feature = "runtime-dev")]
+        ChainAccount::Mock(address) => v.eth_address == address,
+        _ => false,
+    })
+}
+
 fn process_notice_state<T: Config>(
     chain_id: ChainId,
     notice_id: NoticeId,
     notice_state: NoticeState,
+    validators: &[ValidatorKeys],
 ) -> Result<bool, Reason> {
     match notice_state {
         NoticeState::Pending { signature_pairs } => {
             let signer = chain_id.signer_address()?;
-            if !signature_pairs.has_signer(signer) {
+            // a notice signed by a previous validator set is signed again, replacing the stale signatures
+            let is_stale = is_validator_signer(signer, validators)
+                && signature_pairs.has_stale_signers(validators);
+            if !signature_pairs.has_signer(signer) || is_stale {
                 let notice = Notices::get(chain_id, notice_id)
                     .ok_or(Reason::NoticeMissing(chain_id, notice_id))?;
                 let signature: ChainSignature = notice.sign_notice()?; // NO_COV_FAIL: key already checked
                 log!("Posting Signature for [{},{}]", notice_id.0, notice_id.1);
 
                 let call = <Call<T>>::publish_signature(chain_id, notice_id, signature);
-                SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
-                    .map_err(|()| Reason::FailedToSubmitExtrinsic)?; // NO_COV_FAIL: extrinsic is valid
+                SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).map_err(
+                    |()| {
+                        metrics::increment(SUBMISSION_FAILURES, chain_id, 1);
+                        Reason::FailedToSubmitExtrinsic
+                    },
+                )?; // NO_COV_FAIL: extrinsic is valid
+                metrics::increment(NOTICES_SIGNED, chain_id, 1);
 
                 Ok(true)
             } else {
@@ -272,8 +538,9 @@ fn process_notice_state<T: Config>(
 }
 
 pub fn process_notices<T: Config>(_block_number: T::BlockNumber) -> (usize, usize, Vec<Reason>) {
+    let validators: Vec<ValidatorKeys> = Validators::iter().map(|(_, v)| v).collect();
     NoticeStates::iter().fold((0, 0, vec![]), |(succ, skip, mut fail), (chain_id, notice_id, notice_state)| {
-        match process_notice_state::<T>(chain_id, notice_id, notice_state) {
+        match process_notice_state::<T>(chain_id, notice_id, notice_state, &validators) {
             Ok(true) => (succ + 1, skip, fail),
             Ok(false) => (succ, skip + 1, fail),
             Err(err) => {
@@ -300,8 +567,18 @@ pub fn publish_signature<T: Config>(
             let notice = Notices::get(chain_id, notice_id)
                 .ok_or(Reason::NoticeMissing(chain_id, notice_id))?;
             let validator = recover_validator::<T>(&notice.encode_notice(), signature)?;
+            let validators: Vec<ValidatorKeys> = Validators::iter().map(|(_, v)| v).collect();
 
+            // signatures from validators which have since been replaced are dropped
+            let pruned = signature_pairs.retain_validators(&validators);
             if signature_pairs.has_validator_signature(signature.chain_id(), &validator) {
+                if pruned {
+                    NoticeStates::insert(
+                        chain_id,
+                        notice_id,
+                        NoticeState::Pending { signature_pairs },
+                    );
+                }
                 return Ok(());
             }
 
@@ -349,11 +626,12 @@ mod tests {
                 chain_id,
                 notice_id,
                 NoticeState::Pending {
-                    signature_pairs: ChainSignatureList::Eth(vec![]),
+                    signature_pairs: ChainSignatureList::Eth(BoundedVec::default()),
                 },
             );
 
-            let result = handle_notice_invoked::<Test>(chain_id, notice_id, notice_hash, vec![]);
+            let result =
+                handle_notice_invoked::<Test>(chain_id, notice_id, notice_hash, vec![0x01]);
 
             assert_eq!(result, Ok(()));
 
@@ -364,6 +642,24 @@ mod tests {
                 NoticeState::Executed
             );
             assert_eq!(NoticeHolds::get(chain_id), Some(notice_hold_id));
+
+            let events_post: Vec<_> = System::events().into_iter().collect();
+            assert_eq!(
+                events_post.last().unwrap().event,
+                mock::Event::pallet_cash(crate::Event::NoticeExecuted(
+                    chain_id,
+                    notice_id,
+                    notice_hash,
+                    vec![0x01]
+                ))
+            );
+
+            // a replayed invocation is acknowledged without another event
+            assert_eq!(
+                handle_notice_invoked::<Test>(chain_id, notice_id, notice_hash, vec![0x01]),
+                Ok(())
+            );
+            assert_eq!(System::events().len(), events_post.len());
         });
     }
 
@@ -388,7 +684,7 @@ mod tests {
                 chain_id,
                 notice_id,
                 NoticeState::Pending {
-                    signature_pairs: ChainSignatureList::Eth(vec![]),
+                    signature_pairs: ChainSignatureList::Eth(BoundedVec::default()),
                 },
             );
 
@@ -448,7 +744,7 @@ mod tests {
                 chain_id,
                 notice_id,
                 NoticeState::Pending {
-                    signature_pairs: ChainSignatureList::Eth(vec![]),
+                    signature_pairs: ChainSignatureList::Eth(BoundedVec::default()),
                 },
             );
 
@@ -462,12 +758,120 @@ mod tests {
             assert_eq!(
                 NoticeStates::get(chain_id, notice_id),
                 NoticeState::Pending {
-                    signature_pairs: ChainSignatureList::Eth(vec![]),
+                    signature_pairs: ChainSignatureList::Eth(BoundedVec::default()),
                 }
             );
         });
     }
 
+    /** `prune_notices` tests **/
+
+    #[test]
+    fn test_prune_notices() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let old_notice_id = NoticeId(1, 0);
+            let old_notice_hash = ChainHash::Eth([1; 32]);
+            let new_notice_id = NoticeId(5, 0);
+            let new_notice_hash = ChainHash::Eth([5; 32]);
+
+            for (notice_id, notice_hash) in &[
+                (old_notice_id, old_notice_hash),
+                (new_notice_id, new_notice_hash),
+            ] {
+                NoticeHashes::insert(notice_hash, notice_id);
+                assert_eq!(
+                    handle_notice_invoked::<Test>(chain_id, *notice_id, *notice_hash, vec![]),
+                    Ok(())
+                );
+            }
+            LatestNotice::insert(chain_id, (NoticeId(6, 0), ChainHash::Eth([6; 32])));
+
+            assert_eq!(ExecutedNoticeRange::get(chain_id), (0, 2));
+
+            // only the notice outside of the retention period is pruned
+            prune_notices::<Test>();
+            assert_eq!(ExecutedNoticeRange::get(chain_id), (1, 2));
+            assert_eq!(ExecutedNotices::get(chain_id, 0), None);
+            assert_eq!(
+                ExecutedNotices::get(chain_id, 1),
+                Some((new_notice_id, new_notice_hash))
+            );
+            assert_eq!(
+                NoticeStates::get(chain_id, old_notice_id),
+                NoticeState::Missing
+            );
+            assert_eq!(NoticeHashes::get(old_notice_hash), None);
+            assert_eq!(
+                NoticeStates::get(chain_id, new_notice_id),
+                NoticeState::Executed
+            );
+            assert!(PrunedNoticeFilters::get(chain_id).contains(&old_notice_hash));
+            assert!(!PrunedNoticeFilters::get(chain_id).contains(&new_notice_hash));
+
+            // replaying the pruned notice is ignored, other unknown notices still fail
+            assert_eq!(
+                handle_notice_invoked::<Test>(chain_id, old_notice_id, old_notice_hash, vec![]),
+                Ok(())
+            );
+            assert_eq!(
+                NoticeStates::get(chain_id, old_notice_id),
+                NoticeState::Missing
+            );
+            assert_eq!(
+                handle_notice_invoked::<Test>(
+                    chain_id,
+                    NoticeId(2, 0),
+                    ChainHash::Eth([2; 32]),
+                    vec![]
+                ),
+                Err(Reason::HashMismatch)
+            );
+        });
+    }
+
+    #[test]
+    fn test_prune_notices_in_batches() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            for index in 0..MAX_PRUNE_NOTICES + 1 {
+                let notice_id = NoticeId(1, index);
+                let notice_hash = ChainHash::Eth([index as u8; 32]);
+                NoticeHashes::insert(notice_hash, notice_id);
+                assert_eq!(
+                    handle_notice_invoked::<Test>(chain_id, notice_id, notice_hash, vec![]),
+                    Ok(())
+                );
+            }
+            LatestNotice::insert(chain_id, (NoticeId(6, 0), ChainHash::Eth([6; 32])));
+
+            prune_notices::<Test>();
+            assert_eq!(
+                ExecutedNoticeRange::get(chain_id),
+                (MAX_PRUNE_NOTICES as u64, MAX_PRUNE_NOTICES as u64 + 1)
+            );
+
+            prune_notices::<Test>();
+            assert_eq!(
+                ExecutedNoticeRange::get(chain_id),
+                (MAX_PRUNE_NOTICES as u64 + 1, MAX_PRUNE_NOTICES as u64 + 1)
+            );
+            assert_eq!(
+                ExecutedNotices::iter_prefix(chain_id).collect::<Vec<_>>(),
+                vec![]
+            );
+        });
+    }
+
+    #[test]
+    fn test_set_notice_retention() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(NoticeRetentionEras::get(), DEFAULT_NOTICE_RETENTION_ERAS);
+            assert_ok!(CashModule::set_notice_retention(Origin::root(), 1));
+            assert_eq!(NoticeRetentionEras::get(), 1);
+        });
+    }
+
     /** `process_notice_state` tests **/
 
     // Currently, the env vars set in other tests make this very difficult to test
@@ -510,7 +914,7 @@ mod tests {
             let notice_state = NoticeState::pending(&notice);
 
             assert_eq!(
-                process_notice_state::<Test>(chain_id, notice_id, notice_state),
+                process_notice_state::<Test>(chain_id, notice_id, notice_state, &[]),
                 Err(Reason::NoticeMissing(chain_id, notice_id))
             );
         });
@@ -524,7 +928,7 @@ mod tests {
             let notice_state = NoticeState::Executed {};
 
             assert_eq!(
-                process_notice_state::<Test>(chain_id, notice_id, notice_state),
+                process_notice_state::<Test>(chain_id, notice_id, notice_state, &[]),
                 Ok(false)
             );
         });
@@ -537,11 +941,13 @@ mod tests {
             let notice_id = NoticeId(5, 6);
             let signer = <Ethereum as Chain>::signer_address().unwrap();
             let notice_state = NoticeState::Pending {
-                signature_pairs: ChainSignatureList::Eth(vec![(signer, [0u8; 65])]),
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![(signer, [0u8; 65])].try_into().unwrap(),
+                ),
             };
 
             assert_eq!(
-                process_notice_state::<Test>(chain_id, notice_id, notice_state),
+                process_notice_state::<Test>(chain_id, notice_id, notice_state, &[]),
                 Ok(false)
             );
         });
@@ -563,7 +969,45 @@ mod tests {
             Notices::insert(chain_id, notice_id, notice);
 
             assert_eq!(
-                process_notice_state::<Test>(chain_id, notice_id, notice_state),
+                process_notice_state::<Test>(chain_id, notice_id, notice_state, &[]),
+                Ok(true)
+            );
+        });
+    }
+
+    #[test]
+    fn test_process_notice_state_stale_signers() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let notice_id = NoticeId(5, 6);
+            let notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
+                id: NoticeId(80, 1),
+                parent: [3u8; 32],
+                asset: [1; 20],
+                amount: 100,
+                account: [2; 20],
+            });
+            let signer = <Ethereum as Chain>::signer_address().unwrap();
+            let validators = vec![ValidatorKeys {
+                substrate_id: AccountId32::new([0u8; 32]),
+                eth_address: signer,
+            }];
+            let notice_state = NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![(signer, [0u8; 65]), ([9u8; 20], [0u8; 65])]
+                        .try_into()
+                        .unwrap(),
+                ),
+            };
+            Notices::insert(chain_id, notice_id, notice);
+
+            // only a current validator signs again
+            assert_eq!(
+                process_notice_state::<Test>(chain_id, notice_id, notice_state.clone(), &[]),
+                Ok(false)
+            );
+            assert_eq!(
+                process_notice_state::<Test>(chain_id, notice_id, notice_state, &validators),
                 Ok(true)
             );
         });
@@ -597,7 +1041,7 @@ mod tests {
             // Missing
             let notice_id_3 = NoticeId(5, 8);
             let notice_state_3 = NoticeState::Pending {
-                signature_pairs: ChainSignatureList::Eth(vec![]),
+                signature_pairs: ChainSignatureList::Eth(BoundedVec::default()),
             };
             NoticeStates::insert(chain_id, notice_id_3, notice_state_3);
 
@@ -661,7 +1105,7 @@ mod tests {
                 _ => panic!("absurd"),
             };
             let notice_state = NoticeState::Pending {
-                signature_pairs: ChainSignatureList::Eth(vec![]),
+                signature_pairs: ChainSignatureList::Eth(BoundedVec::default()),
             };
             NoticeStates::insert(chain_id, notice_id, notice_state);
             Notices::insert(chain_id, notice_id, notice);
@@ -675,7 +1119,9 @@ mod tests {
             );
 
             let expected_notice_state = NoticeState::Pending {
-                signature_pairs: ChainSignatureList::Eth(vec![(signer, eth_signature)]),
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![(signer, eth_signature)].try_into().unwrap(),
+                ),
             };
 
             assert_eq!(
@@ -690,6 +1136,57 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_publish_signature_replaces_stale_signers() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let notice_id = NoticeId(5, 6);
+            let notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
+                id: NoticeId(80, 1),
+                parent: [3u8; 32],
+                asset: [1; 20],
+                amount: 100,
+                account: [2; 20],
+            });
+            let signature = notice.sign_notice().unwrap();
+            let signer = <Ethereum as Chain>::signer_address().unwrap();
+            let eth_signature = match signature {
+                ChainSignature::Eth(a) => a,
+                _ => panic!("absurd"),
+            };
+            let notice_state = NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![([9u8; 20], [0u8; 65]), (signer, eth_signature)]
+                        .try_into()
+                        .unwrap(),
+                ),
+            };
+            NoticeStates::insert(chain_id, notice_id, notice_state);
+            Notices::insert(chain_id, notice_id, notice);
+            let substrate_id = AccountId32::new([0u8; 32]);
+            Validators::insert(
+                substrate_id.clone(),
+                ValidatorKeys {
+                    substrate_id,
+                    eth_address: signer,
+                },
+            );
+
+            assert_eq!(
+                publish_signature::<Test>(chain_id, notice_id, signature),
+                Ok(())
+            );
+            assert_eq!(
+                NoticeStates::get(chain_id, notice_id),
+                NoticeState::Pending {
+                    signature_pairs: ChainSignatureList::Eth(
+                        vec![(signer, eth_signature)].try_into().unwrap(),
+                    ),
+                }
+            );
+        });
+    }
+
     #[test]
     fn test_publish_signature_pending_and_missing() {
         new_test_ext().execute_with(|| {
@@ -732,7 +1229,9 @@ mod tests {
             };
             let signer = <Ethereum as Chain>::signer_address().unwrap();
             let notice_state = NoticeState::Pending {
-                signature_pairs: ChainSignatureList::Eth(vec![(signer, eth_signature)]),
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![(signer, eth_signature)].try_into().unwrap(),
+                ),
             };
             NoticeStates::insert(chain_id, notice_id, notice_state);
             Notices::insert(chain_id, notice_id, notice);
@@ -772,7 +1271,17 @@ mod tests {
                 _ => panic!("invalid signature"),
             };
             let notice_state = NoticeState::Pending {
-                signature_pairs: ChainSignatureList::Dot(vec![([0; 32], eth_signature)]),
+                signature_pairs: ChainSignatureList::Dot(
+                    vec![(
+                        Ss58Address {
+                            network: Ss58Network::Polkadot,
+                            key: [0; 32],
+                        },
+                        eth_signature,
+                    )]
+                    .try_into()
+                    .unwrap(),
+                ),
             };
             NoticeStates::insert(chain_id, notice_id, notice_state);
             Notices::insert(chain_id, notice_id, notice);
@@ -808,7 +1317,9 @@ mod tests {
             });
             let signature = ChainSignature::Eth([1u8; 65]);
             let notice_state = NoticeState::Pending {
-                signature_pairs: ChainSignatureList::Eth(vec![([1u8; 20], [1u8; 65])]),
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![([1u8; 20], [1u8; 65])].try_into().unwrap(),
+                ),
             };
             NoticeStates::insert(chain_id, notice_id, notice_state);
             Notices::insert(chain_id, notice_id, notice);
@@ -842,7 +1353,9 @@ mod tests {
             };
             let signer = <Ethereum as Chain>::signer_address().unwrap();
             let notice_state = NoticeState::Pending {
-                signature_pairs: ChainSignatureList::Eth(vec![(signer, *eth_signature)]),
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![(signer, *eth_signature)].try_into().unwrap(),
+                ),
             };
             NoticeStates::insert(chain_id, notice_id, notice_state);
             Notices::insert(chain_id, notice_id, notice);
@@ -855,4 +1368,108 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn test_set_extraction_batch_budget() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(set_extraction_batch_budget::<Test>(
+                ChainId::Eth,
+                Some(2048)
+            ));
+            assert_eq!(ExtractionBatchBudgets::get(ChainId::Eth), Some(2048));
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(
+                    crate::Event::SetExtractionBatchBudget(ChainId::Eth, Some(2048))
+                ))
+            );
+
+            assert_ok!(set_extraction_batch_budget::<Test>(ChainId::Eth, None));
+            assert_eq!(ExtractionBatchBudgets::get(ChainId::Eth), None);
+
+            assert_eq!(
+                set_extraction_batch_budget::<Test>(ChainId::Gate, Some(2048)),
+                Err(Reason::InvalidChain)
+            );
+        });
+    }
+
+    #[test]
+    fn test_dispatch_extraction_batches() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let asset = ChainAsset::Eth([1; 20]);
+            let account_a = ChainAccount::Eth([2; 20]);
+            let account_b = ChainAccount::Eth([3; 20]);
+            ExtractionBatchBudgets::insert(chain_id, BatchExtractionNotice::encoded_len(2) as u32);
+
+            dispatch_extraction_notice::<Test>(asset, account_a, 100);
+            dispatch_extraction_notice::<Test>(asset, account_b, 200);
+            dispatch_extraction_notice::<Test>(asset, account_a, 300);
+            assert_eq!(LatestNotice::get(chain_id), None);
+            assert_eq!(PendingBatchExtractions::get(chain_id).len(), 3);
+
+            dispatch_extraction_batches::<Test>();
+
+            assert_eq!(PendingBatchExtractions::get(chain_id), vec![]);
+            let first = Notices::get(chain_id, NoticeId(0, 1)).unwrap();
+            let second = Notices::get(chain_id, NoticeId(0, 2)).unwrap();
+            assert_eq!(
+                first,
+                Notice::BatchExtractionNotice(BatchExtractionNotice::Eth {
+                    id: NoticeId(0, 1),
+                    parent: [0u8; 32],
+                    extractions: vec![([1; 20], [2; 20], 100), ([1; 20], [3; 20], 200)],
+                })
+            );
+            assert_eq!(
+                first.encode_notice().len(),
+                BatchExtractionNotice::encoded_len(2)
+            );
+            assert_eq!(
+                ExtractionBatches::get(chain_id, NoticeId(0, 2)),
+                vec![(asset, account_a, 300)]
+            );
+            assert_eq!(
+                AccountNotices::get(account_a),
+                vec![NoticeId(0, 1), NoticeId(0, 2)]
+            );
+            assert_eq!(AccountNotices::get(account_b), vec![NoticeId(0, 1)]);
+
+            assert_ok!(handle_notice_invoked::<Test>(
+                chain_id,
+                NoticeId(0, 2),
+                second.hash(),
+                vec![]
+            ));
+            assert_eq!(ExtractionBatches::get(chain_id, NoticeId(0, 2)), vec![]);
+            assert_eq!(ExtractionBatches::get(chain_id, NoticeId(0, 1)).len(), 2);
+        });
+    }
+
+    #[test]
+    fn test_dispatch_extraction_batches_bounded() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let asset = ChainAsset::Eth([1; 20]);
+            let account = ChainAccount::Eth([2; 20]);
+            ExtractionBatchBudgets::insert(chain_id, 1 << 20);
+
+            let total = MAX_BATCHED_EXTRACTIONS as usize + 1;
+            for _ in 0..total {
+                dispatch_extraction_notice::<Test>(asset, account, 100);
+            }
+
+            dispatch_extraction_batches::<Test>();
+            assert_eq!(PendingBatchExtractions::get(chain_id).len(), 1);
+            assert_eq!(
+                ExtractionBatches::get(chain_id, NoticeId(0, 1)).len(),
+                total - 1
+            );
+
+            dispatch_extraction_batches::<Test>();
+            assert_eq!(PendingBatchExtractions::get(chain_id), vec![]);
+            assert_eq!(ExtractionBatches::get(chain_id, NoticeId(0, 2)).len(), 1);
+        });
+    }
 }
@@ -0,0 +1,177 @@
+use crate::{
+    log,
+    reason::Reason,
+    types::{AssetPrice, CashPeg, Timestamp},
+    CashPegStatus, Config, Event, Module, PegTolerance,
+};
+use frame_support::storage::StorageValue;
+use pallet_oracle::ticker::{CASH_TICKER, WCASH_TICKER};
+
+/// Set how far and for how long wrapped CASH may trade off peg before it is reported.
+pub fn set_peg_tolerance<T: Config>(
+    max_deviation_bps: u32,
+    delay: Timestamp,
+) -> Result<(), Reason> {
+    PegTolerance::put((max_deviation_bps, delay));
+    <Module<T>>::deposit_event(Event::SetPegTolerance(max_deviation_bps, delay));
+    Ok(())
+}
+
+/// Return the distance of a price from the peg, in basis points.
+fn peg_deviation_bps(peg: AssetPrice, price: AssetPrice) -> u32 {
+    let distance = if price > peg {
+        price - peg
+    } else {
+        peg - price
+    };
+    (distance.saturating_mul(10_000) / peg).min(u32::MAX as u128) as u32
+}
+
+/// Update the deviation of the market price of wrapped CASH from the value of CASH, if there is a market price.
+/// Reports once when the deviation has exceeded the tolerance for long enough, so governance can respond,
+///  and again once it returns within the tolerance.
+/// Called when the block is initialized, and thus may not fail.
+pub fn track_cash_peg<T: Config>(now: Timestamp) {
+    let (price, peg) = match (
+        pallet_oracle::get_price_by_ticker::<T>(WCASH_TICKER),
+        pallet_oracle::get_price_by_ticker::<T>(CASH_TICKER),
+    ) {
+        (Some(price), Some(peg)) if peg.value > 0 => (price.value, peg.value),
+        _ => return,
+    };
+
+    let (max_deviation_bps, delay) = PegTolerance::get();
+    let last = CashPegStatus::get().unwrap_or_default();
+    let deviation_bps = peg_deviation_bps(peg, price);
+    let off_peg_since = if deviation_bps > max_deviation_bps {
+        Some(last.off_peg_since.unwrap_or(now))
+    } else {
+        None
+    };
+    let reported = match off_peg_since {
+        Some(since) => last.reported || now.saturating_sub(since) >= delay,
+        None => false,
+    };
+
+    if reported && !last.reported {
+        log!(
+            "Wrapped CASH off peg at {} ({} bps) since {}",
+            price,
+            deviation_bps,
+            off_peg_since.unwrap_or(now)
+        );
+        <Module<T>>::deposit_event(Event::CashOffPeg(
+            price,
+            deviation_bps,
+            off_peg_since.unwrap_or(now),
+        ));
+    } else if !reported && last.reported {
+        <Module<T>>::deposit_event(Event::CashPegRestored(price));
+    }
+
+    CashPegStatus::put(CashPeg {
+        price,
+        deviation_bps,
+        off_peg_since,
+        reported,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    const HOUR: Timestamp = 60 * 60 * 1000;
+
+    fn set_market_price(price: &'static str) {
+        pallet_oracle::Prices::insert(WCASH_TICKER, Price::from_nominal(WCASH_TICKER, price).value);
+    }
+
+    fn last_event() -> Option<mock::Event> {
+        System::events().last().map(|e| e.event.clone())
+    }
+
+    #[test]
+    fn test_set_peg_tolerance() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                PegTolerance::get(),
+                (DEFAULT_PEG_TOLERANCE_BPS, DEFAULT_PEG_REPORT_DELAY)
+            );
+            assert_ok!(set_peg_tolerance::<Test>(100, HOUR));
+            assert_eq!(PegTolerance::get(), (100, HOUR));
+            assert_eq!(
+                last_event(),
+                Some(mock::Event::pallet_cash(crate::Event::SetPegTolerance(
+                    100, HOUR
+                )))
+            );
+        });
+    }
+
+    #[test]
+    fn test_track_cash_peg() {
+        new_test_ext().execute_with(|| {
+            PegTolerance::put((100, HOUR));
+
+            // no market price, nothing to track
+            track_cash_peg::<Test>(0);
+            assert_eq!(CashPegStatus::get(), None);
+
+            set_market_price("0.995");
+            track_cash_peg::<Test>(0);
+            assert_eq!(
+                CashPegStatus::get(),
+                Some(CashPeg {
+                    price: 995000,
+                    deviation_bps: 50,
+                    off_peg_since: None,
+                    reported: false,
+                })
+            );
+
+            // off peg, but not yet for long enough to report
+            set_market_price("0.97");
+            track_cash_peg::<Test>(HOUR);
+            track_cash_peg::<Test>(2 * HOUR - 1);
+            assert_eq!(
+                CashPegStatus::get().map(|peg| (peg.deviation_bps, peg.off_peg_since)),
+                Some((300, Some(HOUR)))
+            );
+            assert_eq!(System::events().len(), 0);
+
+            track_cash_peg::<Test>(2 * HOUR);
+            assert_eq!(
+                last_event(),
+                Some(mock::Event::pallet_cash(crate::Event::CashOffPeg(
+                    970000, 300, HOUR
+                )))
+            );
+
+            // only reported once
+            set_market_price("1.05");
+            track_cash_peg::<Test>(3 * HOUR);
+            assert_eq!(System::events().len(), 1);
+            assert_eq!(CashPegStatus::get().map(|peg| peg.reported), Some(true));
+
+            set_market_price("1.001");
+            track_cash_peg::<Test>(4 * HOUR);
+            assert_eq!(
+                last_event(),
+                Some(mock::Event::pallet_cash(crate::Event::CashPegRestored(
+                    1001000
+                )))
+            );
+            assert_eq!(
+                CashPegStatus::get(),
+                Some(CashPeg {
+                    price: 1001000,
+                    deviation_bps: 10,
+                    off_peg_since: None,
+                    reported: false,
+                })
+            );
+        });
+    }
+}
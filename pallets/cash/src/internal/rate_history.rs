@@ -0,0 +1,133 @@
+use crate::{
+    chains::ChainAsset,
+    internal::assets::get_rates,
+    params::{RATE_HISTORY_INTERVAL, RATE_HISTORY_LENGTH},
+    rates::APR,
+    reason::Reason,
+    require,
+    types::{CashIndex, RateSnapshot, Timestamp},
+    Config, GlobalCashIndex, RateHistory, RateHistoryCursor, SupportedAssets,
+};
+use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
+use our_std::vec::Vec;
+
+/// Record the CASH index and the rates of every supported asset, once per interval.
+/// Snapshots are kept in a ring buffer, overwriting the oldest once it is full.
+/// Called when the block is initialized, and thus may not fail.
+pub fn record_rate_history<T: Config>(now: Timestamp) {
+    let (slot, last_timestamp) = RateHistoryCursor::get();
+    if last_timestamp != 0 && now < last_timestamp.saturating_add(RATE_HISTORY_INTERVAL) {
+        return;
+    }
+
+    let rates = SupportedAssets::iter()
+        .filter_map(|(asset, _)| {
+            get_rates::<T>(asset)
+                .ok()
+                .map(|(borrow_rate, supply_rate)| (asset, borrow_rate, supply_rate))
+        })
+        .collect();
+    let snapshot = RateSnapshot {
+        timestamp: now,
+        cash_index: GlobalCashIndex::get(),
+        rates,
+    };
+
+    RateHistory::insert(slot, snapshot);
+    RateHistoryCursor::put(((slot + 1) % RATE_HISTORY_LENGTH, now));
+}
+
+/// Return the recorded (timestamp, borrow rate, supply rate, CASH index) of an asset, oldest first.
+pub fn get_rate_history<T: Config>(
+    asset: ChainAsset,
+) -> Result<Vec<(Timestamp, APR, APR, CashIndex)>, Reason> {
+    require!(
+        SupportedAssets::contains_key(asset),
+        Reason::AssetNotSupported
+    );
+
+    let (next_slot, _) = RateHistoryCursor::get();
+    Ok((0..RATE_HISTORY_LENGTH)
+        .filter_map(|i| RateHistory::get((next_slot + i) % RATE_HISTORY_LENGTH))
+        .filter_map(|snapshot| {
+            snapshot.rates.iter().find(|(a, _, _)| *a == asset).map(
+                |&(_, borrow_rate, supply_rate)| {
+                    (
+                        snapshot.timestamp,
+                        borrow_rate,
+                        supply_rate,
+                        snapshot.cash_index,
+                    )
+                },
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{common::*, *};
+
+    #[test]
+    fn test_record_rate_history() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            init_eth_asset()?;
+            let (borrow_rate, supply_rate) = get_rates::<Test>(Eth)?;
+            let start = 1000;
+
+            assert_eq!(
+                get_rate_history::<Test>(Usdc),
+                Err(Reason::AssetNotSupported)
+            );
+            assert_eq!(get_rate_history::<Test>(Eth), Ok(vec![]));
+
+            record_rate_history::<Test>(start);
+            GlobalCashIndex::put(CashIndex::from_nominal("1.5"));
+            record_rate_history::<Test>(start + RATE_HISTORY_INTERVAL - 1);
+            record_rate_history::<Test>(start + RATE_HISTORY_INTERVAL);
+
+            assert_eq!(
+                get_rate_history::<Test>(Eth),
+                Ok(vec![
+                    (
+                        start,
+                        borrow_rate,
+                        supply_rate,
+                        CashIndex::from_nominal("1")
+                    ),
+                    (
+                        start + RATE_HISTORY_INTERVAL,
+                        borrow_rate,
+                        supply_rate,
+                        CashIndex::from_nominal("1.5")
+                    ),
+                ])
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_rate_history_overwrites_oldest() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            init_eth_asset()?;
+
+            for i in 0..=(RATE_HISTORY_LENGTH as u64) {
+                record_rate_history::<Test>(1000 + i * RATE_HISTORY_INTERVAL);
+            }
+
+            let history = get_rate_history::<Test>(Eth)?;
+            assert_eq!(history.len(), RATE_HISTORY_LENGTH as usize);
+            assert_eq!(history[0].0, 1000 + RATE_HISTORY_INTERVAL);
+            assert_eq!(
+                history.last().map(|h| h.0),
+                Some(1000 + RATE_HISTORY_LENGTH as u64 * RATE_HISTORY_INTERVAL)
+            );
+            assert_eq!(RateHistoryCursor::get().0, 1);
+
+            Ok(())
+        })
+    }
+}
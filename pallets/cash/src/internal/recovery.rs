@@ -0,0 +1,348 @@
+use crate::{
+    chains::ChainAccount,
+    core::get_recent_timestamp,
+    internal::transfer::move_portfolio,
+    params::{MAX_GUARDIANS, RECOVERY_DELAY, RECOVERY_RELEASE_BATCH_SIZE},
+    pipeline::CashPipeline,
+    reason::{MathError, Reason},
+    require,
+    types::Timestamp,
+    Config, Event, GlobalCashIndex, Guardians, Module, PortfolioConsents, QueuedRecoveries,
+    RecoveryApprovals, RecoveryQueue, RecoveryQueueRange,
+};
+use frame_support::storage::{StorageDoubleMap, StorageMap, StorageValue};
+use our_std::{log, vec::Vec};
+
+/// Set the guardians of an account, any threshold of which may recover it to a new account.
+/// Setting no guardians removes them, and any approvals given by the previous guardians are discarded.
+pub fn set_guardians_internal<T: Config>(
+    account: ChainAccount,
+    threshold: u32,
+    guardians: Vec<ChainAccount>,
+) -> Result<(), Reason> {
+    if guardians.is_empty() {
+        require!(threshold == 0, Reason::InvalidGuardians);
+    } else {
+        require!(
+            threshold > 0 && threshold as usize <= guardians.len(),
+            Reason::InvalidGuardians
+        );
+    }
+    require!(guardians.len() <= MAX_GUARDIANS, Reason::InvalidGuardians);
+    require!(!guardians.contains(&account), Reason::InvalidGuardians);
+    for (i, guardian) in guardians.iter().enumerate() {
+        require!(!guardians[..i].contains(guardian), Reason::InvalidGuardians);
+    }
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    if guardians.is_empty() {
+        Guardians::remove(account);
+    } else {
+        Guardians::insert(account, (threshold, guardians.clone()));
+    }
+    RecoveryApprovals::remove_prefix(account);
+
+    <Module<T>>::deposit_event(Event::SetGuardians(account, threshold, guardians));
+
+    Ok(())
+}
+
+/// Approve the recovery of an account to a new account, as one of its guardians.
+/// Once enough guardians approve the same new account, the recovery is queued until the delay has passed.
+pub fn approve_recovery_internal<T: Config>(
+    guardian: ChainAccount,
+    account: ChainAccount,
+    new_account: ChainAccount,
+) -> Result<(), Reason> {
+    let (threshold, guardians) = Guardians::get(account).ok_or(Reason::NotGuardian)?;
+    require!(guardians.contains(&guardian), Reason::NotGuardian);
+    require!(account != new_account, Reason::SelfTransfer);
+    require!(
+        !QueuedRecoveries::contains_key(account),
+        Reason::RecoveryPending
+    );
+
+    let mut approvals = RecoveryApprovals::get(account, new_account);
+    if !approvals.contains(&guardian) {
+        approvals.push(guardian);
+    }
+
+    if approvals.len() < threshold as usize {
+        RecoveryApprovals::insert(account, new_account, approvals);
+        <Module<T>>::deposit_event(Event::RecoveryApproved(account, new_account, guardian));
+        return Ok(());
+    }
+
+    let now = get_recent_timestamp::<T>()?;
+    let eta = now.checked_add(RECOVERY_DELAY).ok_or(MathError::Overflow)?;
+    let (first, next) = RecoveryQueueRange::get();
+    let next_position = next.checked_add(1).ok_or(MathError::Overflow)?;
+
+    log!(
+        "Queueing recovery of {:?} to {:?} until {}",
+        account,
+        new_account,
+        eta
+    );
+    RecoveryApprovals::remove_prefix(account);
+    QueuedRecoveries::insert(account, (eta, new_account));
+    RecoveryQueue::insert(next, (account, eta));
+    RecoveryQueueRange::put((first, next_position));
+    <Module<T>>::deposit_event(Event::RecoveryQueued(account, new_account, eta));
+
+    Ok(())
+}
+
+/// Cancel the queued recovery of an account, as the account itself, before it executes.
+pub fn cancel_recovery_internal<T: Config>(account: ChainAccount) -> Result<(), Reason> {
+    require!(QueuedRecoveries::contains_key(account), Reason::NoRecovery);
+    QueuedRecoveries::remove(account);
+    RecoveryApprovals::remove_prefix(account);
+    <Module<T>>::deposit_event(Event::RecoveryCancelled(account));
+    Ok(())
+}
+
+/// Reassign the entire portfolio of an account to the new account.
+/// Debts may only be moved if the new account has accepted the portfolio of the account, as for a transfer.
+/// The guardians of the account are carried over, unless the new account already has its own.
+pub fn recover_account_internal<T: Config>(
+    account: ChainAccount,
+    new_account: ChainAccount,
+) -> Result<(), Reason> {
+    let index = GlobalCashIndex::get();
+    let consented = PortfolioConsents::get(new_account) == Some(account);
+    let (pipeline, moved) =
+        move_portfolio::<T>(CashPipeline::new(), account, new_account, consented)?;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    pipeline.commit::<T>();
+    if consented {
+        PortfolioConsents::remove(new_account);
+    }
+    if let Some(guardians) = Guardians::take(account) {
+        if !Guardians::contains_key(new_account) {
+            Guardians::insert(new_account, guardians);
+        }
+    }
+
+    moved.deposit_events::<T>(index);
+    <Module<T>>::deposit_event(Event::AccountRecovered(account, new_account));
+
+    Ok(())
+}
+
+/// Execute the queued recoveries which have waited out the delay, in the order they were queued, up to a batch at a time.
+/// Since the delay is constant, the recoveries become ready in the order they were queued.
+/// A recovery which was cancelled, or cancelled and queued again, is skipped at its original position.
+/// A recovery which cannot be applied, such as one leaving the new account undercollateralized, is dropped.
+pub fn release_queued_recoveries<T: Config>(now: Timestamp) {
+    let (first, next) = RecoveryQueueRange::get();
+    let mut position = first;
+    while position < next && position - first < RECOVERY_RELEASE_BATCH_SIZE as u64 {
+        if let Some((account, eta)) = RecoveryQueue::get(position) {
+            if eta > now {
+                break;
+            }
+            RecoveryQueue::remove(position);
+            match QueuedRecoveries::get(account) {
+                Some((queued_eta, new_account)) if queued_eta == eta => {
+                    QueuedRecoveries::remove(account);
+                    if let Err(reason) = recover_account_internal::<T>(account, new_account) {
+                        <Module<T>>::deposit_event(Event::RecoveryFailed(
+                            account,
+                            new_account,
+                            reason,
+                        ));
+                    }
+                }
+                _ => (),
+            }
+        }
+        position += 1;
+    }
+
+    if position > first {
+        RecoveryQueueRange::put((position, next));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tests::{assets::*, common::*, *},
+        types::*,
+    };
+
+    const DAY: Timestamp = 24 * 60 * 60 * 1000;
+
+    #[allow(non_upper_case_globals)]
+    const account: ChainAccount = ChainAccount::Eth([1u8; 20]);
+    #[allow(non_upper_case_globals)]
+    const new_account: ChainAccount = ChainAccount::Eth([2u8; 20]);
+    #[allow(non_upper_case_globals)]
+    const guardian_a: ChainAccount = ChainAccount::Eth([3u8; 20]);
+    #[allow(non_upper_case_globals)]
+    const guardian_b: ChainAccount = ChainAccount::Eth([4u8; 20]);
+    #[allow(non_upper_case_globals)]
+    const guardian_c: ChainAccount = ChainAccount::Eth([5u8; 20]);
+
+    #[test]
+    fn test_set_guardians_invalid() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                set_guardians_internal::<Test>(account, 0, vec![guardian_a]),
+                Err(Reason::InvalidGuardians)
+            );
+            assert_eq!(
+                set_guardians_internal::<Test>(account, 2, vec![guardian_a]),
+                Err(Reason::InvalidGuardians)
+            );
+            assert_eq!(
+                set_guardians_internal::<Test>(account, 1, vec![guardian_a, guardian_a]),
+                Err(Reason::InvalidGuardians)
+            );
+            assert_eq!(
+                set_guardians_internal::<Test>(account, 1, vec![account]),
+                Err(Reason::InvalidGuardians)
+            );
+            assert_eq!(Guardians::get(account), None);
+        });
+    }
+
+    #[test]
+    fn test_recovery_queued_until_delay() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            init_usdc_asset()?;
+            init_asset_balance(Usdc, account, Balance::from_nominal("10", USD).value);
+            let now = 500;
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(now);
+
+            let guardians = vec![guardian_a, guardian_b, guardian_c];
+            set_guardians_internal::<Test>(account, 2, guardians.clone())?;
+            assert_eq!(Guardians::get(account), Some((2, guardians.clone())));
+
+            assert_eq!(
+                approve_recovery_internal::<Test>(new_account, account, new_account),
+                Err(Reason::NotGuardian)
+            );
+            approve_recovery_internal::<Test>(guardian_a, account, new_account)?;
+            approve_recovery_internal::<Test>(guardian_a, account, new_account)?;
+            assert_eq!(
+                RecoveryApprovals::get(account, new_account),
+                vec![guardian_a]
+            );
+            assert_eq!(QueuedRecoveries::get(account), None);
+
+            approve_recovery_internal::<Test>(guardian_b, account, new_account)?;
+            assert_eq!(
+                QueuedRecoveries::get(account),
+                Some((now + RECOVERY_DELAY, new_account))
+            );
+            assert_eq!(RecoveryApprovals::get(account, new_account), vec![]);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::RecoveryQueued(
+                    account,
+                    new_account,
+                    now + RECOVERY_DELAY
+                )))
+            );
+            assert_eq!(
+                approve_recovery_internal::<Test>(guardian_c, account, new_account),
+                Err(Reason::RecoveryPending)
+            );
+
+            release_queued_recoveries::<Test>(now + RECOVERY_DELAY - 1);
+            assert_eq!(
+                AssetBalances::get(Usdc, account),
+                Balance::from_nominal("10", USD).value
+            );
+
+            release_queued_recoveries::<Test>(now + RECOVERY_DELAY);
+            assert_eq!(AssetBalances::get(Usdc, account), 0);
+            assert_eq!(
+                AssetBalances::get(Usdc, new_account),
+                Balance::from_nominal("10", USD).value
+            );
+            assert_eq!(QueuedRecoveries::get(account), None);
+            assert_eq!(Guardians::get(account), None);
+            assert_eq!(Guardians::get(new_account), Some((2, guardians)));
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::AccountRecovered(
+                    account,
+                    new_account
+                )))
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_cancel_recovery() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(DAY);
+            set_guardians_internal::<Test>(account, 1, vec![guardian_a])?;
+
+            assert_eq!(
+                cancel_recovery_internal::<Test>(account),
+                Err(Reason::NoRecovery)
+            );
+            approve_recovery_internal::<Test>(guardian_a, account, new_account)?;
+            assert_ok!(cancel_recovery_internal::<Test>(account));
+            assert_eq!(QueuedRecoveries::get(account), None);
+
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(2 * DAY);
+            approve_recovery_internal::<Test>(guardian_a, account, new_account)?;
+            assert_eq!(RecoveryQueueRange::get(), (0, 2));
+
+            release_queued_recoveries::<Test>(DAY + RECOVERY_DELAY);
+            assert_eq!(Guardians::get(account), Some((1, vec![guardian_a])));
+            assert_eq!(
+                QueuedRecoveries::get(account),
+                Some((2 * DAY + RECOVERY_DELAY, new_account))
+            );
+            assert_eq!(RecoveryQueueRange::get(), (1, 2));
+
+            release_queued_recoveries::<Test>(2 * DAY + RECOVERY_DELAY);
+            assert_eq!(QueuedRecoveries::get(account), None);
+            assert_eq!(Guardians::get(new_account), Some((1, vec![guardian_a])));
+            assert_eq!(RecoveryQueueRange::get(), (2, 2));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_recover_debt_requires_consent() -> Result<(), Reason> {
+        new_test_ext().execute_with(|| {
+            init_usdc_asset()?;
+            init_asset_balance(Usdc, new_account, Balance::from_nominal("10", USD).value);
+            CashPrincipals::insert(account, CashPrincipal::from_nominal("-1"));
+
+            assert_eq!(
+                recover_account_internal::<Test>(account, new_account),
+                Err(Reason::PortfolioConsentRequired)
+            );
+            assert_eq!(
+                CashPrincipals::get(account),
+                CashPrincipal::from_nominal("-1")
+            );
+
+            crate::internal::transfer::accept_portfolio_internal::<Test>(new_account, account)?;
+            assert_ok!(recover_account_internal::<Test>(account, new_account));
+            assert_eq!(CashPrincipals::get(account), CashPrincipal::ZERO);
+            assert_eq!(
+                CashPrincipals::get(new_account),
+                CashPrincipal::from_nominal("-1")
+            );
+            assert_eq!(PortfolioConsents::get(new_account), None);
+
+            Ok(())
+        })
+    }
+}
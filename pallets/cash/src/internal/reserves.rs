@@ -0,0 +1,97 @@
+use crate::{
+    chains::{ChainAccount, ChainAsset},
+    internal,
+    reason::Reason,
+    types::CashPrincipalAmount,
+    ChainCashPrincipals, Config, Event, GlobalCashIndex, Module, ProtocolReserves,
+};
+use frame_support::storage::{StorageMap, StorageValue};
+
+/// Sweep all of the protocol reserves of an asset to a treasury account, via a CASH extraction notice.
+/// Reserves are accrued up until the current block first.
+pub fn sweep_reserves<T: Config>(asset: ChainAsset, recipient: ChainAccount) -> Result<(), Reason> {
+    internal::assets::get_asset::<T>(asset)?;
+    match recipient {
+        ChainAccount::Eth(_) | ChainAccount::Matic(_) => (),
+        _ => return Err(Reason::InvalidChain),
+    }
+
+    internal::accrual::accrue_asset::<T>(asset)?;
+
+    let principal = ProtocolReserves::get(asset);
+    if principal == CashPrincipalAmount::ZERO {
+        return Err(Reason::NoReserves);
+    }
+    let chain_id = recipient.chain_id();
+    let chain_cash_principal_new = ChainCashPrincipals::get(chain_id).add(principal)?;
+    let index = GlobalCashIndex::get();
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    ProtocolReserves::insert(asset, CashPrincipalAmount::ZERO);
+    ChainCashPrincipals::insert(chain_id, chain_cash_principal_new);
+    internal::notices::dispatch_cash_extraction_notice::<T>(recipient, principal, index);
+
+    <Module<T>>::deposit_event(Event::SweptReserves(asset, recipient, principal, index));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{common::*, *};
+    use frame_support::storage::StorageDoubleMap;
+
+    #[test]
+    fn test_sweep_reserves() {
+        new_test_ext().execute_with(|| {
+            let treasury = ChainAccount::Eth([7; 20]);
+            assert_eq!(
+                sweep_reserves::<Test>(Eth, treasury),
+                Err(Reason::AssetNotSupported)
+            );
+
+            assert_ok!(init_eth_asset());
+            assert_eq!(
+                sweep_reserves::<Test>(Eth, ChainAccount::Gate([7; 32])),
+                Err(Reason::InvalidChain)
+            );
+            assert_eq!(
+                sweep_reserves::<Test>(Eth, treasury),
+                Err(Reason::NoReserves)
+            );
+
+            let principal = CashPrincipalAmount::from_nominal("100");
+            ProtocolReserves::insert(Eth, principal);
+            ChainCashPrincipals::insert(ChainId::Eth, CashPrincipalAmount::from_nominal("50"));
+            assert_ok!(sweep_reserves::<Test>(Eth, treasury));
+
+            assert_eq!(ProtocolReserves::get(Eth), CashPrincipalAmount::ZERO);
+            assert_eq!(
+                ChainCashPrincipals::get(ChainId::Eth),
+                CashPrincipalAmount::from_nominal("150")
+            );
+            let (notice_id, _) = LatestNotice::get(ChainId::Eth).unwrap();
+            assert_eq!(
+                Notices::get(ChainId::Eth, notice_id),
+                Some(Notice::CashExtractionNotice(CashExtractionNotice::Eth {
+                    id: notice_id,
+                    parent: [0u8; 32],
+                    account: [7; 20],
+                    principal: principal.0,
+                    index: GlobalCashIndex::get().0,
+                }))
+            );
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::SweptReserves(
+                    Eth,
+                    treasury,
+                    principal,
+                    GlobalCashIndex::get()
+                )))
+            );
+        });
+    }
+}
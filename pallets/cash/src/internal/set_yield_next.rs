@@ -20,16 +20,14 @@ pub enum SetYieldNextError {
 }
 
 fn get_cash_yield_index_after<T: Config>(dt: Timestamp) -> Result<CashIndex, Reason> {
-    let cash_yield = CashYield::get();
-    let cash_index_old = GlobalCashIndex::get();
-    let increment = cash_yield.compound(dt)?;
-    Ok(cash_index_old.increment(increment.into())?)
+    Ok(CashYield::get().accrue(GlobalCashIndex::get(), dt)?)
 }
 
 pub fn set_yield_next<T: Config>(
     next_yield: APR,
     next_yield_start: Timestamp,
 ) -> Result<(), Reason> {
+    let next_yield = APR::checked(next_yield.0, APR::MAX)?;
     let now = get_recent_timestamp::<T>()?;
     let min_t = now
         .checked_add(MIN_NEXT_SYNC_TIME)
@@ -84,6 +82,18 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_yield_too_high() {
+        new_test_ext().execute_with(|| {
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(500);
+            assert_eq!(
+                set_yield_next::<Test>(APR(APR::MAX.0 + 1), 86400500),
+                Err(Reason::InvalidAPR)
+            );
+            assert_eq!(CashYieldNext::get(), None);
+        });
+    }
+
     #[test]
     fn test_too_soon_to_cancel() {
         new_test_ext().execute_with(|| {
@@ -127,7 +137,7 @@ mod tests {
                     ChainId::Eth,
                     expected_notice_id,
                     NoticeState::Pending {
-                        signature_pairs: ChainSignatureList::Eth(vec![])
+                        signature_pairs: ChainSignatureList::Eth(BoundedVec::default())
                     }
                 ),
                 notice_state
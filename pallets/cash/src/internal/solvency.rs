@@ -0,0 +1,279 @@
+use crate::{
+    chains::{ChainAccount, ChainAsset},
+    internal::{
+        self,
+        assets::{get_asset, get_price, get_price_or_zero},
+        exodus::get_collateral_and_debt,
+    },
+    log,
+    params::SOLVENCY_SNAPSHOT_INTERVAL,
+    pipeline::{load_portfolio, CashPipeline},
+    reason::{MathError, Reason},
+    require,
+    symbol::{CASH, USD},
+    types::{AssetAmount, Quantity, SolvencySnapshot, Timestamp},
+    BadDebts, Config, Event, GlobalCashIndex, LastSolvencySnapshot, Module, ProtocolReserves,
+    SupportedAssets, TotalBadDebt, TotalBorrowAssets, TotalCashPrincipal, TotalSupplyAssets,
+};
+use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
+
+/// Take a snapshot of the solvency of the protocol, once per interval.
+/// Called when the block is initialized, and thus may not fail.
+pub fn record_solvency_snapshot<T: Config>(now: Timestamp) {
+    if let Some(last) = LastSolvencySnapshot::get() {
+        if now < last.timestamp.saturating_add(SOLVENCY_SNAPSHOT_INTERVAL) {
+            return;
+        }
+    }
+
+    match get_solvency_snapshot::<T>(now) {
+        Ok(snapshot) => LastSolvencySnapshot::put(snapshot),
+        Err(err) => log!("Could not take solvency snapshot: {:?}", err),
+    }
+}
+
+/// Return the value of the collateral supplied to the protocol, versus its borrows and the CASH it owes.
+/// Assets without a price are valued at zero.
+pub fn get_solvency_snapshot<T: Config>(now: Timestamp) -> Result<SolvencySnapshot, Reason> {
+    let mut collateral_value: AssetAmount = 0;
+    let mut borrow_value: AssetAmount = 0;
+    for (asset, info) in SupportedAssets::iter() {
+        let price = get_price_or_zero::<T>(info.units());
+        let supplied = info
+            .as_quantity(TotalSupplyAssets::get(asset))
+            .mul_price(price)?;
+        let borrowed = info
+            .as_quantity(TotalBorrowAssets::get(asset))
+            .mul_price(price)?;
+        collateral_value = collateral_value
+            .checked_add(supplied.value)
+            .ok_or(MathError::Overflow)?;
+        borrow_value = borrow_value
+            .checked_add(borrowed.value)
+            .ok_or(MathError::Overflow)?;
+    }
+
+    let cash_liabilities = GlobalCashIndex::get()
+        .cash_quantity(TotalCashPrincipal::get())?
+        .mul_price(get_price_or_zero::<T>(CASH))?;
+    let bad_debt = TotalBadDebt::get();
+
+    Ok(SolvencySnapshot {
+        timestamp: now,
+        collateral_value,
+        borrow_value,
+        cash_liabilities: cash_liabilities.value,
+        bad_debt,
+    })
+}
+
+/// Return the amount in USD by which the debts of an account exceed its collateral, at face value.
+fn get_shortfall<T: Config>(account: ChainAccount) -> Result<AssetAmount, Reason> {
+    let (collateral, debt) = get_collateral_and_debt::<T>(&load_portfolio::<T>(account)?)?;
+    Ok(debt.saturating_sub(collateral))
+}
+
+/// Record the bad debt of an account whose debts exceed its collateral, or clear it once they no longer do.
+/// Called after an account has been liquidated, and thus may not fail.
+pub fn check_bad_debt<T: Config>(account: ChainAccount) {
+    let shortfall = match get_shortfall::<T>(account) {
+        Ok(shortfall) => shortfall,
+        Err(err) => {
+            log!("Could not check bad debt of {:?}: {:?}", account, err);
+            return;
+        }
+    };
+
+    if shortfall == 0 {
+        set_bad_debt(account, None);
+    } else if BadDebts::get(account) != Some(shortfall) {
+        set_bad_debt(account, Some(shortfall));
+        <Module<T>>::deposit_event(Event::BadDebtDetected(account, shortfall));
+    }
+}
+
+/// Set or clear the bad debt of an account, keeping the total in step.
+fn set_bad_debt(account: ChainAccount, shortfall: Option<AssetAmount>) {
+    let previous = BadDebts::get(account).unwrap_or(0);
+    let total = TotalBadDebt::get()
+        .saturating_sub(previous)
+        .saturating_add(shortfall.unwrap_or(0));
+    match shortfall {
+        Some(shortfall) => BadDebts::insert(account, shortfall),
+        None => BadDebts::remove(account),
+    }
+    TotalBadDebt::put(total);
+}
+
+/// Write off the bad debt of an account against the protocol reserves of an asset.
+/// The account is credited with enough CASH to cover its current shortfall, taken out of the reserves.
+pub fn write_off_bad_debt<T: Config>(
+    account: ChainAccount,
+    asset: ChainAsset,
+) -> Result<(), Reason> {
+    require!(BadDebts::contains_key(account), Reason::NoBadDebt);
+    get_asset::<T>(asset)?;
+
+    internal::accrual::accrue_asset::<T>(asset)?;
+
+    let shortfall = get_shortfall::<T>(account)?;
+    require!(shortfall > 0, Reason::NoBadDebt);
+    let index = GlobalCashIndex::get();
+    let principal = index.cash_principal_amount(
+        Quantity::new(shortfall, USD).div_price(get_price::<T>(CASH)?, CASH)?,
+    )?;
+    let reserves_new = ProtocolReserves::get(asset)
+        .sub(principal)
+        .map_err(|_| Reason::InsufficientReserves)?;
+    let pipeline = CashPipeline::new().credit_cash::<T>(account, principal)?;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    pipeline.commit::<T>();
+    ProtocolReserves::insert(asset, reserves_new);
+    set_bad_debt(account, None);
+
+    <Module<T>>::deposit_event(Event::BadDebtWrittenOff(account, asset, principal, index));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{common::*, *};
+
+    #[allow(non_upper_case_globals)]
+    const account_a: ChainAccount = ChainAccount::Eth([1u8; 20]);
+
+    #[allow(non_upper_case_globals)]
+    const account_b: ChainAccount = ChainAccount::Eth([2u8; 20]);
+
+    #[test]
+    fn test_record_solvency_snapshot() {
+        new_test_ext().execute_with(|| {
+            init_eth_asset().unwrap();
+            init_asset_balance(Eth, account_a, eth.as_quantity_nominal("1").value as i128);
+            init_asset_balance(
+                Eth,
+                account_b,
+                -(eth.as_quantity_nominal("0.5").value as i128),
+            );
+            TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("1000"));
+            set_bad_debt(account_b, Some(Quantity::from_nominal("25", USD).value));
+
+            let start = 1000;
+            record_solvency_snapshot::<Test>(start);
+            let expected = SolvencySnapshot {
+                timestamp: start,
+                collateral_value: Quantity::from_nominal("2000", USD).value,
+                borrow_value: Quantity::from_nominal("1000", USD).value,
+                cash_liabilities: Quantity::from_nominal("1000", USD).value,
+                bad_debt: Quantity::from_nominal("25", USD).value,
+            };
+            assert_eq!(LastSolvencySnapshot::get(), Some(expected));
+
+            // nothing is recorded until the interval has passed
+            set_bad_debt(account_b, None);
+            record_solvency_snapshot::<Test>(start + SOLVENCY_SNAPSHOT_INTERVAL - 1);
+            assert_eq!(LastSolvencySnapshot::get(), Some(expected));
+
+            record_solvency_snapshot::<Test>(start + SOLVENCY_SNAPSHOT_INTERVAL);
+            assert_eq!(
+                LastSolvencySnapshot::get(),
+                Some(SolvencySnapshot {
+                    timestamp: start + SOLVENCY_SNAPSHOT_INTERVAL,
+                    bad_debt: 0,
+                    ..expected
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn test_check_bad_debt() {
+        new_test_ext().execute_with(|| {
+            init_eth_asset().unwrap();
+            init_asset_balance(Eth, account_a, eth.as_quantity_nominal("1").value as i128);
+            CashPrincipals::insert(account_a, CashPrincipal::from_nominal("-3000"));
+
+            let shortfall = Quantity::from_nominal("1000", USD).value;
+            check_bad_debt::<Test>(account_a);
+            assert_eq!(BadDebts::get(account_a), Some(shortfall));
+            assert_eq!(TotalBadDebt::get(), shortfall);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::BadDebtDetected(
+                    account_a, shortfall
+                )))
+            );
+
+            // the event is not repeated while the shortfall is unchanged
+            let event_count = System::events().len();
+            check_bad_debt::<Test>(account_a);
+            assert_eq!(System::events().len(), event_count);
+
+            CashPrincipals::insert(account_a, CashPrincipal::from_nominal("-1500"));
+            check_bad_debt::<Test>(account_a);
+            assert_eq!(BadDebts::get(account_a), None);
+            assert_eq!(TotalBadDebt::get(), 0);
+        });
+    }
+
+    #[test]
+    fn test_write_off_bad_debt() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                write_off_bad_debt::<Test>(account_a, Eth),
+                Err(Reason::NoBadDebt)
+            );
+
+            init_eth_asset().unwrap();
+            init_asset_balance(Eth, account_a, eth.as_quantity_nominal("1").value as i128);
+            CashPrincipals::insert(account_a, CashPrincipal::from_nominal("-3000"));
+            TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("3000"));
+            check_bad_debt::<Test>(account_a);
+
+            assert_eq!(
+                write_off_bad_debt::<Test>(account_a, Wbtc),
+                Err(Reason::AssetNotSupported)
+            );
+            ProtocolReserves::insert(Eth, CashPrincipalAmount::from_nominal("999"));
+            assert_eq!(
+                write_off_bad_debt::<Test>(account_a, Eth),
+                Err(Reason::InsufficientReserves)
+            );
+
+            ProtocolReserves::insert(Eth, CashPrincipalAmount::from_nominal("1500"));
+            assert_ok!(write_off_bad_debt::<Test>(account_a, Eth));
+
+            let principal = CashPrincipalAmount::from_nominal("1000");
+            assert_eq!(
+                ProtocolReserves::get(Eth),
+                CashPrincipalAmount::from_nominal("500")
+            );
+            assert_eq!(
+                CashPrincipals::get(account_a),
+                CashPrincipal::from_nominal("-2000")
+            );
+            assert_eq!(
+                TotalCashPrincipal::get(),
+                CashPrincipalAmount::from_nominal("2000")
+            );
+            assert_eq!(BadDebts::get(account_a), None);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::BadDebtWrittenOff(
+                    account_a,
+                    Eth,
+                    principal,
+                    GlobalCashIndex::get()
+                )))
+            );
+            assert_eq!(
+                write_off_bad_debt::<Test>(account_a, Eth),
+                Err(Reason::NoBadDebt)
+            );
+        });
+    }
+}
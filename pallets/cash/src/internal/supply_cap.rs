@@ -7,6 +7,7 @@ use crate::{
 };
 
 pub fn set_supply_cap<T: Config>(asset: ChainAsset, cap: AssetAmount) -> Result<(), Reason> {
+    internal::chain_status::require_active(asset.chain_id())?;
     let asset_info = internal::assets::get_asset::<T>(asset)?;
     internal::assets::support_asset::<T>(AssetInfo {
         supply_cap: cap,
@@ -82,7 +83,7 @@ mod tests {
                     ChainId::Eth,
                     expected_notice_id,
                     NoticeState::Pending {
-                        signature_pairs: ChainSignatureList::Eth(vec![])
+                        signature_pairs: ChainSignatureList::Eth(BoundedVec::default())
                     }
                 ),
                 notice_state
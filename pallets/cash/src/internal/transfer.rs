@@ -1,14 +1,19 @@
 use crate::{
-    chains::ChainAccount,
-    internal::{assets::get_value, miner::get_some_miner},
+    chains::{ChainAccount, ChainAsset},
+    internal::{
+        account_history::deposit_account_event,
+        assets::{get_asset, get_value},
+        miner::get_some_miner,
+    },
     params::{MIN_TX_VALUE, TRANSFER_FEE},
     pipeline::CashPipeline,
     reason::Reason,
     require, require_min_tx_value,
-    types::{AssetInfo, AssetQuantity, CashPrincipalAmount},
-    Config, Event, GlobalCashIndex, Module,
+    types::{AssetAmount, AssetInfo, AssetQuantity, CashIndex, CashPrincipalAmount},
+    AssetsWithNonZeroBalance, Config, Event, GlobalCashIndex, Module, PortfolioConsents,
 };
-use frame_support::storage::StorageValue;
+use frame_support::storage::{IterableStorageDoubleMap, StorageMap, StorageValue};
+use our_std::vec::Vec;
 
 pub fn transfer_internal<T: Config>(
     asset: AssetInfo,
@@ -25,17 +30,17 @@ pub fn transfer_internal<T: Config>(
     CashPipeline::new()
         .transfer_asset::<T>(sender, recipient, asset.asset, amount)?
         .transfer_cash::<T>(sender, miner, fee_principal)?
-        .check_collateralized::<T>(sender)?
+        .check_collateralized_fresh::<T>(sender)?
         .commit::<T>();
 
-    <Module<T>>::deposit_event(Event::Transfer(
+    deposit_account_event::<T>(Event::Transfer(
         asset.asset,
         sender,
         recipient,
         amount.value,
     ));
-    <Module<T>>::deposit_event(Event::TransferCash(sender, miner, fee_principal, index));
-    <Module<T>>::deposit_event(Event::MinerPaid(miner, fee_principal));
+    deposit_account_event::<T>(Event::TransferCash(sender, miner, fee_principal, index));
+    <Module<T>>::deposit_event(Event::MinerPaid(miner, fee_principal, index));
 
     Ok(())
 }
@@ -55,12 +60,133 @@ pub fn transfer_cash_principal_internal<T: Config>(
     CashPipeline::new()
         .transfer_cash::<T>(sender, recipient, principal)?
         .transfer_cash::<T>(sender, miner, fee_principal)?
-        .check_collateralized::<T>(sender)?
+        .check_collateralized_fresh::<T>(sender)?
         .commit::<T>();
 
-    <Module<T>>::deposit_event(Event::TransferCash(sender, recipient, principal, index));
-    <Module<T>>::deposit_event(Event::TransferCash(sender, miner, fee_principal, index));
-    <Module<T>>::deposit_event(Event::MinerPaid(miner, fee_principal));
+    deposit_account_event::<T>(Event::TransferCash(sender, recipient, principal, index));
+    deposit_account_event::<T>(Event::TransferCash(sender, miner, fee_principal, index));
+    <Module<T>>::deposit_event(Event::MinerPaid(miner, fee_principal, index));
+
+    Ok(())
+}
+
+/// Accept the entire portfolio of the sender, including its debts, into the recipient.
+pub fn accept_portfolio_internal<T: Config>(
+    recipient: ChainAccount,
+    sender: ChainAccount,
+) -> Result<(), Reason> {
+    require!(recipient != sender, Reason::SelfTransfer);
+
+    PortfolioConsents::insert(recipient, sender);
+
+    <Module<T>>::deposit_event(Event::AcceptPortfolio(recipient, sender));
+
+    Ok(())
+}
+
+/// The balances moved from one account to another by a portfolio move, reported once committed.
+pub struct PortfolioMove {
+    pub assets: Vec<(ChainAsset, ChainAccount, ChainAccount, AssetAmount)>,
+    pub cash: Option<(ChainAccount, ChainAccount, CashPrincipalAmount)>,
+}
+
+impl PortfolioMove {
+    pub fn deposit_events<T: Config>(self, index: CashIndex) {
+        for (asset, from, to, amount) in self.assets {
+            deposit_account_event::<T>(Event::Transfer(asset, from, to, amount));
+        }
+        if let Some((from, to, principal)) = self.cash {
+            deposit_account_event::<T>(Event::TransferCash(from, to, principal, index));
+        }
+    }
+}
+
+/// Move every asset balance and the CASH of the sender to the recipient, within the pipeline.
+/// Debts may only be moved if allowed, in which case the recipient must remain collateralized.
+pub fn move_portfolio<T: Config>(
+    mut pipeline: CashPipeline,
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    allow_debt: bool,
+) -> Result<(CashPipeline, PortfolioMove), Reason> {
+    let mut moved = PortfolioMove {
+        assets: Vec::new(),
+        cash: None,
+    };
+    let mut has_debt = false;
+
+    let assets: Vec<_> = AssetsWithNonZeroBalance::iter_prefix(sender).collect();
+    for (chain_asset, _) in assets {
+        let asset = get_asset::<T>(chain_asset)?;
+        let balance = pipeline.state.get_asset_balance::<T>(asset, sender);
+        let amount = balance.value.abs() as AssetAmount;
+        let (from, to) = if balance.value > 0 {
+            (sender, recipient)
+        } else if balance.value < 0 {
+            require!(allow_debt, Reason::PortfolioConsentRequired);
+            has_debt = true;
+            (recipient, sender)
+        } else {
+            continue;
+        };
+        pipeline =
+            pipeline.transfer_asset::<T>(from, to, chain_asset, asset.as_quantity(amount))?;
+        moved.assets.push((chain_asset, from, to, amount));
+    }
+
+    // Moving the asset balances settles their interest into CASH, so read the principal last
+    let principal = pipeline.state.get_cash_principal::<T>(sender);
+    if principal.0 > 0 {
+        moved.cash = Some((sender, recipient, principal.amount_withdrawable()?));
+    } else if principal.0 < 0 {
+        require!(allow_debt, Reason::PortfolioConsentRequired);
+        has_debt = true;
+        moved.cash = Some((recipient, sender, principal.amount_repayable()?));
+    }
+    if let Some((from, to, principal)) = moved.cash {
+        pipeline = pipeline.transfer_cash::<T>(from, to, principal)?;
+    }
+
+    if has_debt {
+        pipeline = pipeline.check_collateralized_fresh::<T>(recipient)?;
+    }
+
+    Ok((pipeline, moved))
+}
+
+/// Move every asset balance and the CASH of the sender to the recipient, atomically.
+/// The transfer fee is charged to the sender first, so that it is left with nothing.
+/// Debts may only be moved if the recipient has accepted the portfolio of the sender.
+pub fn transfer_portfolio_internal<T: Config>(
+    sender: ChainAccount,
+    recipient: ChainAccount,
+) -> Result<(), Reason> {
+    require!(sender != recipient, Reason::SelfTransfer);
+
+    let miner = get_some_miner::<T>();
+    let index = GlobalCashIndex::get();
+    let fee_principal = index.cash_principal_amount(TRANSFER_FEE)?;
+    let consented = PortfolioConsents::get(recipient) == Some(sender);
+
+    let pipeline = if sender == miner {
+        CashPipeline::new()
+    } else {
+        CashPipeline::new().transfer_cash::<T>(sender, miner, fee_principal)?
+    };
+    let (pipeline, moved) = move_portfolio::<T>(pipeline, sender, recipient, consented)?;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    pipeline.commit::<T>();
+    if consented {
+        PortfolioConsents::remove(recipient);
+    }
+
+    moved.deposit_events::<T>(index);
+    if sender != miner {
+        deposit_account_event::<T>(Event::TransferCash(sender, miner, fee_principal, index));
+        <Module<T>>::deposit_event(Event::MinerPaid(miner, fee_principal, index));
+    }
 
     Ok(())
 }
@@ -69,7 +195,7 @@ pub fn transfer_cash_principal_internal<T: Config>(
 mod tests {
     use super::*;
     use crate::{
-        tests::{assets::*, common::*, mock::*},
+        tests::{assets::*, common::*, mock::*, *},
         types::*,
         *,
     };
@@ -398,4 +524,88 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn test_accept_portfolio_internal() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                accept_portfolio_internal::<Test>(account_b, account_b),
+                Err(Reason::SelfTransfer)
+            );
+            assert_ok!(accept_portfolio_internal::<Test>(account_b, account_a));
+            assert_eq!(PortfolioConsents::get(account_b), Some(account_a));
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(crate::Event::AcceptPortfolio(
+                    account_b, account_a
+                )))
+            );
+        });
+    }
+
+    #[test]
+    fn test_transfer_portfolio_internal_ok() {
+        new_test_ext().execute_with(|| {
+            init_usdc_asset().unwrap();
+            init_asset_balance(Usdc, account_a, Balance::from_nominal("10", USD).value);
+            init_cash(account_a, CashPrincipal::from_nominal("2"));
+
+            assert_ok!(transfer_portfolio_internal::<Test>(account_a, account_b));
+
+            assert_eq!(AssetBalances::get(Usdc, account_a), 0);
+            assert_eq!(
+                AssetBalances::get(Usdc, account_b),
+                Balance::from_nominal("10", USD).value
+            );
+            assert_eq!(
+                AssetsWithNonZeroBalance::iter_prefix(account_a).collect::<Vec<_>>(),
+                vec![]
+            );
+            assert_eq!(
+                CashPrincipals::get(account_a),
+                CashPrincipal::from_nominal("0")
+            );
+            assert_eq!(
+                CashPrincipals::get(account_b),
+                CashPrincipal::from_nominal("1.99")
+            );
+            assert_eq!(
+                CashPrincipals::get(miner),
+                CashPrincipal::from_nominal("0.01")
+            );
+        });
+    }
+
+    #[test]
+    fn test_transfer_portfolio_internal_debt_requires_consent() {
+        new_test_ext().execute_with(|| {
+            init_usdc_asset().unwrap();
+            init_asset_balance(Usdc, account_a, Balance::from_nominal("10", USD).value);
+            init_asset_balance(Usdc, account_b, Balance::from_nominal("100", USD).value);
+
+            // Paying the fee leaves a CASH debt to move
+            assert_eq!(
+                transfer_portfolio_internal::<Test>(account_a, account_b),
+                Err(Reason::PortfolioConsentRequired)
+            );
+
+            assert_ok!(accept_portfolio_internal::<Test>(account_b, account_a));
+            assert_ok!(transfer_portfolio_internal::<Test>(account_a, account_b));
+
+            assert_eq!(AssetBalances::get(Usdc, account_a), 0);
+            assert_eq!(
+                AssetBalances::get(Usdc, account_b),
+                Balance::from_nominal("110", USD).value
+            );
+            assert_eq!(
+                CashPrincipals::get(account_a),
+                CashPrincipal::from_nominal("0")
+            );
+            assert_eq!(
+                CashPrincipals::get(account_b),
+                CashPrincipal::from_nominal("-0.01")
+            );
+            assert_eq!(PortfolioConsents::get(account_b), None);
+        });
+    }
 }
@@ -1,16 +1,21 @@
 use crate::{
-    chains::{Chain, Ethereum},
-    core::recover_validator,
+    chains::{super_majority, Chain, ChainBlock, ChainBlockTally, Ethereum},
+    core::{get_validator_set, recover_validator},
     internal,
-    notices::EncodeNotice,
-    params::{UNSIGNED_TXS_LONGEVITY, UNSIGNED_TXS_PRIORITY},
+    notices::{EncodeNotice, NoticeState},
+    params::{UNSIGNED_TXS_LONGEVITY, UNSIGNED_TXS_PRIORITY, UNSIGNED_TXS_QUORUM_PRIORITY},
     reason::Reason,
-    AllowedNextCodeHash, Call, Config, Notices, Validators,
+    types::{SignersSet, ValidatorKeys},
+    AllowedNextCodeHash, Call, ChilledValidators, Config, LastProcessedBlock, NoticeStates,
+    Notices, PendingChainBlocks, QuorumThreshold, Validators,
 };
 use codec::Encode;
-use frame_support::storage::{IterableStorageMap, StorageDoubleMap, StorageValue};
-use our_std::{log, RuntimeDebug};
-use sp_runtime::transaction_validity::{TransactionSource, TransactionValidity, ValidTransaction};
+use frame_support::storage::{IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue};
+use our_std::{log, vec::Vec, RuntimeDebug};
+use sp_runtime::{
+    transaction_validity::{TransactionSource, TransactionValidity, ValidTransaction},
+    Permill,
+};
 
 #[derive(Eq, PartialEq, RuntimeDebug, Clone, Copy)]
 pub enum ValidationError {
@@ -22,6 +27,37 @@ pub enum ValidationError {
     InvalidPrice(Reason),
     UnknownNotice,
     InvalidTrxRequest(Reason),
+    MissingLastBlock,
+    StaleChainBlocks,
+    StaleNotice,
+    DuplicateSignature,
+    InvalidEquivocation(Reason),
+    InvalidExecutionFee(Reason),
+    InvalidValidatorMetadata(Reason),
+}
+
+/// Whether support from the validator would be the one to give a block enough support.
+fn completes_support(
+    prior: Option<&ChainBlockTally>,
+    block: ChainBlock,
+    validator: &ValidatorKeys,
+    validator_set: &SignersSet,
+    threshold: Permill,
+) -> bool {
+    let mut tally = match prior {
+        Some(prior) if prior.block == block => {
+            if prior.has_enough_support(validator_set, threshold) {
+                return false;
+            }
+            prior.clone()
+        }
+        _ => ChainBlockTally {
+            block,
+            support: SignersSet::new(),
+            dissent: SignersSet::new(),
+        },
+    };
+    tally.add_support(validator).is_ok() && tally.has_enough_support(validator_set, threshold)
 }
 
 pub fn check_validation_failure<T: Config>(
@@ -88,13 +124,49 @@ pub fn validate_unsigned<T: Config>(
 
             let validator = recover_validator::<T>(&blocks.encode(), *signature)
                 .map_err(|_| ValidationError::InvalidValidator)?;
+            if ChilledValidators::contains_key(&validator.substrate_id) {
+                return Err(ValidationError::InvalidValidator);
+            }
+            let validator_set =
+                get_validator_set::<T>().map_err(|_| ValidationError::InvalidValidator)?;
+            let last_block =
+                LastProcessedBlock::get(chain_id).ok_or(ValidationError::MissingLastBlock)?;
+            let pending_blocks = PendingChainBlocks::get(chain_id);
+            let threshold = QuorumThreshold::get();
+
+            // Only blocks past the last processed block which the validator doesn't yet support count
+            let mut fresh_numbers = Vec::new();
+            let mut completes_quorum = false;
+            for block in blocks.blocks() {
+                if block.number() <= last_block.number() {
+                    continue;
+                }
+                let offset = (block.number() - last_block.number() - 1) as usize;
+                let prior = pending_blocks.get(offset);
+                if let Some(prior) = prior {
+                    if prior.block == block && prior.has_supporter(&validator.substrate_id) {
+                        continue;
+                    }
+                }
+                fresh_numbers.push(block.number());
+                completes_quorum |=
+                    completes_support(prior, block, &validator, &validator_set, threshold);
+            }
+
+            if fresh_numbers.is_empty() {
+                return Err(ValidationError::StaleChainBlocks);
+            }
 
             let mut validity = ValidTransaction::with_tag_prefix("Gateway::receive_chain_blocks")
-                .priority(UNSIGNED_TXS_PRIORITY)
+                .priority(if completes_quorum {
+                    UNSIGNED_TXS_QUORUM_PRIORITY
+                } else {
+                    UNSIGNED_TXS_PRIORITY
+                })
                 .longevity(UNSIGNED_TXS_LONGEVITY)
                 .propagate(true);
 
-            for block_number in blocks.block_numbers() {
+            for block_number in fresh_numbers {
                 validity =
                     validity.and_provides((validator.substrate_id.clone(), block_number, chain_id));
             }
@@ -115,6 +187,69 @@ pub fn validate_unsigned<T: Config>(
             )
         }
 
+        Call::report_equivocation(blocks, signature) => {
+            let (validator, _offense) =
+                internal::equivocation::check_equivocation::<T>(blocks, *signature)
+                    .map_err(ValidationError::InvalidEquivocation)?;
+            Ok(
+                ValidTransaction::with_tag_prefix("Gateway::report_equivocation")
+                    .priority(UNSIGNED_TXS_PRIORITY)
+                    .longevity(UNSIGNED_TXS_LONGEVITY)
+                    .and_provides(validator.substrate_id)
+                    .propagate(true)
+                    .build(),
+            )
+        }
+
+        Call::receive_anchor_prices(report, signature) => {
+            let validator = recover_validator::<T>(&report.encode(), *signature)
+                .map_err(|_| ValidationError::InvalidValidator)?;
+            for (ticker, _) in report.prices.iter() {
+                pallet_oracle::anchor::check_anchor_price::<T>(*ticker, report.timestamp)
+                    .map_err(|err| ValidationError::InvalidPrice(err.into()))?;
+            }
+            Ok(
+                ValidTransaction::with_tag_prefix("Gateway::receive_anchor_prices")
+                    .priority(UNSIGNED_TXS_PRIORITY)
+                    .longevity(UNSIGNED_TXS_LONGEVITY)
+                    .and_provides((validator.substrate_id, report.timestamp))
+                    .propagate(true)
+                    .build(),
+            )
+        }
+
+        Call::receive_execution_fees(report, signature) => {
+            let validator = recover_validator::<T>(&report.encode(), *signature)
+                .map_err(|_| ValidationError::InvalidValidator)?;
+            for (chain_id, _) in report.fees.iter() {
+                internal::execution_fees::check_execution_fee(*chain_id, report.timestamp)
+                    .map_err(ValidationError::InvalidExecutionFee)?;
+            }
+            Ok(
+                ValidTransaction::with_tag_prefix("Gateway::receive_execution_fees")
+                    .priority(UNSIGNED_TXS_PRIORITY)
+                    .longevity(UNSIGNED_TXS_LONGEVITY)
+                    .and_provides((validator.substrate_id, report.timestamp))
+                    .propagate(true)
+                    .build(),
+            )
+        }
+
+        Call::set_validator_metadata(metadata, signature) => {
+            let validator = recover_validator::<T>(&metadata.encode(), *signature)
+                .map_err(|_| ValidationError::InvalidValidator)?;
+            internal::validator_metadata::check_validator_metadata(&validator, metadata)
+                .map_err(ValidationError::InvalidValidatorMetadata)?;
+            Ok(
+                ValidTransaction::with_tag_prefix("Gateway::set_validator_metadata")
+                    .priority(UNSIGNED_TXS_PRIORITY)
+                    .longevity(UNSIGNED_TXS_LONGEVITY)
+                    .and_provides((validator.substrate_id, metadata.timestamp))
+                    .propagate(true)
+                    .build(),
+            )
+        }
+
         Call::exec_trx_request(request, signature, nonce) => {
             let signer_res = internal::exec_trx_request::is_minimally_valid_trx_request::<T>(
                 request.to_vec(),
@@ -158,18 +293,42 @@ pub fn validate_unsigned<T: Config>(
                 .map_err(|_| ValidationError::InvalidValidator)?;
 
             // XXX what happens if not eth here? seems broken
-            if Validators::iter().any(|(_, v)| v.eth_address == validator.eth_address) {
-                Ok(
-                    ValidTransaction::with_tag_prefix("Gateway::publish_signature")
-                        .priority(UNSIGNED_TXS_PRIORITY)
-                        .longevity(UNSIGNED_TXS_LONGEVITY)
-                        .and_provides((chain_id, notice_id, signature))
-                        .propagate(true)
-                        .build(),
-                )
-            } else {
-                Err(ValidationError::InvalidValidator)
+            let validators: Vec<ValidatorKeys> = Validators::iter().map(|(_, v)| v).collect();
+            if !validators
+                .iter()
+                .any(|v| v.eth_address == validator.eth_address)
+            {
+                return Err(ValidationError::InvalidValidator);
+            }
+
+            let signature_pairs = match NoticeStates::get(chain_id, notice_id) {
+                NoticeState::Pending { signature_pairs } => signature_pairs,
+                NoticeState::Executed => return Err(ValidationError::StaleNotice),
+                NoticeState::Missing => return Err(ValidationError::UnknownNotice),
+            };
+            if signature_pairs.has_validator_signature(*chain_id, &validator) {
+                return Err(ValidationError::DuplicateSignature);
             }
+
+            let signatures = validators
+                .iter()
+                .filter(|v| signature_pairs.has_validator_signature(*chain_id, v))
+                .count();
+            let priority =
+                if signatures + 1 == super_majority(validators.len(), QuorumThreshold::get()) {
+                    UNSIGNED_TXS_QUORUM_PRIORITY
+                } else {
+                    UNSIGNED_TXS_PRIORITY
+                };
+
+            Ok(
+                ValidTransaction::with_tag_prefix("Gateway::publish_signature")
+                    .priority(priority)
+                    .longevity(UNSIGNED_TXS_LONGEVITY)
+                    .and_provides((chain_id, notice_id, validator.substrate_id))
+                    .propagate(true)
+                    .build(),
+            )
         }
         _ => Err(ValidationError::InvalidCall),
     }
@@ -180,6 +339,11 @@ mod tests {
     use super::*;
     use crate::{core::validator_sign, tests::*, Call};
     use ethereum_client::EthereumBlock;
+    use pallet_oracle::{
+        error::OracleError,
+        types::{AnchorConfig, AnchorReport},
+        AnchorConfigs, AnchorPrices,
+    };
 
     #[test]
     fn test_set_miner_external() {
@@ -310,6 +474,15 @@ mod tests {
                     eth_address,
                 },
             );
+            LastProcessedBlock::insert(
+                ChainId::Eth,
+                ChainBlock::Eth(EthereumBlock {
+                    hash: [0; 32],
+                    parent_hash: [0; 32],
+                    number: 0,
+                    events: vec![],
+                }),
+            );
 
             let blocks = ChainBlocks::Eth(vec![
                 EthereumBlock {
@@ -332,8 +505,9 @@ mod tests {
                 },
             ]);
             let signature = validator_sign::<Test>(&blocks.encode()).unwrap();
+            // The sole validator completes the quorum for the first block
             let exp = ValidTransaction::with_tag_prefix("Gateway::receive_chain_blocks")
-                .priority(100)
+                .priority(UNSIGNED_TXS_QUORUM_PRIORITY)
                 .longevity(32)
                 .propagate(true)
                 .and_provides((substrate_id.clone(), 1u64, ChainId::Eth))
@@ -351,6 +525,135 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_receive_chain_blocks_stale() {
+        new_test_ext().execute_with(|| {
+            let substrate_id = AccountId32::new([1u8; 32]);
+            let eth_address = <Ethereum as Chain>::signer_address().unwrap();
+            let validator = ValidatorKeys {
+                substrate_id: substrate_id.clone(),
+                eth_address,
+            };
+            Validators::insert(substrate_id.clone(), validator.clone());
+            Validators::insert(
+                AccountId32::new([2u8; 32]),
+                ValidatorKeys {
+                    substrate_id: AccountId32::new([2u8; 32]),
+                    eth_address: [2; 20],
+                },
+            );
+
+            let block_1 = EthereumBlock {
+                hash: [1; 32],
+                parent_hash: [0; 32],
+                number: 1,
+                events: vec![],
+            };
+            let block_2 = EthereumBlock {
+                hash: [2; 32],
+                parent_hash: [1; 32],
+                number: 2,
+                events: vec![],
+            };
+            let blocks = ChainBlocks::Eth(vec![block_1.clone(), block_2.clone()]);
+            let signature = validator_sign::<Test>(&blocks.encode()).unwrap();
+
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::receive_chain_blocks::<Test>(blocks.clone(), signature)
+                ),
+                Err(ValidationError::MissingLastBlock)
+            );
+
+            // Block 1 was processed, and the validator already supports block 2
+            LastProcessedBlock::insert(ChainId::Eth, ChainBlock::Eth(block_1));
+            PendingChainBlocks::insert(
+                ChainId::Eth,
+                vec![ChainBlockTally::new(ChainBlock::Eth(block_2), &validator).unwrap()],
+            );
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::receive_chain_blocks::<Test>(blocks, signature)
+                ),
+                Err(ValidationError::StaleChainBlocks)
+            );
+        });
+    }
+
+    #[test]
+    fn test_receive_anchor_prices_is_validator() {
+        new_test_ext().execute_with(|| {
+            let substrate_id = AccountId32::new([1u8; 32]);
+            let eth_address = <Ethereum as Chain>::signer_address().unwrap();
+            let report = AnchorReport {
+                timestamp: 1000,
+                prices: vec![(ETH.ticker, 2000_000000)],
+            };
+            let signature = validator_sign::<Test>(&report.encode()).unwrap();
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::receive_anchor_prices::<Test>(report.clone(), signature)
+                ),
+                Err(ValidationError::InvalidValidator)
+            );
+
+            Validators::insert(
+                substrate_id.clone(),
+                ValidatorKeys {
+                    substrate_id: substrate_id.clone(),
+                    eth_address,
+                },
+            );
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1000);
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::receive_anchor_prices::<Test>(report.clone(), signature)
+                ),
+                Err(ValidationError::InvalidPrice(Reason::OracleError(
+                    OracleError::NoAnchor
+                )))
+            );
+
+            let config = AnchorConfig {
+                pool: [0x8a; 20],
+                base_is_token0: false,
+                base_decimals: 18,
+                quote_decimals: 6,
+                twap_period: 1800,
+                band_bps: 1000,
+            };
+            AnchorConfigs::insert(ETH.ticker, config);
+            let exp = ValidTransaction::with_tag_prefix("Gateway::receive_anchor_prices")
+                .priority(100)
+                .longevity(32)
+                .and_provides((substrate_id, 1000u64))
+                .propagate(true)
+                .build();
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::receive_anchor_prices::<Test>(report.clone(), signature)
+                ),
+                Ok(exp)
+            );
+
+            AnchorPrices::insert(ETH.ticker, (2000_000000, 1000));
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::receive_anchor_prices::<Test>(report, signature)
+                ),
+                Err(ValidationError::InvalidPrice(Reason::OracleError(
+                    OracleError::StalePrice
+                )))
+            );
+        });
+    }
+
     #[test]
     fn test_exec_trx_request_nonce_zero() {
         new_test_ext().execute_with(|| {
@@ -546,7 +849,9 @@ mod tests {
             };
             let signer = <Ethereum as Chain>::signer_address().unwrap();
             let notice_state = NoticeState::Pending {
-                signature_pairs: ChainSignatureList::Eth(vec![(signer, *eth_signature)]),
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![(signer, *eth_signature)].try_into().unwrap(),
+                ),
             };
             NoticeStates::insert(chain_id, notice_id, notice_state);
             Notices::insert(chain_id, notice_id, notice);
@@ -580,7 +885,9 @@ mod tests {
             };
             let signer = <Ethereum as Chain>::signer_address().unwrap();
             let notice_state = NoticeState::Pending {
-                signature_pairs: ChainSignatureList::Eth(vec![(signer, eth_signature)]),
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![(signer, eth_signature)].try_into().unwrap(),
+                ),
             };
             NoticeStates::insert(chain_id, notice_id, notice_state);
             Notices::insert(chain_id, notice_id, notice);
@@ -609,12 +916,8 @@ mod tests {
             });
             let signer = <Ethereum as Chain>::signer_address().unwrap();
             let signature = notice.sign_notice().unwrap();
-            let eth_signature = match signature {
-                ChainSignature::Eth(a) => a,
-                _ => panic!("invalid signature"),
-            };
             let notice_state = NoticeState::Pending {
-                signature_pairs: ChainSignatureList::Eth(vec![(signer, eth_signature)]),
+                signature_pairs: ChainSignatureList::Eth(vec![].try_into().unwrap()),
             };
             NoticeStates::insert(chain_id, notice_id, notice_state);
             Notices::insert(chain_id, notice_id, notice);
@@ -622,15 +925,77 @@ mod tests {
             Validators::insert(
                 substrate_id.clone(),
                 ValidatorKeys {
-                    substrate_id,
+                    substrate_id: substrate_id.clone(),
                     eth_address: signer,
                 },
             );
+            Validators::insert(
+                AccountId32::new([1u8; 32]),
+                ValidatorKeys {
+                    substrate_id: AccountId32::new([1u8; 32]),
+                    eth_address: [1; 20],
+                },
+            );
 
+            // The first of two signatures does not complete the quorum
             let exp = ValidTransaction::with_tag_prefix("Gateway::publish_signature")
                 .priority(UNSIGNED_TXS_PRIORITY)
                 .longevity(UNSIGNED_TXS_LONGEVITY)
-                .and_provides((chain_id, notice_id, signature))
+                .and_provides((chain_id, notice_id, substrate_id.clone()))
+                .propagate(true)
+                .build();
+
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::InBlock {},
+                    &Call::publish_signature::<Test>(chain_id, notice_id, signature),
+                ),
+                Ok(exp)
+            );
+        });
+    }
+
+    #[test]
+    fn test_publish_signature_completes_quorum() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let notice_id = NoticeId(5, 6);
+            let notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
+                id: NoticeId(80, 1),
+                parent: [3u8; 32],
+                asset: [1; 20],
+                amount: 100,
+                account: [2; 20],
+            });
+            let signer = <Ethereum as Chain>::signer_address().unwrap();
+            let signature = notice.sign_notice().unwrap();
+            let notice_state = NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![([1; 20], [1; 65])].try_into().unwrap(),
+                ),
+            };
+            NoticeStates::insert(chain_id, notice_id, notice_state);
+            Notices::insert(chain_id, notice_id, notice);
+            let substrate_id = AccountId32::new([0u8; 32]);
+            Validators::insert(
+                substrate_id.clone(),
+                ValidatorKeys {
+                    substrate_id: substrate_id.clone(),
+                    eth_address: signer,
+                },
+            );
+            Validators::insert(
+                AccountId32::new([1u8; 32]),
+                ValidatorKeys {
+                    substrate_id: AccountId32::new([1u8; 32]),
+                    eth_address: [1; 20],
+                },
+            );
+
+            let exp = ValidTransaction::with_tag_prefix("Gateway::publish_signature")
+                .priority(UNSIGNED_TXS_QUORUM_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides((chain_id, notice_id, substrate_id))
                 .propagate(true)
                 .build();
 
@@ -644,6 +1009,59 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_publish_signature_duplicate_or_stale() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let notice_id = NoticeId(5, 6);
+            let notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
+                id: NoticeId(80, 1),
+                parent: [3u8; 32],
+                asset: [1; 20],
+                amount: 100,
+                account: [2; 20],
+            });
+            let signer = <Ethereum as Chain>::signer_address().unwrap();
+            let signature = notice.sign_notice().unwrap();
+            let eth_signature = match signature {
+                ChainSignature::Eth(a) => a,
+                _ => panic!("invalid signature"),
+            };
+            let notice_state = NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Eth(
+                    vec![(signer, eth_signature)].try_into().unwrap(),
+                ),
+            };
+            NoticeStates::insert(chain_id, notice_id, notice_state);
+            Notices::insert(chain_id, notice_id, notice);
+            let substrate_id = AccountId32::new([0u8; 32]);
+            Validators::insert(
+                substrate_id.clone(),
+                ValidatorKeys {
+                    substrate_id,
+                    eth_address: signer,
+                },
+            );
+
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::InBlock {},
+                    &Call::publish_signature::<Test>(chain_id, notice_id, signature),
+                ),
+                Err(ValidationError::DuplicateSignature)
+            );
+
+            NoticeStates::insert(chain_id, notice_id, NoticeState::Executed);
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::InBlock {},
+                    &Call::publish_signature::<Test>(chain_id, notice_id, signature),
+                ),
+                Err(ValidationError::StaleNotice)
+            );
+        });
+    }
+
     #[test]
     fn test_other() {
         new_test_ext().execute_with(|| {
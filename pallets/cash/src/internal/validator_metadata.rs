@@ -0,0 +1,218 @@
+use crate::{
+    chains::{ChainHash, ChainSignature},
+    core::recover_validator,
+    notices::NoticeState,
+    params::{MAX_VALIDATOR_NAME_LEN, MAX_VALIDATOR_WEBSITE_LEN},
+    reason::Reason,
+    require,
+    types::{ValidatorKeys, ValidatorMetadata},
+    Config, Event, Module, NoticeHashes, NoticeStates, ValidatorMetadatas, Validators,
+};
+use codec::Encode;
+use frame_support::storage::{IterableStorageMap, StorageDoubleMap, StorageMap};
+use our_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+/// Check that the metadata is well-formed, and newer than any the validator already published.
+pub fn check_validator_metadata(
+    validator: &ValidatorKeys,
+    metadata: &ValidatorMetadata,
+) -> Result<(), Reason> {
+    require!(
+        metadata.name.len() <= MAX_VALIDATOR_NAME_LEN,
+        Reason::InvalidValidatorMetadata
+    );
+    require!(
+        metadata.website.len() <= MAX_VALIDATOR_WEBSITE_LEN,
+        Reason::InvalidValidatorMetadata
+    );
+
+    let mut chain_ids = BTreeSet::new();
+    for address in metadata.signing_addresses.iter() {
+        require!(
+            chain_ids.insert(address.chain_id()),
+            Reason::InvalidValidatorMetadata
+        );
+    }
+
+    if let Some(prior) = ValidatorMetadatas::get(&validator.substrate_id) {
+        require!(
+            metadata.timestamp > prior.timestamp,
+            Reason::StaleValidatorMetadata
+        );
+    }
+    Ok(())
+}
+
+/// Set the metadata published by the operator of a validator, as signed by its validator key.
+pub fn set_validator_metadata<T: Config>(
+    metadata: ValidatorMetadata,
+    signature: ChainSignature,
+) -> Result<(), Reason> {
+    let validator = recover_validator::<T>(&metadata.encode(), signature)?;
+    check_validator_metadata(&validator, &metadata)?;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    ValidatorMetadatas::insert(&validator.substrate_id, &metadata);
+    <Module<T>>::deposit_event(Event::SetValidatorMetadata(
+        validator.substrate_id,
+        metadata,
+    ));
+    Ok(())
+}
+
+/// Return each current validator, with the metadata its operator published, if any.
+pub fn get_validator_metadata<T: Config>() -> Vec<(ValidatorKeys, Option<ValidatorMetadata>)> {
+    Validators::iter()
+        .map(|(substrate_id, validator)| (validator, ValidatorMetadatas::get(substrate_id)))
+        .collect()
+}
+
+/// Return each current validator, with the metadata its operator published, and whether it has signed the notice.
+/// Signatures are only kept while a notice is pending, so none are reported once it has been executed.
+pub fn get_notice_signers<T: Config>(
+    notice_hash: ChainHash,
+) -> Result<Vec<(ValidatorKeys, Option<ValidatorMetadata>, bool)>, Reason> {
+    let chain_id = notice_hash.chain_id();
+    let notice_id = NoticeHashes::get(notice_hash).ok_or(Reason::UnknownNotice)?;
+    let notice_state = NoticeStates::get(chain_id, notice_id);
+    Ok(get_validator_metadata::<T>()
+        .into_iter()
+        .map(|(validator, metadata)| {
+            let signed = match &notice_state {
+                NoticeState::Pending { signature_pairs } => {
+                    signature_pairs.has_validator_signature(chain_id, &validator)
+                }
+                _ => false,
+            };
+            (validator, metadata, signed)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn metadata(timestamp: Timestamp) -> ValidatorMetadata {
+        ValidatorMetadata {
+            timestamp,
+            name: b"Operator A".to_vec(),
+            website: b"https://example.com".to_vec(),
+            signing_addresses: vec![ChainAccount::Eth(val_a().eth_address)],
+        }
+    }
+
+    #[test]
+    fn test_set_validator_metadata() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let metadata_a = metadata(1000);
+            let signature = validator_a_sign(&metadata_a.encode()).unwrap();
+
+            assert_eq!(
+                set_validator_metadata::<Test>(metadata(2000), signature),
+                Err(Reason::UnknownValidator)
+            );
+            assert_ok!(set_validator_metadata::<Test>(
+                metadata_a.clone(),
+                signature
+            ));
+            assert_eq!(
+                ValidatorMetadatas::get(&val_a().substrate_id),
+                Some(metadata_a.clone())
+            );
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(
+                    crate::Event::SetValidatorMetadata(val_a().substrate_id, metadata_a.clone())
+                ))
+            );
+            assert_eq!(
+                set_validator_metadata::<Test>(metadata_a, signature),
+                Err(Reason::StaleValidatorMetadata)
+            );
+        });
+    }
+
+    #[test]
+    fn test_check_validator_metadata() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(check_validator_metadata(&val_a(), &metadata(1000)));
+            assert_eq!(
+                check_validator_metadata(
+                    &val_a(),
+                    &ValidatorMetadata {
+                        name: vec![b'a'; MAX_VALIDATOR_NAME_LEN + 1],
+                        ..metadata(1000)
+                    }
+                ),
+                Err(Reason::InvalidValidatorMetadata)
+            );
+            assert_eq!(
+                check_validator_metadata(
+                    &val_a(),
+                    &ValidatorMetadata {
+                        website: vec![b'a'; MAX_VALIDATOR_WEBSITE_LEN + 1],
+                        ..metadata(1000)
+                    }
+                ),
+                Err(Reason::InvalidValidatorMetadata)
+            );
+            assert_eq!(
+                check_validator_metadata(
+                    &val_a(),
+                    &ValidatorMetadata {
+                        signing_addresses: vec![
+                            ChainAccount::Eth([1; 20]),
+                            ChainAccount::Eth([2; 20])
+                        ],
+                        ..metadata(1000)
+                    }
+                ),
+                Err(Reason::InvalidValidatorMetadata)
+            );
+        });
+    }
+
+    #[test]
+    fn test_get_notice_signers() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let notice_id = NoticeId(80, 1);
+            let notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
+                id: notice_id,
+                parent: [3u8; 32],
+                asset: [1; 20],
+                account: [2; 20],
+                amount: 50,
+            });
+            let notice_hash = notice.hash();
+            assert_eq!(
+                get_notice_signers::<Test>(notice_hash),
+                Err(Reason::UnknownNotice)
+            );
+
+            ValidatorMetadatas::insert(&val_a().substrate_id, metadata(1000));
+            let signature_pairs =
+                ChainSignatureList::Eth(vec![(val_a().eth_address, [0u8; 65])].try_into().unwrap());
+            Notices::insert(ChainId::Eth, notice_id, &notice);
+            NoticeHashes::insert(notice_hash, notice_id);
+            NoticeStates::insert(
+                ChainId::Eth,
+                notice_id,
+                NoticeState::Pending { signature_pairs },
+            );
+
+            let mut signers = get_notice_signers::<Test>(notice_hash).unwrap();
+            signers.sort_by_key(|(validator, _, _)| validator.eth_address);
+            let mut expected = vec![
+                (val_a(), Some(metadata(1000)), true),
+                (val_b(), None, false),
+            ];
+            expected.sort_by_key(|(validator, _, _)| validator.eth_address);
+            assert_eq!(signers, expected);
+        });
+    }
+}
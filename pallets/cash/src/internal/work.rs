@@ -0,0 +1,172 @@
+use crate::{
+    error, internal, params,
+    reason::Reason,
+    types::{Timestamp, Work},
+    Config, DeferredWork, Event, InitializeWeightBudget, Module, SupportedAssets, WeightInfo,
+};
+use frame_support::{
+    storage::{IterableStorageMap, StorageValue},
+    weights::Weight,
+};
+use our_std::{convert::TryInto, vec::Vec};
+
+/// The deferrable work due as each block is initialized, in order of priority.
+pub const BLOCK_WORK: [Work; 11] = [
+    Work::ReleaseGovernance,
+    Work::ReleaseRecoveries,
+    Work::ReleaseExtractions,
+    Work::DispatchExtractionBatches,
+    Work::ExpirePendingBlocks,
+    Work::RetryFailedEvents,
+    Work::ProcessExodus,
+    Work::TrackCashPeg,
+    Work::RecordRateHistory,
+    Work::CheckSolvency,
+    Work::PruneNotices,
+];
+
+/// Set the weight budget for the deferrable work done as each block is initialized.
+pub fn set_initialize_weight_budget<T: Config>(budget: Weight) -> Result<(), Reason> {
+    InitializeWeightBudget::put(budget);
+    <Module<T>>::deposit_event(Event::SetInitializeWeightBudget(budget));
+    Ok(())
+}
+
+/// Return the benchmarked weight of a unit of work.
+pub fn work_weight<T: Config>(work: Work) -> Weight {
+    match work {
+        Work::ReleaseGovernance => <T as Config>::WeightInfo::release_queued_governance(
+            params::GOVERNANCE_RELEASE_BATCH_SIZE,
+        ),
+        Work::ReleaseRecoveries => <T as Config>::WeightInfo::release_queued_recoveries(
+            params::RECOVERY_RELEASE_BATCH_SIZE,
+        ),
+        Work::ReleaseExtractions => <T as Config>::WeightInfo::release_queued_extractions(
+            params::EXTRACTION_RELEASE_BATCH_SIZE,
+        ),
+        Work::DispatchExtractionBatches => {
+            <T as Config>::WeightInfo::dispatch_extraction_batches(params::MAX_BATCHED_EXTRACTIONS)
+        }
+        Work::ExpirePendingBlocks => <T as Config>::WeightInfo::expire_pending_blocks(),
+        Work::RetryFailedEvents => {
+            <T as Config>::WeightInfo::retry_failed_events(params::FAILED_EVENT_RETRY_BATCH_SIZE)
+        }
+        Work::ProcessExodus => <T as Config>::WeightInfo::process_exodus(),
+        Work::TrackCashPeg => <T as Config>::WeightInfo::track_cash_peg(),
+        Work::RecordRateHistory => {
+            <T as Config>::WeightInfo::record_rate_history(supported_asset_count())
+        }
+        Work::CheckSolvency => <T as Config>::WeightInfo::check_solvency(supported_asset_count()),
+        Work::PruneNotices => <T as Config>::WeightInfo::prune_notices(params::MAX_PRUNE_NOTICES),
+    }
+}
+
+/// Return the number of supported assets, over which some work iterates.
+fn supported_asset_count() -> u32 {
+    SupportedAssets::iter()
+        .count()
+        .try_into()
+        .unwrap_or(u32::MAX)
+}
+
+/// Do a unit of work.
+pub fn do_work<T: Config>(work: Work, now: Timestamp) -> Result<(), Reason> {
+    match work {
+        Work::ReleaseGovernance => internal::governance::release_queued_governance::<T>(now),
+        Work::ReleaseRecoveries => internal::recovery::release_queued_recoveries::<T>(now),
+        Work::ReleaseExtractions => {
+            internal::extraction_limit::release_queued_extractions::<T>(now)
+        }
+        Work::DispatchExtractionBatches => internal::notices::dispatch_extraction_batches::<T>(),
+        Work::ExpirePendingBlocks => internal::events::expire_pending_blocks::<T>(),
+        Work::RetryFailedEvents => internal::events::retry_failed_events::<T>(),
+        Work::ProcessExodus => internal::exodus::process_exodus::<T>(),
+        Work::TrackCashPeg => internal::peg::track_cash_peg::<T>(now),
+        Work::RecordRateHistory => internal::rate_history::record_rate_history::<T>(now),
+        Work::CheckSolvency => internal::solvency::record_solvency_snapshot::<T>(now),
+        Work::PruneNotices => internal::notices::prune_notices::<T>(),
+    }
+    Ok(())
+}
+
+/// Do each unit of work in order which still fits within the budget.
+/// Returns the weight used, and the work which did not fit, still in order.
+fn do_queued_work<T: Config>(
+    queue: Vec<Work>,
+    now: Timestamp,
+    budget: Weight,
+) -> (Weight, Vec<Work>) {
+    let mut used_weight: Weight = 0;
+    let mut deferred = Vec::new();
+    for work in queue {
+        let weight = work_weight::<T>(work);
+        if used_weight.saturating_add(weight) > budget {
+            deferred.push(work);
+            continue;
+        }
+
+        used_weight = used_weight.saturating_add(weight);
+        if let Err(err) = do_work::<T>(work, now) {
+            error!("Failed to do work {:?}: {:?}", work, err);
+        }
+    }
+    (used_weight, deferred)
+}
+
+/// Do the work due as a block is initialized, as long as the budget allows, returning the weight used.
+/// Work deferred by previous blocks comes first, and whatever does not fit is deferred to the next block.
+pub fn do_block_work<T: Config>(now: Timestamp, budget: Weight) -> Weight {
+    let mut queue = DeferredWork::get();
+    for work in BLOCK_WORK.iter() {
+        if !queue.contains(work) {
+            queue.push(*work);
+        }
+    }
+
+    let (used_weight, deferred) = do_queued_work::<T>(queue, now, budget);
+    DeferredWork::put(deferred);
+    used_weight
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::*, LastSolvencySnapshot, RateHistoryCursor};
+
+    #[test]
+    fn test_set_initialize_weight_budget() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(set_initialize_weight_budget::<Test>(1_000));
+            assert_eq!(InitializeWeightBudget::get(), 1_000);
+            assert_eq!(
+                System::events().last().map(|e| e.event.clone()),
+                Some(mock::Event::pallet_cash(
+                    crate::Event::SetInitializeWeightBudget(1_000)
+                ))
+            );
+        });
+    }
+
+    #[test]
+    fn test_block_work_spills_over() {
+        new_test_ext().execute_with(|| {
+            let now = 1_000;
+            let weight = work_weight::<Test>(Work::ReleaseGovernance);
+
+            assert_eq!(do_block_work::<Test>(now, 0), 0);
+            assert_eq!(DeferredWork::get(), BLOCK_WORK.to_vec());
+            assert_eq!(RateHistoryCursor::get(), (0, 0));
+            assert_eq!(LastSolvencySnapshot::get(), None);
+
+            // the deferred work is done first, as the budget allows
+            assert_eq!(do_block_work::<Test>(now, weight), weight);
+            assert_eq!(DeferredWork::get(), BLOCK_WORK[1..].to_vec());
+
+            // what is left over is done in the next block
+            do_block_work::<Test>(now, Weight::MAX);
+            assert_eq!(DeferredWork::get(), vec![]);
+            assert_eq!(RateHistoryCursor::get(), (1, now));
+            assert_eq!(LastSolvencySnapshot::get().map(|s| s.timestamp), Some(now));
+        });
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{
+    chains::{ChainAccount, ChainAsset, ChainId},
+    internal::migrations::iter_chain_keyed_map,
+    log,
+    notices::NoticeState,
+    types::{CashPrincipal, CashPrincipalAmount},
+    AssetBalances, Config, LastIndices, LastMinerSharePrincipal, NoticeHashes, NoticeStates,
+    Notices, PrunedNoticeFilters, SupplyIndices, SupportedAssets, TotalBorrowAssets,
+    TotalCashPrincipal, TotalSupplyAssets,
+};
+use frame_support::storage::{
+    IterableStorageDoubleMap, IterableStorageMap, StorageMap, StorageValue,
+};
+
+/// Check the core protocol invariants across the whole state.
+/// Intended for `try-runtime`, as this iterates over every account and notice.
+pub fn check_invariants<T: Config>() -> Result<(), &'static str> {
+    check_cash_principal::<T>()?;
+    check_asset_totals::<T>()?;
+    check_notice_parents::<T>()?;
+    Ok(())
+}
+
+/// CASH principal is only created as interest accrues or as accounts borrow it, and is counted by the total
+///  as soon as it is, so the principal held by accounts and chains can never exceed the total. Neither can it
+///  once we add the principal owed to suppliers which has not yet settled into their accounts, the protocol
+///  reserves, and the share owed to the last miner, all of which are counted by the total as interest accrues.
+/// The total may still be greater, as borrowers which hold CASH pay their interest out of it as they settle.
+pub fn check_cash_principal<T: Config>() -> Result<(), &'static str> {
+    let mut held: u128 = 0;
+    for (_account, principal) in
+        iter_chain_keyed_map::<ChainAccount, CashPrincipal>(b"CashPrincipals")
+    {
+        if principal.0 > 0 {
+            held = held
+                .checked_add(principal.0 as u128)
+                .ok_or("Cash principal overflow")?;
+        }
+    }
+    for (_chain_id, principal) in
+        iter_chain_keyed_map::<ChainId, CashPrincipalAmount>(b"ChainCashPrincipals")
+    {
+        held = held
+            .checked_add(principal.0)
+            .ok_or("Cash principal overflow")?;
+    }
+    for (asset, account, balance) in AssetBalances::iter() {
+        if balance > 0 {
+            let asset_info = SupportedAssets::get(asset).ok_or("Balance of unsupported asset")?;
+            let unsettled = SupplyIndices::get(asset)
+                .cash_principal_since(
+                    LastIndices::get(asset, account),
+                    asset_info.as_balance(balance),
+                )
+                .map_err(|_| "Cash principal overflow")?;
+            held = held
+                .checked_add(unsettled.0 as u128)
+                .ok_or("Cash principal overflow")?;
+        }
+    }
+    for (_asset, principal) in
+        iter_chain_keyed_map::<ChainAsset, CashPrincipalAmount>(b"ProtocolReserves")
+    {
+        held = held
+            .checked_add(principal.0)
+            .ok_or("Cash principal overflow")?;
+    }
+    held = held
+        .checked_add(LastMinerSharePrincipal::get().0)
+        .ok_or("Cash principal overflow")?;
+
+    let total = TotalCashPrincipal::get().0;
+    if held > total {
+        log!("Cash principal held {} > total {}", held, total);
+        return Err("Cash principal held exceeds total cash principal");
+    }
+    Ok(())
+}
+
+/// The total amount borrowed of an asset can never exceed the total amount supplied.
+pub fn check_asset_totals<T: Config>() -> Result<(), &'static str> {
+    for (asset, total_borrow) in TotalBorrowAssets::iter() {
+        let total_supply = TotalSupplyAssets::get(asset);
+        if total_borrow > total_supply {
+            log!(
+                "Asset {:?} total borrow {} > total supply {}",
+                asset,
+                total_borrow,
+                total_supply
+            );
+            return Err("Total borrows exceed total supplies");
+        }
+    }
+    Ok(())
+}
+
+/// Every pending notice must have a parent in the notice chain, which may have been pruned.
+pub fn check_notice_parents<T: Config>() -> Result<(), &'static str> {
+    for (chain_id, notice_id, state) in NoticeStates::iter() {
+        if let NoticeState::Pending { .. } = state {
+            let notice = Notices::get(chain_id, notice_id).ok_or("Pending notice missing")?;
+            let parent = notice.parent_hash();
+            if parent != chain_id.zero_hash()
+                && !NoticeHashes::contains_key(parent)
+                && !PrunedNoticeFilters::get(chain_id).contains(&parent)
+            {
+                log!(
+                    "Pending notice {:?} on {:?} has no parent {:?}",
+                    notice_id,
+                    chain_id,
+                    parent
+                );
+                return Err("Pending notice has no parent");
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        internal::migrations::MODULE_PREFIX,
+        pipeline::CashPipeline,
+        tests::{common::*, *},
+    };
+    use frame_support::{storage::migration::put_storage_value, Blake2_128Concat, StorageHasher};
+
+    #[test]
+    fn test_check_cash_principal() {
+        new_test_ext().execute_with(|| {
+            let account_a = ChainAccount::Eth([1u8; 20]);
+            let account_b = ChainAccount::Eth([2u8; 20]);
+            CashPrincipals::insert(account_a, CashPrincipal(300));
+            CashPrincipals::insert(account_b, CashPrincipal(-500));
+            ChainCashPrincipals::insert(ChainId::Eth, CashPrincipalAmount(200));
+            TotalCashPrincipal::put(CashPrincipalAmount(500));
+            assert_ok!(check_invariants::<Test>());
+
+            TotalCashPrincipal::put(CashPrincipalAmount(400));
+            assert_eq!(
+                check_invariants::<Test>(),
+                Err("Cash principal held exceeds total cash principal")
+            );
+
+            LastMinerSharePrincipal::put(CashPrincipalAmount(100));
+            TotalCashPrincipal::put(CashPrincipalAmount(550));
+            assert_eq!(
+                check_invariants::<Test>(),
+                Err("Cash principal held exceeds total cash principal")
+            );
+        });
+    }
+
+    #[test]
+    fn test_check_cash_principal_unknown_chain() {
+        new_test_ext().execute_with(|| {
+            // principal held by an account on a chain this runtime does not know still counts
+            let unknown_account = [200u8, 3, 3, 3];
+            put_storage_value(
+                MODULE_PREFIX,
+                b"CashPrincipals",
+                &Blake2_128Concat::hash(&unknown_account),
+                CashPrincipal(300),
+            );
+            assert_eq!(CashPrincipals::iter().count(), 0);
+            TotalCashPrincipal::put(CashPrincipalAmount(300));
+            assert_ok!(check_invariants::<Test>());
+
+            TotalCashPrincipal::put(CashPrincipalAmount(200));
+            assert_eq!(
+                check_invariants::<Test>(),
+                Err("Cash principal held exceeds total cash principal")
+            );
+        });
+    }
+
+    #[test]
+    fn test_check_cash_principal_with_accrual() {
+        new_test_ext().execute_with(|| {
+            let account_a = ChainAccount::Eth([1u8; 20]);
+            let account_b = ChainAccount::Eth([2u8; 20]);
+            let asset_info = AssetInfo {
+                reserve_factor: ReserveFactor::from_nominal("0.1"),
+                ..eth
+            };
+            assert_ok!(init_eth_asset());
+            SupportedAssets::insert(Eth, asset_info);
+            init_asset_balance(Eth, account_a, eth.as_balance_nominal("10").value);
+            init_asset_balance(Eth, account_b, eth.as_balance_nominal("-5").value);
+            init_cash(account_b, CashPrincipal::from_nominal("1000"));
+            LastAccrualTimestamps::insert(Eth, 10);
+            LastBlockTimestamp::put(10 + MILLISECONDS_PER_YEAR);
+            assert_ok!(check_invariants::<Test>());
+
+            // Interest accrues to the total long before it settles into any account
+            let total_pre = TotalCashPrincipal::get();
+            assert_ok!(internal::accrual::accrue_asset::<Test>(Eth));
+            assert!(TotalCashPrincipal::get() > total_pre);
+            assert!(ProtocolReserves::get(Eth) > CashPrincipalAmount::ZERO);
+            assert!(LastMinerSharePrincipal::get() > CashPrincipalAmount::ZERO);
+            assert_ok!(check_invariants::<Test>());
+
+            // Settling both the supplier and the borrower, who pays out of the CASH it holds
+            CashPipeline::new()
+                .transfer_asset::<Test>(account_a, account_b, Eth, eth.as_quantity_nominal("1"))
+                .expect("transfer_asset failed")
+                .commit::<Test>();
+            assert!(CashPrincipals::get(account_a) > CashPrincipal::ZERO);
+            assert!(CashPrincipals::get(account_b) < CashPrincipal::from_nominal("1000"));
+            assert_ok!(check_invariants::<Test>());
+
+            // Paying the miner
+            assert_ok!(internal::initialize::initialize_block::<Test>(
+                10 + 2 * MILLISECONDS_PER_YEAR
+            ));
+            assert_eq!(LastMinerSharePrincipal::get(), CashPrincipalAmount::ZERO);
+            assert_ok!(check_invariants::<Test>());
+
+            CashPrincipals::insert(
+                account_a,
+                CashPrincipals::get(account_a)
+                    .add_amount(TotalCashPrincipal::get())
+                    .unwrap(),
+            );
+            assert_eq!(
+                check_invariants::<Test>(),
+                Err("Cash principal held exceeds total cash principal")
+            );
+        });
+    }
+
+    #[test]
+    fn test_check_asset_totals() {
+        new_test_ext().execute_with(|| {
+            TotalSupplyAssets::insert(Eth, 1000);
+            TotalBorrowAssets::insert(Eth, 1000);
+            assert_ok!(check_invariants::<Test>());
+
+            TotalBorrowAssets::insert(Eth, 1001);
+            assert_eq!(
+                check_invariants::<Test>(),
+                Err("Total borrows exceed total supplies")
+            );
+        });
+    }
+
+    #[test]
+    fn test_check_notice_parents() {
+        new_test_ext().execute_with(|| {
+            let notice_id = NoticeId(0, 1);
+            let notice = Notice::CashExtractionNotice(CashExtractionNotice::Eth {
+                id: notice_id,
+                parent: [1u8; 32],
+                account: [2u8; 20],
+                principal: 100,
+                index: 1,
+            });
+            Notices::insert(ChainId::Eth, notice_id, &notice);
+            NoticeStates::insert(ChainId::Eth, notice_id, NoticeState::pending(&notice));
+            assert_eq!(
+                check_invariants::<Test>(),
+                Err("Pending notice has no parent")
+            );
+
+            NoticeHashes::insert(ChainHash::Eth([1u8; 32]), NoticeId(0, 0));
+            assert_ok!(check_invariants::<Test>());
+
+            NoticeHashes::remove(ChainHash::Eth([1u8; 32]));
+            let mut filter = NoticeFilter::default();
+            filter.insert(&ChainHash::Eth([1u8; 32]));
+            PrunedNoticeFilters::insert(ChainId::Eth, filter);
+            assert_ok!(check_invariants::<Test>());
+        });
+    }
+}
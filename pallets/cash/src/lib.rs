@@ -13,22 +13,27 @@ extern crate trx_request;
 use crate::{
     chains::{
         ChainAccount, ChainAccountSignature, ChainAsset, ChainBlock, ChainBlockEvent,
-        ChainBlockEvents, ChainBlockTally, ChainBlocks, ChainHash, ChainId, ChainReorg,
-        ChainReorgTally, ChainSignature, ChainSignatureList, ChainStarport,
+        ChainBlockEvents, ChainBlockNumber, ChainBlockTally, ChainBlocks, ChainHash, ChainId,
+        ChainReorg, ChainReorgTally, ChainSignature, ChainSignatureList, ChainStarport,
+        ChainStatus, FailedChainBlockEvent,
     },
-    notices::{Notice, NoticeId, NoticeState},
+    notices::{EraId, Notice, NoticeFilter, NoticeId, NoticeState},
     portfolio::Portfolio,
-    symbol::CASH,
+    symbol::{Symbol, CASH},
     types::{
-        AssetAmount, AssetBalance, AssetIndex, AssetInfo, Balance, Bips, CashIndex, CashPrincipal,
-        CashPrincipalAmount, CodeHash, EncodedNotice, GovernanceResult, InterestRateModel,
-        LiquidityFactor, Nonce, Reason, SessionIndex, Timestamp, ValidatorKeys, APR,
+        AccountHistoryEntry, AssetAmount, AssetBalance, AssetIndex, AssetInfo, AssetPrice, Balance,
+        Bips, CashIndex, CashOrChainAsset, CashPeg, CashPrincipal, CashPrincipalAmount, CodeHash,
+        EncodedNotice, EventId, ExecutionFeeReport, Factor, GovernanceEventId, GovernanceId,
+        GovernanceResult, InterestRateModel, LiquidationAuction, LiquidationAuctionConfig,
+        LiquidityFactor, LockOutcome, Nonce, Offense, ProtocolState, RateSnapshot, Reason,
+        ReserveFactor, SessionIndex, SolvencySnapshot, Ticker, Timestamp, ValidatorIdentity,
+        ValidatorKeys, ValidatorMetadata, Work, APR,
     },
 };
 use codec::{alloc::string::String, Encode};
 use frame_support::{
     decl_event, decl_module, decl_storage, dispatch,
-    traits::{StoredMap, UnfilteredDispatchable},
+    traits::{Get, IsSubType, StoredMap, UnfilteredDispatchable},
     weights::{DispatchClass, GetDispatchInfo, Pays, Weight},
     Parameter,
 };
@@ -42,10 +47,10 @@ use our_std::{
 use sp_core::crypto::AccountId32;
 use sp_runtime::{
     transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
-    Percent,
+    Percent, Permill,
 };
 
-use pallet_oracle;
+use pallet_oracle::{self, types::AnchorReport};
 use pallet_session;
 use pallet_timestamp;
 use types_derive::type_alias;
@@ -53,15 +58,21 @@ use types_derive::type_alias;
 #[macro_use]
 extern crate lazy_static;
 
+pub mod bounded;
 pub mod chains;
 pub mod core;
 pub mod events;
 pub mod factor;
+#[cfg(any(feature = "codec_fixtures", test))]
+pub mod fixtures;
 pub mod internal;
+pub mod invariants;
+pub mod metrics;
 pub mod notices;
 pub mod params;
 pub mod pipeline;
 pub mod portfolio;
+pub mod pure;
 pub mod rates;
 pub mod reason;
 pub mod require;
@@ -96,6 +107,7 @@ pub trait Config:
 
     /// The overarching dispatch call type.
     type Call: From<Call<Self>>
+        + IsSubType<Call<Self>>
         + Parameter
         + UnfilteredDispatchable<Origin = Self::Origin>
         + GetDispatchInfo;
@@ -113,6 +125,10 @@ pub trait Config:
 
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
+
+    /// The maximum number of validators in a validator set, at most `params::MaxValidators`.
+    /// Stored signature lists and attestation sets are bounded by `params::MaxValidators`, which may never decrease.
+    type MaxValidators: Get<u32>;
 }
 
 decl_storage! {
@@ -132,6 +148,9 @@ decl_storage! {
         /// The current set of allowed validators, and their associated keys.
         Validators get(fn validators): map hasher(blake2_128_concat) SubstrateId => Option<ValidatorKeys>;
 
+        /// The metadata published by the operator of each validator, signed with its validator key.
+        ValidatorMetadatas get(fn validator_metadata): map hasher(blake2_128_concat) SubstrateId => Option<ValidatorMetadata>;
+
         /// An index to track interest earned by CASH holders and owed by CASH borrowers.
         /// Note - the implementation of Default for CashIndex returns ONE. This also provides
         /// the initial value as it is currently implemented.
@@ -143,8 +162,29 @@ decl_storage! {
         /// The current APR on CASH held, and the base rate paid by borrowers.
         CashYield get(fn cash_yield) config(): APR;
 
+        /// The latest deviation of the market price of wrapped CASH from one dollar, once there is a market price.
+        CashPegStatus get(fn cash_peg_status): Option<CashPeg>;
+
+        /// How far (basis points) and for how long (milliseconds) wrapped CASH may trade off peg before it is reported. [max_deviation_bps, delay]
+        PegTolerance get(fn peg_tolerance): (u32, Timestamp) = (params::DEFAULT_PEG_TOLERANCE_BPS, params::DEFAULT_PEG_REPORT_DELAY);
+
         /// The liquidation incentive on seized collateral (e.g. 8% = 800 bips).
-        GlobalLiquidationIncentive get(fn liquidation_incentive): Bips;
+        GlobalLiquidationIncentive get(fn liquidation_incentive): Bips = params::DEFAULT_LIQUIDATION_INCENTIVE;
+
+        /// The fraction of a borrow which may be repaid in a single liquidation.
+        LiquidationCloseFactor get(fn close_factor): Factor = params::DEFAULT_CLOSE_FACTOR;
+
+        /// The assets which may not currently be seized as collateral in a liquidation.
+        SeizePaused get(fn seize_paused): map hasher(blake2_128_concat) ChainAsset => bool;
+
+        /// The parameters for liquidating large borrows by auction, if enabled.
+        LiquidationAuctionParams get(fn liquidation_auction_params): Option<LiquidationAuctionConfig>;
+
+        /// The ongoing liquidation auctions, by borrower.
+        LiquidationAuctions get(fn liquidation_auction): map hasher(blake2_128_concat) ChainAccount => Option<LiquidationAuction>;
+
+        /// The rate at which the weight of a signed extrinsic is converted to a fee, in CASH per unit of weight.
+        CashFeePerWeight get(fn cash_fee_per_weight): Factor = params::DEFAULT_CASH_FEE_PER_WEIGHT;
 
         /// The fraction of borrower interest that is paid to the protocol (e.g. 1/10th = 1000 bips).
         Spreads get(fn spread): map hasher(blake2_128_concat) ChainAsset => Bips;
@@ -194,6 +234,18 @@ decl_storage! {
         /// The change authority notices which must be fully signed before we allow notice signing to continue
         NoticeHolds get(fn notice_hold): map hasher(blake2_128_concat) ChainId => Option<NoticeId>;
 
+        /// The executed notices which have yet to be pruned, by chain and position in order of execution. [notice_id, notice_hash]
+        ExecutedNotices get(fn executed_notice): double_map hasher(blake2_128_concat) ChainId, hasher(twox_64_concat) u64 => Option<(NoticeId, ChainHash)>;
+
+        /// The position of the oldest executed notice yet to be pruned, and of the next to be executed, by chain. [first, next]
+        ExecutedNoticeRange get(fn executed_notice_range): map hasher(blake2_128_concat) ChainId => (u64, u64);
+
+        /// The filter of executed notices which have been pruned, by chain.
+        PrunedNoticeFilters get(fn pruned_notice_filter): map hasher(blake2_128_concat) ChainId => NoticeFilter;
+
+        /// The number of notice eras for which executed notices are retained, before being pruned.
+        NoticeRetentionEras get(fn notice_retention_eras): u32 = params::DEFAULT_NOTICE_RETENTION_ERAS;
+
         /// Index of notices by chain account
         AccountNotices get(fn account_notices): map hasher(blake2_128_concat) ChainAccount => Vec<NoticeId>;
 
@@ -203,6 +255,33 @@ decl_storage! {
         /// The asset metadata for each supported asset, which will also be synced with the starports.
         SupportedAssets get(fn asset): map hasher(blake2_128_concat) ChainAsset => Option<AssetInfo>;
 
+        /// The assets on each chain grouped under a symbol, which share one price, rate model and liquidity factor.
+        AssetGroups get(fn asset_group): map hasher(blake2_128_concat) Symbol => Vec<ChainAsset>;
+
+        /// The symbol under which each grouped asset is held.
+        AssetSymbols get(fn asset_symbol): map hasher(blake2_128_concat) ChainAsset => Option<Symbol>;
+
+        /// The market each oracle ticker is expected to price, which assets listed with the ticker must belong to.
+        PriceKeyMapping get(fn price_key_mapping): map hasher(blake2_128_concat) Ticker => Option<CashOrChainAsset>;
+
+        /// The amount of each asset held by its starport, according to the locks and extractions of the asset.
+        StarportHoldings get(fn starport_holding): map hasher(blake2_128_concat) ChainAsset => AssetAmount;
+
+        /// When the protocol was frozen to return the collateral of every account, if it ever was.
+        ExodusStarted get(fn exodus_started): Option<Timestamp>;
+
+        /// The accounts whose collateral remains to be returned in the exodus, by position in the queue.
+        ExodusAccounts get(fn exodus_account): map hasher(twox_64_concat) u32 => Option<ChainAccount>;
+
+        /// The position of the next account to return collateral to in the exodus, and the number of accounts queued.
+        ExodusCursor get(fn exodus_cursor): (u32, u32);
+
+        /// The weight budget for the deferrable work done as each block is initialized.
+        InitializeWeightBudget get(fn initialize_weight_budget): Weight = params::DEFAULT_INITIALIZE_WEIGHT_BUDGET;
+
+        /// The work which did not fit in the weight budget of a previous block, to be done first.
+        DeferredWork get(fn deferred_work): Vec<Work>;
+
         /// Miner of the current block.
         Miner get(fn miner): Option<ChainAccount>;
 
@@ -212,9 +291,27 @@ decl_storage! {
         /// Validator spread due to miner of last block.
         LastMinerSharePrincipal get(fn last_miner_share_principal): CashPrincipalAmount;
 
+        /// The CASH principal set aside from borrower interest as protocol reserves, by asset.
+        ProtocolReserves get(fn protocol_reserves): map hasher(blake2_128_concat) ChainAsset => CashPrincipalAmount;
+
+        /// The shortfall in USD of each account whose debts exceeded its collateral after a liquidation, until written off.
+        BadDebts get(fn bad_debt): map hasher(blake2_128_concat) ChainAccount => Option<AssetAmount>;
+
+        /// The sum of the bad debts of all accounts, in USD.
+        TotalBadDebt get(fn total_bad_debt): AssetAmount;
+
+        /// The latest periodic snapshot of the solvency of the protocol.
+        LastSolvencySnapshot get(fn last_solvency_snapshot): Option<SolvencySnapshot>;
+
         /// The timestamp of the previous block or defaults to timestamp at genesis.
         LastBlockTimestamp get(fn last_block_timestamp): Timestamp;
 
+        /// The timestamp up until which interest was last accrued, by asset.
+        LastAccrualTimestamps get(fn last_accrual_timestamp): map hasher(blake2_128_concat) ChainAsset => Timestamp;
+
+        /// The borrow rate to accrue interest at until the next accrual, by asset.
+        BorrowRates get(fn borrow_rate): map hasher(blake2_128_concat) ChainAsset => APR;
+
         /// The cash index of the previous yield accrual point or defaults to initial cash index.
         LastYieldCashIndex get(fn last_yield_cash_index): CashIndex;
 
@@ -230,11 +327,116 @@ decl_storage! {
         /// The mapping of worker tallies for each descendant block, on current fork of underlying chain.
         PendingChainBlocks get(fn pending_chain_blocks): map hasher(blake2_128_concat) ChainId => Vec<ChainBlockTally>;
 
+        /// The block at which the first of the pending chain blocks began waiting for a quorum, by chain.
+        PendingChainBlocksSince get(fn pending_chain_blocks_since): map hasher(blake2_128_concat) ChainId => Option<u64>;
+
+        /// The number of blocks pending chain blocks may wait for a quorum before their events time out, or zero for never.
+        EventExpiration get(fn event_expiration): u32 = params::DEFAULT_EVENT_EXPIRATION_BLOCKS;
+
         /// The mapping of worker tallies for each alternate reorg, relative to current fork of underlying chain.
         PendingChainReorgs get(fn pending_chain_reorgs): map hasher(blake2_128_concat) ChainId => Vec<ChainReorgTally>;
 
+        /// The hash of the payload each validator attested to, by event, retained for proving equivocation.
+        AttestedPayloads get(fn attested_payloads): map hasher(blake2_128_concat) EventId => Vec<(ValidatorIdentity, ChainHash)>;
+
+        /// The number of notices dispatched during each notice era, by chain.
+        EraNoticeCounts get(fn era_notice_count): map hasher(blake2_128_concat) (ChainId, EraId) => u32;
+
+        /// The maximum fraction of the notices in an era which a validator may fail to sign, before being reported.
+        MaxMissedSignatures get(fn max_missed_signatures): Factor = params::DEFAULT_MAX_MISSED_SIGNATURES;
+
+        /// The fraction of the validators which must sign to accept a chain block or authorize a notice.
+        QuorumThreshold get(fn quorum_threshold): Permill = params::DEFAULT_QUORUM_THRESHOLD;
+
+        /// The validators whose attestations are ignored due to an offense, until the validator set is changed.
+        ChilledValidators get(fn chilled_validator): map hasher(blake2_128_concat) ValidatorIdentity => Option<Offense>;
+
+        /// The events which failed to apply, awaiting a retry or to be discarded by governance.
+        FailedChainBlockEvents get(fn failed_chain_block_events): Vec<FailedChainBlockEvent>;
+
+        /// The events accepted during this block, applied together in a deterministic order when it is finalized.
+        PendingChainEvents get(fn pending_chain_events): Vec<ChainBlockEvent>;
+
+        /// The chains whose events must be proven by the receipts of each block, rather than trusting the logs returned by an RPC server.
+        EventProofMode get(fn event_proof_mode): map hasher(blake2_128_concat) ChainId => bool;
+
+        /// The status of each underlying chain, which governance may restrict without pausing the whole protocol.
+        ChainStatuses get(fn chain_status): map hasher(blake2_128_concat) ChainId => ChainStatus;
+
         /// Mapping of chain to the relevant Starport address.
         Starports get(fn starports): map hasher(blake2_128_concat) ChainId => Option<ChainStarport>;
+
+        /// The maximum amount of each asset which may be extracted per extraction window, if limited.
+        ExtractionLimits get(fn extraction_limit): map hasher(blake2_128_concat) ChainAsset => Option<AssetAmount>;
+
+        /// The amounts extracted within the rolling extraction window, by asset, in order. [bucket start, amount]
+        ExtractionWindows get(fn extraction_window): map hasher(blake2_128_concat) ChainAsset => Vec<(Timestamp, AssetAmount)>;
+
+        /// The extractions waiting for room in the window due to the extraction limit, by asset, in order. [recipient, amount]
+        QueuedExtractions get(fn queued_extractions): map hasher(blake2_128_concat) ChainAsset => Vec<(ChainAccount, AssetAmount)>;
+
+        /// The maximum length (bytes) of an encoded batch of extraction notices, by chain, if extractions are batched.
+        ExtractionBatchBudgets get(fn extraction_batch_budget): map hasher(blake2_128_concat) ChainId => Option<u32>;
+
+        /// The extractions waiting to be dispatched in the next batch, by chain. [asset, recipient, amount]
+        PendingBatchExtractions get(fn pending_batch_extractions): map hasher(blake2_128_concat) ChainId => Vec<(ChainAsset, ChainAccount, AssetAmount)>;
+
+        /// The extractions covered by each batch notice which has not yet executed, by chain. [asset, recipient, amount]
+        ExtractionBatches get(fn extraction_batch): double_map hasher(blake2_128_concat) ChainId, hasher(blake2_128_concat) NoticeId => Vec<(ChainAsset, ChainAccount, AssetAmount)>;
+
+        /// The estimated fee to execute an extraction on each chain, in its native asset, and when it was observed.
+        ExecutionFees get(fn execution_fee): map hasher(blake2_128_concat) ChainId => Option<(AssetAmount, Timestamp)>;
+
+        /// The account whose entire portfolio, including its debts, each recipient has accepted. [recipient => sender]
+        PortfolioConsents get(fn portfolio_consent): map hasher(blake2_128_concat) ChainAccount => Option<ChainAccount>;
+
+        /// The guardians nominated by each account, a threshold of which may recover it. [threshold, guardians]
+        Guardians get(fn guardians): map hasher(blake2_128_concat) ChainAccount => Option<(u32, Vec<ChainAccount>)>;
+
+        /// The guardians which have approved recovering an account to a new account, short of the threshold. [account, new_account => guardians]
+        RecoveryApprovals get(fn recovery_approvals): double_map hasher(blake2_128_concat) ChainAccount, hasher(blake2_128_concat) ChainAccount => Vec<ChainAccount>;
+
+        /// The recoveries approved by the guardians of each account, waiting out the recovery delay. [eta, new_account]
+        QueuedRecoveries get(fn queued_recovery): map hasher(blake2_128_concat) ChainAccount => Option<(Timestamp, ChainAccount)>;
+
+        /// The accounts whose recoveries were queued, by position in order of queueing. [account, eta]
+        RecoveryQueue get(fn recovery_queue): map hasher(twox_64_concat) u64 => Option<(ChainAccount, Timestamp)>;
+
+        /// The position of the oldest queued recovery yet to be released, and of the next to be queued. [first, next]
+        RecoveryQueueRange get(fn recovery_queue_range): (u64, u64);
+
+        /// The ring buffer of periodic snapshots of the CASH index and asset rates, by slot.
+        RateHistory get(fn rate_history): map hasher(twox_64_concat) u32 => Option<RateSnapshot>;
+
+        /// The slot of the rate history to write next, and when the last snapshot was taken. [next_slot, timestamp]
+        RateHistoryCursor get(fn rate_history_cursor): (u32, Timestamp);
+
+        /// The recent entries in the history of each account, kept in a ring buffer per account. [account, slot => entry]
+        AccountHistory get(fn account_history): double_map hasher(blake2_128_concat) ChainAccount, hasher(twox_64_concat) u32 => Option<AccountHistoryEntry>;
+
+        /// The number of entries ever recorded in the history of each account.
+        AccountHistoryCount get(fn account_history_count): map hasher(blake2_128_concat) ChainAccount => u64;
+
+        /// The delay (milliseconds) before a governance proposal containing sensitive changes executes, zero executes it immediately.
+        GovernanceDelay get(fn governance_delay): Timestamp;
+
+        /// The identifier to assign to the next queued governance proposal.
+        NextGovernanceId get(fn next_governance_id): GovernanceId;
+
+        /// The governance proposals waiting out the governance delay. [eta, originating event, extrinsics]
+        QueuedGovernance get(fn queued_governance): map hasher(blake2_128_concat) GovernanceId => Option<(Timestamp, ChainBlockEvent, Vec<Vec<u8>>)>;
+
+        /// The result of each call in the governance proposals received from chains, so that calls which succeeded are not repeated on retry. [event_id, call_index => result]
+        GovernanceCallResults get(fn governance_call_result): double_map hasher(blake2_128_concat) GovernanceEventId, hasher(twox_64_concat) u32 => Option<GovernanceResult>;
+
+        /// How each lock received from a chain was applied, retained while its block may still be reorganized. [event_id, event_hash => outcomes]
+        LockOutcomes get(fn lock_outcomes): double_map hasher(blake2_128_concat) EventId, hasher(blake2_128_concat) ChainHash => Vec<LockOutcome>;
+
+        /// The latest anchor price reported for each ticker by each validator, since its anchor price was last posted. [ticker, validator => price, timestamp]
+        AnchorReports get(fn anchor_report): double_map hasher(blake2_128_concat) Ticker, hasher(blake2_128_concat) ValidatorIdentity => Option<(AssetPrice, Timestamp)>;
+
+        /// The version of the storage layout, used to determine which migrations to run on upgrade.
+        StorageVersion get(fn storage_version): u32;
     }
 
     add_extra_genesis {
@@ -247,6 +449,7 @@ decl_storage! {
             Pallet::<T>::initialize_validators(config.validators.clone());
             Pallet::<T>::initialize_starports(config.starports.clone());
             Pallet::<T>::initialize_genesis_blocks(config.genesis_blocks.clone());
+            StorageVersion::put(params::CURRENT_STORAGE_VERSION);
         })
     }
 }
@@ -300,8 +503,8 @@ decl_event!(
         /// An account using CASH as collateral has been liquidated. [asset, liquidator, borrower, amount]
         LiquidateCashCollateral(ChainAsset, ChainAccount, ChainAccount, AssetAmount),
 
-        /// Miner paid. [miner, principal]
-        MinerPaid(ChainAccount, CashPrincipalAmount),
+        /// Miner paid. [miner, principal, index]
+        MinerPaid(ChainAccount, CashPrincipalAmount, CashIndex),
 
         /// The next code hash has been allowed. [hash]
         AllowedNextCodeHash(CodeHash),
@@ -332,6 +535,156 @@ decl_event!(
 
         /// Failed to process a given extrinsic. [reason]
         Failure(Reason),
+
+        /// An extraction exceeded the asset's extraction limit and was queued. [asset, sender, recipient, amount]
+        ExtractionQueued(ChainAsset, ChainAccount, ChainAccount, AssetAmount),
+
+        /// A queued extraction has been released. [asset, recipient, amount]
+        ExtractionReleased(ChainAsset, ChainAccount, AssetAmount),
+
+        /// The extraction limit for an asset has been set or removed. [asset, limit]
+        SetExtractionLimit(ChainAsset, Option<AssetAmount>),
+
+        /// A failed Ethereum event was discarded without being applied. [event_id]
+        DiscardedFailedChainBlockEvent(ChainBlockEvent),
+
+        /// The number of notice eras for which executed notices are retained has been set. [eras]
+        SetNoticeRetention(u32),
+
+        /// A notice has been executed by the starport on its chain. [chain_id, notice_id, notice_hash, result]
+        NoticeExecuted(ChainId, NoticeId, ChainHash, Vec<u8>),
+
+        /// The protocol reserves of an asset have been swept to a treasury account. [asset, recipient, principal, index]
+        SweptReserves(ChainAsset, ChainAccount, CashPrincipalAmount, CashIndex),
+
+        /// The liquidation incentive on seized collateral has been set. [incentive]
+        SetLiquidationIncentive(Bips),
+
+        /// The close factor for liquidations has been set. [close_factor]
+        SetCloseFactor(Factor),
+
+        /// Seizing an asset as collateral has been paused or unpaused. [asset, paused]
+        SetSeizePaused(ChainAsset, bool),
+
+        /// The rate at which signed extrinsic fees are charged in CASH has been set. [rate]
+        SetFeeRate(Factor),
+
+        /// A validator has been reported for an offense, and slashed and chilled if it equivocated. [validator, offense, principal]
+        ValidatorOffense(ValidatorIdentity, Offense, CashPrincipalAmount),
+
+        /// The maximum fraction of notices a validator may fail to sign per era has been set. [fraction]
+        SetMaxMissedSignatures(Factor),
+
+        /// A governance proposal containing sensitive changes has been queued until the governance delay has passed. [id, eta, extrinsics]
+        GovernanceQueued(GovernanceId, Timestamp, Vec<Vec<u8>>),
+
+        /// A queued governance proposal has been cancelled. [id]
+        GovernanceCancelled(GovernanceId),
+
+        /// The delay before sensitive governance proposals execute has been set. [delay]
+        SetGovernanceDelay(Timestamp),
+
+        /// The size budget for batches of extraction notices on a chain has been set or removed. [chain_id, budget]
+        SetExtractionBatchBudget(ChainId, Option<u32>),
+
+        /// An account has accepted the entire portfolio of another account, including its debts. [recipient, sender]
+        AcceptPortfolio(ChainAccount, ChainAccount),
+
+        /// The guardians of an account have been set. [account, threshold, guardians]
+        SetGuardians(ChainAccount, u32, Vec<ChainAccount>),
+
+        /// A guardian has approved recovering an account to a new account. [account, new_account, guardian]
+        RecoveryApproved(ChainAccount, ChainAccount, ChainAccount),
+
+        /// Enough guardians have approved a recovery, which has been queued until the recovery delay has passed. [account, new_account, eta]
+        RecoveryQueued(ChainAccount, ChainAccount, Timestamp),
+
+        /// A queued recovery has been cancelled by the account. [account]
+        RecoveryCancelled(ChainAccount),
+
+        /// The portfolio of an account has been reassigned to a new account by its guardians. [account, new_account]
+        AccountRecovered(ChainAccount, ChainAccount),
+
+        /// A queued recovery could not be applied, and has been dropped. [account, new_account, reason]
+        RecoveryFailed(ChainAccount, ChainAccount, Reason),
+
+        /// The fraction of the validators required for a quorum has been set. [threshold]
+        SetQuorumThreshold(Permill),
+
+        /// A lock of an unsupported asset, or during the exodus, has been refunded to the sender, instead of credited. [asset, sender, recipient, amount]
+        LockRefunded(ChainAsset, ChainAccount, ChainAccount, AssetAmount),
+
+        /// A notice which had not executed has been revoked, as the event it was dispatched for was reorganized away. [chain_id, notice_id]
+        NoticeRevoked(ChainId, NoticeId),
+
+        /// The tolerance for wrapped CASH trading off peg has been set. [max_deviation_bps, delay]
+        SetPegTolerance(u32, Timestamp),
+
+        /// Wrapped CASH has traded off peg for longer than the tolerance allows. [price, deviation_bps, since]
+        CashOffPeg(AssetPrice, u32, Timestamp),
+
+        /// Wrapped CASH has returned to trading within the tolerance of its peg. [price]
+        CashPegRestored(AssetPrice),
+
+        /// Whether events of an underlying chain must be proven by the receipts of each block has been set. [chain_id, enabled]
+        SetEventProofMode(ChainId, bool),
+
+        /// The assets grouped under a symbol have been set. [symbol, assets]
+        SetAssetGroup(Symbol, Vec<ChainAsset>),
+
+        /// A balance of an asset has been converted into the same asset on another chain. [from_asset, to_asset, account, amount]
+        Converted(ChainAsset, ChainAsset, ChainAccount, AssetAmount),
+
+        /// The protocol has been frozen to return the collateral of every account. [accounts]
+        ExodusStarted(u32),
+
+        /// The collateral of an account could not be returned in the exodus, and was left in place. [account, reason]
+        ExodusSkipped(ChainAccount, Reason),
+
+        /// The collateral of every account queued for the exodus has been returned. [accounts]
+        ExodusCompleted(u32),
+
+        /// A lock of CASH during the exodus has been refunded to the sender, instead of credited. [sender, recipient, principal, index]
+        LockCashRefunded(ChainAccount, ChainAccount, CashPrincipalAmount, CashIndex),
+
+        /// An event expired without its block reaching a quorum, and will be applied only if attested again. [event_id, reason]
+        ExpiredChainBlockEvent(ChainBlockEvent, Reason),
+
+        /// The number of blocks after which unattested events expire has been set. [blocks]
+        SetEventExpiration(u32),
+
+        /// The status of an underlying chain has been set. [chain_id, status]
+        SetChainStatus(ChainId, ChainStatus),
+
+        /// The weight budget for the deferrable work done as each block is initialized has been set. [budget]
+        SetInitializeWeightBudget(Weight),
+
+        /// The operator of a validator has published its metadata. [validator, metadata]
+        SetValidatorMetadata(ValidatorIdentity, ValidatorMetadata),
+
+        /// The parameters for liquidating large borrows by auction have been set, or auctions disabled. [config]
+        SetLiquidationAuction(Option<LiquidationAuctionConfig>),
+
+        /// A liquidation auction has started for a borrower. [borrower, start_block]
+        LiquidationAuctionStarted(ChainAccount, u64),
+
+        /// The liquidation auction for a borrower has ended, as they are no longer underwater. [borrower]
+        LiquidationAuctionEnded(ChainAccount),
+
+        /// The debts of an account exceed its collateral after a liquidation, leaving a shortfall in USD. [account, shortfall]
+        BadDebtDetected(ChainAccount, AssetAmount),
+
+        /// The bad debt of an account has been written off against the protocol reserves of an asset. [account, asset, principal, index]
+        BadDebtWrittenOff(ChainAccount, ChainAsset, CashPrincipalAmount, CashIndex),
+
+        /// A call in a governance proposal received from a chain has been executed. [event_id, call_index]
+        GovernanceCallSucceeded(GovernanceEventId, u32),
+
+        /// A call in a governance proposal received from a chain has failed, and will be retried along with the event. [event_id, call_index, result]
+        GovernanceCallFailed(GovernanceEventId, u32, GovernanceResult),
+
+        /// The market priced by an oracle ticker has been set, or cleared. [ticker, market]
+        SetPriceKey(Ticker, Option<CashOrChainAsset>),
     }
 );
 
@@ -428,7 +781,11 @@ fn has_requisite_signatures(notice_state: NoticeState, validators: &Vec<Validato
                     vec_to_set::<EthAddrType>(signature_pairs.iter().map(|p| p.0).collect());
                 let validator_set =
                     vec_to_set::<EthAddrType>(validators.iter().map(|v| v.eth_address).collect());
-                chains::has_super_majority::<EthAddrType>(&signature_set, &validator_set)
+                chains::has_super_majority::<EthAddrType>(
+                    &signature_set,
+                    &validator_set,
+                    QuorumThreshold::get(),
+                )
             }
             _ => false,
         },
@@ -511,6 +868,34 @@ fn get_exec_req_weights<T: Config>(request: Vec<u8>) -> frame_support::weights::
             <T as Config>::WeightInfo::exec_trx_request_liquidate()
         }
 
+        Ok(trx_request::TrxRequest::TransferPortfolio(_account)) => {
+            <T as Config>::WeightInfo::exec_trx_request_transfer_portfolio()
+        }
+
+        Ok(trx_request::TrxRequest::AcceptPortfolio(_account)) => {
+            <T as Config>::WeightInfo::exec_trx_request_accept_portfolio()
+        }
+
+        Ok(trx_request::TrxRequest::SetGuardians(_threshold, guardians)) => {
+            <T as Config>::WeightInfo::exec_trx_request_set_guardians(guardians.len() as u32)
+        }
+
+        Ok(trx_request::TrxRequest::RecoverAccount(_account, _new_account)) => {
+            <T as Config>::WeightInfo::exec_trx_request_recover_account()
+        }
+
+        Ok(trx_request::TrxRequest::CancelRecovery) => {
+            <T as Config>::WeightInfo::exec_trx_request_cancel_recovery()
+        }
+
+        Ok(trx_request::TrxRequest::Convert(_max_amount, _from_asset, _to_asset)) => {
+            <T as Config>::WeightInfo::exec_trx_request_convert()
+        }
+
+        Ok(trx_request::TrxRequest::StartAuction(_borrower)) => {
+            <T as Config>::WeightInfo::exec_trx_request_start_auction()
+        }
+
         _ => params::ERROR_WEIGHT,
     }
 }
@@ -540,7 +925,7 @@ fn get_chain_reorg_weights_eth_like<T: Config>(
     {
         let validator = core::recover_validator::<T>(&reorg.encode(), *signature)?;
         // if reorg would get applied, just estimate gas by counting the number of reorged events
-        if prior.would_have_enough_support(&prior.support, &validator) {
+        if prior.would_have_enough_support(&prior.support, &validator, QuorumThreshold::get()) {
             let avg_weight = <T as Config>::WeightInfo::exec_trx_request_extract();
             // TODO: only count forward weight if we have passed than min_event_blocks
             Ok(event_count * avg_weight)
@@ -601,15 +986,37 @@ decl_module! {
         // Events must be initialized if they are used by the pallet.
         fn deposit_event() = default;
 
+        /// The maximum number of validators in a validator set.
+        const MaxValidators: u32 = T::MaxValidators::get();
+
+        fn integrity_test() {
+            assert!(
+                T::MaxValidators::get() <= params::MaxValidators::get(),
+                "MaxValidators exceeds the bound of the stored validator sets"
+            );
+        }
+
         fn on_runtime_upgrade() -> Weight {
-            0 // XXX
+            internal::migrations::on_runtime_upgrade::<T>()
+        }
+
+        /// Checks the protocol invariants before a runtime upgrade.
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<(), &'static str> {
+            invariants::check_invariants::<T>()
+        }
+
+        /// Checks the protocol invariants still hold after a runtime upgrade.
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            invariants::check_invariants::<T>()
         }
 
         /// Called by substrate on block initialization.
         /// Our initialization function is fallible, but that's not allowed.
         fn on_initialize(block: T::BlockNumber) -> frame_support::weights::Weight {
             match internal::initialize::on_initialize::<T>() {
-                Ok(()) => <T as Config>::WeightInfo::on_initialize(SupportedAssets::iter().count().try_into().unwrap()),
+                Ok(work_weight) => <T as Config>::WeightInfo::on_initialize(SupportedAssets::iter().count().try_into().unwrap()).saturating_add(work_weight),
                 Err(err) => {
                     // This should never happen...
                     error!("Could not initialize block!!! {:#?} {:#?}", block, err);
@@ -618,9 +1025,15 @@ decl_module! {
             }
         }
 
+        /// Called by substrate on block finalization, to apply the chain events accepted during the block.
+        /// The weight of applying them is accounted for by the extrinsics which accepted them.
+        fn on_finalize(_block: T::BlockNumber) {
+            internal::events::apply_chain_events::<T>();
+        }
+
         /// Offchain Worker entry point.
         fn offchain_worker(block_number: T::BlockNumber) {
-            match internal::events::track_chain_events::<T>() {
+            match internal::events::track_chain_events::<T>(block_number) {
                 Ok(()) => (),
                 Err(Reason::WorkerBusy) => {
                     debug!("offchain_worker is still busy in track_chain_events");
@@ -630,6 +1043,14 @@ decl_module! {
                 }
             }
 
+            if let Err(err) = internal::anchors::track_anchor_prices::<T>(block_number) {
+                error!("offchain_worker error during track_anchor_prices: {:?}", err);
+            }
+
+            if let Err(err) = internal::execution_fees::track_execution_fees::<T>(block_number) {
+                error!("offchain_worker error during track_execution_fees: {:?}", err);
+            }
+
             // XXX we need to 'lock' notices too right?
             match internal::notices::process_notices::<T>(block_number) {
                 (succ, skip, failures) => {
@@ -651,7 +1072,7 @@ decl_module! {
         }
 
         /// Sets the keys for the next set of validators beginning at the next session. [Root]
-        #[weight = (<T as Config>::WeightInfo::change_validators(), DispatchClass::Operational, Pays::No)]
+        #[weight = (<T as Config>::WeightInfo::change_validators(validators.len() as u32), DispatchClass::Operational, Pays::No)]
         pub fn change_validators(origin, validators: Vec<ValidatorKeys>) -> dispatch::DispatchResult {
             ensure_root(origin)?;
             Ok(check_failure::<T>(internal::change_validators::change_validators::<T>(validators))?)
@@ -701,6 +1122,69 @@ decl_module! {
             Ok(check_failure::<T>(internal::supply_cap::set_supply_cap::<T>(asset, amount))?)
         }
 
+        /// Sets the maximum total amount of a given chain asset which may be borrowed, a cap of zero winds down borrowing. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_borrow_cap(), DispatchClass::Operational, Pays::No)]
+        pub fn set_borrow_cap(origin, asset: ChainAsset, amount: AssetAmount) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::borrow_cap::set_borrow_cap::<T>(asset, amount))?)
+        }
+
+        /// Sets the maximum amount of an asset which may be extracted per extraction window, or removes the limit. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_extraction_limit(), DispatchClass::Operational, Pays::No)]
+        pub fn set_extraction_limit(origin, asset: ChainAsset, limit: Option<AssetAmount>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::extraction_limit::set_extraction_limit::<T>(asset, limit))?)
+        }
+
+        /// Sets the maximum encoded length of a batch of extraction notices for a chain, or stops batching. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_extraction_batch_budget(), DispatchClass::Operational, Pays::No)]
+        pub fn set_extraction_batch_budget(origin, chain_id: ChainId, budget: Option<u32>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::notices::set_extraction_batch_budget::<T>(chain_id, budget))?)
+        }
+
+        /// Discards a failed chain event which is not expected to ever apply successfully. [Root]
+        #[weight = (<T as Config>::WeightInfo::discard_failed_event(), DispatchClass::Operational, Pays::No)]
+        pub fn discard_failed_event(origin, event: ChainBlockEvent) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::events::discard_failed_event::<T>(event))?)
+        }
+
+        /// Sets whether events of an underlying chain must be proven by the receipts of each block. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_event_proof_mode(), DispatchClass::Operational, Pays::No)]
+        pub fn set_event_proof_mode(origin, chain_id: ChainId, enabled: bool) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::events::set_event_proof_mode::<T>(chain_id, enabled))?)
+        }
+
+        /// Sets the status of an underlying chain, e.g. halting it while its starport is compromised. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_chain_status(), DispatchClass::Operational, Pays::No)]
+        pub fn set_chain_status(origin, chain_id: ChainId, status: ChainStatus) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::chain_status::set_chain_status::<T>(chain_id, status))?)
+        }
+
+        /// Sets the number of blocks after which events whose blocks have not reached a quorum expire, or zero for never. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_event_expiration(), DispatchClass::Operational, Pays::No)]
+        pub fn set_event_expiration(origin, blocks: u32) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::events::set_event_expiration::<T>(blocks))?)
+        }
+
+        /// Sets the number of notice eras for which executed notices are retained, before being pruned. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_notice_retention(), DispatchClass::Operational, Pays::No)]
+        pub fn set_notice_retention(origin, eras: u32) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::notices::set_notice_retention::<T>(eras))?)
+        }
+
+        /// Sets the weight budget for the deferrable work done as each block is initialized, the rest spilling over to later blocks. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_initialize_weight_budget(), DispatchClass::Operational, Pays::No)]
+        pub fn set_initialize_weight_budget(origin, budget: Weight) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::work::set_initialize_weight_budget::<T>(budget))?)
+        }
+
         /// Set the liquidity factor for an asset [Root]
         #[weight = (<T as Config>::WeightInfo::set_liquidity_factor(), DispatchClass::Operational, Pays::No)]
         pub fn set_liquidity_factor(origin, asset: ChainAsset, factor: LiquidityFactor) -> dispatch::DispatchResult {
@@ -708,6 +1192,90 @@ decl_module! {
             Ok(check_failure::<T>(internal::assets::set_liquidity_factor::<T>(asset, factor))?)
         }
 
+        /// Set the portion of borrower interest on an asset which goes to protocol reserves. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_reserve_factor(), DispatchClass::Operational, Pays::No)]
+        pub fn set_reserve_factor(origin, asset: ChainAsset, factor: ReserveFactor) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_reserve_factor::<T>(asset, factor))?)
+        }
+
+        /// Sweep the protocol reserves of an asset to a treasury account, via a CASH extraction notice. [Root]
+        #[weight = (<T as Config>::WeightInfo::sweep_reserves(), DispatchClass::Operational, Pays::No)]
+        pub fn sweep_reserves(origin, asset: ChainAsset, recipient: ChainAccount) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::reserves::sweep_reserves::<T>(asset, recipient))?)
+        }
+
+        /// Write off the bad debt of an account against the protocol reserves of an asset, crediting it the shortfall in CASH. [Root]
+        #[weight = (<T as Config>::WeightInfo::write_off_bad_debt(), DispatchClass::Operational, Pays::No)]
+        pub fn write_off_bad_debt(origin, account: ChainAccount, asset: ChainAsset) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::solvency::write_off_bad_debt::<T>(account, asset))?)
+        }
+
+        /// Set the liquidation incentive on seized collateral, in bips. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_liquidation_incentive(), DispatchClass::Operational, Pays::No)]
+        pub fn set_liquidation_incentive(origin, incentive: Bips) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::liquidate::set_liquidation_incentive::<T>(incentive))?)
+        }
+
+        /// Set the fraction of a borrow which may be repaid in a single liquidation. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_close_factor(), DispatchClass::Operational, Pays::No)]
+        pub fn set_close_factor(origin, close_factor: Factor) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::liquidate::set_close_factor::<T>(close_factor))?)
+        }
+
+        /// Pause or unpause seizing an asset as collateral in liquidations. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_seize_paused(), DispatchClass::Operational, Pays::No)]
+        pub fn set_seize_paused(origin, asset: ChainAsset, paused: bool) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::liquidate::set_seize_paused::<T>(asset, paused))?)
+        }
+
+        /// Set the parameters for liquidating large borrows by auction, or disable auctions given none. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_liquidation_auction(), DispatchClass::Operational, Pays::No)]
+        pub fn set_liquidation_auction(origin, config: Option<LiquidationAuctionConfig>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::liquidate::set_liquidation_auction::<T>(config))?)
+        }
+
+        /// Set the rate at which the weight of a signed extrinsic is converted to a fee in CASH. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_fee_rate(), DispatchClass::Operational, Pays::No)]
+        pub fn set_fee_rate(origin, rate: Factor) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::fees::set_fee_rate::<T>(rate))?)
+        }
+
+        /// Set the maximum fraction of the notices in an era which a validator may fail to sign, before being reported. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_max_missed_signatures(), DispatchClass::Operational, Pays::No)]
+        pub fn set_max_missed_signatures(origin, fraction: Factor) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::liveness::set_max_missed_signatures::<T>(fraction))?)
+        }
+
+        /// Set the fraction of the validators which must sign to accept a chain block or authorize a notice. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_quorum_threshold(), DispatchClass::Operational, Pays::No)]
+        pub fn set_quorum_threshold(origin, threshold: Permill) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::change_validators::set_quorum_threshold::<T>(threshold))?)
+        }
+
+        /// Set the delay before governance proposals containing sensitive changes execute, in milliseconds. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_governance_delay(), DispatchClass::Operational, Pays::No)]
+        pub fn set_governance_delay(origin, delay: Timestamp) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::governance::set_governance_delay::<T>(delay))?)
+        }
+
+        /// Cancel a queued governance proposal before it executes, in case of emergency. [Root]
+        #[weight = (<T as Config>::WeightInfo::cancel_governance(), DispatchClass::Operational, Pays::No)]
+        pub fn cancel_governance(origin, id: GovernanceId) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::governance::cancel_governance::<T>(id))?)
+        }
+
         /// Update the interest rate model for a given asset. [Root]
         #[weight = (<T as Config>::WeightInfo::set_rate_model(), DispatchClass::Operational, Pays::No)]
         pub fn set_rate_model(origin, asset: ChainAsset, model: InterestRateModel) -> dispatch::DispatchResult {
@@ -722,6 +1290,34 @@ decl_module! {
             Ok(check_failure::<T>(internal::set_yield_next::set_yield_next::<T>(next_apr, next_apr_start))?)
         }
 
+        /// Set how far and for how long wrapped CASH may trade off peg before it is reported, in basis points and milliseconds. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_peg_tolerance(), DispatchClass::Operational, Pays::No)]
+        pub fn set_peg_tolerance(origin, max_deviation_bps: u32, delay: Timestamp) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::peg::set_peg_tolerance::<T>(max_deviation_bps, delay))?)
+        }
+
+        /// Group the same asset on several chains under its symbol, or ungroup it given no assets. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_asset_group(assets.len() as u32), DispatchClass::Operational, Pays::No)]
+        pub fn set_asset_group(origin, symbol: Symbol, assets: Vec<ChainAsset>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_asset_group::<T>(symbol, assets))?)
+        }
+
+        /// Set the market an oracle ticker is expected to price, or clear it given none. [Root]
+        #[weight = (<T as Config>::WeightInfo::set_price_key(), DispatchClass::Operational, Pays::No)]
+        pub fn set_price_key(origin, ticker: Ticker, market: Option<CashOrChainAsset>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_price_key::<T>(ticker, market))?)
+        }
+
+        /// Freeze the protocol for good and return the net collateral of every account to its chain. [Root]
+        #[weight = (<T as Config>::WeightInfo::start_exodus(), DispatchClass::Operational, Pays::No)]
+        pub fn start_exodus(origin) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::exodus::start_exodus::<T>())?)
+        }
+
         /// Adds the asset to the runtime by defining it as a supported asset. [Root]
         #[weight = (<T as Config>::WeightInfo::support_asset(), DispatchClass::Operational, Pays::No)]
         pub fn support_asset(origin, asset_info: AssetInfo) -> dispatch::DispatchResult {
@@ -745,7 +1341,37 @@ decl_module! {
             Ok(check_failure::<T>(internal::events::receive_chain_reorg::<T>(reorg, signature))?)
         }
 
-        #[weight = (<T as Config>::WeightInfo::publish_signature(), DispatchClass::Operational, Pays::No)]
+        /// Report a validator for attesting to chain blocks which conflict with its prior attestations. [User] [Free]
+        #[weight = (<T as Config>::WeightInfo::report_equivocation(), DispatchClass::Operational, Pays::No)]
+        pub fn report_equivocation(origin, blocks: ChainBlocks, signature: ChainSignature) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::equivocation::report_equivocation::<T>(blocks, signature))?)
+        }
+
+        /// Receive the anchor prices a worker observed from Uniswap V3 pools. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn receive_anchor_prices(origin, report: AnchorReport, signature: ChainSignature) -> dispatch::DispatchResult {
+            log!("receive_anchor_prices(origin, report, signature): {:?} {:?}", report, signature);
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::anchors::receive_anchor_prices::<T>(report, signature))?)
+        }
+
+        /// Receive the execution fees a worker estimated from the gas prices of underlying chains.
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn receive_execution_fees(origin, report: ExecutionFeeReport, signature: ChainSignature) -> dispatch::DispatchResult {
+            log!("receive_execution_fees(origin, report, signature): {:?} {:?}", report, signature);
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::execution_fees::receive_execution_fees::<T>(report, signature))?)
+        }
+
+        /// Publish the metadata of a validator, as signed by its validator key. [User] [Free]
+        #[weight = (<T as Config>::WeightInfo::set_validator_metadata(), DispatchClass::Operational, Pays::No)]
+        pub fn set_validator_metadata(origin, metadata: ValidatorMetadata, signature: ChainSignature) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::validator_metadata::set_validator_metadata::<T>(metadata, signature))?)
+        }
+
+        #[weight = (<T as Config>::WeightInfo::publish_signature(T::MaxValidators::get()), DispatchClass::Operational, Pays::No)]
         pub fn publish_signature(origin, chain_id: ChainId, notice_id: NoticeId, signature: ChainSignature) -> dispatch::DispatchResult {
             ensure_none(origin)?;
             Ok(check_failure::<T>(internal::notices::publish_signature::<T>(chain_id, notice_id, signature))?)
@@ -780,6 +1406,10 @@ impl<T: Config> Module<T> {
         if validators.is_empty() {
             warn!("Validators must be set in the genesis config");
         }
+        assert!(
+            validators.len() <= T::MaxValidators::get() as usize,
+            "Too many validators in genesis config"
+        );
         for validator in validators {
             // Note: See pipeline commit for usage of T::AccountStore
             log!("Adding validator: {:?}", validator);
@@ -829,6 +1459,14 @@ impl<T: Config> Module<T> {
         Ok(core::get_account_balance::<T>(account, asset)?)
     }
 
+    /// Get the balance of CASH or of an asset for the given account.
+    pub fn get_balance(
+        account: ChainAccount,
+        asset: CashOrChainAsset,
+    ) -> Result<AssetBalance, Reason> {
+        Ok(core::get_balance::<T>(account, asset)?.value)
+    }
+
     /// Get the asset info for the given asset.
     pub fn get_asset(asset: ChainAsset) -> Result<AssetInfo, Reason> {
         Ok(internal::assets::get_asset::<T>(asset)?)
@@ -857,6 +1495,22 @@ impl<T: Config> Module<T> {
         Ok(core::get_liquidity::<T>(account)?.value)
     }
 
+    /// Get the liquidity and health factor the given account would have after redeeming and borrowing an asset.
+    pub fn get_hypothetical_liquidity(
+        account: ChainAccount,
+        modify_asset: ChainAsset,
+        redeem_amount: AssetAmount,
+        borrow_amount: AssetAmount,
+    ) -> Result<(AssetBalance, Option<Factor>), Reason> {
+        let (liquidity, health_factor) = core::get_hypothetical_liquidity::<T>(
+            account,
+            modify_asset,
+            redeem_amount,
+            borrow_amount,
+        )?;
+        Ok((liquidity.value, health_factor))
+    }
+
     /// Get the total supply for the given asset.
     pub fn get_market_totals(asset: ChainAsset) -> Result<(AssetAmount, AssetAmount), Reason> {
         Ok(core::get_market_totals::<T>(asset)?)
@@ -897,6 +1551,49 @@ impl<T: Config> Module<T> {
         Ok(core::get_portfolio::<T>(account)?)
     }
 
+    /// Get the last processed block number, pending block count and queued event count, per chain.
+    pub fn get_last_processed_blocks() -> Result<Vec<(ChainId, ChainBlockNumber, u32, u32)>, Reason>
+    {
+        Ok(core::get_last_processed_blocks::<T>()?)
+    }
+
+    /// Get the status of a notice and its signatures, by the hash of the notice.
+    pub fn get_notice_status(
+        notice_hash: ChainHash,
+    ) -> Result<(ChainId, NoticeId, EncodedNotice, u32, u32, bool), Reason> {
+        Ok(core::get_notice_status::<T>(notice_hash)?)
+    }
+
+    /// Get the notices for a chain which are ready to be submitted to its starport, with their signatures.
+    pub fn get_pending_notices(
+        chain_id: ChainId,
+        limit: u32,
+    ) -> Result<Vec<(NoticeId, ChainHash, EncodedNotice, Vec<Vec<u8>>)>, Reason> {
+        Ok(core::get_pending_notices::<T>(chain_id, limit)?)
+    }
+
+    /// Get the estimated fee to execute an extraction on a chain, in its native asset, and when it was observed.
+    pub fn get_execution_fee(chain_id: ChainId) -> Result<(AssetAmount, Timestamp), Reason> {
+        Ok(internal::execution_fees::get_execution_fee(chain_id)?)
+    }
+
+    /// Get the recorded (timestamp, borrow rate, supply rate, CASH index) history of an asset, oldest first.
+    pub fn get_rate_history(
+        asset: ChainAsset,
+    ) -> Result<Vec<(Timestamp, APR, APR, CashIndex)>, Reason> {
+        Ok(internal::rate_history::get_rate_history::<T>(asset)?)
+    }
+
+    /// Get the amount of an asset held by its starport, and how far it falls short of extracting an amount.
+    pub fn get_starport_liquidity(
+        asset: ChainAsset,
+        amount: AssetAmount,
+    ) -> Result<(AssetAmount, AssetAmount), Reason> {
+        Ok(internal::holdings::get_starport_liquidity::<T>(
+            asset, amount,
+        )?)
+    }
+
     /// Get the active validators, and  sets
     pub fn get_validator_info() -> Result<(Vec<ValidatorKeys>, Vec<(ChainAccount, String)>), Reason>
     {
@@ -918,6 +1615,48 @@ impl<T: Config> Module<T> {
             .collect();
         Ok((validator_keys, miner_earnings))
     }
+
+    /// Get up to a limit of the entries kept in the history of an account since a block, oldest first.
+    pub fn get_account_history(
+        account: ChainAccount,
+        from_block: u64,
+        limit: u32,
+    ) -> Result<Vec<AccountHistoryEntry>, Reason> {
+        Ok(internal::account_history::get_account_history::<T>(
+            account, from_block, limit,
+        ))
+    }
+
+    /// Execute a signed trx request against the current state without committing it.
+    /// Returns the change in the balance of each asset for every account the request touches.
+    pub fn dry_run_trx_request(
+        request: Vec<u8>,
+        signature: ChainAccountSignature,
+    ) -> Result<Vec<(ChainAccount, CashOrChainAsset, AssetBalance)>, Reason> {
+        Ok(internal::exec_trx_request::dry_run::<T>(
+            request, signature,
+        )?)
+    }
+
+    /// Get each current validator, with the metadata its operator published, if any.
+    pub fn get_validator_metadata(
+    ) -> Result<Vec<(ValidatorKeys, Option<ValidatorMetadata>)>, Reason> {
+        Ok(internal::validator_metadata::get_validator_metadata::<T>())
+    }
+
+    /// Get each current validator, with the metadata its operator published, and whether it has signed a notice.
+    pub fn get_notice_signers(
+        notice_hash: ChainHash,
+    ) -> Result<Vec<(ValidatorKeys, Option<ValidatorMetadata>, bool)>, Reason> {
+        Ok(internal::validator_metadata::get_notice_signers::<T>(
+            notice_hash,
+        )?)
+    }
+
+    /// Get a summary of the markets, CASH, validators and chains, for dashboards and monitoring.
+    pub fn get_protocol_state() -> Result<ProtocolState, Reason> {
+        Ok(core::get_protocol_state::<T>()?)
+    }
 }
 
 impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
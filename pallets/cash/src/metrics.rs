@@ -0,0 +1,92 @@
+use crate::chains::{ChainBlockNumber, ChainId};
+use runtime_interfaces::metrics_interface;
+use sp_runtime::offchain::storage::StorageValueRef;
+
+pub use runtime_interfaces::{
+    EVENTS_FETCHED, NOTICES_SIGNED, QUORUM_WAIT, RPC_LATENCY, SUBMISSION_FAILURES,
+};
+
+/// The label under which metrics for an underlying chain are exported.
+fn chain_label(chain_id: ChainId) -> &'static str {
+    match chain_id {
+        ChainId::Gate => "gate",
+        ChainId::Eth => "eth",
+        ChainId::Dot => "dot",
+        ChainId::Matic => "matic",
+        #[cfg(feature = "runtime-dev")]
+        ChainId::Mock => "mock",
+    }
+}
+
+/// Add to an offchain worker counter for an underlying chain.
+pub fn increment(name: &str, chain_id: ChainId, by: u64) {
+    metrics_interface::increment(name, chain_label(chain_id), by)
+}
+
+/// Set an offchain worker gauge for an underlying chain.
+pub fn set(name: &str, chain_id: ChainId, value: u64) {
+    metrics_interface::set(name, chain_label(chain_id), value)
+}
+
+/// Perform an RPC request to an underlying chain, recording how long it took.
+pub fn time_rpc<R, F: FnOnce() -> R>(chain_id: ChainId, request: F) -> R {
+    let start = sp_io::offchain::timestamp();
+    let result = request();
+    let elapsed = sp_io::offchain::timestamp().diff(&start).millis();
+    set(RPC_LATENCY, chain_id, elapsed);
+    result
+}
+
+/// Record how long the oldest pending block of an underlying chain has been waiting for a quorum.
+/// The wait is measured from when this worker first saw the block pending.
+pub fn track_quorum_wait(chain_id: ChainId, oldest_pending: Option<ChainBlockNumber>) {
+    let key = format!("cash::metrics::quorum_wait::{}", chain_label(chain_id));
+    let krf = StorageValueRef::persistent(key.as_bytes());
+    let now = sp_io::offchain::timestamp().unix_millis();
+    let wait = match oldest_pending {
+        Some(number) => match krf.get::<(ChainBlockNumber, u64)>() {
+            Some(Some((seen, since))) if seen == number => now.saturating_sub(since),
+            _ => {
+                krf.set(&(number, now));
+                0
+            }
+        },
+        None => 0,
+    };
+    set(QUORUM_WAIT, chain_id, wait);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use sp_core::offchain::Timestamp;
+
+    #[test]
+    fn test_track_quorum_wait() {
+        let (mut t, _pool_state, offchain_state) = new_test_ext_with_http_calls(vec![]);
+        t.execute_with(|| {
+            let wait = || runtime_interfaces::get_offchain_metric(QUORUM_WAIT, "dot");
+
+            offchain_state.write().timestamp = Timestamp::from_unix_millis(1000);
+            track_quorum_wait(ChainId::Dot, Some(10));
+            assert_eq!(wait(), Some(0));
+
+            offchain_state.write().timestamp = Timestamp::from_unix_millis(5000);
+            track_quorum_wait(ChainId::Dot, Some(10));
+            assert_eq!(wait(), Some(4000));
+
+            // a new oldest block starts waiting from scratch
+            offchain_state.write().timestamp = Timestamp::from_unix_millis(6000);
+            track_quorum_wait(ChainId::Dot, Some(11));
+            assert_eq!(wait(), Some(0));
+
+            offchain_state.write().timestamp = Timestamp::from_unix_millis(9000);
+            track_quorum_wait(ChainId::Dot, Some(11));
+            assert_eq!(wait(), Some(3000));
+
+            track_quorum_wait(ChainId::Dot, None);
+            assert_eq!(wait(), Some(0));
+        });
+    }
+}
@@ -1,13 +1,18 @@
 use crate::{
+    bounded::BoundedVec,
     chains::{Chain, ChainHash, ChainId, ChainSignature, ChainSignatureList, Ethereum, Polygon},
+    params::{NOTICE_FILTER_BYTES, NOTICE_FILTER_HASHES},
     reason::Reason,
 };
 use codec::{Decode, Encode};
-use ethabi::Token;
-use our_std::{vec::Vec, RuntimeDebug};
+use ethereum_client::abi::{self, Token};
+use our_std::{vec::Vec, Deserialize, RuntimeDebug, Serialize};
 
 use types_derive::{type_alias, Types};
 
+#[cfg(feature = "runtime-dev")]
+use crate::chains::Mock;
+
 /// Type for a generic encoded message, potentially for any chain.
 #[type_alias]
 pub type EncodedNotice = Vec<u8>;
@@ -18,6 +23,7 @@ pub type EraId = u32;
 #[type_alias]
 pub type EraIndex = u32;
 
+#[derive(Serialize, Deserialize)] // used in RPCs
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, Types)]
 pub struct NoticeId(pub EraId, pub EraIndex);
 
@@ -45,53 +51,92 @@ lazy_static! {
     static ref UNLOCK_SIG: <Ethereum as Chain>::Hash =
         <Ethereum as Chain>::hash_bytes(b"unlock(address,uint256,address)");
     static ref UNLOCK_CASH_SIG: <Ethereum as Chain>::Hash =
-        <Ethereum as Chain>::hash_bytes(b"unlockCash(address,uint128)");
+        <Ethereum as Chain>::hash_bytes(b"unlockCash(address,uint128,uint128)");
     static ref SET_FUTURE_YIELD_SIG: <Ethereum as Chain>::Hash =
         <Ethereum as Chain>::hash_bytes(b"setFutureYield(uint128,uint128,uint256)");
     static ref SET_SUPPLY_CAP_SIG: <Ethereum as Chain>::Hash =
         <Ethereum as Chain>::hash_bytes(b"setSupplyCap(address,uint256)");
     static ref CHANGE_AUTHORITIES_SIG: <Ethereum as Chain>::Hash =
         <Ethereum as Chain>::hash_bytes(b"changeAuthorities(address[])");
+    static ref UNLOCK_BATCH_SIG: <Ethereum as Chain>::Hash =
+        <Ethereum as Chain>::hash_bytes(b"unlockBatch(address[],uint256[],address[])");
 }
 
+#[derive(Serialize, Deserialize)] // used in RPCs
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum ExtractionNotice {
     Eth {
         id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
         parent: <Ethereum as Chain>::Hash,
+        #[serde(with = "crate::serdes::hex_address")]
         asset: <Ethereum as Chain>::Address,
+        #[serde(with = "crate::serdes::hex_address")]
         account: <Ethereum as Chain>::Address,
         amount: <Ethereum as Chain>::Amount,
     },
     Matic {
         id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
         parent: <Polygon as Chain>::Hash,
+        #[serde(with = "crate::serdes::hex_address")]
         asset: <Polygon as Chain>::Address,
+        #[serde(with = "crate::serdes::hex_address")]
         account: <Polygon as Chain>::Address,
         amount: <Polygon as Chain>::Amount,
     },
+    #[cfg(feature = "runtime-dev")]
+    Mock {
+        id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
+        parent: <Mock as Chain>::Hash,
+        #[serde(with = "crate::serdes::hex_address")]
+        asset: <Mock as Chain>::Address,
+        #[serde(with = "crate::serdes::hex_address")]
+        account: <Mock as Chain>::Address,
+        amount: <Mock as Chain>::Amount,
+    },
 }
 
+#[derive(Serialize, Deserialize)] // used in RPCs
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum CashExtractionNotice {
     Eth {
         id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
         parent: <Ethereum as Chain>::Hash,
+        #[serde(with = "crate::serdes::hex_address")]
         account: <Ethereum as Chain>::Address,
         principal: <Ethereum as Chain>::Amount,
+        index: <Ethereum as Chain>::CashIndex,
     },
     Matic {
         id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
         parent: <Polygon as Chain>::Hash,
+        #[serde(with = "crate::serdes::hex_address")]
         account: <Polygon as Chain>::Address,
         principal: <Polygon as Chain>::Amount,
+        index: <Polygon as Chain>::CashIndex,
+    },
+    #[cfg(feature = "runtime-dev")]
+    Mock {
+        id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
+        parent: <Mock as Chain>::Hash,
+        #[serde(with = "crate::serdes::hex_address")]
+        account: <Mock as Chain>::Address,
+        principal: <Mock as Chain>::Amount,
+        index: <Mock as Chain>::CashIndex,
     },
 }
 
+#[derive(Serialize, Deserialize)] // used in RPCs
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum FutureYieldNotice {
     Eth {
         id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
         parent: <Ethereum as Chain>::Hash,
         next_cash_yield: <Ethereum as Chain>::Rate,
         next_cash_index: <Ethereum as Chain>::CashIndex,
@@ -99,6 +144,7 @@ pub enum FutureYieldNotice {
     },
     Matic {
         id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
         parent: <Polygon as Chain>::Hash,
         next_cash_yield: <Polygon as Chain>::Rate,
         next_cash_index: <Polygon as Chain>::CashIndex,
@@ -106,36 +152,83 @@ pub enum FutureYieldNotice {
     },
 }
 
+#[derive(Serialize, Deserialize)] // used in RPCs
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum SetSupplyCapNotice {
     Eth {
         id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
         parent: <Ethereum as Chain>::Hash,
+        #[serde(with = "crate::serdes::hex_address")]
         asset: <Ethereum as Chain>::Address,
         cap: <Ethereum as Chain>::Amount,
     },
     Matic {
         id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
         parent: <Polygon as Chain>::Hash,
+        #[serde(with = "crate::serdes::hex_address")]
         asset: <Polygon as Chain>::Address,
         cap: <Polygon as Chain>::Amount,
     },
 }
 
+#[derive(Serialize, Deserialize)] // used in RPCs
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum ChangeAuthorityNotice {
     Eth {
         id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
         parent: <Ethereum as Chain>::Hash,
+        #[serde(with = "crate::serdes::hex_addresses")]
         new_authorities: Vec<<Ethereum as Chain>::Address>,
     },
     Matic {
         id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
         parent: <Polygon as Chain>::Hash,
+        #[serde(with = "crate::serdes::hex_addresses")]
         new_authorities: Vec<<Polygon as Chain>::Address>,
     },
 }
 
+/// A notice which unlocks several extractions at once, each as [asset, account, amount].
+#[derive(Serialize, Deserialize)] // used in RPCs
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub enum BatchExtractionNotice {
+    Eth {
+        id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
+        parent: <Ethereum as Chain>::Hash,
+        #[serde(with = "crate::serdes::hex_extractions")]
+        extractions: Vec<(
+            <Ethereum as Chain>::Address,
+            <Ethereum as Chain>::Address,
+            <Ethereum as Chain>::Amount,
+        )>,
+    },
+    Matic {
+        id: NoticeId,
+        #[serde(with = "crate::serdes::hex_hash")]
+        parent: <Polygon as Chain>::Hash,
+        #[serde(with = "crate::serdes::hex_extractions")]
+        extractions: Vec<(
+            <Polygon as Chain>::Address,
+            <Polygon as Chain>::Address,
+            <Polygon as Chain>::Amount,
+        )>,
+    },
+}
+
+impl BatchExtractionNotice {
+    /// The length of the encoded notice for a batch of the given number of extractions.
+    /// Each extraction adds a word to each of the three arrays, on top of the fixed header.
+    pub fn encoded_len(count: usize) -> usize {
+        ETH_CHAIN_IDENT.len() + 3 * 32 + 4 + 6 * 32 + 3 * 32 * count
+    }
+}
+
+#[derive(Serialize, Deserialize)] // used in RPCs
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum Notice {
     ExtractionNotice(ExtractionNotice),
@@ -143,11 +236,12 @@ pub enum Notice {
     FutureYieldNotice(FutureYieldNotice),
     SetSupplyCapNotice(SetSupplyCapNotice),
     ChangeAuthorityNotice(ChangeAuthorityNotice),
+    BatchExtractionNotice(BatchExtractionNotice),
 }
 
 impl Notice {
     pub fn hash(&self) -> ChainHash {
-        self.chain_id().hash_bytes(&self.encode_notice()[..])
+        self.chain_id().hash_notice(&self.encode_notice()[..])
     }
 
     pub fn chain_id(&self) -> ChainId {
@@ -155,10 +249,14 @@ impl Notice {
             Notice::ExtractionNotice(n) => match n {
                 ExtractionNotice::Eth { .. } => ChainId::Eth,
                 ExtractionNotice::Matic { .. } => ChainId::Matic,
+                #[cfg(feature = "runtime-dev")]
+                ExtractionNotice::Mock { .. } => ChainId::Mock,
             },
             Notice::CashExtractionNotice(n) => match n {
                 CashExtractionNotice::Eth { .. } => ChainId::Eth,
                 CashExtractionNotice::Matic { .. } => ChainId::Matic,
+                #[cfg(feature = "runtime-dev")]
+                CashExtractionNotice::Mock { .. } => ChainId::Mock,
             },
             Notice::FutureYieldNotice(n) => match n {
                 FutureYieldNotice::Eth { .. } => ChainId::Eth,
@@ -172,6 +270,43 @@ impl Notice {
                 ChangeAuthorityNotice::Eth { .. } => ChainId::Eth,
                 ChangeAuthorityNotice::Matic { .. } => ChainId::Matic,
             },
+            Notice::BatchExtractionNotice(n) => match n {
+                BatchExtractionNotice::Eth { .. } => ChainId::Eth,
+                BatchExtractionNotice::Matic { .. } => ChainId::Matic,
+            },
+        }
+    }
+
+    pub fn parent_hash(&self) -> ChainHash {
+        match self {
+            Notice::ExtractionNotice(n) => match n {
+                ExtractionNotice::Eth { parent, .. } => ChainHash::Eth(*parent),
+                ExtractionNotice::Matic { parent, .. } => ChainHash::Matic(*parent),
+                #[cfg(feature = "runtime-dev")]
+                ExtractionNotice::Mock { parent, .. } => ChainHash::Mock(*parent),
+            },
+            Notice::CashExtractionNotice(n) => match n {
+                CashExtractionNotice::Eth { parent, .. } => ChainHash::Eth(*parent),
+                CashExtractionNotice::Matic { parent, .. } => ChainHash::Matic(*parent),
+                #[cfg(feature = "runtime-dev")]
+                CashExtractionNotice::Mock { parent, .. } => ChainHash::Mock(*parent),
+            },
+            Notice::FutureYieldNotice(n) => match n {
+                FutureYieldNotice::Eth { parent, .. } => ChainHash::Eth(*parent),
+                FutureYieldNotice::Matic { parent, .. } => ChainHash::Matic(*parent),
+            },
+            Notice::SetSupplyCapNotice(n) => match n {
+                SetSupplyCapNotice::Eth { parent, .. } => ChainHash::Eth(*parent),
+                SetSupplyCapNotice::Matic { parent, .. } => ChainHash::Matic(*parent),
+            },
+            Notice::ChangeAuthorityNotice(n) => match n {
+                ChangeAuthorityNotice::Eth { parent, .. } => ChainHash::Eth(*parent),
+                ChangeAuthorityNotice::Matic { parent, .. } => ChainHash::Matic(*parent),
+            },
+            Notice::BatchExtractionNotice(n) => match n {
+                BatchExtractionNotice::Eth { parent, .. } => ChainHash::Eth(*parent),
+                BatchExtractionNotice::Matic { parent, .. } => ChainHash::Matic(*parent),
+            },
         }
     }
 
@@ -186,21 +321,23 @@ pub trait EncodeNotice {
 
 const ETH_CHAIN_IDENT: &'static [u8] = b"ETH:";
 const MATIC_CHAIN_IDENT: &'static [u8] = b"MAT:";
+#[cfg(feature = "runtime-dev")]
+const MOCK_CHAIN_IDENT: &'static [u8] = b"MOK:";
 
 fn encode_notice_params_eth_like(
     id: &NoticeId,
     parent: &<Ethereum as Chain>::Hash,
     signature: <Ethereum as Chain>::Hash,
-    tokens: &[ethabi::Token],
+    tokens: &[Token],
     header: Vec<u8>,
 ) -> Vec<u8> {
     let mut result = header;
-    let header_encoded = ethabi::encode(&[
-        Token::Uint(id.era_id().into()),
-        Token::Uint(id.era_index().into()),
-        Token::Uint(parent.into()),
+    let header_encoded = abi::encode(&[
+        Token::uint(id.era_id()),
+        Token::uint(id.era_index()),
+        Token::Uint(*parent),
     ]);
-    let abi_encoded = ethabi::encode(tokens);
+    let abi_encoded = abi::encode(tokens);
 
     result.extend_from_slice(&header_encoded[..]);
     result.extend_from_slice(&signature[0..4]);
@@ -222,9 +359,9 @@ impl EncodeNotice for ExtractionNotice {
                 parent,
                 *UNLOCK_SIG,
                 &[
-                    Token::Address(asset.into()),
-                    Token::Uint((*amount).into()),
-                    Token::Address(account.into()),
+                    Token::Address(*asset),
+                    Token::uint(*amount),
+                    Token::Address(*account),
                 ],
                 ETH_CHAIN_IDENT.to_vec(),
             ),
@@ -239,12 +376,30 @@ impl EncodeNotice for ExtractionNotice {
                 parent,
                 *UNLOCK_SIG,
                 &[
-                    Token::Address(asset.into()),
-                    Token::Uint((*amount).into()),
-                    Token::Address(account.into()),
+                    Token::Address(*asset),
+                    Token::uint(*amount),
+                    Token::Address(*account),
                 ],
                 MATIC_CHAIN_IDENT.to_vec(),
             ),
+            #[cfg(feature = "runtime-dev")]
+            ExtractionNotice::Mock {
+                id,
+                parent,
+                asset,
+                account,
+                amount,
+            } => encode_notice_params_eth_like(
+                id,
+                parent,
+                *UNLOCK_SIG,
+                &[
+                    Token::Address(*asset),
+                    Token::uint(*amount),
+                    Token::Address(*account),
+                ],
+                MOCK_CHAIN_IDENT.to_vec(),
+            ),
         }
     }
 }
@@ -257,13 +412,15 @@ impl EncodeNotice for CashExtractionNotice {
                 parent,
                 account,
                 principal,
+                index,
             } => encode_notice_params_eth_like(
                 id,
                 parent,
                 *UNLOCK_CASH_SIG,
                 &[
-                    Token::Address(account.into()),
-                    Token::Uint((*principal).into()),
+                    Token::Address(*account),
+                    Token::uint(*principal),
+                    Token::uint(*index),
                 ],
                 ETH_CHAIN_IDENT.to_vec(),
             ),
@@ -272,16 +429,36 @@ impl EncodeNotice for CashExtractionNotice {
                 parent,
                 account,
                 principal,
+                index,
             } => encode_notice_params_eth_like(
                 id,
                 parent,
                 *UNLOCK_CASH_SIG,
                 &[
-                    Token::Address(account.into()),
-                    Token::Uint((*principal).into()),
+                    Token::Address(*account),
+                    Token::uint(*principal),
+                    Token::uint(*index),
                 ],
                 MATIC_CHAIN_IDENT.to_vec(),
             ),
+            #[cfg(feature = "runtime-dev")]
+            CashExtractionNotice::Mock {
+                id,
+                parent,
+                account,
+                principal,
+                index,
+            } => encode_notice_params_eth_like(
+                id,
+                parent,
+                *UNLOCK_CASH_SIG,
+                &[
+                    Token::Address(*account),
+                    Token::uint(*principal),
+                    Token::uint(*index),
+                ],
+                MOCK_CHAIN_IDENT.to_vec(),
+            ),
         }
     }
 }
@@ -300,9 +477,9 @@ impl EncodeNotice for FutureYieldNotice {
                 parent,
                 *SET_FUTURE_YIELD_SIG,
                 &[
-                    Token::Uint((*next_cash_yield).into()),
-                    Token::Uint((*next_cash_index).into()),
-                    Token::Uint((*next_cash_yield_start).into()),
+                    Token::uint(*next_cash_yield),
+                    Token::uint(*next_cash_index),
+                    Token::uint(*next_cash_yield_start),
                 ],
                 ETH_CHAIN_IDENT.to_vec(),
             ),
@@ -317,9 +494,9 @@ impl EncodeNotice for FutureYieldNotice {
                 parent,
                 *SET_FUTURE_YIELD_SIG,
                 &[
-                    Token::Uint((*next_cash_yield).into()),
-                    Token::Uint((*next_cash_index).into()),
-                    Token::Uint((*next_cash_yield_start).into()),
+                    Token::uint(*next_cash_yield),
+                    Token::uint(*next_cash_index),
+                    Token::uint(*next_cash_yield_start),
                 ],
                 MATIC_CHAIN_IDENT.to_vec(),
             ),
@@ -339,7 +516,7 @@ impl EncodeNotice for SetSupplyCapNotice {
                 id,
                 parent,
                 *SET_SUPPLY_CAP_SIG,
-                &[Token::Address(asset.into()), Token::Uint((*cap).into())],
+                &[Token::Address(*asset), Token::uint(*cap)],
                 ETH_CHAIN_IDENT.to_vec(),
             ),
             SetSupplyCapNotice::Matic {
@@ -351,7 +528,7 @@ impl EncodeNotice for SetSupplyCapNotice {
                 id,
                 parent,
                 *SET_SUPPLY_CAP_SIG,
-                &[Token::Address(asset.into()), Token::Uint((*cap).into())],
+                &[Token::Address(*asset), Token::uint(*cap)],
                 MATIC_CHAIN_IDENT.to_vec(),
             ),
         }
@@ -372,7 +549,7 @@ impl EncodeNotice for ChangeAuthorityNotice {
                 &[Token::Array(
                     new_authorities
                         .iter()
-                        .map(|auth| Token::Address(auth.into()))
+                        .map(|auth| Token::Address(*auth))
                         .collect(),
                 )],
                 ETH_CHAIN_IDENT.to_vec(),
@@ -388,7 +565,7 @@ impl EncodeNotice for ChangeAuthorityNotice {
                 &[Token::Array(
                     new_authorities
                         .iter()
-                        .map(|auth| Token::Address(auth.into()))
+                        .map(|auth| Token::Address(*auth))
                         .collect(),
                 )],
                 MATIC_CHAIN_IDENT.to_vec(),
@@ -397,6 +574,64 @@ impl EncodeNotice for ChangeAuthorityNotice {
     }
 }
 
+fn encode_batch_extraction_tokens(
+    extractions: &[(
+        <Ethereum as Chain>::Address,
+        <Ethereum as Chain>::Address,
+        <Ethereum as Chain>::Amount,
+    )],
+) -> Vec<Token> {
+    vec![
+        Token::Array(
+            extractions
+                .iter()
+                .map(|(asset, _, _)| Token::Address(*asset))
+                .collect(),
+        ),
+        Token::Array(
+            extractions
+                .iter()
+                .map(|(_, _, amount)| Token::uint(*amount))
+                .collect(),
+        ),
+        Token::Array(
+            extractions
+                .iter()
+                .map(|(_, account, _)| Token::Address(*account))
+                .collect(),
+        ),
+    ]
+}
+
+impl EncodeNotice for BatchExtractionNotice {
+    fn encode_notice(&self) -> EncodedNotice {
+        match self {
+            BatchExtractionNotice::Eth {
+                id,
+                parent,
+                extractions,
+            } => encode_notice_params_eth_like(
+                id,
+                parent,
+                *UNLOCK_BATCH_SIG,
+                &encode_batch_extraction_tokens(extractions),
+                ETH_CHAIN_IDENT.to_vec(),
+            ),
+            BatchExtractionNotice::Matic {
+                id,
+                parent,
+                extractions,
+            } => encode_notice_params_eth_like(
+                id,
+                parent,
+                *UNLOCK_BATCH_SIG,
+                &encode_batch_extraction_tokens(extractions),
+                MATIC_CHAIN_IDENT.to_vec(),
+            ),
+        }
+    }
+}
+
 impl EncodeNotice for Notice {
     fn encode_notice(&self) -> EncodedNotice {
         match self {
@@ -405,6 +640,7 @@ impl EncodeNotice for Notice {
             Notice::FutureYieldNotice(n) => n.encode_notice(),
             Notice::SetSupplyCapNotice(n) => n.encode_notice(),
             Notice::ChangeAuthorityNotice(n) => n.encode_notice(),
+            Notice::BatchExtractionNotice(n) => n.encode_notice(),
         }
     }
 }
@@ -412,24 +648,32 @@ impl EncodeNotice for Notice {
 pub fn default_notice_signatures(notice: &Notice) -> ChainSignatureList {
     match notice {
         Notice::ExtractionNotice(n) => match n {
-            ExtractionNotice::Eth { .. } => ChainSignatureList::Eth(vec![]),
-            ExtractionNotice::Matic { .. } => ChainSignatureList::Matic(vec![]),
+            ExtractionNotice::Eth { .. } => ChainSignatureList::Eth(BoundedVec::default()),
+            ExtractionNotice::Matic { .. } => ChainSignatureList::Matic(BoundedVec::default()),
+            #[cfg(feature = "runtime-dev")]
+            ExtractionNotice::Mock { .. } => ChainSignatureList::Mock(BoundedVec::default()),
         },
         Notice::CashExtractionNotice(n) => match n {
-            CashExtractionNotice::Eth { .. } => ChainSignatureList::Eth(vec![]),
-            CashExtractionNotice::Matic { .. } => ChainSignatureList::Matic(vec![]),
+            CashExtractionNotice::Eth { .. } => ChainSignatureList::Eth(BoundedVec::default()),
+            CashExtractionNotice::Matic { .. } => ChainSignatureList::Matic(BoundedVec::default()),
+            #[cfg(feature = "runtime-dev")]
+            CashExtractionNotice::Mock { .. } => ChainSignatureList::Mock(BoundedVec::default()),
         },
         Notice::FutureYieldNotice(n) => match n {
-            FutureYieldNotice::Eth { .. } => ChainSignatureList::Eth(vec![]),
-            FutureYieldNotice::Matic { .. } => ChainSignatureList::Matic(vec![]),
+            FutureYieldNotice::Eth { .. } => ChainSignatureList::Eth(BoundedVec::default()),
+            FutureYieldNotice::Matic { .. } => ChainSignatureList::Matic(BoundedVec::default()),
         },
         Notice::SetSupplyCapNotice(n) => match n {
-            SetSupplyCapNotice::Eth { .. } => ChainSignatureList::Eth(vec![]),
-            SetSupplyCapNotice::Matic { .. } => ChainSignatureList::Matic(vec![]),
+            SetSupplyCapNotice::Eth { .. } => ChainSignatureList::Eth(BoundedVec::default()),
+            SetSupplyCapNotice::Matic { .. } => ChainSignatureList::Matic(BoundedVec::default()),
         },
         Notice::ChangeAuthorityNotice(n) => match n {
-            ChangeAuthorityNotice::Eth { .. } => ChainSignatureList::Eth(vec![]),
-            ChangeAuthorityNotice::Matic { .. } => ChainSignatureList::Matic(vec![]),
+            ChangeAuthorityNotice::Eth { .. } => ChainSignatureList::Eth(BoundedVec::default()),
+            ChangeAuthorityNotice::Matic { .. } => ChainSignatureList::Matic(BoundedVec::default()),
+        },
+        Notice::BatchExtractionNotice(n) => match n {
+            BatchExtractionNotice::Eth { .. } => ChainSignatureList::Eth(BoundedVec::default()),
+            BatchExtractionNotice::Matic { .. } => ChainSignatureList::Matic(BoundedVec::default()),
         },
     }
 }
@@ -456,11 +700,88 @@ impl Default for NoticeState {
     }
 }
 
+/// Type for a compact (bloom) filter of the hashes of executed notices which have been pruned.
+/// May report false positives, but never false negatives.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct NoticeFilter(pub Vec<u8>);
+
+impl NoticeFilter {
+    fn bit_indices(notice_hash: &ChainHash) -> Vec<usize> {
+        let bytes = notice_hash.encode();
+        let num_bits = NOTICE_FILTER_BYTES * 8;
+        (0..NOTICE_FILTER_HASHES)
+            .map(|i| {
+                let mut word = [0u8; 4];
+                for (j, b) in word.iter_mut().enumerate() {
+                    *b = bytes[(1 + i * 4 + j) % bytes.len()];
+                }
+                u32::from_le_bytes(word) as usize % num_bits
+            })
+            .collect()
+    }
+
+    pub fn insert(&mut self, notice_hash: &ChainHash) {
+        if self.0.len() != NOTICE_FILTER_BYTES {
+            self.0 = vec![0u8; NOTICE_FILTER_BYTES];
+        }
+        for bit in Self::bit_indices(notice_hash) {
+            self.0[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+
+    pub fn contains(&self, notice_hash: &ChainHash) -> bool {
+        self.0.len() == NOTICE_FILTER_BYTES
+            && Self::bit_indices(notice_hash)
+                .iter()
+                .all(|bit| self.0[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+}
+
+impl Default for NoticeFilter {
+    fn default() -> Self {
+        NoticeFilter(vec![])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use ethabi::{Function, Param, ParamType, Token};
 
+    #[test]
+    fn test_notice_filter() {
+        let mut filter = NoticeFilter::default();
+        let hash_a = ChainHash::Eth([1u8; 32]);
+        let hash_b = ChainHash::Eth([2u8; 32]);
+        assert!(!filter.contains(&hash_a));
+        filter.insert(&hash_a);
+        assert!(filter.contains(&hash_a));
+        assert!(!filter.contains(&hash_b));
+        assert_eq!(filter.0.len(), NOTICE_FILTER_BYTES);
+    }
+
+    #[test]
+    fn test_notice_hash_matches_starport() {
+        let notice = Notice::CashExtractionNotice(CashExtractionNotice::Eth {
+            id: NoticeId(80, 1),
+            parent: [3u8; 32],
+            account: [1u8; 20],
+            principal: 50,
+            index: 2,
+        });
+        let encoded = notice.encode_notice();
+
+        // the Starport takes `keccak256(abi.encodePacked(notice))`, with no signed message preamble
+        assert_eq!(
+            notice.hash(),
+            ChainHash::Eth(gateway_crypto::keccak(&encoded))
+        );
+        assert_ne!(
+            notice.hash(),
+            ChainHash::Eth(gateway_crypto::eth_keccak_for_signature(&encoded, true))
+        );
+    }
+
     #[test]
     fn test_encodes_extraction_notice() -> Result<(), ethabi::Error> {
         let asset = [2u8; 20];
@@ -529,12 +850,14 @@ mod tests {
     fn test_encodes_cash_extraction_notice() -> Result<(), ethabi::Error> {
         let account = [1u8; 20];
         let principal = 50;
+        let index = 60;
 
         let notice = Notice::CashExtractionNotice(CashExtractionNotice::Eth {
             id: NoticeId(80, 1),
             parent: [3u8; 32],
             account,
             principal,
+            index,
         });
 
         let expected = [
@@ -545,11 +868,13 @@ mod tests {
             0, 0, 1, // eraIndex
             3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3,
             3, 3, 3, // parent
-            0x4c, 0xfa, 0x3d, 0xa3, // Function Signature (0x4cfa3da3)
+            0x3e, 0x62, 0xd8, 0xb4, // Function Signature (0x3e62d8b4)
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
             1, 1, 1, // account
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             0, 0, 50, // principal
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 60, // index
         ];
         let encoded = notice.encode_notice();
         assert_eq!(encoded, expected);
@@ -557,6 +882,7 @@ mod tests {
         // Test against auto-encoding
         let account_token = Token::Address(account.into());
         let principal_token = Token::Uint(principal.into());
+        let index_token = Token::Uint(index.into());
 
         let unlock_cash_fn = Function {
             name: String::from("unlockCash"),
@@ -569,12 +895,16 @@ mod tests {
                     name: String::from("principal"),
                     kind: ParamType::Uint(128),
                 },
+                Param {
+                    name: String::from("index"),
+                    kind: ParamType::Uint(128),
+                },
             ],
             outputs: vec![],
             constant: false,
         };
         assert_eq!(
-            &unlock_cash_fn.encode_input(&[account_token, principal_token])?[..],
+            &unlock_cash_fn.encode_input(&[account_token, principal_token, index_token])?[..],
             &expected[100..]
         );
         Ok(())
@@ -759,4 +1089,63 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_encodes_batch_extraction_notice() -> Result<(), ethabi::Error> {
+        let extractions = vec![([1u8; 20], [2u8; 20], 50), ([3u8; 20], [4u8; 20], 60)];
+
+        let notice = Notice::BatchExtractionNotice(BatchExtractionNotice::Eth {
+            id: NoticeId(80, 1),
+            parent: [3u8; 32],
+            extractions: extractions.clone(),
+        });
+        let encoded = notice.encode_notice();
+        assert_eq!(&encoded[0..4], b"ETH:");
+        assert_eq!(encoded.len(), BatchExtractionNotice::encoded_len(2));
+
+        // Test against auto-encoding
+        let assets_token = Token::Array(
+            extractions
+                .iter()
+                .map(|(asset, _, _)| Token::Address(asset.into()))
+                .collect(),
+        );
+        let amounts_token = Token::Array(
+            extractions
+                .iter()
+                .map(|(_, _, amount)| Token::Uint((*amount).into()))
+                .collect(),
+        );
+        let accounts_token = Token::Array(
+            extractions
+                .iter()
+                .map(|(_, account, _)| Token::Address(account.into()))
+                .collect(),
+        );
+
+        let unlock_batch_fn = Function {
+            name: String::from("unlockBatch"),
+            inputs: vec![
+                Param {
+                    name: String::from("assets"),
+                    kind: ParamType::Array(Box::new(ParamType::Address)),
+                },
+                Param {
+                    name: String::from("amounts"),
+                    kind: ParamType::Array(Box::new(ParamType::Uint(256))),
+                },
+                Param {
+                    name: String::from("accounts"),
+                    kind: ParamType::Array(Box::new(ParamType::Address)),
+                },
+            ],
+            outputs: vec![],
+            constant: false,
+        };
+        assert_eq!(
+            &unlock_batch_fn.encode_input(&[assets_token, amounts_token, accounts_token])?[..],
+            &encoded[100..]
+        );
+        Ok(())
+    }
 }
@@ -1,8 +1,10 @@
 use crate::{
     chains::{ChainAccount, ChainBlockNumber},
     symbol::{CASH, USD},
-    types::{CashPrincipal, Quantity, Timestamp},
+    types::{Bips, CashPrincipal, Factor, Quantity, Timestamp},
 };
+use frame_support::parameter_types;
+use sp_runtime::Permill;
 
 /// The large value (USD) used for ingesting gov events.
 pub const INGRESS_LARGE: Quantity = Quantity::from_nominal("1000000000000", USD);
@@ -14,6 +16,35 @@ pub const INGRESS_QUOTA: Quantity = Quantity::from_nominal("10000", USD);
 /// Maximum size of the block queue before we back-off sending new blocks.
 pub const INGRESS_SLACK: u32 = 50;
 
+/// Number of underlying chain blocks behind the last processed block for which validator attestations are retained.
+/// Equivocations may only be reported while the conflicting attestation is retained.
+pub const ATTESTATION_RETENTION_BLOCKS: ChainBlockNumber = 1000;
+
+/// Default maximum fraction of the notices in an era which a validator may fail to sign, before being reported.
+pub const DEFAULT_MAX_MISSED_SIGNATURES: Factor = Factor::from_nominal("0.5");
+
+/// Minimum number of notices left pending at the end of an era, for validators to be judged unresponsive.
+pub const MIN_LIVENESS_NOTICES: u32 = 4;
+
+/// Default fraction of the validators which must sign to accept a chain block or authorize a notice.
+/// Just under two thirds, so that rounding up requires exactly two thirds of any realistic validator set.
+pub const DEFAULT_QUORUM_THRESHOLD: Permill = Permill::from_parts(666_666);
+
+/// Default distance of the market price of wrapped CASH from one dollar, in basis points, beyond which it is off peg.
+pub const DEFAULT_PEG_TOLERANCE_BPS: u32 = 200;
+
+/// Default time (milliseconds) wrapped CASH must stay off peg before the deviation is reported to governance.
+pub const DEFAULT_PEG_REPORT_DELAY: Timestamp = 24 * 60 * 60 * 1000;
+
+/// The fraction of the validators which the quorum threshold must exceed, so that no two quorums can disagree.
+pub const MIN_QUORUM_THRESHOLD: Permill = Permill::from_percent(50);
+
+/// Fraction of the CASH principal of a validator which is slashed for equivocating.
+pub const EQUIVOCATION_SLASH_FRACTION: Factor = Factor::from_nominal("0.1");
+
+/// Maximum difference (milliseconds) between a timestamp reported by an underlying chain and the Gateway clock.
+pub const MAX_CHAIN_TIMESTAMP_DRIFT: Timestamp = 15 * 60 * 1000;
+
 /// Number of milliseconds in a year.
 pub const MILLISECONDS_PER_YEAR: Timestamp = 365 * 24 * 60 * 60 * 1000;
 
@@ -23,6 +54,130 @@ pub const MIN_EVENT_BLOCKS: ChainBlockNumber = 3;
 /// Maximum number of underlying chain blocks to wait before just ingesting any event.
 pub const MAX_EVENT_BLOCKS: ChainBlockNumber = 60;
 
+/// Length of the rolling window (milliseconds) over which per-asset extraction limits apply.
+pub const EXTRACTION_WINDOW: Timestamp = 24 * 60 * 60 * 1000;
+
+/// Length of the buckets (milliseconds) in which extractions are tallied within the rolling window.
+/// An extraction counts against the limit for at least a full window, and at most one bucket longer.
+pub const EXTRACTION_BUCKET: Timestamp = 60 * 60 * 1000;
+
+/// Maximum number of extractions of each asset which may wait for the extraction limit.
+pub const MAX_QUEUED_EXTRACTIONS: u32 = 256;
+
+/// Maximum number of extraction queues read or extractions released in a single block.
+pub const EXTRACTION_RELEASE_BATCH_SIZE: u32 = 32;
+
+/// Number of workers chosen each block to propose new underlying chain blocks.
+/// Every worker still supports blocks which have already been proposed.
+pub const WORKER_LEADERS_PER_BLOCK: u64 = 2;
+
+/// Default maximum number of underlying chain blocks a worker scans for events per invocation.
+/// Validators may override this with the `WORKER_MAX_BLOCKS_PER_SCAN` config.
+pub const WORKER_MAX_BLOCKS_PER_SCAN: u64 = 20;
+
+/// Default maximum number of underlying chain events a worker submits in a single extrinsic.
+/// Validators may override this with the `WORKER_MAX_EVENTS_PER_SUBMISSION` config.
+pub const WORKER_MAX_EVENTS_PER_SUBMISSION: u64 = 200;
+
+/// Number of blocks between anchor price observations of Uniswap V3 pools by the worker leaders.
+pub const ANCHOR_POLL_INTERVAL_BLOCKS: u64 = 10;
+
+/// Number of blocks between gas price observations of underlying chains by the worker leaders.
+pub const EXECUTION_FEE_POLL_INTERVAL_BLOCKS: u64 = 50;
+
+/// Estimated gas used to execute an extraction notice through the Starport, including signature checks.
+pub const EXTRACTION_GAS_ESTIMATE: u128 = 150_000;
+
+/// Delay (milliseconds) before a recovery approved by the guardians of an account executes.
+/// Gives the owner of the account time to cancel a recovery it did not ask for.
+pub const RECOVERY_DELAY: Timestamp = 2 * 24 * 60 * 60 * 1000;
+
+/// Maximum number of guardians an account may nominate.
+pub const MAX_GUARDIANS: usize = 10;
+
+/// Maximum number of chains on which the same asset may be grouped under its symbol.
+pub const MAX_ASSET_GROUP_SIZE: usize = 8;
+
+/// Maximum length (bytes) of the name an operator publishes for their validator.
+pub const MAX_VALIDATOR_NAME_LEN: usize = 64;
+
+/// Maximum length (bytes) of the website an operator publishes for their validator.
+pub const MAX_VALIDATOR_WEBSITE_LEN: usize = 128;
+
+/// Interval (milliseconds) between snapshots of the CASH index and asset rates kept in the rate history.
+pub const RATE_HISTORY_INTERVAL: Timestamp = 60 * 60 * 1000;
+
+/// Number of snapshots kept in the rate history, before the oldest is overwritten.
+pub const RATE_HISTORY_LENGTH: u32 = 30 * 24;
+
+/// Interval (milliseconds) between snapshots of the solvency of the protocol.
+pub const SOLVENCY_SNAPSHOT_INTERVAL: Timestamp = 60 * 60 * 1000;
+
+/// Number of entries kept in the history of each account, before the oldest is overwritten.
+pub const ACCOUNT_HISTORY_LENGTH: u32 = 100;
+
+/// Number of blocks to wait before first retrying a failed chain event, doubling after each retry.
+pub const FAILED_EVENT_RETRY_BLOCKS: u64 = 10;
+
+/// Maximum number of times to retry a failed chain event, before leaving it to governance.
+pub const FAILED_EVENT_MAX_RETRIES: u32 = 8;
+
+/// Maximum number of failed chain events kept to retry.
+/// Beyond it, failed events are only reported, until governance discards some.
+pub const MAX_FAILED_EVENTS: u32 = 256;
+
+/// Maximum number of failed chain events retried in a single block.
+pub const FAILED_EVENT_RETRY_BATCH_SIZE: u32 = 8;
+
+/// Default number of blocks a pending chain block may wait for a quorum, before its events time out.
+pub const DEFAULT_EVENT_EXPIRATION_BLOCKS: u32 = 600;
+
+parameter_types! {
+    /// Maximum number of validators in any validator set, which the runtime's `MaxValidators` may not exceed.
+    /// Bounds the notice signature lists and event attestation sets as stored, which only ever hold validators.
+    /// Since the bound is enforced when decoding, it may never decrease without migrating the stored sets.
+    pub const MaxValidators: u32 = 100;
+}
+
+/// Default number of notice eras for which executed notices are retained, before being pruned.
+pub const DEFAULT_NOTICE_RETENTION_ERAS: u32 = 4;
+
+/// Number of bips in a whole.
+pub const BIPS_PER_UNIT: Bips = 10_000;
+
+/// Default liquidation incentive on seized collateral, until set by governance.
+pub const DEFAULT_LIQUIDATION_INCENTIVE: Bips = 800; // 8%
+
+/// Maximum liquidation incentive on seized collateral which governance may set.
+pub const MAX_LIQUIDATION_INCENTIVE: Bips = 5000; // 50%
+
+/// Default close factor, the fraction of a borrow which may be repaid in a single liquidation.
+pub const DEFAULT_CLOSE_FACTOR: Factor = Factor::ONE;
+
+/// Maximum number of executed notices to prune in a single block.
+pub const MAX_PRUNE_NOTICES: u32 = 100;
+
+/// Maximum number of queued governance proposals executed in a single block.
+pub const GOVERNANCE_RELEASE_BATCH_SIZE: u32 = 4;
+
+/// Maximum number of queued recoveries read or executed in a single block.
+pub const RECOVERY_RELEASE_BATCH_SIZE: u32 = 8;
+
+/// Maximum number of pending extractions dispatched in batch notices in a single block.
+pub const MAX_BATCHED_EXTRACTIONS: u32 = 128;
+
+/// Default weight budget for the deferrable work done as each block is initialized, a quarter of a block.
+pub const DEFAULT_INITIALIZE_WEIGHT_BUDGET: u64 = 500_000_000_000;
+
+/// Number of accounts whose collateral is returned in each block of an exodus.
+pub const EXODUS_BATCH_SIZE: u32 = 50;
+
+/// Size (bytes) of the filter used to remember pruned notices, per chain.
+pub const NOTICE_FILTER_BYTES: usize = 4096;
+
+/// Number of bits set in the filter used to remember pruned notices, per notice.
+pub const NOTICE_FILTER_HASHES: usize = 3;
+
 /// Minimum amount of time (milliseconds) into the future that a synchronized change may be scheduled for.
 /// Must be sufficient time to propagate changes to L1s before they occur.
 pub const MIN_NEXT_SYNC_TIME: Timestamp = 24 * 60 * 60 * 1000; // XXX confirm
@@ -34,6 +189,9 @@ pub const MIN_PRINCIPAL_GATE: CashPrincipal = CashPrincipal::from_nominal("1");
 /// Minimum value (USD) required across all protocol interactions.
 pub const MIN_TX_VALUE: Quantity = Quantity::from_nominal("1", USD);
 
+/// Default rate at which the weight of a signed extrinsic is converted to a fee, in CASH per unit of weight.
+pub const DEFAULT_CASH_FEE_PER_WEIGHT: Factor = Factor::from_nominal("0.0001");
+
 /// Flat transfer fee (CASH).
 pub const TRANSFER_FEE: Quantity = Quantity::from_nominal("0.01", CASH);
 
@@ -43,6 +201,9 @@ pub const SESSION_PERIOD: u32 = 14400; // Assuming 6s blocks, ~1 period per day
 /// Standard priority for all unsigned transactions.
 pub const UNSIGNED_TXS_PRIORITY: u64 = 100;
 
+/// Priority for unsigned transactions which complete a quorum, so they are included first.
+pub const UNSIGNED_TXS_QUORUM_PRIORITY: u64 = 200;
+
 /// Standard longevity for all unsigned transactions.
 pub const UNSIGNED_TXS_LONGEVITY: u64 = 32;
 
@@ -54,3 +215,7 @@ pub const GATEWAY_VOID: ChainAccount = ChainAccount::Gate([0u8; 32]);
 
 /// The maximum length of a trx request
 pub const MAX_TRX_REQUEST_LEN: usize = 2048;
+
+/// The current version of the storage layout.
+/// Must be bumped along with a migration whenever the encoding of stored types changes.
+pub const CURRENT_STORAGE_VERSION: u32 = 6;
@@ -8,16 +8,19 @@ use sp_core::crypto::AccountId32;
 
 use crate::{
     chains::{ChainAccount, ChainId},
-    internal::balance_helpers::*,
+    internal::accrual::{get_accrued_indices, get_asset_accrual, put_asset_accrual, AssetAccrual},
     params::MIN_PRINCIPAL_GATE,
     portfolio::Portfolio,
+    pure::{self, Market, Position},
     reason::Reason,
+    symbol::Symbol,
     types::{
         AssetBalance, AssetIndex, AssetInfo, Balance, CashPrincipal, CashPrincipalAmount, Quantity,
     },
-    AssetAmount, AssetBalances, AssetsWithNonZeroBalance, BorrowIndices, CashPrincipals,
-    ChainAsset, ChainCashPrincipals, Config, GlobalCashIndex, LastIndices, SupplyIndices,
-    SupportedAssets, TotalBorrowAssets, TotalCashPrincipal, TotalSupplyAssets,
+    AssetAmount, AssetBalances, AssetSymbols, AssetsWithNonZeroBalance, BorrowIndices,
+    CashPrincipals, ChainAsset, ChainCashPrincipals, Config, GlobalCashIndex, LastIndices,
+    LastMinerSharePrincipal, SupplyIndices, SupportedAssets, TotalBorrowAssets, TotalCashPrincipal,
+    TotalSupplyAssets,
 };
 
 trait Apply {
@@ -34,6 +37,8 @@ pub struct State {
     cash_principals: BTreeMap<ChainAccount, CashPrincipal>,
     total_cash_principal: Option<CashPrincipalAmount>,
     chain_cash_principals: BTreeMap<ChainId, CashPrincipalAmount>,
+    asset_accruals: BTreeMap<ChainAsset, AssetAccrual>,
+    last_miner_share_principal: Option<CashPrincipalAmount>,
 }
 
 impl State {
@@ -47,6 +52,8 @@ impl State {
             cash_principals: BTreeMap::new(),
             total_cash_principal: None,
             chain_cash_principals: BTreeMap::new(),
+            asset_accruals: BTreeMap::new(),
+            last_miner_share_principal: None,
         }
     }
 
@@ -57,15 +64,16 @@ impl State {
         let mut principal = self.get_cash_principal::<T>(account);
         let global_cash_index = GlobalCashIndex::get();
 
-        let mut positions = Vec::new();
+        // positions in grouped assets are aggregated into one position per symbol
+        let mut positions: Vec<(AssetInfo, Balance)> = Vec::new();
+        let mut symbols: Vec<Option<Symbol>> = Vec::new();
         for asset in self.get_assets_with_non_zero_balance::<T>(account) {
             let asset_info = SupportedAssets::get(asset).ok_or(Reason::AssetNotSupported)?;
-            let supply_index = SupplyIndices::get(asset);
-            let borrow_index = BorrowIndices::get(asset);
+            let (supply_index, borrow_index) = self.get_asset_indices::<T>(asset)?;
             let balance = self.get_asset_balance::<T>(asset_info, account);
             let last_index = self.get_last_index::<T>(asset_info, account);
 
-            (principal, _) = effect_of_asset_interest_internal(
+            (principal, _) = pure::effect_of_asset_interest(
                 balance,
                 balance,
                 principal,
@@ -73,7 +81,15 @@ impl State {
                 supply_index,
                 borrow_index,
             )?;
-            positions.push((asset_info, balance));
+
+            let symbol = AssetSymbols::get(asset);
+            match symbol.and_then(|_| symbols.iter().position(|s| *s == symbol)) {
+                Some(index) => positions[index].1 = positions[index].1.add(balance)?,
+                None => {
+                    positions.push((asset_info, balance));
+                    symbols.push(symbol);
+                }
+            }
         }
 
         let cash = global_cash_index.cash_balance(principal)?;
@@ -214,6 +230,55 @@ impl State {
         self.total_cash_principal = Some(total_cash_principal);
     }
 
+    pub fn get_last_miner_share_principal<T: Config>(self: &Self) -> CashPrincipalAmount {
+        self.last_miner_share_principal
+            .unwrap_or_else(|| LastMinerSharePrincipal::get())
+    }
+
+    pub fn set_last_miner_share_principal<T: Config>(
+        self: &mut Self,
+        last_miner_share_principal: CashPrincipalAmount,
+    ) {
+        self.last_miner_share_principal = Some(last_miner_share_principal);
+    }
+
+    /// Return the supply and borrow indices of an asset, including any interest not yet accrued.
+    pub fn get_asset_indices<T: Config>(
+        self: &Self,
+        asset: ChainAsset,
+    ) -> Result<(AssetIndex, AssetIndex), Reason> {
+        match self.asset_accruals.get(&asset) {
+            Some(accrual) => Ok((accrual.supply_index, accrual.borrow_index)),
+            None => get_accrued_indices::<T>(asset),
+        }
+    }
+
+    /// Accrue interest on an asset the first time it is touched, returning its supply and borrow indices.
+    /// Must be called before the totals of the asset are changed.
+    pub fn accrue_asset<T: Config>(
+        self: &mut Self,
+        asset: ChainAsset,
+    ) -> Result<(AssetIndex, AssetIndex), Reason> {
+        if let Some(accrual) = self.asset_accruals.get(&asset) {
+            return Ok((accrual.supply_index, accrual.borrow_index));
+        }
+        match get_asset_accrual::<T>(asset)? {
+            Some(accrual) => {
+                let total_cash_principal_new = self
+                    .get_total_cash_principal::<T>()
+                    .add(accrual.cash_principal_borrow_increase)?;
+                let last_miner_share_principal_new = self
+                    .get_last_miner_share_principal::<T>()
+                    .add(accrual.miner_share_principal)?;
+                self.set_total_cash_principal::<T>(total_cash_principal_new);
+                self.set_last_miner_share_principal::<T>(last_miner_share_principal_new);
+                self.asset_accruals.insert(asset, accrual);
+                Ok((accrual.supply_index, accrual.borrow_index))
+            }
+            None => Ok((SupplyIndices::get(asset), BorrowIndices::get(asset))),
+        }
+    }
+
     pub fn get_chain_cash_principal<T: Config>(
         self: &Self,
         chain_id: ChainId,
@@ -294,94 +359,68 @@ impl State {
             .for_each(|(chain_id, chain_cash_principal)| {
                 ChainCashPrincipals::insert(chain_id, chain_cash_principal);
             });
+        self.asset_accruals
+            .iter()
+            .for_each(|(chain_asset, accrual)| {
+                put_asset_accrual(*chain_asset, *accrual);
+            });
+        if let Some(last_miner_share_principal_new) = self.last_miner_share_principal {
+            LastMinerSharePrincipal::put(last_miner_share_principal_new);
+        }
     }
 }
 
 fn prepare_augment_asset<T: Config>(
-    mut st: State,
+    st: State,
     recipient: ChainAccount,
     asset: ChainAsset,
     quantity: Quantity,
 ) -> Result<State, Reason> {
-    let asset_info = SupportedAssets::get(asset).ok_or(Reason::AssetNotSupported)?;
-    let supply_index = SupplyIndices::get(asset);
-    let borrow_index = BorrowIndices::get(asset);
-    let total_supply_pre = st.get_total_supply_asset::<T>(asset_info);
-    let total_borrow_pre = st.get_total_borrow_asset::<T>(asset_info);
-    let recipient_balance_pre = st.get_asset_balance::<T>(asset_info, recipient);
-    let recipient_last_index_pre = st.get_last_index::<T>(asset_info, recipient);
-    let recipient_cash_principal_pre = st.get_cash_principal::<T>(recipient);
-
-    let (recipient_repay_amount, recipient_supply_amount) =
-        repay_and_supply_amount(recipient_balance_pre.value, quantity)?;
-
-    let total_supply_new = total_supply_pre.add(recipient_supply_amount)?;
-
-    let total_borrow_new = total_borrow_pre
-        .sub(recipient_repay_amount)
-        .map_err(|_| Reason::TotalBorrowUnderflow)?;
-
-    let recipient_balance_post = recipient_balance_pre.add_quantity(quantity)?;
-
-    let (recipient_cash_principal_post, recipient_last_index_post) =
-        effect_of_asset_interest_internal(
-            recipient_balance_pre,
-            recipient_balance_post,
-            recipient_cash_principal_pre,
-            recipient_last_index_pre,
-            supply_index,
-            borrow_index,
-        )?;
-
-    st.set_total_supply_asset::<T>(asset_info, total_supply_new);
-    st.set_total_borrow_asset::<T>(asset_info, total_borrow_new);
-    st.set_asset_balance::<T>(asset_info, recipient, recipient_balance_post);
-    st.set_last_index::<T>(asset_info, recipient, recipient_last_index_post);
-    st.set_cash_principal::<T>(recipient, recipient_cash_principal_post);
-
-    Ok(st)
+    prepare_asset::<T>(st, recipient, asset, |market, position| {
+        pure::augment_asset(market, position, quantity)
+    })
 }
 
 fn prepare_reduce_asset<T: Config>(
-    mut st: State,
+    st: State,
     sender: ChainAccount,
     asset: ChainAsset,
     quantity: Quantity,
+) -> Result<State, Reason> {
+    prepare_asset::<T>(st, sender, asset, |market, position| {
+        pure::reduce_asset(market, position, quantity)
+    })
+}
+
+/// Load the market in an asset and the position of an account in it, and store the result of changing them.
+fn prepare_asset<T: Config>(
+    mut st: State,
+    account: ChainAccount,
+    asset: ChainAsset,
+    f: impl FnOnce(Market, Position) -> Result<(Market, Position), Reason>,
 ) -> Result<State, Reason> {
     let asset_info = SupportedAssets::get(asset).ok_or(Reason::AssetNotSupported)?;
-    let supply_index = SupplyIndices::get(asset);
-    let borrow_index = BorrowIndices::get(asset);
-    let total_supply_pre = st.get_total_supply_asset::<T>(asset_info);
-    let total_borrow_pre = st.get_total_borrow_asset::<T>(asset_info);
-    let sender_balance_pre = st.get_asset_balance::<T>(asset_info, sender);
-    let sender_last_index_pre = st.get_last_index::<T>(asset_info, sender);
-    let sender_cash_principal_pre = st.get_cash_principal::<T>(sender);
-
-    let (sender_withdraw_amount, sender_borrow_amount) =
-        withdraw_and_borrow_amount(sender_balance_pre.value, quantity)?;
-
-    let total_supply_new = total_supply_pre
-        .sub(sender_withdraw_amount)
-        .map_err(|_| Reason::InsufficientTotalFunds)?;
-
-    let total_borrow_new = total_borrow_pre.add(sender_borrow_amount)?;
-
-    let sender_balance_post = sender_balance_pre.sub_quantity(quantity)?;
-
-    let (sender_cash_principal_post, sender_last_index_post) = effect_of_asset_interest_internal(
-        sender_balance_pre,
-        sender_balance_post,
-        sender_cash_principal_pre,
-        sender_last_index_pre,
+    let (supply_index, borrow_index) = st.accrue_asset::<T>(asset)?;
+    let market_pre = Market {
+        total_supply: st.get_total_supply_asset::<T>(asset_info),
+        total_borrow: st.get_total_borrow_asset::<T>(asset_info),
         supply_index,
         borrow_index,
-    )?;
+        borrow_cap: asset_info.borrow_cap,
+    };
+    let position_pre = Position {
+        balance: st.get_asset_balance::<T>(asset_info, account),
+        last_index: st.get_last_index::<T>(asset_info, account),
+        cash_principal: st.get_cash_principal::<T>(account),
+    };
 
-    st.set_total_supply_asset::<T>(asset_info, total_supply_new);
-    st.set_total_borrow_asset::<T>(asset_info, total_borrow_new);
-    st.set_asset_balance::<T>(asset_info, sender, sender_balance_post);
-    st.set_last_index::<T>(asset_info, sender, sender_last_index_post);
-    st.set_cash_principal::<T>(sender, sender_cash_principal_post);
+    let (market_post, position_post) = f(market_pre, position_pre)?;
+
+    st.set_total_supply_asset::<T>(asset_info, market_post.total_supply);
+    st.set_total_borrow_asset::<T>(asset_info, market_post.total_borrow);
+    st.set_asset_balance::<T>(asset_info, account, position_post.balance);
+    st.set_last_index::<T>(asset_info, account, position_post.last_index);
+    st.set_cash_principal::<T>(account, position_post.cash_principal);
 
     Ok(st)
 }
@@ -392,26 +431,19 @@ fn prepare_augment_cash<T: Config>(
     principal: CashPrincipalAmount,
     from_external: bool,
 ) -> Result<State, Reason> {
-    let recipient_cash_pre = st.get_cash_principal::<T>(recipient);
-
-    let (recipient_repay_principal, _recipient_supply_principal) =
-        repay_and_supply_principal(recipient_cash_pre, principal)?;
-
-    let recipient_cash_post = recipient_cash_pre.add_amount(principal)?;
-    let total_cash_post = st
-        .get_total_cash_principal::<T>()
-        .sub(recipient_repay_principal)
-        .map_err(|_| Reason::InsufficientChainCash)?;
+    let (recipient_cash_post, total_cash_post) = pure::augment_cash(
+        st.get_cash_principal::<T>(recipient),
+        st.get_total_cash_principal::<T>(),
+        principal,
+    )?;
 
     st.set_cash_principal::<T>(recipient, recipient_cash_post);
     st.set_total_cash_principal::<T>(total_cash_post);
 
     if from_external {
         let chain_id = recipient.chain_id();
-        let chain_cash_principal_post = st
-            .get_chain_cash_principal::<T>(chain_id)
-            .sub(principal)
-            .map_err(|_| Reason::NegativeChainCash)?;
+        let chain_cash_principal_post =
+            pure::lock_chain_cash(st.get_chain_cash_principal::<T>(chain_id), principal)?;
         st.set_chain_cash_principal::<T>(chain_id, chain_cash_principal_post);
     }
 
@@ -424,15 +456,11 @@ fn prepare_reduce_cash<T: Config>(
     principal: CashPrincipalAmount,
     to_external: bool,
 ) -> Result<State, Reason> {
-    let sender_cash_pre = st.get_cash_principal::<T>(sender);
-
-    let (_sender_withdraw_principal, sender_borrow_principal) =
-        withdraw_and_borrow_principal(sender_cash_pre, principal)?;
-
-    let sender_cash_post = sender_cash_pre.sub_amount(principal)?;
-    let total_cash_post = st
-        .get_total_cash_principal::<T>()
-        .add(sender_borrow_principal)?;
+    let (sender_cash_post, total_cash_post) = pure::reduce_cash(
+        st.get_cash_principal::<T>(sender),
+        st.get_total_cash_principal::<T>(),
+        principal,
+    )?;
 
     st.set_cash_principal::<T>(sender, sender_cash_post);
     st.set_total_cash_principal::<T>(total_cash_post);
@@ -440,7 +468,7 @@ fn prepare_reduce_cash<T: Config>(
     if to_external {
         let chain_id = sender.chain_id();
         let chain_cash_principal_post =
-            st.get_chain_cash_principal::<T>(chain_id).add(principal)?;
+            pure::extract_chain_cash(st.get_chain_cash_principal::<T>(chain_id), principal)?;
         st.set_chain_cash_principal::<T>(chain_id, chain_cash_principal_post);
     }
 
@@ -588,6 +616,19 @@ impl CashPipeline {
         })
     }
 
+    /// Credit CASH principal to an account which does not come from another account, such as from protocol reserves.
+    pub fn credit_cash<T: Config>(
+        self: Self,
+        recipient: ChainAccount,
+        principal: CashPrincipalAmount,
+    ) -> Result<Self, Reason> {
+        self.apply_effect::<T>(Effect::AugmentCash {
+            recipient,
+            principal,
+            from_external: false,
+        })
+    }
+
     pub fn lock_cash<T: Config>(
         self: Self,
         recipient: ChainAccount,
@@ -627,6 +668,22 @@ impl CashPipeline {
         }
     }
 
+    /// Check that the account is collateralized using only fresh prices, for anything which may increase a borrow.
+    pub fn check_collateralized_fresh<T: Config>(
+        self: Self,
+        account: ChainAccount,
+    ) -> Result<Self, Reason> {
+        let liquidity = self
+            .state
+            .build_portfolio::<T>(account)?
+            .get_fresh_liquidity::<T>()?;
+        if liquidity.value < 0 {
+            Err(Reason::InsufficientLiquidity)?
+        } else {
+            Ok(self)
+        }
+    }
+
     pub fn check_underwater<T: Config>(self: Self, account: ChainAccount) -> Result<Self, Reason> {
         let liquidity = self
             .state
@@ -684,31 +741,6 @@ impl CashPipeline {
     }
 }
 
-/// Return CASH Principal including asset interest, and a new asset index,
-///  given a balance change, the previous position, and current market global indices.
-fn effect_of_asset_interest_internal(
-    balance_old: Balance,
-    balance_new: Balance,
-    cash_principal_pre: CashPrincipal,
-    last_index: AssetIndex,
-    supply_index: AssetIndex,
-    borrow_index: AssetIndex,
-) -> Result<(CashPrincipal, AssetIndex), Reason> {
-    let cash_index = if balance_old.value >= 0 {
-        supply_index
-    } else {
-        borrow_index
-    };
-    let cash_principal_delta = cash_index.cash_principal_since(last_index, balance_old)?;
-    let cash_principal_post = cash_principal_pre.add(cash_principal_delta)?;
-    let last_index_post = if balance_new.value >= 0 {
-        supply_index
-    } else {
-        borrow_index
-    };
-    Ok((cash_principal_post, last_index_post))
-}
-
 pub fn load_portfolio<T: Config>(account: ChainAccount) -> Result<Portfolio, Reason> {
     CashPipeline::new().state.build_portfolio::<T>(account)
 }
@@ -718,8 +750,10 @@ mod tests {
     use super::*;
     use crate::{
         chains::*,
+        params::MILLISECONDS_PER_YEAR,
         tests::{assert_ok, assets::*, common::*, mock::*},
         types::*,
+        BorrowRates, LastAccrualTimestamps, LastBlockTimestamp, ProtocolReserves,
     };
     use our_std::convert::TryInto;
 
@@ -769,11 +803,103 @@ mod tests {
                     .collect(),
                     total_cash_principal: None,
                     chain_cash_principals: vec![].into_iter().collect(),
+                    asset_accruals: vec![].into_iter().collect(),
+                    last_miner_share_principal: None,
                 }
             );
         })
     }
 
+    #[test]
+    fn test_transfer_asset_accrues_interest() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+            let now = 10 + MILLISECONDS_PER_YEAR / 4;
+            LastBlockTimestamp::put(now);
+            LastAccrualTimestamps::insert(Eth, 10);
+            TotalSupplyAssets::insert(Eth, eth.as_quantity_nominal("300").value);
+            TotalBorrowAssets::insert(Eth, eth.as_quantity_nominal("150").value);
+            let accrual = get_asset_accrual::<Test>(Eth)
+                .expect("get_asset_accrual failed")
+                .expect("nothing to accrue");
+
+            let state = CashPipeline::new()
+                .transfer_asset::<Test>(account_a, account_b, Eth, eth.as_quantity_nominal("1"))
+                .expect("transfer_asset failed")
+                .state;
+
+            assert_eq!(
+                state.asset_accruals,
+                vec![(Eth, accrual)].into_iter().collect()
+            );
+            assert_eq!(
+                state.last_indices.get(&(Eth, account_b)),
+                Some(&accrual.supply_index)
+            );
+            assert_eq!(
+                state.get_last_miner_share_principal::<Test>(),
+                accrual.miner_share_principal
+            );
+
+            state.commit::<Test>();
+
+            assert_eq!(LastAccrualTimestamps::get(Eth), now);
+            assert_eq!(SupplyIndices::get(Eth), accrual.supply_index);
+            assert_eq!(BorrowIndices::get(Eth), accrual.borrow_index);
+            assert_eq!(
+                TotalCashPrincipal::get(),
+                accrual.cash_principal_borrow_increase
+            );
+        })
+    }
+
+    #[test]
+    fn test_transfer_asset_borrow_cap() {
+        new_test_ext().execute_with(|| {
+            SupportedAssets::insert(
+                Eth,
+                AssetInfo {
+                    borrow_cap: eth.as_quantity_nominal("1").value,
+                    ..eth
+                },
+            );
+
+            assert_eq!(
+                CashPipeline::new()
+                    .transfer_asset::<Test>(account_a, account_b, Eth, eth.as_quantity_nominal("2"))
+                    .map(|_| ()),
+                Err(Reason::BorrowCapExceeded)
+            );
+            CashPipeline::new()
+                .transfer_asset::<Test>(account_a, account_b, Eth, eth.as_quantity_nominal("1"))
+                .expect("transfer_asset failed")
+                .commit::<Test>();
+
+            // A cap of zero winds down borrowing, but repayments still go through
+            SupportedAssets::insert(
+                Eth,
+                AssetInfo {
+                    borrow_cap: 0,
+                    ..eth
+                },
+            );
+            assert_eq!(
+                CashPipeline::new()
+                    .transfer_asset::<Test>(account_a, account_b, Eth, eth.as_quantity_nominal("1"))
+                    .map(|_| ()),
+                Err(Reason::BorrowCapExceeded)
+            );
+            CashPipeline::new()
+                .transfer_asset::<Test>(account_b, account_a, Eth, eth.as_quantity_nominal("0.5"))
+                .expect("transfer_asset failed")
+                .commit::<Test>();
+            assert_eq!(
+                TotalBorrowAssets::get(Eth),
+                eth.as_quantity_nominal("0.5").value
+            );
+        })
+    }
+
     #[test]
     fn test_lock_asset_success_state() {
         new_test_ext().execute_with(|| {
@@ -804,6 +930,8 @@ mod tests {
                         .collect(),
                     total_cash_principal: None,
                     chain_cash_principals: vec![].into_iter().collect(),
+                    asset_accruals: vec![].into_iter().collect(),
+                    last_miner_share_principal: None,
                 }
             );
         })
@@ -839,6 +967,8 @@ mod tests {
                         .collect(),
                     total_cash_principal: None,
                     chain_cash_principals: vec![].into_iter().collect(),
+                    asset_accruals: vec![].into_iter().collect(),
+                    last_miner_share_principal: None,
                 }
             );
         })
@@ -870,6 +1000,8 @@ mod tests {
                     .collect(),
                     total_cash_principal: Some(quantity),
                     chain_cash_principals: vec![].into_iter().collect(),
+                    asset_accruals: vec![].into_iter().collect(),
+                    last_miner_share_principal: None,
                 }
             );
         })
@@ -904,6 +1036,8 @@ mod tests {
                     )]
                     .into_iter()
                     .collect(),
+                    asset_accruals: vec![].into_iter().collect(),
+                    last_miner_share_principal: None,
                 }
             );
         })
@@ -938,6 +1072,8 @@ mod tests {
                     )]
                     .into_iter()
                     .collect(),
+                    asset_accruals: vec![].into_iter().collect(),
+                    last_miner_share_principal: None,
                 }
             );
         })
@@ -1221,6 +1357,8 @@ mod tests {
                     .collect(),
                     total_cash_principal: None,
                     chain_cash_principals: vec![].into_iter().collect(),
+                    asset_accruals: vec![].into_iter().collect(),
+                    last_miner_share_principal: None,
                 }
             );
         })
@@ -1394,6 +1532,21 @@ mod tests {
                 ]
                 .into_iter()
                 .collect(),
+                asset_accruals: vec![(
+                    Eth,
+                    AssetAccrual {
+                        supply_index: AssetIndex::from_nominal("18000"),
+                        borrow_index: AssetIndex::from_nominal("19000"),
+                        cash_principal_borrow_increase: CashPrincipalAmount::from_nominal("20"),
+                        miner_share_principal: CashPrincipalAmount::from_nominal("21"),
+                        protocol_reserves: CashPrincipalAmount::from_nominal("24"),
+                        borrow_rate: APR::from_nominal("0.25"),
+                        timestamp: 22000,
+                    },
+                )]
+                .into_iter()
+                .collect(),
+                last_miner_share_principal: Some(CashPrincipalAmount::from_nominal("23000")),
             };
 
             state.commit::<Test>();
@@ -1450,6 +1603,18 @@ mod tests {
                 ChainCashPrincipals::get(ChainId::Dot),
                 CashPrincipalAmount::from_nominal("17000")
             );
+            assert_eq!(SupplyIndices::get(Eth), AssetIndex::from_nominal("18000"));
+            assert_eq!(BorrowIndices::get(Eth), AssetIndex::from_nominal("19000"));
+            assert_eq!(LastAccrualTimestamps::get(Eth), 22000);
+            assert_eq!(
+                ProtocolReserves::get(Eth),
+                CashPrincipalAmount::from_nominal("24")
+            );
+            assert_eq!(BorrowRates::get(Eth), APR::from_nominal("0.25"));
+            assert_eq!(
+                LastMinerSharePrincipal::get(),
+                CashPrincipalAmount::from_nominal("23000")
+            );
         })
     }
 
@@ -1,12 +1,14 @@
 use crate::{
-    internal::assets::get_price,
-    reason::Reason,
-    symbol::CASH,
-    types::{AssetInfo, Balance},
+    factor::Factor,
+    internal::assets::{get_fresh_price, get_price},
+    reason::{MathError, Reason},
+    symbol::{Units, CASH},
+    types::{AssetInfo, Balance, Uint},
     Config,
 };
 use codec::{Decode, Encode};
 use our_std::RuntimeDebug;
+use pallet_oracle::types::Price;
 use types_derive::Types;
 
 /// Type for representing a set of positions for an account.
@@ -19,23 +21,72 @@ pub struct Portfolio {
 impl Portfolio {
     /// Get the hypothetical liquidity value.
     pub fn get_liquidity<T: Config>(&self) -> Result<Balance, Reason> {
-        let mut liquidity = self.cash.mul_price(get_price::<T>(CASH)?)?;
+        self.get_liquidity_with_prices(get_price::<T>)
+    }
+
+    /// Get the hypothetical liquidity value, requiring every price to be fresh.
+    /// Used for anything which may open or increase a borrow, unlike repayments and liquidations.
+    pub fn get_fresh_liquidity<T: Config>(&self) -> Result<Balance, Reason> {
+        self.get_liquidity_with_prices(get_fresh_price::<T>)
+    }
+
+    /// Get the hypothetical health factor, the ratio of collateral to borrows after liquidity factors.
+    /// An account is liquidatable when its health factor falls below one, and has none without borrows.
+    pub fn get_health_factor<T: Config>(&self) -> Result<Option<Factor>, Reason> {
+        let (collateral, borrows) = self.get_collateral_and_borrows_with_prices(get_price::<T>)?;
+        if borrows.value == 0 {
+            return Ok(None);
+        }
+        let borrows_value = borrows.value.checked_neg().ok_or(MathError::Overflow)?;
+        Ok(Some(Factor::from_fraction(
+            collateral.value as Uint,
+            borrows_value as Uint,
+        )?))
+    }
+
+    fn get_liquidity_with_prices<F>(&self, get_price: F) -> Result<Balance, Reason>
+    where
+        F: Fn(Units) -> Result<Price, Reason>,
+    {
+        let (collateral, borrows) = self.get_collateral_and_borrows_with_prices(get_price)?;
+        Ok(collateral.add(borrows)?)
+    }
+
+    /// Get the value of the positive and negative positions, after liquidity factors.
+    fn get_collateral_and_borrows_with_prices<F>(
+        &self,
+        get_price: F,
+    ) -> Result<(Balance, Balance), Reason>
+    where
+        F: Fn(Units) -> Result<Price, Reason>,
+    {
+        let cash = self.cash.mul_price(get_price(CASH)?)?;
+        let zero = Balance::new(0, cash.units);
+        let (mut collateral, mut borrows) = if cash.value >= 0 {
+            (cash, zero)
+        } else {
+            (zero, cash)
+        };
         for (info, balance) in &self.positions {
-            let price = get_price::<T>(balance.units)?;
+            let price = get_price(balance.units)?;
             let worth = (*balance).mul_price(price)?;
             if worth.value >= 0 {
-                liquidity = liquidity.add(worth.mul_factor(info.liquidity_factor)?)?
+                collateral = collateral.add(worth.mul_factor(info.liquidity_factor)?)?
             } else {
-                liquidity = liquidity.add(worth.div_factor(info.liquidity_factor)?)?
+                borrows = borrows.add(worth.div_factor(info.liquidity_factor)?)?
             }
         }
-        Ok(liquidity)
+        Ok((collateral, borrows))
     }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use crate::{pipeline, tests::*};
+    use super::*;
+    use crate::{
+        pipeline,
+        tests::{common::*, *},
+    };
 
     struct TestAsset {
         asset: u8,
@@ -64,8 +115,10 @@ pub mod tests {
                 let decimals = asset_case.decimals;
                 let liquidity_factor = LiquidityFactor::from_nominal(asset_case.liquidity_factor);
                 let miner_shares: MinerShares = Default::default();
+                let reserve_factor: ReserveFactor = Default::default();
                 let rate_model: InterestRateModel = Default::default();
                 let supply_cap = AssetAmount::MAX;
+                let borrow_cap = AssetAmount::MAX;
                 let symbol = Symbol::new(&asset_case.ticker);
                 let ticker = Ticker::new(&asset_case.ticker);
 
@@ -74,8 +127,10 @@ pub mod tests {
                     decimals,
                     liquidity_factor,
                     miner_shares,
+                    reserve_factor,
                     rate_model,
                     supply_cap,
+                    borrow_cap,
                     symbol,
                     ticker,
                 };
@@ -204,4 +259,38 @@ pub mod tests {
             .drain(..)
             .for_each(test_get_liquidity_test_case);
     }
+
+    #[test]
+    fn test_get_health_factor() {
+        new_test_ext().execute_with(|| {
+            init_eth_asset().unwrap();
+            init_wbtc_asset().unwrap();
+
+            let mut portfolio = Portfolio {
+                cash: Balance::from_nominal("1000", CASH),
+                positions: vec![(wbtc, Balance::from_nominal("0.1", WBTC))],
+            };
+            assert_eq!(portfolio.get_health_factor::<Test>(), Ok(None));
+
+            // (1000 + 0.1 * 60000 * 0.6) / (1 * 2000 / 0.8) = 4600 / 2500
+            portfolio
+                .positions
+                .push((eth, Balance::from_nominal("-1", ETH)));
+            assert_eq!(
+                portfolio.get_health_factor::<Test>(),
+                Ok(Some(Factor::from_nominal("1.84")))
+            );
+            assert_eq!(
+                portfolio.get_liquidity::<Test>(),
+                Ok(Balance::from_nominal("2100", USD))
+            );
+
+            // 3600 / (2500 + 1000)
+            portfolio.cash = Balance::from_nominal("-1000", CASH);
+            assert_eq!(
+                portfolio.get_health_factor::<Test>(),
+                Ok(Some(Factor::from_fraction(36u128, 35u128).unwrap()))
+            );
+        });
+    }
 }
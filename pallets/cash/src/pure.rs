@@ -0,0 +1,485 @@
+//! The balance and interest engine as pure functions over explicit state, without touching storage.
+//! The storage-backed pipeline applies the same functions, so sequences of locks, extractions and
+//!  transfers can be exercised here (e.g. by property tests or fuzzers) without any externalities.
+
+use crate::{
+    chains::{ChainAccount, ChainAsset, ChainId},
+    internal::balance_helpers::{
+        repay_and_supply_amount, repay_and_supply_principal, withdraw_and_borrow_amount,
+        withdraw_and_borrow_principal,
+    },
+    reason::Reason,
+    types::{
+        AssetAmount, AssetBalance, AssetIndex, AssetInfo, Balance, CashPrincipal,
+        CashPrincipalAmount, Quantity,
+    },
+};
+use our_std::{collections::btree_map::BTreeMap, RuntimeDebug};
+
+/// The state of the market in an asset which changes as balances of it move.
+#[derive(Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Market {
+    pub total_supply: Quantity,
+    pub total_borrow: Quantity,
+    pub supply_index: AssetIndex,
+    pub borrow_index: AssetIndex,
+    pub borrow_cap: AssetAmount,
+}
+
+/// The position of an account in an asset, along with the CASH principal its interest settles into.
+#[derive(Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Position {
+    pub balance: Balance,
+    pub last_index: AssetIndex,
+    pub cash_principal: CashPrincipal,
+}
+
+/// Return CASH Principal including asset interest, and a new asset index,
+///  given a balance change, the previous position, and current market global indices.
+pub fn effect_of_asset_interest(
+    balance_old: Balance,
+    balance_new: Balance,
+    cash_principal_pre: CashPrincipal,
+    last_index: AssetIndex,
+    supply_index: AssetIndex,
+    borrow_index: AssetIndex,
+) -> Result<(CashPrincipal, AssetIndex), Reason> {
+    let cash_index = if balance_old.value >= 0 {
+        supply_index
+    } else {
+        borrow_index
+    };
+    let cash_principal_delta = cash_index.cash_principal_since(last_index, balance_old)?;
+    let cash_principal_post = cash_principal_pre.add(cash_principal_delta)?;
+    let last_index_post = if balance_new.value >= 0 {
+        supply_index
+    } else {
+        borrow_index
+    };
+    Ok((cash_principal_post, last_index_post))
+}
+
+/// Add a quantity of an asset to a position, repaying any debt before supplying the rest.
+pub fn augment_asset(
+    market: Market,
+    position: Position,
+    quantity: Quantity,
+) -> Result<(Market, Position), Reason> {
+    let (repay_amount, supply_amount) = repay_and_supply_amount(position.balance.value, quantity)?;
+
+    let total_supply = market.total_supply.add(supply_amount)?;
+    let total_borrow = market
+        .total_borrow
+        .sub(repay_amount)
+        .map_err(|_| Reason::TotalBorrowUnderflow)?;
+
+    let balance = position.balance.add_quantity(quantity)?;
+    let (cash_principal, last_index) = effect_of_asset_interest(
+        position.balance,
+        balance,
+        position.cash_principal,
+        position.last_index,
+        market.supply_index,
+        market.borrow_index,
+    )?;
+
+    Ok((
+        Market {
+            total_supply,
+            total_borrow,
+            ..market
+        },
+        Position {
+            balance,
+            last_index,
+            cash_principal,
+        },
+    ))
+}
+
+/// Take a quantity of an asset from a position, withdrawing any supply before borrowing the rest.
+pub fn reduce_asset(
+    market: Market,
+    position: Position,
+    quantity: Quantity,
+) -> Result<(Market, Position), Reason> {
+    let (withdraw_amount, borrow_amount) =
+        withdraw_and_borrow_amount(position.balance.value, quantity)?;
+
+    let total_supply = market
+        .total_supply
+        .sub(withdraw_amount)
+        .map_err(|_| Reason::InsufficientTotalFunds)?;
+    let total_borrow = market.total_borrow.add(borrow_amount)?;
+
+    // Only new borrowing is capped, so that lowering a cap never blocks withdrawals or repayments
+    if borrow_amount.value > 0 && total_borrow.value > market.borrow_cap {
+        return Err(Reason::BorrowCapExceeded);
+    }
+
+    let balance = position.balance.sub_quantity(quantity)?;
+    let (cash_principal, last_index) = effect_of_asset_interest(
+        position.balance,
+        balance,
+        position.cash_principal,
+        position.last_index,
+        market.supply_index,
+        market.borrow_index,
+    )?;
+
+    Ok((
+        Market {
+            total_supply,
+            total_borrow,
+            ..market
+        },
+        Position {
+            balance,
+            last_index,
+            cash_principal,
+        },
+    ))
+}
+
+/// Add CASH principal to an account, returning its new principal and the new total principal.
+/// Only the part which repays debt reduces the total, which counts the CASH owed to the protocol.
+pub fn augment_cash(
+    cash_principal: CashPrincipal,
+    total_cash_principal: CashPrincipalAmount,
+    principal: CashPrincipalAmount,
+) -> Result<(CashPrincipal, CashPrincipalAmount), Reason> {
+    let (repay_principal, _supply_principal) =
+        repay_and_supply_principal(cash_principal, principal)?;
+    let total_cash_principal_post = total_cash_principal
+        .sub(repay_principal)
+        .map_err(|_| Reason::InsufficientChainCash)?;
+    Ok((
+        cash_principal.add_amount(principal)?,
+        total_cash_principal_post,
+    ))
+}
+
+/// Take CASH principal from an account, returning its new principal and the new total principal.
+/// Only the part which is borrowed increases the total.
+pub fn reduce_cash(
+    cash_principal: CashPrincipal,
+    total_cash_principal: CashPrincipalAmount,
+    principal: CashPrincipalAmount,
+) -> Result<(CashPrincipal, CashPrincipalAmount), Reason> {
+    let (_withdraw_principal, borrow_principal) =
+        withdraw_and_borrow_principal(cash_principal, principal)?;
+    let total_cash_principal_post = total_cash_principal.add(borrow_principal)?;
+    Ok((
+        cash_principal.sub_amount(principal)?,
+        total_cash_principal_post,
+    ))
+}
+
+/// Return the CASH principal remaining on a chain after some is locked back into the protocol from it.
+pub fn lock_chain_cash(
+    chain_cash_principal: CashPrincipalAmount,
+    principal: CashPrincipalAmount,
+) -> Result<CashPrincipalAmount, Reason> {
+    chain_cash_principal
+        .sub(principal)
+        .map_err(|_| Reason::NegativeChainCash)
+}
+
+/// Return the CASH principal on a chain after some is extracted to it.
+pub fn extract_chain_cash(
+    chain_cash_principal: CashPrincipalAmount,
+    principal: CashPrincipalAmount,
+) -> Result<CashPrincipalAmount, Reason> {
+    Ok(chain_cash_principal.add(principal)?)
+}
+
+/// An explicit, self-contained state of the balances held in the protocol.
+/// Interest accrues only when the indices of a market are changed, and liquidity checks, which
+///  depend on prices, are left to callers.
+#[derive(Clone, Default, Eq, PartialEq, RuntimeDebug)]
+pub struct Ledger {
+    pub assets: BTreeMap<ChainAsset, (AssetInfo, Market)>,
+    pub balances: BTreeMap<(ChainAsset, ChainAccount), (AssetBalance, AssetIndex)>,
+    pub cash_principals: BTreeMap<ChainAccount, CashPrincipal>,
+    pub total_cash_principal: CashPrincipalAmount,
+    pub chain_cash_principals: BTreeMap<ChainId, CashPrincipalAmount>,
+}
+
+impl Ledger {
+    /// Add a market for an asset, with its indices starting at one.
+    pub fn support_asset(mut self, info: AssetInfo) -> Self {
+        let market = Market {
+            total_supply: info.as_quantity(0),
+            total_borrow: info.as_quantity(0),
+            supply_index: AssetIndex::ONE,
+            borrow_index: AssetIndex::ONE,
+            borrow_cap: info.borrow_cap,
+        };
+        self.assets.insert(info.asset, (info, market));
+        self
+    }
+
+    pub fn get_market(&self, asset: ChainAsset) -> Result<(AssetInfo, Market), Reason> {
+        self.assets
+            .get(&asset)
+            .copied()
+            .ok_or(Reason::AssetNotSupported)
+    }
+
+    pub fn get_position(&self, info: AssetInfo, account: ChainAccount) -> Position {
+        let (balance, last_index) = self
+            .balances
+            .get(&(info.asset, account))
+            .copied()
+            .unwrap_or_default();
+        Position {
+            balance: info.as_balance(balance),
+            last_index,
+            cash_principal: self.get_cash_principal(account),
+        }
+    }
+
+    pub fn get_cash_principal(&self, account: ChainAccount) -> CashPrincipal {
+        self.cash_principals
+            .get(&account)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn get_chain_cash_principal(&self, chain_id: ChainId) -> CashPrincipalAmount {
+        self.chain_cash_principals
+            .get(&chain_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn set(&mut self, info: AssetInfo, market: Market, account: ChainAccount, position: Position) {
+        self.assets.insert(info.asset, (info, market));
+        self.balances.insert(
+            (info.asset, account),
+            (position.balance.value, position.last_index),
+        );
+        self.cash_principals
+            .insert(account, position.cash_principal);
+    }
+
+    fn augment_asset(
+        mut self,
+        recipient: ChainAccount,
+        asset: ChainAsset,
+        amount: AssetAmount,
+    ) -> Result<Self, Reason> {
+        let (info, market) = self.get_market(asset)?;
+        let position = self.get_position(info, recipient);
+        let (market, position) = augment_asset(market, position, info.as_quantity(amount))?;
+        self.set(info, market, recipient, position);
+        Ok(self)
+    }
+
+    fn reduce_asset(
+        mut self,
+        sender: ChainAccount,
+        asset: ChainAsset,
+        amount: AssetAmount,
+    ) -> Result<Self, Reason> {
+        let (info, market) = self.get_market(asset)?;
+        let position = self.get_position(info, sender);
+        let (market, position) = reduce_asset(market, position, info.as_quantity(amount))?;
+        self.set(info, market, sender, position);
+        Ok(self)
+    }
+
+    /// Credit an amount of an asset locked into its starport.
+    pub fn lock_asset(
+        self,
+        recipient: ChainAccount,
+        asset: ChainAsset,
+        amount: AssetAmount,
+    ) -> Result<Self, Reason> {
+        self.augment_asset(recipient, asset, amount)
+    }
+
+    /// Debit an amount of an asset extracted from its starport, which must hold enough of it.
+    pub fn extract_asset(
+        self,
+        sender: ChainAccount,
+        asset: ChainAsset,
+        amount: AssetAmount,
+    ) -> Result<Self, Reason> {
+        let ledger = self.reduce_asset(sender, asset, amount)?;
+        let (_info, market) = ledger.get_market(asset)?;
+        if market.total_borrow > market.total_supply {
+            return Err(Reason::InsufficientTotalFunds);
+        }
+        Ok(ledger)
+    }
+
+    pub fn transfer_asset(
+        self,
+        sender: ChainAccount,
+        recipient: ChainAccount,
+        asset: ChainAsset,
+        amount: AssetAmount,
+    ) -> Result<Self, Reason> {
+        if sender == recipient {
+            return Err(Reason::SelfTransfer);
+        }
+        self.reduce_asset(sender, asset, amount)?
+            .augment_asset(recipient, asset, amount)
+    }
+
+    /// Credit CASH principal locked into the starport of the recipient's chain.
+    pub fn lock_cash(
+        mut self,
+        recipient: ChainAccount,
+        principal: CashPrincipalAmount,
+    ) -> Result<Self, Reason> {
+        let chain_id = recipient.chain_id();
+        let chain_cash_principal =
+            lock_chain_cash(self.get_chain_cash_principal(chain_id), principal)?;
+        let (cash_principal, total_cash_principal) = augment_cash(
+            self.get_cash_principal(recipient),
+            self.total_cash_principal,
+            principal,
+        )?;
+        self.chain_cash_principals
+            .insert(chain_id, chain_cash_principal);
+        self.cash_principals.insert(recipient, cash_principal);
+        self.total_cash_principal = total_cash_principal;
+        Ok(self)
+    }
+
+    /// Debit CASH principal extracted to the starport of the sender's chain.
+    pub fn extract_cash(
+        mut self,
+        sender: ChainAccount,
+        principal: CashPrincipalAmount,
+    ) -> Result<Self, Reason> {
+        let chain_id = sender.chain_id();
+        let chain_cash_principal =
+            extract_chain_cash(self.get_chain_cash_principal(chain_id), principal)?;
+        let (cash_principal, total_cash_principal) = reduce_cash(
+            self.get_cash_principal(sender),
+            self.total_cash_principal,
+            principal,
+        )?;
+        self.chain_cash_principals
+            .insert(chain_id, chain_cash_principal);
+        self.cash_principals.insert(sender, cash_principal);
+        self.total_cash_principal = total_cash_principal;
+        Ok(self)
+    }
+
+    pub fn transfer_cash(
+        mut self,
+        sender: ChainAccount,
+        recipient: ChainAccount,
+        principal: CashPrincipalAmount,
+    ) -> Result<Self, Reason> {
+        if sender == recipient {
+            return Err(Reason::SelfTransfer);
+        }
+        let (sender_cash_principal, total_cash_principal) = reduce_cash(
+            self.get_cash_principal(sender),
+            self.total_cash_principal,
+            principal,
+        )?;
+        let (recipient_cash_principal, total_cash_principal) = augment_cash(
+            self.get_cash_principal(recipient),
+            total_cash_principal,
+            principal,
+        )?;
+        self.cash_principals.insert(sender, sender_cash_principal);
+        self.cash_principals
+            .insert(recipient, recipient_cash_principal);
+        self.total_cash_principal = total_cash_principal;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::assets::ETH, types::LiquidityFactor};
+
+    const ALICE: ChainAccount = ChainAccount::Eth([1u8; 20]);
+    const BOB: ChainAccount = ChainAccount::Eth([2u8; 20]);
+    const ASSET: ChainAsset = ChainAsset::Eth([0xee; 20]);
+
+    fn ledger() -> Ledger {
+        Ledger::default().support_asset(AssetInfo {
+            liquidity_factor: LiquidityFactor::from_nominal("0.8"),
+            ..AssetInfo::minimal(ASSET, ETH)
+        })
+    }
+
+    #[test]
+    fn test_lock_transfer_extract() -> Result<(), Reason> {
+        let ledger = ledger()
+            .lock_asset(ALICE, ASSET, 1000)?
+            .transfer_asset(ALICE, BOB, ASSET, 1500)?;
+        let (info, market) = ledger.get_market(ASSET)?;
+        assert_eq!(ledger.get_position(info, ALICE).balance.value, -500);
+        assert_eq!(ledger.get_position(info, BOB).balance.value, 1500);
+        assert_eq!(market.total_supply.value, 1500);
+        assert_eq!(market.total_borrow.value, 500);
+
+        assert_eq!(
+            ledger.clone().extract_asset(BOB, ASSET, 1500),
+            Err(Reason::InsufficientTotalFunds)
+        );
+        let ledger = ledger
+            .lock_asset(ALICE, ASSET, 500)?
+            .extract_asset(BOB, ASSET, 1500)?;
+        let (info, market) = ledger.get_market(ASSET)?;
+        assert_eq!(ledger.get_position(info, ALICE).balance.value, 0);
+        assert_eq!(ledger.get_position(info, BOB).balance.value, 0);
+        assert_eq!(market.total_supply.value, 0);
+        assert_eq!(market.total_borrow.value, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_interest_settles_into_cash() -> Result<(), Reason> {
+        let ledger = ledger().lock_asset(ALICE, ASSET, 1_000_000_000_000_000_000)?;
+        let mut ledger = ledger.clone();
+        let (info, mut market) = ledger.get_market(ASSET)?;
+        market.supply_index = AssetIndex::from_nominal("1.5");
+        ledger.assets.insert(ASSET, (info, market));
+
+        let ledger = ledger.lock_asset(ALICE, ASSET, 1)?;
+        assert!(ledger.get_cash_principal(ALICE) > CashPrincipal::ZERO);
+        assert_eq!(
+            ledger.get_position(info, ALICE).last_index,
+            AssetIndex::from_nominal("1.5")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_cash_totals() -> Result<(), Reason> {
+        let ledger = Ledger::default()
+            .extract_cash(ALICE, CashPrincipalAmount(100))?
+            .transfer_cash(BOB, ALICE, CashPrincipalAmount(30))?;
+        assert_eq!(ledger.get_cash_principal(ALICE), CashPrincipal(-70));
+        assert_eq!(ledger.get_cash_principal(BOB), CashPrincipal(-30));
+        assert_eq!(ledger.total_cash_principal, CashPrincipalAmount(100));
+        assert_eq!(
+            ledger.get_chain_cash_principal(ChainId::Eth),
+            CashPrincipalAmount(100)
+        );
+
+        let ledger = ledger.lock_cash(ALICE, CashPrincipalAmount(100))?;
+        assert_eq!(ledger.get_cash_principal(ALICE), CashPrincipal(30));
+        assert_eq!(ledger.total_cash_principal, CashPrincipalAmount(30));
+        assert_eq!(
+            ledger.get_chain_cash_principal(ChainId::Eth),
+            CashPrincipalAmount::ZERO
+        );
+        assert_eq!(
+            ledger.lock_cash(BOB, CashPrincipalAmount(1)),
+            Err(Reason::NegativeChainCash)
+        );
+        Ok(())
+    }
+}
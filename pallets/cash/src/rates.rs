@@ -6,7 +6,9 @@ use crate::{
     factor::{BigUint, Factor},
     params::MILLISECONDS_PER_YEAR,
     reason::{MathError, Reason},
-    types::{AssetAmount, MinerShares, Timestamp, Uint},
+    types::{
+        AssetAmount, CashIndex, CashPerCashPrincipal, MinerShares, ReserveFactor, Timestamp, Uint,
+    },
 };
 
 use types_derive::Types;
@@ -19,6 +21,9 @@ pub enum RatesError {
     KinkAboveFull,
     KinkUtilizationTooHigh,
     Overflowed,
+    ReserveFactorTooHigh,
+    MinAboveMax,
+    TargetUtilizationOutOfBounds,
 }
 
 /// Annualized interest rate
@@ -42,6 +47,93 @@ impl APR {
         APR(uint_from_string_with_decimals(Self::DECIMALS, s))
     }
 
+    /// Return the rate, as long as it does not exceed the maximum.
+    /// Rates set by governance are bounded by `APR::MAX`.
+    pub fn checked(rate: Uint, max: APR) -> Result<APR, Reason> {
+        if rate > max.0 {
+            Err(Reason::InvalidAPR)
+        } else {
+            Ok(APR(rate))
+        }
+    }
+
+    /// Return the rate, or the maximum if it would exceed it.
+    pub fn saturating(rate: Uint, max: APR) -> APR {
+        APR(rate.min(max.0))
+    }
+
+    /// Return the increase in cash per cash principal from compounding over the time delta.
+    pub fn cash_index_increment(self, dt: Timestamp) -> Result<CashPerCashPrincipal, MathError> {
+        Ok(self.compound(dt)?.into())
+    }
+
+    /// Return the CASH index after compounding over the time delta.
+    pub fn accrue(self, index: CashIndex, dt: Timestamp) -> Result<CashIndex, MathError> {
+        index.increment(self.cash_index_increment(dt)?)
+    }
+
+    /// Return the fraction accrued in a block of the given duration, without compounding.
+    pub fn per_block(self, block_time: Timestamp) -> Result<Factor, MathError> {
+        self.simple(block_time)
+    }
+
+    /// Return the rate which accrues the fraction in each block of the given duration, without compounding.
+    /// Rounds to the nearest representable rate.
+    pub fn from_per_block(rate: Factor, block_time: Timestamp) -> Result<APR, MathError> {
+        let annual = BigUint::from_uint(rate.0)
+            .mul_uint(MILLISECONDS_PER_YEAR as Uint)
+            .div_uint(block_time as Uint)?
+            .to_uint()?;
+        Ok(APR(round_decimals(
+            annual,
+            Factor::DECIMALS,
+            APR::DECIMALS,
+        )?))
+    }
+
+    /// Return the fraction accrued over a year of compounding continuously, i.e. e^r - 1.
+    /// Unlike `compound`, which is only accurate over short periods, this sums the series until it converges.
+    pub fn effective_annual(self) -> Result<Factor, MathError> {
+        let one = Factor::ONE.0;
+        let x = Factor::ONE.mul_decimal(self.0, APR::DECIMALS).to_uint()?;
+        let mut term = x;
+        let mut sum = x;
+        let mut n: Uint = 1;
+        while term > 0 {
+            n += 1;
+            term = term.checked_mul(x).ok_or(MathError::Overflow)? / one / n;
+            sum = sum.checked_add(term).ok_or(MathError::Overflow)?;
+        }
+        Ok(Factor(sum))
+    }
+
+    /// Return the continuously compounding rate which accrues the fraction over a year, i.e. ln(1 + y).
+    /// Rounds to the nearest representable rate.
+    pub fn from_effective_annual(annual: Factor) -> Result<APR, MathError> {
+        // ln(1 + y) = 2 * atanh(z) = 2 * (z + z^3/3 + z^5/5 + ...), where z = y / (2 + y) < 1
+        let one = Factor::ONE.0;
+        let y = annual.0;
+        let two_plus_y = one
+            .checked_mul(2)
+            .and_then(|two| two.checked_add(y))
+            .ok_or(MathError::Overflow)?;
+        let z = BigUint::from_uint(y)
+            .mul_uint(one)
+            .div_uint(two_plus_y)?
+            .to_uint()?;
+        let z_2 = z.checked_mul(z).ok_or(MathError::Overflow)? / one;
+        let mut term = z;
+        let mut sum: Uint = 0;
+        let mut k: Uint = 1;
+        while term > 0 {
+            sum = sum.checked_add(term / k).ok_or(MathError::Overflow)?;
+            term = term.checked_mul(z_2).ok_or(MathError::Overflow)? / one;
+            k += 2;
+        }
+        let rate = sum.checked_mul(2).ok_or(MathError::Overflow)?;
+        Ok(APR(round_decimals(rate, Factor::DECIMALS, APR::DECIMALS)?))
+    }
+
     /// Official approximation of e^(r*dt) using e^x = 1 + x + x^2/2 + x^3/6 + ...
     pub fn compound(self, dt: Timestamp) -> Result<Factor, MathError> {
         let one = Factor::ONE;
@@ -86,6 +178,14 @@ impl From<APR> for String {
     }
 }
 
+/// Convert a number to fewer decimals, rounding to the nearest.
+fn round_decimals(number: Uint, from_decimals: u8, to_decimals: u8) -> Result<Uint, MathError> {
+    let scale = 10u128
+        .checked_pow(from_decimals.saturating_sub(to_decimals) as u32)
+        .ok_or(MathError::Overflow)?;
+    Ok(number.checked_add(scale / 2).ok_or(MathError::Overflow)? / scale)
+}
+
 /// Get the utilization ratio given the amount supplied and borrowed.
 pub fn get_utilization(supplied: AssetAmount, borrowed: AssetAmount) -> Result<Factor, MathError> {
     if borrowed == 0 {
@@ -96,6 +196,10 @@ pub fn get_utilization(supplied: AssetAmount, borrowed: AssetAmount) -> Result<F
 }
 
 /// This represents an interest rate model type and parameters.
+///
+/// Kink - a jump rate model, whose rate rises more steeply past the kink
+/// Fixed - a constant rate, regardless of utilization
+/// Dynamic - a rate which drifts towards the target utilization over time, within bounds
 #[derive(Serialize, Deserialize)] // used in config
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, Types)]
 pub enum InterestRateModel {
@@ -108,6 +212,12 @@ pub enum InterestRateModel {
     Fixed {
         rate: APR,
     },
+    Dynamic {
+        target_utilization: Factor,
+        min_rate: APR,
+        max_rate: APR,
+        max_change: APR,
+    },
 }
 
 /// This is for convenience, we shouldn't rely on a sane default model.
@@ -142,6 +252,7 @@ impl InterestRateModel {
     /// Check the model parameters for sanity
     ///
     /// Kink - monotonically increasing rate with a kink somewhere between 0% and 100% utilization
+    /// Dynamic - bounds within the maximum rate, and a target strictly between 0% and 100% utilization
     pub fn check_parameters(self: &Self) -> Result<(), RatesError> {
         match self {
             Self::Kink {
@@ -171,6 +282,24 @@ impl InterestRateModel {
                     return Err(RatesError::ModelRateOutOfBounds);
                 }
             }
+            Self::Dynamic {
+                target_utilization,
+                min_rate,
+                max_rate,
+                max_change,
+            } => {
+                if *max_rate > APR::MAX || *max_change > APR::MAX {
+                    return Err(RatesError::ModelRateOutOfBounds);
+                }
+
+                if min_rate > max_rate {
+                    return Err(RatesError::MinAboveMax);
+                }
+
+                if *target_utilization == Factor::ZERO || *target_utilization >= Factor::ONE {
+                    return Err(RatesError::TargetUtilizationOutOfBounds);
+                }
+            }
         };
 
         Ok(())
@@ -208,11 +337,11 @@ impl InterestRateModel {
     }
 
     /// Get the borrow rate
-    /// Current rate is not used at the moment
+    /// Current rate is only used by the dynamic model, which keeps it within its bounds
     pub fn get_borrow_rate<T: Into<APR>>(
         self: &Self,
         utilization: Factor,
-        _current_rate: T,
+        current_rate: T,
     ) -> Result<APR, RatesError> {
         match self {
             Self::Kink {
@@ -246,23 +375,76 @@ impl InterestRateModel {
                 }
             }
             Self::Fixed { rate } => Ok(*rate),
+            Self::Dynamic {
+                min_rate, max_rate, ..
+            } => Ok(current_rate.into().max(*min_rate).min(*max_rate)),
+        }
+    }
+
+    /// Get the borrow rate to accrue at going forward, after accruing at the current rate for the time delta.
+    ///
+    /// The dynamic model moves the rate up when above the target utilization and down when below,
+    ///  by up to the max change per year, in proportion to the distance from the target.
+    /// Other models do not depend on the current rate, so their rate is simply recomputed.
+    pub fn get_next_borrow_rate(
+        self: &Self,
+        utilization: Factor,
+        current_rate: APR,
+        dt: Timestamp,
+    ) -> Result<APR, RatesError> {
+        match self {
+            Self::Dynamic {
+                target_utilization,
+                min_rate,
+                max_rate,
+                max_change,
+            } => {
+                let rate = self.get_borrow_rate(utilization, current_rate)?;
+                let (distance, range) = if utilization > *target_utilization {
+                    (
+                        utilization.0 - target_utilization.0,
+                        Factor::ONE.0 - target_utilization.0,
+                    )
+                } else {
+                    (target_utilization.0 - utilization.0, target_utilization.0)
+                };
+                let deviation = Factor::from_fraction(distance.min(range), range)
+                    .map_err(|_| RatesError::Overflowed)?;
+                let change = crate::types::mul(
+                    max_change.simple(dt).map_err(|_| RatesError::Overflowed)?.0,
+                    Factor::DECIMALS,
+                    deviation.0,
+                    Factor::DECIMALS,
+                    APR::DECIMALS,
+                )
+                .map_err(|_| RatesError::Overflowed)?;
+                let next = if utilization > *target_utilization {
+                    APR::saturating(rate.0.saturating_add(change), *max_rate)
+                } else {
+                    APR(rate.0.saturating_sub(change))
+                };
+                Ok(next.max(*min_rate))
+            }
+            _ => self.get_borrow_rate(utilization, current_rate),
         }
     }
 
     fn borrow_rate_to_supply_rate(
         borrow_rate: Uint,
         miner_shares: Uint,
+        reserve_factor: Uint,
         utilization: Uint,
     ) -> Result<Uint, MathError> {
-        // Borrow Rate * (1-miner shares) * utilization
+        // Borrow Rate * (1-miner shares-reserve factor) * utilization
 
-        // (1-miner shares)
+        // (1-miner shares-reserve factor)
         let reserve_multiplier = MinerShares::ONE
             .0
             .checked_sub(miner_shares)
+            .and_then(|x| x.checked_sub(reserve_factor))
             .ok_or(MathError::Underflow)?;
 
-        // Borrow Rate * (1-miner shares)
+        // Borrow Rate * (1-miner shares-reserve factor)
         let acc = crate::types::mul(
             borrow_rate,
             APR::DECIMALS,
@@ -271,7 +453,7 @@ impl InterestRateModel {
             APR::DECIMALS,
         )?;
 
-        // Borrow Rate * (1-miner shares) * utilization
+        // Borrow Rate * (1-miner shares-reserve factor) * utilization
         let acc = crate::types::mul(
             acc,
             APR::DECIMALS,
@@ -289,25 +471,32 @@ impl InterestRateModel {
         utilization: Factor,
         current_rate: APR,
         miner_shares: MinerShares,
+        reserve_factor: ReserveFactor,
     ) -> Result<(APR, APR), RatesError> {
         let borrow_rate = self.get_borrow_rate(utilization, current_rate)?;
-        // unsafe version Borrow Rate * (1-miner shares) * utilization
-        let supply_rate =
-            Self::borrow_rate_to_supply_rate(borrow_rate.0, miner_shares.0, utilization.0)
-                .map_err(|_| RatesError::Overflowed)?;
+        // unsafe version Borrow Rate * (1-miner shares-reserve factor) * utilization
+        let supply_rate = Self::borrow_rate_to_supply_rate(
+            borrow_rate.0,
+            miner_shares.0,
+            reserve_factor.0,
+            utilization.0,
+        )
+        .map_err(|_| RatesError::Overflowed)?;
         Ok((borrow_rate, APR(supply_rate)))
     }
 
     /// Get the supply rate
     ///
-    /// always Borrow Rate * (1-miner shares) * utilization
+    /// always Borrow Rate * (1-miner shares-reserve factor) * utilization
     pub fn get_supply_rate(
         self: &Self,
         utilization: Factor,
         current_rate: APR,
         miner_shares: MinerShares,
+        reserve_factor: ReserveFactor,
     ) -> Result<APR, RatesError> {
-        let (_, supply_rate) = self.get_rates(utilization, current_rate, miner_shares)?;
+        let (_, supply_rate) =
+            self.get_rates(utilization, current_rate, miner_shares, reserve_factor)?;
         Ok(supply_rate)
     }
 }
@@ -483,6 +672,56 @@ mod test {
                 expected: Err(RatesError::ModelRateOutOfBounds),
                 message: "rate must be less than max rate",
             },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::Dynamic {
+                    target_utilization: Factor::from_nominal("0.8"),
+                    min_rate: 100.into(),
+                    max_rate: 3000.into(),
+                    max_change: 1000.into(),
+                },
+                expected: Ok(()),
+                message: "valid dynamic model should pass",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::Dynamic {
+                    target_utilization: Factor::from_nominal("0.8"),
+                    min_rate: 100.into(),
+                    max_rate: APR(APR::MAX.0 + 1),
+                    max_change: 1000.into(),
+                },
+                expected: Err(RatesError::ModelRateOutOfBounds),
+                message: "max rate must be less than max rate",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::Dynamic {
+                    target_utilization: Factor::from_nominal("0.8"),
+                    min_rate: 3001.into(),
+                    max_rate: 3000.into(),
+                    max_change: 1000.into(),
+                },
+                expected: Err(RatesError::MinAboveMax),
+                message: "min rate must not be above max rate",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::Dynamic {
+                    target_utilization: Factor::ZERO,
+                    min_rate: 100.into(),
+                    max_rate: 3000.into(),
+                    max_change: 1000.into(),
+                },
+                expected: Err(RatesError::TargetUtilizationOutOfBounds),
+                message: "target utilization must be above zero",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::Dynamic {
+                    target_utilization: Factor::ONE,
+                    min_rate: 100.into(),
+                    max_rate: 3000.into(),
+                    max_change: 1000.into(),
+                },
+                expected: Err(RatesError::TargetUtilizationOutOfBounds),
+                message: "target utilization must be below one",
+            },
         ]
     }
 
@@ -579,6 +818,36 @@ mod test {
             .for_each(test_get_borrow_rate_case)
     }
 
+    #[test]
+    fn test_get_next_borrow_rate() {
+        let year = MILLISECONDS_PER_YEAR;
+        let model = InterestRateModel::Dynamic {
+            target_utilization: Factor::from_nominal("0.8"),
+            min_rate: 100.into(),
+            max_rate: 3000.into(),
+            max_change: 1000.into(),
+        };
+        let next = |utilization: &'static str, current: Uint, dt| {
+            model.get_next_borrow_rate(Factor::from_nominal(utilization), APR(current), dt)
+        };
+
+        assert_eq!(next("0.8", 500, year), Ok(500.into()));
+        assert_eq!(next("1", 500, year), Ok(1500.into()));
+        assert_eq!(next("2", 500, year), Ok(1500.into()));
+        assert_eq!(next("0.9", 500, year / 2), Ok(750.into()));
+        assert_eq!(next("0.4", 500, year / 2), Ok(250.into()));
+        assert_eq!(next("0.4", 500, year), Ok(100.into()));
+        assert_eq!(next("1", 2500, year), Ok(3000.into()));
+        assert_eq!(next("0.8", 5000, year), Ok(3000.into()));
+        assert_eq!(next("0.8", 0, 0), Ok(100.into()));
+
+        let kink = InterestRateModel::new_kink(100, 200, Factor::from_nominal("0.5"), 500);
+        assert_eq!(
+            kink.get_next_borrow_rate(Factor::from_nominal("0.1"), APR(5000), year),
+            Ok(120.into())
+        );
+    }
+
     #[test]
     fn test_compound() {
         let mut rates = vec!["0", "0.0001", "0.03", "0.1", "0.2"];
@@ -633,4 +902,92 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_get_rates_reserve_factor() {
+        let model = InterestRateModel::Fixed {
+            rate: APR::from_nominal("0.1"),
+        };
+        let utilization = Factor::from_nominal("0.5");
+        assert_eq!(
+            model.get_rates(
+                utilization,
+                APR::ZERO,
+                MinerShares::from_nominal("0.1"),
+                ReserveFactor::from_nominal("0.2")
+            ),
+            Ok((APR::from_nominal("0.1"), APR::from_nominal("0.035")))
+        );
+        assert_eq!(
+            model.get_rates(
+                utilization,
+                APR::ZERO,
+                MinerShares::from_nominal("0.9"),
+                ReserveFactor::from_nominal("0.2")
+            ),
+            Err(RatesError::Overflowed)
+        );
+    }
+
+    #[test]
+    fn test_apr_bounds() {
+        assert_eq!(APR::checked(APR::MAX.0, APR::MAX), Ok(APR::MAX));
+        assert_eq!(
+            APR::checked(APR::MAX.0 + 1, APR::MAX),
+            Err(Reason::InvalidAPR)
+        );
+        assert_eq!(APR::saturating(APR::MAX.0 + 1, APR::MAX), APR::MAX);
+        assert_eq!(APR::saturating(100, APR::MAX), APR::from_nominal("0.01"));
+    }
+
+    #[test]
+    fn test_apr_accrue() {
+        let r = APR::from_nominal("0.24");
+        let index = CashIndex::from_nominal("1.123");
+        let dt = MILLISECONDS_PER_YEAR / 4;
+        assert_eq!(
+            r.accrue(index, dt),
+            Ok(CashIndex::from_nominal("1.192441828000000000"))
+        );
+        assert_eq!(r.accrue(index, 0), Ok(index));
+    }
+
+    #[test]
+    fn test_apr_per_block() {
+        let r = APR::from_nominal("0.1");
+        let per_block = r.per_block(6000).unwrap();
+        // 0.1 * 6s / 31536000s
+        assert_eq!(per_block, Factor(19025875190));
+        assert_eq!(APR::from_per_block(per_block, 6000), Ok(r));
+        assert_eq!(
+            APR::from_per_block(Factor::from_nominal("0.0000001"), 1000),
+            Ok(APR::from_nominal("3.1536"))
+        );
+        assert_eq!(
+            APR::from_per_block(per_block, 0),
+            Err(MathError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_apr_effective_annual() {
+        // e^0.05 - 1 = 0.0512710963760240396...
+        let actual = APR::from_nominal("0.05").effective_annual().unwrap();
+        assert!((actual.0 as i128 - 51271096376024039).abs() < 1000);
+
+        for rate in vec!["0", "0.0001", "0.03", "0.1", "0.2", "0.35", "1"] {
+            let r = APR::from_nominal(rate);
+            let annual = r.effective_annual().unwrap();
+            let float_rate = (r.0 as f64) / 10f64.powf(APR::DECIMALS as f64);
+            let reference = (float_rate.exp_m1() * (Factor::ONE.0 as f64)) as u128;
+            let error_wei = if reference > annual.0 {
+                reference - annual.0
+            } else {
+                annual.0 - reference
+            };
+            // f64 is only precise to ~1e-16
+            assert!(error_wei < 1000, "effective annual rate out of range");
+            assert_eq!(APR::from_effective_annual(annual), Ok(r));
+        }
+    }
 }
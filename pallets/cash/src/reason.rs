@@ -15,11 +15,11 @@ use types_derive::Types;
 pub enum Reason {
     AssetNotSupported,
     BadAccount,
-    BadAddress,
+    BadAddress(ChainId),
     BadAsset,
     BadChainId,
     BadFactor,
-    BadHash,
+    BadHash(Option<ChainId>),
     BadSymbol,
     BadTicker,
     BadUnits,
@@ -75,28 +75,70 @@ pub enum Reason {
     StarportMissing,
     InvalidChainBlock,
     TrxRequestTooLong,
+    UnknownFailedEvent,
+    UnknownNotice,
+    TooManyValidators,
+    TooManySignatures,
+    StalePrice,
+    BorrowCapExceeded,
+    NoReserves,
+    SeizePaused,
+    CloseFactorExceeded,
+    NoEquivocation,
+    ValidatorChilled,
+    TimestampOutOfBounds,
+    UnknownGovernance,
+    StaleExecutionFee,
+    NoExecutionFee,
+    PortfolioConsentRequired,
+    InvalidGuardians,
+    NotGuardian,
+    RecoveryPending,
+    NoRecovery,
+    InvalidQuorumThreshold,
+    InvalidAssetGroup,
+    InsufficientStarportLiquidity,
+    ExodusStarted,
+    EventTimedOut,
+    ChainDisabled,
+    InvalidRecipient,
+    InvalidValidatorMetadata,
+    StaleValidatorMetadata,
+    NoBadDebt,
+    InsufficientReserves,
+    GovernanceCallsFailed,
+    PriceKeyMismatch,
+    ExtractionQueueFull,
+    LiquidationAuctionNotAllowed,
+    LiquidationAuctionOngoing,
+}
+
+/// Return the index of the variant of an inner error, from its encoding, to use as an error code.
+fn variant_index<E: Encode>(err: E) -> u8 {
+    err.encode().first().copied().unwrap_or(0)
 }
 
 impl From<Reason> for frame_support::dispatch::DispatchError {
     fn from(reason: Reason) -> frame_support::dispatch::DispatchError {
         // XXX better way to assign codes?
-        //  also we can use them to differentiate between inner type variants
+        //  inner type variants are differentiated by the error code, using their encoded variant index
         let (index, error, message) = match reason {
             Reason::AssetNotSupported => (0, 0, "asset not supported"),
             Reason::BadAccount => (1, 0, "bad account"),
-            Reason::BadAddress => (1, 1, "bad address"),
+            Reason::BadAddress(chain_id) => (1, 16 + variant_index(chain_id), "bad address"),
             Reason::BadAsset => (1, 2, "bad asset"),
             Reason::BadChainId => (1, 3, "bad chain id"),
             Reason::BadFactor => (1, 4, "bad factor"),
-            Reason::BadHash => (1, 5, "bad hash"),
+            Reason::BadHash(None) => (1, 5, "bad hash"),
+            Reason::BadHash(Some(chain_id)) => (1, 32 + variant_index(chain_id), "bad hash"),
             Reason::BadSymbol => (1, 6, "bad symbol"),
             Reason::BadTicker => (1, 7, "bad ticker"),
             Reason::BadUnits => (1, 8, "bad units"),
             Reason::ChainMismatch => (2, 0, "chain mismatch"),
             Reason::HashMismatch => (2, 1, "hash mismatch"),
             Reason::BlockMismatch => (2, 2, "block mismatch"),
-            Reason::CryptoError(_) => (3, 0, "crypto error"),
-            Reason::EventError(_) => (4, 0, "event error"),
+            Reason::CryptoError(err) => (3, variant_index(err), "crypto error"),
+            Reason::EventError(err) => (4, variant_index(err), "event error"),
             Reason::FailedToSubmitExtrinsic => (5, 0, "failed to submit extrinsic"),
             Reason::WorkerFetchError => (6, 0, "worker fetch error"),
             Reason::WorkerBusy => (6, 1, "worker busy"),
@@ -110,25 +152,28 @@ impl From<Reason> for frame_support::dispatch::DispatchError {
             Reason::InvalidLiquidation => (10, 2, "invalid liquidation parameters"),
             Reason::InvalidUTF8 => (10, 3, "invalid utf8"),
             Reason::KeyNotFound => (11, 0, "key not found"),
-            Reason::MathError(_) => (12, 0, "math error"),
+            Reason::MathError(err) => (12, variant_index(err), "math error"),
             Reason::MaxForNonCashAsset => (13, 0, "max for non cash asset"),
             Reason::MinTxValueNotMet => (14, 0, "min tx value not met"),
             Reason::None => (15, 0, "none"),
             Reason::NoPrice => (16, 0, "no price"),
             Reason::NoSuchAsset => (16, 1, "no such asset"),
-            Reason::NoticeMissing(_, _) => (17, 0, "notice missing"),
+            Reason::NoticeMissing(chain_id, _) => (17, variant_index(chain_id), "notice missing"),
             Reason::NotImplemented => (18, 0, "not implemented"),
-            Reason::OracleError(_) => (19, 0, "oracle error"),
-            Reason::RatesError(_) => (20, 0, "rates error"),
+            Reason::OracleError(err) => (19, variant_index(err), "oracle error"),
+            Reason::RatesError(err) => (20, variant_index(err), "rates error"),
             Reason::RepayTooMuch => (21, 0, "repay too much"),
             Reason::SelfTransfer => (22, 0, "self transfer"),
             Reason::SerdeError => (23, 0, "serde error"),
-            Reason::SetYieldNextError(_) => (24, 0, "set yield next error"),
+            Reason::SetYieldNextError(err) => (24, variant_index(err), "set yield next error"),
             Reason::SignatureAccountMismatch => (25, 0, "signature account mismatch"),
             Reason::SignatureMismatch => (25, 1, "signature mismatch"),
             Reason::TimestampMissing => (26, 0, "timestamp missing"),
             Reason::TimeTravelNotAllowed => (26, 1, "time travel not allowed"),
-            Reason::TrxRequestParseError(_) => (27, 0, "trx request parse error"),
+            Reason::TimestampOutOfBounds => (26, 2, "chain timestamp out of bounds"),
+            Reason::TrxRequestParseError(err) => {
+                (27, variant_index(err), "trx request parse error")
+            }
             Reason::UnknownValidator => (28, 0, "unknown validator"),
             Reason::InvalidChain => (29, 0, "invalid chain"),
             Reason::PendingAuthNotice => (30, 0, "change auth notice is already pending"),
@@ -146,6 +191,43 @@ impl From<Reason> for frame_support::dispatch::DispatchError {
             Reason::StarportMissing => (40, 0, "starport address not set"),
             Reason::InvalidChainBlock => (41, 0, "invalid chain block"),
             Reason::TrxRequestTooLong => (42, 0, "the trx request was too long"),
+            Reason::UnknownFailedEvent => (43, 0, "no such failed event"),
+            Reason::UnknownNotice => (44, 0, "no such notice"),
+            Reason::TooManyValidators => (45, 0, "too many validators"),
+            Reason::TooManySignatures => (46, 0, "too many signatures"),
+            Reason::StalePrice => (16, 2, "stale price"),
+            Reason::BorrowCapExceeded => (47, 0, "borrow cap exceeded"),
+            Reason::NoReserves => (48, 0, "no reserves to sweep"),
+            Reason::SeizePaused => (49, 0, "seizing collateral asset is paused"),
+            Reason::CloseFactorExceeded => (50, 0, "repay exceeds close factor"),
+            Reason::NoEquivocation => (51, 0, "no conflicting attestation"),
+            Reason::ValidatorChilled => (52, 0, "validator is chilled"),
+            Reason::UnknownGovernance => (53, 0, "no such queued governance"),
+            Reason::StaleExecutionFee => (54, 0, "stale execution fee"),
+            Reason::NoExecutionFee => (55, 0, "no execution fee estimate"),
+            Reason::PortfolioConsentRequired => (56, 0, "recipient must accept portfolio debts"),
+            Reason::InvalidGuardians => (57, 0, "invalid guardians or threshold"),
+            Reason::NotGuardian => (58, 0, "not a guardian of the account"),
+            Reason::RecoveryPending => (59, 0, "recovery already queued"),
+            Reason::NoRecovery => (60, 0, "no such queued recovery"),
+            Reason::InvalidQuorumThreshold => (61, 0, "quorum threshold must exceed half"),
+            Reason::InvalidAssetGroup => (62, 0, "assets cannot be grouped under symbol"),
+            Reason::InsufficientStarportLiquidity => (63, 0, "starport holds too little of asset"),
+            Reason::ExodusStarted => (64, 0, "protocol is frozen for exodus"),
+            Reason::EventTimedOut => (65, 0, "event not attested in time"),
+            Reason::ChainDisabled => (66, 0, "chain is halted or ingest only"),
+            Reason::InvalidRecipient => (67, 0, "cannot extract to recipient"),
+            Reason::InvalidValidatorMetadata => (68, 0, "invalid validator metadata"),
+            Reason::StaleValidatorMetadata => (69, 0, "stale validator metadata"),
+            Reason::NoBadDebt => (70, 0, "account has no bad debt"),
+            Reason::InsufficientReserves => (71, 0, "insufficient protocol reserves"),
+            Reason::GovernanceCallsFailed => (72, 0, "some governance calls failed"),
+            Reason::PriceKeyMismatch => (73, 0, "ticker prices a different market"),
+            Reason::ExtractionQueueFull => (74, 0, "too many extractions queued for asset"),
+            Reason::LiquidationAuctionNotAllowed => {
+                (75, 0, "borrower cannot be liquidated by auction")
+            }
+            Reason::LiquidationAuctionOngoing => (76, 0, "liquidation auction already started"),
         };
         frame_support::dispatch::DispatchError::Module {
             index,
@@ -262,3 +344,33 @@ impl From<trx_request::ParseError<'_>> for TrxReqParseError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_support::dispatch::DispatchError;
+
+    fn error_code(reason: Reason) -> (u8, u8) {
+        match reason.into() {
+            DispatchError::Module { index, error, .. } => (index, error),
+            _ => panic!("not a module error"),
+        }
+    }
+
+    #[test]
+    fn test_reason_error_codes_carry_context() {
+        assert_eq!(error_code(Reason::BadAddress(ChainId::Gate)), (1, 16));
+        assert_eq!(error_code(Reason::BadAddress(ChainId::Matic)), (1, 19));
+        assert_eq!(error_code(Reason::BadHash(Some(ChainId::Eth))), (1, 33));
+        assert_eq!(error_code(Reason::BadHash(None)), (1, 5));
+        assert_eq!(error_code(Reason::MathError(MathError::Overflow)), (12, 2));
+        assert_eq!(
+            error_code(Reason::CryptoError(CryptoError::RecoverError)),
+            (3, 5)
+        );
+        assert_ne!(
+            error_code(Reason::MathError(MathError::Overflow)),
+            error_code(Reason::MathError(MathError::Underflow))
+        );
+    }
+}
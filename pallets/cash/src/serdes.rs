@@ -1,8 +1,8 @@
-use our_std::{convert::TryInto, str::FromStr};
+use our_std::{convert::TryInto, str::FromStr, vec::Vec};
 use serde::{de, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
-    chains::{Chain, ChainAccount, ChainAsset, Ethereum},
+    chains::{Chain, ChainAccount, ChainAsset, ChainHash, Ethereum, Gateway},
     symbol::Symbol,
     types::ValidatorKeys,
 };
@@ -85,6 +85,155 @@ impl Serialize for ChainAsset {
     }
 }
 
+// ChainHash
+
+struct ChainHashVisitor;
+
+impl<'de> de::Visitor<'de> for ChainHashVisitor {
+    type Value = ChainHash;
+
+    fn expecting(&self, formatter: &mut our_std::fmt::Formatter) -> our_std::fmt::Result {
+        formatter.write_str("a string of the form <chain>:<hash>")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        ChainHash::from_str(value).map_err(|_| E::custom("bad hash"))
+    }
+}
+
+impl<'de> de::Deserialize<'de> for ChainHash {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        de.deserialize_any(ChainHashVisitor)
+    }
+}
+
+impl Serialize for ChainHash {
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let s: String = (*self).into();
+        ser.serialize_str(&s)
+    }
+}
+
+// Notice fields, as hex strings, for returning notices from RPCs.
+// Every chain with notices uses Ethereum style addresses and hashes.
+
+pub mod hex_address {
+    use super::*;
+
+    pub fn serialize<S>(address: &[u8; 20], ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ser.serialize_str(&<Ethereum as Chain>::address_string(address))
+    }
+
+    pub fn deserialize<'de, D>(de: D) -> Result<[u8; 20], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(de)?;
+        <Ethereum as Chain>::str_to_address(&s).map_err(|_| de::Error::custom("bad address"))
+    }
+}
+
+pub mod hex_hash {
+    use super::*;
+
+    pub fn serialize<S>(hash: &[u8; 32], ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ser.serialize_str(&<Ethereum as Chain>::hash_string(hash))
+    }
+
+    pub fn deserialize<'de, D>(de: D) -> Result<[u8; 32], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(de)?;
+        <Ethereum as Chain>::str_to_hash(&s).map_err(|_| de::Error::custom("bad hash"))
+    }
+}
+
+pub mod hex_addresses {
+    use super::*;
+
+    pub fn serialize<S>(addresses: &Vec<[u8; 20]>, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        addresses
+            .iter()
+            .map(<Ethereum as Chain>::address_string)
+            .collect::<Vec<_>>()
+            .serialize(ser)
+    }
+
+    pub fn deserialize<'de, D>(de: D) -> Result<Vec<[u8; 20]>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<String>::deserialize(de)?
+            .iter()
+            .map(|s| {
+                <Ethereum as Chain>::str_to_address(s).map_err(|_| de::Error::custom("bad address"))
+            })
+            .collect()
+    }
+}
+
+/// Batched extractions, each as [asset, account, amount].
+pub mod hex_extractions {
+    use super::*;
+
+    pub fn serialize<S>(
+        extractions: &Vec<([u8; 20], [u8; 20], u128)>,
+        ser: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        extractions
+            .iter()
+            .map(|(asset, account, amount)| {
+                (
+                    <Ethereum as Chain>::address_string(asset),
+                    <Ethereum as Chain>::address_string(account),
+                    *amount,
+                )
+            })
+            .collect::<Vec<_>>()
+            .serialize(ser)
+    }
+
+    pub fn deserialize<'de, D>(de: D) -> Result<Vec<([u8; 20], [u8; 20], u128)>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<(String, String, u128)>::deserialize(de)?
+            .iter()
+            .map(|(asset, account, amount)| {
+                match (
+                    <Ethereum as Chain>::str_to_address(asset),
+                    <Ethereum as Chain>::str_to_address(account),
+                ) {
+                    (Ok(asset), Ok(account)) => Ok((asset, account, *amount)),
+                    _ => Err(de::Error::custom("bad address")),
+                }
+            })
+            .collect()
+    }
+}
+
 // Symbol
 
 struct SymbolVisitor;
@@ -123,6 +272,52 @@ impl Serialize for Symbol {
     }
 }
 
+// SubstrateId, as a hex string or (for existing chain specs) a byte array
+
+struct SubstrateIdVisitor;
+
+impl<'de> de::Visitor<'de> for SubstrateIdVisitor {
+    type Value = [u8; 32];
+
+    fn expecting(&self, formatter: &mut our_std::fmt::Formatter) -> our_std::fmt::Result {
+        formatter.write_str("a hex string or array of 32 bytes")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        <Gateway as Chain>::str_to_address(value).map_err(|_| E::custom("bad substrate id"))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(33, &self));
+        }
+        Ok(bytes)
+    }
+}
+
+struct SubstrateIdBytes([u8; 32]);
+
+impl<'de> de::Deserialize<'de> for SubstrateIdBytes {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(SubstrateIdBytes(de.deserialize_any(SubstrateIdVisitor)?))
+    }
+}
+
 // ValidatorKeys & Vec<ValidatorKeys>
 
 impl<'de> de::Deserialize<'de> for ValidatorKeys {
@@ -186,7 +381,7 @@ impl<'de> de::Deserialize<'de> for ValidatorKeys {
                             if substrate_id.is_some() {
                                 return Err(de::Error::duplicate_field("substrate_id"));
                             }
-                            let s_id: [u8; 32] = map.next_value()?;
+                            let SubstrateIdBytes(s_id) = map.next_value()?;
                             substrate_id = Some(
                                 s_id.try_into()
                                     .map_err(|_| de::Error::custom("bad substrate id"))?,
@@ -227,9 +422,81 @@ impl Serialize for ValidatorKeys {
     {
         let mut map = ser.serialize_map(Some(2))?;
         map.serialize_key("substrate_id")?;
-        map.serialize_value(&<[u8; 32]>::from(self.substrate_id.clone()))?;
+        map.serialize_value(&<Gateway as Chain>::address_string(&<[u8; 32]>::from(
+            self.substrate_id.clone(),
+        )))?;
         map.serialize_key("eth_address")?;
         map.serialize_value(&<Ethereum as Chain>::address_string(&self.eth_address))?;
         map.end()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validator_keys_from_hex_and_bytes() {
+        let expected = ValidatorKeys {
+            substrate_id: [1u8; 32].into(),
+            eth_address: [2u8; 20],
+        };
+        let from_hex: ValidatorKeys = serde_json::from_str(
+            r#"{
+                "substrate_id": "0x0101010101010101010101010101010101010101010101010101010101010101",
+                "eth_address": "0x0202020202020202020202020202020202020202"
+            }"#,
+        )
+        .unwrap();
+        let from_bytes: ValidatorKeys = serde_json::from_str(&format!(
+            r#"{{"substrate_id": {:?}, "eth_address": "0x0202020202020202020202020202020202020202"}}"#,
+            [1u8; 32]
+        ))
+        .unwrap();
+        assert_eq!(from_hex, expected);
+        assert_eq!(from_bytes, expected);
+
+        let round_trip: ValidatorKeys =
+            serde_json::from_str(&serde_json::to_string(&expected).unwrap()).unwrap();
+        assert_eq!(round_trip, expected);
+    }
+
+    #[test]
+    fn test_chain_hash_round_trip() {
+        let hash = ChainHash::Eth([0xab; 32]);
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(
+            json,
+            r#""ETH:0xabababababababababababababababababababababababababababababababab""#
+        );
+        assert_eq!(serde_json::from_str::<ChainHash>(&json).unwrap(), hash);
+        assert!(serde_json::from_str::<ChainHash>(r#""ETH:0xabab""#).is_err());
+    }
+
+    #[test]
+    fn test_notice_round_trip() {
+        use crate::notices::{BatchExtractionNotice, ExtractionNotice, Notice, NoticeId};
+
+        let notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
+            id: NoticeId(80, 1),
+            parent: [3u8; 32],
+            asset: [1u8; 20],
+            account: [2u8; 20],
+            amount: 50,
+        });
+        let json = serde_json::to_string(&notice).unwrap();
+        assert_eq!(
+            json,
+            r#"{"ExtractionNotice":{"Eth":{"id":[80,1],"parent":"0x0303030303030303030303030303030303030303030303030303030303030303","asset":"0x0101010101010101010101010101010101010101","account":"0x0202020202020202020202020202020202020202","amount":50}}}"#
+        );
+        assert_eq!(serde_json::from_str::<Notice>(&json).unwrap(), notice);
+
+        let batch = Notice::BatchExtractionNotice(BatchExtractionNotice::Matic {
+            id: NoticeId(80, 2),
+            parent: [3u8; 32],
+            extractions: vec![([1u8; 20], [2u8; 20], 50), ([4u8; 20], [5u8; 20], 60)],
+        });
+        let json = serde_json::to_string(&batch).unwrap();
+        assert_eq!(serde_json::from_str::<Notice>(&json).unwrap(), batch);
+    }
+}
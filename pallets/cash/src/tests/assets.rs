@@ -13,7 +13,9 @@ pub const eth: AssetInfo = AssetInfo {
         full_rate: APR(5000),
     },
     miner_shares: Factor::from_nominal("0.05"),
+    reserve_factor: Factor::ZERO,
     supply_cap: Quantity::from_nominal("1000", ETH).value,
+    borrow_cap: AssetAmount::MAX,
     symbol: Symbol(ETH.ticker.0),
     ticker: Ticker(ETH.ticker.0),
 };
@@ -31,7 +33,9 @@ pub const uni: AssetInfo = AssetInfo {
         full_rate: APR(2000),
     },
     miner_shares: Factor::from_nominal("0.05"),
+    reserve_factor: Factor::ZERO,
     supply_cap: Quantity::from_nominal("1000", UNI).value,
+    borrow_cap: AssetAmount::MAX,
     symbol: Symbol(UNI.ticker.0),
     ticker: Ticker(UNI.ticker.0),
 };
@@ -49,7 +53,9 @@ pub const wbtc: AssetInfo = AssetInfo {
         full_rate: APR(2000),
     },
     miner_shares: Factor::from_nominal("0.05"),
+    reserve_factor: Factor::ZERO,
     supply_cap: Quantity::from_nominal("1000", WBTC).value,
+    borrow_cap: AssetAmount::MAX,
     symbol: Symbol(WBTC.ticker.0),
     ticker: Ticker(WBTC.ticker.0),
 };
@@ -66,7 +72,9 @@ pub const usdc: AssetInfo = AssetInfo {
         full_rate: APR(2000),
     },
     miner_shares: Factor::from_nominal("0.05"),
+    reserve_factor: Factor::ZERO,
     supply_cap: Quantity::from_nominal("1000", USD).value,
+    borrow_cap: AssetAmount::MAX,
     symbol: Symbol(USD.ticker.0),
     ticker: Ticker(USD.ticker.0),
 };
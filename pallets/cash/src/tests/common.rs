@@ -43,6 +43,7 @@ pub fn init_asset_balance(asset: ChainAsset, account: ChainAccount, balance: Ass
                 .checked_add(balance)
                 .unwrap() as u128,
         );
+        internal::holdings::add_holdings(asset, balance as AssetAmount);
     } else {
         TotalBorrowAssets::insert(
             asset,
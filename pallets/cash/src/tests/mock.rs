@@ -162,6 +162,7 @@ impl Config for Test {
     type AccountStore = System;
     type SessionInterface = Self;
     type WeightInfo = ();
+    type MaxValidators = crate::params::MaxValidators;
 }
 
 impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
@@ -2,7 +2,7 @@
 
 pub use codec::{Decode, Encode};
 use ethereum_client::{encode_block_hash_hex, encode_block_number_hex};
-pub use frame_support::{assert_err, assert_ok, dispatch::DispatchError};
+pub use frame_support::{assert_err, assert_ok, dispatch::DispatchError, traits::OnFinalize};
 pub use hex_literal::hex;
 pub use our_std::convert::TryInto;
 pub use our_std::{iter::FromIterator, str::FromStr};
@@ -13,6 +13,7 @@ pub mod assets;
 pub mod common;
 pub mod mock;
 pub mod protocol;
+pub mod starport;
 pub mod testdata;
 pub mod worker;
 
@@ -168,6 +169,11 @@ pub fn initialize_storage_with_blocks(genesis_blocks: Vec<ChainBlock>) {
     <pallet_timestamp::Pallet<Test>>::set_timestamp(500);
 }
 
+/// Finalize the block, applying the chain events accepted during it.
+pub fn finalize_block() {
+    CashModule::on_finalize(System::block_number());
+}
+
 pub fn validator_a_sign(data: &[u8]) -> Result<ChainSignature, Reason> {
     std::env::set_var(
         "ETH_KEY",
@@ -178,12 +184,16 @@ pub fn validator_a_sign(data: &[u8]) -> Result<ChainSignature, Reason> {
 
 pub fn a_receive_chain_blocks(blocks: &ChainBlocks) -> Result<(), DispatchError> {
     let signature = validator_a_sign(&blocks.encode())?;
-    CashModule::receive_chain_blocks(Origin::none(), blocks.clone(), signature)
+    let result = CashModule::receive_chain_blocks(Origin::none(), blocks.clone(), signature);
+    finalize_block();
+    result
 }
 
 pub fn a_receive_chain_reorg(reorg: &ChainReorg) -> Result<(), DispatchError> {
     let signature = validator_a_sign(&reorg.encode())?;
-    CashModule::receive_chain_reorg(Origin::none(), reorg.clone(), signature)
+    let result = CashModule::receive_chain_reorg(Origin::none(), reorg.clone(), signature);
+    finalize_block();
+    result
 }
 
 pub fn validator_b_sign(data: &[u8]) -> Result<ChainSignature, Reason> {
@@ -196,12 +206,16 @@ pub fn validator_b_sign(data: &[u8]) -> Result<ChainSignature, Reason> {
 
 pub fn b_receive_chain_blocks(blocks: &ChainBlocks) -> Result<(), DispatchError> {
     let signature = validator_b_sign(&blocks.encode())?;
-    CashModule::receive_chain_blocks(Origin::none(), blocks.clone(), signature)
+    let result = CashModule::receive_chain_blocks(Origin::none(), blocks.clone(), signature);
+    finalize_block();
+    result
 }
 
 pub fn b_receive_chain_reorg(reorg: &ChainReorg) -> Result<(), DispatchError> {
     let signature = validator_b_sign(&reorg.encode())?;
-    CashModule::receive_chain_reorg(Origin::none(), reorg.clone(), signature)
+    let result = CashModule::receive_chain_reorg(Origin::none(), reorg.clone(), signature);
+    finalize_block();
+    result
 }
 
 pub fn all_receive_chain_blocks(blocks: &ChainBlocks) -> Result<(), DispatchError> {
@@ -0,0 +1,276 @@
+use super::test;
+use super::*;
+use crate::internal::{self, extract};
+use ethereum_client::{EthereumBlock, EthereumEvent};
+use our_std::collections::btree_set::BTreeSet;
+use pallet_oracle::{types::Price, Prices};
+
+/// Errors which would cause the Starport contract to revert.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum StarportError {
+    ChainMismatch,
+    InsufficientSignatures,
+    NoticeReplay,
+    UnknownSigner,
+}
+
+/// A simulated Ethereum Starport, which mines its own blocks and invokes notices like the contract.
+/// Allows exercising the full round trip between Gateway and an underlying chain without RPC.
+pub struct Starport {
+    pub authorities: Vec<<Ethereum as Chain>::Address>,
+    pub last_block: EthereumBlock,
+    pub pending_events: Vec<EthereumEvent>,
+    pub invoked: Vec<<Ethereum as Chain>::Hash>,
+    pub unlocked: Vec<(
+        <Ethereum as Chain>::Address,
+        <Ethereum as Chain>::Address,
+        <Ethereum as Chain>::Amount,
+    )>,
+}
+
+impl Starport {
+    pub fn new(genesis: EthereumBlock, validators: &[ValidatorKeys]) -> Self {
+        Starport {
+            authorities: validators.iter().map(|v| v.eth_address).collect(),
+            last_block: genesis,
+            pending_events: vec![],
+            invoked: vec![],
+            unlocked: vec![],
+        }
+    }
+
+    /// Lock an asset into the Starport, to be credited to the recipient on Gateway.
+    pub fn lock(
+        &mut self,
+        asset: <Ethereum as Chain>::Address,
+        sender: <Ethereum as Chain>::Address,
+        recipient: ChainAccount,
+        amount: <Ethereum as Chain>::Amount,
+    ) {
+        let (chain, address) = match recipient {
+            ChainAccount::Eth(address) => ("ETH", address),
+            ChainAccount::Matic(address) => ("MATIC", address),
+            _ => panic!("unsupported recipient {:?}", recipient),
+        };
+        let mut padded = [0u8; 32];
+        padded[..20].copy_from_slice(&address);
+        self.pending_events.push(EthereumEvent::Lock {
+            asset,
+            sender,
+            chain: String::from(chain),
+            recipient: padded,
+            amount,
+        });
+    }
+
+    /// Mine the next block, including all the events emitted since the last one.
+    pub fn mine(&mut self) -> EthereumBlock {
+        let number = self.last_block.number + 1;
+        let parent_hash = self.last_block.hash;
+        self.last_block = EthereumBlock {
+            hash: <Ethereum as Chain>::hash_bytes(&(parent_hash, number).encode()),
+            parent_hash,
+            number,
+            events: self.pending_events.drain(..).collect(),
+        };
+        self.last_block.clone()
+    }
+
+    /// Mine the given number of blocks, returning them for Gateway to receive.
+    pub fn mine_blocks(&mut self, count: usize) -> ChainBlocks {
+        ChainBlocks::Eth((0..count).map(|_| self.mine()).collect())
+    }
+
+    /// Invoke a notice, checking the signatures against the current authorities.
+    pub fn invoke(
+        &mut self,
+        notice: &Notice,
+        signatures: &ChainSignatureList,
+    ) -> Result<(), StarportError> {
+        let pairs = match (notice.chain_id(), signatures) {
+            (ChainId::Eth, ChainSignatureList::Eth(pairs)) => pairs,
+            _ => return Err(StarportError::ChainMismatch),
+        };
+        let notice_hash = match notice.hash() {
+            ChainHash::Eth(hash) => hash,
+            _ => return Err(StarportError::ChainMismatch),
+        };
+        if self.invoked.contains(&notice_hash) {
+            return Err(StarportError::NoticeReplay);
+        }
+
+        let message = notice.encode_notice();
+        let mut signers = BTreeSet::new();
+        for (address, signature) in pairs.iter() {
+            let signer = <Ethereum as Chain>::recover_address(&message, *signature)
+                .map_err(|_| StarportError::UnknownSigner)?;
+            if signer != *address || !self.authorities.contains(&signer) {
+                return Err(StarportError::UnknownSigner);
+            }
+            signers.insert(signer);
+        }
+        let authorities = self.authorities.iter().cloned().collect();
+        if !has_super_majority(&signers, &authorities, DEFAULT_QUORUM_THRESHOLD) {
+            return Err(StarportError::InsufficientSignatures);
+        }
+
+        let (id, result) = match notice {
+            Notice::ExtractionNotice(ExtractionNotice::Eth {
+                id,
+                asset,
+                account,
+                amount,
+                ..
+            }) => {
+                self.unlocked.push((*asset, *account, *amount));
+                (id, vec![])
+            }
+            Notice::BatchExtractionNotice(BatchExtractionNotice::Eth {
+                id, extractions, ..
+            }) => {
+                self.unlocked.extend(extractions.iter().cloned());
+                (id, vec![])
+            }
+            Notice::ChangeAuthorityNotice(ChangeAuthorityNotice::Eth {
+                id,
+                new_authorities,
+                ..
+            }) => {
+                self.authorities = new_authorities.clone();
+                (id, vec![])
+            }
+            Notice::CashExtractionNotice(CashExtractionNotice::Eth { id, .. })
+            | Notice::FutureYieldNotice(FutureYieldNotice::Eth { id, .. })
+            | Notice::SetSupplyCapNotice(SetSupplyCapNotice::Eth { id, .. }) => (id, vec![]),
+            _ => return Err(StarportError::ChainMismatch),
+        };
+
+        self.invoked.push(notice_hash);
+        self.pending_events.push(EthereumEvent::NoticeInvoked {
+            era_id: id.era_id(),
+            era_index: id.era_index(),
+            notice_hash,
+            result,
+        });
+        Ok(())
+    }
+}
+
+/// Sign the latest notice as every validator, and return it with its signatures.
+fn sign_latest_notice(chain_id: ChainId) -> Result<(Notice, ChainSignatureList), Reason> {
+    let (notice_id, _) = LatestNotice::get(chain_id).ok_or(Reason::UnknownNotice)?;
+    let notice = Notices::get(chain_id, notice_id).ok_or(Reason::UnknownNotice)?;
+    let message = notice.encode_notice();
+    internal::notices::publish_signature::<Test>(chain_id, notice_id, validator_a_sign(&message)?)?;
+    internal::notices::publish_signature::<Test>(chain_id, notice_id, validator_b_sign(&message)?)?;
+    match NoticeStates::get(chain_id, notice_id) {
+        NoticeState::Pending { signature_pairs } => Ok((notice, signature_pairs)),
+        _ => Err(Reason::UnknownNotice),
+    }
+}
+
+#[test]
+fn lock_accrue_extract_execute() -> Result<(), Reason> {
+    let sender = [3u8; 20];
+    let holder = ChainAccount::Eth([4u8; 20]);
+    new_test_ext().execute_with(|| {
+        initialize_storage();
+        Prices::insert(ETH.ticker, Price::from_nominal(ETH.ticker, "2000.00").value);
+        let mut starport = Starport::new(premined_block(), &[val_a(), val_b()]);
+
+        // Lock
+
+        starport.lock([0xee; 20], sender, holder, qty!("10", ETH).value);
+        assert_ok!(all_receive_chain_blocks(&starport.mine_blocks(1)));
+        assert_ok!(all_receive_chain_blocks(&starport.mine_blocks(3)));
+        assert_eq!(AssetBalances::get(&Eth, &holder), bal!("10", ETH).value);
+
+        // Accrue
+
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(500 + MILLISECONDS_PER_YEAR / 12);
+        assert_ok!(internal::accrual::accrue_asset::<Test>(Eth));
+        assert_eq!(AssetBalances::get(&Eth, &holder), bal!("10", ETH).value);
+
+        // Extract
+
+        let asset = internal::assets::get_asset::<Test>(Eth)?;
+        assert_ok!(extract::extract_internal::<Test>(
+            asset,
+            holder,
+            holder,
+            qty!("4", ETH)
+        ));
+        assert_eq!(AssetBalances::get(&Eth, &holder), bal!("6", ETH).value);
+
+        // Execute
+
+        let (notice, signatures) = sign_latest_notice(ChainId::Eth)?;
+        assert_ok!(starport.invoke(&notice, &signatures));
+        assert_eq!(
+            starport.unlocked,
+            vec![([0xee; 20], [4u8; 20], qty!("4", ETH).value)]
+        );
+        assert_eq!(
+            starport.invoke(&notice, &signatures),
+            Err(StarportError::NoticeReplay)
+        );
+
+        assert_ok!(all_receive_chain_blocks(&starport.mine_blocks(1)));
+        assert_ok!(all_receive_chain_blocks(&starport.mine_blocks(3)));
+        let notice_id = LatestNotice::get(ChainId::Eth).unwrap().0;
+        assert_eq!(
+            NoticeStates::get(ChainId::Eth, notice_id),
+            NoticeState::Executed
+        );
+        assert_eq!(Notices::get(ChainId::Eth, notice_id), None);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn starport_requires_super_majority() -> Result<(), Reason> {
+    let holder = ChainAccount::Eth([4u8; 20]);
+    new_test_ext().execute_with(|| {
+        initialize_storage();
+        Prices::insert(ETH.ticker, Price::from_nominal(ETH.ticker, "2000.00").value);
+        let mut starport = Starport::new(premined_block(), &[val_a(), val_b()]);
+
+        starport.lock([0xee; 20], [3u8; 20], holder, qty!("10", ETH).value);
+        assert_ok!(all_receive_chain_blocks(&starport.mine_blocks(1)));
+        assert_ok!(all_receive_chain_blocks(&starport.mine_blocks(3)));
+        let asset = internal::assets::get_asset::<Test>(Eth)?;
+        assert_ok!(extract::extract_internal::<Test>(
+            asset,
+            holder,
+            holder,
+            qty!("1", ETH)
+        ));
+
+        let (notice_id, _) = LatestNotice::get(ChainId::Eth).unwrap();
+        let notice = Notices::get(ChainId::Eth, notice_id).unwrap();
+        let message = notice.encode_notice();
+        assert_ok!(internal::notices::publish_signature::<Test>(
+            ChainId::Eth,
+            notice_id,
+            validator_a_sign(&message)?
+        ));
+        let signatures = match NoticeStates::get(ChainId::Eth, notice_id) {
+            NoticeState::Pending { signature_pairs } => signature_pairs,
+            _ => panic!("notice should be pending"),
+        };
+        assert_eq!(
+            starport.invoke(&notice, &signatures),
+            Err(StarportError::InsufficientSignatures)
+        );
+
+        starport.authorities = vec![val_b().eth_address];
+        assert_eq!(
+            starport.invoke(&notice, &signatures),
+            Err(StarportError::UnknownSigner)
+        );
+        assert_eq!(starport.unlocked, vec![]);
+
+        Ok(())
+    })
+}
@@ -2,19 +2,24 @@ use codec::{Decode, Encode};
 use frame_support::sp_runtime::DispatchError;
 
 use our_std::{
-    collections::btree_set::BTreeSet,
     consts::{int_from_string_with_decimals, static_pow10, uint_from_string_with_decimals},
     convert::{TryFrom, TryInto},
+    str::FromStr,
     Deserialize, RuntimeDebug, Serialize,
 };
 use types_derive::{type_alias, Types};
 
-pub use pallet_oracle::{ticker::Ticker, types::Price};
+pub use pallet_oracle::{
+    ticker::Ticker,
+    types::{AssetPrice, Price},
+};
 
 pub use crate::{
-    chains::{Chain, ChainAsset, ChainBlockNumber, ChainId, Ethereum},
+    bounded::{BoundedBTreeSet, BoundedVec},
+    chains::{Chain, ChainAccount, ChainAsset, ChainBlockNumber, ChainHash, ChainId, Ethereum},
     factor::{BigInt, BigUint, Factor},
-    notices::{Notice, NoticeId},
+    notices::{EraId, Notice, NoticeId},
+    params::MaxValidators,
     rates::{InterestRateModel, APR},
     reason::{MathError, Reason},
     symbol::{Symbol, Units, CASH, USD},
@@ -83,10 +88,18 @@ pub type LiquidityFactor = Factor;
 #[type_alias]
 pub type MinerShares = Factor;
 
+/// Type for the reserve factor portion of interest going to protocol reserves.
+#[type_alias]
+pub type ReserveFactor = Factor;
+
 /// Type for a code hash.
 #[type_alias]
 pub type CodeHash = <Ethereum as Chain>::Hash;
 
+/// Type for identifying a queued governance proposal.
+#[type_alias]
+pub type GovernanceId = u32;
+
 /// Governance Result type
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum GovernanceResult {
@@ -105,7 +118,7 @@ pub type ValidatorIdentity = SubstrateId;
 
 /// Type for signers set used to identify validators that signed this event.
 #[type_alias]
-pub type SignersSet = BTreeSet<ValidatorIdentity>;
+pub type SignersSet = BoundedBTreeSet<ValidatorIdentity, MaxValidators>;
 
 /// Type for representing the keys to sign notices.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
@@ -114,6 +127,213 @@ pub struct ValidatorKeys {
     pub eth_address: <Ethereum as Chain>::Address,
 }
 
+/// Type for the metadata an operator publishes about their validator, signed with its validator key.
+/// The signing addresses are those the operator uses on each underlying chain, at most one per chain.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct ValidatorMetadata {
+    pub timestamp: Timestamp,
+    pub name: Vec<u8>,
+    pub website: Vec<u8>,
+    pub signing_addresses: Vec<ChainAccount>,
+}
+
+/// Type for identifying an attested event, by the underlying chain block containing it.
+#[type_alias]
+pub type EventId = (ChainId, ChainBlockNumber);
+
+/// Type for identifying a single event received from a chain, by its event id and the hash of the event.
+#[type_alias]
+pub type ChainEventId = (EventId, ChainHash);
+
+/// Type for identifying a governance proposal received from a chain, by its event id and the hash of the event.
+/// A single underlying block may contain several proposals.
+#[type_alias]
+pub type GovernanceEventId = (EventId, ChainHash);
+
+/// Type for an offense committed by a validator.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub enum Offense {
+    Equivocation(EventId),
+    Unresponsive(ChainId, EraId),
+}
+
+/// Type for how a lock received from a chain was applied, so that a reorg reverts it the same way.
+/// A refund records its notice, which is never batched so that it can be revoked.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub enum LockOutcome {
+    Credited,
+    Refunded(NoticeId),
+}
+
+/// Type for the estimated fees to execute an extraction on underlying chains, as observed by a worker.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct ExecutionFeeReport {
+    pub timestamp: Timestamp,
+    pub fees: Vec<(ChainId, AssetAmount)>,
+}
+
+/// Type for a snapshot of the CASH index and the (borrow, supply) rates of each asset, kept in the rate history.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct RateSnapshot {
+    pub timestamp: Timestamp,
+    pub cash_index: CashIndex,
+    pub rates: Vec<(ChainAsset, APR, APR)>,
+}
+
+/// Type for a change to the balances of accounts, kept in the history of each account it involves.
+/// Each variant mirrors the event of the same name.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub enum AccountActivity {
+    Locked(ChainAsset, ChainAccount, ChainAccount, AssetAmount),
+    LockedCash(ChainAccount, ChainAccount, CashPrincipalAmount, CashIndex),
+    ReorgRevertLocked(ChainAsset, ChainAccount, ChainAccount, AssetAmount),
+    ReorgRevertLockedCash(ChainAccount, ChainAccount, CashPrincipalAmount, CashIndex),
+    Extract(ChainAsset, ChainAccount, ChainAccount, AssetAmount),
+    ExtractCash(ChainAccount, ChainAccount, CashPrincipalAmount, CashIndex),
+    Transfer(ChainAsset, ChainAccount, ChainAccount, AssetAmount),
+    TransferCash(ChainAccount, ChainAccount, CashPrincipalAmount, CashIndex),
+    Liquidate(
+        ChainAsset,
+        ChainAsset,
+        ChainAccount,
+        ChainAccount,
+        AssetAmount,
+    ),
+    LiquidateCash(
+        ChainAsset,
+        ChainAccount,
+        ChainAccount,
+        CashPrincipalAmount,
+        CashIndex,
+    ),
+    LiquidateCashCollateral(ChainAsset, ChainAccount, ChainAccount, AssetAmount),
+}
+
+impl AccountActivity {
+    /// Return the distinct accounts involved in the activity.
+    pub fn accounts(&self) -> Vec<ChainAccount> {
+        let (a, b) = match *self {
+            AccountActivity::Locked(_, a, b, _)
+            | AccountActivity::LockedCash(a, b, _, _)
+            | AccountActivity::ReorgRevertLocked(_, a, b, _)
+            | AccountActivity::ReorgRevertLockedCash(a, b, _, _)
+            | AccountActivity::Extract(_, a, b, _)
+            | AccountActivity::ExtractCash(a, b, _, _)
+            | AccountActivity::Transfer(_, a, b, _)
+            | AccountActivity::TransferCash(a, b, _, _)
+            | AccountActivity::Liquidate(_, _, a, b, _)
+            | AccountActivity::LiquidateCash(_, a, b, _, _)
+            | AccountActivity::LiquidateCashCollateral(_, a, b, _) => (a, b),
+        };
+        if a == b {
+            vec![a]
+        } else {
+            vec![a, b]
+        }
+    }
+}
+
+/// Type for an entry in the history of an account, along with the block it happened in.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct AccountHistoryEntry {
+    pub block_number: u64,
+    pub activity: AccountActivity,
+}
+
+/// Type for the parameters of liquidating large borrows by auction.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct LiquidationAuctionConfig {
+    /// The value in USD of a borrow, above which it is liquidated by auction.
+    pub min_value: AssetAmount,
+    /// The liquidation incentive when the auction starts, in bips.
+    pub start_incentive: Bips,
+    /// The liquidation incentive the auction ramps up to, in bips.
+    pub max_incentive: Bips,
+    /// The number of blocks over which the incentive ramps up.
+    pub ramp_blocks: u32,
+}
+
+/// Type for an ongoing liquidation auction of a borrower.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct LiquidationAuction {
+    /// The block in which the auction started.
+    pub start_block: u64,
+}
+
+/// Type for a periodic snapshot of the solvency of the protocol, with every value in USD.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, Types)]
+pub struct SolvencySnapshot {
+    /// When the snapshot was taken.
+    pub timestamp: Timestamp,
+    /// The value of all the assets supplied to the protocol.
+    pub collateral_value: AssetAmount,
+    /// The value of all the assets borrowed from the protocol.
+    pub borrow_value: AssetAmount,
+    /// The value of all the CASH in existence, which the protocol owes to its holders.
+    pub cash_liabilities: AssetAmount,
+    /// The shortfall of every account whose debts exceed its collateral, not yet written off.
+    pub bad_debt: AssetAmount,
+}
+
+/// Type for tracking how far the market price of wrapped CASH has strayed from its value of one dollar.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, Types)]
+pub struct CashPeg {
+    /// The latest market price of wrapped CASH.
+    pub price: AssetPrice,
+    /// The distance of the market price from one dollar, in basis points.
+    pub deviation_bps: u32,
+    /// When the deviation first exceeded the tolerance, if it still does.
+    pub off_peg_since: Option<Timestamp>,
+    /// Whether the deviation has persisted long enough to be reported.
+    pub reported: bool,
+}
+
+/// Type for the state of a single market, as summarized for monitoring.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct MarketState {
+    pub asset: ChainAsset,
+    pub total_supply: AssetAmount,
+    pub total_borrow: AssetAmount,
+    /// The CASH principal the protocol holds in reserve from the interest on the market.
+    pub reserves: CashPrincipalAmount,
+    pub supply_cap: AssetAmount,
+    pub borrow_cap: AssetAmount,
+    pub supply_rate: APR,
+    pub borrow_rate: APR,
+}
+
+/// Type for a summary of the state of the whole protocol, as polled by dashboards and monitoring.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct ProtocolState {
+    pub markets: Vec<MarketState>,
+    pub cash_index: CashIndex,
+    pub cash_yield: APR,
+    pub total_cash_principal: CashPrincipalAmount,
+    /// Each current validator, with the addresses its operator published for signing on each chain.
+    pub validators: Vec<(ValidatorKeys, Vec<ChainAccount>)>,
+    /// The number of notices still pending on each chain.
+    pub pending_notices: Vec<(ChainId, u32)>,
+    /// The number of the last block processed from each chain.
+    pub last_processed_blocks: Vec<(ChainId, ChainBlockNumber)>,
+}
+
+/// Type for a unit of work done as blocks are initialized, which may be deferred to a later block.
+/// Ordered by priority, the highest first.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, Types)]
+pub enum Work {
+    ReleaseGovernance,
+    ReleaseRecoveries,
+    ReleaseExtractions,
+    DispatchExtractionBatches,
+    ExpirePendingBlocks,
+    RetryFailedEvents,
+    ProcessExodus,
+    TrackCashPeg,
+    RecordRateHistory,
+    CheckSolvency,
+    PruneNotices,
+}
+
 /// Type for referring to either an asset or CASH.
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum CashOrChainAsset {
@@ -121,6 +341,27 @@ pub enum CashOrChainAsset {
     ChainAsset(ChainAsset),
 }
 
+impl FromStr for CashOrChainAsset {
+    type Err = Reason;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        if string.eq_ignore_ascii_case("CASH") {
+            Ok(CashOrChainAsset::Cash)
+        } else {
+            Ok(CashOrChainAsset::ChainAsset(ChainAsset::from_str(string)?))
+        }
+    }
+}
+
+impl From<CashOrChainAsset> for String {
+    fn from(asset: CashOrChainAsset) -> String {
+        match asset {
+            CashOrChainAsset::Cash => String::from("CASH"),
+            CashOrChainAsset::ChainAsset(chain_asset) => String::from(chain_asset),
+        }
+    }
+}
+
 /// Type for representing a quantity, potentially of any symbol.
 #[derive(Serialize, Deserialize)] // used in config
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
@@ -130,11 +371,20 @@ pub struct AssetInfo {
     pub liquidity_factor: LiquidityFactor,
     pub rate_model: InterestRateModel,
     pub miner_shares: MinerShares,
+    #[serde(default)]
+    pub reserve_factor: ReserveFactor,
     pub supply_cap: AssetAmount,
+    #[serde(default = "uncapped")]
+    pub borrow_cap: AssetAmount,
     pub symbol: Symbol,
     pub ticker: Ticker,
 }
 
+/// The borrow cap of assets configured without one.
+fn uncapped() -> AssetAmount {
+    AssetAmount::MAX
+}
+
 impl AssetInfo {
     pub fn minimal(asset: ChainAsset, units: Units) -> Self {
         AssetInfo {
@@ -143,7 +393,9 @@ impl AssetInfo {
             liquidity_factor: LiquidityFactor::default(),
             rate_model: InterestRateModel::default(),
             miner_shares: MinerShares::default(),
+            reserve_factor: ReserveFactor::default(),
             supply_cap: AssetAmount::default(),
+            borrow_cap: uncapped(),
             symbol: Symbol(units.ticker.0),
             ticker: units.ticker,
         }
@@ -887,6 +1139,33 @@ mod tests {
 
     const ETH: Units = Units::from_ticker_str("ETH", 18);
 
+    #[test]
+    fn test_cash_or_chain_asset_str() {
+        let asset = CashOrChainAsset::ChainAsset(ChainAsset::Eth([0xee; 20]));
+        assert_eq!(
+            CashOrChainAsset::from_str("CASH"),
+            Ok(CashOrChainAsset::Cash)
+        );
+        assert_eq!(
+            CashOrChainAsset::from_str("cash"),
+            Ok(CashOrChainAsset::Cash)
+        );
+        assert_eq!(
+            CashOrChainAsset::from_str("eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"),
+            Ok(asset)
+        );
+        assert_eq!(CashOrChainAsset::from_str("ETH"), Err(Reason::BadAsset));
+        assert_eq!(
+            CashOrChainAsset::from_str("XXX:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"),
+            Err(Reason::BadChainId)
+        );
+        assert_eq!(String::from(CashOrChainAsset::Cash), "CASH");
+        assert_eq!(
+            String::from(asset),
+            "ETH:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+        );
+    }
+
     #[test]
     fn test_scale_codec() {
         let a = Quantity::from_nominal("3", CASH);
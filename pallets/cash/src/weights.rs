@@ -52,20 +52,65 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_cash.
 pub trait WeightInfo {
     fn on_initialize(z: u32) -> Weight;
-    fn publish_signature() -> Weight;
+    fn publish_signature(z: u32) -> Weight;
     fn set_yield_next() -> Weight;
+    fn set_peg_tolerance() -> Weight;
     fn receive_chain_blocks() -> Weight;
     fn receive_chain_reorg_pending(z: u32) -> Weight;
+    fn report_equivocation() -> Weight;
+    fn set_validator_metadata() -> Weight;
     fn support_asset() -> Weight;
+    fn set_asset_group(a: u32) -> Weight;
+    fn set_price_key() -> Weight;
+    fn start_exodus() -> Weight;
     fn set_rate_model() -> Weight;
     fn set_liquidity_factor() -> Weight;
+    fn set_reserve_factor() -> Weight;
+    fn sweep_reserves() -> Weight;
+    fn write_off_bad_debt() -> Weight;
     fn set_supply_cap() -> Weight;
+    fn set_borrow_cap() -> Weight;
+    fn set_liquidation_incentive() -> Weight;
+    fn set_close_factor() -> Weight;
+    fn set_seize_paused() -> Weight;
+    fn set_liquidation_auction() -> Weight;
+    fn set_fee_rate() -> Weight;
+    fn set_max_missed_signatures() -> Weight;
+    fn set_quorum_threshold() -> Weight;
+    fn set_governance_delay() -> Weight;
+    fn cancel_governance() -> Weight;
+    fn set_extraction_limit() -> Weight;
+    fn set_extraction_batch_budget() -> Weight;
+    fn discard_failed_event() -> Weight;
+    fn set_event_proof_mode() -> Weight;
+    fn set_chain_status() -> Weight;
+    fn set_event_expiration() -> Weight;
+    fn set_notice_retention() -> Weight;
+    fn set_initialize_weight_budget() -> Weight;
+    fn release_queued_governance(z: u32) -> Weight;
+    fn release_queued_recoveries(z: u32) -> Weight;
+    fn release_queued_extractions(z: u32) -> Weight;
+    fn dispatch_extraction_batches(z: u32) -> Weight;
+    fn expire_pending_blocks() -> Weight;
+    fn retry_failed_events(z: u32) -> Weight;
+    fn process_exodus() -> Weight;
+    fn track_cash_peg() -> Weight;
+    fn record_rate_history(z: u32) -> Weight;
+    fn check_solvency(z: u32) -> Weight;
+    fn prune_notices(z: u32) -> Weight;
     fn allow_next_code_with_hash() -> Weight;
     fn set_next_code_via_hash(z: u32) -> Weight;
-    fn change_validators() -> Weight;
+    fn change_validators(z: u32) -> Weight;
     fn exec_trx_request_extract() -> Weight;
     fn exec_trx_request_transfer() -> Weight;
     fn exec_trx_request_liquidate() -> Weight;
+    fn exec_trx_request_transfer_portfolio() -> Weight;
+    fn exec_trx_request_accept_portfolio() -> Weight;
+    fn exec_trx_request_set_guardians(g: u32) -> Weight;
+    fn exec_trx_request_recover_account() -> Weight;
+    fn exec_trx_request_cancel_recovery() -> Weight;
+    fn exec_trx_request_convert() -> Weight;
+    fn exec_trx_request_start_auction() -> Weight;
 }
 
 /// Weights for pallet_cash using the Substrate node and recommended hardware.
@@ -78,9 +123,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(28 as Weight))
             .saturating_add(T::DbWeight::get().writes(11 as Weight))
     }
-    fn publish_signature() -> Weight {
-        (182_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+    fn publish_signature(z: u32) -> Weight {
+        (176_000_000 as Weight)
+            .saturating_add((3_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(z as Weight)))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
     fn set_yield_next() -> Weight {
@@ -88,6 +135,9 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
+    fn set_peg_tolerance() -> Weight {
+        (16_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
     fn receive_chain_blocks() -> Weight {
         (189_000_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(6 as Weight))
@@ -100,9 +150,37 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(7 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn report_equivocation() -> Weight {
+        (196_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn set_validator_metadata() -> Weight {
+        (104_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
     fn support_asset() -> Weight {
         (15_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_asset_group(a: u32) -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add((24_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(a as Weight)))
+    }
+    fn set_price_key() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn start_exodus() -> Weight {
+        (120_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
     fn set_rate_model() -> Weight {
         (21_000_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
@@ -113,11 +191,169 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_reserve_factor() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn sweep_reserves() -> Weight {
+        (61_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+    fn write_off_bad_debt() -> Weight {
+        (96_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
     fn set_supply_cap() -> Weight {
         (54_000_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(2 as Weight))
             .saturating_add(T::DbWeight::get().writes(5 as Weight))
     }
+    fn set_borrow_cap() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_liquidation_incentive() -> Weight {
+        (16_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_close_factor() -> Weight {
+        (16_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_seize_paused() -> Weight {
+        (19_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_liquidation_auction() -> Weight {
+        (17_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_fee_rate() -> Weight {
+        (16_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_max_missed_signatures() -> Weight {
+        (16_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_quorum_threshold() -> Weight {
+        (16_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_governance_delay() -> Weight {
+        (15_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn cancel_governance() -> Weight {
+        (19_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_extraction_limit() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_extraction_batch_budget() -> Weight {
+        (16_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn discard_failed_event() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_event_proof_mode() -> Weight {
+        (12_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_chain_status() -> Weight {
+        (16_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn set_event_expiration() -> Weight {
+        (11_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_notice_retention() -> Weight {
+        (11_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_initialize_weight_budget() -> Weight {
+        (11_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn release_queued_governance(z: u32) -> Weight {
+        (14_000_000 as Weight)
+            .saturating_add((46_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(z as Weight)))
+    }
+    fn release_queued_recoveries(z: u32) -> Weight {
+        (11_000_000 as Weight)
+            .saturating_add((58_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((8 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(z as Weight)))
+    }
+    fn release_queued_extractions(z: u32) -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add((22_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(z as Weight)))
+    }
+    fn dispatch_extraction_batches(z: u32) -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add((43_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(z as Weight)))
+    }
+    fn expire_pending_blocks() -> Weight {
+        (38_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn retry_failed_events(z: u32) -> Weight {
+        (12_000_000 as Weight)
+            .saturating_add((47_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(z as Weight)))
+    }
+    fn process_exodus() -> Weight {
+        (1_460_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(152 as Weight))
+            .saturating_add(T::DbWeight::get().writes(103 as Weight))
+    }
+    fn track_cash_peg() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn record_rate_history(z: u32) -> Weight {
+        (26_000_000 as Weight)
+            .saturating_add((19_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn check_solvency(z: u32) -> Weight {
+        (29_000_000 as Weight)
+            .saturating_add((21_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn prune_notices(z: u32) -> Weight {
+        (16_000_000 as Weight)
+            .saturating_add((9_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(z as Weight)))
+    }
     fn allow_next_code_with_hash() -> Weight {
         (12_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
@@ -128,10 +364,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
-    fn change_validators() -> Weight {
-        (123_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(11 as Weight))
-            .saturating_add(T::DbWeight::get().writes(12 as Weight))
+    fn change_validators(z: u32) -> Weight {
+        (41_000_000 as Weight)
+            .saturating_add((86_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(z as Weight)))
     }
     fn exec_trx_request_extract() -> Weight {
         (295_000_000 as Weight)
@@ -148,6 +386,42 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(33 as Weight))
             .saturating_add(T::DbWeight::get().writes(19 as Weight))
     }
+    fn exec_trx_request_transfer_portfolio() -> Weight {
+        (486_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(31 as Weight))
+            .saturating_add(T::DbWeight::get().writes(21 as Weight))
+    }
+    fn exec_trx_request_accept_portfolio() -> Weight {
+        (68_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn exec_trx_request_set_guardians(g: u32) -> Weight {
+        (71_000_000 as Weight)
+            .saturating_add((1_200_000 as Weight).saturating_mul(g as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn exec_trx_request_recover_account() -> Weight {
+        (84_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn exec_trx_request_cancel_recovery() -> Weight {
+        (66_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn exec_trx_request_convert() -> Weight {
+        (290_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(22 as Weight))
+            .saturating_add(T::DbWeight::get().writes(12 as Weight))
+    }
+    fn exec_trx_request_start_auction() -> Weight {
+        (112_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -159,9 +433,11 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(28 as Weight))
             .saturating_add(RocksDbWeight::get().writes(11 as Weight))
     }
-    fn publish_signature() -> Weight {
-        (182_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+    fn publish_signature(z: u32) -> Weight {
+        (176_000_000 as Weight)
+            .saturating_add((3_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(z as Weight)))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
     fn set_yield_next() -> Weight {
@@ -169,6 +445,9 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(6 as Weight))
     }
+    fn set_peg_tolerance() -> Weight {
+        (16_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
     fn receive_chain_blocks() -> Weight {
         (189_000_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(6 as Weight))
@@ -181,9 +460,37 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(7 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn report_equivocation() -> Weight {
+        (196_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn set_validator_metadata() -> Weight {
+        (104_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
     fn support_asset() -> Weight {
         (15_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_asset_group(a: u32) -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add((24_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(a as Weight)))
+    }
+    fn set_price_key() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn start_exodus() -> Weight {
+        (120_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
     fn set_rate_model() -> Weight {
         (21_000_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
@@ -194,11 +501,169 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_reserve_factor() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn sweep_reserves() -> Weight {
+        (61_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+    fn write_off_bad_debt() -> Weight {
+        (96_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
+    }
     fn set_supply_cap() -> Weight {
         (54_000_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(2 as Weight))
             .saturating_add(RocksDbWeight::get().writes(5 as Weight))
     }
+    fn set_borrow_cap() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_liquidation_incentive() -> Weight {
+        (16_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_close_factor() -> Weight {
+        (16_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_seize_paused() -> Weight {
+        (19_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_liquidation_auction() -> Weight {
+        (17_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_fee_rate() -> Weight {
+        (16_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_max_missed_signatures() -> Weight {
+        (16_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_quorum_threshold() -> Weight {
+        (16_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_governance_delay() -> Weight {
+        (15_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn cancel_governance() -> Weight {
+        (19_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_extraction_limit() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_extraction_batch_budget() -> Weight {
+        (16_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn discard_failed_event() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_event_proof_mode() -> Weight {
+        (12_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_chain_status() -> Weight {
+        (16_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn set_event_expiration() -> Weight {
+        (11_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_notice_retention() -> Weight {
+        (11_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_initialize_weight_budget() -> Weight {
+        (11_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn release_queued_governance(z: u32) -> Weight {
+        (14_000_000 as Weight)
+            .saturating_add((46_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(z as Weight)))
+    }
+    fn release_queued_recoveries(z: u32) -> Weight {
+        (11_000_000 as Weight)
+            .saturating_add((58_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((8 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(z as Weight)))
+    }
+    fn release_queued_extractions(z: u32) -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add((22_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(z as Weight)))
+    }
+    fn dispatch_extraction_batches(z: u32) -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add((43_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(z as Weight)))
+    }
+    fn expire_pending_blocks() -> Weight {
+        (38_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn retry_failed_events(z: u32) -> Weight {
+        (12_000_000 as Weight)
+            .saturating_add((47_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((7 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(z as Weight)))
+    }
+    fn process_exodus() -> Weight {
+        (1_460_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(152 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(103 as Weight))
+    }
+    fn track_cash_peg() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn record_rate_history(z: u32) -> Weight {
+        (26_000_000 as Weight)
+            .saturating_add((19_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn check_solvency(z: u32) -> Weight {
+        (29_000_000 as Weight)
+            .saturating_add((21_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn prune_notices(z: u32) -> Weight {
+        (16_000_000 as Weight)
+            .saturating_add((9_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(z as Weight)))
+    }
     fn allow_next_code_with_hash() -> Weight {
         (12_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
@@ -209,10 +674,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
-    fn change_validators() -> Weight {
-        (123_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(11 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(12 as Weight))
+    fn change_validators(z: u32) -> Weight {
+        (41_000_000 as Weight)
+            .saturating_add((86_000_000 as Weight).saturating_mul(z as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(z as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(z as Weight)))
     }
     fn exec_trx_request_extract() -> Weight {
         (295_000_000 as Weight)
@@ -229,4 +696,40 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(33 as Weight))
             .saturating_add(RocksDbWeight::get().writes(19 as Weight))
     }
+    fn exec_trx_request_transfer_portfolio() -> Weight {
+        (486_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(31 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(21 as Weight))
+    }
+    fn exec_trx_request_accept_portfolio() -> Weight {
+        (68_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn exec_trx_request_set_guardians(g: u32) -> Weight {
+        (71_000_000 as Weight)
+            .saturating_add((1_200_000 as Weight).saturating_mul(g as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn exec_trx_request_recover_account() -> Weight {
+        (84_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn exec_trx_request_cancel_recovery() -> Weight {
+        (66_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn exec_trx_request_convert() -> Weight {
+        (290_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(22 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(12 as Weight))
+    }
+    fn exec_trx_request_start_auction() -> Weight {
+        (112_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
 }
@@ -0,0 +1,272 @@
+use frame_support::storage::StorageMap;
+use sp_core::{U256, U512};
+
+use crate::{
+    error::OracleError,
+    is_price_time_fresh,
+    oracle::MAX_PRICE_FUTURE_MS,
+    ticker::Ticker,
+    types::{AnchorConfig, AssetPrice, Price, Timestamp},
+    AnchorConfigs, AnchorPrices, Config, Event, Module,
+};
+use timestamp::GetConvertedTimestamp;
+
+/// The largest tick supported by Uniswap V3 pools.
+pub const MAX_TICK: i32 = 887272;
+
+/// The largest number of decimals we support for either side of an anchor pool.
+const MAX_ANCHOR_DECIMALS: u8 = 38;
+
+/// Magic constants from the Uniswap V3 `TickMath` library, each `2^128 / sqrt(1.0001)^(2^i)`.
+const TICK_RATIOS: [u128; 19] = [
+    0xfff97272373d413259a46990580e213a,
+    0xfff2e50f5f656932ef12357cf3c7fdcc,
+    0xffe5caca7e10e4e61c3624eaa0941cd0,
+    0xffcb9843d60f6159c9db58835c926644,
+    0xff973b41fa98c081472e6896dfb254c0,
+    0xff2ea16466c96a3843ec78b326b52861,
+    0xfe5dee046a99a2a811c461f1969c3053,
+    0xfcbe86c7900a88aedcffc83b479aa3a4,
+    0xf987a7253ac413176f2b074cf7815e54,
+    0xf3392b0822b70005940c7a398e4b70f3,
+    0xe7159475a2c29b7443b29c7fa6e889d9,
+    0xd097f3bdfd2022b8845ad8f792aa5825,
+    0xa9f746462d870fdf8a65dc1f90e061e5,
+    0x70d869a156d2a1b890bb3df62baf32f7,
+    0x31be135f97d08fd981231505542fcfa6,
+    0x9aa508b5b7a84e1c677de54f3e99bc9,
+    0x5d6af8dedb81196699c329225ee604,
+    0x2216e584f5fa1ea926041bedfe98,
+    0x48a170391f7dc42444e8fa2,
+];
+
+/// Compute `sqrt(1.0001^tick) * 2^96`, exactly as the Uniswap V3 `TickMath` library does.
+pub fn sqrt_ratio_at_tick(tick: i32) -> Result<U256, OracleError> {
+    let abs_tick = tick.checked_abs().ok_or(OracleError::InvalidValue)?;
+    if abs_tick > MAX_TICK {
+        return Err(OracleError::InvalidValue);
+    }
+
+    let mut ratio = if abs_tick & 0x1 != 0 {
+        U256::from(0xfffcb933bd6fad37aa2d162d1a594001u128)
+    } else {
+        U256::one() << 128
+    };
+    for (i, magic) in TICK_RATIOS.iter().enumerate() {
+        if abs_tick & (0x2 << i) != 0 {
+            ratio = (ratio * U256::from(*magic)) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::max_value() / ratio;
+    }
+
+    // Round up, so that the ratio at a tick is never below the true value
+    let rounding = if ratio.low_u32() == 0 { 0u8 } else { 1u8 };
+    Ok((ratio >> 32) + U256::from(rounding))
+}
+
+/// Compute the USD price of the asset of an anchor pool, given the average tick of the pool.
+pub fn price_at_tick(config: &AnchorConfig, tick: i32) -> Result<AssetPrice, OracleError> {
+    let sqrt_ratio = U512::from(sqrt_ratio_at_tick(tick)?);
+    let ratio_x192 = sqrt_ratio * sqrt_ratio;
+    let base_amount = U512::from(10).pow(config.base_decimals.into());
+    let quote_amount = if config.base_is_token0 {
+        (ratio_x192 * base_amount) >> 192
+    } else {
+        (base_amount << 192) / ratio_x192
+    };
+    let price = quote_amount * U512::from(10).pow(Price::DECIMALS.into())
+        / U512::from(10).pow(config.quote_decimals.into());
+    if price.is_zero() || price.bits() > 128 {
+        return Err(OracleError::InvalidValue);
+    }
+    Ok(price.low_u128())
+}
+
+/// Set or clear the Uniswap V3 pool anchoring the reported prices for a ticker.
+pub fn set_anchor_config<T: Config>(
+    ticker: Ticker,
+    config: Option<AnchorConfig>,
+) -> Result<(), OracleError> {
+    if let Some(config) = config {
+        if config.twap_period == 0
+            || config.base_decimals > MAX_ANCHOR_DECIMALS
+            || config.quote_decimals > MAX_ANCHOR_DECIMALS
+        {
+            return Err(OracleError::InvalidValue);
+        }
+    }
+
+    // * WARNING begin storage - all checks must happen above * //
+
+    match config {
+        Some(config) => AnchorConfigs::insert(&ticker, config),
+        None => AnchorConfigs::remove(&ticker),
+    }
+    AnchorPrices::remove(&ticker);
+    <Module<T>>::deposit_event(Event::SetAnchorConfig(ticker, config));
+    Ok(())
+}
+
+/// Check that an anchor price observed at the given time may be posted for a ticker.
+pub fn check_anchor_price<T: Config>(
+    ticker: Ticker,
+    timestamp: Timestamp,
+) -> Result<(), OracleError> {
+    if !AnchorConfigs::contains_key(ticker) {
+        return Err(OracleError::NoAnchor);
+    }
+    if let Some((_, last_updated)) = AnchorPrices::get(ticker) {
+        if timestamp <= last_updated {
+            return Err(OracleError::StalePrice);
+        }
+    }
+    let now = T::GetConvertedTimestamp::get_recent_timestamp()
+        .map_err(|_| OracleError::InvalidTimestamp)?;
+    if timestamp > now.saturating_add(MAX_PRICE_FUTURE_MS) {
+        return Err(OracleError::TimestampTooHigh);
+    }
+    Ok(())
+}
+
+/// Post an anchor price for a ticker, as agreed by a quorum of validators observing it from the given time.
+pub fn post_anchor_price<T: Config>(
+    ticker: Ticker,
+    price: AssetPrice,
+    timestamp: Timestamp,
+) -> Result<(), OracleError> {
+    check_anchor_price::<T>(ticker, timestamp)?;
+
+    // * WARNING begin storage - all checks must happen above * //
+
+    put_anchor_price::<T>(ticker, price, timestamp);
+    Ok(())
+}
+
+/// Store an anchor price for a ticker, which must already have been checked by `check_anchor_price`.
+pub fn put_anchor_price<T: Config>(ticker: Ticker, price: AssetPrice, timestamp: Timestamp) {
+    AnchorPrices::insert(&ticker, (price, timestamp));
+    <Module<T>>::deposit_event(Event::AnchorPricePosted(ticker, price));
+}
+
+/// Return the anchor price for a ticker if it is fresh and the given price falls outside its band.
+pub fn get_violated_anchor<T: Config>(ticker: Ticker, price: AssetPrice) -> Option<AssetPrice> {
+    let config = AnchorConfigs::get(ticker)?;
+    let (anchor, anchor_time) = AnchorPrices::get(ticker)?;
+    if is_price_time_fresh::<T>(ticker, Some(anchor_time)).unwrap_or(false)
+        && !config.within_band(anchor, price)
+    {
+        Some(anchor)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn eth_usdc() -> AnchorConfig {
+        AnchorConfig {
+            pool: [0x8a; 20],
+            base_is_token0: false,
+            base_decimals: 18,
+            quote_decimals: 6,
+            twap_period: 1800,
+            band_bps: 2000,
+        }
+    }
+
+    #[test]
+    fn test_sqrt_ratio_at_tick() {
+        assert_eq!(sqrt_ratio_at_tick(0), Ok(U256::one() << 96));
+        assert_eq!(sqrt_ratio_at_tick(-MAX_TICK), Ok(U256::from(4295128739u64)));
+        assert_eq!(
+            sqrt_ratio_at_tick(MAX_TICK),
+            Ok(U256::from_dec_str("1461446703485210103287273052203988822378723970342").unwrap())
+        );
+        assert_eq!(
+            sqrt_ratio_at_tick(MAX_TICK + 1),
+            Err(OracleError::InvalidValue)
+        );
+        assert_eq!(sqrt_ratio_at_tick(i32::MIN), Err(OracleError::InvalidValue));
+    }
+
+    #[test]
+    fn test_price_at_tick() {
+        let config = eth_usdc();
+        assert_eq!(price_at_tick(&config, 201234), Ok(1823_708271)); // 1e12 / 1.0001^201234
+        assert_eq!(
+            price_at_tick(
+                &AnchorConfig {
+                    base_is_token0: true,
+                    base_decimals: 6,
+                    ..config
+                },
+                0
+            ),
+            Ok(1_000000)
+        );
+        assert_eq!(
+            price_at_tick(&config, -MAX_TICK),
+            Err(OracleError::InvalidValue)
+        );
+    }
+
+    #[test]
+    fn test_post_anchor_price() {
+        new_test_ext().execute_with(|| {
+            let ticker = Ticker::new("ETH");
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1609340760000);
+            assert_eq!(
+                post_anchor_price::<Test>(ticker, 2000_000000, 1609340760000),
+                Err(OracleError::NoAnchor)
+            );
+            assert_eq!(
+                set_anchor_config::<Test>(
+                    ticker,
+                    Some(AnchorConfig {
+                        twap_period: 0,
+                        ..eth_usdc()
+                    })
+                ),
+                Err(OracleError::InvalidValue)
+            );
+
+            assert_eq!(set_anchor_config::<Test>(ticker, Some(eth_usdc())), Ok(()));
+            assert_eq!(
+                post_anchor_price::<Test>(ticker, 2000_000000, 1609340760000),
+                Ok(())
+            );
+            assert_eq!(
+                AnchorPrices::get(ticker),
+                Some((2000_000000, 1609340760000))
+            );
+            assert_eq!(
+                post_anchor_price::<Test>(ticker, 2100_000000, 1609340760000),
+                Err(OracleError::StalePrice)
+            );
+            assert_eq!(
+                post_anchor_price::<Test>(ticker, 2100_000000, 1609340760000 + 200000),
+                Err(OracleError::TimestampTooHigh)
+            );
+
+            assert_eq!(get_violated_anchor::<Test>(ticker, 2300_000000), None);
+            assert_eq!(
+                get_violated_anchor::<Test>(ticker, 2500_000000),
+                Some(2000_000000)
+            );
+            assert_eq!(
+                get_violated_anchor::<Test>(ticker, 1500_000000),
+                Some(2000_000000)
+            );
+
+            // Changing the pool forgets the anchor price
+            assert_eq!(set_anchor_config::<Test>(ticker, Some(eth_usdc())), Ok(()));
+            assert_eq!(AnchorPrices::get(ticker), None);
+            assert_eq!(get_violated_anchor::<Test>(ticker, 2500_000000), None);
+        });
+    }
+}
@@ -26,6 +26,7 @@ pub enum OracleError {
     NoPrice,
     InvalidValue,
     TimestampTooHigh,
+    NoAnchor,
 }
 
 impl From<CryptoError> for OracleError {
@@ -55,6 +56,7 @@ impl From<OracleError> for frame_support::dispatch::DispatchError {
             OracleError::NoPrice => (15, 0, "NoPrice"),
             OracleError::InvalidValue => (16, 0, "InvalidValue"),
             OracleError::TimestampTooHigh => (17, 0, "TimestampTooHigh"),
+            OracleError::NoAnchor => (18, 0, "NoAnchor"),
         };
         frame_support::dispatch::DispatchError::Module {
             index,
@@ -3,7 +3,7 @@
 use crate::{
     error::OracleError,
     ticker::{Ticker, CASH_TICKER, USD_TICKER},
-    types::{AssetPrice, Price, ReporterSet, Timestamp},
+    types::{AnchorConfig, AssetPrice, Price, PriceGuard, Reporter, ReporterSet, Timestamp},
 };
 use frame_support::{
     decl_event, decl_module, decl_storage, dispatch,
@@ -11,13 +11,15 @@ use frame_support::{
     weights::{DispatchClass, GetDispatchInfo, Pays},
     Parameter,
 };
-use frame_system::{ensure_none, offchain::CreateSignedTransaction};
+use frame_system::{ensure_none, ensure_root, offchain::CreateSignedTransaction};
 use our_std::log;
 use pallet_timestamp;
 use sp_runtime::transaction_validity::{
     InvalidTransaction, TransactionSource, TransactionValidity,
 };
+use timestamp::GetConvertedTimestamp;
 
+pub mod anchor;
 pub mod error;
 pub mod inherent;
 pub mod oracle;
@@ -58,6 +60,18 @@ decl_storage! {
         /// Mapping of assets to the last time their price was updated.
         pub PriceTimes get(fn price_time): map hasher(blake2_128_concat) Ticker => Option<Timestamp>;
 
+        /// Mapping of price tickers to their staleness and deviation guards, if any.
+        pub PriceGuards get(fn price_guard): map hasher(blake2_128_concat) Ticker => Option<PriceGuard>;
+
+        /// Mapping of price tickers to a price, its timestamp and its reporter, deviating too far from the last price and awaiting confirmation by another reporter.
+        pub PendingPrices get(fn pending_price): map hasher(blake2_128_concat) Ticker => Option<(AssetPrice, Timestamp, Reporter)>;
+
+        /// Mapping of price tickers to the Uniswap V3 pool anchoring their reported prices, if any.
+        pub AnchorConfigs get(fn anchor_config): map hasher(blake2_128_concat) Ticker => Option<AnchorConfig>;
+
+        /// Mapping of price tickers to their latest anchor price and the time it was observed.
+        pub AnchorPrices get(fn anchor_price): map hasher(blake2_128_concat) Ticker => Option<(AssetPrice, Timestamp)>;
+
         /// Ethereum addresses of open oracle price reporters.
         pub PriceReporters get(fn reporters): ReporterSet; // XXX if > 1, how are we combining?
     }
@@ -75,6 +89,21 @@ decl_event!(
     pub enum Event {
         /// Failed to process a given extrinsic. [reason]
         Failure(OracleError),
+
+        /// The price guards for a ticker have been set. [ticker, guard]
+        SetPriceGuard(Ticker, Option<PriceGuard>),
+
+        /// A price deviating too far from the last price awaits a confirming report. [ticker, price]
+        PriceDeviationPending(Ticker, AssetPrice),
+
+        /// The anchor pool for a ticker has been set. [ticker, config]
+        SetAnchorConfig(Ticker, Option<AnchorConfig>),
+
+        /// An anchor price has been observed by a validator. [ticker, anchor]
+        AnchorPricePosted(Ticker, AssetPrice),
+
+        /// A reported price falling outside the band around the anchor price was rejected. [ticker, price, anchor]
+        PriceGuarded(Ticker, AssetPrice, AssetPrice),
     }
 );
 
@@ -99,7 +128,7 @@ decl_module! {
         // Events must be initialized if they are used by the pallet.
         fn deposit_event() = default;
 
-        /// Set the price using the open price feed. [User] [Free]
+        /// Set the price using the open price feed, submitted by validators or directly by a reporter. [User] [Free]
         #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
         pub fn post_price(origin, payload: Vec<u8>, signature: Vec<u8>) -> dispatch::DispatchResult {
             ensure_none(origin)?;
@@ -118,6 +147,20 @@ decl_module! {
             })?)
         }
 
+        /// Set or clear the staleness and deviation guards for a ticker. [Root]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn set_price_guard(origin, ticker: Ticker, guard: Option<PriceGuard>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(oracle::set_price_guard::<T>(ticker, guard))?)
+        }
+
+        /// Set or clear the Uniswap V3 pool anchoring the reported prices for a ticker. [Root]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn set_anchor_config(origin, ticker: Ticker, config: Option<AnchorConfig>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(anchor::set_anchor_config::<T>(ticker, config))?)
+        }
+
         /// Offchain Worker entry point.
         fn offchain_worker(block_number: T::BlockNumber) {
             if let Err(e) = oracle::process_prices::<T>(block_number) {
@@ -128,11 +171,67 @@ decl_module! {
 }
 
 /// Return the USD price associated with the given units.
+/// Falls back to the anchor price when there is no reported price, or the reported price is stale, as long as the anchor is not.
 pub fn get_price_by_ticker<T: Config>(ticker: Ticker) -> Option<Price> {
     match ticker {
         t if t == USD_TICKER => Some(Price::from_nominal(USD_TICKER, "1.0")),
         t if t == CASH_TICKER => Some(Price::from_nominal(CASH_TICKER, "1.0")),
-        _ => Prices::get(ticker).map(|price| Price::new(ticker, price)),
+        _ => match (Prices::get(ticker), AnchorPrices::get(ticker)) {
+            (Some(price), Some((anchor, anchor_time))) => {
+                let reported_fresh =
+                    is_price_time_fresh::<T>(ticker, PriceTimes::get(ticker)).unwrap_or(true);
+                let anchor_fresh =
+                    is_price_time_fresh::<T>(ticker, Some(anchor_time)).unwrap_or(false);
+                if !reported_fresh && anchor_fresh {
+                    Some(anchor)
+                } else {
+                    Some(price)
+                }
+            }
+            (Some(price), None) => Some(price),
+            (None, Some((anchor, anchor_time))) => {
+                if is_price_time_fresh::<T>(ticker, Some(anchor_time)).unwrap_or(false) {
+                    Some(anchor)
+                } else {
+                    None
+                }
+            }
+            (None, None) => None,
+        }
+        .map(|price| Price::new(ticker, price)),
+    }
+}
+
+/// Check that the price for the given ticker is recent enough to be relied upon, according to its guard.
+/// The price is fresh if either the reported price or the anchor price is.
+pub fn check_price_fresh<T: Config>(ticker: Ticker) -> Result<(), OracleError> {
+    if ticker == USD_TICKER || ticker == CASH_TICKER {
+        return Ok(());
+    }
+    let anchor_time = AnchorPrices::get(ticker).map(|(_, anchor_time)| anchor_time);
+    if is_price_time_fresh::<T>(ticker, PriceTimes::get(ticker))?
+        || is_price_time_fresh::<T>(ticker, anchor_time)?
+    {
+        Ok(())
+    } else {
+        Err(OracleError::StalePrice)
+    }
+}
+
+/// Whether a price for the given ticker last updated at the given time is recent enough, according to its guard.
+pub fn is_price_time_fresh<T: Config>(
+    ticker: Ticker,
+    last_updated: Option<Timestamp>,
+) -> Result<bool, OracleError> {
+    match PriceGuards::get(ticker) {
+        Some(guard) if guard.max_age > 0 => {
+            let now = T::GetConvertedTimestamp::get_recent_timestamp()
+                .map_err(|_| OracleError::InvalidTimestamp)?;
+            Ok(last_updated
+                .map(|last_updated| now.saturating_sub(last_updated) <= guard.max_age)
+                .unwrap_or(false))
+        }
+        _ => Ok(true),
     }
 }
 
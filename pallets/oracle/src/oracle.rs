@@ -8,12 +8,16 @@ use sp_runtime::offchain::{
 };
 
 use crate::{
+    anchor,
     error::OracleError,
     ticker::Ticker,
-    types::{AssetPrice, Timestamp},
+    types::{AssetPrice, PriceGuard, Reporter, Timestamp},
 };
-use crate::{Config, PriceReporters, PriceTimes, Prices, ORACLE_POLL_INTERVAL_BLOCKS};
-use our_std::convert::TryInto;
+use crate::{
+    Config, Event, Module, PendingPrices, PriceGuards, PriceReporters, PriceTimes, Prices,
+    ORACLE_POLL_INTERVAL_BLOCKS,
+};
+use ethereum_client::abi::{self, ParamType, Token};
 use our_std::{collections::btree_map::BTreeMap, str::FromStr, vec::Vec, RuntimeDebug};
 use timestamp::GetConvertedTimestamp;
 
@@ -53,53 +57,42 @@ const ORACLE_FETCH_DEADLINE: u64 = 2_000;
 /// https://github.com/compound-finance/open-oracle/blob/aff3634c9f23dc40b3803f44863244d22f623e7e/contracts/OpenOraclePriceData.sol#L58
 pub fn parse_message(message: &[u8]) -> Result<Message, OracleError> {
     let types = [
-        ethabi::param_type::ParamType::String,
-        ethabi::param_type::ParamType::Uint(64),
-        ethabi::param_type::ParamType::String,
-        ethabi::param_type::ParamType::Uint(64),
+        ParamType::String,
+        ParamType::Uint,
+        ParamType::String,
+        ParamType::Uint,
     ];
-    let mut abi_decoded =
-        ethabi::decode(&types, &message).map_err(|_| OracleError::HexParseError)?;
-    if !abi_decoded.len() == 4 {
-        Err(OracleError::EthAbiParseError)?;
-    }
-
-    let mut abi_drain = abi_decoded.drain(..);
+    let abi_decoded = abi::decode(&types, &message).map_err(|_| OracleError::HexParseError)?;
+    let mut abi_drain = abi_decoded.into_iter();
 
     let kind = abi_drain
         .next()
-        .ok_or(OracleError::EthAbiParseError)?
-        .to_string()
+        .and_then(Token::into_string)
         .ok_or(OracleError::EthAbiParseError)?;
     if kind != "prices" {
         Err(OracleError::InvalidKind)?;
     }
 
-    let timestamp: u64 = abi_drain
+    let timestamp = abi_drain
         .next()
-        .ok_or(OracleError::EthAbiParseError)?
-        .to_uint()
-        .ok_or(OracleError::EthAbiParseError)?
-        .try_into()
-        .map_err(|_| OracleError::InvalidTimestamp)?;
+        .and_then(Token::into_uint)
+        .ok_or(OracleError::EthAbiParseError)?;
+    let timestamp = abi::word_to_u64(&timestamp).ok_or(OracleError::InvalidTimestamp)?;
 
     let key = abi_drain
         .next()
-        .ok_or(OracleError::EthAbiParseError)?
-        .to_string()
+        .and_then(Token::into_string)
         .ok_or(OracleError::EthAbiParseError)?;
 
     if key.len() > MAXIMUM_TICKER_LENGTH {
         Err(OracleError::InvalidTicker)?;
     }
 
-    let value: u64 = abi_drain
+    let value = abi_drain
         .next()
-        .ok_or(OracleError::EthAbiParseError)?
-        .to_uint()
-        .ok_or(OracleError::EthAbiParseError)?
-        .try_into()
-        .map_err(|_| OracleError::InvalidValue)?;
+        .and_then(Token::into_uint)
+        .ok_or(OracleError::EthAbiParseError)?;
+    let value = abi::word_to_u64(&value).ok_or(OracleError::InvalidValue)?;
 
     Ok(Message {
         kind,
@@ -183,19 +176,28 @@ impl OpenPriceFeedApiResponse {
 const OCW_LATEST_BLOCK_NUMBER: &[u8; 41] = b"cash::latest_price_feed_poll_block_number";
 const OCW_STORAGE_LOCK: &[u8; 34] = b"cash::storage_lock_open_price_feed";
 
-pub fn check_signature<T: Config>(
+/// Recover the address which signed a price message, whether or not it is a known reporter.
+pub fn recover_reporter<T: Config>(
     payload: &Vec<u8>,
     signature: &Vec<u8>,
-) -> Result<bool, OracleError> {
-    // check signature
+) -> Result<Reporter, OracleError> {
     let parsed_sig: [u8; 65] = gateway_crypto::eth_signature_from_bytes(&signature)?;
 
     // note that this is actually a double-hash situation but that is expected behavior
     // the hashed message is hashed again in the eth convention inside eth_recover
     let hashed = gateway_crypto::keccak(&payload);
-    let recovered =
-        runtime_interfaces::keyring_interface::eth_recover(hashed.into(), parsed_sig, true)?;
+    Ok(runtime_interfaces::keyring_interface::eth_recover(
+        hashed.into(),
+        parsed_sig,
+        true,
+    )?)
+}
 
+pub fn check_signature<T: Config>(
+    payload: &Vec<u8>,
+    signature: &Vec<u8>,
+) -> Result<bool, OracleError> {
+    let recovered = recover_reporter::<T>(payload, signature)?;
     Ok(PriceReporters::get().contains(recovered))
 }
 
@@ -222,16 +224,69 @@ pub fn get_and_check_parsed_price<T: Config>(
     Ok((parsed, ticker))
 }
 pub fn post_price<T: Config>(payload: Vec<u8>, signature: Vec<u8>) -> Result<(), OracleError> {
-    if !check_signature::<T>(&payload, &signature)? {
+    let reporter = recover_reporter::<T>(&payload, &signature)?;
+    if !PriceReporters::get().contains(reporter) {
         Err(OracleError::InvalidReporter)?;
     }
 
     let (parsed, ticker) = get_and_check_parsed_price::<T>(&payload)?;
+    let price = parsed.value as AssetPrice;
+    let timestamp = parsed.timestamp as Timestamp;
+
+    if let Some(anchor) = anchor::get_violated_anchor::<T>(ticker, price) {
+        <Module<T>>::deposit_event(Event::PriceGuarded(ticker, price, anchor));
+        return Ok(());
+    }
 
     // * WARNING begin storage - all checks must happen above * //
 
-    Prices::insert(&ticker, parsed.value as AssetPrice);
-    PriceTimes::insert(&ticker, parsed.timestamp as Timestamp);
+    if !is_price_confirmed::<T>(ticker, price, timestamp, reporter) {
+        PendingPrices::insert(&ticker, (price, timestamp, reporter));
+        <Module<T>>::deposit_event(Event::PriceDeviationPending(ticker, price));
+        return Ok(());
+    }
+
+    Prices::insert(&ticker, price);
+    PriceTimes::insert(&ticker, timestamp);
+    PendingPrices::remove(&ticker);
+    Ok(())
+}
+
+/// Whether a new price is within the allowed deviation of the current price,
+///  or else confirms a deviating price reported before it by a different reporter.
+pub fn is_price_confirmed<T: Config>(
+    ticker: Ticker,
+    price: AssetPrice,
+    timestamp: Timestamp,
+    reporter: Reporter,
+) -> bool {
+    let guard = PriceGuards::get(ticker).unwrap_or_default();
+    match Prices::get(ticker) {
+        Some(current) if !guard.within_deviation(current, price) => {
+            match PendingPrices::get(ticker) {
+                Some((pending, pending_timestamp, pending_reporter)) => {
+                    reporter != pending_reporter
+                        && timestamp > pending_timestamp
+                        && guard.within_deviation(pending, price)
+                }
+                None => false,
+            }
+        }
+        _ => true,
+    }
+}
+
+/// Set or clear the staleness and deviation guards for a ticker.
+pub fn set_price_guard<T: Config>(
+    ticker: Ticker,
+    guard: Option<PriceGuard>,
+) -> Result<(), OracleError> {
+    match guard {
+        Some(guard) => PriceGuards::insert(&ticker, guard),
+        None => PriceGuards::remove(&ticker),
+    }
+    PendingPrices::remove(&ticker);
+    <Module<T>>::deposit_event(Event::SetPriceGuard(ticker, guard));
     Ok(())
 }
 
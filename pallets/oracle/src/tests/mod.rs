@@ -1,4 +1,9 @@
-use crate::{error::OracleError, ticker::Ticker, *};
+use crate::{
+    error::OracleError,
+    ticker::Ticker,
+    types::{AnchorConfig, PriceGuard},
+    *,
+};
 
 use sp_core::offchain::testing;
 
@@ -113,6 +118,207 @@ fn test_post_price_stale_price() {
     });
 }
 
+#[test]
+fn test_post_price_deviation_pending() {
+    // an eth price message
+    let test_payload = hex::decode("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000000000000000000000000000000000002baa48a00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034554480000000000000000000000000000000000000000000000000000000000").unwrap();
+    let test_signature = hex::decode("41a3f89a526dee766049f3699e9e975bfbabda4db677c9f5c41fbcc0730fccb84d08b2208c4ffae0b87bb162e2791cc305ee4e9a1d936f9e6154356154e9a8e9000000000000000000000000000000000000000000000000000000000000001c").unwrap();
+    new_test_ext().execute_with(|| {
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(1609340760000);
+        initialize_storage(); // sets up ETH
+        let guard = PriceGuard {
+            max_age: 0,
+            max_deviation_bps: 1000, // 10%
+        };
+        assert_ok!(OracleModule::set_price_guard(
+            Origin::root(),
+            ETH_TICKER,
+            Some(guard)
+        ));
+        Prices::insert(ETH_TICKER, 500000000);
+        PriceTimes::insert(ETH_TICKER, 1);
+
+        // a price ~46% higher is held until confirmed
+        assert_ok!(OracleModule::post_price(
+            Origin::none(),
+            test_payload.clone(),
+            test_signature.clone()
+        ));
+        assert_eq!(OracleModule::price(ETH_TICKER), Some(500000000));
+        let (pending, pending_timestamp, reporter) =
+            OracleModule::pending_price(ETH_TICKER).unwrap();
+        assert_eq!((pending, pending_timestamp), (732580000, 1609340760000));
+        assert_eq!(PriceReporters::get().contains(reporter), true);
+
+        // replaying the same report does not confirm it
+        assert_ok!(OracleModule::post_price(
+            Origin::none(),
+            test_payload,
+            test_signature
+        ));
+        assert_eq!(OracleModule::price(ETH_TICKER), Some(500000000));
+
+        // nor does a later report close to it from the same reporter
+        assert_eq!(
+            oracle::is_price_confirmed::<Test>(ETH_TICKER, 740000000, 1609340760001, reporter),
+            false
+        );
+
+        // a later report close to the pending price from another reporter does
+        let other = [0x11; 20];
+        assert_eq!(
+            oracle::is_price_confirmed::<Test>(ETH_TICKER, 740000000, 1609340760001, other),
+            true
+        );
+        assert_eq!(
+            oracle::is_price_confirmed::<Test>(ETH_TICKER, 900000000, 1609340760001, other),
+            false
+        );
+        assert_eq!(
+            oracle::is_price_confirmed::<Test>(ETH_TICKER, 520000000, 1609340760001, other),
+            true
+        );
+    });
+}
+
+#[test]
+fn test_check_price_fresh() {
+    new_test_ext().execute_with(|| {
+        let guard = PriceGuard {
+            max_age: 1000,
+            max_deviation_bps: 0,
+        };
+        assert_ok!(check_price_fresh::<Test>(ETH_TICKER));
+        assert_err!(
+            OracleModule::set_price_guard(Origin::none(), ETH_TICKER, Some(guard)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(OracleModule::set_price_guard(
+            Origin::root(),
+            ETH_TICKER,
+            Some(guard)
+        ));
+
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(10_500);
+        assert_eq!(
+            check_price_fresh::<Test>(ETH_TICKER),
+            Err(OracleError::StalePrice)
+        );
+        PriceTimes::insert(ETH_TICKER, 10_000);
+        assert_ok!(check_price_fresh::<Test>(ETH_TICKER));
+
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(11_001);
+        assert_eq!(
+            check_price_fresh::<Test>(ETH_TICKER),
+            Err(OracleError::StalePrice)
+        );
+        assert_ok!(check_price_fresh::<Test>(ticker::USD_TICKER));
+
+        assert_ok!(OracleModule::set_price_guard(
+            Origin::root(),
+            ETH_TICKER,
+            None
+        ));
+        assert_ok!(check_price_fresh::<Test>(ETH_TICKER));
+    });
+}
+
+#[test]
+fn test_post_price_outside_anchor_band() {
+    // an eth price message
+    let test_payload = hex::decode("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000000000000000000000000000000000002baa48a00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034554480000000000000000000000000000000000000000000000000000000000").unwrap();
+    let test_signature = hex::decode("41a3f89a526dee766049f3699e9e975bfbabda4db677c9f5c41fbcc0730fccb84d08b2208c4ffae0b87bb162e2791cc305ee4e9a1d936f9e6154356154e9a8e9000000000000000000000000000000000000000000000000000000000000001c").unwrap();
+    new_test_ext().execute_with(|| {
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(1609340760000);
+        initialize_storage(); // sets up ETH
+        let config = AnchorConfig {
+            pool: [0x8a; 20],
+            base_is_token0: false,
+            base_decimals: 18,
+            quote_decimals: 6,
+            twap_period: 1800,
+            band_bps: 1000, // 10%
+        };
+        assert_err!(
+            OracleModule::set_anchor_config(Origin::none(), ETH_TICKER, Some(config)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(OracleModule::set_anchor_config(
+            Origin::root(),
+            ETH_TICKER,
+            Some(config)
+        ));
+        assert_ok!(anchor::post_anchor_price::<Test>(
+            ETH_TICKER,
+            600000000,
+            1609340700000
+        ));
+
+        // a price ~22% above the anchor is rejected
+        assert_ok!(OracleModule::post_price(
+            Origin::none(),
+            test_payload.clone(),
+            test_signature.clone()
+        ));
+        assert_eq!(OracleModule::price(ETH_TICKER), None);
+
+        // but accepted once the anchor catches up
+        assert_ok!(anchor::post_anchor_price::<Test>(
+            ETH_TICKER,
+            700000000,
+            1609340760000
+        ));
+        assert_ok!(OracleModule::post_price(
+            Origin::none(),
+            test_payload,
+            test_signature
+        ));
+        assert_eq!(OracleModule::price(ETH_TICKER), Some(732580000));
+    });
+}
+
+#[test]
+fn test_anchor_price_fallback() {
+    new_test_ext().execute_with(|| {
+        let guard = PriceGuard {
+            max_age: 1000,
+            max_deviation_bps: 0,
+        };
+        AnchorPrices::insert(ETH_TICKER, (2000000000, 10_000));
+        assert_eq!(OracleModule::get_price(ETH_TICKER), Ok(2000000000));
+
+        Prices::insert(ETH_TICKER, 2100000000);
+        PriceTimes::insert(ETH_TICKER, 9_000);
+        assert_eq!(OracleModule::get_price(ETH_TICKER), Ok(2100000000));
+
+        // a stale reported price falls back to a fresh anchor price
+        assert_ok!(OracleModule::set_price_guard(
+            Origin::root(),
+            ETH_TICKER,
+            Some(guard)
+        ));
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(10_500);
+        assert_eq!(OracleModule::get_price(ETH_TICKER), Ok(2000000000));
+        assert_ok!(check_price_fresh::<Test>(ETH_TICKER));
+
+        // unless both are stale
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(11_001);
+        assert_eq!(OracleModule::get_price(ETH_TICKER), Ok(2100000000));
+        assert_eq!(
+            check_price_fresh::<Test>(ETH_TICKER),
+            Err(OracleError::StalePrice)
+        );
+
+        // and a stale anchor price is never used on its own
+        Prices::remove(ETH_TICKER);
+        PriceTimes::remove(ETH_TICKER);
+        assert_eq!(
+            OracleModule::get_price(ETH_TICKER),
+            Err(OracleError::NoPrice)
+        );
+    });
+}
+
 #[test]
 fn offchain_worker_test() {
     use frame_support::traits::OffchainWorker;
@@ -40,3 +40,7 @@ impl From<Ticker> for String {
 
 pub const USD_TICKER: Ticker = Ticker::new("USD");
 pub const CASH_TICKER: Ticker = Ticker::new("CASH");
+
+/// The market price of wrapped CASH on external markets, reported like any other price.
+/// CASH itself is always valued at one dollar by the protocol, this is only used to monitor the peg.
+pub const WCASH_TICKER: Ticker = Ticker::new("WCASH");
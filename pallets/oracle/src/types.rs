@@ -41,6 +41,70 @@ impl Price {
     }
 }
 
+/// Type for the guards against stale or sharply moving prices for a ticker.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, Types)]
+pub struct PriceGuard {
+    /// The maximum age of a price, in milliseconds, before it is considered stale (zero to disable).
+    pub max_age: Timestamp,
+    /// The maximum change of a single update, in basis points, before it must be confirmed (zero to disable).
+    pub max_deviation_bps: u32,
+}
+
+impl PriceGuard {
+    /// Whether a price is within the maximum deviation from a reference price.
+    pub fn within_deviation(&self, reference: AssetPrice, price: AssetPrice) -> bool {
+        within_bps(reference, price, self.max_deviation_bps)
+    }
+}
+
+/// Type for the address of a Uniswap V3 pool.
+#[type_alias]
+pub type PoolAddress = [u8; 20];
+
+/// Type for the Uniswap V3 pool used to anchor the reported prices for a ticker.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct AnchorConfig {
+    /// The pool pairing the asset with a USD stablecoin.
+    pub pool: PoolAddress,
+    /// Whether the asset is token0 of the pool, otherwise the stablecoin is.
+    pub base_is_token0: bool,
+    /// The decimals of the asset.
+    pub base_decimals: u8,
+    /// The decimals of the stablecoin.
+    pub quote_decimals: u8,
+    /// The period over which the pool price is averaged, in seconds.
+    pub twap_period: u32,
+    /// The maximum distance of a reported price from the anchor price, in basis points (zero to disable).
+    pub band_bps: u32,
+}
+
+impl AnchorConfig {
+    /// Whether a price falls within the band around an anchor price.
+    pub fn within_band(&self, anchor: AssetPrice, price: AssetPrice) -> bool {
+        within_bps(anchor, price, self.band_bps)
+    }
+}
+
+/// Type for the anchor prices observed by a validator at a given time.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct AnchorReport {
+    pub timestamp: Timestamp,
+    pub prices: Vec<(Ticker, AssetPrice)>,
+}
+
+/// Whether a price is within the given number of basis points of a reference price (zero to disable).
+pub fn within_bps(reference: AssetPrice, price: AssetPrice, bps: u32) -> bool {
+    if bps == 0 || reference == 0 {
+        return true;
+    }
+    let diff = if price > reference {
+        price - reference
+    } else {
+        reference - price
+    };
+    diff.saturating_mul(10_000) <= reference.saturating_mul(bps as u128)
+}
+
 /// Type for a set of open price feed reporters.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, Types)]
 pub struct ReporterSet(pub Vec<Reporter>);
@@ -1,10 +1,14 @@
-use crate::{error::OracleError, oracle, Call, Config};
+use crate::{error::OracleError, oracle, Call, Config, PriceReporters, PriceTimes};
 use codec::{Decode, Encode};
+use frame_support::storage::{StorageMap, StorageValue};
 use our_std::{log, RuntimeDebug};
 use sp_runtime::transaction_validity::{TransactionSource, TransactionValidity, ValidTransaction};
 
 const MAX_EXTERNAL_PAIRS: usize = 30;
 const UNSIGNED_TXS_PRIORITY: u64 = 100;
+/// Most a reporter's price is raised above the standard priority, by one for each second it is newer than the current price.
+/// Kept below the priority of quorum completing worker transactions, which must not be crowded out by prices.
+const MAX_PRICE_PRIORITY_BUMP: u64 = 99;
 const UNSIGNED_TXS_LONGEVITY: u64 = 32;
 
 #[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug, Clone, Copy)]
@@ -32,28 +36,40 @@ pub fn validate_unsigned<T: Config>(
 ) -> Result<TransactionValidity, ValidationError> {
     match call {
         Call::post_price(payload, signature) => {
-            if oracle::check_signature::<T>(&payload, &signature) == Ok(true) {
-                match source {
-                    TransactionSource::Local | TransactionSource::InBlock => {
+            let reporter = oracle::recover_reporter::<T>(&payload, &signature)
+                .map_err(|_| ValidationError::InvalidPriceSignature)?;
+            if !PriceReporters::get().contains(reporter) {
+                return Err(ValidationError::InvalidPriceSignature);
+            }
+            match source {
+                TransactionSource::Local | TransactionSource::InBlock => {
+                    Ok(ValidTransaction::with_tag_prefix("Gateway::post_price")
+                        .priority(UNSIGNED_TXS_PRIORITY)
+                        .longevity(UNSIGNED_TXS_LONGEVITY)
+                        .and_provides(signature)
+                        .propagate(false)
+                        .build())
+                }
+                // prices submitted directly by reporters are authorized by their signature alone,
+                //  each reporter may only have one price per ticker in the pool at a time,
+                //  and a newer price outranks so replaces an older one still in the pool
+                _ => match oracle::get_and_check_parsed_price::<T>(payload) {
+                    Ok((parsed, ticker)) => {
+                        let newer_secs = parsed
+                            .timestamp
+                            .saturating_sub(PriceTimes::get(&ticker).unwrap_or(0))
+                            / 1000;
                         Ok(ValidTransaction::with_tag_prefix("Gateway::post_price")
-                            .priority(UNSIGNED_TXS_PRIORITY)
+                            .priority(
+                                UNSIGNED_TXS_PRIORITY + newer_secs.min(MAX_PRICE_PRIORITY_BUMP),
+                            )
                             .longevity(UNSIGNED_TXS_LONGEVITY)
-                            .and_provides(signature)
-                            .propagate(false)
+                            .and_provides((reporter, ticker))
+                            .propagate(true)
                             .build())
                     }
-                    _ => match oracle::get_and_check_parsed_price::<T>(payload) {
-                        Ok(_) => Ok(ValidTransaction::with_tag_prefix("Gateway::post_price")
-                            .priority(UNSIGNED_TXS_PRIORITY)
-                            .longevity(UNSIGNED_TXS_LONGEVITY)
-                            .and_provides(signature)
-                            .propagate(true)
-                            .build()),
-                        Err(err) => Err(ValidationError::InvalidPrice(err)),
-                    },
-                }
-            } else {
-                Err(ValidationError::InvalidPriceSignature)
+                    Err(err) => Err(ValidationError::InvalidPrice(err)),
+                },
             }
         }
         Call::post_prices(pairs) => {
@@ -159,15 +175,60 @@ mod tests {
                     &Call::post_price::<Test>(msg.to_vec(), sig.to_vec()),
                 ),
                 Ok(ValidTransaction::with_tag_prefix("Gateway::post_price")
-                    .priority(UNSIGNED_TXS_PRIORITY)
+                    .priority(UNSIGNED_TXS_PRIORITY + MAX_PRICE_PRIORITY_BUMP)
                     .longevity(UNSIGNED_TXS_LONGEVITY)
-                    .and_provides(sig.to_vec())
+                    .and_provides((PriceReporters::get().0[0], Ticker::new("BTC")))
                     .propagate(true)
                     .build())
             );
         });
     }
 
+    #[test]
+    fn test_post_price_newer_remote() {
+        new_test_ext().execute_with(|| {
+            PriceReporters::put(ReporterSet(vec![[133, 97, 91, 7, 102, 21, 49, 124, 128, 241, 76, 186, 214, 80, 30, 236, 3, 28, 213, 28]]));
+
+            let msg = hex_literal::hex!("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000688e4cda00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034254430000000000000000000000000000000000000000000000000000000000");
+            let sig = hex_literal::hex!("69538bfa1a2097ea206780654d7baac3a17ee57547ee3eeb5d8bcb58a2fcdf401ff8834f4a003193f24224437881276fe76c8e1c0a361081de854457d41d0690000000000000000000000000000000000000000000000000000000000000001c");
+            // the price is 5 seconds newer than the current one
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1609340760000);
+            PriceTimes::insert(Ticker::new("BTC"), 1609340755000);
+
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::post_price::<Test>(msg.to_vec(), sig.to_vec()),
+                ),
+                Ok(ValidTransaction::with_tag_prefix("Gateway::post_price")
+                    .priority(UNSIGNED_TXS_PRIORITY + 5)
+                    .longevity(UNSIGNED_TXS_LONGEVITY)
+                    .and_provides((PriceReporters::get().0[0], Ticker::new("BTC")))
+                    .propagate(true)
+                    .build())
+            );
+        });
+    }
+
+    #[test]
+    fn test_post_price_unknown_reporter_remote() {
+        new_test_ext().execute_with(|| {
+            PriceReporters::put(ReporterSet(vec![[0; 20]]));
+
+            let msg = hex_literal::hex!("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000688e4cda00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034254430000000000000000000000000000000000000000000000000000000000");
+            let sig = hex_literal::hex!("69538bfa1a2097ea206780654d7baac3a17ee57547ee3eeb5d8bcb58a2fcdf401ff8834f4a003193f24224437881276fe76c8e1c0a361081de854457d41d0690000000000000000000000000000000000000000000000000000000000000001c");
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(500);
+
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::post_price::<Test>(msg.to_vec(), sig.to_vec()),
+                ),
+                Err(ValidationError::InvalidPriceSignature)
+            );
+        });
+    }
+
     #[test]
     fn test_post_price_valid_local() {
         new_test_ext().execute_with(|| {
@@ -2,6 +2,8 @@
 extern crate lazy_static;
 
 use gateway_crypto::CryptoError;
+#[cfg(feature = "std")]
+use prometheus_endpoint::{register, CounterVec, GaugeVec, Opts, PrometheusError, Registry, U64};
 use std::collections::HashMap;
 use std::iter::FromIterator;
 use std::{str, sync::Mutex};
@@ -13,6 +15,43 @@ pub struct ValidatorConfig {
 
 pub type PriceFeedData = (Vec<(Vec<u8>, Vec<u8>)>, u64);
 
+/// Number of underlying chain events fetched by the worker.
+pub const EVENTS_FETCHED: &str = "gateway_ocw_events_fetched_total";
+/// Number of extrinsics the worker failed to submit.
+pub const SUBMISSION_FAILURES: &str = "gateway_ocw_submission_failures_total";
+/// Time taken by the last RPC request to an underlying chain, in milliseconds.
+pub const RPC_LATENCY: &str = "gateway_ocw_rpc_latency_ms";
+/// Number of notices signed by the worker.
+pub const NOTICES_SIGNED: &str = "gateway_ocw_notices_signed_total";
+/// Time the oldest pending block has been waiting for a quorum, in milliseconds.
+pub const QUORUM_WAIT: &str = "gateway_ocw_quorum_wait_ms";
+
+const COUNTERS: [(&str, &str); 3] = [
+    (EVENTS_FETCHED, "Underlying chain events fetched"),
+    (SUBMISSION_FAILURES, "Extrinsics which failed to submit"),
+    (NOTICES_SIGNED, "Notices signed"),
+];
+
+const GAUGES: [(&str, &str); 2] = [
+    (
+        RPC_LATENCY,
+        "Latency of the last underlying chain RPC request in ms",
+    ),
+    (
+        QUORUM_WAIT,
+        "Time the oldest pending block has waited for quorum in ms",
+    ),
+];
+
+#[derive(Default)]
+struct OffchainMetrics {
+    values: HashMap<(String, String), u64>,
+    #[cfg(feature = "std")]
+    counters: HashMap<&'static str, CounterVec<U64>>,
+    #[cfg(feature = "std")]
+    gauges: HashMap<&'static str, GaugeVec<U64>>,
+}
+
 lazy_static! {
     static ref VALIDATOR_CONFIG_DEFAULTS: Mutex<ValidatorConfig> = Mutex::new(ValidatorConfig {
         map: HashMap::from_iter([
@@ -30,6 +69,62 @@ lazy_static! {
     });
     static ref VALIDATOR_CONFIG: Mutex<Option<ValidatorConfig>> = Mutex::new(None);
     static ref PRICE_FEED_DATA: Mutex<Option<PriceFeedData>> = Mutex::new(None);
+    static ref OFFCHAIN_METRICS: Mutex<OffchainMetrics> = Mutex::new(OffchainMetrics::default());
+}
+
+/// Register the offchain worker metrics with the node's prometheus registry.
+#[cfg(feature = "std")]
+pub fn register_offchain_metrics(registry: &Registry) -> Result<(), PrometheusError> {
+    let mut metrics = match OFFCHAIN_METRICS.lock() {
+        Ok(metrics) => metrics,
+        _ => return Ok(()), // XXX todo: log?
+    };
+    for (name, help) in COUNTERS.iter() {
+        let counter = CounterVec::new(Opts::new(*name, *help), &["chain"])?;
+        metrics.counters.insert(*name, register(counter, registry)?);
+    }
+    for (name, help) in GAUGES.iter() {
+        let gauge = GaugeVec::new(Opts::new(*name, *help), &["chain"])?;
+        metrics.gauges.insert(*name, register(gauge, registry)?);
+    }
+    Ok(())
+}
+
+/// Get the current value of an offchain worker metric for a chain.
+pub fn get_offchain_metric(name: &str, chain: &str) -> Option<u64> {
+    match OFFCHAIN_METRICS.lock() {
+        Ok(metrics) => metrics
+            .values
+            .get(&(name.to_string(), chain.to_string()))
+            .cloned(),
+        _ => None,
+    }
+}
+
+fn metrics_interface_increment_internal(name: &str, chain: &str, by: u64) {
+    if let Ok(mut metrics) = OFFCHAIN_METRICS.lock() {
+        let value = metrics
+            .values
+            .entry((name.to_string(), chain.to_string()))
+            .or_insert(0);
+        *value = value.saturating_add(by);
+        #[cfg(feature = "std")]
+        if let Some(counter) = metrics.counters.get(name) {
+            counter.with_label_values(&[chain]).inc_by(by);
+        }
+    }
+}
+
+fn metrics_interface_set_internal(name: &str, chain: &str, value: u64) {
+    if let Ok(mut metrics) = OFFCHAIN_METRICS.lock() {
+        metrics
+            .values
+            .insert((name.to_string(), chain.to_string()), value);
+        #[cfg(feature = "std")]
+        if let Some(gauge) = metrics.gauges.get(name) {
+            gauge.with_label_values(&[chain]).set(value);
+        }
+    }
 }
 
 pub fn initialize_validator_config(map: HashMap<String, String>) {
@@ -101,11 +196,13 @@ pub trait ValidatorConfigInterface {
     }
 
     /// Get the Ethereum node RPC URL
+    ///
+    /// This may be a comma-separated list of endpoints, which are failed over between in order.
     fn get_eth_rpc_url() -> Option<String> {
         validator_config_interface_get_internal(ETH_RPC_URL_ENV_VAR)
     }
 
-    /// Get the Matic node RPC URL
+    /// Get the Matic node RPC URL, or a comma-separated list of endpoints
     fn get_matic_rpc_url() -> Option<String> {
         validator_config_interface_get_internal(MATIC_RPC_URL_ENV_VAR)
     }
@@ -187,6 +284,22 @@ pub trait PriceFeedInterface {
     }
 }
 
+/// The MetricsInterface lets the offchain workers report on their progress to the node,
+/// which exports the metrics through its prometheus endpoint. Metrics are labelled by chain,
+/// so that validator operators can alert when ingestion from an underlying chain stalls.
+#[sp_runtime_interface::runtime_interface]
+pub trait MetricsInterface {
+    /// Add to a counter for an underlying chain
+    fn increment(name: &str, chain: &str, by: u64) {
+        metrics_interface_increment_internal(name, chain, by)
+    }
+
+    /// Set a gauge for an underlying chain
+    fn set(name: &str, chain: &str, value: u64) {
+        metrics_interface_set_internal(name, chain, value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,4 +324,27 @@ mod tests {
         let actual_nonsense = validator_config_interface_get_internal("nonsense");
         assert!(actual_nonsense.is_none());
     }
+
+    #[test]
+    fn test_metrics() {
+        let registry = Registry::new();
+        register_offchain_metrics(&registry).unwrap();
+
+        metrics_interface_increment_internal(EVENTS_FETCHED, "eth", 3);
+        metrics_interface_increment_internal(EVENTS_FETCHED, "eth", 2);
+        metrics_interface_set_internal(RPC_LATENCY, "matic", 150);
+        metrics_interface_set_internal(RPC_LATENCY, "matic", 120);
+
+        assert_eq!(get_offchain_metric(EVENTS_FETCHED, "eth"), Some(5));
+        assert_eq!(get_offchain_metric(EVENTS_FETCHED, "matic"), None);
+        assert_eq!(get_offchain_metric(RPC_LATENCY, "matic"), Some(120));
+
+        let exported: Vec<String> = registry
+            .gather()
+            .iter()
+            .map(|family| family.get_name().to_string())
+            .collect();
+        assert!(exported.contains(&EVENTS_FETCHED.to_string()));
+        assert!(exported.contains(&RPC_LATENCY.to_string()));
+    }
 }
@@ -47,13 +47,18 @@ pub use pallet_timestamp::Call as TimestampCall;
 
 use our_std::warn;
 use pallet_cash::{
-    chains::{ChainAccount, ChainAsset},
+    chains::{
+        ChainAccount, ChainAccountSignature, ChainAsset, ChainBlockNumber, ChainHash, ChainId,
+    },
     core::BTreeMap,
+    factor::Factor,
     portfolio::Portfolio,
     rates::APR,
     reason::Reason,
     types::{
-        AssetAmount, AssetBalance, AssetInfo, Balance, CashIndex, CashPrincipal, ValidatorKeys,
+        AccountHistoryEntry, AssetAmount, AssetBalance, AssetInfo, Balance, CashIndex,
+        CashOrChainAsset, CashPrincipal, EncodedNotice, NoticeId, ProtocolState, Timestamp,
+        ValidatorKeys, ValidatorMetadata,
     },
 };
 use pallet_oracle::{ticker::Ticker, types::AssetPrice};
@@ -118,10 +123,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("gateway"),
     impl_name: create_runtime_str!("gateway"),
     authoring_version: 1,
-    spec_version: 16,
+    spec_version: 17,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targetting.
@@ -291,6 +296,7 @@ impl pallet_cash::Config for Runtime {
     type AccountStore = System;
     type SessionInterface = Self;
     type WeightInfo = pallet_cash::weights::SubstrateWeight<Runtime>;
+    type MaxValidators = pallet_cash::params::MaxValidators;
 }
 
 // ---------------------- Recipe Pallet Configurations ----------------------
@@ -323,6 +329,7 @@ where
             frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
             frame_system::CheckNonce::<Runtime>::from(index),
             frame_system::CheckWeight::<Runtime>::new(),
+            pallet_cash::internal::fees::ChargeCashFee::<Runtime>::new(),
         );
 
         #[cfg_attr(not(feature = "std"), allow(unused_variables))]
@@ -396,6 +403,7 @@ pub type SignedExtra = (
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
+    pallet_cash::internal::fees::ChargeCashFee<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -531,6 +539,10 @@ impl_runtime_apis! {
             Cash::get_account_balance(account, asset)
         }
 
+        fn get_balance(account: ChainAccount, asset: CashOrChainAsset) -> Result<AssetBalance, Reason> {
+            Cash::get_balance(account, asset)
+        }
+
         fn get_cash_yield() -> Result<APR, Reason> {
             Cash::get_cash_yield()
         }
@@ -547,6 +559,10 @@ impl_runtime_apis! {
             Cash::get_liquidity(account)
         }
 
+        fn get_hypothetical_liquidity(account: ChainAccount, modify_asset: ChainAsset, redeem_amount: AssetAmount, borrow_amount: AssetAmount) -> Result<(AssetBalance, Option<Factor>), Reason> {
+            Cash::get_hypothetical_liquidity(account, modify_asset, redeem_amount, borrow_amount)
+        }
+
         fn get_market_totals(asset: ChainAsset) -> Result<(AssetAmount, AssetAmount), Reason> {
             Cash::get_market_totals(asset)
         }
@@ -586,6 +602,50 @@ impl_runtime_apis! {
         fn get_validator_info() -> Result<(Vec<ValidatorKeys>, Vec<(ChainAccount, String)>), Reason> {
             Cash::get_validator_info()
         }
+
+        fn get_last_processed_blocks() -> Result<Vec<(ChainId, ChainBlockNumber, u32, u32)>, Reason> {
+            Cash::get_last_processed_blocks()
+        }
+
+        fn get_notice_status(notice_hash: ChainHash) -> Result<(ChainId, NoticeId, EncodedNotice, u32, u32, bool), Reason> {
+            Cash::get_notice_status(notice_hash)
+        }
+
+        fn get_pending_notices(chain_id: ChainId, limit: u32) -> Result<Vec<(NoticeId, ChainHash, EncodedNotice, Vec<Vec<u8>>)>, Reason> {
+            Cash::get_pending_notices(chain_id, limit)
+        }
+
+        fn get_execution_fee(chain_id: ChainId) -> Result<(AssetAmount, Timestamp), Reason> {
+            Cash::get_execution_fee(chain_id)
+        }
+
+        fn get_rate_history(asset: ChainAsset) -> Result<Vec<(Timestamp, APR, APR, CashIndex)>, Reason> {
+            Cash::get_rate_history(asset)
+        }
+
+        fn get_starport_liquidity(asset: ChainAsset, amount: AssetAmount) -> Result<(AssetAmount, AssetAmount), Reason> {
+            Cash::get_starport_liquidity(asset, amount)
+        }
+
+        fn get_validator_metadata() -> Result<Vec<(ValidatorKeys, Option<ValidatorMetadata>)>, Reason> {
+            Cash::get_validator_metadata()
+        }
+
+        fn get_notice_signers(notice_hash: ChainHash) -> Result<Vec<(ValidatorKeys, Option<ValidatorMetadata>, bool)>, Reason> {
+            Cash::get_notice_signers(notice_hash)
+        }
+
+        fn get_account_history(account: ChainAccount, from_block: u64, limit: u32) -> Result<Vec<AccountHistoryEntry>, Reason> {
+            Cash::get_account_history(account, from_block, limit)
+        }
+
+        fn dry_run_trx_request(request: Vec<u8>, signature: ChainAccountSignature) -> Result<Vec<(ChainAccount, CashOrChainAsset, AssetBalance)>, Reason> {
+            Cash::dry_run_trx_request(request, signature)
+        }
+
+        fn get_protocol_state() -> Result<ProtocolState, Reason> {
+            Cash::get_protocol_state()
+        }
     }
 
     #[cfg(feature = "runtime-benchmarks")]
@@ -622,4 +682,12 @@ impl_runtime_apis! {
             Ok(batches)
         }
     }
+
+    #[cfg(feature = "try-runtime")]
+    impl frame_try_runtime::TryRuntime<Block> for Runtime {
+        fn on_runtime_upgrade() -> Result<(Weight, Weight), sp_runtime::RuntimeString> {
+            let weight = Executive::try_runtime_upgrade()?;
+            Ok((weight, BlockWeights::get().max_block))
+        }
+    }
 }
@@ -42,6 +42,13 @@ pub enum TrxRequest {
     Extract(MaxAmount, Asset, Account),
     Transfer(MaxAmount, Asset, Account),
     Liquidate(MaxAmount, Asset, Asset, Account),
+    TransferPortfolio(Account),
+    AcceptPortfolio(Account),
+    SetGuardians(u32, Vec<Account>),
+    RecoverAccount(Account, Account),
+    CancelRecovery,
+    Convert(MaxAmount, Asset, Asset),
+    StartAuction(Account),
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -200,6 +207,88 @@ fn parse_liquidate<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>>
     }
 }
 
+fn parse_transfer_portfolio<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [account_token] => {
+            let account = parse_account(account_token)?;
+
+            Ok(TrxRequest::TransferPortfolio(account))
+        }
+        _ => Err(ParseError::InvalidArgs("TransferPortfolio", 1, args.len())),
+    }
+}
+
+fn parse_accept_portfolio<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [account_token] => {
+            let account = parse_account(account_token)?;
+
+            Ok(TrxRequest::AcceptPortfolio(account))
+        }
+        _ => Err(ParseError::InvalidArgs("AcceptPortfolio", 1, args.len())),
+    }
+}
+
+fn parse_set_guardians<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [threshold_token, guardian_tokens @ ..] => {
+            let threshold: u32 = parse_amount(threshold_token)?
+                .try_into()
+                .map_err(|_| ParseError::InvalidAmount)?;
+            let guardians = guardian_tokens
+                .iter()
+                .map(parse_account)
+                .collect::<Result<Vec<Account>, _>>()?;
+
+            Ok(TrxRequest::SetGuardians(threshold, guardians))
+        }
+        _ => Err(ParseError::InvalidArgs("SetGuardians", 1, args.len())),
+    }
+}
+
+fn parse_recover_account<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [account_token, new_account_token] => {
+            let account = parse_account(account_token)?;
+            let new_account = parse_account(new_account_token)?;
+
+            Ok(TrxRequest::RecoverAccount(account, new_account))
+        }
+        _ => Err(ParseError::InvalidArgs("RecoverAccount", 2, args.len())),
+    }
+}
+
+fn parse_cancel_recovery<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [] => Ok(TrxRequest::CancelRecovery),
+        _ => Err(ParseError::InvalidArgs("CancelRecovery", 0, args.len())),
+    }
+}
+
+fn parse_convert<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [amount_token, from_asset_token, to_asset_token] => {
+            let max_amount = parse_max_amount(amount_token)?;
+            let from_asset = parse_asset(from_asset_token)?;
+            let to_asset = parse_asset(to_asset_token)?;
+
+            Ok(TrxRequest::Convert(max_amount, from_asset, to_asset))
+        }
+        _ => Err(ParseError::InvalidArgs("Convert", 3, args.len())),
+    }
+}
+
+fn parse_start_auction<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [account_token] => {
+            let account = parse_account(account_token)?;
+
+            Ok(TrxRequest::StartAuction(account))
+        }
+        _ => Err(ParseError::InvalidArgs("StartAuction", 1, args.len())),
+    }
+}
+
 fn parse<'a>(tokens: Lexer<'a, Token<'a>>) -> Result<TrxRequest, ParseError<'a>> {
     // TODO: I don't love having to clone here at all
     tokens
@@ -225,6 +314,27 @@ fn parse<'a>(tokens: Lexer<'a, Token<'a>>) -> Result<TrxRequest, ParseError<'a>>
         [Token::LeftDelim, Token::Identifier("Liquidate"), args @ .., Token::RightDelim] => {
             parse_liquidate(args)
         }
+        [Token::LeftDelim, Token::Identifier("TransferPortfolio"), args @ .., Token::RightDelim] => {
+            parse_transfer_portfolio(args)
+        }
+        [Token::LeftDelim, Token::Identifier("AcceptPortfolio"), args @ .., Token::RightDelim] => {
+            parse_accept_portfolio(args)
+        }
+        [Token::LeftDelim, Token::Identifier("SetGuardians"), args @ .., Token::RightDelim] => {
+            parse_set_guardians(args)
+        }
+        [Token::LeftDelim, Token::Identifier("RecoverAccount"), args @ .., Token::RightDelim] => {
+            parse_recover_account(args)
+        }
+        [Token::LeftDelim, Token::Identifier("CancelRecovery"), args @ .., Token::RightDelim] => {
+            parse_cancel_recovery(args)
+        }
+        [Token::LeftDelim, Token::Identifier("Convert"), args @ .., Token::RightDelim] => {
+            parse_convert(args)
+        }
+        [Token::LeftDelim, Token::Identifier("StartAuction"), args @ .., Token::RightDelim] => {
+            parse_start_auction(args)
+        }
         [Token::LeftDelim, Token::Identifier(fun), .., Token::RightDelim] => {
             Err(ParseError::UnknownFunction(fun))
         }
@@ -339,6 +449,48 @@ mod tests {
             Asset::Eth(ETH),
             Account::Eth(ALAN)
         )),
+        parse_transfer_portfolio:
+        "(TransferPortfolio Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::TransferPortfolio(
+            Account::Eth(ALAN)
+        )),
+        parse_accept_portfolio:
+        "(AcceptPortfolio Gate:0x0202020202020202020202020202020202020202020202020202020202020202)" => Ok(TrxRequest::AcceptPortfolio(
+            Account::Gate(BERT)
+        )),
+        parse_fail_transfer_portfolio_args:
+        "(TransferPortfolio Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidArgs("TransferPortfolio", 1, 2)),
+        parse_set_guardians:
+        "(SetGuardians 1 Eth:0x0101010101010101010101010101010101010101 Gate:0x0202020202020202020202020202020202020202020202020202020202020202)" => Ok(TrxRequest::SetGuardians(
+            1,
+            vec![Account::Eth(ALAN), Account::Gate(BERT)]
+        )),
+        parse_set_guardians_none:
+        "(SetGuardians 0)" => Ok(TrxRequest::SetGuardians(0, vec![])),
+        parse_recover_account:
+        "(RecoverAccount Eth:0x0101010101010101010101010101010101010101 Gate:0x0202020202020202020202020202020202020202020202020202020202020202)" => Ok(TrxRequest::RecoverAccount(
+            Account::Eth(ALAN),
+            Account::Gate(BERT)
+        )),
+        parse_cancel_recovery:
+        "(CancelRecovery)" => Ok(TrxRequest::CancelRecovery),
+        parse_convert:
+        "(Convert 3 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Matic:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee)" => Ok(TrxRequest::Convert(
+            MaxAmount::Amount(3),
+            Asset::Eth(ETH),
+            Asset::Matic(ETH)
+        )),
+        parse_start_auction:
+        "(StartAuction Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::StartAuction(
+            Account::Eth(ALAN)
+        )),
+        parse_fail_start_auction_args:
+        "(StartAuction)" => Err(ParseError::InvalidArgs("StartAuction", 1, 0)),
+        parse_fail_convert_to_account:
+        "(Convert 3 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee)" => Err(ParseError::InvalidArgs("Convert", 3, 2)),
+        parse_fail_set_guardians_no_threshold:
+        "(SetGuardians)" => Err(ParseError::InvalidArgs("SetGuardians", 1, 0)),
+        parse_fail_set_guardians_threshold_too_large:
+        "(SetGuardians 4294967296 Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidAmount),
         // TODO: Should we prohibit non-Cash from being Maxable?
         parse_fail_no_zero_ex:
         "(Extract 3 Eth:xxeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidChainAccount(Chain::Eth)),